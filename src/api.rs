@@ -0,0 +1,403 @@
+//! REST control/status API
+//!
+//! Lets an operator inspect and steer the running bot without a restart:
+//!
+//! - `GET /status`: balance, open positions, daily P&L, whether trading is on
+//! - `GET /markets`: markets from the latest scan
+//! - `POST /pause`, `POST /resume`: flip the shared trading switch the
+//!   executor checks before every order
+//! - `POST /flatten`: pause trading, cancel open orders and close every
+//!   position through the executor
+//!
+//! Handlers work on shared handles to the live components, like the
+//! metrics endpoint. Only built with the `api` feature.
+
+use crate::client::mock::ClobClientTrait;
+use crate::config::Secret;
+use crate::executor::Executor;
+use crate::risk::RiskManager;
+use crate::storage::Database;
+use crate::types::{Market, Position};
+use axum::{
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Json, Response},
+    routing::{get, post},
+    Router,
+};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::sync::{Mutex, RwLock};
+
+/// Live components the API reads and controls
+pub struct ApiState<C> {
+    /// Balance and positions from its client; `/flatten` closes through it
+    pub executor: Arc<Executor<C>>,
+    /// Shared with `Executor::with_trading_switch`
+    pub trading_enabled: Arc<AtomicBool>,
+    /// Source of the daily P&L; omitted from `/status` when `None`
+    pub risk_manager: Option<Arc<Mutex<RiskManager>>>,
+    /// Markets from the latest scan, replaced by the main loop
+    pub candidates: Arc<RwLock<Vec<Market>>>,
+    /// Where closing trades from `/flatten` are saved, if anywhere
+    pub db: Option<Arc<Database>>,
+    /// Bearer token required on every request, if set
+    pub token: Option<Secret>,
+}
+
+impl<C: ClobClientTrait> ApiState<C> {
+    /// State with trading enabled and no scan results yet
+    pub fn new(executor: Arc<Executor<C>>) -> Self {
+        Self {
+            executor,
+            trading_enabled: Arc::new(AtomicBool::new(true)),
+            risk_manager: None,
+            candidates: Arc::new(RwLock::new(Vec::new())),
+            db: None,
+            token: None,
+        }
+    }
+}
+
+/// Response to `GET /status`
+#[derive(Debug, Serialize)]
+pub struct StatusResponse {
+    pub trading_enabled: bool,
+    pub balance: Decimal,
+    pub open_positions: Vec<Position>,
+    pub daily_pnl: Option<Decimal>,
+}
+
+/// Response to `POST /pause` and `POST /resume`
+#[derive(Debug, Serialize)]
+pub struct TradingResponse {
+    pub trading_enabled: bool,
+}
+
+/// Response to `POST /flatten`
+#[derive(Debug, Default, Serialize)]
+pub struct FlattenReport {
+    pub cancelled_orders: usize,
+    /// Token ids of the positions closed
+    pub closed: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+/// Error body returned with a non-2xx status
+struct ApiError(StatusCode, String);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (self.0, Json(serde_json::json!({ "error": self.1 }))).into_response()
+    }
+}
+
+impl From<crate::error::BotError> for ApiError {
+    fn from(e: crate::error::BotError) -> Self {
+        Self(StatusCode::BAD_GATEWAY, e.to_string())
+    }
+}
+
+async fn status_handler<C: ClobClientTrait>(
+    State(state): State<Arc<ApiState<C>>>,
+) -> Result<Json<StatusResponse>, ApiError> {
+    let balance = state.executor.clob.get_balance().await?;
+    let open_positions = state
+        .executor
+        .clob
+        .get_positions()
+        .await?
+        .into_iter()
+        .filter(|p| p.size != Decimal::ZERO)
+        .collect();
+    let daily_pnl = match &state.risk_manager {
+        Some(risk) => Some(risk.lock().await.daily_pnl()),
+        None => None,
+    };
+    Ok(Json(StatusResponse {
+        trading_enabled: state.trading_enabled.load(Ordering::SeqCst),
+        balance,
+        open_positions,
+        daily_pnl,
+    }))
+}
+
+async fn markets_handler<C>(State(state): State<Arc<ApiState<C>>>) -> Json<Vec<Market>> {
+    Json(state.candidates.read().await.clone())
+}
+
+async fn pause_handler<C>(State(state): State<Arc<ApiState<C>>>) -> Json<TradingResponse> {
+    state.trading_enabled.store(false, Ordering::SeqCst);
+    tracing::warn!("Trading paused via control API");
+    Json(TradingResponse { trading_enabled: false })
+}
+
+async fn resume_handler<C>(State(state): State<Arc<ApiState<C>>>) -> Json<TradingResponse> {
+    state.trading_enabled.store(true, Ordering::SeqCst);
+    tracing::info!("Trading resumed via control API");
+    Json(TradingResponse { trading_enabled: true })
+}
+
+async fn flatten_handler<C: ClobClientTrait>(
+    State(state): State<Arc<ApiState<C>>>,
+) -> Result<Json<FlattenReport>, ApiError> {
+    tracing::warn!("Flattening all positions via control API");
+    Ok(Json(flatten(&state.executor, &state.trading_enabled, state.db.as_deref()).await?))
+}
+
+/// Pause trading, cancel open orders, then close each position
+///
+/// Trading stays paused afterwards so the next scan does not reopen what
+/// was just closed. Closes go through `Executor::close_position`, so its
+/// positions and P&L see them, and the trades are saved to `db` if given.
+/// Positions that fail to close are reported in `errors` and left open.
+pub async fn flatten<C: ClobClientTrait>(
+    executor: &Executor<C>,
+    trading_enabled: &AtomicBool,
+    db: Option<&Database>,
+) -> crate::error::Result<FlattenReport> {
+    trading_enabled.store(false, Ordering::SeqCst);
+    let mut report = FlattenReport::default();
+    match executor.clob.cancel_all().await {
+        Ok(n) => report.cancelled_orders = n,
+        Err(e) => report.errors.push(format!("cancel orders: {}", e)),
+    }
+
+    for position in executor.clob.get_positions().await? {
+        if position.size <= Decimal::ZERO {
+            continue;
+        }
+        let trade = match executor.close_position(&position).await {
+            Ok(Some(trade)) => trade,
+            Ok(None) => {
+                report.errors.push(format!("{}: close order was not filled", position.token_id));
+                continue;
+            }
+            Err(e) => {
+                report.errors.push(format!("{}: {}", position.token_id, e));
+                continue;
+            }
+        };
+        if let Some(db) = db {
+            if let Err(e) = db.save_trade(&trade).await {
+                tracing::warn!("Failed to save closing trade {}: {}", trade.id, e);
+            }
+        }
+        report.closed.push(position.token_id);
+    }
+    Ok(report)
+}
+
+/// Reject requests without the configured bearer token
+async fn require_token<C>(State(state): State<Arc<ApiState<C>>>, request: Request, next: Next) -> Response {
+    if let Some(token) = &state.token {
+        let authorized = request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .is_some_and(|v| v == token.expose());
+        if !authorized {
+            return ApiError(StatusCode::UNAUTHORIZED, "missing or invalid token".into()).into_response();
+        }
+    }
+    next.run(request).await
+}
+
+/// Router serving the control API
+pub fn create_router<C: ClobClientTrait + 'static>(state: Arc<ApiState<C>>) -> Router {
+    Router::new()
+        .route("/status", get(status_handler))
+        .route("/markets", get(markets_handler))
+        .route("/pause", post(pause_handler))
+        .route("/resume", post(resume_handler))
+        .route("/flatten", post(flatten_handler))
+        .route_layer(middleware::from_fn_with_state(state.clone(), require_token))
+        .with_state(state)
+}
+
+/// Start the API server
+pub async fn start_api_server<C: ClobClientTrait + 'static>(
+    state: Arc<ApiState<C>>,
+    addr: std::net::SocketAddr,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!("Control API listening on http://{}", listener.local_addr()?);
+    axum::serve(listener, create_router(state)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mock::MockClobClient;
+    use crate::config::RiskConfig;
+    use crate::types::Side;
+    use rust_decimal_macros::dec;
+
+    fn position(token_id: &str, size: Decimal) -> Position {
+        Position {
+            token_id: token_id.to_string(),
+            market_id: "m1".to_string(),
+            side: Side::Buy,
+            size,
+            avg_entry_price: dec!(0.50),
+            current_price: dec!(0.55),
+            unrealized_pnl: dec!(0.05) * size,
+        }
+    }
+
+    fn clob_with_position() -> MockClobClient {
+        let clob = MockClobClient::new().with_balance(dec!(750));
+        clob.state()
+            .write()
+            .unwrap()
+            .positions
+            .insert("yes".to_string(), position("yes", dec!(100)));
+        clob
+    }
+
+    fn state(clob: MockClobClient) -> ApiState<MockClobClient> {
+        ApiState::new(Arc::new(Executor::new(clob, RiskConfig::default())))
+    }
+
+    async fn serve(state: ApiState<MockClobClient>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = create_router(Arc::new(state));
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+        format!("http://{}", addr)
+    }
+
+    #[tokio::test]
+    async fn test_status_endpoint() {
+        let base = serve(state(clob_with_position())).await;
+
+        let resp = reqwest::get(format!("{}/status", base)).await.unwrap();
+        assert!(resp.status().is_success());
+        let body: serde_json::Value = resp.json().await.unwrap();
+
+        assert_eq!(body["trading_enabled"], true);
+        assert_eq!(body["balance"], "750");
+        assert_eq!(body["open_positions"].as_array().unwrap().len(), 1);
+        assert_eq!(body["open_positions"][0]["token_id"], "yes");
+        assert!(body["daily_pnl"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_resume_flip_flag() {
+        let state = state(MockClobClient::new());
+        let flag = state.trading_enabled.clone();
+        let base = serve(state).await;
+        let client = reqwest::Client::new();
+
+        let resp = client.post(format!("{}/pause", base)).send().await.unwrap();
+        assert!(resp.status().is_success());
+        assert!(!flag.load(Ordering::SeqCst));
+        let body: serde_json::Value = reqwest::get(format!("{}/status", base)).await.unwrap().json().await.unwrap();
+        assert_eq!(body["trading_enabled"], false);
+
+        client.post(format!("{}/resume", base)).send().await.unwrap();
+        assert!(flag.load(Ordering::SeqCst));
+
+        // Control endpoints do not accept GET
+        let resp = reqwest::get(format!("{}/pause", base)).await.unwrap();
+        assert_eq!(resp.status(), reqwest::StatusCode::METHOD_NOT_ALLOWED);
+        assert!(flag.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_executor_respects_pause() {
+        use crate::error::BotError;
+        use crate::types::Signal;
+
+        let state = state(MockClobClient::new());
+        let executor = Executor::new(MockClobClient::new(), RiskConfig::default())
+            .with_trading_switch(state.trading_enabled.clone());
+        let base = serve(state).await;
+        let signal = Signal {
+            market_id: "m1".to_string(),
+            token_id: "t1".to_string(),
+            side: Side::Buy,
+            model_probability: dec!(0.70),
+            market_probability: dec!(0.55),
+            edge: dec!(0.15),
+            net_edge: dec!(0.15),
+            confidence: dec!(0.80),
+            suggested_size: dec!(0.05),
+            timestamp: chrono::Utc::now(),
+            max_staleness: None,
+        };
+
+        reqwest::Client::new().post(format!("{}/pause", base)).send().await.unwrap();
+        let err = executor.execute(&signal, dec!(1000)).await.unwrap_err();
+        assert!(matches!(err.root(), BotError::RiskLimit(msg) if msg.contains("paused")), "{}", err);
+
+        reqwest::Client::new().post(format!("{}/resume", base)).send().await.unwrap();
+        assert!(executor.execute(&signal, dec!(1000)).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_token_required_when_configured() {
+        let mut state = state(MockClobClient::new());
+        state.token = Some(Secret::new("s3cret"));
+        let flag = state.trading_enabled.clone();
+        let base = serve(state).await;
+        let client = reqwest::Client::new();
+
+        let resp = client.post(format!("{}/pause", base)).send().await.unwrap();
+        assert_eq!(resp.status(), reqwest::StatusCode::UNAUTHORIZED);
+        assert!(flag.load(Ordering::SeqCst));
+
+        let resp = client
+            .post(format!("{}/pause", base))
+            .bearer_auth("s3cret")
+            .send()
+            .await
+            .unwrap();
+        assert!(resp.status().is_success());
+        assert!(!flag.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_flatten_closes_positions() {
+        let clob = clob_with_position();
+        clob.state()
+            .write()
+            .unwrap()
+            .positions
+            .insert("no".to_string(), position("no", Decimal::ZERO));
+
+        let executor = Executor::new(clob, RiskConfig::default());
+        let trading_enabled = AtomicBool::new(true);
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::connect(dir.path().join("bot.db")).await.unwrap();
+
+        let report = flatten(&executor, &trading_enabled, Some(&db)).await.unwrap();
+
+        assert_eq!(report.closed, vec!["yes".to_string()]);
+        assert!(report.errors.is_empty());
+        // Paused so the next scan does not reopen the position
+        assert!(!trading_enabled.load(Ordering::SeqCst));
+        // The executor booked the close and the trade was saved
+        assert_eq!(executor.get_positions().await.get("yes"), Some(&dec!(-100)));
+        let saved = db.get_recent_trades(10).await.unwrap();
+        assert_eq!(saved.len(), 1);
+        assert_eq!((saved[0].token_id.as_str(), saved[0].side), ("yes", Side::Sell));
+        assert!(saved[0].client_order_id.as_deref().is_some_and(|id| id.starts_with("close-")));
+
+        let clob = &executor.clob;
+        let state = clob.state();
+        let state = state.read().unwrap();
+        let order = state.orders.last().unwrap();
+        assert_eq!(order.side, Side::Sell);
+        assert_eq!(order.size, dec!(100));
+        // Default mock book's best bid
+        assert_eq!(order.price, dec!(0.54));
+    }
+}
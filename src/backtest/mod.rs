@@ -60,6 +60,16 @@ impl Default for BacktestConfig {
     }
 }
 
+impl BacktestConfig {
+    /// Charge the same maker and taker rates as live trading
+    pub fn with_fee_model(mut self, fees: crate::config::FeeModel) -> Self {
+        self.maker_fee = fees.rate(true);
+        self.taker_fee = fees.rate(false);
+        self.commission_model = CommissionModel::Percentage;
+        self
+    }
+}
+
 /// Slippage model types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SlippageModel {
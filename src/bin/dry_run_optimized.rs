@@ -13,7 +13,7 @@
 //! - trailing_stop: 10%
 //! - max_trades_per_hour: 3
 
-use polymarket_bot::testing::optimized_simulator::EnhancedDryRunSimulator;
+use polymarket_bot::testing::optimized_simulator::{compare, ComparisonData, EnhancedDryRunSimulator, SimulatorConfig};
 use polymarket_bot::config::{StrategyConfig, RiskConfig};
 use polymarket_bot::types::{Market, Outcome};
 use rust_decimal::Decimal;
@@ -46,6 +46,10 @@ fn generate_test_markets() -> Vec<Market> {
             liquidity: dec!(100000),
             active: true,
             closed: false,
+            category: None,
+            tags: Vec::new(),
+            tick_size: None,
+            min_size: None,
         },
         Market {
             id: "eth-5k".to_string(),
@@ -68,6 +72,10 @@ fn generate_test_markets() -> Vec<Market> {
             liquidity: dec!(80000),
             active: true,
             closed: false,
+            category: None,
+            tags: Vec::new(),
+            tick_size: None,
+            min_size: None,
         },
         Market {
             id: "fed-rate".to_string(),
@@ -90,6 +98,10 @@ fn generate_test_markets() -> Vec<Market> {
             liquidity: dec!(50000),
             active: true,
             closed: false,
+            category: None,
+            tags: Vec::new(),
+            tick_size: None,
+            min_size: None,
         },
         Market {
             id: "trump-approval".to_string(),
@@ -112,6 +124,10 @@ fn generate_test_markets() -> Vec<Market> {
             liquidity: dec!(120000),
             active: true,
             closed: false,
+            category: None,
+            tags: Vec::new(),
+            tick_size: None,
+            min_size: None,
         },
         Market {
             id: "sp500-6k".to_string(),
@@ -134,6 +150,10 @@ fn generate_test_markets() -> Vec<Market> {
             liquidity: dec!(45000),
             active: true,
             closed: false,
+            category: None,
+            tags: Vec::new(),
+            tick_size: None,
+            min_size: None,
         },
         // Additional markets for more diverse testing
         Market {
@@ -157,6 +177,10 @@ fn generate_test_markets() -> Vec<Market> {
             liquidity: dec!(35000),
             active: true,
             closed: false,
+            category: None,
+            tags: Vec::new(),
+            tick_size: None,
+            min_size: None,
         },
         Market {
             id: "sol-500".to_string(),
@@ -179,6 +203,10 @@ fn generate_test_markets() -> Vec<Market> {
             liquidity: dec!(55000),
             active: true,
             closed: false,
+            category: None,
+            tags: Vec::new(),
+            tick_size: None,
+            min_size: None,
         },
     ]
 }
@@ -190,9 +218,24 @@ fn optimized_strategy_config() -> StrategyConfig {
         min_confidence: dec!(0.70),   // 70% (was 50%)
         kelly_fraction: dec!(0.15),   // 15% (was 25-35%)
         scan_interval_secs: 180,
+        scan_concurrency: 4,
         model_update_interval_secs: 900,
         compound_enabled: true,
         compound_sqrt_scaling: true,
+        fee_rate: Decimal::ZERO,
+        slippage_estimate: Decimal::ZERO,
+        max_price_deviation: dec!(0.05),
+        min_liquidity: dec!(10000),
+        crypto_min_liquidity: dec!(1000),
+        min_minutes_to_resolution: 0,
+        max_minutes_to_resolution: None,
+        size_decay_minutes: 0,
+        outcome_matching: Default::default(),
+        allowlist: Vec::new(),
+        denylist: Vec::new(),
+        categories: Vec::new(),
+        anti_chase: Default::default(),
+        abstain_below_confidence: None,
     }
 }
 
@@ -204,6 +247,7 @@ fn optimized_risk_config() -> RiskConfig {
         max_daily_loss_pct: dec!(0.05),   // 5% (was 10%)
         min_balance_reserve: dec!(100),
         max_open_positions: 5,            // Reduced from default
+        ..RiskConfig::default()
     }
 }
 
@@ -214,9 +258,24 @@ fn original_strategy_config() -> StrategyConfig {
         min_confidence: dec!(0.50),   // 50%
         kelly_fraction: dec!(0.25),   // 25%
         scan_interval_secs: 180,
+        scan_concurrency: 4,
         model_update_interval_secs: 900,
         compound_enabled: true,
         compound_sqrt_scaling: true,
+        fee_rate: Decimal::ZERO,
+        slippage_estimate: Decimal::ZERO,
+        max_price_deviation: dec!(0.05),
+        min_liquidity: dec!(10000),
+        crypto_min_liquidity: dec!(1000),
+        min_minutes_to_resolution: 0,
+        max_minutes_to_resolution: None,
+        size_decay_minutes: 0,
+        outcome_matching: Default::default(),
+        allowlist: Vec::new(),
+        denylist: Vec::new(),
+        categories: Vec::new(),
+        anti_chase: Default::default(),
+        abstain_below_confidence: None,
     }
 }
 
@@ -227,6 +286,7 @@ fn original_risk_config() -> RiskConfig {
         max_daily_loss_pct: dec!(0.10),   // 10%
         min_balance_reserve: dec!(100),
         max_open_positions: 10,
+        ..RiskConfig::default()
     }
 }
 
@@ -364,6 +424,40 @@ async fn main() -> anyhow::Result<()> {
     let opt_risk_adj = if opt_dd > Decimal::ZERO { opt_pnl_pct / opt_dd } else { dec!(0) };
     println!("   Return/Drawdown Ratio: Original={:.3}, Optimized={:.3}", orig_risk_adj, opt_risk_adj);
     
+    // Is the per-trade difference more than noise?
+    let ab_report = compare(
+        &SimulatorConfig {
+            stop_loss: Some(dec!(0.15)),
+            take_profit: Some(dec!(0.25)),
+            trailing_stop: Some(dec!(0.10)),
+            max_trades_per_hour: Some(3),
+            ..SimulatorConfig::new(optimized_strategy_config(), optimized_risk_config())
+        },
+        &SimulatorConfig {
+            stop_loss: Some(dec!(0.20)),
+            take_profit: Some(dec!(0.30)),
+            trailing_stop: Some(dec!(0.15)),
+            ..SimulatorConfig::new(original_strategy_config(), original_risk_config())
+        },
+        &ComparisonData {
+            markets: markets.clone(),
+            steps: 100,
+            initial_balance: dec!(1000),
+            seed: 42,
+        },
+    )
+    .await?;
+    let sig = &ab_report.significance;
+    println!("\n🔬 SIGNIFICANCE (optimized - original, per trade):");
+    println!(
+        "   Mean P&L diff: ${:.4} | 95% CI: ${:.4} to ${:.4} | p = {:.3} ({})",
+        sig.mean_difference,
+        sig.ci_low,
+        sig.ci_high,
+        sig.p_value,
+        if sig.significant { "significant" } else { "not significant" }
+    );
+
     // Save results
     std::fs::create_dir_all("logs")?;
     
@@ -422,6 +516,7 @@ async fn main() -> anyhow::Result<()> {
                 "sharpe_ratio": optimized_result.sharpe_ratio.to_string()
             }
         },
+        "ab_report": ab_report,
         "winner": if opt_risk_adj > orig_risk_adj { "OPTIMIZED" } else { "ORIGINAL" }
     });
     
@@ -7,13 +7,16 @@
 //! - Dynamic market discovery via search_crypto_hourly_markets()
 //! - ML-based multi-factor prediction (technical + sentiment + orderbook)
 //! - Binance kline integration for feature extraction
+//! - Perp funding rate as a crowding tilt
 //! - Risk-controlled position sizing
 //! - Real-time logging
 
 use chrono::{DateTime, Duration, Utc};
 use reqwest::Client;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
 use std::fs::{self, File};
 use std::io::Write;
 use tokio::time::{interval, Duration as TokioDuration};
@@ -21,8 +24,16 @@ use tracing::{debug, info, warn};
 use tracing_subscriber;
 
 use polymarket_bot::client::gamma::GammaClient;
-use polymarket_bot::types::Market;
-use polymarket_bot::ml::predictor::{MLPredictor, MLPredictorConfig, MarketDataInput, KlineData};
+use polymarket_bot::data::{FundingRateClient, SymbolMapper};
+use polymarket_bot::monitor::settlement::winning_outcome;
+use polymarket_bot::storage::Database;
+use polymarket_bot::strategy::{FilterChain, HasMappableSymbol, MinLiquidity, NotAlreadyTraded, SettlementWindow};
+use polymarket_bot::fusion::Signal as FusionSignal;
+use polymarket_bot::types::{Market, OrderBook, OutcomeMatcher};
+use polymarket_bot::utils::truncate_chars;
+use polymarket_bot::ml::predictor::{sentiment_symbol, MLPredictor, MLPredictorConfig, MarketDataInput, KlineData};
+use polymarket_bot::sentiment::{SentimentEngine, SentimentSignal};
+use polymarket_bot::risk::{OrderBookLevel, OrderBookSnapshot};
 
 const GAMMA_API_URL: &str = "https://gamma-api.polymarket.com";
 const BINANCE_API_URL: &str = "https://api.binance.com";
@@ -31,8 +42,11 @@ const INITIAL_CAPITAL: f64 = 100.0;
 const MIN_EDGE: f64 = 0.03; // 3%
 const MAX_POSITION_PCT: f64 = 0.05; // 5% of capital
 const MAX_TRADES_PER_HOUR: u32 = 5;
-const MIN_LIQUIDITY: f64 = 5000.0; // $5k minimum liquidity
-const MAX_SETTLEMENT_MINUTES: i64 = 30; // Only trade markets settling within 30 mins
+const MIN_LIQUIDITY: Decimal = dec!(5000); // $5k minimum liquidity
+const MAX_SETTLEMENT_MINUTES: u64 = 30; // Only trade markets settling within 30 mins
+const DEDUP_DB_PATH: &str = "data/live_trading.db"; // Traded markets survive restarts here
+const DEDUP_WINDOW_HOURS: i64 = 24; // Markets traded this recently stay skipped after a restart
+const IMBALANCE_DEPTH_LEVELS: usize = 10; // Order book levels used for imbalance
 
 /// Trade record for logging
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -89,6 +103,12 @@ struct BinanceKline {
 struct ExtendedBinanceData {
     pub context: BinanceContext,
     pub klines: Vec<KlineData>,
+    /// Depth-weighted order book imbalance (None if depth fetch failed)
+    pub orderbook_imbalance: Option<f64>,
+    /// Social sentiment for the base asset (None if sentiment is disabled)
+    pub sentiment: Option<SentimentSignal>,
+    /// Perp funding tilt (None if the futures API failed)
+    pub funding: Option<FusionSignal>,
 }
 
 /// Live trader state
@@ -101,7 +121,12 @@ struct LiveTrader {
     last_hour_reset: DateTime<Utc>,
     log_file: File,
     ml_predictor: MLPredictor,
-    traded_market_ids: HashSet<String>,  // Deduplication: prevent repeat trades on same market
+    sentiment_engine: Option<SentimentEngine>,  // Only enabled with TWITTER_BEARER_TOKEN
+    funding: FundingRateClient,
+    symbols: SymbolMapper,
+    traded: NotAlreadyTraded,  // Deduplication: prevent repeat trades on same market
+    db: Option<Database>,  // Persists `traded` across restarts
+    scan_filters: FilterChain,
 }
 
 impl LiveTrader {
@@ -119,8 +144,29 @@ impl LiveTrader {
         let ml_config = MLPredictorConfig::default();
         let ml_predictor = MLPredictor::new(ml_config);
 
+        // Sentiment needs real tweets; without a token the engine only has mock data
+        let sentiment_engine = std::env::var("TWITTER_BEARER_TOKEN")
+            .ok()
+            .map(|token| SentimentEngine::new(Some(token)));
+
         info!("📁 Log file: {}", log_path);
         info!("🤖 ML Predictor initialized with multi-factor fusion");
+        if sentiment_engine.is_none() {
+            info!("💬 Sentiment disabled (set TWITTER_BEARER_TOKEN to enable)");
+        }
+
+        let symbols = SymbolMapper::default();
+        let traded = NotAlreadyTraded::new();
+
+        // Restore dedup state so a restart doesn't re-trade open positions
+        fs::create_dir_all("data")?;
+        let db = Database::connect(DEDUP_DB_PATH).await?;
+        db.purge_expired_traded().await?;
+        let restored = db.recently_traded_markets(Duration::hours(DEDUP_WINDOW_HOURS)).await?;
+        for market_id in &restored {
+            traded.mark_traded(market_id);
+        }
+        info!("🔁 Restored {} recently traded markets from {}", restored.len(), DEDUP_DB_PATH);
 
         Ok(Self {
             gamma,
@@ -131,7 +177,12 @@ impl LiveTrader {
             last_hour_reset: Utc::now(),
             log_file,
             ml_predictor,
-            traded_market_ids: HashSet::new(),
+            sentiment_engine,
+            funding: FundingRateClient::new(),
+            scan_filters: scan_filters(&traded, &symbols),
+            symbols,
+            traded,
+            db: Some(db),
         })
     }
 
@@ -235,19 +286,8 @@ impl LiveTrader {
     }
 
     /// Map market question to Binance symbol
-    fn get_binance_symbol(&self, question: &str) -> Option<&'static str> {
-        let q = question.to_lowercase();
-        if q.contains("bitcoin") || q.contains("btc") {
-            Some("BTCUSDT")
-        } else if q.contains("ethereum") || q.contains("eth") {
-            Some("ETHUSDT")
-        } else if q.contains("solana") || q.contains("sol") {
-            Some("SOLUSDT")
-        } else if q.contains("xrp") {
-            Some("XRPUSDT")
-        } else {
-            None
-        }
+    fn get_binance_symbol(&self, question: &str) -> Option<&str> {
+        self.symbols.symbol_for(question)
     }
 
     /// Get extended Binance data with klines for ML features
@@ -266,39 +306,91 @@ impl LiveTrader {
         // Convert to KlineData format for ML predictor
         let klines: Vec<KlineData> = raw_klines
             .iter()
-            .map(|k| KlineData {
-                timestamp: k[0].as_i64().unwrap_or(0),
-                open: k[1].as_str().and_then(|s| s.parse().ok()).unwrap_or(0.0),
-                high: k[2].as_str().and_then(|s| s.parse().ok()).unwrap_or(0.0),
-                low: k[3].as_str().and_then(|s| s.parse().ok()).unwrap_or(0.0),
-                close: k[4].as_str().and_then(|s| s.parse().ok()).unwrap_or(0.0),
-                volume: k[5].as_str().and_then(|s| s.parse().ok()).unwrap_or(0.0),
-            })
+            .map(|k| KlineData::from_binance_row(k))
             .collect();
 
-        Ok(ExtendedBinanceData { context, klines })
+        let orderbook_imbalance = match self.get_binance_order_book(symbol).await {
+            Ok(book) => book
+                .depth_weighted_imbalance(IMBALANCE_DEPTH_LEVELS)
+                .to_f64(),
+            Err(e) => {
+                debug!("Binance depth error for {}: {}", symbol, e);
+                None
+            }
+        };
+
+        let sentiment = match &self.sentiment_engine {
+            Some(engine) => Some(engine.analyze_asset(sentiment_symbol(symbol)).await),
+            None => None,
+        };
+
+        let funding = match self.funding.signal(symbol).await {
+            Ok(signal) => Some(signal),
+            Err(e) => {
+                debug!("Binance funding error for {}: {}", symbol, e);
+                None
+            }
+        };
+
+        Ok(ExtendedBinanceData { context, klines, orderbook_imbalance, sentiment, funding })
+    }
+
+    /// Fetch Binance order book depth as a liquidity snapshot
+    async fn get_binance_order_book(&self, symbol: &str) -> anyhow::Result<OrderBookSnapshot> {
+        let url = format!(
+            "{}/api/v3/depth?symbol={}&limit={}",
+            BINANCE_API_URL, symbol, IMBALANCE_DEPTH_LEVELS
+        );
+        let resp: serde_json::Value = self.http.get(&url).send().await?.json().await?;
+
+        let parse_levels = |arr: &serde_json::Value| -> Vec<OrderBookLevel> {
+            arr.as_array()
+                .map(|a| {
+                    a.iter()
+                        .filter_map(|l| {
+                            Some(OrderBookLevel {
+                                price: l[0].as_str()?.parse().ok()?,
+                                size: l[1].as_str()?.parse().ok()?,
+                            })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        let book = OrderBook {
+            bids: parse_levels(&resp["bids"]),
+            asks: parse_levels(&resp["asks"]),
+        };
+        Ok(OrderBookSnapshot::from_book(symbol, book))
     }
 
     /// Generate ML-based prediction for a market (multi-factor fusion)
     fn predict(&self, market: &Market, extended_data: &ExtendedBinanceData) -> (String, f64, f64) {
         // Get current Yes/No prices
         let yes_price = market
-            .outcomes
-            .iter()
-            .find(|o| o.outcome.to_lowercase() == "yes")
-            .map(|o| o.price.to_string().parse::<f64>().unwrap_or(0.5))
+            .find_outcome("yes", &OutcomeMatcher::default())
+            .map(|o| o.price.to_f64().unwrap_or(0.5))
             .unwrap_or(0.5);
 
         // Build MarketDataInput for ML predictor
-        let market_data = MarketDataInput {
+        let mut market_data = MarketDataInput {
             symbol: extended_data.context.symbol.clone(),
             price: extended_data.context.current_price,
             klines: extended_data.klines.clone(),
-            orderbook_imbalance: None, // TODO: integrate order book data
+            orderbook_imbalance: extended_data.orderbook_imbalance,
             volume_24h: extended_data.context.volume_24h,
-            sentiment_score: None, // TODO: integrate Twitter/social sentiment
+            sentiment_score: None,
+            sentiment_confidence: None,
+            funding_tilt: None,
             question: market.question.clone(),
         };
+        if let Some(signal) = &extended_data.sentiment {
+            market_data = market_data.with_sentiment(signal);
+        }
+        if let Some(signal) = &extended_data.funding {
+            market_data = market_data.with_funding(signal);
+        }
 
         // Run ML prediction with multi-factor fusion
         let ml_result = self.ml_predictor.predict(&market_data, yes_price);
@@ -326,10 +418,8 @@ impl LiveTrader {
     #[allow(dead_code)]
     fn predict_simple(&self, market: &Market, binance: &BinanceContext) -> (String, f64, f64) {
         let yes_price = market
-            .outcomes
-            .iter()
-            .find(|o| o.outcome.to_lowercase() == "yes")
-            .map(|o| o.price.to_string().parse::<f64>().unwrap_or(0.5))
+            .find_outcome("yes", &OutcomeMatcher::default())
+            .map(|o| o.price.to_f64().unwrap_or(0.5))
             .unwrap_or(0.5);
 
         let no_price = 1.0 - yes_price;
@@ -426,7 +516,7 @@ impl LiveTrader {
             .outcomes
             .iter()
             .find(|o| o.outcome == side)
-            .map(|o| o.price.to_string().parse::<f64>().unwrap_or(0.5))
+            .map(|o| o.price.to_f64().unwrap_or(0.5))
             .unwrap_or(0.5);
 
         let shares = amount / price;
@@ -450,18 +540,13 @@ impl LiveTrader {
 
         self.capital -= amount;
         self.hourly_trade_count += 1;
-        self.traded_market_ids.insert(market.id.clone());  // Dedup: track traded markets
+        self.traded.mark_traded(&market.id);  // Dedup: track traded markets
         self.trades.push(trade.clone());
         self.log_trade(&trade);
 
         trade
     }
 
-    /// Check if a market has already been traded (deduplication)
-    fn already_traded(&self, market_id: &str) -> bool {
-        self.traded_market_ids.contains(market_id)
-    }
-
     /// Check and update settlements for open trades
     async fn check_settlements(&mut self) {
         let mut settlements_to_process = Vec::new();
@@ -487,11 +572,11 @@ impl LiveTrader {
                     let payout = shares;
                     let pnl = payout - amount;
                     self.capital += payout;
-                    (pnl, format!("✅ SETTLED WON: {} | PnL: ${:.2}", question.chars().take(40).collect::<String>(), pnl))
+                    (pnl, format!("✅ SETTLED WON: {} | PnL: ${:.2}", truncate_chars(&question, 40), pnl))
                 } else {
                     // Lost: payout is $0
                     let pnl = -amount;
-                    (pnl, format!("❌ SETTLED LOST: {} | PnL: ${:.2}", question.chars().take(40).collect::<String>(), pnl))
+                    (pnl, format!("❌ SETTLED LOST: {} | PnL: ${:.2}", truncate_chars(&question, 40), pnl))
                 };
 
                 // Update trade
@@ -506,23 +591,10 @@ impl LiveTrader {
         }
     }
 
-    /// Fetch market resolution from Polymarket API
+    /// Outcome the market resolved to, once Gamma reports it settled
     async fn fetch_market_resolution(&self, market_id: &str) -> anyhow::Result<Option<String>> {
-        let url = format!("{}/markets/{}", GAMMA_API_URL, market_id);
-        let resp = self.http.get(&url).send().await?;
-        
-        if !resp.status().is_success() {
-            return Ok(None);
-        }
-
-        let market: serde_json::Value = resp.json().await?;
-        
-        // Check if market is resolved
-        if let Some(resolved_outcome) = market.get("resolvedOutcome").and_then(|v| v.as_str()) {
-            return Ok(Some(resolved_outcome.to_string()));
-        }
-        
-        Ok(None)
+        let market = self.gamma.get_market(market_id).await?;
+        Ok(winning_outcome(&market).map(|o| o.outcome.clone()))
     }
 
     /// Main trading loop
@@ -563,40 +635,12 @@ impl LiveTrader {
 
             // Filter by liquidity and find opportunities
             let mut opportunities: Vec<(Market, String, f64, f64, ExtendedBinanceData)> = Vec::new();
+            let filtered = self.scan_filters.apply(&markets);
+            if !filtered.skipped.is_empty() {
+                debug!("Skipped {} markets: {:?}", filtered.skipped.len(), filtered.skipped);
+            }
 
-            for market in &markets {
-                // DEDUP: Skip markets we've already traded
-                if self.already_traded(&market.id) {
-                    debug!("Skipping {} - already traded", market.question);
-                    continue;
-                }
-
-                // Skip markets not settling soon (within MAX_SETTLEMENT_MINUTES)
-                if let Some(end_date) = market.end_date {
-                    let now = Utc::now();
-                    let time_to_settlement = end_date - now;
-                    let mins_to_settlement = time_to_settlement.num_minutes();
-                    
-                    // Skip if already expired or too far in the future
-                    if mins_to_settlement < 0 || mins_to_settlement > MAX_SETTLEMENT_MINUTES {
-                        debug!(
-                            "Skipping {} - settles in {} mins (max: {})",
-                            market.question, mins_to_settlement, MAX_SETTLEMENT_MINUTES
-                        );
-                        continue;
-                    }
-                } else {
-                    // No end_date means we can't verify settlement time, skip
-                    debug!("Skipping {} - no end_date", market.question);
-                    continue;
-                }
-
-                // Skip low liquidity
-                let liq: f64 = market.liquidity.to_string().parse().unwrap_or(0.0);
-                if liq < MIN_LIQUIDITY {
-                    continue;
-                }
-
+            for market in filtered.passed {
                 // Get Binance symbol
                 let symbol = match self.get_binance_symbol(&market.question) {
                     Some(s) => s,
@@ -650,6 +694,13 @@ impl LiveTrader {
                 *confidence,
                 &extended_data.context,
             );
+            if let Some(db) = &self.db {
+                // The record lapses once the market has ended
+                let expires_at = market.end_date.unwrap_or_else(|| Utc::now() + Duration::hours(1));
+                if let Err(e) = db.mark_traded(&market.id, expires_at).await {
+                    warn!("Failed to persist traded market {}: {}", market.id, e);
+                }
+            }
 
             self.log("═══════════════════════════════════════════════════════════");
             self.log(&format!("🎯 TRADE EXECUTED: {}", trade.id));
@@ -681,6 +732,15 @@ impl LiveTrader {
     }
 }
 
+/// Filters run on every discovered market, cheapest first
+fn scan_filters(traded: &NotAlreadyTraded, symbols: &SymbolMapper) -> FilterChain {
+    FilterChain::new()
+        .with_filter(traded.clone())
+        .with_filter(SettlementWindow::new(MAX_SETTLEMENT_MINUTES))
+        .with_filter(MinLiquidity::new(MIN_LIQUIDITY))
+        .with_filter(HasMappableSymbol::new(symbols.clone()))
+}
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // Initialize logging
@@ -711,7 +771,12 @@ mod tests {
             last_hour_reset: Utc::now(),
             log_file: File::create("/dev/null").unwrap(),
             ml_predictor: MLPredictor::new(MLPredictorConfig::default()),
-            traded_market_ids: HashSet::new(),
+            sentiment_engine: None,
+            funding: FundingRateClient::new(),
+            scan_filters: FilterChain::new(),
+            symbols: SymbolMapper::default(),
+            traded: NotAlreadyTraded::new(),
+            db: None,
         }
     }
 
@@ -724,6 +789,7 @@ mod tests {
         assert_eq!(trader.get_binance_symbol("Solana hourly"), Some("SOLUSDT"));
         assert_eq!(trader.get_binance_symbol("XRP up or down"), Some("XRPUSDT"));
         assert_eq!(trader.get_binance_symbol("Random market"), None);
+        assert_eq!(trader.get_binance_symbol("Bitcoin vs Ethereum this week?"), None);
     }
 
     #[test]
@@ -786,10 +852,12 @@ mod tests {
                 rsi_14: Some(55.0),
             },
             klines,
+            orderbook_imbalance: Some(0.3),
+            sentiment: None,
+            funding: None,
         };
 
         // Create a mock market
-        use rust_decimal::Decimal;
         use polymarket_bot::types::Outcome;
         let market = Market {
             id: "test-market-id".to_string(),
@@ -812,6 +880,10 @@ mod tests {
             end_date: Some(Utc::now() + Duration::hours(1)),
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         };
 
         // Run ML prediction
@@ -529,6 +529,10 @@ async fn buy_position(
         end_date: None,
         active: true,
         closed: false,
+        category: None,
+        tags: Vec::new(),
+        tick_size: None,
+        min_size: None,
     };
     
     match trader.buy(&mock_market, PositionSide::Yes, amount,
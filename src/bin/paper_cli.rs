@@ -10,6 +10,7 @@
 use clap::{Parser, Subcommand};
 use polymarket_bot::paper::{PaperTrader, PaperTraderConfig, PortfolioSummary, PositionSide};
 use polymarket_bot::client::GammaClient;
+use polymarket_bot::utils::truncate_chars;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use std::str::FromStr;
@@ -210,7 +211,7 @@ fn print_positions(positions: &[polymarket_bot::paper::Position]) {
             "{} {} | {} @ {:.4} | Value: ${:.2} | PnL: ${:.2} ({:.1}%)",
             pnl_emoji,
             pos.side,
-            &pos.question[..pos.question.len().min(30)],
+            truncate_chars(&pos.question, 30),
             pos.entry_price,
             pos.current_value,
             pos.unrealized_pnl,
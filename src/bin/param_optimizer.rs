@@ -54,6 +54,10 @@ fn generate_test_markets() -> Vec<Market> {
                 Outcome { outcome: "No".to_string(), token_id: "btc-100k-no".to_string(), price: dec!(0.55) },
             ],
             volume: dec!(500000), liquidity: dec!(100000), active: true, closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         },
         Market {
             id: "eth-5k".to_string(),
@@ -65,6 +69,10 @@ fn generate_test_markets() -> Vec<Market> {
                 Outcome { outcome: "No".to_string(), token_id: "eth-5k-no".to_string(), price: dec!(0.65) },
             ],
             volume: dec!(300000), liquidity: dec!(80000), active: true, closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         },
         Market {
             id: "fed-rate".to_string(),
@@ -76,6 +84,10 @@ fn generate_test_markets() -> Vec<Market> {
                 Outcome { outcome: "No".to_string(), token_id: "fed-rate-no".to_string(), price: dec!(0.40) },
             ],
             volume: dec!(200000), liquidity: dec!(50000), active: true, closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         },
         Market {
             id: "trump-approval".to_string(),
@@ -87,6 +99,10 @@ fn generate_test_markets() -> Vec<Market> {
                 Outcome { outcome: "No".to_string(), token_id: "trump-approval-no".to_string(), price: dec!(0.68) },
             ],
             volume: dec!(450000), liquidity: dec!(120000), active: true, closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         },
         Market {
             id: "sp500-6k".to_string(),
@@ -98,6 +114,10 @@ fn generate_test_markets() -> Vec<Market> {
                 Outcome { outcome: "No".to_string(), token_id: "sp500-6k-no".to_string(), price: dec!(0.28) },
             ],
             volume: dec!(180000), liquidity: dec!(45000), active: true, closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         },
         Market {
             id: "ai-regulation".to_string(),
@@ -109,6 +129,10 @@ fn generate_test_markets() -> Vec<Market> {
                 Outcome { outcome: "No".to_string(), token_id: "ai-reg-no".to_string(), price: dec!(0.60) },
             ],
             volume: dec!(150000), liquidity: dec!(35000), active: true, closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         },
         Market {
             id: "sol-500".to_string(),
@@ -120,6 +144,10 @@ fn generate_test_markets() -> Vec<Market> {
                 Outcome { outcome: "No".to_string(), token_id: "sol-500-no".to_string(), price: dec!(0.75) },
             ],
             volume: dec!(220000), liquidity: dec!(55000), active: true, closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         },
         Market {
             id: "gold-3k".to_string(),
@@ -131,6 +159,10 @@ fn generate_test_markets() -> Vec<Market> {
                 Outcome { outcome: "No".to_string(), token_id: "gold-3k-no".to_string(), price: dec!(0.45) },
             ],
             volume: dec!(280000), liquidity: dec!(70000), active: true, closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         },
     ]
 }
@@ -153,9 +185,24 @@ async fn run_simulation(params: &ParamSet, markets: &[Market], seed: u64) -> any
         min_confidence: params.min_confidence,
         kelly_fraction: params.kelly_fraction,
         scan_interval_secs: 180,
+        scan_concurrency: 4,
         model_update_interval_secs: 900,
         compound_enabled: true,
         compound_sqrt_scaling: true,
+        fee_rate: Decimal::ZERO,
+        slippage_estimate: Decimal::ZERO,
+        max_price_deviation: dec!(0.05),
+        min_liquidity: dec!(10000),
+        crypto_min_liquidity: dec!(1000),
+        min_minutes_to_resolution: 0,
+        max_minutes_to_resolution: None,
+        size_decay_minutes: 0,
+        outcome_matching: Default::default(),
+        allowlist: Vec::new(),
+        denylist: Vec::new(),
+        categories: Vec::new(),
+        anti_chase: Default::default(),
+        abstain_below_confidence: None,
     };
     
     let risk = RiskConfig {
@@ -164,6 +211,7 @@ async fn run_simulation(params: &ParamSet, markets: &[Market], seed: u64) -> any
         max_daily_loss_pct: dec!(0.05),  // Fixed at 5%
         min_balance_reserve: dec!(100),
         max_open_positions: 5,
+        ..RiskConfig::default()
     };
     
     let mut sim = EnhancedDryRunSimulator::new(dec!(1000), strategy, risk)
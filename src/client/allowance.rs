@@ -0,0 +1,218 @@
+//! USDC allowance for the exchange contracts
+//!
+//! Orders only fill if the exchange may move the wallet's USDC. The CLOB
+//! reports the current allowance; raising it takes an on-chain ERC-20
+//! `approve`, which the bot only sends when `polymarket.auto_approve` is
+//! set.
+
+use crate::error::{BotError, Result};
+use async_trait::async_trait;
+use ethers::abi::{self, Token};
+use ethers::middleware::SignerMiddleware;
+use ethers::providers::{Http, Middleware, Provider};
+use ethers::signers::LocalWallet;
+use ethers::types::{Address, Bytes, TransactionRequest, U256};
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// USDC.e on Polygon
+pub const USDC_ADDRESS: &str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
+
+/// Contracts that spend collateral: CTF exchange, neg-risk exchange and
+/// neg-risk adapter
+pub const EXCHANGE_SPENDERS: [&str; 3] = [
+    "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E",
+    "0xC5d563A36AE78145C45a50134d48A1215220f80a",
+    "0xd91E80cF2E7be2e162c6513ceD06f1dD0dA35296",
+];
+
+/// `approve(address,uint256)` selector
+const APPROVE_SELECTOR: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
+
+/// USDC has 6 decimals
+const USDC_DECIMALS: u32 = 6;
+
+/// Allowance checks and approvals (allows mocking)
+#[async_trait]
+pub trait Allowance: Send + Sync {
+    /// USDC the exchange may currently spend
+    async fn check_allowance(&self) -> Result<Decimal>;
+
+    /// Approve the exchange for unlimited USDC, returning transaction hashes
+    async fn approve(&self) -> Result<Vec<String>>;
+
+    /// Whether approvals may be sent without asking
+    fn auto_approve(&self) -> bool;
+
+    /// Make sure at least `min` USDC is approved
+    ///
+    /// Returns the approval transactions sent, empty when the allowance
+    /// already suffices. Without the opt-in a short allowance is an error.
+    async fn ensure_allowance(&self, min: Decimal) -> Result<Vec<String>> {
+        let current = self.check_allowance().await?;
+        if current >= min {
+            return Ok(Vec::new());
+        }
+        if !self.auto_approve() {
+            return Err(BotError::Config(format!(
+                "USDC allowance {} is below {}; approve the exchange contracts or set polymarket.auto_approve = true",
+                current, min
+            )));
+        }
+        tracing::info!("USDC allowance {} below {}, sending approval", current, min);
+        self.approve().await
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub(crate) struct AllowanceResponse {
+    #[serde(default)]
+    allowance: Option<String>,
+    #[serde(default)]
+    allowances: HashMap<String, String>,
+}
+
+impl AllowanceResponse {
+    /// Smallest allowance over the exchange contracts, in USDC
+    pub(crate) fn usdc(&self) -> Result<Decimal> {
+        let raw = self
+            .allowance
+            .iter()
+            .chain(self.allowances.values())
+            .map(|v| parse_base_units(v))
+            .collect::<Result<Vec<_>>>()?;
+        raw.into_iter()
+            .min()
+            .ok_or_else(|| BotError::Api("Allowance missing from response".into()))
+    }
+}
+
+/// Parse a 6-decimal base unit amount; unlimited approvals overflow
+/// `Decimal` and saturate at `Decimal::MAX`
+fn parse_base_units(value: &str) -> Result<Decimal> {
+    let value = value.trim();
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(BotError::Api(format!("Invalid allowance: {:?}", value)));
+    }
+    match value.parse::<Decimal>() {
+        Ok(units) => Ok(units / Decimal::from(10u64.pow(USDC_DECIMALS))),
+        Err(_) => Ok(Decimal::MAX),
+    }
+}
+
+/// Send an unlimited USDC approval to every exchange contract and wait for
+/// each to be mined
+pub(crate) async fn send_approvals(rpc_url: &str, wallet: LocalWallet) -> Result<Vec<String>> {
+    let provider = Provider::<Http>::try_from(rpc_url)
+        .map_err(|e| BotError::Config(format!("Invalid RPC URL {}: {}", rpc_url, e)))?;
+    let client = SignerMiddleware::new(provider, wallet);
+    let usdc: Address = USDC_ADDRESS.parse().expect("valid address");
+
+    let mut hashes = Vec::new();
+    for spender in EXCHANGE_SPENDERS {
+        let spender: Address = spender.parse().expect("valid address");
+        let tx = TransactionRequest::new().to(usdc).data(approve_calldata(spender, U256::MAX));
+        let pending = client
+            .send_transaction(tx, None)
+            .await
+            .map_err(|e| BotError::Execution(format!("Approval failed: {}", e)))?;
+        let hash = format!("{:?}", pending.tx_hash());
+        let receipt = pending
+            .await
+            .map_err(|e| BotError::Execution(format!("Approval {} failed: {}", hash, e)))?;
+        if receipt.and_then(|r| r.status).map(|s| s.is_zero()).unwrap_or(true) {
+            return Err(BotError::Execution(format!("Approval {} reverted", hash)));
+        }
+        tracing::info!("Approved {:?} to spend USDC in {}", spender, hash);
+        hashes.push(hash);
+    }
+    Ok(hashes)
+}
+
+fn approve_calldata(spender: Address, amount: U256) -> Bytes {
+    let mut data = APPROVE_SELECTOR.to_vec();
+    data.extend(abi::encode(&[Token::Address(spender), Token::Uint(amount)]));
+    data.into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct FakeWallet {
+        allowance: Decimal,
+        auto_approve: bool,
+        approvals: AtomicUsize,
+    }
+
+    impl FakeWallet {
+        fn new(allowance: Decimal, auto_approve: bool) -> Self {
+            Self { allowance, auto_approve, approvals: AtomicUsize::new(0) }
+        }
+    }
+
+    #[async_trait]
+    impl Allowance for FakeWallet {
+        async fn check_allowance(&self) -> Result<Decimal> {
+            Ok(self.allowance)
+        }
+
+        async fn approve(&self) -> Result<Vec<String>> {
+            self.approvals.fetch_add(1, Ordering::SeqCst);
+            Ok(vec!["0xabc".to_string()])
+        }
+
+        fn auto_approve(&self) -> bool {
+            self.auto_approve
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ensure_allowance_approves_only_below_threshold() {
+        let enough = FakeWallet::new(dec!(500), true);
+        assert!(enough.ensure_allowance(dec!(500)).await.unwrap().is_empty());
+        assert_eq!(enough.approvals.load(Ordering::SeqCst), 0);
+
+        let short = FakeWallet::new(dec!(499.99), true);
+        assert_eq!(short.ensure_allowance(dec!(500)).await.unwrap(), vec!["0xabc"]);
+        assert_eq!(short.approvals.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_ensure_allowance_requires_opt_in() {
+        let wallet = FakeWallet::new(Decimal::ZERO, false);
+        let err = wallet.ensure_allowance(dec!(100)).await.unwrap_err();
+        assert!(err.to_string().contains("auto_approve"));
+        assert_eq!(wallet.approvals.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn test_allowance_response_takes_smallest_spender() {
+        let resp: AllowanceResponse = serde_json::from_str(
+            r#"{"balance":"0","allowances":{"0xa":"250000000","0xb":"115792089237316195423570985008687907853269984665640564039457584007913129639935"}}"#,
+        )
+        .unwrap();
+        assert_eq!(resp.usdc().unwrap(), dec!(250));
+
+        let resp: AllowanceResponse =
+            serde_json::from_str(r#"{"balance":"0","allowance":"1500000"}"#).unwrap();
+        assert_eq!(resp.usdc().unwrap(), dec!(1.5));
+        assert!(serde_json::from_str::<AllowanceResponse>(r#"{"balance":"0"}"#)
+            .unwrap()
+            .usdc()
+            .is_err());
+    }
+
+    #[test]
+    fn test_approve_calldata() {
+        let spender: Address = EXCHANGE_SPENDERS[0].parse().unwrap();
+        let data = approve_calldata(spender, U256::MAX);
+        assert_eq!(data.len(), 4 + 32 + 32);
+        assert_eq!(&data[..4], &APPROVE_SELECTOR);
+        assert_eq!(&data[16..36], spender.as_bytes());
+        assert!(data[36..].iter().all(|b| *b == 0xff));
+    }
+}
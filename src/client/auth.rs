@@ -5,14 +5,51 @@
 
 use crate::error::{BotError, Result};
 use ethers::signers::{LocalWallet, Signer};
-use ethers::types::{Address, H256, U256};
-use ethers::utils::keccak256;
+use ethers::types::{Address, Signature, H256, U256};
+use ethers::utils::{keccak256, to_checksum};
 
 // EIP-712 domain constants for CLOB auth
 const CLOB_DOMAIN_NAME: &str = "ClobAuthDomain";
 const CLOB_VERSION: &str = "1";
 const CLOB_AUTH_MESSAGE: &str = "This message attests that I control the given wallet";
 
+/// Chains this client can trade on
+///
+/// Only Polygon mainnet: the exchange, USDC and spender addresses used for
+/// signing and approvals are mainnet deployments.
+pub const SUPPORTED_CHAINS: [(u64, &str); 1] = [(137, "Polygon")];
+
+/// Name of a supported chain, `None` for any other chain id
+pub fn chain_name(chain_id: u64) -> Option<&'static str> {
+    SUPPORTED_CHAINS
+        .iter()
+        .find(|(id, _)| *id == chain_id)
+        .map(|(_, name)| *name)
+}
+
+/// Parse a hex EVM address, enforcing its EIP-55 checksum when it is
+/// mixed-case
+///
+/// All-lowercase and all-uppercase addresses carry no checksum and are
+/// accepted as is.
+pub fn parse_address(value: &str) -> Result<Address> {
+    let hex_part = value
+        .strip_prefix("0x")
+        .ok_or_else(|| BotError::Config(format!("Address {} must start with 0x", value)))?;
+    if hex_part.len() != 40 || !hex_part.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(BotError::Config(format!("Address {} must be 40 hex digits", value)));
+    }
+    let address: Address = value
+        .parse()
+        .map_err(|e| BotError::Config(format!("Invalid address {}: {}", value, e)))?;
+    let mixed_case = hex_part.bytes().any(|b| b.is_ascii_lowercase())
+        && hex_part.bytes().any(|b| b.is_ascii_uppercase());
+    if mixed_case && to_checksum(&address, None) != value {
+        return Err(BotError::Config(format!("Address {} has an invalid checksum", value)));
+    }
+    Ok(address)
+}
+
 /// Signer for Polymarket API authentication
 #[derive(Clone)]
 pub struct PolySigner {
@@ -22,7 +59,15 @@ pub struct PolySigner {
 
 impl PolySigner {
     /// Create a new signer from a private key (hex string, with or without 0x prefix)
+    ///
+    /// Fails for chains the exchange is not deployed on.
     pub fn from_private_key(private_key: &str, chain_id: u64) -> Result<Self> {
+        if chain_name(chain_id).is_none() {
+            return Err(BotError::Config(format!(
+                "Unsupported chain id {}, expected 137 (Polygon)",
+                chain_id
+            )));
+        }
         let key_hex = private_key.trim_start_matches("0x");
         let wallet: LocalWallet = key_hex
             .parse()
@@ -43,6 +88,11 @@ impl PolySigner {
         format!("{:?}", self.wallet.address())
     }
     
+    /// Wallet for signing on-chain transactions
+    pub(crate) fn wallet(&self) -> &LocalWallet {
+        &self.wallet
+    }
+
     /// Get chain ID
     pub fn chain_id(&self) -> u64 {
         self.chain_id
@@ -119,15 +169,29 @@ impl PolySigner {
 
     /// Sign an order for submission to CLOB
     pub async fn sign_order(&self, order_data: &OrderSignData) -> Result<String> {
-        let domain_separator = self.compute_domain_separator();
-        let struct_hash = self.compute_order_struct_hash(order_data);
+        self.sign_hash(self.order_digest(order_data)).await
+    }
 
-        let mut data = vec![0x19, 0x01];
-        data.extend_from_slice(&domain_separator);
-        data.extend_from_slice(&struct_hash);
-        let digest = H256::from(keccak256(&data));
+    /// Address that produced `signature` over `order`
+    ///
+    /// The exchange rejects an order whose signature does not recover to
+    /// its `signer`, so this catches hashing mistakes before submission.
+    pub fn recover_address(&self, order: &OrderSignData, signature: &str) -> Result<Address> {
+        let bytes = hex::decode(signature.trim_start_matches("0x"))
+            .map_err(|e| BotError::Auth(format!("Invalid signature hex: {}", e)))?;
+        let signature = Signature::try_from(bytes.as_slice())
+            .map_err(|e| BotError::Auth(format!("Invalid signature: {}", e)))?;
+        signature
+            .recover(self.order_digest(order))
+            .map_err(|e| BotError::Auth(format!("Signature recovery failed: {}", e)))
+    }
 
-        self.sign_hash(digest).await
+    /// EIP-712 digest of an order under the CTF exchange domain
+    fn order_digest(&self, order: &OrderSignData) -> H256 {
+        let mut data = vec![0x19, 0x01];
+        data.extend_from_slice(&self.compute_domain_separator());
+        data.extend_from_slice(&self.compute_order_struct_hash(order));
+        H256::from(keccak256(&data))
     }
 
     fn compute_domain_separator(&self) -> [u8; 32] {
@@ -214,3 +278,142 @@ pub struct OrderSignData {
     pub side: u8,
     pub signature_type: u8,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::transaction::eip712::{Eip712, TypedData};
+
+    /// Hardhat's first default account; never holds real funds
+    const TEST_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const TEST_ADDRESS: &str = "0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266";
+    /// `TEST_KEY`'s signature over `order()` on Polygon; RFC 6979 signing is
+    /// deterministic, so any change here means the hashing changed
+    const EXPECTED_ORDER_SIGNATURE: &str = "0x4e4a18de9ac827f073445bb64331b74a5f57feed1b86424cfaa61db51ae0c0de291110ad3c3541ac576a93bfd35adca6f9e4861ce3d46123e56eeadf3e55fd0c1c";
+
+    fn signer() -> PolySigner {
+        PolySigner::from_private_key(TEST_KEY, 137).unwrap()
+    }
+
+    /// Buy 100 shares at 0.50: pay 50 USDC for 100 outcome tokens
+    fn order(signer: &PolySigner) -> OrderSignData {
+        OrderSignData {
+            salt: U256::from(479249096354u64),
+            maker: signer.address(),
+            signer: signer.address(),
+            taker: Address::zero(),
+            token_id: U256::from_dec_str(
+                "71321045679252212594626385532706912750332728571942532289631379312455583992563",
+            )
+            .unwrap(),
+            maker_amount: U256::from(50_000_000u64),
+            taker_amount: U256::from(100_000_000u64),
+            expiration: U256::zero(),
+            nonce: U256::zero(),
+            fee_rate_bps: U256::zero(),
+            side: 0,
+            signature_type: 0,
+        }
+    }
+
+    #[test]
+    fn test_order_digest_matches_typed_data_encoding() {
+        let signer = signer();
+        let order = order(&signer);
+        let typed: TypedData = serde_json::from_value(serde_json::json!({
+            "types": {
+                "EIP712Domain": [
+                    {"name": "name", "type": "string"},
+                    {"name": "version", "type": "string"},
+                    {"name": "chainId", "type": "uint256"},
+                    {"name": "verifyingContract", "type": "address"}
+                ],
+                "Order": [
+                    {"name": "salt", "type": "uint256"},
+                    {"name": "maker", "type": "address"},
+                    {"name": "signer", "type": "address"},
+                    {"name": "taker", "type": "address"},
+                    {"name": "tokenId", "type": "uint256"},
+                    {"name": "makerAmount", "type": "uint256"},
+                    {"name": "takerAmount", "type": "uint256"},
+                    {"name": "expiration", "type": "uint256"},
+                    {"name": "nonce", "type": "uint256"},
+                    {"name": "feeRateBps", "type": "uint256"},
+                    {"name": "side", "type": "uint8"},
+                    {"name": "signatureType", "type": "uint8"}
+                ]
+            },
+            "primaryType": "Order",
+            "domain": {
+                "name": "Polymarket CTF Exchange",
+                "version": "1",
+                "chainId": 137,
+                "verifyingContract": "0x4bFb41d5B3570DeFd03C39a9A4D8dE6Bd8B8982E"
+            },
+            "message": {
+                "salt": order.salt.to_string(),
+                "maker": format!("{:?}", order.maker),
+                "signer": format!("{:?}", order.signer),
+                "taker": format!("{:?}", order.taker),
+                "tokenId": order.token_id.to_string(),
+                "makerAmount": order.maker_amount.to_string(),
+                "takerAmount": order.taker_amount.to_string(),
+                "expiration": "0",
+                "nonce": "0",
+                "feeRateBps": "0",
+                "side": 0,
+                "signatureType": 0
+            }
+        }))
+        .unwrap();
+
+        assert_eq!(signer.order_digest(&order).0, typed.encode_eip712().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_sign_order_vector() {
+        let signer = signer();
+        assert_eq!(signer.address_hex(), TEST_ADDRESS);
+        let order = order(&signer);
+
+        let signature = signer.sign_order(&order).await.unwrap();
+        assert_eq!(signature, EXPECTED_ORDER_SIGNATURE);
+        assert_eq!(signer.recover_address(&order, &signature).unwrap(), signer.address());
+    }
+
+    #[test]
+    fn test_unsupported_chain_rejected() {
+        let err = PolySigner::from_private_key(TEST_KEY, 1).err().unwrap();
+        assert!(err.to_string().contains("Unsupported chain id 1"));
+        // Amoy has its own contract addresses, which orders aren't signed for
+        assert!(PolySigner::from_private_key(TEST_KEY, 80002).is_err());
+        assert_eq!(chain_name(137), Some("Polygon"));
+    }
+
+    #[test]
+    fn test_parse_address_checksum() {
+        let checksummed = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+        assert_eq!(parse_address(checksummed).unwrap(), signer().address());
+        assert!(parse_address(TEST_ADDRESS).is_ok());
+        assert!(parse_address(&TEST_ADDRESS.to_uppercase().replace("0X", "0x")).is_ok());
+
+        // One letter's case flipped
+        let err = parse_address("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".replace("aB", "ab").as_str())
+            .unwrap_err();
+        assert!(err.to_string().contains("invalid checksum"));
+        assert!(parse_address("0x123456").is_err());
+        assert!(parse_address("f39fd6e51aad88f6f4ce6ab8827279cfffb92266").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_recover_address_detects_tampering() {
+        let signer = signer();
+        let order = order(&signer);
+        let signature = signer.sign_order(&order).await.unwrap();
+
+        let tampered = OrderSignData { maker_amount: U256::from(49_000_000u64), ..order.clone() };
+        assert_ne!(signer.recover_address(&tampered, &signature).unwrap(), signer.address());
+
+        assert!(signer.recover_address(&order, "0x1234").is_err());
+    }
+}
@@ -0,0 +1,405 @@
+//! Local order books rebuilt from the market websocket
+//!
+//! Each token's book starts from a snapshot and is kept current by
+//! applying level deltas to it. A delta that does not follow on from the
+//! book (a skipped sequence number, or a best bid/ask the exchange reports
+//! that the local book disagrees with) means an update was lost: the book
+//! is dropped and the token queued for a resync from a fresh snapshot.
+//! Level prices outside [0, 1] are clamped and counted as anomalies.
+
+use super::mock::ClobClientTrait;
+use super::polymarket_ws::{MarketEvent, OrderLevel, PriceChange};
+use crate::error::Result;
+use crate::types::{CheckedPrice, OrderBook, OrderBookLevel, Side};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::str::FromStr;
+
+/// Change to one price level; a zero size removes the level
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelDelta {
+    pub side: Side,
+    pub price: Decimal,
+    pub size: Decimal,
+}
+
+/// What became of an update
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BookStatus {
+    Applied,
+    /// Older than the book or malformed, ignored
+    Stale,
+    /// The book can't be trusted until the token is resynced
+    ResyncNeeded,
+}
+
+#[derive(Debug, Default)]
+struct TrackedBook {
+    bids: BTreeMap<Decimal, Decimal>,
+    asks: BTreeMap<Decimal, Decimal>,
+    sequence: u64,
+}
+
+impl TrackedBook {
+    fn apply(&mut self, delta: &LevelDelta) {
+        let levels = match delta.side {
+            Side::Buy => &mut self.bids,
+            Side::Sell => &mut self.asks,
+        };
+        if delta.size > Decimal::ZERO {
+            levels.insert(delta.price, delta.size);
+        } else {
+            levels.remove(&delta.price);
+        }
+    }
+
+    fn best_bid(&self) -> Option<Decimal> {
+        self.bids.keys().next_back().copied()
+    }
+
+    fn best_ask(&self) -> Option<Decimal> {
+        self.asks.keys().next().copied()
+    }
+
+    fn to_book(&self) -> OrderBook {
+        let level = |(&price, &size)| OrderBookLevel { price, size };
+        OrderBook {
+            bids: self.bids.iter().rev().map(level).collect(),
+            asks: self.asks.iter().map(level).collect(),
+        }
+    }
+}
+
+/// Order books per token_id, maintained from snapshots and deltas
+#[derive(Debug, Default)]
+pub struct OrderBookMaintainer {
+    books: HashMap<String, TrackedBook>,
+    resync: BTreeSet<String>,
+    price_anomalies: u64,
+}
+
+impl OrderBookMaintainer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Current book for a token, bids best first and asks best first
+    ///
+    /// `None` before the first snapshot and while awaiting a resync.
+    pub fn book(&self, token_id: &str) -> Option<OrderBook> {
+        self.books.get(token_id).map(TrackedBook::to_book)
+    }
+
+    /// Sequence number of the last update applied to a token's book
+    pub fn sequence(&self, token_id: &str) -> Option<u64> {
+        self.books.get(token_id).map(|b| b.sequence)
+    }
+
+    /// Tokens whose books were dropped and need a fresh snapshot
+    pub fn pending_resyncs(&self) -> Vec<String> {
+        self.resync.iter().cloned().collect()
+    }
+
+    /// Level prices received outside [0, 1] and clamped
+    pub fn price_anomalies(&self) -> u64 {
+        self.price_anomalies
+    }
+
+    /// Replace a token's book, clearing any pending resync
+    pub fn apply_snapshot(&mut self, token_id: &str, book: &OrderBook, sequence: u64) {
+        let anomalies = &mut self.price_anomalies;
+        let mut levels = |levels: &[OrderBookLevel]| {
+            levels
+                .iter()
+                .filter(|l| l.size > Decimal::ZERO)
+                .map(|l| (checked_price(token_id, l.price, anomalies), l.size))
+                .collect()
+        };
+        self.books.insert(
+            token_id.to_string(),
+            TrackedBook {
+                bids: levels(&book.bids),
+                asks: levels(&book.asks),
+                sequence,
+            },
+        );
+        self.resync.remove(token_id);
+    }
+
+    /// Apply deltas numbered `sequence`, which must directly follow the
+    /// book's last update
+    ///
+    /// A snapshot with sequence 0 has no numbering to continue, so the
+    /// first delta after it is taken as is.
+    pub fn apply_delta(&mut self, token_id: &str, sequence: u64, deltas: &[LevelDelta]) -> BookStatus {
+        let Some(book) = self.books.get_mut(token_id) else {
+            return self.request_resync(token_id);
+        };
+        if sequence <= book.sequence {
+            return BookStatus::Stale;
+        }
+        if book.sequence != 0 && sequence != book.sequence + 1 {
+            tracing::warn!(
+                "Order book gap for {}: expected sequence {}, got {}",
+                token_id,
+                book.sequence + 1,
+                sequence
+            );
+            return self.request_resync(token_id);
+        }
+        for delta in deltas {
+            let price = checked_price(token_id, delta.price, &mut self.price_anomalies);
+            book.apply(&LevelDelta { price, ..*delta });
+        }
+        book.sequence = sequence;
+        BookStatus::Applied
+    }
+
+    /// Apply a market channel event, returning the status per token touched
+    ///
+    /// The websocket has no sequence numbers: a `price_change` carries the
+    /// best bid and ask after the change, and a book that disagrees with
+    /// them has missed an update.
+    pub fn apply_event(&mut self, event: &MarketEvent) -> Vec<(String, BookStatus)> {
+        match event {
+            MarketEvent::Book(msg) => {
+                let book = OrderBook {
+                    bids: parse_levels(&msg.bids),
+                    asks: parse_levels(&msg.asks),
+                };
+                let sequence = msg.timestamp.parse().unwrap_or(0);
+                self.apply_snapshot(&msg.asset_id, &book, sequence);
+                vec![(msg.asset_id.clone(), BookStatus::Applied)]
+            }
+            MarketEvent::PriceChange(msg) => {
+                let timestamp = msg.timestamp.parse().unwrap_or(0);
+                let mut statuses: Vec<(String, BookStatus)> = Vec::new();
+                for change in &msg.price_changes {
+                    // A resync drops the book, so later changes for the token stay unapplied
+                    let status = self.apply_change(&change.asset_id, timestamp, change);
+                    match statuses.iter_mut().find(|(t, _)| *t == change.asset_id) {
+                        Some(entry) => entry.1 = status,
+                        None => statuses.push((change.asset_id.clone(), status)),
+                    }
+                }
+                statuses
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Fetch fresh snapshots for every token awaiting a resync
+    ///
+    /// Tokens whose fetch fails stay pending for the next call.
+    pub async fn resync<C: ClobClientTrait + ?Sized>(&mut self, clob: &C) -> Result<usize> {
+        let mut resynced = 0;
+        for token_id in self.pending_resyncs() {
+            let book = clob.get_order_book(&token_id).await?;
+            self.apply_snapshot(&token_id, &book, 0);
+            resynced += 1;
+        }
+        Ok(resynced)
+    }
+
+    fn apply_change(
+        &mut self,
+        token_id: &str,
+        timestamp: u64,
+        change: &PriceChange,
+    ) -> BookStatus {
+        let (Ok(price), Ok(size)) = (Decimal::from_str(&change.price), Decimal::from_str(&change.size)) else {
+            return BookStatus::Stale;
+        };
+        let side = if change.side.eq_ignore_ascii_case("BUY") { Side::Buy } else { Side::Sell };
+        let Some(book) = self.books.get_mut(token_id) else {
+            return self.request_resync(token_id);
+        };
+        if timestamp < book.sequence {
+            return BookStatus::Stale;
+        }
+        let price = checked_price(token_id, price, &mut self.price_anomalies);
+        book.apply(&LevelDelta { side, price, size });
+        book.sequence = timestamp;
+
+        let reported = |s: &str| (!s.is_empty()).then(|| Decimal::from_str(s).ok()).flatten();
+        let consistent = reported(&change.best_bid).is_none_or(|b| Some(b) == book.best_bid())
+            && reported(&change.best_ask).is_none_or(|a| Some(a) == book.best_ask());
+        if !consistent {
+            tracing::warn!(
+                "Order book for {} disagrees with reported best {}/{}",
+                token_id,
+                change.best_bid,
+                change.best_ask
+            );
+            return self.request_resync(token_id);
+        }
+        BookStatus::Applied
+    }
+
+    fn request_resync(&mut self, token_id: &str) -> BookStatus {
+        self.books.remove(token_id);
+        self.resync.insert(token_id.to_string());
+        BookStatus::ResyncNeeded
+    }
+}
+
+/// Clamp a level price into [0, 1], counting it if it was outside
+fn checked_price(token_id: &str, price: Decimal, anomalies: &mut u64) -> Decimal {
+    let checked = CheckedPrice::new(price);
+    if checked.is_anomaly() {
+        tracing::warn!("Order book level for {} priced {} outside [0, 1], clamped", token_id, price);
+        *anomalies += 1;
+    }
+    checked.price
+}
+
+fn parse_levels(levels: &[OrderLevel]) -> Vec<OrderBookLevel> {
+    levels
+        .iter()
+        .filter_map(|l| {
+            Some(OrderBookLevel {
+                price: l.price_decimal()?,
+                size: l.size_decimal()?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mock::MockClobClient;
+    use crate::client::polymarket_ws::{BookMessage, PriceChangeMessage};
+    use rust_decimal_macros::dec;
+
+    fn level(price: Decimal, size: Decimal) -> OrderBookLevel {
+        OrderBookLevel { price, size }
+    }
+
+    fn delta(side: Side, price: Decimal, size: Decimal) -> LevelDelta {
+        LevelDelta { side, price, size }
+    }
+
+    fn snapshot() -> OrderBook {
+        OrderBook {
+            bids: vec![level(dec!(0.48), dec!(100)), level(dec!(0.47), dec!(200))],
+            asks: vec![level(dec!(0.52), dec!(150))],
+        }
+    }
+
+    fn prices(levels: &[OrderBookLevel]) -> Vec<(Decimal, Decimal)> {
+        levels.iter().map(|l| (l.price, l.size)).collect()
+    }
+
+    #[test]
+    fn test_snapshot_then_deltas() {
+        let mut books = OrderBookMaintainer::new();
+        assert!(books.book("t1").is_none());
+        books.apply_snapshot("t1", &snapshot(), 10);
+
+        // Add a better bid, modify the ask, remove the 0.47 bid
+        let status = books.apply_delta(
+            "t1",
+            11,
+            &[
+                delta(Side::Buy, dec!(0.49), dec!(50)),
+                delta(Side::Sell, dec!(0.52), dec!(75)),
+                delta(Side::Buy, dec!(0.47), Decimal::ZERO),
+            ],
+        );
+        assert_eq!(status, BookStatus::Applied);
+
+        let book = books.book("t1").unwrap();
+        assert_eq!(prices(&book.bids), vec![(dec!(0.49), dec!(50)), (dec!(0.48), dec!(100))]);
+        assert_eq!(prices(&book.asks), vec![(dec!(0.52), dec!(75))]);
+        assert_eq!(books.sequence("t1"), Some(11));
+
+        // A replayed delta changes nothing
+        assert_eq!(books.apply_delta("t1", 11, &[delta(Side::Sell, dec!(0.52), Decimal::ZERO)]), BookStatus::Stale);
+        assert_eq!(books.book("t1").unwrap().asks.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_gap_triggers_resync() {
+        let mut books = OrderBookMaintainer::new();
+        books.apply_snapshot("t1", &snapshot(), 10);
+
+        let status = books.apply_delta("t1", 13, &[delta(Side::Buy, dec!(0.49), dec!(50))]);
+        assert_eq!(status, BookStatus::ResyncNeeded);
+        assert!(books.book("t1").is_none());
+        assert_eq!(books.pending_resyncs(), vec!["t1".to_string()]);
+        // Deltas are refused until a snapshot arrives
+        assert_eq!(books.apply_delta("t1", 14, &[]), BookStatus::ResyncNeeded);
+
+        let mut clob = MockClobClient::new();
+        clob.set_order_book("t1", snapshot());
+        assert_eq!(books.resync(&clob).await.unwrap(), 1);
+        assert!(books.pending_resyncs().is_empty());
+        assert_eq!(books.book("t1").unwrap().best_bid(), Some(dec!(0.48)));
+        assert_eq!(books.apply_delta("t1", 20, &[]), BookStatus::Applied);
+    }
+
+    fn price_change(price: &str, size: &str, side: &str, best_bid: &str, best_ask: &str) -> MarketEvent {
+        MarketEvent::PriceChange(PriceChangeMessage {
+            event_type: "price_change".to_string(),
+            market: "m1".to_string(),
+            price_changes: vec![PriceChange {
+                asset_id: "t1".to_string(),
+                price: price.to_string(),
+                size: size.to_string(),
+                side: side.to_string(),
+                hash: String::new(),
+                best_bid: best_bid.to_string(),
+                best_ask: best_ask.to_string(),
+            }],
+            timestamp: "2000".to_string(),
+        })
+    }
+
+    #[test]
+    fn test_websocket_events_checked_against_reported_best_prices() {
+        let mut books = OrderBookMaintainer::new();
+        let snapshot = MarketEvent::Book(BookMessage {
+            event_type: "book".to_string(),
+            asset_id: "t1".to_string(),
+            market: "m1".to_string(),
+            bids: vec![OrderLevel { price: "0.48".to_string(), size: "100".to_string() }],
+            asks: vec![OrderLevel { price: "0.52".to_string(), size: "150".to_string() }],
+            timestamp: "1000".to_string(),
+            hash: String::new(),
+        });
+        books.apply_event(&snapshot);
+
+        let statuses = books.apply_event(&price_change("0.50", "30", "BUY", "0.50", "0.52"));
+        assert_eq!(statuses, vec![("t1".to_string(), BookStatus::Applied)]);
+        assert_eq!(books.book("t1").unwrap().best_bid(), Some(dec!(0.50)));
+
+        // The exchange says the ask is 0.51, a level we never saw
+        let statuses = books.apply_event(&price_change("0.50", "0", "BUY", "0.48", "0.51"));
+        assert_eq!(statuses, vec![("t1".to_string(), BookStatus::ResyncNeeded)]);
+        assert_eq!(books.pending_resyncs(), vec!["t1".to_string()]);
+
+        books.apply_event(&snapshot);
+        assert!(books.pending_resyncs().is_empty());
+    }
+
+    #[test]
+    fn test_out_of_range_prices_flagged_and_clamped() {
+        let mut books = OrderBookMaintainer::new();
+        let book = OrderBook {
+            bids: vec![level(dec!(0.48), dec!(100)), level(dec!(-0.1), dec!(5))],
+            asks: vec![level(dec!(0.52), dec!(150))],
+        };
+        books.apply_snapshot("t1", &book, 0);
+        assert_eq!(books.price_anomalies(), 1);
+        assert_eq!(books.book("t1").unwrap().bids.last().unwrap().price, Decimal::ZERO);
+
+        let statuses = books.apply_event(&price_change("1.2", "10", "SELL", "", ""));
+        assert_eq!(statuses, vec![("t1".to_string(), BookStatus::Applied)]);
+        assert_eq!(books.price_anomalies(), 2);
+        assert_eq!(books.book("t1").unwrap().asks.last().unwrap().price, Decimal::ONE);
+
+        books.apply_delta("t1", 3000, &[delta(Side::Buy, dec!(0.49), dec!(10))]);
+        assert_eq!(books.price_anomalies(), 2);
+    }
+}
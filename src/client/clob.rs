@@ -3,12 +3,16 @@
 //! Handles order placement, cancellation, and account queries.
 //! Implements Polymarket's Level 1 (EIP-712) and Level 2 (HMAC) authentication.
 
+use crate::client::allowance::{self, Allowance, AllowanceResponse};
 use crate::client::auth::{ApiCredentials, PolySigner};
-use crate::error::{BotError, Result};
-use crate::types::{Order, OrderStatus, OrderType, Side};
+use crate::error::{BotError, Result, ResultExt};
+use crate::types::{OpenOrder, Order, OrderStatus, OrderType, Side};
+pub use crate::types::{OrderBook, OrderBookLevel};
+use futures_util::future::{BoxFuture, FutureExt, Shared};
 use reqwest::Client;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use hmac::{Hmac, Mac};
@@ -50,8 +54,57 @@ pub struct ClobClient {
     #[allow(dead_code)]
     funder: Option<String>,
     credentials: Arc<RwLock<Option<ApiCredentials>>>,
+    /// Submissions by client order id, kept so a retry joins the original
+    submissions: Arc<std::sync::Mutex<HashMap<String, Submission>>>,
+    /// Polygon RPC for approval transactions, set when approvals are opted in
+    approval_rpc: Option<String>,
 }
 
+/// Outcome of a keyed submission, shared by every caller using the key
+type Submission = Shared<BoxFuture<'static, std::result::Result<OrderStatus, SubmitError>>>;
+
+/// Cloneable form of a failed submission
+#[derive(Debug, Clone)]
+enum SubmitError {
+    /// Never reached the CLOB (connection failure or rate limit); safe to
+    /// resend
+    Unsent { message: String, retry_after_secs: Option<u64> },
+    /// May have reached the CLOB (timeout, 5xx); the order has to be looked
+    /// up before it is sent again
+    Ambiguous(String),
+    Failed(String),
+}
+
+impl From<BotError> for SubmitError {
+    fn from(e: BotError) -> Self {
+        match e.root() {
+            BotError::RateLimited { retry_after_secs } => Self::Unsent {
+                message: e.to_string(),
+                retry_after_secs: Some(*retry_after_secs),
+            },
+            _ if e.never_reached_server() => Self::Unsent { message: e.to_string(), retry_after_secs: None },
+            _ if e.is_retryable() => Self::Ambiguous(e.to_string()),
+            _ => Self::Failed(e.to_string()),
+        }
+    }
+}
+
+impl From<SubmitError> for BotError {
+    fn from(e: SubmitError) -> Self {
+        match e {
+            SubmitError::Unsent { retry_after_secs: Some(retry_after_secs), .. } => {
+                BotError::RateLimited { retry_after_secs }
+            }
+            SubmitError::Unsent { message, retry_after_secs: None } => BotError::Connect(message),
+            SubmitError::Ambiguous(msg) => BotError::Timeout(msg),
+            SubmitError::Failed(msg) => BotError::Execution(msg),
+        }
+    }
+}
+
+/// Keyed submissions remembered before finished ones are pruned
+const MAX_TRACKED_SUBMISSIONS: usize = 1000;
+
 #[derive(Debug, Serialize)]
 struct CreateOrderRequest {
     token_id: String,
@@ -61,6 +114,8 @@ struct CreateOrderRequest {
     order_type: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     expiration: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_order_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -68,6 +123,30 @@ struct OrderResponse {
     #[serde(rename = "orderID")]
     order_id: String,
     status: String,
+    /// Shares matched on arrival
+    #[serde(rename = "sizeMatched", default)]
+    size_matched: Option<String>,
+}
+
+impl OrderResponse {
+    /// Status of `order` as the CLOB acknowledged it
+    ///
+    /// A "matched" order without a `sizeMatched` filled completely.
+    fn order_status(self, order: &Order) -> OrderStatus {
+        let filled = match self.size_matched.as_deref().and_then(|s| s.parse::<Decimal>().ok()) {
+            Some(matched) => matched.clamp(Decimal::ZERO, order.size),
+            None if self.status.eq_ignore_ascii_case("matched") => order.size,
+            None => Decimal::ZERO,
+        };
+        OrderStatus {
+            order_id: self.order_id,
+            status: self.status,
+            filled_size: filled,
+            remaining_size: order.size - filled,
+            avg_price: None,
+            fee: Decimal::ZERO,
+        }
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -75,6 +154,36 @@ struct BalanceResponse {
     balance: String,
 }
 
+/// Order status from a CLOB order object
+fn order_status_from_json(o: &serde_json::Value) -> OrderStatus {
+    let decimal = |field: &str| o[field].as_str().and_then(|s| s.parse().ok());
+    OrderStatus {
+        order_id: o["orderID"].as_str().unwrap_or_default().to_string(),
+        status: o["status"].as_str().unwrap_or_default().to_string(),
+        filled_size: decimal("sizeFilled").unwrap_or(Decimal::ZERO),
+        remaining_size: decimal("sizeRemaining").unwrap_or(Decimal::ZERO),
+        avg_price: decimal("avgPrice"),
+        fee: Decimal::ZERO,
+    }
+}
+
+/// Resting order from a CLOB order object, `None` if a field is missing
+fn open_order_from_json(o: &serde_json::Value) -> Option<OpenOrder> {
+    let decimal = |field: &str| o[field].as_str().and_then(|s| s.parse::<Decimal>().ok());
+    let side = match o["side"].as_str()? {
+        s if s.eq_ignore_ascii_case("buy") => Side::Buy,
+        s if s.eq_ignore_ascii_case("sell") => Side::Sell,
+        _ => return None,
+    };
+    Some(OpenOrder {
+        order_id: o["id"].as_str().or_else(|| o["orderID"].as_str())?.to_string(),
+        token_id: o["asset_id"].as_str()?.to_string(),
+        side,
+        price: decimal("price")?,
+        remaining_size: decimal("original_size")? - decimal("size_matched").unwrap_or(Decimal::ZERO),
+    })
+}
+
 impl ClobClient {
     /// Create a new CLOB client
     pub fn new(base_url: &str, signer: PolySigner, funder: Option<String>) -> Result<Self> {
@@ -88,9 +197,22 @@ impl ClobClient {
             signer,
             funder,
             credentials: Arc::new(RwLock::new(None)),
+            submissions: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            approval_rpc: None,
         })
     }
 
+    /// Address of the signing key
+    pub fn signer_address(&self) -> ethers::types::Address {
+        self.signer.address()
+    }
+
+    /// Allow `ensure_allowance` to send USDC approvals through `rpc_url`
+    pub fn with_auto_approve(mut self, rpc_url: &str) -> Self {
+        self.approval_rpc = Some(rpc_url.to_string());
+        self
+    }
+
     /// Initialize API credentials by creating or deriving them from the server
     /// This uses Level 1 (EIP-712) authentication
     pub async fn initialize(&self) -> Result<()> {
@@ -132,7 +254,7 @@ impl ClobClient {
             req = req.header(&key, &value);
         }
         
-        let resp: serde_json::Value = req.send().await?.json().await?;
+        let resp: serde_json::Value = req.send().await?.error_for_status()?.json().await?;
         
         Ok(ApiCredentials {
             api_key: resp["apiKey"]
@@ -161,7 +283,7 @@ impl ClobClient {
             req = req.header(&key, &value);
         }
         
-        let resp: serde_json::Value = req.send().await?.json().await?;
+        let resp: serde_json::Value = req.send().await?.error_for_status()?.json().await?;
         
         Ok(ApiCredentials {
             api_key: resp["apiKey"]
@@ -229,15 +351,138 @@ impl ClobClient {
             req = req.header(&key, &value);
         }
         
-        let resp: BalanceResponse = req.send().await?.json().await?;
+        let resp: BalanceResponse = req.send().await?.error_for_status()?.json().await?;
 
         resp.balance
             .parse()
             .map_err(|e| BotError::Api(format!("Invalid balance: {}", e)))
     }
 
+    /// USDC the exchange contracts may spend for this account
+    pub async fn check_allowance(&self) -> Result<Decimal> {
+        let creds = self.credentials.read().await;
+        let creds = creds
+            .as_ref()
+            .ok_or_else(|| BotError::Auth("Not authenticated".into()))?;
+
+        let path = "/balance-allowance";
+        let url = format!("{}{}?asset_type=COLLATERAL", self.base_url, path);
+        let headers = self.create_l2_headers(creds, "GET", path, None)?;
+
+        let mut req = self.http.get(&url);
+        for (key, value) in headers {
+            req = req.header(&key, &value);
+        }
+
+        let resp: AllowanceResponse = req.send().await?.error_for_status()?.json().await?;
+        resp.usdc()
+    }
+
     /// Place a limit order
+    ///
+    /// Orders with a `client_order_id` are idempotent: the request runs in
+    /// its own task, so it completes even if the caller gives up waiting,
+    /// and a later call with the same key gets that result instead of
+    /// posting again. A submission that never reached the CLOB or was
+    /// rejected releases its key. After a timeout or 5xx the key is kept,
+    /// and the next call with it looks the order up by key and only posts
+    /// again if the CLOB does not have it.
     pub async fn place_order(&self, order: &Order) -> Result<OrderStatus> {
+        let Some(key) = order.client_order_id.clone() else {
+            return self.post_order(order).await.context_operation("place_order");
+        };
+
+        let submission = {
+            let mut submissions = self.submissions.lock().unwrap();
+            if submissions.len() >= MAX_TRACKED_SUBMISSIONS {
+                submissions.retain(|_, s| matches!(s.peek(), None | Some(Err(SubmitError::Ambiguous(_)))));
+            }
+            let pending = submissions
+                .get(&key)
+                .filter(|s| !matches!(s.peek(), Some(Err(SubmitError::Ambiguous(_)))))
+                .cloned();
+            match pending {
+                Some(existing) => existing,
+                None => {
+                    let lookup = submissions.contains_key(&key);
+                    let client = self.clone();
+                    let order = order.clone();
+                    let task_key = key.clone();
+                    let task = tokio::spawn(async move {
+                        if lookup {
+                            match client.find_order(&task_key).await {
+                                Ok(Some(status)) => return Ok(status),
+                                Ok(None) => {}
+                                Err(e) => {
+                                    return Err(SubmitError::Ambiguous(format!(
+                                        "Could not look up order {}: {}",
+                                        task_key, e
+                                    )))
+                                }
+                            }
+                        }
+                        client.post_order(&order).await.map_err(SubmitError::from)
+                    });
+                    let submission = async move {
+                        task.await
+                            .unwrap_or_else(|e| Err(SubmitError::Failed(format!("Order task failed: {}", e))))
+                    }
+                    .boxed()
+                    .shared();
+                    submissions.insert(key.clone(), submission.clone());
+                    submission
+                }
+            }
+        };
+
+        match submission.await {
+            Ok(status) => Ok(status),
+            Err(e) => {
+                if !matches!(e, SubmitError::Ambiguous(_)) {
+                    self.submissions.lock().unwrap().remove(&key);
+                }
+                Err(BotError::from(e).context_order(key).context_operation("place_order"))
+            }
+        }
+    }
+
+    /// The order submitted with `client_order_id`, whatever its status
+    pub async fn find_order(&self, client_order_id: &str) -> Result<Option<OrderStatus>> {
+        async {
+            let creds = self.credentials.read().await;
+            let creds = creds
+                .as_ref()
+                .ok_or_else(|| BotError::Auth("Not authenticated".into()))?;
+
+            let path = "/orders";
+            let url = format!("{}{}", self.base_url, path);
+            let headers = self.create_l2_headers(creds, "GET", path, None)?;
+
+            let mut req = self.http.get(&url);
+            for (key, value) in headers {
+                req = req.header(&key, &value);
+            }
+
+            let resp: Vec<serde_json::Value> = req
+                .query(&[("client_order_id", client_order_id)])
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+
+            Ok(resp
+                .iter()
+                .find(|o| o["clientOrderId"].as_str() == Some(client_order_id))
+                .map(order_status_from_json))
+        }
+        .await
+        .context_order(client_order_id)
+        .context_operation("find_order")
+    }
+
+    /// Post an order to the CLOB
+    async fn post_order(&self, order: &Order) -> Result<OrderStatus> {
         let creds = self.credentials.read().await;
         let creds = creds
             .as_ref()
@@ -257,6 +502,7 @@ impl ClobClient {
                 OrderType::GTD => "GTD".to_string(),
             },
             expiration: None,
+            client_order_id: order.client_order_id.clone(),
         };
 
         let path = "/order";
@@ -275,73 +521,112 @@ impl ClobClient {
             .body(body)
             .send()
             .await?
+            .error_for_status()?
             .json()
             .await?;
 
-        Ok(OrderStatus {
-            order_id: resp.order_id,
-            status: resp.status,
-            filled_size: Decimal::ZERO,
-            remaining_size: order.size,
-            avg_price: None,
-        })
+        Ok(resp.order_status(order))
     }
 
     /// Cancel an order
     pub async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        async {
+            let creds = self.credentials.read().await;
+            let creds = creds
+                .as_ref()
+                .ok_or_else(|| BotError::Auth("Not authenticated".into()))?;
+
+            let path = format!("/order/{}", order_id);
+            let url = format!("{}{}", self.base_url, path);
+            let headers = self.create_l2_headers(creds, "DELETE", &path, None)?;
+        
+            let mut req = self.http.delete(&url);
+            for (key, value) in headers {
+                req = req.header(&key, &value);
+            }
+        
+            req.send().await?.error_for_status()?;
+            Ok(())
+        }
+        .await
+        .context_order(order_id)
+        .context_operation("cancel_order")
+    }
+
+    /// Cancel every open order, returning how many were cancelled
+    pub async fn cancel_all(&self) -> Result<usize> {
         let creds = self.credentials.read().await;
         let creds = creds
             .as_ref()
             .ok_or_else(|| BotError::Auth("Not authenticated".into()))?;
 
-        let path = format!("/order/{}", order_id);
+        let path = "/cancel-all";
         let url = format!("{}{}", self.base_url, path);
-        let headers = self.create_l2_headers(creds, "DELETE", &path, None)?;
+        let headers = self.create_l2_headers(creds, "DELETE", path, None)?;
         
         let mut req = self.http.delete(&url);
         for (key, value) in headers {
             req = req.header(&key, &value);
         }
         
-        req.send().await?;
-        Ok(())
+        let resp: serde_json::Value = req.send().await?.error_for_status()?.json().await?;
+        Ok(resp["canceled"].as_array().map(|a| a.len()).unwrap_or(0))
     }
 
     /// Get order status
     pub async fn get_order(&self, order_id: &str) -> Result<OrderStatus> {
+        async {
+            let creds = self.credentials.read().await;
+            let creds = creds
+                .as_ref()
+                .ok_or_else(|| BotError::Auth("Not authenticated".into()))?;
+
+            let path = format!("/order/{}", order_id);
+            let url = format!("{}{}", self.base_url, path);
+            let headers = self.create_l2_headers(creds, "GET", &path, None)?;
+        
+            let mut req = self.http.get(&url);
+            for (key, value) in headers {
+                req = req.header(&key, &value);
+            }
+        
+            let resp: serde_json::Value = req.send().await?.error_for_status()?.json().await?;
+            Ok(order_status_from_json(&resp))
+        }
+        .await
+        .context_order(order_id)
+        .context_operation("get_order")
+    }
+
+    /// Get all open orders
+    pub async fn get_open_orders(&self) -> Result<Vec<OrderStatus>> {
         let creds = self.credentials.read().await;
         let creds = creds
             .as_ref()
             .ok_or_else(|| BotError::Auth("Not authenticated".into()))?;
 
-        let path = format!("/order/{}", order_id);
+        let path = "/orders";
         let url = format!("{}{}", self.base_url, path);
-        let headers = self.create_l2_headers(creds, "GET", &path, None)?;
+        let headers = self.create_l2_headers(creds, "GET", path, None)?;
         
         let mut req = self.http.get(&url);
         for (key, value) in headers {
             req = req.header(&key, &value);
         }
         
-        let resp: serde_json::Value = req.send().await?.json().await?;
+        let resp: Vec<serde_json::Value> = req
+            .query(&[("status", "open")])
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
 
-        Ok(OrderStatus {
-            order_id: resp["orderID"].as_str().unwrap_or_default().to_string(),
-            status: resp["status"].as_str().unwrap_or_default().to_string(),
-            filled_size: resp["sizeFilled"]
-                .as_str()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(Decimal::ZERO),
-            remaining_size: resp["sizeRemaining"]
-                .as_str()
-                .and_then(|s| s.parse().ok())
-                .unwrap_or(Decimal::ZERO),
-            avg_price: resp["avgPrice"].as_str().and_then(|s| s.parse().ok()),
-        })
+        Ok(resp.iter().map(order_status_from_json).collect())
     }
 
-    /// Get all open orders
-    pub async fn get_open_orders(&self) -> Result<Vec<OrderStatus>> {
+    /// Open orders resting on one token, whoever placed them
+    pub async fn get_open_orders_for(&self, token_id: &str) -> Result<Vec<OpenOrder>> {
         let creds = self.credentials.read().await;
         let creds = creds
             .as_ref()
@@ -350,34 +635,24 @@ impl ClobClient {
         let path = "/orders";
         let url = format!("{}{}", self.base_url, path);
         let headers = self.create_l2_headers(creds, "GET", path, None)?;
-        
+
         let mut req = self.http.get(&url);
         for (key, value) in headers {
             req = req.header(&key, &value);
         }
-        
+
         let resp: Vec<serde_json::Value> = req
-            .query(&[("status", "open")])
+            .query(&[("status", "open"), ("asset_id", token_id)])
             .send()
             .await?
+            .error_for_status()?
             .json()
             .await?;
 
         Ok(resp
-            .into_iter()
-            .map(|o| OrderStatus {
-                order_id: o["orderID"].as_str().unwrap_or_default().to_string(),
-                status: o["status"].as_str().unwrap_or_default().to_string(),
-                filled_size: o["sizeFilled"]
-                    .as_str()
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or(Decimal::ZERO),
-                remaining_size: o["sizeRemaining"]
-                    .as_str()
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or(Decimal::ZERO),
-                avg_price: o["avgPrice"].as_str().and_then(|s| s.parse().ok()),
-            })
+            .iter()
+            .filter_map(open_order_from_json)
+            .filter(|o| o.token_id == token_id)
             .collect())
     }
 
@@ -390,6 +665,7 @@ impl ClobClient {
             .query(&[("token_id", token_id)])
             .send()
             .await?
+            .error_for_status()?
             .json()
             .await?;
 
@@ -408,6 +684,7 @@ impl ClobClient {
             .query(&[("token_id", token_id)])
             .send()
             .await?
+            .error_for_status()?
             .json()
             .await?;
 
@@ -448,7 +725,7 @@ impl ClobClient {
             req = req.header(&key, &value);
         }
         
-        let resp: Vec<serde_json::Value> = req.send().await?.json().await?;
+        let resp: Vec<serde_json::Value> = req.send().await?.error_for_status()?.json().await?;
 
         Ok(resp
             .into_iter()
@@ -483,38 +760,63 @@ impl ClobClient {
     }
 }
 
-/// Order book data
-#[derive(Debug, Clone)]
-pub struct OrderBook {
-    pub bids: Vec<OrderBookLevel>,
-    pub asks: Vec<OrderBookLevel>,
-}
-
-/// Single level in order book
-#[derive(Debug, Clone)]
-pub struct OrderBookLevel {
-    pub price: Decimal,
-    pub size: Decimal,
-}
+#[async_trait::async_trait]
+impl Allowance for ClobClient {
+    async fn check_allowance(&self) -> Result<Decimal> {
+        ClobClient::check_allowance(self).await
+    }
 
-impl OrderBook {
-    /// Get best bid price
-    pub fn best_bid(&self) -> Option<Decimal> {
-        self.bids.first().map(|l| l.price)
+    async fn approve(&self) -> Result<Vec<String>> {
+        let rpc_url = self
+            .approval_rpc
+            .as_deref()
+            .ok_or_else(|| BotError::Config("USDC approvals are not enabled".into()))?;
+        allowance::send_approvals(rpc_url, self.signer.wallet().clone()).await
     }
 
-    /// Get best ask price
-    pub fn best_ask(&self) -> Option<Decimal> {
-        self.asks.first().map(|l| l.price)
+    fn auto_approve(&self) -> bool {
+        self.approval_rpc.is_some()
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_order_response_reports_matched_size() {
+        let order = Order {
+            token_id: "t1".to_string(),
+            side: Side::Buy,
+            price: dec!(0.50),
+            size: dec!(100),
+            order_type: OrderType::GTC,
+            client_order_id: None,
+        };
+        let response = |json: serde_json::Value| serde_json::from_value::<OrderResponse>(json).unwrap();
+
+        let partial = response(serde_json::json!({"orderID": "o1", "status": "live", "sizeMatched": "40"}));
+        let status = partial.order_status(&order);
+        assert_eq!((status.filled_size, status.remaining_size), (dec!(40), dec!(60)));
+
+        let matched = response(serde_json::json!({"orderID": "o2", "status": "matched"}));
+        assert_eq!(matched.order_status(&order).remaining_size, Decimal::ZERO);
 
-    /// Get spread
-    pub fn spread(&self) -> Option<Decimal> {
-        Some(self.best_ask()? - self.best_bid()?)
+        let resting = response(serde_json::json!({"orderID": "o3", "status": "live"}));
+        assert_eq!(resting.order_status(&order).filled_size, Decimal::ZERO);
     }
 
-    /// Get midpoint
-    pub fn midpoint(&self) -> Option<Decimal> {
-        Some((self.best_bid()? + self.best_ask()?) / Decimal::TWO)
+    #[test]
+    fn test_open_order_from_json() {
+        let order = open_order_from_json(&serde_json::json!({
+            "id": "o1", "asset_id": "t1", "side": "BUY", "price": "0.50",
+            "original_size": "100", "size_matched": "40"
+        }))
+        .unwrap();
+        assert_eq!((order.side, order.price, order.remaining_size), (Side::Buy, dec!(0.50), dec!(60)));
+
+        // Without a side the order can't be checked, so it is dropped
+        assert!(open_order_from_json(&serde_json::json!({"id": "o2", "asset_id": "t1"})).is_none());
     }
 }
@@ -3,11 +3,13 @@
 //! Fetches market information, prices, and metadata.
 
 use crate::error::{BotError, Result};
-use crate::types::{Market, Outcome};
+use crate::types::{CheckedPrice, Market, Outcome};
 use chrono::{DateTime, Utc};
 use reqwest::Client;
 use rust_decimal::Decimal;
 use serde::Deserialize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
 use tracing::debug;
 
 /// Known crypto series IDs
@@ -34,6 +36,8 @@ pub const CRYPTO_SEARCH_QUERIES: &[&str] = &[
 pub struct GammaClient {
     http: Client,
     base_url: String,
+    /// Outcome prices received outside [0, 1], shared between clones
+    price_anomalies: Arc<AtomicU64>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -52,6 +56,34 @@ struct GammaMarket {
     outcome_prices: Option<String>, // JSON string "[0.55, 0.45]"
     #[serde(rename = "clobTokenIds")]
     clob_token_ids: Option<String>, // JSON string
+    #[serde(default)]
+    category: Option<String>,
+    /// Missing or null for many markets
+    #[serde(default)]
+    tags: Option<Vec<GammaTag>>,
+    /// A number, though some endpoints send it as a string
+    #[serde(rename = "orderPriceMinTickSize", default)]
+    tick_size: Option<serde_json::Value>,
+    #[serde(rename = "orderMinSize", default)]
+    min_size: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct GammaTag {
+    #[serde(default)]
+    label: Option<String>,
+    #[serde(default)]
+    slug: Option<String>,
+}
+
+impl GammaTag {
+    /// Display label, falling back to the slug
+    fn name(self) -> Option<String> {
+        self.label
+            .or(self.slug)
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty())
+    }
 }
 
 impl GammaClient {
@@ -64,9 +96,15 @@ impl GammaClient {
         Ok(Self {
             http,
             base_url: base_url.trim_end_matches('/').to_string(),
+            price_anomalies: Arc::new(AtomicU64::new(0)),
         })
     }
 
+    /// Outcome prices received outside [0, 1] and clamped
+    pub fn price_anomaly_count(&self) -> u64 {
+        self.price_anomalies.load(Ordering::Relaxed)
+    }
+
     /// Get all active markets
     pub async fn get_markets(&self) -> Result<Vec<Market>> {
         let url = format!("{}/markets", self.base_url);
@@ -137,6 +175,25 @@ impl GammaClient {
         Ok(resp.into_iter().filter_map(|m| self.parse_market(m)).collect())
     }
 
+    /// Most recently ended closed markets, resolved or not
+    pub async fn get_closed_markets(&self, limit: usize) -> Result<Vec<Market>> {
+        let url = format!("{}/markets", self.base_url);
+        let resp: Vec<GammaMarket> = self
+            .http
+            .get(&url)
+            .query(&[
+                ("closed", "true"),
+                ("_sort", "end_date:desc"),
+                ("_limit", &limit.to_string()),
+            ])
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        Ok(resp.into_iter().filter_map(|m| self.parse_market(m)).collect())
+    }
+
     fn parse_market(&self, gm: GammaMarket) -> Option<Market> {
         // Parse outcome prices - API returns string array like ["0.55", "0.45"]
         let prices: Vec<f64> = gm
@@ -176,13 +233,27 @@ impl GammaClient {
         let outcomes: Vec<Outcome> = outcome_names
             .into_iter()
             .enumerate()
-            .map(|(i, name)| Outcome {
-                token_id: token_ids.get(i).cloned().unwrap_or_default(),
-                outcome: name,
-                price: prices
+            .map(|(i, name)| {
+                let raw = prices
                     .get(i)
                     .map(|&p| Decimal::try_from(p).unwrap_or(Decimal::ZERO))
-                    .unwrap_or(Decimal::ZERO),
+                    .unwrap_or(Decimal::ZERO);
+                let checked = CheckedPrice::new(raw);
+                if checked.is_anomaly() {
+                    tracing::warn!(
+                        "Market {} outcome {} priced {} outside [0, 1], clamped to {}",
+                        gm.id,
+                        name,
+                        raw,
+                        checked.price
+                    );
+                    self.price_anomalies.fetch_add(1, Ordering::Relaxed);
+                }
+                Outcome {
+                    token_id: token_ids.get(i).cloned().unwrap_or_default(),
+                    outcome: name,
+                    price: checked.price,
+                }
             })
             .collect();
 
@@ -204,6 +275,10 @@ impl GammaClient {
             outcomes,
             active: gm.active,
             closed: gm.closed,
+            category: gm.category,
+            tags: gm.tags.unwrap_or_default().into_iter().filter_map(GammaTag::name).collect(),
+            tick_size: gm.tick_size.as_ref().and_then(parse_decimal).filter(|t| *t > Decimal::ZERO),
+            min_size: gm.min_size.as_ref().and_then(parse_decimal),
         })
     }
 
@@ -475,3 +550,87 @@ struct EventResponse {
     title: String,
     markets: Option<Vec<GammaMarket>>,
 }
+
+/// A decimal sent as either a JSON number or a string
+fn parse_decimal(value: &serde_json::Value) -> Option<Decimal> {
+    match value {
+        serde_json::Value::Number(n) => n.to_string().parse().ok(),
+        serde_json::Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> Market {
+        let client = GammaClient::new("http://localhost").unwrap();
+        client.parse_market(serde_json::from_str(json).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_parse_market_tags_and_category() {
+        let market = parse(
+            r#"{
+                "id": "123",
+                "question": "Will BTC hit $100k by June?",
+                "active": true,
+                "closed": false,
+                "outcomes": "[\"Yes\", \"No\"]",
+                "outcomePrices": "[\"0.4\", \"0.6\"]",
+                "clobTokenIds": "[\"yes-token\", \"no-token\"]",
+                "category": "Crypto",
+                "tags": [
+                    {"id": "1", "label": "Crypto", "slug": "crypto"},
+                    {"id": "2", "slug": "bitcoin"},
+                    {"id": "3", "label": " "}
+                ]
+            }"#,
+        );
+
+        assert_eq!(market.category.as_deref(), Some("Crypto"));
+        assert_eq!(market.tags, vec!["Crypto", "bitcoin"]);
+        assert_eq!(market.yes_price(), Some(rust_decimal_macros::dec!(0.4)));
+    }
+
+    #[test]
+    fn test_parse_market_order_increments() {
+        let base = r#""id": "1", "question": "Q?", "active": true, "closed": false"#;
+        let market = parse(&format!(r#"{{{}, "orderPriceMinTickSize": 0.001, "orderMinSize": 5}}"#, base));
+        assert_eq!(market.tick_size, Some(rust_decimal_macros::dec!(0.001)));
+        assert_eq!(market.min_size, Some(rust_decimal_macros::dec!(5)));
+
+        let market = parse(&format!(r#"{{{}, "orderPriceMinTickSize": "0.01"}}"#, base));
+        assert_eq!(market.tick_size, Some(rust_decimal_macros::dec!(0.01)));
+        assert_eq!(market.min_size, None);
+    }
+
+    #[test]
+    fn test_parse_market_clamps_out_of_range_prices() {
+        let client = GammaClient::new("http://localhost").unwrap();
+        let json = r#"{"id": "1", "question": "Q?", "active": true, "closed": false,
+            "outcomes": "[\"Yes\", \"No\"]", "outcomePrices": "[\"1.2\", \"-0.1\"]"}"#;
+        let market = client.parse_market(serde_json::from_str(json).unwrap()).unwrap();
+
+        assert_eq!(market.yes_price(), Some(Decimal::ONE));
+        assert_eq!(market.no_price(), Some(Decimal::ZERO));
+        assert_eq!(client.price_anomaly_count(), 2);
+        assert_eq!(client.clone().price_anomaly_count(), 2);
+
+        let json = r#"{"id": "2", "question": "Q?", "active": true, "closed": false,
+            "outcomePrices": "[\"0.4\", \"0.6\"]"}"#;
+        client.parse_market(serde_json::from_str(json).unwrap()).unwrap();
+        assert_eq!(client.price_anomaly_count(), 2);
+    }
+
+    #[test]
+    fn test_parse_market_without_tags() {
+        let base = r#""id": "1", "question": "Q?", "active": true, "closed": false"#;
+        for json in [format!("{{{}}}", base), format!(r#"{{{}, "tags": null, "category": null}}"#, base)] {
+            let market = parse(&json);
+            assert!(market.tags.is_empty());
+            assert!(market.category.is_none());
+        }
+    }
+}
@@ -5,9 +5,9 @@
 //! - Integration tests with controlled responses
 //! - Dry run simulations
 
-use crate::client::{OrderBook, OrderBookLevel};
+use crate::client::{ClobClient, GammaClient, OrderBook, OrderBookLevel};
 use crate::error::Result;
-use crate::types::{Market, Order, OrderStatus, Position, Side, Outcome};
+use crate::types::{Market, OpenOrder, Order, OrderStatus, Position, Side, Outcome};
 use async_trait::async_trait;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
@@ -24,6 +24,43 @@ pub trait ClobClientTrait: Send + Sync {
     async fn cancel_order(&self, order_id: &str) -> Result<()>;
     async fn get_open_orders(&self) -> Result<Vec<OrderStatus>>;
     async fn get_positions(&self) -> Result<Vec<Position>>;
+
+    /// The order submitted with `client_order_id`, whatever its status
+    ///
+    /// `Ok(None)` means the exchange never got it, so it is safe to submit
+    /// again. Clients that can't look orders up by key return an error.
+    async fn find_order(&self, client_order_id: &str) -> Result<Option<OrderStatus>> {
+        Err(crate::error::BotError::Execution(format!(
+            "Cannot look up order {} by client order id",
+            client_order_id
+        )))
+    }
+
+    /// Open orders on `token_id`, with their side and price
+    ///
+    /// Unlike `get_open_orders` this covers orders placed by any process.
+    /// Clients that can't report an order's token return an error.
+    async fn get_open_orders_for(&self, token_id: &str) -> Result<Vec<OpenOrder>> {
+        Err(crate::error::BotError::Execution(format!(
+            "Cannot list open orders for {}",
+            token_id
+        )))
+    }
+
+    /// Learn which market a market's tokens belong to
+    ///
+    /// Orders carry only a token id; clients that report positions by
+    /// market use this to fill in the market id.
+    fn register_market(&self, _market: &Market) {}
+
+    /// Cancel every open order, returning how many were cancelled
+    async fn cancel_all(&self) -> Result<usize> {
+        let open = self.get_open_orders().await?;
+        for order in &open {
+            self.cancel_order(&order.order_id).await?;
+        }
+        Ok(open.len())
+    }
 }
 
 /// Trait for Gamma operations (allows mocking)
@@ -32,6 +69,108 @@ pub trait GammaClientTrait: Send + Sync {
     async fn get_top_markets(&self, limit: usize) -> Result<Vec<Market>>;
     async fn get_market(&self, market_id: &str) -> Result<Market>;
     async fn get_crypto_markets(&self) -> Result<Vec<Market>>;
+    async fn get_closed_markets(&self, limit: usize) -> Result<Vec<Market>>;
+}
+
+#[async_trait]
+impl ClobClientTrait for ClobClient {
+    async fn get_balance(&self) -> Result<Decimal> {
+        ClobClient::get_balance(self).await
+    }
+
+    async fn get_order_book(&self, token_id: &str) -> Result<OrderBook> {
+        ClobClient::get_order_book(self, token_id).await
+    }
+
+    async fn place_order(&self, order: &Order) -> Result<OrderStatus> {
+        ClobClient::place_order(self, order).await
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        ClobClient::cancel_order(self, order_id).await
+    }
+
+    async fn get_open_orders(&self) -> Result<Vec<OrderStatus>> {
+        ClobClient::get_open_orders(self).await
+    }
+
+    async fn get_positions(&self) -> Result<Vec<Position>> {
+        ClobClient::get_positions(self).await
+    }
+
+    async fn get_open_orders_for(&self, token_id: &str) -> Result<Vec<OpenOrder>> {
+        ClobClient::get_open_orders_for(self, token_id).await
+    }
+
+    async fn find_order(&self, client_order_id: &str) -> Result<Option<OrderStatus>> {
+        ClobClient::find_order(self, client_order_id).await
+    }
+
+    async fn cancel_all(&self) -> Result<usize> {
+        ClobClient::cancel_all(self).await
+    }
+}
+
+#[async_trait]
+impl<T: ClobClientTrait + ?Sized> ClobClientTrait for Arc<T> {
+    async fn get_balance(&self) -> Result<Decimal> {
+        (**self).get_balance().await
+    }
+
+    async fn get_order_book(&self, token_id: &str) -> Result<OrderBook> {
+        (**self).get_order_book(token_id).await
+    }
+
+    async fn place_order(&self, order: &Order) -> Result<OrderStatus> {
+        (**self).place_order(order).await
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        (**self).cancel_order(order_id).await
+    }
+
+    async fn get_open_orders(&self) -> Result<Vec<OrderStatus>> {
+        (**self).get_open_orders().await
+    }
+
+    async fn get_positions(&self) -> Result<Vec<Position>> {
+        (**self).get_positions().await
+    }
+
+    async fn get_open_orders_for(&self, token_id: &str) -> Result<Vec<OpenOrder>> {
+        (**self).get_open_orders_for(token_id).await
+    }
+
+    async fn find_order(&self, client_order_id: &str) -> Result<Option<OrderStatus>> {
+        (**self).find_order(client_order_id).await
+    }
+
+    fn register_market(&self, market: &Market) {
+        (**self).register_market(market)
+    }
+
+    async fn cancel_all(&self) -> Result<usize> {
+        (**self).cancel_all().await
+    }
+}
+
+#[async_trait]
+impl GammaClientTrait for GammaClient {
+    async fn get_top_markets(&self, limit: usize) -> Result<Vec<Market>> {
+        GammaClient::get_top_markets(self, limit).await
+    }
+
+    async fn get_market(&self, market_id: &str) -> Result<Market> {
+        GammaClient::get_market(self, market_id).await
+    }
+
+    async fn get_crypto_markets(&self) -> Result<Vec<Market>> {
+        GammaClient::get_crypto_markets(self).await
+    }
+
+    async fn get_closed_markets(&self, limit: usize) -> Result<Vec<Market>> {
+        GammaClient::get_closed_markets(self, limit).await
+    }
 }
 
 /// Mock state for tracking simulated trades
@@ -53,6 +192,22 @@ pub struct MockOrder {
     pub size: Decimal,
     pub status: String,
     pub timestamp: chrono::DateTime<Utc>,
+    pub client_order_id: Option<String>,
+}
+
+impl MockOrder {
+    /// Current status of the order as the exchange would report it
+    pub fn order_status(&self) -> OrderStatus {
+        let filled = if self.status == "FILLED" { self.size } else { Decimal::ZERO };
+        OrderStatus {
+            order_id: self.order_id.clone(),
+            status: self.status.clone(),
+            filled_size: filled,
+            remaining_size: if self.status == "CANCELLED" { Decimal::ZERO } else { self.size - filled },
+            avg_price: (filled > Decimal::ZERO).then_some(self.price),
+            fee: Decimal::ZERO,
+        }
+    }
 }
 
 impl Default for MockState {
@@ -174,6 +329,14 @@ impl ClobClientTrait for MockClobClient {
         }
 
         let mut state = self.state.write().unwrap();
+
+        // A resubmission with a known idempotency key is the same order
+        if let Some(key) = &order.client_order_id {
+            if let Some(existing) = state.orders.iter().find(|o| o.client_order_id.as_ref() == Some(key)) {
+                return Ok(existing.order_status());
+            }
+        }
+
         let order_id = format!("mock_order_{}", state.trades_executed + 1);
         
         // Update balance (simulate immediate fill)
@@ -199,6 +362,7 @@ impl ClobClientTrait for MockClobClient {
             size: order.size,
             status: "FILLED".to_string(),
             timestamp: Utc::now(),
+            client_order_id: order.client_order_id.clone(),
         });
         
         state.trades_executed += 1;
@@ -210,6 +374,7 @@ impl ClobClientTrait for MockClobClient {
             filled_size: order.size,
             remaining_size: Decimal::ZERO,
             avg_price: Some(order.price),
+            fee: Decimal::ZERO,
         })
     }
 
@@ -234,6 +399,7 @@ impl ClobClientTrait for MockClobClient {
                 filled_size: Decimal::ZERO,
                 remaining_size: o.size,
                 avg_price: None,
+                fee: Decimal::ZERO,
             })
             .collect())
     }
@@ -243,6 +409,33 @@ impl ClobClientTrait for MockClobClient {
         let state = self.state.read().unwrap();
         Ok(state.positions.values().cloned().collect())
     }
+
+    async fn get_open_orders_for(&self, token_id: &str) -> Result<Vec<OpenOrder>> {
+        self.simulate_latency().await;
+        let state = self.state.read().unwrap();
+        Ok(state
+            .orders
+            .iter()
+            .filter(|o| o.token_id == token_id && (o.status == "OPEN" || o.status == "PARTIAL"))
+            .map(|o| OpenOrder {
+                order_id: o.order_id.clone(),
+                token_id: o.token_id.clone(),
+                side: o.side,
+                price: o.price,
+                remaining_size: o.size,
+            })
+            .collect())
+    }
+
+    async fn find_order(&self, client_order_id: &str) -> Result<Option<OrderStatus>> {
+        self.simulate_latency().await;
+        let state = self.state.read().unwrap();
+        Ok(state
+            .orders
+            .iter()
+            .find(|o| o.client_order_id.as_deref() == Some(client_order_id))
+            .map(MockOrder::order_status))
+    }
 }
 
 /// Mock Gamma client for market data
@@ -292,6 +485,10 @@ impl MockGammaClient {
                 ],
                 active: true,
                 closed: false,
+                category: None,
+                tags: Vec::new(),
+                tick_size: None,
+                min_size: None,
             },
             Market {
                 id: "eth_5k_2026".to_string(),
@@ -314,6 +511,10 @@ impl MockGammaClient {
                 ],
                 active: true,
                 closed: false,
+                category: None,
+                tags: Vec::new(),
+                tick_size: None,
+                min_size: None,
             },
             Market {
                 id: "btc_up_24h".to_string(),
@@ -336,6 +537,10 @@ impl MockGammaClient {
                 ],
                 active: true,
                 closed: false,
+                category: None,
+                tags: Vec::new(),
+                tick_size: None,
+                min_size: None,
             },
         ]
     }
@@ -380,6 +585,13 @@ impl GammaClientTrait for MockGammaClient {
             .cloned()
             .collect())
     }
+
+    async fn get_closed_markets(&self, limit: usize) -> Result<Vec<Market>> {
+        if self.simulate_failures {
+            return Err(crate::error::BotError::Api("Mock failure".into()));
+        }
+        Ok(self.markets.iter().filter(|m| m.closed).take(limit).cloned().collect())
+    }
 }
 
 /// Builder for creating test scenarios
@@ -453,6 +665,7 @@ mod tests {
             price: dec!(0.55),
             size: dec!(100),
             order_type: crate::types::OrderType::GTC,
+            client_order_id: None,
         };
         
         let result = client.place_order(&order).await.unwrap();
@@ -473,6 +686,7 @@ mod tests {
             price: dec!(0.55),
             size: dec!(100), // Would cost $55
             order_type: crate::types::OrderType::GTC,
+            client_order_id: None,
         };
         
         assert!(client.place_order(&order).await.is_err());
@@ -514,6 +728,7 @@ mod tests {
                 price: dec!(0.10),
                 size: dec!(10),
                 order_type: crate::types::OrderType::GTC,
+                client_order_id: None,
             };
             client.place_order(&order).await.unwrap();
         }
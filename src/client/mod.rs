@@ -5,20 +5,28 @@
 //! - Gamma API: Market data and information
 //! - WebSocket: Real-time price updates via polymarket_ws
 //! - Mock: Test clients for offline testing
+//! - Paper: Simulated fills against live books for dry-run
+//! - Book maintainer: Local order books rebuilt from websocket snapshots and deltas
 
+pub mod allowance;
+pub mod book_maintainer;
 pub mod clob;
 pub mod gamma;
 mod auth;
 pub mod polymarket_ws;
 pub mod mock;
+pub mod paper;
 pub mod orderbook_stream;
 #[cfg(test)]
 mod tests;
 
+pub use allowance::Allowance;
+pub use book_maintainer::{BookStatus, LevelDelta, OrderBookMaintainer};
 pub use clob::{ClobClient, OrderBook, OrderBookLevel};
 pub use gamma::{GammaClient, CRYPTO_SERIES, CRYPTO_SEARCH_QUERIES};
-pub use auth::PolySigner;
+pub use auth::{chain_name, parse_address, PolySigner, SUPPORTED_CHAINS};
 pub use orderbook_stream::{OrderBookManager, OrderBookUpdate, LocalOrderBook};
+pub use paper::{PaperClobClient, PaperState, PaperOrder};
 
 // WebSocket implementation based on official Polymarket docs
 pub use polymarket_ws::{
@@ -29,6 +37,7 @@ pub use polymarket_ws::{
 };
 
 use crate::config::PolymarketConfig;
+use ethers::utils::to_checksum;
 use crate::error::Result;
 
 /// Unified Polymarket client
@@ -41,13 +50,28 @@ pub struct PolymarketClient {
 impl PolymarketClient {
     /// Create a new Polymarket client
     pub async fn new(config: PolymarketConfig) -> Result<Self> {
-        let signer = PolySigner::from_private_key(&config.private_key, config.chain_id)?;
-        let clob = ClobClient::new(&config.clob_url, signer, config.funder_address.clone())?;
+        let signer = PolySigner::from_private_key(config.private_key.expose(), config.chain_id)?;
+        if let Some(funder) = &config.funder_address {
+            parse_address(funder)?;
+        }
+        let mut clob = ClobClient::new(&config.clob_url, signer, config.funder_address.clone())?;
+        if config.auto_approve {
+            clob = clob.with_auto_approve(&config.rpc_url);
+        }
         let gamma = GammaClient::new(&config.gamma_url)?;
 
         Ok(Self { clob, gamma, config })
     }
 
+    /// Address holding the account's funds: the funder for proxy wallets,
+    /// otherwise the signing key's own address
+    pub fn trading_address(&self) -> String {
+        self.config
+            .funder_address
+            .clone()
+            .unwrap_or_else(|| to_checksum(&self.clob.signer_address(), None))
+    }
+
     /// Create a WebSocket stream for real-time market data
     ///
     /// Uses the official Polymarket WebSocket protocol with:
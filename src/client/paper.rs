@@ -0,0 +1,518 @@
+//! Paper trading CLOB client
+//!
+//! Implements `ClobClientTrait` without touching the exchange: orders are
+//! matched against live order books (REST, or the websocket stream when one
+//! is attached), a fee is charged on every fill, and balance and positions
+//! are tracked in memory. `--dry-run` runs the normal execution path on top
+//! of this client.
+//!
+//! The part of a GTC order that cannot fill immediately rests as an open
+//! order until cancelled; it is never matched later.
+
+use crate::client::mock::ClobClientTrait;
+use crate::client::{ClobClient, OrderBook, OrderBookLevel, OrderBookManager};
+use crate::config::{FeeModel, PaperConfig};
+use crate::error::{BotError, Result};
+use crate::types::{Market, OpenOrder, Order, OrderStatus, OrderType, Position, Side};
+use async_trait::async_trait;
+use chrono::Utc;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+/// Levels read from the websocket book per side
+const STREAM_BOOK_DEPTH: usize = 50;
+
+/// Virtual account state
+#[derive(Debug, Clone, Default)]
+pub struct PaperState {
+    pub balance: Decimal,
+    /// Open positions by token id
+    pub positions: HashMap<String, Position>,
+    pub orders: Vec<PaperOrder>,
+    /// Total fees charged
+    pub fees_paid: Decimal,
+    /// Realized P&L from closing fills, before fees
+    pub realized_pnl: Decimal,
+}
+
+/// An order placed with the paper client
+#[derive(Debug, Clone)]
+pub struct PaperOrder {
+    pub order_id: String,
+    pub token_id: String,
+    pub side: Side,
+    pub price: Decimal,
+    pub size: Decimal,
+    pub filled_size: Decimal,
+    pub avg_price: Option<Decimal>,
+    pub status: String,
+    pub timestamp: chrono::DateTime<Utc>,
+}
+
+/// CLOB client that simulates fills against real order books
+///
+/// `B` supplies order books; the default uses the public `/book` endpoint of
+/// the live CLOB, which needs no authentication.
+pub struct PaperClobClient<B = ClobClient> {
+    books: B,
+    stream: Option<Arc<OrderBookManager>>,
+    fees: FeeModel,
+    state: Arc<RwLock<PaperState>>,
+    /// Market id by token id, from `register_market`
+    markets: RwLock<HashMap<String, String>>,
+}
+
+impl<B: ClobClientTrait> PaperClobClient<B> {
+    pub fn new(books: B, starting_balance: Decimal) -> Self {
+        Self {
+            books,
+            stream: None,
+            fees: FeeModel::default(),
+            state: Arc::new(RwLock::new(PaperState {
+                balance: starting_balance,
+                ..Default::default()
+            })),
+            markets: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub fn from_config(books: B, config: &PaperConfig) -> Self {
+        Self::new(books, config.starting_balance).with_fee_rate(config.fee_rate)
+    }
+
+    /// Flat fee charged on each fill's notional (0.01 = 1%)
+    pub fn with_fee_rate(self, fee_rate: Decimal) -> Self {
+        self.with_fee_model(FeeModel::flat_rate(fee_rate))
+    }
+
+    /// Fee schedule for fills; paper fills always cross the book, so they
+    /// pay the taker rate
+    pub fn with_fee_model(mut self, fees: FeeModel) -> Self {
+        self.fees = fees;
+        self
+    }
+
+    /// Prefer books from a websocket stream, falling back to `B` for tokens
+    /// the stream has no data for
+    pub fn with_order_book_stream(mut self, stream: Arc<OrderBookManager>) -> Self {
+        self.stream = Some(stream);
+        self
+    }
+
+    pub fn state(&self) -> Arc<RwLock<PaperState>> {
+        self.state.clone()
+    }
+
+    async fn stream_book(&self, token_id: &str) -> Option<OrderBook> {
+        let stream = self.stream.as_ref()?;
+        let to_levels = |entries: Vec<crate::client::orderbook_stream::OrderBookEntry>| {
+            entries
+                .into_iter()
+                .map(|e| OrderBookLevel { price: e.price, size: e.size })
+                .collect::<Vec<_>>()
+        };
+        let bids = to_levels(stream.get_bids(token_id, STREAM_BOOK_DEPTH).await);
+        let asks = to_levels(stream.get_asks(token_id, STREAM_BOOK_DEPTH).await);
+        if bids.is_empty() && asks.is_empty() {
+            return None;
+        }
+        Some(OrderBook { bids, asks })
+    }
+}
+
+/// Walk the opposite side of the book up to the limit price
+///
+/// Returns the filled size and its notional value.
+fn match_order(book: &OrderBook, order: &Order) -> (Decimal, Decimal) {
+    let mut levels: Vec<&OrderBookLevel> = match order.side {
+        Side::Buy => book.asks.iter().filter(|l| l.price <= order.price).collect(),
+        Side::Sell => book.bids.iter().filter(|l| l.price >= order.price).collect(),
+    };
+    match order.side {
+        Side::Buy => levels.sort_by_key(|l| l.price),
+        Side::Sell => levels.sort_by_key(|l| std::cmp::Reverse(l.price)),
+    }
+
+    let mut remaining = order.size;
+    let mut notional = Decimal::ZERO;
+    for level in levels {
+        if remaining <= Decimal::ZERO {
+            break;
+        }
+        let take = remaining.min(level.size);
+        notional += take * level.price;
+        remaining -= take;
+    }
+    (order.size - remaining, notional)
+}
+
+#[async_trait]
+impl<B: ClobClientTrait> ClobClientTrait for PaperClobClient<B> {
+    async fn get_balance(&self) -> Result<Decimal> {
+        Ok(self.state.read().unwrap().balance)
+    }
+
+    async fn get_order_book(&self, token_id: &str) -> Result<OrderBook> {
+        if let Some(book) = self.stream_book(token_id).await {
+            return Ok(book);
+        }
+        self.books.get_order_book(token_id).await
+    }
+
+    async fn place_order(&self, order: &Order) -> Result<OrderStatus> {
+        if order.size <= Decimal::ZERO {
+            return Err(BotError::Execution("Order size must be positive".into()));
+        }
+
+        let book = self.get_order_book(&order.token_id).await?;
+        let (filled, notional) = match_order(&book, order);
+        if order.order_type == OrderType::FOK && filled < order.size {
+            return Err(BotError::Execution(format!(
+                "FOK order for {} could not be fully filled ({} of {})",
+                order.token_id, filled, order.size
+            )));
+        }
+        let fee = notional * self.fees.rate(false);
+        let avg_price = (filled > Decimal::ZERO).then(|| notional / filled);
+
+        let market_id = self.markets.read().unwrap().get(&order.token_id).cloned().unwrap_or_default();
+        let mut state = self.state.write().unwrap();
+        match order.side {
+            Side::Buy => {
+                if notional + fee > state.balance {
+                    return Err(BotError::Execution(format!(
+                        "Insufficient paper balance: need ${:.2}, have ${:.2}",
+                        notional + fee,
+                        state.balance
+                    )));
+                }
+            }
+            Side::Sell => {
+                let held = state.positions.get(&order.token_id).map(|p| p.size).unwrap_or_default();
+                if filled > held {
+                    return Err(BotError::Execution(format!(
+                        "Insufficient paper position in {}: selling {} of {}",
+                        order.token_id, filled, held
+                    )));
+                }
+            }
+        }
+
+        if let Some(price) = avg_price {
+            match order.side {
+                Side::Buy => {
+                    state.balance -= notional + fee;
+                    let position = state
+                        .positions
+                        .entry(order.token_id.clone())
+                        .or_insert_with(|| Position {
+                            token_id: order.token_id.clone(),
+                            // Empty for tokens of unregistered markets
+                            market_id,
+                            side: Side::Buy,
+                            size: Decimal::ZERO,
+                            avg_entry_price: Decimal::ZERO,
+                            current_price: price,
+                            unrealized_pnl: Decimal::ZERO,
+                        });
+                    // Fees are tracked on the account, not in the entry price
+                    position.apply_fill(Side::Buy, filled, price, Decimal::ZERO);
+                }
+                Side::Sell => {
+                    state.balance += notional - fee;
+                    let position = state.positions.get_mut(&order.token_id).expect("checked above");
+                    let realized = position.apply_fill(Side::Sell, filled, price, Decimal::ZERO);
+                    if position.size.is_zero() {
+                        state.positions.remove(&order.token_id);
+                    }
+                    state.realized_pnl += realized;
+                }
+            }
+            state.fees_paid += fee;
+        }
+
+        let status = if filled == order.size {
+            "FILLED"
+        } else if filled > Decimal::ZERO {
+            "PARTIAL"
+        } else {
+            "OPEN"
+        };
+        let order_id = format!("paper_{}", state.orders.len() + 1);
+        state.orders.push(PaperOrder {
+            order_id: order_id.clone(),
+            token_id: order.token_id.clone(),
+            side: order.side,
+            price: order.price,
+            size: order.size,
+            filled_size: filled,
+            avg_price,
+            status: status.to_string(),
+            timestamp: Utc::now(),
+        });
+
+        tracing::debug!(
+            "Paper {} {}: filled {} of {} @ {:?}, fee ${:.4}",
+            status, order.token_id, filled, order.size, avg_price, fee
+        );
+
+        Ok(OrderStatus {
+            order_id,
+            status: status.to_string(),
+            filled_size: filled,
+            remaining_size: order.size - filled,
+            avg_price,
+            fee,
+        })
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        let mut state = self.state.write().unwrap();
+        if let Some(order) = state
+            .orders
+            .iter_mut()
+            .find(|o| o.order_id == order_id && (o.status == "OPEN" || o.status == "PARTIAL"))
+        {
+            order.status = "CANCELLED".to_string();
+        }
+        Ok(())
+    }
+
+    async fn get_open_orders(&self) -> Result<Vec<OrderStatus>> {
+        let state = self.state.read().unwrap();
+        Ok(state
+            .orders
+            .iter()
+            .filter(|o| o.status == "OPEN" || o.status == "PARTIAL")
+            .map(|o| OrderStatus {
+                order_id: o.order_id.clone(),
+                status: o.status.clone(),
+                filled_size: o.filled_size,
+                remaining_size: o.size - o.filled_size,
+                avg_price: o.avg_price,
+                fee: Decimal::ZERO,
+            })
+            .collect())
+    }
+
+    async fn get_positions(&self) -> Result<Vec<Position>> {
+        Ok(self.state.read().unwrap().positions.values().cloned().collect())
+    }
+
+    async fn get_open_orders_for(&self, token_id: &str) -> Result<Vec<OpenOrder>> {
+        let state = self.state.read().unwrap();
+        Ok(state
+            .orders
+            .iter()
+            .filter(|o| o.token_id == token_id && (o.status == "OPEN" || o.status == "PARTIAL"))
+            .map(|o| OpenOrder {
+                order_id: o.order_id.clone(),
+                token_id: o.token_id.clone(),
+                side: o.side,
+                price: o.price,
+                remaining_size: o.size - o.filled_size,
+            })
+            .collect())
+    }
+
+    fn register_market(&self, market: &Market) {
+        let mut markets = self.markets.write().unwrap();
+        for outcome in &market.outcomes {
+            markets.insert(outcome.token_id.clone(), market.id.clone());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mock::MockClobClient;
+    use crate::config::RiskConfig;
+    use crate::executor::Executor;
+    use crate::types::Signal;
+    use rust_decimal_macros::dec;
+
+    fn book() -> OrderBook {
+        OrderBook {
+            bids: vec![
+                OrderBookLevel { price: dec!(0.48), size: dec!(100) },
+                OrderBookLevel { price: dec!(0.47), size: dec!(100) },
+            ],
+            asks: vec![
+                OrderBookLevel { price: dec!(0.50), size: dec!(100) },
+                OrderBookLevel { price: dec!(0.51), size: dec!(100) },
+            ],
+        }
+    }
+
+    fn paper(balance: Decimal, fee_rate: Decimal) -> PaperClobClient<MockClobClient> {
+        let mut books = MockClobClient::new();
+        books.set_order_book("token", book());
+        PaperClobClient::new(books, balance).with_fee_rate(fee_rate)
+    }
+
+    fn order(side: Side, price: Decimal, size: Decimal) -> Order {
+        Order {
+            token_id: "token".to_string(),
+            side,
+            price,
+            size,
+            order_type: OrderType::GTC,
+            client_order_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_buy_walks_book_and_charges_fee() {
+        let client = paper(dec!(1000), dec!(0.01));
+
+        let status = client.place_order(&order(Side::Buy, dec!(0.51), dec!(150))).await.unwrap();
+
+        // 100 @ 0.50 + 50 @ 0.51 = 75.50
+        assert_eq!(status.status, "FILLED");
+        assert_eq!(status.filled_size, dec!(150));
+        assert_eq!(status.fee, dec!(0.755));
+        assert_eq!(client.get_balance().await.unwrap(), dec!(1000) - dec!(75.50) - dec!(0.755));
+
+        let positions = client.get_positions().await.unwrap();
+        assert_eq!(positions.len(), 1);
+        assert_eq!(positions[0].size, dec!(150));
+        assert_eq!(positions[0].avg_entry_price, dec!(75.50) / dec!(150));
+        assert_eq!(client.state().read().unwrap().fees_paid, dec!(0.755));
+    }
+
+    #[tokio::test]
+    async fn test_sell_closes_position() {
+        let client = paper(dec!(1000), dec!(0.01));
+        client.place_order(&order(Side::Buy, dec!(0.50), dec!(100))).await.unwrap();
+
+        let status = client.place_order(&order(Side::Sell, dec!(0.48), dec!(100))).await.unwrap();
+
+        assert_eq!(status.avg_price, Some(dec!(0.48)));
+        // 1000 - 50.00 - 0.50 + 48.00 - 0.48
+        assert_eq!(client.get_balance().await.unwrap(), dec!(997.02));
+        assert!(client.get_positions().await.unwrap().is_empty());
+        assert_eq!(client.state().read().unwrap().realized_pnl, dec!(-2.00));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_what_the_account_cannot_cover() {
+        let client = paper(dec!(10), Decimal::ZERO);
+
+        assert!(client.place_order(&order(Side::Buy, dec!(0.50), dec!(100))).await.is_err());
+        assert!(client.place_order(&order(Side::Sell, dec!(0.48), dec!(10))).await.is_err());
+        assert_eq!(client.get_balance().await.unwrap(), dec!(10));
+        assert!(client.state().read().unwrap().orders.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unfilled_remainder_rests_until_cancelled() {
+        let client = paper(dec!(1000), Decimal::ZERO);
+
+        let status = client.place_order(&order(Side::Buy, dec!(0.50), dec!(150))).await.unwrap();
+        assert_eq!(status.status, "PARTIAL");
+        assert_eq!(status.remaining_size, dec!(50));
+        assert_eq!(client.get_open_orders().await.unwrap().len(), 1);
+
+        assert_eq!(client.cancel_all().await.unwrap(), 1);
+        assert!(client.get_open_orders().await.unwrap().is_empty());
+        // The filled part stays
+        assert_eq!(client.get_positions().await.unwrap()[0].size, dec!(100));
+
+        let mut fok = order(Side::Buy, dec!(0.50), dec!(150));
+        fok.order_type = OrderType::FOK;
+        assert!(client.place_order(&fok).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_executor_records_paper_fill() {
+        let executor = Executor::new(paper(dec!(1000), dec!(0.02)), RiskConfig::default());
+        let signal = Signal {
+            market_id: "market".to_string(),
+            token_id: "token".to_string(),
+            side: Side::Buy,
+            model_probability: dec!(0.60),
+            market_probability: dec!(0.50),
+            edge: dec!(0.10),
+            net_edge: dec!(0.10),
+            confidence: dec!(0.80),
+            suggested_size: dec!(0.04),
+            timestamp: Utc::now(),
+            max_staleness: None,
+        };
+
+        let trade = executor.execute(&signal, dec!(1000)).await.unwrap().unwrap();
+
+        // $40 at 0.50 = 80 shares, all at the best ask
+        assert_eq!(trade.market_id, "market");
+        assert_eq!(trade.size, dec!(80));
+        assert_eq!(trade.price, dec!(0.50));
+        assert_eq!(trade.fee, dec!(0.80));
+        assert_eq!(executor.clob.get_balance().await.unwrap(), dec!(959.20));
+    }
+
+    #[tokio::test]
+    async fn test_paper_position_settles() {
+        use crate::client::mock::MockGammaClient;
+        use crate::monitor::SettlementMonitor;
+        use crate::types::Outcome;
+
+        let market = |yes_price: Decimal, closed: bool| Market {
+            id: "market".to_string(),
+            question: "Will it happen?".to_string(),
+            description: None,
+            end_date: None,
+            volume: dec!(1000),
+            liquidity: dec!(1000),
+            outcomes: vec![
+                Outcome { token_id: "token".to_string(), outcome: "Yes".to_string(), price: yes_price },
+                Outcome { token_id: "other".to_string(), outcome: "No".to_string(), price: Decimal::ONE - yes_price },
+            ],
+            active: !closed,
+            closed,
+            category: None,
+            tags: Vec::new(),
+            tick_size: None,
+            min_size: None,
+        };
+        let executor = Executor::new(paper(dec!(1000), Decimal::ZERO), RiskConfig::default());
+        executor.register_market(&market(dec!(0.50), false)).await;
+        let signal = Signal {
+            market_id: "market".to_string(),
+            token_id: "token".to_string(),
+            side: Side::Buy,
+            model_probability: dec!(0.60),
+            market_probability: dec!(0.50),
+            edge: dec!(0.10),
+            net_edge: dec!(0.10),
+            confidence: dec!(0.80),
+            suggested_size: dec!(0.04),
+            timestamp: Utc::now(),
+            max_staleness: None,
+        };
+        executor.execute(&signal, dec!(1000)).await.unwrap().unwrap();
+
+        let positions = executor.clob.get_positions().await.unwrap();
+        assert_eq!(positions[0].market_id, "market");
+
+        // Resolved Yes: 80 shares bought at 0.50 pay $1 each
+        let monitor = SettlementMonitor::new(MockGammaClient::new().with_markets(vec![market(dec!(1), true)]));
+        let settlements = monitor.check(&positions).await;
+        assert_eq!(settlements.len(), 1);
+        assert_eq!(settlements[0].market_id, "market");
+        assert_eq!(settlements[0].realized_pnl, dec!(40));
+    }
+
+    #[tokio::test]
+    async fn test_fills_pay_the_taker_rate() {
+        let fees = FeeModel::MakerTaker { maker_bps: dec!(0), taker_bps: dec!(20) };
+        let client = paper(dec!(1000), Decimal::ZERO).with_fee_model(fees);
+
+        let status = client.place_order(&order(Side::Buy, dec!(0.50), dec!(100))).await.unwrap();
+
+        // 20 bps of $50
+        assert_eq!(status.fee, dec!(0.10));
+        assert_eq!(client.get_balance().await.unwrap(), dec!(949.90));
+    }
+}
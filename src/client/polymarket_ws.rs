@@ -9,16 +9,17 @@
 //! - Subscribe: {"assets_ids": [...], "type": "market"}
 //! - Keep-alive: Send "PING" every 10 seconds
 
+use super::book_maintainer::OrderBookMaintainer;
 use crate::error::{BotError, Result};
 use futures_util::{SinkExt, StreamExt};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::str::FromStr;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{broadcast, mpsc};
+use tokio::sync::{broadcast, mpsc, RwLock};
 use tokio::time::{interval, timeout};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 use tracing::{debug, error, info, warn};
@@ -49,6 +50,9 @@ const READ_TIMEOUT_SECS: u64 = 60;
 const INITIAL_RECONNECT_DELAY_MS: u64 = 1000;
 const MAX_RECONNECT_DELAY_MS: u64 = 60000;
 
+/// Fraction of each reconnect delay that is randomized
+const RECONNECT_JITTER: f64 = 0.2;
+
 // =============================================================================
 // Message Types (from official docs)
 // =============================================================================
@@ -151,6 +155,8 @@ pub enum MarketEvent {
     TickSizeChange(TickSizeChangeMessage),
     /// Unknown event type
     Unknown(String),
+    /// Reconnects exhausted; no further events will arrive
+    Fatal(String),
 }
 
 // =============================================================================
@@ -203,6 +209,12 @@ pub struct WsConfig {
     pub read_timeout_secs: u64,
     /// Max reconnect attempts (0 = unlimited)
     pub max_reconnect_attempts: u32,
+    /// First reconnect delay in milliseconds, doubled on each failure
+    pub initial_reconnect_delay_ms: u64,
+    /// Cap on the reconnect delay in milliseconds
+    pub max_reconnect_delay_ms: u64,
+    /// Fraction of each delay randomized so clients don't retry in lockstep
+    pub reconnect_jitter: f64,
     /// Channel buffer size
     pub channel_buffer_size: usize,
 }
@@ -215,18 +227,40 @@ impl Default for WsConfig {
             connect_timeout_secs: CONNECT_TIMEOUT_SECS,
             read_timeout_secs: READ_TIMEOUT_SECS,
             max_reconnect_attempts: 0,
+            initial_reconnect_delay_ms: INITIAL_RECONNECT_DELAY_MS,
+            max_reconnect_delay_ms: MAX_RECONNECT_DELAY_MS,
+            reconnect_jitter: RECONNECT_JITTER,
             channel_buffer_size: 10000,
         }
     }
 }
 
+impl WsConfig {
+    /// Delay before retry `attempt` (0-based) without jitter: capped
+    /// exponential backoff
+    pub fn base_reconnect_delay(&self, attempt: u32) -> Duration {
+        let delay = self
+            .initial_reconnect_delay_ms
+            .saturating_mul(1u64.checked_shl(attempt).unwrap_or(u64::MAX));
+        Duration::from_millis(delay.min(self.max_reconnect_delay_ms))
+    }
+
+    /// Delay before retry `attempt`, shortened by up to `reconnect_jitter`
+    /// of itself at random
+    pub fn reconnect_delay(&self, attempt: u32) -> Duration {
+        let jitter = self.reconnect_jitter.clamp(0.0, 1.0) * rand::random::<f64>();
+        self.base_reconnect_delay(attempt).mul_f64(1.0 - jitter)
+    }
+}
+
 // =============================================================================
 // Connection State
 // =============================================================================
 
 /// Connection state
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum ConnectionState {
+    #[default]
     Disconnected,
     Connecting,
     Connected,
@@ -234,6 +268,25 @@ pub enum ConnectionState {
     Failed,
 }
 
+/// Connection progress shared between a client and its connection task
+#[derive(Debug, Default)]
+struct Link {
+    state: std::sync::Mutex<ConnectionState>,
+    /// Consecutive failed connection attempts
+    attempts: AtomicU32,
+    reconnects: AtomicU64,
+}
+
+impl Link {
+    fn state(&self) -> ConnectionState {
+        *self.state.lock().unwrap()
+    }
+
+    fn set_state(&self, state: ConnectionState) {
+        *self.state.lock().unwrap() = state;
+    }
+}
+
 // =============================================================================
 // Market WebSocket Client
 // =============================================================================
@@ -255,8 +308,7 @@ pub enum ConnectionState {
 /// ```
 pub struct MarketWsClient {
     config: WsConfig,
-    connected: Arc<AtomicBool>,
-    reconnect_count: Arc<AtomicU64>,
+    link: Arc<Link>,
     shutdown_tx: Option<broadcast::Sender<()>>,
     subscribe_tx: Option<mpsc::Sender<SubscribeCommand>>,
 }
@@ -272,8 +324,7 @@ impl MarketWsClient {
     pub fn new(config: WsConfig) -> Self {
         Self {
             config,
-            connected: Arc::new(AtomicBool::new(false)),
-            reconnect_count: Arc::new(AtomicU64::new(0)),
+            link: Arc::new(Link::default()),
             shutdown_tx: None,
             subscribe_tx: None,
         }
@@ -281,12 +332,22 @@ impl MarketWsClient {
 
     /// Check if connected
     pub fn is_connected(&self) -> bool {
-        self.connected.load(Ordering::Relaxed)
+        self.state() == ConnectionState::Connected
+    }
+
+    /// Current connection state
+    pub fn state(&self) -> ConnectionState {
+        self.link.state()
+    }
+
+    /// Consecutive failed connection attempts, reset on connecting
+    pub fn attempt_count(&self) -> u32 {
+        self.link.attempts.load(Ordering::Relaxed)
     }
 
     /// Get reconnection count
     pub fn reconnect_count(&self) -> u64 {
-        self.reconnect_count.load(Ordering::Relaxed)
+        self.link.reconnects.load(Ordering::Relaxed)
     }
 
     /// Connect to the market channel
@@ -301,8 +362,7 @@ impl MarketWsClient {
         self.subscribe_tx = Some(subscribe_tx);
 
         let config = self.config.clone();
-        let connected = Arc::clone(&self.connected);
-        let reconnect_count = Arc::clone(&self.reconnect_count);
+        let link = Arc::clone(&self.link);
 
         tokio::spawn(async move {
             connection_loop(
@@ -311,8 +371,7 @@ impl MarketWsClient {
                 event_tx,
                 subscribe_rx,
                 shutdown_tx,
-                connected,
-                reconnect_count,
+                link,
             )
             .await;
         });
@@ -320,8 +379,15 @@ impl MarketWsClient {
         // Wait for initial connection
         let start = std::time::Instant::now();
         while start.elapsed() < Duration::from_secs(self.config.connect_timeout_secs) {
-            if self.is_connected() {
-                return Ok(event_rx);
+            match self.state() {
+                ConnectionState::Connected => return Ok(event_rx),
+                ConnectionState::Failed => {
+                    return Err(BotError::WebSocket(format!(
+                        "Gave up after {} connection attempts",
+                        self.attempt_count()
+                    )))
+                }
+                _ => {}
             }
             tokio::time::sleep(Duration::from_millis(100)).await;
         }
@@ -373,13 +439,12 @@ async fn connection_loop(
     event_tx: mpsc::Sender<MarketEvent>,
     mut subscribe_rx: mpsc::Receiver<SubscribeCommand>,
     shutdown_tx: broadcast::Sender<()>,
-    connected: Arc<AtomicBool>,
-    reconnect_count: Arc<AtomicU64>,
+    link: Arc<Link>,
 ) {
     let mut shutdown_rx = shutdown_tx.subscribe();
     let mut attempt = 0u32;
-    let mut delay_ms = INITIAL_RECONNECT_DELAY_MS;
     let mut current_assets = initial_assets;
+    link.set_state(ConnectionState::Connecting);
 
     loop {
         // Check for shutdown
@@ -399,7 +464,7 @@ async fn connection_loop(
             &event_tx,
             &mut subscribe_rx,
             &mut shutdown_rx,
-            &connected,
+            &link,
         )
         .await
         {
@@ -407,7 +472,6 @@ async fn connection_loop(
                 // Normal disconnect, update assets and reset backoff
                 current_assets = updated_assets;
                 attempt = 0;
-                delay_ms = INITIAL_RECONNECT_DELAY_MS;
             }
             Err(e) => {
                 error!("WebSocket error: {}", e);
@@ -415,28 +479,35 @@ async fn connection_loop(
 
                 if config.max_reconnect_attempts > 0 && attempt >= config.max_reconnect_attempts {
                     error!("Max reconnect attempts reached");
-                    break;
+                    link.attempts.store(attempt, Ordering::Relaxed);
+                    link.set_state(ConnectionState::Failed);
+                    let _ = event_tx
+                        .send(MarketEvent::Fatal(format!(
+                            "Gave up after {} attempts: {}",
+                            attempt, e
+                        )))
+                        .await;
+                    return;
                 }
             }
         }
 
-        connected.store(false, Ordering::Relaxed);
-        reconnect_count.fetch_add(1, Ordering::Relaxed);
+        link.attempts.store(attempt, Ordering::Relaxed);
+        link.set_state(ConnectionState::Reconnecting);
+        link.reconnects.fetch_add(1, Ordering::Relaxed);
 
-        info!("Reconnecting in {}ms...", delay_ms);
+        let delay = config.reconnect_delay(attempt.saturating_sub(1));
+        info!("Reconnecting in {}ms...", delay.as_millis());
         tokio::select! {
-            _ = tokio::time::sleep(Duration::from_millis(delay_ms)) => {}
+            _ = tokio::time::sleep(delay) => {}
             _ = shutdown_rx.recv() => {
                 info!("Shutdown during backoff");
                 break;
             }
         }
-
-        // Exponential backoff
-        delay_ms = (delay_ms * 2).min(MAX_RECONNECT_DELAY_MS);
     }
 
-    connected.store(false, Ordering::Relaxed);
+    link.set_state(ConnectionState::Disconnected);
 }
 
 async fn connect_once(
@@ -445,7 +516,7 @@ async fn connect_once(
     event_tx: &mpsc::Sender<MarketEvent>,
     subscribe_rx: &mut mpsc::Receiver<SubscribeCommand>,
     shutdown_rx: &mut broadcast::Receiver<()>,
-    connected: &Arc<AtomicBool>,
+    link: &Link,
 ) -> Result<Vec<String>> {
     // Build WebSocket URL
     let ws_url = format!("{}/ws/{}", config.base_url, MARKET_CHANNEL);
@@ -476,7 +547,8 @@ async fn connect_once(
         "Connected to Polymarket WebSocket, subscribed to {} assets",
         asset_ids.len()
     );
-    connected.store(true, Ordering::Relaxed);
+    link.attempts.store(0, Ordering::Relaxed);
+    link.set_state(ConnectionState::Connected);
 
     // Track current subscriptions
     let mut current_assets: Vec<String> = asset_ids.to_vec();
@@ -616,6 +688,8 @@ pub struct PriceStream {
     event_rx: mpsc::Receiver<MarketEvent>,
     /// Last known prices per asset
     prices: HashMap<String, MarketUpdate>,
+    /// Fed every book and price change event before it is converted
+    books: Option<Arc<RwLock<OrderBookMaintainer>>>,
 }
 
 impl PriceStream {
@@ -624,9 +698,16 @@ impl PriceStream {
         Self {
             event_rx,
             prices: HashMap::new(),
+            books: None,
         }
     }
 
+    /// Keep `books` current from the events this stream receives
+    pub fn with_books(mut self, books: Arc<RwLock<OrderBookMaintainer>>) -> Self {
+        self.books = Some(books);
+        self
+    }
+
     /// Connect and create a price stream
     pub async fn connect(config: WsConfig, asset_ids: Vec<String>) -> Result<Self> {
         let mut client = MarketWsClient::new(config);
@@ -642,6 +723,9 @@ impl PriceStream {
     pub async fn recv(&mut self) -> Option<MarketUpdate> {
         loop {
             let event = self.event_rx.recv().await?;
+            if let Some(books) = &self.books {
+                books.write().await.apply_event(&event);
+            }
 
             match event {
                 MarketEvent::Book(book) => {
@@ -723,6 +807,11 @@ impl PriceStream {
                     return Some(update);
                 }
 
+                MarketEvent::Fatal(reason) => {
+                    error!("Market stream ended: {}", reason);
+                    return None;
+                }
+
                 _ => {
                     // Skip tick_size_change and unknown events
                     continue;
@@ -770,6 +859,29 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_price_stream_feeds_books() {
+        let (tx, rx) = mpsc::channel(4);
+        let books = Arc::new(RwLock::new(OrderBookMaintainer::new()));
+        let mut stream = PriceStream::new(rx).with_books(books.clone());
+
+        let book = r#"{"event_type": "book", "asset_id": "t1", "market": "0xabc",
+            "bids": [{"price": "0.48", "size": "30"}], "asks": [{"price": "0.52", "size": "25"}],
+            "timestamp": "1000"}"#;
+        tx.send(parse_message(book).unwrap()).await.unwrap();
+        stream.recv().await.unwrap();
+
+        let change = r#"{"event_type": "price_change", "market": "0xabc", "timestamp": "1001",
+            "price_changes": [{"asset_id": "t1", "price": "0.50", "size": "10", "side": "BUY",
+            "best_bid": "0.50", "best_ask": "0.52"}]}"#;
+        tx.send(parse_message(change).unwrap()).await.unwrap();
+        stream.recv().await.unwrap();
+
+        let book = books.read().await.book("t1").unwrap();
+        assert_eq!(book.bids.len(), 2);
+        assert_eq!(book.bids[0].price, Decimal::from_str("0.50").unwrap());
+    }
+
     #[test]
     fn test_parse_price_change_message() {
         let json = r#"{
@@ -854,4 +966,71 @@ mod tests {
         assert!(!client.is_connected());
         assert_eq!(client.reconnect_count(), 0);
     }
+
+    fn unreachable_config(max_attempts: u32) -> WsConfig {
+        // Bind then drop a listener so the port refuses connections
+        let port = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap()
+            .port();
+        WsConfig {
+            base_url: format!("ws://127.0.0.1:{}", port),
+            connect_timeout_secs: 1,
+            max_reconnect_attempts: max_attempts,
+            initial_reconnect_delay_ms: 1,
+            max_reconnect_delay_ms: 4,
+            ..WsConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_reconnect_delay_grows_and_caps() {
+        let config = WsConfig::default();
+        let delays: Vec<u64> = (0..9)
+            .map(|attempt| config.base_reconnect_delay(attempt).as_millis() as u64)
+            .collect();
+        assert_eq!(delays, vec![1000, 2000, 4000, 8000, 16000, 32000, 60000, 60000, 60000]);
+        assert_eq!(config.base_reconnect_delay(u32::MAX), Duration::from_millis(60000));
+
+        for attempt in [0, 3, 10] {
+            let base = config.base_reconnect_delay(attempt);
+            for _ in 0..50 {
+                let delay = config.reconnect_delay(attempt);
+                assert!(delay <= base && delay >= base.mul_f64(0.8), "{:?} vs {:?}", delay, base);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repeated_failures_give_up_with_fatal_event() {
+        let (event_tx, mut event_rx) = mpsc::channel(8);
+        let (_subscribe_tx, subscribe_rx) = mpsc::channel(1);
+        let (shutdown_tx, _) = broadcast::channel(1);
+        let link = Arc::new(Link::default());
+
+        connection_loop(
+            unreachable_config(4),
+            vec!["t1".to_string()],
+            event_tx,
+            subscribe_rx,
+            shutdown_tx,
+            Arc::clone(&link),
+        )
+        .await;
+
+        assert!(matches!(event_rx.recv().await, Some(MarketEvent::Fatal(reason)) if reason.contains("4 attempts")));
+        assert_eq!(link.state(), ConnectionState::Failed);
+        assert_eq!(link.attempts.load(Ordering::Relaxed), 4);
+        assert_eq!(link.reconnects.load(Ordering::Relaxed), 3);
+    }
+
+    #[tokio::test]
+    async fn test_connect_fails_fast_once_attempts_exhausted() {
+        let mut client = MarketWsClient::new(unreachable_config(2));
+        let err = client.connect(vec!["t1".to_string()]).await.unwrap_err();
+        assert!(err.to_string().contains("2 connection attempts"));
+        assert_eq!(client.state(), ConnectionState::Failed);
+        assert_eq!(client.attempt_count(), 2);
+    }
 }
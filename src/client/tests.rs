@@ -2,7 +2,9 @@
 
 #[cfg(test)]
 mod tests {
+    use crate::client::{OrderBook, OrderBookLevel};
     use crate::types::{Order, OrderType, Side};
+    use rust_decimal::Decimal;
     use rust_decimal_macros::dec;
 
     #[test]
@@ -13,6 +15,7 @@ mod tests {
             price: dec!(0.55),
             size: dec!(100),
             order_type: OrderType::GTC,
+            client_order_id: None,
         };
         
         assert_eq!(order.token_id, "token123");
@@ -28,6 +31,7 @@ mod tests {
             price: dec!(0.5),
             size: dec!(10),
             order_type: OrderType::GTC,
+            client_order_id: None,
         };
         assert_eq!(order.order_type, OrderType::GTC);
     }
@@ -40,6 +44,7 @@ mod tests {
             price: dec!(0.5),
             size: dec!(10),
             order_type: OrderType::FOK,
+            client_order_id: None,
         };
         assert_eq!(order.order_type, OrderType::FOK);
     }
@@ -52,6 +57,7 @@ mod tests {
             price: dec!(0.5),
             size: dec!(10),
             order_type: OrderType::GTD,
+            client_order_id: None,
         };
         assert_eq!(order.order_type, OrderType::GTD);
     }
@@ -75,6 +81,7 @@ mod tests {
             price: dec!(0.45),
             size: dec!(50),
             order_type: OrderType::GTC,
+            client_order_id: None,
         };
         let json = serde_json::to_string(&order).unwrap();
         assert!(json.contains("\"token_id\":\"abc\""));
@@ -243,4 +250,49 @@ mod tests {
             assert_eq!(is_crypto_up_down, expected, "Failed for: {}", question);
         }
     }
+
+    fn book(bids: &[(Decimal, Decimal)], asks: &[(Decimal, Decimal)]) -> OrderBook {
+        let levels = |side: &[(Decimal, Decimal)]| {
+            side.iter().map(|&(price, size)| OrderBookLevel { price, size }).collect()
+        };
+        OrderBook { bids: levels(bids), asks: levels(asks) }
+    }
+
+    #[test]
+    fn test_microprice_skews_towards_thin_side() {
+        // 900 bid vs 100 ask: buyers dominate, fair value sits near the ask
+        let heavy_bid = book(&[(dec!(0.50), dec!(900))], &[(dec!(0.52), dec!(100))]);
+        assert_eq!(heavy_bid.mid(), Some(dec!(0.51)));
+        assert_eq!(heavy_bid.microprice(), Some(dec!(0.518)));
+
+        let heavy_ask = book(&[(dec!(0.50), dec!(100))], &[(dec!(0.52), dec!(900))]);
+        assert_eq!(heavy_ask.microprice(), Some(dec!(0.502)));
+
+        let balanced = book(&[(dec!(0.50), dec!(300))], &[(dec!(0.52), dec!(300))]);
+        assert_eq!(balanced.microprice(), balanced.mid());
+
+        let one_sided = book(&[(dec!(0.50), dec!(300))], &[]);
+        assert_eq!(one_sided.mid(), None);
+        assert_eq!(one_sided.microprice(), None);
+    }
+
+    #[test]
+    fn test_depth_within_and_vwap() {
+        let book = book(
+            &[(dec!(0.50), dec!(100)), (dec!(0.45), dec!(1000))],
+            &[(dec!(0.52), dec!(100)), (dec!(0.54), dec!(200)), (dec!(0.60), dec!(1000))],
+        );
+
+        // Mid 0.51: 500 bps (0.0255) reaches only the touch, 600 bps (0.0306)
+        // also the second ask
+        assert_eq!(book.depth_within(500), dec!(50) + dec!(52));
+        assert_eq!(book.depth_within(600), dec!(50) + dec!(52) + dec!(108));
+        assert_eq!(book.depth_within(0), Decimal::ZERO);
+
+        assert_eq!(book.vwap_to_fill(dec!(100), Side::Buy), Some(dec!(0.52)));
+        assert_eq!(book.vwap_to_fill(dec!(200), Side::Buy), Some(dec!(0.53)));
+        assert_eq!(book.vwap_to_fill(dec!(200), Side::Sell), Some(dec!(0.475)));
+        assert_eq!(book.vwap_to_fill(dec!(2000), Side::Sell), None);
+        assert_eq!(book.vwap_to_fill(Decimal::ZERO, Side::Buy), None);
+    }
 }
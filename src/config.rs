@@ -1,8 +1,15 @@
 //! Configuration management
 
+use crate::types::OutcomeMatcher;
+use arc_swap::ArcSwap;
 use rust_decimal::Decimal;
 use serde::Deserialize;
-use std::path::Path;
+use anyhow::Context;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use thiserror::Error;
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
@@ -11,9 +18,291 @@ pub struct Config {
     pub risk: RiskConfig,
     pub database: DatabaseConfig,
     pub llm: Option<LlmConfig>,
+    /// Add the Binance kline model to the ensemble for crypto up/down markets
+    pub binance_model: Option<BinanceModelConfig>,
+    /// Add the base rate of similar resolved markets to the ensemble
+    pub base_rate_model: Option<BaseRateModelConfig>,
+    /// Add order book imbalance, momentum and spread to the ensemble
+    pub microstructure_model: Option<MicrostructureModelConfig>,
+    /// Keyword to exchange symbol mappings for crypto market questions
+    #[serde(default)]
+    pub symbols: crate::data::SymbolMapConfig,
     pub telegram: Option<TelegramConfig>,
+    /// Discord/Slack-style webhooks that receive the same notifications
+    /// as Telegram
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+    /// Notification message templates; unset ones use the built-ins
+    #[serde(default)]
+    pub templates: crate::notify::TemplateSet,
+    /// Coalescing and rate limits for notifications (None = unthrottled)
+    pub notify_throttle: Option<NotifyThrottleConfig>,
+    /// Alert when an open position's unrealized PnL crosses a threshold
+    pub pnl_alerts: Option<PnlAlertConfig>,
+    /// Close filled positions at a take-profit or stop-loss (None = hold)
+    pub exits: Option<ExitConfig>,
     pub ingester: Option<IngesterConfig>,
     pub copy_trade: Option<CopyTradeConfig>,
+    #[serde(default)]
+    pub paper: PaperConfig,
+    pub metrics: Option<MetricsConfig>,
+    /// REST control/status API (needs the `api` feature)
+    pub api: Option<ApiConfig>,
+    #[serde(default)]
+    pub logging: LoggingConfig,
+    /// Fee schedule applied to fills (see `fee_model`)
+    #[serde(default)]
+    pub fees: Option<FeeModel>,
+    /// Bot-wide limits on how often trades are submitted
+    #[serde(default)]
+    pub throttle: ThrottleConfig,
+}
+
+/// Log output settings
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LoggingConfig {
+    #[serde(default)]
+    pub format: LogFormat,
+    /// Filter directive such as "info" or "polymarket_bot=debug";
+    /// `RUST_LOG` takes precedence when set
+    #[serde(default)]
+    pub level: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human-readable lines
+    #[default]
+    Text,
+    /// One JSON object per line, including span fields
+    Json,
+}
+
+/// Prometheus endpoint (needs the `metrics` feature)
+#[derive(Debug, Clone, Deserialize)]
+pub struct MetricsConfig {
+    /// Address to listen on
+    #[serde(default = "default_metrics_bind")]
+    pub bind: String,
+    #[serde(default = "default_metrics_port")]
+    pub port: u16,
+}
+
+fn default_metrics_bind() -> String {
+    "0.0.0.0".to_string()
+}
+
+fn default_metrics_port() -> u16 {
+    9184
+}
+
+/// REST control/status API (needs the `api` feature)
+#[derive(Debug, Clone, Deserialize)]
+pub struct ApiConfig {
+    /// Address to listen on; the API can pause trading and close
+    /// positions, so keep it off public interfaces
+    #[serde(default = "default_api_bind")]
+    pub bind: String,
+    #[serde(default = "default_api_port")]
+    pub port: u16,
+    /// Require `Authorization: Bearer <token>` on every request
+    #[serde(default)]
+    pub token: Option<Secret>,
+}
+
+fn default_api_bind() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_api_port() -> u16 {
+    9185
+}
+
+/// How fees are charged on a fill, in basis points of its notional
+///
+/// A fill is a maker fill when it rested on the book before trading and a
+/// taker fill when it crossed the spread on arrival.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(tag = "model", rename_all = "snake_case")]
+pub enum FeeModel {
+    /// One rate for every fill
+    Flat { bps: Decimal },
+    /// Separate rates for maker and taker fills
+    MakerTaker { maker_bps: Decimal, taker_bps: Decimal },
+}
+
+impl Default for FeeModel {
+    fn default() -> Self {
+        Self::Flat { bps: Decimal::ZERO }
+    }
+}
+
+impl FeeModel {
+    /// Flat model from a rate given as a fraction (0.01 = 1%)
+    pub fn flat_rate(rate: Decimal) -> Self {
+        Self::Flat { bps: rate * Decimal::from(BPS_PER_UNIT) }
+    }
+
+    /// Fee rate for a fill as a fraction of its notional
+    pub fn rate(&self, is_maker: bool) -> Decimal {
+        let bps = match *self {
+            Self::Flat { bps } => bps,
+            Self::MakerTaker { maker_bps, .. } if is_maker => maker_bps,
+            Self::MakerTaker { taker_bps, .. } => taker_bps,
+        };
+        bps / Decimal::from(BPS_PER_UNIT)
+    }
+
+    /// Fee on a fill of `size` shares at `price`
+    pub fn fee(&self, size: Decimal, price: Decimal, is_maker: bool) -> Decimal {
+        size * price * self.rate(is_maker)
+    }
+}
+
+const BPS_PER_UNIT: u32 = 10_000;
+
+/// Trade rate limits enforced by `strategy::TradeThrottle`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ThrottleConfig {
+    /// Most trades in any rolling hour (0 = unlimited)
+    #[serde(default)]
+    pub max_trades_per_hour: u32,
+    /// Least time between two trades, in seconds (0 = no gap)
+    #[serde(default)]
+    pub min_seconds_between_trades: u64,
+    /// File for persisting recent trade times across restarts (None = in-memory only)
+    #[serde(default)]
+    pub state_path: Option<String>,
+}
+
+/// Notification limits enforced by `notify::NotifyThrottle`
+#[derive(Debug, Clone, Deserialize)]
+pub struct NotifyThrottleConfig {
+    /// Repeats of the same alert type within this many seconds are folded
+    /// into one summary (0 = no coalescing)
+    #[serde(default = "default_notify_window_secs")]
+    pub window_secs: u64,
+    /// Sustained message rate (0 = unlimited)
+    #[serde(default = "default_notify_max_per_minute")]
+    pub max_per_minute: u32,
+    /// Messages that may go out back to back before the rate applies
+    #[serde(default = "default_notify_burst")]
+    pub burst: u32,
+}
+
+fn default_notify_window_secs() -> u64 {
+    60
+}
+
+fn default_notify_max_per_minute() -> u32 {
+    20
+}
+
+fn default_notify_burst() -> u32 {
+    5
+}
+
+impl Default for NotifyThrottleConfig {
+    fn default() -> Self {
+        Self {
+            window_secs: default_notify_window_secs(),
+            max_per_minute: default_notify_max_per_minute(),
+            burst: default_notify_burst(),
+        }
+    }
+}
+
+/// Unrealized PnL thresholds watched by `monitor::PnlAlertTracker`
+#[derive(Debug, Clone, Deserialize)]
+pub struct PnlAlertConfig {
+    /// Gain on cost that triggers a take-profit alert (0.20 = +20%)
+    #[serde(default = "default_take_profit_pct")]
+    pub take_profit_pct: Decimal,
+    /// Loss on cost that triggers a stop-loss alert (0.10 = -10%)
+    #[serde(default = "default_stop_loss_pct")]
+    pub stop_loss_pct: Decimal,
+    /// How far the return must fall back past a threshold before it can
+    /// alert again
+    #[serde(default = "default_pnl_hysteresis_pct")]
+    pub hysteresis_pct: Decimal,
+}
+
+fn default_take_profit_pct() -> Decimal {
+    Decimal::new(20, 2)
+}
+
+fn default_stop_loss_pct() -> Decimal {
+    Decimal::new(10, 2)
+}
+
+fn default_pnl_hysteresis_pct() -> Decimal {
+    Decimal::new(5, 2)
+}
+
+impl Default for PnlAlertConfig {
+    fn default() -> Self {
+        Self {
+            take_profit_pct: default_take_profit_pct(),
+            stop_loss_pct: default_stop_loss_pct(),
+            hysteresis_pct: default_pnl_hysteresis_pct(),
+        }
+    }
+}
+
+/// Take-profit / stop-loss levels for `executor::ExitManager`, relative to
+/// the entry price
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExitConfig {
+    /// Close once the mark is this far above entry (0.20 = +20%)
+    pub take_profit_pct: Option<Decimal>,
+    /// Close once the mark is this far below entry (0.10 = -10%)
+    pub stop_loss_pct: Option<Decimal>,
+    /// Trail the stop this far behind the best mark, as a share of entry
+    pub trailing_pct: Option<Decimal>,
+    /// How often to re-read the order book for a mark
+    #[serde(default = "default_exit_poll_secs")]
+    pub poll_secs: u64,
+}
+
+fn default_exit_poll_secs() -> u64 {
+    15
+}
+
+impl Default for ExitConfig {
+    fn default() -> Self {
+        Self {
+            take_profit_pct: None,
+            stop_loss_pct: None,
+            trailing_pct: None,
+            poll_secs: default_exit_poll_secs(),
+        }
+    }
+}
+
+/// Paper trading account used by `--dry-run`
+#[derive(Debug, Clone, Deserialize)]
+pub struct PaperConfig {
+    /// Virtual starting balance (USDC)
+    #[serde(default = "default_paper_balance")]
+    pub starting_balance: Decimal,
+    /// Fee charged on each fill's notional (0.01 = 1%), used when there is
+    /// no `[fees]` section
+    #[serde(default)]
+    pub fee_rate: Decimal,
+}
+
+fn default_paper_balance() -> Decimal {
+    Decimal::new(1000, 0)
+}
+
+impl Default for PaperConfig {
+    fn default() -> Self {
+        Self {
+            starting_balance: default_paper_balance(),
+            fee_rate: Decimal::ZERO,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -63,7 +352,7 @@ pub struct TelegramUserbotConfig {
     /// Telegram API ID
     pub api_id: i32,
     /// Telegram API Hash
-    pub api_hash: String,
+    pub api_hash: Secret,
     /// Session file path
     pub session_file: String,
     /// Chat IDs to monitor
@@ -73,7 +362,7 @@ pub struct TelegramUserbotConfig {
 #[derive(Debug, Clone, Deserialize)]
 pub struct TelegramBotIngesterConfig {
     /// Bot token (can reuse from telegram config)
-    pub bot_token: String,
+    pub bot_token: Secret,
     /// Channel usernames to monitor
     pub channels: Vec<String>,
 }
@@ -81,7 +370,7 @@ pub struct TelegramBotIngesterConfig {
 #[derive(Debug, Clone, Deserialize)]
 pub struct TwitterConfig {
     /// Twitter API bearer token
-    pub bearer_token: Option<String>,
+    pub bearer_token: Option<Secret>,
     /// User IDs to monitor
     pub user_ids: Vec<String>,
     /// Keywords to filter
@@ -123,13 +412,23 @@ pub struct PolymarketConfig {
     /// Gamma API endpoint (market data)
     pub gamma_url: String,
     /// Private key for signing (hex, without 0x prefix)
-    pub private_key: String,
+    pub private_key: Secret,
     /// Funder address (for proxy wallets)
     pub funder_address: Option<String>,
     /// Chain ID (137 for Polygon mainnet)
     pub chain_id: u64,
     /// Signature type (0=EOA, 1=Magic, 2=Proxy)
     pub signature_type: u8,
+    /// Send USDC approvals on startup when the allowance is short
+    #[serde(default)]
+    pub auto_approve: bool,
+    /// Polygon RPC endpoint for approval transactions
+    #[serde(default = "default_rpc_url")]
+    pub rpc_url: String,
+}
+
+fn default_rpc_url() -> String {
+    "https://polygon-rpc.com".to_string()
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -138,10 +437,18 @@ pub struct StrategyConfig {
     pub min_edge: Decimal,
     /// Minimum confidence score to trade
     pub min_confidence: Decimal,
+    /// Abstain from signals whose confidence is below this, however large
+    /// their edge; stricter than `min_confidence` (unset = never abstain)
+    #[serde(default)]
+    pub abstain_below_confidence: Option<Decimal>,
     /// Kelly fraction (e.g., 0.25 for quarter Kelly)
     pub kelly_fraction: Decimal,
     /// Market scan interval in seconds
     pub scan_interval_secs: u64,
+    /// Markets analyzed at once during a scan; bounds concurrent model
+    /// (LLM) requests
+    #[serde(default = "default_scan_concurrency")]
+    pub scan_concurrency: usize,
     /// Model update interval in seconds
     pub model_update_interval_secs: u64,
     /// Enable compound growth strategy
@@ -150,6 +457,99 @@ pub struct StrategyConfig {
     /// Use sqrt scaling for compound growth (safer)
     #[serde(default = "default_true")]
     pub compound_sqrt_scaling: bool,
+    /// Taker fee per trade as a fraction of notional (charged on entry and exit)
+    #[serde(default)]
+    pub fee_rate: Decimal,
+    /// Slippage per trade as a fraction of price, used when no order book is known
+    #[serde(default)]
+    pub slippage_estimate: Decimal,
+    /// How far outcome prices may sum away from 1 before a market's quotes
+    /// are treated as stale and skipped
+    #[serde(default = "default_max_price_deviation")]
+    pub max_price_deviation: Decimal,
+    /// Skip markets with less liquidity than this, in USD
+    #[serde(default = "default_min_liquidity")]
+    pub min_liquidity: Decimal,
+    /// Liquidity floor for crypto up/down markets, which run thinner
+    #[serde(default = "default_crypto_min_liquidity")]
+    pub crypto_min_liquidity: Decimal,
+    /// Skip markets resolving sooner than this many minutes (0 = no minimum)
+    #[serde(default)]
+    pub min_minutes_to_resolution: u64,
+    /// Skip markets resolving later than this many minutes (unset = no maximum)
+    #[serde(default)]
+    pub max_minutes_to_resolution: Option<u64>,
+    /// Scale suggested size down linearly over the last this many minutes
+    /// before resolution (0 = no decay)
+    #[serde(default)]
+    pub size_decay_minutes: u64,
+    /// How outcome names map to Yes/No (default: case-insensitive with
+    /// Up/Down as Yes/No)
+    #[serde(default)]
+    pub outcome_matching: OutcomeMatcher,
+    /// Only scan markets whose id contains, or that carry a tag equal to,
+    /// one of these (empty = all markets)
+    #[serde(default)]
+    pub allowlist: Vec<String>,
+    /// Never scan markets whose id contains, or that carry a tag equal to,
+    /// one of these
+    #[serde(default)]
+    pub denylist: Vec<String>,
+    /// Only scan markets in these Gamma categories (empty = all)
+    #[serde(default)]
+    pub categories: Vec<String>,
+    /// Hold back signals that would trade into a sharp price move
+    #[serde(default)]
+    pub anti_chase: AntiChaseConfig,
+}
+
+fn default_scan_concurrency() -> usize {
+    4
+}
+
+fn default_max_price_deviation() -> Decimal {
+    Decimal::new(5, 2) // 5%
+}
+
+fn default_min_liquidity() -> Decimal {
+    Decimal::new(10000, 0)
+}
+
+fn default_crypto_min_liquidity() -> Decimal {
+    Decimal::new(1000, 0)
+}
+
+/// Protection against buying right after a runup (or selling right after
+/// a drop), using the momentum tracked by `MarketStateMonitor`
+#[derive(Debug, Clone, Deserialize)]
+pub struct AntiChaseConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Yes-price move over the monitor's window, in percent, that counts
+    /// as a sharp move
+    #[serde(default = "default_chase_threshold_pct")]
+    pub threshold_pct: Decimal,
+    /// Only act on moves that are still accelerating
+    #[serde(default = "default_true")]
+    pub require_acceleration: bool,
+    /// Size multiplier for a signal chasing the move (0 = drop the signal)
+    #[serde(default)]
+    pub size_multiplier: Decimal,
+}
+
+fn default_chase_threshold_pct() -> Decimal {
+    Decimal::new(5, 0) // 5%
+}
+
+impl Default for AntiChaseConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            threshold_pct: default_chase_threshold_pct(),
+            require_acceleration: true,
+            size_multiplier: Decimal::ZERO,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -164,6 +564,38 @@ pub struct RiskConfig {
     pub min_balance_reserve: Decimal,
     /// Maximum number of open positions
     pub max_open_positions: usize,
+    /// Maximum combined exposure to a cluster of correlated markets, as fraction of portfolio
+    #[serde(default = "default_max_correlated_exposure_pct")]
+    pub max_correlated_exposure_pct: Decimal,
+    /// Consecutive losing trades that trigger a cooldown (0 = disabled)
+    #[serde(default = "default_max_consecutive_losses")]
+    pub max_consecutive_losses: u32,
+    /// Cooldown length in minutes after the loss streak (0 = until daily reset)
+    #[serde(default = "default_loss_cooldown_mins")]
+    pub loss_cooldown_mins: u64,
+    /// File for persisting daily P&L across restarts (None = in-memory only)
+    #[serde(default)]
+    pub pnl_state_path: Option<String>,
+    /// Skip signals older than this at execution time, unless the signal
+    /// sets its own limit (None = no limit)
+    #[serde(default)]
+    pub max_signal_age_secs: Option<u64>,
+    /// Skip signals whose token's mid price has moved more than this since
+    /// the signal was generated, e.g. 0.03 = 3 cents (None = no check)
+    #[serde(default)]
+    pub max_price_move: Option<Decimal>,
+}
+
+fn default_max_correlated_exposure_pct() -> Decimal {
+    Decimal::new(20, 2) // 20%
+}
+
+fn default_max_consecutive_losses() -> u32 {
+    3
+}
+
+fn default_loss_cooldown_mins() -> u64 {
+    60
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -178,17 +610,129 @@ pub struct LlmConfig {
     pub provider: String,
     /// API key (not required for ollama)
     #[serde(default)]
-    pub api_key: String,
+    pub api_key: Secret,
     /// Model name (optional, uses provider default)
     pub model: Option<String>,
     /// Base URL for OpenAI-compatible APIs
     pub base_url: Option<String>,
 }
 
+#[derive(Debug, Clone, Deserialize)]
+pub struct BinanceModelConfig {
+    /// Ensemble weight, against the LLM's 0.7 (default: 0.3)
+    #[serde(default = "default_binance_model_weight")]
+    pub weight: Decimal,
+    /// Hourly klines fetched per prediction (default: 50)
+    #[serde(default = "default_kline_limit")]
+    pub kline_limit: usize,
+}
+
+fn default_binance_model_weight() -> Decimal {
+    Decimal::new(3, 1)
+}
+
+fn default_kline_limit() -> usize {
+    crate::model::DEFAULT_KLINE_LIMIT
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BaseRateModelConfig {
+    /// Ensemble weight, against the LLM's 0.7 (default: 0.2)
+    #[serde(default = "default_base_rate_weight")]
+    pub weight: Decimal,
+    /// Fewest resolved similar markets to predict from (default: 10)
+    #[serde(default = "default_base_rate_min_samples")]
+    pub min_samples: u64,
+    /// Samples at which confidence reaches half its maximum (default: 30)
+    #[serde(default = "default_base_rate_half_confidence_samples")]
+    pub half_confidence_samples: u64,
+    /// Confidence approached as the sample grows (default: 0.8)
+    #[serde(default = "default_base_rate_max_confidence")]
+    pub max_confidence: Decimal,
+    /// Recently closed markets checked for resolutions per poll (default: 500)
+    #[serde(default = "default_base_rate_resolutions_per_poll")]
+    pub resolutions_per_poll: usize,
+}
+
+impl BaseRateModelConfig {
+    /// Sample size settings for `BaseRateModel`
+    pub fn model_config(&self) -> crate::model::BaseRateConfig {
+        crate::model::BaseRateConfig {
+            min_samples: self.min_samples,
+            half_confidence_samples: self.half_confidence_samples,
+            max_confidence: self.max_confidence,
+        }
+    }
+}
+
+fn default_base_rate_weight() -> Decimal {
+    Decimal::new(2, 1)
+}
+
+fn default_base_rate_min_samples() -> u64 {
+    crate::model::BaseRateConfig::default().min_samples
+}
+
+fn default_base_rate_half_confidence_samples() -> u64 {
+    crate::model::BaseRateConfig::default().half_confidence_samples
+}
+
+fn default_base_rate_max_confidence() -> Decimal {
+    crate::model::BaseRateConfig::default().max_confidence
+}
+
+fn default_base_rate_resolutions_per_poll() -> usize {
+    crate::monitor::DEFAULT_RESOLUTIONS_PER_POLL
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct MicrostructureModelConfig {
+    /// Ensemble weight, against the LLM's 0.7 (default: 0.1)
+    #[serde(default = "default_microstructure_weight")]
+    pub weight: Decimal,
+    /// Largest shift away from the market price (default: 0.05)
+    #[serde(default = "default_microstructure_max_shift")]
+    pub max_shift: Decimal,
+    /// Book levels per side counted towards imbalance (default: 5)
+    #[serde(default = "default_microstructure_depth_levels")]
+    pub depth_levels: usize,
+    /// Price observations kept per market for momentum (default: 10)
+    #[serde(default = "default_microstructure_momentum_window")]
+    pub momentum_window: usize,
+}
+
+impl MicrostructureModelConfig {
+    /// Settings for `MicrostructureModel`, the rest at their defaults
+    pub fn model_config(&self) -> crate::model::MicrostructureConfig {
+        crate::model::MicrostructureConfig {
+            max_shift: self.max_shift,
+            depth_levels: self.depth_levels,
+            momentum_window: self.momentum_window,
+            ..Default::default()
+        }
+    }
+}
+
+fn default_microstructure_weight() -> Decimal {
+    Decimal::new(1, 1)
+}
+
+fn default_microstructure_max_shift() -> Decimal {
+    crate::model::MicrostructureConfig::default().max_shift
+}
+
+fn default_microstructure_depth_levels() -> usize {
+    crate::model::MicrostructureConfig::default().depth_levels
+}
+
+fn default_microstructure_momentum_window() -> usize {
+    crate::model::MicrostructureConfig::default().momentum_window
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct TelegramConfig {
     /// Bot token from @BotFather
-    pub bot_token: String,
+    pub bot_token: Secret,
     /// Chat ID to send messages to (your user ID or group ID)
     pub chat_id: String,
     /// Send signal notifications (default: true)
@@ -203,6 +747,29 @@ pub struct TelegramConfig {
     /// Send daily reports (default: true)
     #[serde(default = "default_true")]
     pub notify_daily: bool,
+    /// Hours between "still alive" heartbeats; 0 turns them off (default: 0)
+    #[serde(default)]
+    pub heartbeat_hours: u64,
+}
+
+/// JSON webhook notification channel
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookConfig {
+    /// Full webhook URL; it embeds the credentials, so treat it as secret
+    pub url: Secret,
+    #[serde(default)]
+    pub kind: WebhookKind,
+}
+
+/// Payload shape a webhook expects
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookKind {
+    /// `{"content": "..."}`
+    #[default]
+    Discord,
+    /// `{"text": "..."}`
+    Slack,
 }
 
 fn default_true() -> bool {
@@ -211,16 +778,261 @@ fn default_true() -> bool {
 
 impl Config {
     /// Load configuration from file
+    ///
+    /// `${VAR}` references in the file's string values are replaced with
+    /// environment variables, then `POLYBOT_<SECTION>_<FIELD>` variables
+    /// override individual fields (e.g. `POLYBOT_STRATEGY_MIN_EDGE=0.08`).
     pub fn load<P: AsRef<Path>>(path: P) -> anyhow::Result<Self> {
-        let settings = config::Config::builder()
-            .add_source(config::File::with_name(path.as_ref().to_str().unwrap()))
-            .add_source(config::Environment::with_prefix("POLYMARKET"))
-            .build()?;
+        Self::load_with_env(path, &std::env::vars().collect())
+    }
+
+    /// `load` with an explicit environment instead of the process one
+    pub fn load_with_env<P: AsRef<Path>>(path: P, env: &HashMap<String, String>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let raw = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config file {}", path.display()))?;
 
-        let config: Config = settings.try_deserialize()?;
+        let format = match path.extension().and_then(|e| e.to_str()) {
+            Some("yaml") | Some("yml") => config::FileFormat::Yaml,
+            Some("json") => config::FileFormat::Json,
+            _ => config::FileFormat::Toml,
+        };
+        // Parse before interpolating, so only string values are touched
+        // and comments or keys that happen to contain `${` are left alone
+        let mut values = config::Source::collect(&config::File::from_str(&raw, format))?;
+        for value in values.values_mut() {
+            interpolate_value(value, env)?;
+        }
+
+        let mut builder = config::Config::builder()
+            .add_source(InterpolatedFile(values))
+            .add_source(config::Environment::with_prefix("POLYMARKET"));
+        for (key, value) in env_overrides(env) {
+            builder = builder.set_override(key, value)?;
+        }
+
+        let config: Config = builder.build()?.try_deserialize()?;
+        config.validate()?;
         Ok(config)
     }
 
+    /// Fee schedule for execution and paper fills
+    ///
+    /// Configs without a `[fees]` section charge `paper.fee_rate` flat.
+    pub fn fee_model(&self) -> FeeModel {
+        self.fees.unwrap_or_else(|| FeeModel::flat_rate(self.paper.fee_rate))
+    }
+
+    /// Check values are in range and enabled features have their keys
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let p = &self.polymarket;
+        if crate::client::chain_name(p.chain_id).is_none() {
+            return Err(ConfigError::OutOfRange {
+                field: "polymarket.chain_id",
+                value: p.chain_id.to_string(),
+                expected: "137 (Polygon)",
+            });
+        }
+        if let Some(funder) = &p.funder_address {
+            crate::client::parse_address(funder).map_err(|e| ConfigError::Invalid {
+                field: "polymarket.funder_address",
+                reason: match e {
+                    crate::error::BotError::Config(msg) => msg,
+                    other => other.to_string(),
+                },
+            })?;
+        }
+
+        let s = &self.strategy;
+        check_range("strategy.kelly_fraction", s.kelly_fraction, RangeKind::OpenClosed)?;
+        check_range("strategy.min_edge", s.min_edge, RangeKind::Closed)?;
+        check_range("strategy.min_confidence", s.min_confidence, RangeKind::Closed)?;
+        if let Some(floor) = s.abstain_below_confidence {
+            check_range("strategy.abstain_below_confidence", floor, RangeKind::Closed)?;
+            if floor < s.min_confidence {
+                return Err(ConfigError::OutOfRange {
+                    field: "strategy.abstain_below_confidence",
+                    value: floor.to_string(),
+                    expected: ">= strategy.min_confidence",
+                });
+            }
+        }
+        check_range("strategy.fee_rate", s.fee_rate, RangeKind::Closed)?;
+        check_range("strategy.slippage_estimate", s.slippage_estimate, RangeKind::Closed)?;
+        check_range("strategy.max_price_deviation", s.max_price_deviation, RangeKind::Closed)?;
+        for (field, floor) in [
+            ("strategy.min_liquidity", s.min_liquidity),
+            ("strategy.crypto_min_liquidity", s.crypto_min_liquidity),
+        ] {
+            if floor < Decimal::ZERO {
+                return Err(ConfigError::OutOfRange { field, value: floor.to_string(), expected: ">= 0" });
+            }
+        }
+        check_positive("strategy.scan_interval_secs", s.scan_interval_secs)?;
+        check_positive("strategy.scan_concurrency", s.scan_concurrency as u64)?;
+        check_positive("strategy.model_update_interval_secs", s.model_update_interval_secs)?;
+        if let Some(max) = s.max_minutes_to_resolution {
+            if max < s.min_minutes_to_resolution {
+                return Err(ConfigError::OutOfRange {
+                    field: "strategy.max_minutes_to_resolution",
+                    value: max.to_string(),
+                    expected: ">= strategy.min_minutes_to_resolution",
+                });
+            }
+        }
+
+        let r = &self.risk;
+        check_range("risk.max_position_pct", r.max_position_pct, RangeKind::OpenClosed)?;
+        check_range("risk.max_exposure_pct", r.max_exposure_pct, RangeKind::OpenClosed)?;
+        check_range("risk.max_daily_loss_pct", r.max_daily_loss_pct, RangeKind::OpenClosed)?;
+        check_range("risk.max_correlated_exposure_pct", r.max_correlated_exposure_pct, RangeKind::OpenClosed)?;
+        if r.min_balance_reserve < Decimal::ZERO {
+            return Err(ConfigError::OutOfRange {
+                field: "risk.min_balance_reserve",
+                value: r.min_balance_reserve.to_string(),
+                expected: ">= 0",
+            });
+        }
+        check_positive("risk.max_open_positions", r.max_open_positions as u64)?;
+        if let Some(max_move) = r.max_price_move {
+            check_range("risk.max_price_move", max_move, RangeKind::OpenClosed)?;
+        }
+
+        if let Some(binance) = &self.binance_model {
+            check_range("binance_model.weight", binance.weight, RangeKind::OpenClosed)?;
+        }
+        if let Some(base_rate) = &self.base_rate_model {
+            check_range("base_rate_model.weight", base_rate.weight, RangeKind::OpenClosed)?;
+            check_range("base_rate_model.max_confidence", base_rate.max_confidence, RangeKind::OpenClosed)?;
+            check_positive("base_rate_model.min_samples", base_rate.min_samples)?;
+            check_positive("base_rate_model.resolutions_per_poll", base_rate.resolutions_per_poll as u64)?;
+        }
+        if let Some(micro) = &self.microstructure_model {
+            check_range("microstructure_model.weight", micro.weight, RangeKind::OpenClosed)?;
+            check_range("microstructure_model.max_shift", micro.max_shift, RangeKind::OpenClosed)?;
+            check_positive("microstructure_model.depth_levels", micro.depth_levels as u64)?;
+            check_positive("microstructure_model.momentum_window", micro.momentum_window as u64)?;
+        }
+        for (keyword, symbol) in &self.symbols.map {
+            // Questions are matched word by word, so a keyword must be one word
+            if keyword.trim().is_empty() || !keyword.trim().chars().all(char::is_alphanumeric) {
+                return Err(ConfigError::Invalid {
+                    field: "symbols.map",
+                    reason: format!("keyword {:?} must be a single word", keyword),
+                });
+            }
+            if symbol.trim().is_empty() {
+                return Err(ConfigError::Invalid {
+                    field: "symbols.map",
+                    reason: format!("keyword {:?} maps to an empty symbol", keyword),
+                });
+            }
+        }
+
+        if let Some(metrics) = &self.metrics {
+            check_positive("metrics.port", u64::from(metrics.port))?;
+        }
+        if let Some(api) = &self.api {
+            check_positive("api.port", u64::from(api.port))?;
+        }
+
+        if let Some(pnl) = &self.pnl_alerts {
+            if pnl.take_profit_pct <= Decimal::ZERO {
+                return Err(ConfigError::OutOfRange {
+                    field: "pnl_alerts.take_profit_pct",
+                    value: pnl.take_profit_pct.to_string(),
+                    expected: "> 0",
+                });
+            }
+            check_range("pnl_alerts.stop_loss_pct", pnl.stop_loss_pct, RangeKind::OpenClosed)?;
+            check_range("pnl_alerts.hysteresis_pct", pnl.hysteresis_pct, RangeKind::Closed)?;
+        }
+
+        if let Some(exits) = &self.exits {
+            if let Some(take_profit) = exits.take_profit_pct {
+                if take_profit <= Decimal::ZERO {
+                    return Err(ConfigError::OutOfRange {
+                        field: "exits.take_profit_pct",
+                        value: take_profit.to_string(),
+                        expected: "> 0",
+                    });
+                }
+            }
+            if let Some(stop_loss) = exits.stop_loss_pct {
+                check_range("exits.stop_loss_pct", stop_loss, RangeKind::OpenClosed)?;
+            }
+            if let Some(trailing) = exits.trailing_pct {
+                check_range("exits.trailing_pct", trailing, RangeKind::OpenClosed)?;
+            }
+            check_positive("exits.poll_secs", exits.poll_secs)?;
+        }
+
+        check_range("paper.fee_rate", self.paper.fee_rate, RangeKind::Closed)?;
+        match self.fees {
+            Some(FeeModel::Flat { bps }) => check_bps("fees.bps", bps)?,
+            Some(FeeModel::MakerTaker { maker_bps, taker_bps }) => {
+                check_bps("fees.maker_bps", maker_bps)?;
+                check_bps("fees.taker_bps", taker_bps)?;
+            }
+            None => {}
+        }
+        if self.paper.starting_balance <= Decimal::ZERO {
+            return Err(ConfigError::OutOfRange {
+                field: "paper.starting_balance",
+                value: self.paper.starting_balance.to_string(),
+                expected: "> 0",
+            });
+        }
+
+        if let Some(tg) = &self.telegram {
+            require("telegram.bot_token", tg.bot_token.expose(), "[telegram]")?;
+            require("telegram.chat_id", &tg.chat_id, "[telegram]")?;
+        }
+        for webhook in &self.webhooks {
+            require("webhooks.url", webhook.url.expose(), "[[webhooks]]")?;
+        }
+
+        if let Some(llm) = &self.llm {
+            if !llm.provider.eq_ignore_ascii_case("ollama") {
+                require("llm.api_key", llm.api_key.expose(), "[llm]")?;
+            }
+        }
+
+        if let Some(ingester) = self.ingester.as_ref().filter(|i| i.enabled) {
+            if let Some(bot) = &ingester.telegram_bot {
+                require("ingester.telegram_bot.bot_token", bot.bot_token.expose(), "ingester")?;
+            }
+            if let Some(tw) = &ingester.twitter {
+                if tw.bearer_token.as_ref().is_none_or(Secret::is_empty)
+                    && tw.nitter_instance.as_deref().unwrap_or("").is_empty()
+                {
+                    return Err(ConfigError::MissingKey {
+                        field: "ingester.twitter.bearer_token (or nitter_instance)",
+                        feature: "ingester",
+                    });
+                }
+            }
+        }
+
+        if let Some(ct) = self.copy_trade.as_ref().filter(|c| c.enabled) {
+            if !(ct.copy_ratio > 0.0 && ct.copy_ratio <= 1.0) {
+                return Err(ConfigError::OutOfRange {
+                    field: "copy_trade.copy_ratio",
+                    value: ct.copy_ratio.to_string(),
+                    expected: "(0, 1]",
+                });
+            }
+            if ct.follow_users.is_empty() && ct.follow_addresses.is_empty() {
+                return Err(ConfigError::MissingKey {
+                    field: "copy_trade.follow_users (or follow_addresses)",
+                    feature: "copy_trade",
+                });
+            }
+        }
+
+        Ok(())
+    }
+
     /// Load from default locations
     pub fn load_default() -> anyhow::Result<Self> {
         // Try loading from current directory or user config
@@ -235,6 +1047,328 @@ impl Config {
 
         anyhow::bail!("No configuration file found")
     }
+
+    /// Names of restart-only fields that differ from `other`
+    fn immutable_changes(&self, other: &Config) -> Vec<&'static str> {
+        let mut changed = Vec::new();
+        let (a, b) = (&self.polymarket, &other.polymarket);
+        if a.private_key != b.private_key {
+            changed.push("polymarket.private_key");
+        }
+        if a.funder_address != b.funder_address {
+            changed.push("polymarket.funder_address");
+        }
+        if a.chain_id != b.chain_id {
+            changed.push("polymarket.chain_id");
+        }
+        if a.signature_type != b.signature_type {
+            changed.push("polymarket.signature_type");
+        }
+        if a.clob_url != b.clob_url {
+            changed.push("polymarket.clob_url");
+        }
+        if a.gamma_url != b.gamma_url {
+            changed.push("polymarket.gamma_url");
+        }
+        if self.database.path != other.database.path {
+            changed.push("database.path");
+        }
+        changed
+    }
+}
+
+/// String that never shows up in `Debug` output or logs
+///
+/// Use `expose()` where the raw value is actually needed.
+#[derive(Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(transparent)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn new(value: impl Into<String>) -> Self {
+        Self(value.into())
+    }
+
+    /// The raw secret value
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.trim().is_empty()
+    }
+}
+
+impl std::fmt::Debug for Secret {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Secret(***)")
+    }
+}
+
+impl From<&str> for Secret {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for Secret {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+/// Prefix for per-field environment overrides
+const ENV_OVERRIDE_PREFIX: &str = "POLYBOT_";
+
+/// Config sections addressable by env overrides, longest first so
+/// `INGESTER_TWITTER_...` wins over `INGESTER_...`
+const ENV_OVERRIDE_SECTIONS: &[(&str, &str)] = &[
+    ("ingester_telegram_userbot", "ingester.telegram_userbot"),
+    ("ingester_telegram_bot", "ingester.telegram_bot"),
+    ("ingester_processing", "ingester.processing"),
+    ("ingester_twitter", "ingester.twitter"),
+    ("copy_trade", "copy_trade"),
+    ("polymarket", "polymarket"),
+    ("pnl_alerts", "pnl_alerts"),
+    ("strategy", "strategy"),
+    ("database", "database"),
+    ("telegram", "telegram"),
+    ("ingester", "ingester"),
+    ("logging", "logging"),
+    ("metrics", "metrics"),
+    ("exits", "exits"),
+    ("paper", "paper"),
+    ("risk", "risk"),
+    ("llm", "llm"),
+    ("api", "api"),
+];
+
+/// Map `POLYBOT_<SECTION>_<FIELD>` variables to config keys
+fn env_overrides(env: &HashMap<String, String>) -> Vec<(String, String)> {
+    let mut overrides: Vec<(String, String)> = env
+        .iter()
+        .filter_map(|(name, value)| {
+            let rest = name.strip_prefix(ENV_OVERRIDE_PREFIX)?.to_lowercase();
+            let key = ENV_OVERRIDE_SECTIONS.iter().find_map(|(prefix, section)| {
+                let field = rest.strip_prefix(prefix)?.strip_prefix('_')?;
+                (!field.is_empty()).then(|| format!("{}.{}", section, field))
+            });
+            if key.is_none() {
+                tracing::warn!("Ignoring {}: unknown config section", name);
+            }
+            key.map(|k| (k, value.clone()))
+        })
+        .collect();
+    overrides.sort();
+    overrides
+}
+
+/// Config file values after `${VAR}` interpolation
+#[derive(Debug, Clone)]
+struct InterpolatedFile(config::Map<String, config::Value>);
+
+impl config::Source for InterpolatedFile {
+    fn clone_into_box(&self) -> Box<dyn config::Source + Send + Sync> {
+        Box::new(self.clone())
+    }
+
+    fn collect(&self) -> Result<config::Map<String, config::Value>, config::ConfigError> {
+        Ok(self.0.clone())
+    }
+}
+
+/// Interpolate every string in `value`, descending into tables and arrays
+fn interpolate_value(value: &mut config::Value, env: &HashMap<String, String>) -> anyhow::Result<()> {
+    match &mut value.kind {
+        config::ValueKind::String(s) => *s = interpolate_env(s, env)?,
+        config::ValueKind::Table(table) => {
+            for value in table.values_mut() {
+                interpolate_value(value, env)?;
+            }
+        }
+        config::ValueKind::Array(array) => {
+            for value in array {
+                interpolate_value(value, env)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Replace `${VAR}` references with values from `env`
+fn interpolate_env(content: &str, env: &HashMap<String, String>) -> anyhow::Result<String> {
+    let mut out = String::with_capacity(content.len());
+    let mut rest = content;
+
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find('}')
+            .ok_or_else(|| anyhow::anyhow!("Unclosed ${{ in config"))?;
+        let name = &after[..end];
+        let value = env
+            .get(name)
+            .ok_or_else(|| anyhow::anyhow!("Environment variable {} referenced in config is not set", name))?;
+        out.push_str(value);
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+
+    Ok(out)
+}
+
+/// Semantic problem with a loaded config
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ConfigError {
+    #[error("{field} = {value} is out of range, expected {expected}")]
+    OutOfRange {
+        field: &'static str,
+        value: String,
+        expected: &'static str,
+    },
+
+    #[error("{field} is invalid: {reason}")]
+    Invalid {
+        field: &'static str,
+        reason: String,
+    },
+
+    #[error("{field} is required when {feature} is enabled")]
+    MissingKey {
+        field: &'static str,
+        feature: &'static str,
+    },
+}
+
+#[derive(Clone, Copy)]
+enum RangeKind {
+    /// [0, 1]
+    Closed,
+    /// (0, 1]
+    OpenClosed,
+}
+
+fn check_range(field: &'static str, value: Decimal, kind: RangeKind) -> Result<(), ConfigError> {
+    let (ok, expected) = match kind {
+        RangeKind::Closed => (value >= Decimal::ZERO && value <= Decimal::ONE, "[0, 1]"),
+        RangeKind::OpenClosed => (value > Decimal::ZERO && value <= Decimal::ONE, "(0, 1]"),
+    };
+    if ok {
+        Ok(())
+    } else {
+        Err(ConfigError::OutOfRange { field, value: value.to_string(), expected })
+    }
+}
+
+fn check_bps(field: &'static str, value: Decimal) -> Result<(), ConfigError> {
+    if value >= Decimal::ZERO && value <= Decimal::from(BPS_PER_UNIT) {
+        Ok(())
+    } else {
+        Err(ConfigError::OutOfRange { field, value: value.to_string(), expected: "[0, 10000]" })
+    }
+}
+
+fn check_positive(field: &'static str, value: u64) -> Result<(), ConfigError> {
+    if value > 0 {
+        Ok(())
+    } else {
+        Err(ConfigError::OutOfRange { field, value: value.to_string(), expected: "> 0" })
+    }
+}
+
+fn require(field: &'static str, value: &str, feature: &'static str) -> Result<(), ConfigError> {
+    if value.trim().is_empty() {
+        Err(ConfigError::MissingKey { field, feature })
+    } else {
+        Ok(())
+    }
+}
+
+/// Result of a config reload
+#[derive(Debug, Clone, Default)]
+pub struct ReloadOutcome {
+    /// Restart-only fields that changed on disk and were ignored
+    pub rejected_fields: Vec<&'static str>,
+}
+
+/// Re-reads the config file at runtime and swaps in new strategy/risk settings
+///
+/// Components built from `strategy()` / `risk()` see a reload on their next
+/// read. Connection settings (keys, endpoints, db path) can't change without
+/// a restart, so edits to them are logged and ignored.
+pub struct ConfigWatcher {
+    path: PathBuf,
+    /// Config the process started with, for spotting restart-only changes
+    initial: Config,
+    strategy: Arc<ArcSwap<StrategyConfig>>,
+    risk: Arc<ArcSwap<RiskConfig>>,
+    generation: AtomicU64,
+}
+
+impl ConfigWatcher {
+    pub fn new<P: AsRef<Path>>(path: P, config: &Config) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            initial: config.clone(),
+            strategy: Arc::new(ArcSwap::from_pointee(config.strategy.clone())),
+            risk: Arc::new(ArcSwap::from_pointee(config.risk.clone())),
+            generation: AtomicU64::new(0),
+        }
+    }
+
+    /// Live strategy settings
+    pub fn strategy(&self) -> Arc<ArcSwap<StrategyConfig>> {
+        self.strategy.clone()
+    }
+
+    /// Live risk settings
+    pub fn risk(&self) -> Arc<ArcSwap<RiskConfig>> {
+        self.risk.clone()
+    }
+
+    /// Incremented on every successful reload
+    pub fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+
+    /// Re-read the config file and swap in its strategy and risk sections
+    ///
+    /// On a parse error nothing is swapped and the error is returned.
+    pub fn reload(&self) -> anyhow::Result<ReloadOutcome> {
+        let new = Config::load(&self.path)?;
+
+        let rejected_fields = self.initial.immutable_changes(&new);
+        if !rejected_fields.is_empty() {
+            tracing::warn!(
+                "Ignoring changes to {} on reload (restart required)",
+                rejected_fields.join(", ")
+            );
+        }
+
+        self.strategy.store(Arc::new(new.strategy));
+        self.risk.store(Arc::new(new.risk));
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        tracing::info!("Reloaded strategy and risk config from {}", self.path.display());
+
+        Ok(ReloadOutcome { rejected_fields })
+    }
+
+    /// Reload whenever the process receives SIGHUP
+    #[cfg(unix)]
+    pub fn spawn_sighup(self: Arc<Self>) -> std::io::Result<tokio::task::JoinHandle<()>> {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut hangup = signal(SignalKind::hangup())?;
+        Ok(tokio::spawn(async move {
+            while hangup.recv().await.is_some() {
+                if let Err(e) = self.reload() {
+                    tracing::error!("Config reload failed: {}", e);
+                }
+            }
+        }))
+    }
 }
 
 impl Default for StrategyConfig {
@@ -244,9 +1378,24 @@ impl Default for StrategyConfig {
             min_confidence: Decimal::new(60, 2), // 60%
             kelly_fraction: Decimal::new(35, 2), // 35% Kelly
             scan_interval_secs: 180,
+            scan_concurrency: default_scan_concurrency(),
             model_update_interval_secs: 900,
             compound_enabled: true,
             compound_sqrt_scaling: true,
+            fee_rate: Decimal::ZERO,
+            slippage_estimate: Decimal::ZERO,
+            max_price_deviation: default_max_price_deviation(),
+            min_liquidity: default_min_liquidity(),
+            crypto_min_liquidity: default_crypto_min_liquidity(),
+            min_minutes_to_resolution: 0,
+            max_minutes_to_resolution: None,
+            size_decay_minutes: 0,
+            outcome_matching: OutcomeMatcher::default(),
+            allowlist: Vec::new(),
+            denylist: Vec::new(),
+            categories: Vec::new(),
+            anti_chase: AntiChaseConfig::default(),
+            abstain_below_confidence: None,
         }
     }
 }
@@ -259,6 +1408,12 @@ impl Default for RiskConfig {
             max_daily_loss_pct: Decimal::new(10, 2), // 10%
             min_balance_reserve: Decimal::new(100, 0), // $100
             max_open_positions: 10,
+            max_correlated_exposure_pct: default_max_correlated_exposure_pct(),
+            max_consecutive_losses: default_max_consecutive_losses(),
+            loss_cooldown_mins: default_loss_cooldown_mins(),
+            pnl_state_path: None,
+            max_signal_age_secs: None,
+            max_price_move: None,
         }
     }
 }
@@ -73,12 +73,13 @@ bot_token = "123:abc"
 chat_id = "12345"
 "#;
         let config: TelegramConfig = toml::from_str(toml_str).unwrap();
-        assert_eq!(config.bot_token, "123:abc");
+        assert_eq!(config.bot_token.expose(), "123:abc");
         assert_eq!(config.chat_id, "12345");
         assert!(config.notify_signals);
         assert!(config.notify_trades);
         assert!(config.notify_errors);
         assert!(config.notify_daily);
+        assert_eq!(config.heartbeat_hours, 0);
     }
 
     #[test]
@@ -107,7 +108,7 @@ signature_type = 0
         let config: PolymarketConfig = toml::from_str(toml_str).unwrap();
         assert_eq!(config.clob_url, "https://clob.polymarket.com");
         assert_eq!(config.gamma_url, "https://gamma-api.polymarket.com");
-        assert_eq!(config.private_key, "abc123");
+        assert_eq!(config.private_key.expose(), "abc123");
         assert_eq!(config.chain_id, 137);
         assert_eq!(config.signature_type, 0);
         assert!(config.funder_address.is_none());
@@ -136,7 +137,7 @@ api_key = "sk-xxx"
 "#;
         let config: LlmConfig = toml::from_str(toml_str).unwrap();
         assert_eq!(config.provider, "deepseek");
-        assert_eq!(config.api_key, "sk-xxx");
+        assert_eq!(config.api_key.expose(), "sk-xxx");
         assert!(config.model.is_none());
         assert!(config.base_url.is_none());
     }
@@ -162,7 +163,7 @@ provider = "ollama"
 "#;
         let config: LlmConfig = toml::from_str(toml_str).unwrap();
         assert_eq!(config.provider, "ollama");
-        assert_eq!(config.api_key, ""); // defaults to empty
+        assert_eq!(config.api_key.expose(), ""); // defaults to empty
     }
 
     #[test]
@@ -190,6 +191,7 @@ compound_sqrt_scaling = false
         assert_eq!(config.min_confidence, dec!(0.65));
         assert_eq!(config.kelly_fraction, dec!(0.25));
         assert_eq!(config.scan_interval_secs, 300);
+        assert_eq!(config.scan_concurrency, 4);
         assert!(!config.compound_enabled);
         assert!(!config.compound_sqrt_scaling);
     }
@@ -236,7 +238,7 @@ keywords = ["polymarket", "prediction"]
         let config: IngesterConfig = toml::from_str(toml_str).unwrap();
         assert!(config.enabled);
         let twitter = config.twitter.unwrap();
-        assert_eq!(twitter.bearer_token, Some("bearer123".to_string()));
+        assert_eq!(twitter.bearer_token.as_ref().map(|t| t.expose()), Some("bearer123"));
         assert_eq!(twitter.user_ids.len(), 2);
         assert_eq!(twitter.keywords.len(), 2);
     }
@@ -251,7 +253,7 @@ watch_chats = [123, 456, 789]
 "#;
         let config: TelegramUserbotConfig = toml::from_str(toml_str).unwrap();
         assert_eq!(config.api_id, 12345);
-        assert_eq!(config.api_hash, "abc123hash");
+        assert_eq!(config.api_hash.expose(), "abc123hash");
         assert_eq!(config.session_file, "session.dat");
         assert_eq!(config.watch_chats.len(), 3);
     }
@@ -263,7 +265,537 @@ bot_token = "123:abc"
 channels = ["@channel1", "@channel2"]
 "#;
         let config: TelegramBotIngesterConfig = toml::from_str(toml_str).unwrap();
-        assert_eq!(config.bot_token, "123:abc");
+        assert_eq!(config.bot_token.expose(), "123:abc");
         assert_eq!(config.channels.len(), 2);
     }
+
+    fn config_toml(min_edge: &str, db_path: &str) -> String {
+        format!(r#"
+[polymarket]
+clob_url = "https://clob.polymarket.com"
+gamma_url = "https://gamma-api.polymarket.com"
+private_key = "abc"
+chain_id = 137
+signature_type = 0
+
+[strategy]
+min_edge = {min_edge}
+min_confidence = 0.60
+kelly_fraction = 0.35
+scan_interval_secs = 180
+model_update_interval_secs = 900
+
+[risk]
+max_position_pct = 0.05
+max_exposure_pct = 0.50
+max_daily_loss_pct = 0.10
+min_balance_reserve = 100
+max_open_positions = 10
+
+[database]
+path = "{db_path}"
+"#)
+    }
+
+    fn edge_market() -> crate::types::Market {
+        crate::types::Market {
+            id: "m1".to_string(),
+            question: "Test?".to_string(),
+            description: None,
+            end_date: None,
+            volume: dec!(100000),
+            liquidity: dec!(50000),
+            outcomes: vec![
+                crate::types::Outcome {
+                    token_id: "m1_yes".to_string(),
+                    outcome: "Yes".to_string(),
+                    price: dec!(0.50),
+                },
+                crate::types::Outcome {
+                    token_id: "m1_no".to_string(),
+                    outcome: "No".to_string(),
+                    price: dec!(0.50),
+                },
+            ],
+            active: true,
+            closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
+        }
+    }
+
+    #[test]
+    fn test_config_reload_updates_min_edge() {
+        use crate::model::Prediction;
+        use crate::strategy::SignalGenerator;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, config_toml("0.06", "bot.db")).unwrap();
+
+        let config = Config::load(&path).unwrap();
+        let watcher = ConfigWatcher::new(&path, &config);
+        let signal_gen = SignalGenerator::with_shared_config(watcher.strategy(), watcher.risk());
+
+        // 8% edge clears a 6% threshold
+        let prediction = Prediction {
+            probability: dec!(0.58),
+            confidence: dec!(0.90),
+            reasoning: "test".to_string(),
+        };
+        assert!(signal_gen.generate(&edge_market(), &prediction).is_some());
+
+        std::fs::write(&path, config_toml("0.10", "bot.db")).unwrap();
+        let outcome = watcher.reload().unwrap();
+
+        // Same generator, new threshold
+        assert!(outcome.rejected_fields.is_empty());
+        assert_eq!(watcher.generation(), 1);
+        assert_eq!(watcher.strategy().load().min_edge, dec!(0.10));
+        assert!(signal_gen.generate(&edge_market(), &prediction).is_none());
+    }
+
+    #[test]
+    fn test_config_reload_ignores_immutable_fields() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, config_toml("0.06", "bot.db")).unwrap();
+
+        let config = Config::load(&path).unwrap();
+        let watcher = ConfigWatcher::new(&path, &config);
+
+        std::fs::write(&path, config_toml("0.08", "other.db")).unwrap();
+        let outcome = watcher.reload().unwrap();
+
+        assert_eq!(outcome.rejected_fields, vec!["database.path"]);
+        assert_eq!(watcher.strategy().load().min_edge, dec!(0.08));
+    }
+
+    #[test]
+    fn test_config_reload_keeps_old_values_on_parse_error() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, config_toml("0.06", "bot.db")).unwrap();
+
+        let config = Config::load(&path).unwrap();
+        let watcher = ConfigWatcher::new(&path, &config);
+
+        std::fs::write(&path, "[strategy]\nmin_edge = \"oops\"").unwrap();
+        assert!(watcher.reload().is_err());
+        assert_eq!(watcher.generation(), 0);
+        assert_eq!(watcher.strategy().load().min_edge, dec!(0.06));
+    }
+
+    fn valid_config() -> Config {
+        toml::from_str(&config_toml("0.06", "bot.db")).unwrap()
+    }
+
+    fn out_of_range_field(err: ConfigError) -> &'static str {
+        match err {
+            ConfigError::OutOfRange { field, .. } => field,
+            other => panic!("expected OutOfRange, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_accepts_valid_config() {
+        assert_eq!(valid_config().validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_example_config() {
+        let config = Config::load("config.example.toml").unwrap();
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_rejects_kelly_fraction_above_one() {
+        let mut config = valid_config();
+        config.strategy.kelly_fraction = dec!(2.0);
+
+        let err = config.validate().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "strategy.kelly_fraction = 2.0 is out of range, expected (0, 1]"
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_negative_min_edge() {
+        let mut config = valid_config();
+        config.strategy.min_edge = dec!(-0.01);
+        assert_eq!(out_of_range_field(config.validate().unwrap_err()), "strategy.min_edge");
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_risk_limits() {
+        let mut config = valid_config();
+        config.risk.max_position_pct = dec!(0);
+        assert_eq!(out_of_range_field(config.validate().unwrap_err()), "risk.max_position_pct");
+
+        let mut config = valid_config();
+        config.risk.max_daily_loss_pct = dec!(1.5);
+        assert_eq!(out_of_range_field(config.validate().unwrap_err()), "risk.max_daily_loss_pct");
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_pnl_alerts() {
+        let mut config = valid_config();
+        config.pnl_alerts = Some(PnlAlertConfig { take_profit_pct: dec!(0), ..Default::default() });
+        assert_eq!(out_of_range_field(config.validate().unwrap_err()), "pnl_alerts.take_profit_pct");
+
+        // Take-profit may exceed 100%, stop-loss may not
+        config.pnl_alerts = Some(PnlAlertConfig { take_profit_pct: dec!(2), ..Default::default() });
+        assert_eq!(config.validate(), Ok(()));
+        config.pnl_alerts = Some(PnlAlertConfig { stop_loss_pct: dec!(1.5), ..Default::default() });
+        assert_eq!(out_of_range_field(config.validate().unwrap_err()), "pnl_alerts.stop_loss_pct");
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_exits() {
+        let mut config = valid_config();
+        config.exits = Some(ExitConfig { take_profit_pct: Some(dec!(0)), ..Default::default() });
+        assert_eq!(out_of_range_field(config.validate().unwrap_err()), "exits.take_profit_pct");
+        config.exits = Some(ExitConfig { stop_loss_pct: Some(dec!(1.5)), ..Default::default() });
+        assert_eq!(out_of_range_field(config.validate().unwrap_err()), "exits.stop_loss_pct");
+        config.exits = Some(ExitConfig { poll_secs: 0, ..Default::default() });
+        assert_eq!(out_of_range_field(config.validate().unwrap_err()), "exits.poll_secs");
+
+        config.exits = Some(ExitConfig {
+            take_profit_pct: Some(dec!(0.3)),
+            stop_loss_pct: Some(dec!(0.1)),
+            trailing_pct: Some(dec!(0.05)),
+            ..Default::default()
+        });
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_rejects_zero_interval() {
+        let mut config = valid_config();
+        config.strategy.scan_interval_secs = 0;
+
+        let err = config.validate().unwrap_err();
+        assert_eq!(out_of_range_field(err.clone()), "strategy.scan_interval_secs");
+        assert!(err.to_string().contains("expected > 0"));
+    }
+
+    #[test]
+    fn test_validate_rejects_zero_max_price_move() {
+        let mut config = valid_config();
+        config.risk.max_price_move = Some(dec!(0));
+
+        let err = config.validate().unwrap_err();
+        assert_eq!(out_of_range_field(err), "risk.max_price_move");
+    }
+
+    #[test]
+    fn test_validate_rejects_zero_scan_concurrency() {
+        let mut config = valid_config();
+        config.strategy.scan_concurrency = 0;
+
+        let err = config.validate().unwrap_err();
+        assert_eq!(out_of_range_field(err), "strategy.scan_concurrency");
+    }
+
+    #[test]
+    fn test_binance_model_defaults_and_validation() {
+        let mut config = valid_config();
+        config.binance_model = Some(toml::from_str("").unwrap());
+        let binance = config.binance_model.as_ref().unwrap();
+        assert_eq!(binance.weight, dec!(0.3));
+        assert_eq!(binance.kline_limit, 50);
+        assert_eq!(config.validate(), Ok(()));
+
+        config.binance_model.as_mut().unwrap().weight = dec!(0);
+        let err = config.validate().unwrap_err();
+        assert_eq!(out_of_range_field(err), "binance_model.weight");
+    }
+
+    #[test]
+    fn test_base_rate_model_defaults_and_validation() {
+        let mut config = valid_config();
+        config.base_rate_model = Some(toml::from_str("min_samples = 20").unwrap());
+        let base_rate = config.base_rate_model.as_ref().unwrap();
+        assert_eq!(base_rate.weight, dec!(0.2));
+        assert_eq!(base_rate.resolutions_per_poll, 500);
+        let model = base_rate.model_config();
+        assert_eq!((model.min_samples, model.half_confidence_samples), (20, 30));
+        assert_eq!(model.max_confidence, dec!(0.8));
+        assert_eq!(config.validate(), Ok(()));
+
+        config.base_rate_model.as_mut().unwrap().max_confidence = dec!(1.2);
+        let err = config.validate().unwrap_err();
+        assert_eq!(out_of_range_field(err), "base_rate_model.max_confidence");
+    }
+
+    #[test]
+    fn test_microstructure_model_defaults_and_validation() {
+        let mut config = valid_config();
+        config.microstructure_model = Some(toml::from_str("depth_levels = 3").unwrap());
+        let micro = config.microstructure_model.as_ref().unwrap();
+        assert_eq!(micro.weight, dec!(0.1));
+        let model = micro.model_config();
+        assert_eq!((model.depth_levels, model.momentum_window), (3, 10));
+        assert_eq!(model.max_shift, dec!(0.05));
+        assert_eq!(config.validate(), Ok(()));
+
+        config.microstructure_model.as_mut().unwrap().max_shift = dec!(0);
+        let err = config.validate().unwrap_err();
+        assert_eq!(out_of_range_field(err), "microstructure_model.max_shift");
+    }
+
+    #[test]
+    fn test_abstention_floor_must_be_stricter_than_min_confidence() {
+        let mut config = valid_config();
+        config.strategy.abstain_below_confidence = Some(config.strategy.min_confidence);
+        assert_eq!(config.validate(), Ok(()));
+
+        config.strategy.abstain_below_confidence = Some(config.strategy.min_confidence - dec!(0.01));
+        let err = config.validate().unwrap_err();
+        assert_eq!(out_of_range_field(err), "strategy.abstain_below_confidence");
+    }
+
+    #[test]
+    fn test_validate_rejects_unsupported_chain() {
+        let mut config = valid_config();
+        config.polymarket.chain_id = 1;
+
+        let err = config.validate().unwrap_err();
+        assert_eq!(out_of_range_field(err), "polymarket.chain_id");
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_funder_checksum() {
+        let mut config = valid_config();
+        config.polymarket.funder_address = Some("0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266".to_string());
+        assert_eq!(config.validate(), Ok(()));
+
+        config.polymarket.funder_address = Some("0xF39Fd6e51aad88F6F4ce6aB8827279cffFb92266".to_string());
+        match config.validate().unwrap_err() {
+            ConfigError::Invalid { field, reason } => {
+                assert_eq!(field, "polymarket.funder_address");
+                assert!(reason.contains("checksum"), "{}", reason);
+            }
+            other => panic!("expected Invalid, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_multi_word_symbol_keyword() {
+        let mut config = valid_config();
+        config.symbols.map.insert("doge".to_string(), "DOGEUSDT".to_string());
+        assert_eq!(config.validate(), Ok(()));
+
+        config.symbols.map.insert("shiba inu".to_string(), "SHIBUSDT".to_string());
+        match config.validate().unwrap_err() {
+            ConfigError::Invalid { field, .. } => assert_eq!(field, "symbols.map"),
+            other => panic!("expected Invalid, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_validate_requires_keys_for_enabled_features() {
+        let mut config = valid_config();
+        config.llm = Some(LlmConfig {
+            provider: "deepseek".to_string(),
+            api_key: Secret::default(),
+            model: None,
+            base_url: None,
+        });
+        assert_eq!(
+            config.validate(),
+            Err(ConfigError::MissingKey { field: "llm.api_key", feature: "[llm]" })
+        );
+
+        // Ollama runs locally without a key
+        config.llm.as_mut().unwrap().provider = "ollama".to_string();
+        assert_eq!(config.validate(), Ok(()));
+
+        config.copy_trade = Some(CopyTradeConfig {
+            enabled: true,
+            follow_users: vec![],
+            follow_addresses: vec![],
+            copy_ratio: 0.5,
+            delay_secs: 0,
+        });
+        let err = config.validate().unwrap_err();
+        assert!(err.to_string().contains("copy_trade.follow_users"));
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, config_toml("1.5", "bot.db")).unwrap();
+
+        let err = Config::load(&path).unwrap_err();
+        assert!(err.to_string().contains("strategy.min_edge"));
+    }
+
+    fn env(vars: &[(&str, &str)]) -> std::collections::HashMap<String, String> {
+        vars.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    #[test]
+    fn test_load_interpolates_env_vars() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let toml = config_toml("0.06", "bot.db").replace("private_key = \"abc\"", "private_key = \"${TEST_PK}\"");
+        std::fs::write(&path, toml).unwrap();
+
+        let config = Config::load_with_env(&path, &env(&[("TEST_PK", "deadbeef")])).unwrap();
+        assert_eq!(config.polymarket.private_key.expose(), "deadbeef");
+
+        let err = Config::load_with_env(&path, &env(&[])).unwrap_err();
+        assert!(err.to_string().contains("TEST_PK"));
+    }
+
+    #[test]
+    fn test_interpolation_only_touches_string_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let toml = config_toml("\"${TEST_EDGE}\"", "bot.db")
+            .replace("private_key = \"abc\"", "# set private_key = \"${UNSET_IN_COMMENT}\"\nprivate_key = \"${TEST_PK}\"");
+        std::fs::write(&path, toml).unwrap();
+
+        // A value with TOML syntax in it stays a plain string
+        let vars = env(&[("TEST_PK", "dead\"beef\nx = 1"), ("TEST_EDGE", "0.07")]);
+        let config = Config::load_with_env(&path, &vars).unwrap();
+        assert_eq!(config.polymarket.private_key.expose(), "dead\"beef\nx = 1");
+        assert_eq!(config.strategy.min_edge, dec!(0.07));
+    }
+
+    #[test]
+    fn test_env_overrides_take_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        let toml = config_toml("0.06", "bot.db").replace("private_key = \"abc\"", "private_key = \"${TEST_PK}\"");
+        std::fs::write(&path, toml).unwrap();
+
+        let vars = env(&[
+            ("TEST_PK", "from_file"),
+            ("POLYBOT_POLYMARKET_PRIVATE_KEY", "from_override"),
+            ("POLYBOT_STRATEGY_MIN_EDGE", "0.09"),
+            ("POLYBOT_RISK_MAX_OPEN_POSITIONS", "3"),
+            ("POLYBOT_DATABASE_PATH", "override.db"),
+            ("POLYBOT_UNKNOWN_FIELD", "ignored"),
+        ]);
+        let config = Config::load_with_env(&path, &vars).unwrap();
+
+        assert_eq!(config.polymarket.private_key.expose(), "from_override");
+        assert_eq!(config.strategy.min_edge, dec!(0.09));
+        assert_eq!(config.risk.max_open_positions, 3);
+        assert_eq!(config.database.path, "override.db");
+        // Untouched fields keep the file value
+        assert_eq!(config.strategy.min_confidence, dec!(0.60));
+    }
+
+    #[test]
+    fn test_env_override_still_validated() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        std::fs::write(&path, config_toml("0.06", "bot.db")).unwrap();
+
+        let vars = env(&[("POLYBOT_STRATEGY_KELLY_FRACTION", "2.0")]);
+        let err = Config::load_with_env(&path, &vars).unwrap_err();
+        assert!(err.to_string().contains("strategy.kelly_fraction"));
+    }
+
+    #[test]
+    fn test_secrets_redacted_in_debug() {
+        let mut config = valid_config();
+        config.polymarket.private_key = "super-secret-key".into();
+        config.llm = Some(LlmConfig {
+            provider: "openai".to_string(),
+            api_key: "sk-live-123".into(),
+            model: None,
+            base_url: None,
+        });
+
+        let debug = format!("{:?}", config);
+        assert!(!debug.contains("super-secret-key"));
+        assert!(!debug.contains("sk-live-123"));
+        assert!(debug.contains("Secret(***)"));
+        assert_eq!(config.polymarket.private_key.expose(), "super-secret-key");
+    }
+
+    #[test]
+    fn test_logging_section() {
+        assert_eq!(valid_config().logging.format, LogFormat::Text);
+
+        let toml = format!("{}\n[logging]\nformat = \"json\"\nlevel = \"debug\"\n", config_toml("0.06", "bot.db"));
+        let config: Config = toml::from_str(&toml).unwrap();
+        assert_eq!(config.logging.format, LogFormat::Json);
+        assert_eq!(config.logging.level.as_deref(), Some("debug"));
+    }
+
+    #[test]
+    fn test_fee_model_maker_vs_taker() {
+        let fees = FeeModel::MakerTaker { maker_bps: dec!(0), taker_bps: dec!(20) };
+
+        // 200 shares at 0.45 = $90 notional
+        assert_eq!(fees.fee(dec!(200), dec!(0.45), true), dec!(0));
+        assert_eq!(fees.fee(dec!(200), dec!(0.45), false), dec!(0.18));
+
+        let flat = FeeModel::Flat { bps: dec!(10) };
+        assert_eq!(flat.fee(dec!(200), dec!(0.45), true), dec!(0.09));
+        assert_eq!(flat.fee(dec!(200), dec!(0.45), false), dec!(0.09));
+    }
+
+    #[test]
+    fn test_zero_fee_config() {
+        let config = valid_config();
+        assert_eq!(config.fees, None);
+        assert_eq!(config.fee_model(), FeeModel::default());
+        assert_eq!(config.fee_model().fee(dec!(200), dec!(0.45), false), dec!(0));
+    }
+
+    #[test]
+    fn test_fees_section() {
+        let toml = format!(
+            "{}\n[fees]\nmodel = \"maker_taker\"\nmaker_bps = 0\ntaker_bps = 20\n",
+            config_toml("0.06", "bot.db")
+        );
+        let config: Config = toml::from_str(&toml).unwrap();
+        assert_eq!(config.fee_model(), FeeModel::MakerTaker { maker_bps: dec!(0), taker_bps: dec!(20) });
+        assert_eq!(config.fee_model().rate(false), dec!(0.002));
+    }
+
+    #[test]
+    fn test_fee_model_falls_back_to_paper_fee_rate() {
+        let mut config = valid_config();
+        config.paper.fee_rate = dec!(0.01);
+        assert_eq!(config.fee_model(), FeeModel::Flat { bps: dec!(100) });
+
+        config.fees = Some(FeeModel::Flat { bps: dec!(5) });
+        assert_eq!(config.fee_model(), FeeModel::Flat { bps: dec!(5) });
+    }
+
+    #[test]
+    fn test_validate_rejects_negative_fee() {
+        let mut config = valid_config();
+        config.fees = Some(FeeModel::MakerTaker { maker_bps: dec!(-1), taker_bps: dec!(20) });
+        assert_eq!(out_of_range_field(config.validate().unwrap_err()), "fees.maker_bps");
+    }
+
+    #[test]
+    fn test_outcome_matching_section() {
+        let config: Config = toml::from_str(&config_toml("0.06", "bot.db")).unwrap();
+        assert_eq!(config.strategy.outcome_matching, crate::types::OutcomeMatcher::default());
+
+        let toml = format!(
+            "{}\n[strategy.outcome_matching]\nmode = \"synonyms\"\nsynonyms = {{ higher = \"yes\", lower = \"no\" }}\n",
+            config_toml("0.06", "bot.db")
+        );
+        let config: Config = toml::from_str(&toml).unwrap();
+        assert!(config.strategy.outcome_matching.matches("Higher", "yes"));
+        assert!(!config.strategy.outcome_matching.matches("Up", "yes"));
+
+        let toml = format!("{}\n[strategy.outcome_matching]\nmode = \"exact\"\n", config_toml("0.06", "bot.db"));
+        let config: Config = toml::from_str(&toml).unwrap();
+        assert_eq!(config.strategy.outcome_matching, crate::types::OutcomeMatcher::Exact);
+    }
 }
@@ -0,0 +1,191 @@
+//! Perpetual funding rates as a directional signal
+//!
+//! Persistent positive funding means longs are paying shorts to stay in the
+//! trade; crowded longs tend to unwind, so it is read as a mild bearish tilt
+//! (and negative funding as a mild bullish one). Rates come from the Binance
+//! USDⓈ-M futures premium index.
+
+use crate::arbitrage::{Exchange, FundingRate};
+use crate::error::{BotError, Result};
+use crate::fusion::{Signal, SignalBuilder, SignalSource};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::Deserialize;
+use std::time::Duration;
+
+const BINANCE_FUTURES_URL: &str = "https://fapi.binance.com";
+/// Binance funds every 8 hours
+const FUNDING_INTERVAL_HOURS: u64 = 8;
+/// Funding of a balanced market (Binance's default interest component, per 8h)
+const NEUTRAL_FUNDING: Decimal = dec!(0.0001);
+/// Distance from neutral at which the signal reaches full strength
+const FULL_SCALE_FUNDING: Decimal = dec!(0.001);
+/// Largest direction the signal reports; funding is a tilt, not a call
+const MAX_TILT: f64 = 0.5;
+const SIGNAL_CONFIDENCE: f64 = 0.6;
+const SIGNAL_TTL: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PremiumIndex {
+    symbol: String,
+    last_funding_rate: String,
+    next_funding_time: u64,
+    time: u64,
+}
+
+/// Fetches perp funding rates from Binance futures
+pub struct FundingRateClient {
+    http: reqwest::Client,
+    base_url: String,
+}
+
+impl FundingRateClient {
+    pub fn new() -> Self {
+        Self::with_base_url(BINANCE_FUTURES_URL)
+    }
+
+    /// Point at a different host, e.g. a local mock
+    pub fn with_base_url(base_url: &str) -> Self {
+        Self {
+            http: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Latest funding rate for a perp symbol such as "BTCUSDT"
+    pub async fn fetch(&self, symbol: &str) -> Result<FundingRate> {
+        let url = format!("{}/fapi/v1/premiumIndex?symbol={}", self.base_url, symbol);
+        let resp = self.http.get(&url).send().await?;
+        if !resp.status().is_success() {
+            return Err(BotError::from_status(
+                resp.status(),
+                format!("Binance premium index for {}", symbol),
+            ));
+        }
+
+        let index: PremiumIndex = resp.json().await?;
+        let rate = index
+            .last_funding_rate
+            .parse::<Decimal>()
+            .map_err(|e| BotError::Api(format!("Bad funding rate {:?}: {}", index.last_funding_rate, e)))?;
+
+        Ok(FundingRate {
+            exchange: Exchange::Binance,
+            symbol: index.symbol,
+            rate,
+            predicted_rate: None,
+            timestamp: index.time / 1000,
+            next_funding_time: index.next_funding_time / 1000,
+            interval_hours: FUNDING_INTERVAL_HOURS,
+        })
+    }
+
+    /// Fetch the rate and turn it into a fusion signal
+    pub async fn signal(&self, symbol: &str) -> Result<Signal> {
+        Ok(funding_signal(&self.fetch(symbol).await?))
+    }
+}
+
+impl Default for FundingRateClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fusion signal for a funding rate
+///
+/// Direction opposes the funding's distance from neutral, scaled to
+/// `MAX_TILT` at `FULL_SCALE_FUNDING` away; strength is that distance as a
+/// fraction of full scale.
+pub fn funding_signal(funding: &FundingRate) -> Signal {
+    let scaled = ((funding.rate - NEUTRAL_FUNDING) / FULL_SCALE_FUNDING)
+        .clamp(-Decimal::ONE, Decimal::ONE)
+        .to_f64()
+        .unwrap_or(0.0);
+
+    SignalBuilder::new(SignalSource::FundingRate)
+        .direction(-scaled * MAX_TILT)
+        .strength(scaled.abs())
+        .confidence(SIGNAL_CONFIDENCE)
+        .ttl(SIGNAL_TTL)
+        .metadata("funding_rate", funding.rate.to_f64().unwrap_or(0.0))
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate(rate: Decimal) -> FundingRate {
+        FundingRate {
+            exchange: Exchange::Binance,
+            symbol: "BTCUSDT".to_string(),
+            rate,
+            predicted_rate: None,
+            timestamp: 0,
+            next_funding_time: 0,
+            interval_hours: FUNDING_INTERVAL_HOURS,
+        }
+    }
+
+    #[test]
+    fn test_funding_signal_direction_and_strength() {
+        // 0.06% per 8h: crowded longs, half-strength bearish tilt
+        let crowded = funding_signal(&rate(dec!(0.0006)));
+        assert_eq!(crowded.source, SignalSource::FundingRate);
+        assert!((crowded.direction + 0.25).abs() < 1e-9);
+        assert!((crowded.strength - 0.5).abs() < 1e-9);
+        assert_eq!(crowded.metadata["funding_rate"], 0.0006);
+
+        // Negative funding: shorts paying, bullish tilt
+        let short_squeeze = funding_signal(&rate(dec!(-0.0004)));
+        assert!((short_squeeze.direction - 0.25).abs() < 1e-9);
+
+        // Neutral funding says nothing
+        let neutral = funding_signal(&rate(dec!(0.0001)));
+        assert_eq!(neutral.direction, 0.0);
+        assert_eq!(neutral.strength, 0.0);
+
+        // Extreme funding saturates at the maximum tilt
+        let extreme = funding_signal(&rate(dec!(0.01)));
+        assert_eq!(extreme.direction, -MAX_TILT);
+        assert_eq!(extreme.strength, 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_parses_premium_index() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = axum::Router::new().route(
+            "/fapi/v1/premiumIndex",
+            axum::routing::get(|| async {
+                axum::Json(serde_json::json!({
+                    "symbol": "BTCUSDT",
+                    "markPrice": "85000.10",
+                    "indexPrice": "84990.00",
+                    "lastFundingRate": "0.00030000",
+                    "interestRate": "0.00010000",
+                    "nextFundingTime": 1700006400000u64,
+                    "time": 1700000000000u64
+                }))
+            }),
+        );
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        let client = FundingRateClient::with_base_url(&format!("http://{}", addr));
+        let funding = client.fetch("BTCUSDT").await.unwrap();
+        assert_eq!(funding.rate, dec!(0.0003));
+        assert_eq!(funding.next_funding_time, 1700006400);
+        assert_eq!(funding.interval_hours, 8);
+
+        let signal = client.signal("BTCUSDT").await.unwrap();
+        assert!(signal.direction < 0.0);
+    }
+}
@@ -4,11 +4,20 @@
 //! - Data validation and cleaning
 //! - Multi-source aggregation (Polymarket + Binance + others)
 //! - Rate limiting and caching
+//! - Perp funding rates as a fusion signal
+//! - Recording and replaying the market websocket price tape
+//! - Mapping market questions to exchange tickers
 //!
 //! Note: WebSocket is now in `client::polymarket_ws`
 
 pub mod aggregator;
 pub mod cleaning;
+pub mod funding;
+pub mod symbols;
+pub mod tape;
 
 pub use aggregator::{DataAggregator, AggregatedPrice, DataSource};
 pub use cleaning::{DataCleaner, CleaningConfig, ValidationResult, Anomaly};
+pub use funding::{funding_signal, FundingRateClient};
+pub use symbols::{SymbolMapConfig, SymbolMapper, SymbolMatch};
+pub use tape::{TapeEntry, TapePlayer, TapeRecorder, TapeUpdate};
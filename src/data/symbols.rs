@@ -0,0 +1,188 @@
+//! Market question to exchange ticker mapping
+//!
+//! A question maps to the symbol of the keywords it mentions. Keywords
+//! match whole words, so "eth" does not match "whether". A question naming
+//! two different symbols ("Will Bitcoin outperform Ethereum?") is
+//! ambiguous and maps to nothing; one naming none maps to the fallback, if
+//! any.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+/// Built-in keyword mappings to Binance spot pairs
+const DEFAULT_MAPPINGS: [(&str, &str); 7] = [
+    ("bitcoin", "BTCUSDT"),
+    ("btc", "BTCUSDT"),
+    ("ethereum", "ETHUSDT"),
+    ("eth", "ETHUSDT"),
+    ("solana", "SOLUSDT"),
+    ("sol", "SOLUSDT"),
+    ("xrp", "XRPUSDT"),
+];
+
+/// `[symbols]` config section
+#[derive(Debug, Clone, Deserialize)]
+pub struct SymbolMapConfig {
+    /// Keep the built-in coin mappings alongside `map` (default: true)
+    #[serde(default = "default_true")]
+    pub include_defaults: bool,
+    /// Keyword to exchange symbol, e.g. `doge = "DOGEUSDT"`; overrides a
+    /// built-in mapping for the same keyword
+    #[serde(default)]
+    pub map: BTreeMap<String, String>,
+    /// Symbol for questions that mention no keyword
+    #[serde(default)]
+    pub fallback: Option<String>,
+}
+
+impl Default for SymbolMapConfig {
+    fn default() -> Self {
+        Self {
+            include_defaults: true,
+            map: BTreeMap::new(),
+            fallback: None,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// How a question resolved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SymbolMatch<'a> {
+    Symbol(&'a str),
+    /// Mentions keywords for several symbols, sorted
+    Ambiguous(Vec<&'a str>),
+    /// No keyword and no fallback
+    Unmapped,
+}
+
+/// Maps market questions to exchange tickers by keyword
+#[derive(Debug, Clone)]
+pub struct SymbolMapper {
+    /// Lowercase keyword to symbol
+    keywords: BTreeMap<String, String>,
+    fallback: Option<String>,
+}
+
+impl Default for SymbolMapper {
+    fn default() -> Self {
+        Self::from_config(&SymbolMapConfig::default())
+    }
+}
+
+impl SymbolMapper {
+    pub fn from_config(config: &SymbolMapConfig) -> Self {
+        let defaults = DEFAULT_MAPPINGS
+            .iter()
+            .filter(|_| config.include_defaults)
+            .map(|(k, v)| (k.to_string(), v.to_string()));
+        let custom = config
+            .map
+            .iter()
+            .map(|(k, v)| (k.trim().to_lowercase(), v.clone()));
+        Self {
+            keywords: defaults.chain(custom).collect(),
+            fallback: config.fallback.clone(),
+        }
+    }
+
+    /// Resolve a question, telling ambiguity apart from no match
+    pub fn resolve(&self, question: &str) -> SymbolMatch<'_> {
+        let q = question.to_lowercase();
+        let mut symbols: Vec<&str> = q
+            .split(|c: char| !c.is_alphanumeric())
+            .filter_map(|word| self.keywords.get(word))
+            .map(String::as_str)
+            .collect();
+        symbols.sort_unstable();
+        symbols.dedup();
+
+        match symbols.as_slice() {
+            [symbol] => SymbolMatch::Symbol(symbol),
+            [] => match &self.fallback {
+                Some(fallback) => SymbolMatch::Symbol(fallback),
+                None => SymbolMatch::Unmapped,
+            },
+            _ => SymbolMatch::Ambiguous(symbols),
+        }
+    }
+
+    /// Symbol for a question, `None` when unmapped or ambiguous
+    pub fn symbol_for(&self, question: &str) -> Option<&str> {
+        match self.resolve(question) {
+            SymbolMatch::Symbol(symbol) => Some(symbol),
+            SymbolMatch::Ambiguous(symbols) => {
+                tracing::debug!("Ambiguous symbols {:?} in {:?}", symbols, question);
+                None
+            }
+            SymbolMatch::Unmapped => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_mappings() {
+        let mapper = SymbolMapper::default();
+        assert_eq!(mapper.symbol_for("Will Bitcoin go up?"), Some("BTCUSDT"));
+        assert_eq!(mapper.symbol_for("BTC above 100k?"), Some("BTCUSDT"));
+        assert_eq!(mapper.symbol_for("ETH price prediction"), Some("ETHUSDT"));
+        assert_eq!(mapper.symbol_for("Solana hourly"), Some("SOLUSDT"));
+        assert_eq!(mapper.symbol_for("XRP up or down"), Some("XRPUSDT"));
+        // Same symbol twice is not ambiguous
+        assert_eq!(mapper.symbol_for("Bitcoin (BTC) Up or Down"), Some("BTCUSDT"));
+        assert_eq!(mapper.symbol_for("Random market"), None);
+        // Keywords inside other words don't count
+        assert_eq!(mapper.symbol_for("Whether the resolution holds"), None);
+    }
+
+    #[test]
+    fn test_custom_mapping() {
+        let config: SymbolMapConfig = toml::from_str(
+            r#"
+fallback = "BTCUSDT"
+[map]
+doge = "DOGEUSDT"
+Dogecoin = "DOGEUSDT"
+eth = "ETHUSDC"
+"#,
+        )
+        .unwrap();
+        let mapper = SymbolMapper::from_config(&config);
+        assert_eq!(mapper.symbol_for("Dogecoin Up or Down"), Some("DOGEUSDT"));
+        assert_eq!(mapper.symbol_for("ETH Up or Down"), Some("ETHUSDC"));
+        assert_eq!(mapper.symbol_for("Solana Up or Down"), Some("SOLUSDT"));
+        assert_eq!(mapper.symbol_for("Crypto market cap up?"), Some("BTCUSDT"));
+
+        let only_custom = SymbolMapper::from_config(&SymbolMapConfig {
+            include_defaults: false,
+            ..config
+        });
+        assert_eq!(only_custom.symbol_for("Solana Up or Down"), Some("BTCUSDT"));
+    }
+
+    #[test]
+    fn test_ambiguous_question() {
+        let mapper = SymbolMapper::default();
+        let question = "Will Bitcoin outperform Ethereum this week?";
+        assert_eq!(
+            mapper.resolve(question),
+            SymbolMatch::Ambiguous(vec!["BTCUSDT", "ETHUSDT"])
+        );
+        assert_eq!(mapper.symbol_for(question), None);
+
+        // A fallback only covers questions with no keyword at all
+        let with_fallback = SymbolMapper::from_config(&SymbolMapConfig {
+            fallback: Some("BTCUSDT".to_string()),
+            ..SymbolMapConfig::default()
+        });
+        assert_eq!(with_fallback.symbol_for(question), None);
+        assert_eq!(with_fallback.resolve("Random market"), SymbolMatch::Symbol("BTCUSDT"));
+    }
+}
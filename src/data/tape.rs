@@ -0,0 +1,506 @@
+//! Price tape recording and replay
+//!
+//! `TapeRecorder` writes every book snapshot, quote change and trade print
+//! from the market websocket to a JSON Lines tape, one timestamped update
+//! per line. `TapePlayer` reads a tape back and feeds it, in order and
+//! paced like the original session, into a `DataAggregator` or an
+//! `EventEngine`, so a live session can be replayed deterministically.
+
+use super::aggregator::{DataAggregator, DataSource, SourcePrice};
+use crate::client::polymarket_ws::{MarketEvent, OrderLevel};
+use crate::error::{BotError, Result};
+use crate::events::{Event, EventEngine, EventPayload, EventType, MarketDataPayload};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+/// Event source of replayed events
+const TAPE_SOURCE: &str = "tape";
+
+/// One market data update on the tape
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "k", rename_all = "snake_case")]
+pub enum TapeUpdate {
+    /// Full order book snapshot, (price, size) levels best first
+    Book {
+        token_id: String,
+        market_id: String,
+        bids: Vec<(Decimal, Decimal)>,
+        asks: Vec<(Decimal, Decimal)>,
+    },
+    /// Top of book after a price level change
+    Quote {
+        token_id: String,
+        market_id: String,
+        bid: Option<Decimal>,
+        ask: Option<Decimal>,
+    },
+    /// Trade print
+    Trade {
+        token_id: String,
+        market_id: String,
+        price: Decimal,
+        size: Decimal,
+    },
+}
+
+impl TapeUpdate {
+    /// Updates carried by a websocket event
+    ///
+    /// A price change message yields one quote per asset; tick size changes
+    /// and unknown messages yield nothing.
+    pub fn from_event(event: &MarketEvent) -> Vec<Self> {
+        match event {
+            MarketEvent::Book(book) => vec![Self::Book {
+                token_id: book.asset_id.clone(),
+                market_id: book.market.clone(),
+                bids: levels(&book.bids),
+                asks: levels(&book.asks),
+            }],
+            MarketEvent::PriceChange(pc) => pc
+                .price_changes
+                .iter()
+                .map(|change| Self::Quote {
+                    token_id: change.asset_id.clone(),
+                    market_id: pc.market.clone(),
+                    bid: Decimal::from_str(&change.best_bid).ok(),
+                    ask: Decimal::from_str(&change.best_ask).ok(),
+                })
+                .collect(),
+            MarketEvent::LastTradePrice(trade) => {
+                match (Decimal::from_str(&trade.price), Decimal::from_str(&trade.size)) {
+                    (Ok(price), Ok(size)) => vec![Self::Trade {
+                        token_id: trade.asset_id.clone(),
+                        market_id: trade.market.clone(),
+                        price,
+                        size,
+                    }],
+                    _ => Vec::new(),
+                }
+            }
+            MarketEvent::TickSizeChange(_) | MarketEvent::Unknown(_) | MarketEvent::Fatal(_) => {
+                Vec::new()
+            }
+        }
+    }
+
+    pub fn token_id(&self) -> &str {
+        match self {
+            Self::Book { token_id, .. } | Self::Quote { token_id, .. } | Self::Trade { token_id, .. } => token_id,
+        }
+    }
+}
+
+/// A tape update with the time it was received
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TapeEntry {
+    #[serde(rename = "t", with = "chrono::serde::ts_milliseconds")]
+    pub at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub update: TapeUpdate,
+}
+
+impl TapeEntry {
+    /// The entry as a market data event stamped with its recorded time
+    pub fn to_event(&self) -> Event {
+        let payload = match &self.update {
+            TapeUpdate::Book { token_id, bids, asks, .. } => {
+                MarketDataPayload::orderbook_update(token_id, bids.clone(), asks.clone(), TAPE_SOURCE)
+            }
+            TapeUpdate::Quote { token_id, bid, ask, .. } => MarketDataPayload {
+                bid: *bid,
+                ask: *ask,
+                ..MarketDataPayload::orderbook_update(token_id, Vec::new(), Vec::new(), TAPE_SOURCE)
+            },
+            TapeUpdate::Trade { token_id, price, .. } => MarketDataPayload {
+                last: Some(*price),
+                ..MarketDataPayload::orderbook_update(token_id, Vec::new(), Vec::new(), TAPE_SOURCE)
+            },
+        };
+        Event::new(EventType::MarketData, TAPE_SOURCE, EventPayload::MarketData(payload)).with_timestamp(self.at)
+    }
+}
+
+/// Appends market data updates to a tape file
+pub struct TapeRecorder {
+    path: PathBuf,
+    writer: Mutex<BufWriter<File>>,
+}
+
+impl TapeRecorder {
+    /// Append to the tape at `path`, creating it and its directory if needed
+    pub fn create(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| BotError::Internal(format!("Failed to create tape dir: {}", e)))?;
+        }
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| BotError::Internal(format!("Failed to open tape {}: {}", path.display(), e)))?;
+        Ok(Self {
+            path,
+            writer: Mutex::new(BufWriter::new(file)),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Record an update received at `at`
+    pub fn record_at(&self, at: DateTime<Utc>, update: TapeUpdate) -> Result<()> {
+        let mut line = serde_json::to_vec(&TapeEntry { at, update })?;
+        line.push(b'\n');
+        self.writer
+            .lock()
+            .unwrap()
+            .write_all(&line)
+            .map_err(|e| BotError::Internal(format!("Failed to write tape: {}", e)))
+    }
+
+    /// Record every update in a websocket event, stamped now
+    ///
+    /// Returns the number of updates written.
+    pub fn record_event(&self, event: &MarketEvent) -> Result<usize> {
+        let now = Utc::now();
+        let updates = TapeUpdate::from_event(event);
+        let count = updates.len();
+        for update in updates {
+            self.record_at(now, update)?;
+        }
+        Ok(count)
+    }
+
+    /// Write buffered updates to disk
+    pub fn flush(&self) -> Result<()> {
+        self.writer
+            .lock()
+            .unwrap()
+            .flush()
+            .map_err(|e| BotError::Internal(format!("Failed to flush tape: {}", e)))
+    }
+
+    /// Record a websocket event stream while passing it through
+    ///
+    /// Spawns a task that records each event from `events` and forwards
+    /// it to the returned receiver, so the tape can sit in front of a
+    /// `PriceStream`. The tape is flushed when the stream ends.
+    pub fn tee(self: Arc<Self>, mut events: mpsc::Receiver<MarketEvent>) -> mpsc::Receiver<MarketEvent> {
+        let (tx, rx) = mpsc::channel(100);
+        tokio::spawn(async move {
+            while let Some(event) = events.recv().await {
+                if let Err(e) = self.record_event(&event) {
+                    warn!("Tape recording failed: {}", e);
+                }
+                if tx.send(event).await.is_err() {
+                    break;
+                }
+            }
+            if let Err(e) = self.flush() {
+                warn!("Tape flush failed: {}", e);
+            }
+        });
+        rx
+    }
+}
+
+impl Drop for TapeRecorder {
+    fn drop(&mut self) {
+        if let Ok(writer) = self.writer.get_mut() {
+            let _ = writer.flush();
+        }
+    }
+}
+
+/// Replays a recorded tape
+pub struct TapePlayer {
+    entries: Vec<TapeEntry>,
+    speed: f64,
+}
+
+impl TapePlayer {
+    /// Load a tape written by `TapeRecorder`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path)
+            .map_err(|e| BotError::Internal(format!("Failed to open tape {}: {}", path.display(), e)))?;
+        let mut entries = Vec::new();
+        for (n, line) in BufReader::new(file).lines().enumerate() {
+            let line = line.map_err(|e| BotError::Internal(format!("Failed to read tape: {}", e)))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = serde_json::from_str(&line)
+                .map_err(|e| BotError::Validation(format!("{}:{}: {}", path.display(), n + 1, e)))?;
+            entries.push(entry);
+        }
+        Ok(Self::from_entries(entries))
+    }
+
+    /// Replay `entries` in the order given, in real time
+    pub fn from_entries(entries: Vec<TapeEntry>) -> Self {
+        Self { entries, speed: 1.0 }
+    }
+
+    /// Playback speed: 1 is real time, 10 ten times faster; 0 replays
+    /// without pausing between updates
+    pub fn with_speed(mut self, speed: f64) -> Self {
+        self.speed = speed;
+        self
+    }
+
+    pub fn entries(&self) -> &[TapeEntry] {
+        &self.entries
+    }
+
+    /// Feed the tape into an aggregator as Polymarket prices
+    ///
+    /// Prices are stamped with the replay time so the aggregator's
+    /// freshness checks treat them as live. Trades update the last price
+    /// and keep the token's latest quote. Returns the number of updates.
+    pub async fn play_into_aggregator(&self, aggregator: &DataAggregator) -> usize {
+        let mut latest: HashMap<&str, SourcePrice> = HashMap::new();
+        for (delay, entry) in self.paced() {
+            pause(delay).await;
+            let price = latest.entry(entry.update.token_id()).or_insert_with(|| SourcePrice {
+                source: DataSource::Polymarket,
+                bid: None,
+                ask: None,
+                last: None,
+                volume_24h: None,
+                timestamp: Utc::now(),
+                weight: Decimal::ONE,
+            });
+            match &entry.update {
+                TapeUpdate::Book { bids, asks, .. } => {
+                    price.bid = bids.first().map(|(p, _)| *p);
+                    price.ask = asks.first().map(|(p, _)| *p);
+                }
+                TapeUpdate::Quote { bid, ask, .. } => {
+                    price.bid = *bid;
+                    price.ask = *ask;
+                }
+                TapeUpdate::Trade { price: last, .. } => price.last = Some(*last),
+            }
+            price.timestamp = Utc::now();
+            aggregator.update(entry.update.token_id(), price.clone());
+        }
+        self.entries.len()
+    }
+
+    /// Publish the tape into an engine as market data events
+    ///
+    /// Events keep their recorded timestamps. Returns the number published.
+    pub async fn play_into_engine(&self, engine: &EventEngine) -> Result<usize> {
+        for (delay, entry) in self.paced() {
+            pause(delay).await;
+            engine
+                .publish(entry.to_event())
+                .await
+                .map_err(|e| BotError::Internal(format!("Failed to publish replayed event: {}", e)))?;
+        }
+        Ok(self.entries.len())
+    }
+
+    /// Each entry with the pause to take before it
+    fn paced(&self) -> impl Iterator<Item = (Duration, &TapeEntry)> {
+        let speed = self.speed;
+        let mut previous: Option<DateTime<Utc>> = None;
+        self.entries.iter().map(move |entry| {
+            let gap = previous.map_or(chrono::Duration::zero(), |p| entry.at - p);
+            previous = Some(entry.at);
+            let delay = match gap.to_std() {
+                Ok(gap) if speed > 0.0 && speed.is_finite() => gap.div_f64(speed),
+                _ => Duration::ZERO,
+            };
+            (delay, entry)
+        })
+    }
+}
+
+async fn pause(delay: Duration) {
+    if !delay.is_zero() {
+        tokio::time::sleep(delay).await;
+    }
+}
+
+fn levels(levels: &[OrderLevel]) -> Vec<(Decimal, Decimal)> {
+    levels
+        .iter()
+        .filter_map(|l| Some((l.price_decimal()?, l.size_decimal()?)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::polymarket_ws::{BookMessage, LastTradePriceMessage, PriceChange, PriceChangeMessage};
+    use crate::events::{EventEngineConfig, EventError, EventHandler};
+    use chrono::TimeZone;
+    use rust_decimal_macros::dec;
+
+    fn book(asset_id: &str) -> MarketEvent {
+        MarketEvent::Book(BookMessage {
+            event_type: "book".to_string(),
+            asset_id: asset_id.to_string(),
+            market: "0xabc".to_string(),
+            bids: vec![OrderLevel { price: "0.48".to_string(), size: "30".to_string() }],
+            asks: vec![OrderLevel { price: "0.52".to_string(), size: "25".to_string() }],
+            timestamp: String::new(),
+            hash: String::new(),
+        })
+    }
+
+    fn quote(asset_id: &str, bid: &str, ask: &str) -> MarketEvent {
+        MarketEvent::PriceChange(PriceChangeMessage {
+            event_type: "price_change".to_string(),
+            market: "0xabc".to_string(),
+            price_changes: vec![PriceChange {
+                asset_id: asset_id.to_string(),
+                price: bid.to_string(),
+                size: "10".to_string(),
+                side: "BUY".to_string(),
+                hash: String::new(),
+                best_bid: bid.to_string(),
+                best_ask: ask.to_string(),
+            }],
+            timestamp: String::new(),
+        })
+    }
+
+    fn trade(asset_id: &str, price: &str) -> MarketEvent {
+        MarketEvent::LastTradePrice(LastTradePriceMessage {
+            event_type: "last_trade_price".to_string(),
+            asset_id: asset_id.to_string(),
+            market: "0xabc".to_string(),
+            price: price.to_string(),
+            size: "5".to_string(),
+            side: "BUY".to_string(),
+            fee_rate_bps: String::new(),
+            timestamp: String::new(),
+        })
+    }
+
+    /// Records the payload of every market data event it sees
+    #[derive(Default)]
+    struct Collect(Mutex<Vec<(DateTime<Utc>, MarketDataPayload)>>);
+
+    #[async_trait::async_trait]
+    impl EventHandler for Collect {
+        fn name(&self) -> &str {
+            "collect"
+        }
+
+        fn handles(&self) -> Vec<EventType> {
+            vec![EventType::MarketData]
+        }
+
+        async fn handle(&self, event: &Event) -> std::result::Result<Vec<Event>, EventError> {
+            if let EventPayload::MarketData(data) = &event.payload {
+                self.0.lock().unwrap().push((event.timestamp, data.clone()));
+            }
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_tape_round_trip_replays_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tapes/session.jsonl");
+        let start = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+
+        let recorder = TapeRecorder::create(&path).unwrap();
+        let events = [book("t1"), quote("t1", "0.49", "0.51"), trade("t1", "0.51"), quote("t2", "0.30", "0.34")];
+        for (i, event) in events.iter().enumerate() {
+            for update in TapeUpdate::from_event(event) {
+                recorder.record_at(start + chrono::Duration::seconds(i as i64 * 10), update).unwrap();
+            }
+        }
+        // Tick size changes carry no prices
+        assert_eq!(recorder.record_event(&MarketEvent::Unknown("{}".to_string())).unwrap(), 0);
+        drop(recorder);
+
+        let player = TapePlayer::open(&path).unwrap().with_speed(2.0);
+        assert_eq!(player.entries().len(), 4);
+        assert_eq!(
+            player.entries()[2].update,
+            TapeUpdate::Trade {
+                token_id: "t1".to_string(),
+                market_id: "0xabc".to_string(),
+                price: dec!(0.51),
+                size: dec!(5),
+            }
+        );
+
+        // Into the engine, in order, with the recorded timestamps and paced
+        // at twice real time: 30s of tape in 15s
+        let engine = EventEngine::new(EventEngineConfig::default());
+        let collect = Arc::new(Collect::default());
+        engine.register_handler(collect.clone()).await;
+        let began = tokio::time::Instant::now();
+        assert_eq!(player.play_into_engine(&engine).await.unwrap(), 4);
+        assert_eq!(began.elapsed(), Duration::from_secs(15));
+
+        let seen = collect.0.lock().unwrap().clone();
+        let times: Vec<_> = seen.iter().map(|(at, _)| (*at - start).num_seconds()).collect();
+        assert_eq!(times, vec![0, 10, 20, 30]);
+        assert_eq!(seen[0].1.bids, vec![(dec!(0.48), dec!(30))]);
+        assert_eq!((seen[1].1.bid, seen[1].1.ask), (Some(dec!(0.49)), Some(dec!(0.51))));
+        assert_eq!(seen[2].1.last, Some(dec!(0.51)));
+        assert_eq!(seen[3].1.symbol, "t2");
+
+        // Into the aggregator: the trade keeps the quote before it
+        let aggregator = DataAggregator::with_defaults();
+        let player = player.with_speed(0.0);
+        let began = tokio::time::Instant::now();
+        assert_eq!(player.play_into_aggregator(&aggregator).await, 4);
+        assert_eq!(began.elapsed(), Duration::ZERO);
+        let price = aggregator.aggregate("t1").unwrap();
+        assert_eq!(price.sources[0].bid, Some(dec!(0.49)));
+        assert_eq!(price.sources[0].last, Some(dec!(0.51)));
+        assert_eq!(price.price, dec!(0.50));
+    }
+
+    #[tokio::test]
+    async fn test_tee_records_and_forwards() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        let recorder = Arc::new(TapeRecorder::create(&path).unwrap());
+
+        let (tx, rx) = mpsc::channel(10);
+        let mut forwarded = recorder.tee(rx);
+        tx.send(book("t1")).await.unwrap();
+        tx.send(trade("t1", "0.50")).await.unwrap();
+        drop(tx);
+
+        assert!(matches!(forwarded.recv().await, Some(MarketEvent::Book(_))));
+        assert!(matches!(forwarded.recv().await, Some(MarketEvent::LastTradePrice(_))));
+        assert!(forwarded.recv().await.is_none());
+
+        let player = TapePlayer::open(&path).unwrap();
+        assert_eq!(player.entries().len(), 2);
+        assert!(matches!(player.entries()[0].update, TapeUpdate::Book { .. }));
+    }
+
+    #[test]
+    fn test_open_reports_bad_line() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("session.jsonl");
+        std::fs::write(&path, "{\"t\":0,\"k\":\"trade\"}\n").unwrap();
+
+        let err = TapePlayer::open(&path).err().unwrap();
+        assert!(matches!(err, BotError::Validation(msg) if msg.contains("session.jsonl:1")));
+    }
+}
@@ -1,7 +1,18 @@
 //! Error types for the trading bot
+//!
+//! Variants are split so callers can tell transient failures (network
+//! drops, timeouts, rate limits, 5xx) from permanent ones (bad credentials,
+//! invalid requests); see `BotError::is_retryable`. Errors can also carry
+//! the market, order and operation they concern (`context_market` and
+//! friends) so logs and alerts say where a failure happened.
 
+use std::fmt;
+use std::time::Duration;
 use thiserror::Error;
 
+/// Back-off used when a 429 arrives without a usable `Retry-After`
+const DEFAULT_RETRY_AFTER_SECS: u64 = 10;
+
 #[derive(Error, Debug)]
 pub enum BotError {
     #[error("API error: {0}")]
@@ -11,7 +22,17 @@ pub enum BotError {
     Auth(String),
 
     #[error("Network error: {0}")]
-    Network(#[from] reqwest::Error),
+    Network(reqwest::Error),
+
+    /// Could not connect, so the request was never sent
+    #[error("Connection failed: {0}")]
+    Connect(String),
+
+    #[error("HTTP {status}: {message}")]
+    Http { status: u16, message: String },
+
+    #[error("Invalid request: {0}")]
+    Validation(String),
 
     #[error("WebSocket error: {0}")]
     WebSocket(String),
@@ -20,7 +41,7 @@ pub enum BotError {
     Json(#[from] serde_json::Error),
 
     #[error("Database error: {0}")]
-    Database(#[from] sqlx::Error),
+    Database(sqlx::Error),
 
     #[error("Configuration error: {0}")]
     Config(String),
@@ -46,11 +67,337 @@ pub enum BotError {
     #[error("Order rejected: {0}")]
     OrderRejected(String),
 
+    #[error("Request timed out: {0}")]
+    Timeout(String),
+
     #[error("Rate limited: retry after {retry_after_secs} seconds")]
     RateLimited { retry_after_secs: u64 },
 
     #[error("Internal error: {0}")]
     Internal(String),
+
+    #[error("{source} [{context}]")]
+    Context {
+        source: Box<BotError>,
+        context: ErrorContext,
+    },
+}
+
+/// Where an error happened
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorContext {
+    pub market_id: Option<String>,
+    pub order_id: Option<String>,
+    /// What was being attempted, e.g. "place_order"
+    pub operation: Option<String>,
+}
+
+impl fmt::Display for ErrorContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fields = [
+            ("market", &self.market_id),
+            ("order", &self.order_id),
+            ("operation", &self.operation),
+        ];
+        let mut first = true;
+        for (name, value) in fields {
+            if let Some(value) = value {
+                if !first {
+                    f.write_str(" ")?;
+                }
+                write!(f, "{}={}", name, value)?;
+                first = false;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl BotError {
+    /// Error for a non-success HTTP status
+    ///
+    /// 401/403 become `Auth`, 429 `RateLimited`, 400/422 `Validation` and
+    /// anything else `Http`.
+    pub fn from_status(status: reqwest::StatusCode, message: impl Into<String>) -> Self {
+        let message = message.into();
+        match status.as_u16() {
+            401 | 403 => Self::Auth(format!("{}: {}", status, message)),
+            429 => Self::RateLimited { retry_after_secs: DEFAULT_RETRY_AFTER_SECS },
+            400 | 422 => Self::Validation(format!("{}: {}", status, message)),
+            code => Self::Http { status: code, message },
+        }
+    }
+
+    /// Whether the same request may succeed if tried again later
+    ///
+    /// True for dropped connections, timeouts, rate limits, 5xx/408
+    /// responses and busy or unreachable databases. Auth failures, invalid
+    /// requests, risk rejections and the like fail the same way every time.
+    pub fn is_retryable(&self) -> bool {
+        match self.root() {
+            Self::Network(e) => !(e.is_builder() || e.is_decode() || e.is_redirect()),
+            Self::Connect(_) => true,
+            Self::Http { status, .. } => *status >= 500 || *status == 408,
+            Self::Timeout(_) | Self::RateLimited { .. } | Self::WebSocket(_) => true,
+            Self::Database(e) => match e {
+                sqlx::Error::PoolTimedOut | sqlx::Error::Io(_) => true,
+                // SQLITE_BUSY and SQLITE_LOCKED
+                sqlx::Error::Database(db) => matches!(db.code().as_deref(), Some("5") | Some("6")),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Whether a failed request certainly never reached the server
+    ///
+    /// True only for connection failures and rate limiting. A timeout or
+    /// 5xx may come after the server acted on the request, so a request
+    /// that is not idempotent must be reconciled before it is resent.
+    pub fn never_reached_server(&self) -> bool {
+        matches!(self.root(), Self::Connect(_) | Self::RateLimited { .. })
+    }
+
+    /// How long to wait before retrying, when the server said
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self.root() {
+            Self::RateLimited { retry_after_secs } => Some(Duration::from_secs(*retry_after_secs)),
+            _ => None,
+        }
+    }
+}
+
+impl BotError {
+    /// Record the market this error concerns
+    ///
+    /// Context set closer to the failure wins: a field that is already set
+    /// is left alone as the error propagates outwards.
+    pub fn context_market(self, market_id: impl Into<String>) -> Self {
+        self.with_context(|c| {
+            c.market_id.get_or_insert_with(|| market_id.into());
+        })
+    }
+
+    /// Record the order this error concerns
+    pub fn context_order(self, order_id: impl Into<String>) -> Self {
+        self.with_context(|c| {
+            c.order_id.get_or_insert_with(|| order_id.into());
+        })
+    }
+
+    /// Record what was being attempted
+    pub fn context_operation(self, operation: impl Into<String>) -> Self {
+        self.with_context(|c| {
+            c.operation.get_or_insert_with(|| operation.into());
+        })
+    }
+
+    fn with_context(self, update: impl FnOnce(&mut ErrorContext)) -> Self {
+        let (source, mut context) = match self {
+            Self::Context { source, context } => (source, context),
+            e => (Box::new(e), ErrorContext::default()),
+        };
+        update(&mut context);
+        Self::Context { source, context }
+    }
+
+    /// Context attached to this error, if any
+    pub fn context(&self) -> Option<&ErrorContext> {
+        match self {
+            Self::Context { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// The error without its context, for matching on the variant
+    pub fn root(&self) -> &BotError {
+        match self {
+            Self::Context { source, .. } => source.root(),
+            e => e,
+        }
+    }
+
+    /// Log this error with its context as separate fields
+    pub fn log(&self, message: &str) {
+        let context = self.context().cloned().unwrap_or_default();
+        tracing::error!(
+            market_id = context.market_id.as_deref(),
+            order_id = context.order_id.as_deref(),
+            operation = context.operation.as_deref(),
+            error = %self.root(),
+            "{}",
+            message
+        );
+    }
+}
+
+/// Attach context to the error of a `Result`
+pub trait ResultExt<T> {
+    fn context_market(self, market_id: impl Into<String>) -> Result<T>;
+    fn context_order(self, order_id: impl Into<String>) -> Result<T>;
+    fn context_operation(self, operation: impl Into<String>) -> Result<T>;
+}
+
+impl<T> ResultExt<T> for Result<T> {
+    fn context_market(self, market_id: impl Into<String>) -> Result<T> {
+        self.map_err(|e| e.context_market(market_id))
+    }
+
+    fn context_order(self, order_id: impl Into<String>) -> Result<T> {
+        self.map_err(|e| e.context_order(order_id))
+    }
+
+    fn context_operation(self, operation: impl Into<String>) -> Result<T> {
+        self.map_err(|e| e.context_operation(operation))
+    }
+}
+
+impl From<reqwest::Error> for BotError {
+    fn from(e: reqwest::Error) -> Self {
+        match e.status() {
+            Some(status) => Self::from_status(status, e.to_string()),
+            None if e.is_timeout() => Self::Timeout(e.to_string()),
+            None if e.is_connect() => Self::Connect(e.to_string()),
+            None => Self::Network(e),
+        }
+    }
+}
+
+impl From<sqlx::Error> for BotError {
+    fn from(e: sqlx::Error) -> Self {
+        Self::Database(e)
+    }
 }
 
 pub type Result<T> = std::result::Result<T, BotError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::StatusCode;
+
+    /// Local server answering every request with `status`
+    async fn status_server(status: StatusCode) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = axum::Router::new().fallback(move || async move { status });
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        format!("http://{}", addr)
+    }
+
+    async fn error_for(status: StatusCode) -> BotError {
+        let url = status_server(status).await;
+        let resp = reqwest::get(&url).await.unwrap();
+        resp.error_for_status().unwrap_err().into()
+    }
+
+    #[tokio::test]
+    async fn test_http_status_classification() {
+        let unauthorized = error_for(StatusCode::UNAUTHORIZED).await;
+        assert!(matches!(unauthorized, BotError::Auth(_)));
+        assert!(!unauthorized.is_retryable());
+
+        let rate_limited = error_for(StatusCode::TOO_MANY_REQUESTS).await;
+        assert!(matches!(rate_limited, BotError::RateLimited { .. }));
+        assert!(rate_limited.is_retryable());
+        assert!(rate_limited.never_reached_server());
+        assert_eq!(rate_limited.retry_after(), Some(Duration::from_secs(DEFAULT_RETRY_AFTER_SECS)));
+
+        let bad_request = error_for(StatusCode::BAD_REQUEST).await;
+        assert!(matches!(bad_request, BotError::Validation(_)));
+        assert!(!bad_request.is_retryable());
+
+        let unavailable = error_for(StatusCode::SERVICE_UNAVAILABLE).await;
+        assert!(matches!(unavailable, BotError::Http { status: 503, .. }));
+        assert!(unavailable.is_retryable());
+        // The server may have acted before failing
+        assert!(!unavailable.never_reached_server());
+        assert!(!error_for(StatusCode::NOT_FOUND).await.is_retryable());
+    }
+
+    #[tokio::test]
+    async fn test_transport_errors_are_retryable() {
+        // Nothing listens on a port we bound and released
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        let refused: BotError = reqwest::get(format!("http://{}", addr)).await.unwrap_err().into();
+        assert!(matches!(refused, BotError::Connect(_)));
+        assert!(refused.is_retryable());
+        assert!(refused.never_reached_server());
+
+        let slow = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_millis(50))
+            .build()
+            .unwrap();
+        let timed_out: BotError = client
+            .get(format!("http://{}", slow.local_addr().unwrap()))
+            .send()
+            .await
+            .unwrap_err()
+            .into();
+        assert!(matches!(timed_out, BotError::Timeout(_)));
+        assert!(timed_out.is_retryable());
+        assert!(!timed_out.never_reached_server());
+
+        // A 200 with a body that is not JSON will not parse next time either
+        let url = status_server(StatusCode::OK).await;
+        let decode: BotError = reqwest::get(&url)
+            .await
+            .unwrap()
+            .json::<serde_json::Value>()
+            .await
+            .unwrap_err()
+            .into();
+        assert!(!decode.is_retryable());
+    }
+
+    fn cancel(order_id: &str) -> Result<()> {
+        Err(BotError::Auth("invalid api key".into()))
+            .context_order(order_id)
+            .context_operation("cancel_order")
+    }
+
+    fn close_position() -> Result<()> {
+        cancel("order-1")?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_context_survives_propagation() {
+        let err = close_position()
+            .context_market("m1")
+            .context_operation("close_position")
+            .unwrap_err();
+
+        let context = err.context().unwrap();
+        assert_eq!(context.market_id.as_deref(), Some("m1"));
+        assert_eq!(context.order_id.as_deref(), Some("order-1"));
+        // The innermost operation is the one that failed
+        assert_eq!(context.operation.as_deref(), Some("cancel_order"));
+
+        assert!(matches!(err.root(), BotError::Auth(_)));
+        assert!(!err.is_retryable());
+        assert_eq!(
+            err.to_string(),
+            "Authentication error: invalid api key [market=m1 order=order-1 operation=cancel_order]"
+        );
+
+        let plain = BotError::Timeout("slow".into());
+        assert!(plain.context().is_none());
+        assert!(plain.context_market("m2").is_retryable());
+    }
+
+    #[test]
+    fn test_other_errors() {
+        assert!(BotError::from(sqlx::Error::PoolTimedOut).is_retryable());
+        assert!(!BotError::from(sqlx::Error::RowNotFound).is_retryable());
+        assert!(BotError::Timeout("order".into()).is_retryable());
+        assert!(!BotError::RiskLimit("max exposure".into()).is_retryable());
+        assert!(!BotError::Config("missing key".into()).is_retryable());
+        assert_eq!(BotError::Auth("bad key".into()).retry_after(), None);
+    }
+}
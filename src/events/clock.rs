@@ -0,0 +1,90 @@
+//! Time source for the event engine
+//!
+//! `SystemClock` is the wall clock. `ManualClock` only moves when told to,
+//! so backtests and tests can drive heartbeats and timestamps
+//! deterministically without sleeping.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Source of "now" and of timed waits
+#[async_trait]
+pub trait Clock: Send + Sync {
+    /// Current time
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Wait until this clock reaches `deadline`
+    async fn sleep_until(&self, deadline: DateTime<Utc>);
+
+    /// Wait until `duration` has passed on this clock
+    async fn sleep(&self, duration: Duration) {
+        let step = chrono::Duration::from_std(duration).unwrap_or(chrono::Duration::MAX);
+        let deadline = self.now().checked_add_signed(step).unwrap_or(DateTime::<Utc>::MAX_UTC);
+        self.sleep_until(deadline).await;
+    }
+}
+
+/// Wall-clock time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+#[async_trait]
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    async fn sleep_until(&self, deadline: DateTime<Utc>) {
+        if let Ok(remaining) = (deadline - Utc::now()).to_std() {
+            tokio::time::sleep(remaining).await;
+        }
+    }
+}
+
+/// Clock that advances only through `advance` and `set`
+#[derive(Debug)]
+pub struct ManualClock {
+    now: watch::Sender<DateTime<Utc>>,
+}
+
+impl ManualClock {
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self { now: watch::Sender::new(start) }
+    }
+
+    /// Move time forward, waking sleepers whose deadline has passed
+    pub fn advance(&self, duration: Duration) {
+        let step = chrono::Duration::from_std(duration).unwrap_or(chrono::Duration::MAX);
+        self.now.send_modify(|now| *now += step);
+    }
+
+    /// Jump to `time`; moving backwards is ignored
+    pub fn set(&self, time: DateTime<Utc>) {
+        self.now.send_if_modified(|now| {
+            let forward = time > *now;
+            if forward {
+                *now = time;
+            }
+            forward
+        });
+    }
+}
+
+#[async_trait]
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.now.borrow()
+    }
+
+    async fn sleep_until(&self, deadline: DateTime<Utc>) {
+        let mut rx = self.now.subscribe();
+        // The sender lives as long as the clock, so `changed` only fails on drop
+        while *rx.borrow_and_update() < deadline {
+            if rx.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,487 @@
+//! Trading handlers for the event-driven loop
+//!
+//! Book updates from the market WebSocket become `MarketData` events; the
+//! handlers below turn them into trades without waiting for a scan timer:
+//!
+//! ```text
+//! MarketData -> SignalHandler -> Signal
+//!            -> RiskHandler   -> Order | Risk
+//!            -> ExecutionHandler -> Fill
+//! ```
+//!
+//! They wrap the same `SignalGenerator`, `RiskManager` and `Executor` the
+//! polling loop uses. Every derived event is a child of the one that caused
+//! it, so a fill shares its correlation id with the book update behind it.
+//! Derived events are published by `EventEngine::publish_cascade`, and are
+//! stamped from the handler's `Clock`; give them the engine's.
+
+use super::{
+    Clock, Event, EventError, EventHandler, EventPayload, EventType, FillPayload, MarketDataPayload, OrderPayload,
+    OrderSide, OrderType, RiskPayload, SignalPayload, SystemClock,
+};
+use crate::client::mock::ClobClientTrait;
+use crate::client::MarketUpdate;
+use crate::executor::{ExitManager, Executor};
+use crate::model::ProbabilityModel;
+use crate::risk::{OrderBookSnapshot, RiskCheckResult, RiskManager};
+use crate::storage::Database;
+use crate::strategy::signal_filter::SignalFilter;
+use crate::strategy::SignalGenerator;
+use crate::types::{Market, OrderBook, OrderBookLevel, Side, Signal};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock};
+
+/// Source of signals generated from market data
+pub const SIGNAL_SOURCE: &str = "signal";
+/// Source of orders and alerts from the risk checks
+pub const RISK_SOURCE: &str = "risk";
+/// Event metadata key holding the market id
+const MARKET_ID_KEY: &str = "market_id";
+/// Signal fields an order event carries in its metadata
+const SIGNAL_FIELDS: [&str; 6] = [
+    "model_probability",
+    "market_probability",
+    "edge",
+    "net_edge",
+    "confidence",
+    "suggested_size",
+];
+
+/// `MarketData` event for a WebSocket price update
+///
+/// `book` is the token's maintained order book, if there is one; its levels
+/// go on the event for the liquidity checks. The event is stamped by `clock`.
+pub fn market_data_event(update: &MarketUpdate, book: Option<&OrderBook>, clock: &dyn Clock) -> Event {
+    let levels = |levels: &[OrderBookLevel]| levels.iter().map(|l| (l.price, l.size)).collect();
+    let payload = MarketDataPayload {
+        symbol: update.token_id.clone(),
+        bid: update.best_bid,
+        ask: update.best_ask,
+        last: update.last_price,
+        volume: None,
+        bids: book.map(|b| levels(&b.bids)).unwrap_or_default(),
+        asks: book.map(|b| levels(&b.asks)).unwrap_or_default(),
+        source: "polymarket".to_string(),
+        exchange_timestamp: chrono::DateTime::from_timestamp_millis(update.timestamp as i64),
+    };
+    Event::new(EventType::MarketData, "polymarket_ws", EventPayload::MarketData(payload))
+        .with_timestamp(clock.now())
+        .with_metadata(MARKET_ID_KEY, &update.market_id)
+}
+
+/// Carry a strategy signal on the bus
+fn signal_payload(signal: &Signal) -> SignalPayload {
+    let direction = match signal.side {
+        Side::Buy => 1,
+        Side::Sell => -1,
+    };
+    SignalPayload::new(&signal.token_id, direction, signal.suggested_size, SIGNAL_SOURCE)
+        .with_edge(signal.net_edge.abs(), signal.confidence)
+        .with_feature("model_probability", signal.model_probability)
+        .with_feature("market_probability", signal.market_probability)
+        .with_feature("edge", signal.edge)
+        .with_feature("net_edge", signal.net_edge)
+}
+
+/// Rebuild the strategy signal carried by a `Signal` event
+fn signal_from_event(event: &Event) -> Option<Signal> {
+    let EventPayload::Signal(payload) = &event.payload else {
+        return None;
+    };
+    let feature = |name: &str| payload.features.get(name).copied();
+    Some(Signal {
+        market_id: event.metadata.get(MARKET_ID_KEY)?.clone(),
+        token_id: payload.symbol.clone(),
+        side: if payload.direction >= 0 { Side::Buy } else { Side::Sell },
+        model_probability: feature("model_probability")?,
+        market_probability: feature("market_probability")?,
+        edge: feature("edge")?,
+        net_edge: feature("net_edge")?,
+        confidence: payload.confidence,
+        suggested_size: payload.strength,
+        timestamp: event.timestamp,
+        max_staleness: None,
+    })
+}
+
+/// `Order` event for a sized signal, carrying the signal in its metadata
+fn order_event(parent: &Event, signal: &Signal, size_shares: Decimal, now: chrono::DateTime<chrono::Utc>) -> Event {
+    let side = match signal.side {
+        Side::Buy => OrderSide::Buy,
+        Side::Sell => OrderSide::Sell,
+    };
+    let mut order = OrderPayload::new(&signal.token_id, side, OrderType::Limit, size_shares)
+        .with_price(signal.market_probability);
+    order.created_at = now;
+    order.updated_at = now;
+    let values = [
+        signal.model_probability,
+        signal.market_probability,
+        signal.edge,
+        signal.net_edge,
+        signal.confidence,
+        signal.suggested_size,
+    ];
+    let mut event = parent
+        .create_child(EventType::Order, RISK_SOURCE, EventPayload::Order(order))
+        .with_timestamp(now)
+        .with_metadata(MARKET_ID_KEY, &signal.market_id)
+        .with_metadata("signal_time", &signal.timestamp.to_rfc3339());
+    for (key, value) in SIGNAL_FIELDS.iter().zip(values) {
+        event = event.with_metadata(key, &value.to_string());
+    }
+    event
+}
+
+/// Rebuild the signal and size of an `Order` event
+fn signal_from_order(event: &Event) -> Option<(Signal, Decimal)> {
+    let EventPayload::Order(order) = &event.payload else {
+        return None;
+    };
+    let field = |name: &str| event.metadata.get(name)?.parse::<Decimal>().ok();
+    let signal = Signal {
+        market_id: event.metadata.get(MARKET_ID_KEY)?.clone(),
+        token_id: order.symbol.clone(),
+        side: match order.side {
+            OrderSide::Buy => Side::Buy,
+            OrderSide::Sell => Side::Sell,
+        },
+        model_probability: field("model_probability")?,
+        market_probability: field("market_probability")?,
+        edge: field("edge")?,
+        net_edge: field("net_edge")?,
+        confidence: field("confidence")?,
+        suggested_size: field("suggested_size")?,
+        timestamp: chrono::DateTime::parse_from_rfc3339(event.metadata.get("signal_time")?)
+            .ok()?
+            .with_timezone(&chrono::Utc),
+        max_staleness: None,
+    };
+    Some((signal, order.quantity))
+}
+
+/// Runs the probability model when a tracked market's price moves
+pub struct SignalHandler {
+    model: Arc<dyn ProbabilityModel>,
+    signal_gen: Arc<SignalGenerator>,
+    /// Tracked markets by Yes token id, with prices kept current
+    markets: RwLock<HashMap<String, Market>>,
+    /// Last model run per market, by `clock` time
+    last_evaluated: Mutex<HashMap<String, chrono::DateTime<chrono::Utc>>>,
+    /// Minimum time between model runs for one market
+    min_interval: Duration,
+    clock: Arc<dyn Clock>,
+}
+
+impl SignalHandler {
+    pub fn new(model: Arc<dyn ProbabilityModel>, signal_gen: Arc<SignalGenerator>, markets: Vec<Market>) -> Self {
+        let matcher = signal_gen.outcome_matcher();
+        let markets = markets
+            .into_iter()
+            .filter_map(|m| {
+                let token = m.find_outcome("yes", &matcher)?.token_id.clone();
+                Some((token, m))
+            })
+            .collect();
+        Self {
+            model,
+            signal_gen,
+            markets: RwLock::new(markets),
+            last_evaluated: Mutex::new(HashMap::new()),
+            min_interval: Duration::from_secs(60),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Stamp signals and time the per-market rate limit from `clock`
+    /// instead of wall-clock time
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Rate-limit model runs per market (LLM calls are not free)
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Yes token ids to subscribe to
+    pub async fn token_ids(&self) -> Vec<String> {
+        self.markets.read().await.keys().cloned().collect()
+    }
+
+    /// Apply a price update; returns the market if it is due for a model run
+    async fn update_market(&self, data: &MarketDataPayload) -> Option<Market> {
+        let mid = match (data.bid, data.ask) {
+            (Some(bid), Some(ask)) => (bid + ask) / Decimal::TWO,
+            _ => data.last?,
+        };
+
+        let market = {
+            let mut markets = self.markets.write().await;
+            let market = markets.get_mut(&data.symbol)?;
+            for outcome in &mut market.outcomes {
+                outcome.price = if outcome.token_id == data.symbol { mid } else { Decimal::ONE - mid };
+            }
+            market.clone()
+        };
+
+        let mut last = self.last_evaluated.lock().await;
+        let now = self.clock.now();
+        let min_interval = chrono::Duration::from_std(self.min_interval).unwrap_or(chrono::Duration::MAX);
+        if last.get(&data.symbol).is_some_and(|t| now - *t < min_interval) {
+            return None;
+        }
+        last.insert(data.symbol.clone(), now);
+        Some(market)
+    }
+}
+
+#[async_trait]
+impl EventHandler for SignalHandler {
+    fn name(&self) -> &str {
+        "SignalHandler"
+    }
+
+    fn handles(&self) -> Vec<EventType> {
+        vec![EventType::MarketData]
+    }
+
+    async fn handle(&self, event: &Event) -> Result<Vec<Event>, EventError> {
+        let EventPayload::MarketData(data) = &event.payload else {
+            return Ok(Vec::new());
+        };
+        let Some(market) = self.update_market(data).await else {
+            return Ok(Vec::new());
+        };
+
+        let prediction = self
+            .model
+            .predict(&market)
+            .await
+            .map_err(|e| EventError::HandlerError(format!("{} failed for {}: {}", self.model.name(), market.id, e)))?;
+        let Some(signal) = self.signal_gen.generate(&market, &prediction) else {
+            return Ok(Vec::new());
+        };
+
+        tracing::info!(
+            market_id = %market.id,
+            "Signal from book update: {:?} edge {:.4} (net {:.4})",
+            signal.side,
+            signal.edge,
+            signal.net_edge
+        );
+        let child = event
+            .create_child(EventType::Signal, SIGNAL_SOURCE, EventPayload::Signal(signal_payload(&signal)))
+            .with_timestamp(self.clock.now())
+            .with_metadata(MARKET_ID_KEY, &market.id);
+        Ok(vec![child])
+    }
+}
+
+/// Sizes signals into orders unless risk limits or the cooldown block them
+///
+/// Orders are sized against the portfolio value last marked on the risk
+/// manager; until one is marked, signals are dropped. Book levels on market
+/// data feed the liquidity monitor, by token, and a token it rates
+/// untradeable gets no orders.
+pub struct RiskHandler {
+    risk_manager: Arc<Mutex<RiskManager>>,
+    signal_filter: Arc<SignalFilter>,
+    clock: Arc<dyn Clock>,
+}
+
+impl RiskHandler {
+    pub fn new(risk_manager: Arc<Mutex<RiskManager>>, signal_filter: Arc<SignalFilter>) -> Self {
+        Self { risk_manager, signal_filter, clock: Arc::new(SystemClock) }
+    }
+
+    /// Stamp orders and alerts from `clock` instead of wall-clock time
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+}
+
+#[async_trait]
+impl EventHandler for RiskHandler {
+    fn name(&self) -> &str {
+        "RiskHandler"
+    }
+
+    fn handles(&self) -> Vec<EventType> {
+        vec![EventType::MarketData, EventType::Signal]
+    }
+
+    async fn handle(&self, event: &Event) -> Result<Vec<Event>, EventError> {
+        if let EventPayload::MarketData(data) = &event.payload {
+            if !data.bids.is_empty() || !data.asks.is_empty() {
+                let level = |&(price, size)| OrderBookLevel { price, size };
+                let book = OrderBook {
+                    bids: data.bids.iter().map(level).collect(),
+                    asks: data.asks.iter().map(level).collect(),
+                };
+                self.risk_manager.lock().await.update_liquidity(OrderBookSnapshot::from_book(&data.symbol, book));
+            }
+            return Ok(Vec::new());
+        }
+        let Some(signal) = signal_from_event(event) else {
+            return Ok(Vec::new());
+        };
+
+        let portfolio_value = {
+            let rm = self.risk_manager.lock().await;
+            if let RiskCheckResult::Blocked { reason } = rm.can_trade() {
+                tracing::warn!("⚠️ Trading blocked: {}", reason);
+                let risk = RiskPayload::trading_blocked(&reason);
+                let child = event
+                    .create_child(EventType::Risk, RISK_SOURCE, EventPayload::Risk(risk))
+                    .with_timestamp(self.clock.now());
+                return Ok(vec![child]);
+            }
+            if !rm.liquidity_monitor.is_tradeable(&signal.token_id) {
+                tracing::debug!("Skipping {} - book too thin to trade", signal.market_id);
+                return Ok(Vec::new());
+            }
+            rm.pnl_tracker.portfolio_value()
+        };
+        let Some(portfolio_value) = portfolio_value.filter(|v| *v > Decimal::ZERO) else {
+            tracing::debug!("Skipping {} - portfolio value not marked yet", signal.market_id);
+            return Ok(Vec::new());
+        };
+        if signal.market_probability <= Decimal::ZERO {
+            return Ok(Vec::new());
+        }
+
+        if !self.signal_filter.deduplicator.can_trade_dynamic(&signal.market_id, false) {
+            tracing::debug!("Skipping {} - cooldown active", signal.market_id);
+            return Ok(Vec::new());
+        }
+        self.signal_filter.deduplicator.mark_traded(&signal.market_id);
+
+        let size_shares = signal.suggested_size * portfolio_value / signal.market_probability;
+        Ok(vec![order_event(event, &signal, size_shares, self.clock.now())])
+    }
+}
+
+/// Submits orders approved by `RiskHandler` through the `Executor`
+pub struct ExecutionHandler<C> {
+    executor: Arc<Executor<C>>,
+    risk_manager: Arc<Mutex<RiskManager>>,
+    db: Option<Arc<Database>>,
+    exits: Option<Arc<ExitManager>>,
+    /// Traded markets by id, registered with the executor before each order
+    markets: HashMap<String, Market>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<C: ClobClientTrait> ExecutionHandler<C> {
+    pub fn new(executor: Arc<Executor<C>>, risk_manager: Arc<Mutex<RiskManager>>) -> Self {
+        Self {
+            executor,
+            risk_manager,
+            db: None,
+            exits: None,
+            markets: HashMap::new(),
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Stamp fills from `clock` instead of wall-clock time
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Round orders to these markets' tick and minimum sizes
+    pub fn with_markets(mut self, markets: Vec<Market>) -> Self {
+        self.markets = markets.into_iter().map(|m| (m.id.clone(), m)).collect();
+        self
+    }
+
+    /// Watch each new position for its take-profit or stop-loss
+    pub fn with_exits(mut self, exits: Arc<ExitManager>) -> Self {
+        self.exits = Some(exits);
+        self
+    }
+
+    /// Persist executed trades
+    pub fn with_database(mut self, db: Arc<Database>) -> Self {
+        self.db = Some(db);
+        self
+    }
+}
+
+#[async_trait]
+impl<C: ClobClientTrait + 'static> EventHandler for ExecutionHandler<C> {
+    fn name(&self) -> &str {
+        "ExecutionHandler"
+    }
+
+    fn handles(&self) -> Vec<EventType> {
+        vec![EventType::Order]
+    }
+
+    async fn handle(&self, event: &Event) -> Result<Vec<Event>, EventError> {
+        let EventPayload::Order(order) = &event.payload else {
+            return Ok(Vec::new());
+        };
+        if event.source != RISK_SOURCE || !order.is_active() {
+            return Ok(Vec::new());
+        }
+        let Some((signal, size_shares)) = signal_from_order(event) else {
+            return Err(EventError::ValidationError(format!("Order event {} carries no signal", event.id)));
+        };
+
+        if let Some(market) = self.markets.get(&signal.market_id) {
+            self.executor.register_market(market).await;
+        }
+        let balance = self
+            .executor
+            .clob
+            .get_balance()
+            .await
+            .map_err(|e| EventError::HandlerError(format!("Balance unavailable: {}", e)))?;
+        let trade = self
+            .executor
+            .execute_shares(&signal, size_shares, balance)
+            .await
+            .map_err(|e| EventError::HandlerError(format!("Execution failed: {}", e)))?;
+        let Some(mut trade) = trade else {
+            return Ok(Vec::new());
+        };
+        trade.correlation_id = Some(event.chain_id().to_string());
+
+        tracing::info!("Trade executed: {}", trade.id);
+        {
+            let mut rm = self.risk_manager.lock().await;
+            rm.pnl_tracker.set_starting_balance(balance);
+            // Fee is an immediate cost; P&L is booked when the position closes
+            rm.record_trade(-trade.fee);
+        }
+        if let Some(db) = &self.db {
+            if let Err(e) = db.save_trade(&trade).await {
+                tracing::warn!("Failed to save trade {}: {}", trade.id, e);
+            }
+        }
+        if let Some(exits) = &self.exits {
+            exits.watch(&trade);
+        }
+
+        let now = self.clock.now();
+        let mut fill = FillPayload::new(&order.order_id, &trade.token_id, order.side, trade.size, trade.price);
+        fill.executed_at = now;
+        fill.exchange_trade_id = Some(trade.order_id.clone());
+        fill.commission = trade.fee;
+        let child = event
+            .create_child(EventType::Fill, "executor", EventPayload::Fill(fill))
+            .with_timestamp(now)
+            .with_metadata(MARKET_ID_KEY, &trade.market_id);
+        Ok(vec![child])
+    }
+}
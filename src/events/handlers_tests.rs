@@ -0,0 +1,305 @@
+//! Tests for the event-driven trading handlers
+
+use super::handlers::*;
+use super::{
+    Clock, Event, EventEngine, EventEngineConfig, EventError, EventHandler, EventPayload, EventType, ManualClock,
+    MarketDataPayload,
+};
+use crate::client::mock::MockClobClient;
+use crate::config::{RiskConfig, StrategyConfig};
+use crate::error::Result;
+use crate::executor::Executor;
+use crate::model::{Prediction, ProbabilityModel};
+use crate::risk::RiskManager;
+use crate::storage::Database;
+use crate::strategy::signal_filter::SignalFilter;
+use crate::strategy::SignalGenerator;
+use crate::client::MarketUpdate;
+use crate::types::{Market, OrderBook, OrderBookLevel, Outcome};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Model with a fixed view that counts its calls
+struct FixedModel {
+    probability: Decimal,
+    calls: AtomicU32,
+}
+
+#[async_trait]
+impl ProbabilityModel for FixedModel {
+    async fn predict(&self, _market: &Market) -> Result<Prediction> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        Ok(Prediction {
+            probability: self.probability,
+            confidence: dec!(0.80),
+            reasoning: "fixed".to_string(),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "fixed"
+    }
+}
+
+/// Records the derived events it sees
+#[derive(Default)]
+struct Recorder(std::sync::Mutex<Vec<Event>>);
+
+impl Recorder {
+    fn types(&self) -> Vec<String> {
+        self.0.lock().unwrap().iter().map(|e| format!("{:?}", e.event_type)).collect()
+    }
+}
+
+#[async_trait]
+impl EventHandler for Recorder {
+    fn name(&self) -> &str {
+        "Recorder"
+    }
+
+    fn handles(&self) -> Vec<EventType> {
+        vec![EventType::Signal, EventType::Order, EventType::Fill, EventType::Risk]
+    }
+
+    async fn handle(&self, event: &Event) -> std::result::Result<Vec<Event>, EventError> {
+        self.0.lock().unwrap().push(event.clone());
+        Ok(Vec::new())
+    }
+}
+
+fn market() -> Market {
+    Market {
+        id: "m1".to_string(),
+        question: "Will it happen?".to_string(),
+        description: None,
+        end_date: None,
+        volume: dec!(100000),
+        liquidity: dec!(50000),
+        active: true,
+        closed: false,
+        outcomes: vec![
+            Outcome { outcome: "Yes".to_string(), token_id: "yes-token".to_string(), price: dec!(0.50) },
+            Outcome { outcome: "No".to_string(), token_id: "no-token".to_string(), price: dec!(0.50) },
+        ],
+        tags: Vec::new(),
+        category: None,
+        tick_size: None,
+        min_size: None,
+    }
+}
+
+fn strategy() -> StrategyConfig {
+    StrategyConfig {
+        min_edge: dec!(0.05),
+        min_confidence: dec!(0.6),
+        fee_rate: Decimal::ZERO,
+        slippage_estimate: Decimal::ZERO,
+        ..StrategyConfig::default()
+    }
+}
+
+fn price_update(token_id: &str, bid: Decimal, ask: Decimal) -> Event {
+    let payload = MarketDataPayload::price_update(token_id, bid, ask, "polymarket");
+    Event::new(EventType::MarketData, "polymarket_ws", EventPayload::MarketData(payload))
+        .with_metadata("market_id", "m1")
+}
+
+struct Harness {
+    engine: EventEngine,
+    model: Arc<FixedModel>,
+    risk_manager: Arc<Mutex<RiskManager>>,
+    recorder: Arc<Recorder>,
+    clock: Arc<ManualClock>,
+}
+
+async fn harness(probability: Decimal) -> Harness {
+    harness_with_database(probability, None).await
+}
+
+async fn harness_with_database(probability: Decimal, db: Option<Arc<Database>>) -> Harness {
+    harness_for(market(), probability, db).await
+}
+
+async fn harness_for(market: Market, probability: Decimal, db: Option<Arc<Database>>) -> Harness {
+    let model = Arc::new(FixedModel { probability, calls: AtomicU32::new(0) });
+    let signal_gen = Arc::new(SignalGenerator::new(strategy(), RiskConfig::default()));
+    signal_gen.set_bankroll(dec!(1000));
+    let risk_manager = Arc::new(Mutex::new(RiskManager::new(RiskConfig::default())));
+    risk_manager.lock().await.mark_to_market(dec!(1000));
+    let executor = Arc::new(Executor::new(MockClobClient::new(), RiskConfig::default()));
+
+    let clock = Arc::new(ManualClock::new(chrono::Utc::now()));
+    let engine = EventEngine::new(EventEngineConfig::default()).with_clock(clock.clone());
+    let recorder = Arc::new(Recorder::default());
+    engine.register_handler(recorder.clone()).await;
+    engine
+        .register_handler(Arc::new(
+            SignalHandler::new(model.clone(), signal_gen, vec![market.clone()])
+                .with_min_interval(Duration::from_secs(60))
+                .with_clock(engine.clock()),
+        ))
+        .await;
+    engine
+        .register_handler(Arc::new(
+            RiskHandler::new(risk_manager.clone(), Arc::new(SignalFilter::new())).with_clock(engine.clock()),
+        ))
+        .await;
+    let mut execution = ExecutionHandler::new(executor, risk_manager.clone())
+        .with_markets(vec![market])
+        .with_clock(engine.clock());
+    if let Some(db) = db {
+        execution = execution.with_database(db);
+    }
+    engine.register_handler(Arc::new(execution)).await;
+    Harness { engine, model, risk_manager, recorder, clock }
+}
+
+#[tokio::test]
+async fn test_price_update_runs_full_handler_chain() {
+    let h = harness(dec!(0.80)).await;
+
+    let update = price_update("yes-token", dec!(0.54), dec!(0.56));
+    let derived = h.engine.publish_cascade(update.clone()).await.unwrap();
+    assert_eq!(h.model.calls.load(Ordering::SeqCst), 1);
+    assert_eq!(h.recorder.types(), vec!["Signal", "Order", "Fill"]);
+
+    let EventPayload::Signal(signal) = &derived[0].payload else {
+        panic!("expected a signal payload");
+    };
+    assert_eq!(signal.symbol, "yes-token");
+    assert_eq!(signal.direction, 1);
+    assert_eq!(signal.features["market_probability"], dec!(0.55));
+
+    let EventPayload::Order(order) = &derived[1].payload else {
+        panic!("expected an order payload");
+    };
+    assert_eq!(order.symbol, "yes-token");
+    assert_eq!(order.price, Some(dec!(0.55)));
+    assert_eq!(order.quantity, signal.strength * dec!(1000) / dec!(0.55));
+
+    let EventPayload::Fill(fill) = &derived[2].payload else {
+        panic!("expected a fill payload");
+    };
+    assert_eq!(fill.order_id, order.order_id);
+    assert!(fill.quantity > Decimal::ZERO);
+
+    // Each event is caused by the previous one and they share one chain
+    assert_eq!(derived[0].causation_id.as_deref(), Some(update.id.as_str()));
+    assert_eq!(derived[1].causation_id.as_deref(), Some(derived[0].id.as_str()));
+    assert_eq!(derived[2].causation_id.as_deref(), Some(derived[1].id.as_str()));
+    assert!(derived.iter().all(|e| e.correlation_id.as_deref() == Some(update.id.as_str())));
+}
+
+#[tokio::test]
+async fn test_saved_trade_links_back_to_its_event_chain() {
+    let dir = tempfile::tempdir().unwrap();
+    let db = Arc::new(Database::connect(dir.path().join("bot.db")).await.unwrap());
+    let h = harness_with_database(dec!(0.80), Some(db.clone())).await;
+
+    let update = price_update("yes-token", dec!(0.54), dec!(0.56));
+    h.engine.publish_cascade(update.clone()).await.unwrap();
+
+    let trades = db.get_trades_by_correlation(&update.id).await.unwrap();
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].token_id, "yes-token");
+    assert_eq!(trades[0].correlation_id.as_deref(), Some(update.id.as_str()));
+}
+
+#[tokio::test]
+async fn test_derived_events_are_stamped_by_the_engine_clock() {
+    let h = harness(dec!(0.80)).await;
+    h.clock.advance(std::time::Duration::from_secs(3600));
+    let now = h.clock.now();
+
+    let derived = h.engine.publish_cascade(price_update("yes-token", dec!(0.54), dec!(0.56))).await.unwrap();
+    assert_eq!(derived.len(), 3);
+    assert!(derived.iter().all(|e| e.timestamp == now));
+    let (EventPayload::Order(order), EventPayload::Fill(fill)) = (&derived[1].payload, &derived[2].payload) else {
+        panic!("expected an order and a fill");
+    };
+    assert_eq!(order.created_at, now);
+    assert_eq!(fill.executed_at, now);
+}
+
+#[tokio::test]
+async fn test_orders_use_the_market_minimum_size() {
+    let market = Market { min_size: Some(dec!(1000000)), ..market() };
+    let h = harness_for(market, dec!(0.80), None).await;
+
+    h.engine.publish_cascade(price_update("yes-token", dec!(0.54), dec!(0.56))).await.unwrap();
+    // The order is far below the minimum, so nothing fills
+    assert_eq!(h.recorder.types(), vec!["Signal", "Order"]);
+}
+
+#[tokio::test]
+async fn test_blocked_trading_emits_risk_event() {
+    let h = harness(dec!(0.80)).await;
+    // A 20% loss on the day trips the 10% daily loss limit
+    h.risk_manager.lock().await.mark_to_market(dec!(800));
+
+    let derived = h.engine.publish_cascade(price_update("yes-token", dec!(0.54), dec!(0.56))).await.unwrap();
+    assert_eq!(h.recorder.types(), vec!["Signal", "Risk"]);
+    let EventPayload::Risk(risk) = &derived[1].payload else {
+        panic!("expected a risk payload");
+    };
+    assert!(risk.message.contains("Daily loss limit"));
+}
+
+#[tokio::test]
+async fn test_price_updates_are_throttled_per_market() {
+    let h = harness(dec!(0.80)).await;
+
+    h.engine.publish_cascade(price_update("yes-token", dec!(0.54), dec!(0.56))).await.unwrap();
+    // Within the minimum interval: no second model run
+    let derived = h.engine.publish_cascade(price_update("yes-token", dec!(0.50), dec!(0.52))).await.unwrap();
+    assert!(derived.is_empty());
+    assert_eq!(h.model.calls.load(Ordering::SeqCst), 1);
+    // The interval is timed by the engine clock
+    h.clock.advance(Duration::from_secs(60));
+    h.engine.publish_cascade(price_update("yes-token", dec!(0.50), dec!(0.52))).await.unwrap();
+    assert_eq!(h.model.calls.load(Ordering::SeqCst), 2);
+
+    // Untracked tokens are ignored
+    h.engine.publish_cascade(price_update("other-token", dec!(0.30), dec!(0.32))).await.unwrap();
+    assert_eq!(h.model.calls.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn test_no_signal_without_edge() {
+    let h = harness(dec!(0.56)).await;
+
+    let derived = h.engine.publish_cascade(price_update("yes-token", dec!(0.54), dec!(0.56))).await.unwrap();
+    assert_eq!(h.model.calls.load(Ordering::SeqCst), 1);
+    assert!(derived.is_empty());
+    assert!(h.recorder.types().is_empty());
+}
+
+#[tokio::test]
+async fn test_thin_book_blocks_orders() {
+    let h = harness(dec!(0.80)).await;
+
+    // One share a side: the liquidity monitor rates the token untradeable
+    let update = MarketUpdate {
+        token_id: "yes-token".to_string(),
+        market_id: "m1".to_string(),
+        best_bid: Some(dec!(0.54)),
+        best_ask: Some(dec!(0.56)),
+        last_price: None,
+        last_size: None,
+        timestamp: 0,
+        event_type: "book".to_string(),
+    };
+    let book = OrderBook {
+        bids: vec![OrderBookLevel { price: dec!(0.54), size: dec!(1) }],
+        asks: vec![OrderBookLevel { price: dec!(0.56), size: dec!(1) }],
+    };
+    h.engine.publish_cascade(market_data_event(&update, Some(&book), h.clock.as_ref())).await.unwrap();
+
+    assert_eq!(h.recorder.types(), vec!["Signal"]);
+    assert!(!h.risk_manager.lock().await.liquidity_monitor.is_tradeable("yes-token"));
+}
@@ -28,10 +28,24 @@ use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::{broadcast, mpsc, RwLock};
+use tracing::Instrument;
 use uuid::Uuid;
 
+pub mod clock;
+pub mod handlers;
+pub mod sink;
+
+pub use clock::{Clock, ManualClock, SystemClock};
+pub use sink::{EventSink, JsonlFileSink};
+
+#[cfg(test)]
+mod handlers_tests;
+#[cfg(test)]
+mod sink_tests;
+
 /// Event priority levels for ordering
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum EventPriority {
@@ -113,6 +127,14 @@ impl Event {
         }
     }
 
+    /// Stamp the event with a given time instead of now
+    ///
+    /// Used for simulated clocks and for events rebuilt from history.
+    pub fn with_timestamp(mut self, timestamp: DateTime<Utc>) -> Self {
+        self.timestamp = timestamp;
+        self
+    }
+
     /// Set event priority
     pub fn with_priority(mut self, priority: EventPriority) -> Self {
         self.priority = priority;
@@ -137,10 +159,16 @@ impl Event {
         self
     }
 
+    /// Correlation id of the chain this event belongs to; a root event's
+    /// chain is named after the event itself
+    pub fn chain_id(&self) -> &str {
+        self.correlation_id.as_deref().unwrap_or(&self.id)
+    }
+
     /// Create a child event caused by this event
     pub fn create_child(&self, event_type: EventType, source: &str, payload: EventPayload) -> Self {
         let mut child = Event::new(event_type, source, payload);
-        child.correlation_id = self.correlation_id.clone().or_else(|| Some(self.id.clone()));
+        child.correlation_id = Some(self.chain_id().to_string());
         child.causation_id = Some(self.id.clone());
         child
     }
@@ -191,6 +219,13 @@ pub struct MarketDataPayload {
 }
 
 impl MarketDataPayload {
+    /// How far `received` is ahead of the exchange timestamp
+    ///
+    /// Negative when the local clock is behind the exchange's.
+    pub fn clock_skew(&self, received: DateTime<Utc>) -> Option<chrono::Duration> {
+        self.exchange_timestamp.map(|exchange| received - exchange)
+    }
+
     /// Create a simple price update
     pub fn price_update(symbol: &str, bid: Decimal, ask: Decimal, source: &str) -> Self {
         Self {
@@ -535,6 +570,11 @@ pub enum RiskEventType {
     MarginCall,
     Liquidation,
     CircuitBreaker,
+    DeadMansSwitch,
+    /// An open position's unrealized gain reached its target
+    TakeProfit,
+    /// An open position's unrealized loss reached its limit
+    StopLoss,
     Custom,
 }
 
@@ -586,6 +626,76 @@ impl RiskPayload {
             message: format!("Position limit warning for {}: {} > {}", symbol, current, limit),
         }
     }
+
+    /// Create an execution circuit breaker event
+    pub fn circuit_breaker(failures: u32, threshold: u32, cooldown: std::time::Duration, last_error: &str) -> Self {
+        Self {
+            risk_type: RiskEventType::CircuitBreaker,
+            symbol: None,
+            current_value: Decimal::from(failures),
+            threshold: Decimal::from(threshold),
+            severity: RiskSeverity::Critical,
+            action: RiskAction::HaltTrading,
+            message: format!(
+                "Trading halted after {} consecutive execution failures, probing again in {}s. Last error: {}",
+                failures,
+                cooldown.as_secs(),
+                last_error
+            ),
+        }
+    }
+
+    /// Create an event for trading halted by a missed monitoring heartbeat
+    pub fn dead_mans_switch(silent_for: std::time::Duration, timeout: std::time::Duration) -> Self {
+        Self {
+            risk_type: RiskEventType::DeadMansSwitch,
+            symbol: None,
+            current_value: Decimal::from(silent_for.as_secs()),
+            threshold: Decimal::from(timeout.as_secs()),
+            severity: RiskSeverity::Emergency,
+            action: RiskAction::HaltTrading,
+            message: format!(
+                "Trading halted: no monitoring heartbeat for {}s (timeout {}s)",
+                silent_for.as_secs(),
+                timeout.as_secs()
+            ),
+        }
+    }
+
+    /// Create an event for an open position crossing a PnL threshold
+    ///
+    /// `current` and `threshold` are unrealized returns on cost (0.2 = +20%).
+    pub fn position_pnl(market_id: &str, current: Decimal, threshold: Decimal) -> Self {
+        let profit = threshold >= Decimal::ZERO;
+        Self {
+            risk_type: if profit { RiskEventType::TakeProfit } else { RiskEventType::StopLoss },
+            symbol: Some(market_id.to_string()),
+            current_value: current,
+            threshold,
+            severity: if profit { RiskSeverity::Info } else { RiskSeverity::Warning },
+            action: if profit { RiskAction::None } else { RiskAction::ReducePosition },
+            message: format!(
+                "Position in {} is {:+.1}% unrealized, past the {:+.1}% {} threshold",
+                market_id,
+                current * Decimal::ONE_HUNDRED,
+                threshold * Decimal::ONE_HUNDRED,
+                if profit { "take-profit" } else { "stop-loss" }
+            ),
+        }
+    }
+
+    /// Create an event for a trade refused by the risk manager
+    pub fn trading_blocked(reason: &str) -> Self {
+        Self {
+            risk_type: RiskEventType::Custom,
+            symbol: None,
+            current_value: Decimal::ZERO,
+            threshold: Decimal::ZERO,
+            severity: RiskSeverity::Warning,
+            action: RiskAction::HaltTrading,
+            message: format!("Trading blocked: {}", reason),
+        }
+    }
 }
 
 /// System event payload
@@ -675,6 +785,8 @@ pub enum EventError {
     Timeout,
     /// Event validation error
     ValidationError(String),
+    /// Reading or writing an event log failed
+    Io(String),
 }
 
 impl std::fmt::Display for EventError {
@@ -685,6 +797,7 @@ impl std::fmt::Display for EventError {
             Self::ChannelError(s) => write!(f, "Channel error: {}", s),
             Self::Timeout => write!(f, "Event timeout"),
             Self::ValidationError(s) => write!(f, "Validation error: {}", s),
+            Self::Io(s) => write!(f, "Event log I/O error: {}", s),
         }
     }
 }
@@ -738,6 +851,15 @@ impl EventBus {
         // Broadcast event
         let _ = self.broadcast_tx.send(event.clone());
 
+        // Handler logs carry the event chain's correlation id; root events
+        // use their own id, which is what their children inherit
+        let span = tracing::info_span!(
+            "event",
+            event_id = %event.id,
+            event_type = ?event.event_type,
+            correlation_id = %event.chain_id(),
+        );
+
         // Dispatch to handlers
         let handlers = self.handlers.read().await;
         let mut result_events = Vec::new();
@@ -760,7 +882,7 @@ impl EventBus {
             });
 
             if should_handle {
-                match handler.handle(&event).await {
+                match handler.handle(&event).instrument(span.clone()).await {
                     Ok(events) => result_events.extend(events),
                     Err(e) => {
                         tracing::error!(
@@ -817,16 +939,48 @@ impl EventBus {
 // Event Store - Persistence and Replay
 // ============================================================================
 
+/// Component whose state can be snapshotted and rebuilt from events
+pub trait Snapshottable {
+    /// Serializable form of the state
+    type State: Serialize + serde::de::DeserializeOwned;
+
+    /// Capture the current state
+    fn snapshot_state(&self) -> Self::State;
+
+    /// Rebuild the component from a captured state
+    fn from_snapshot(state: Self::State) -> Self;
+
+    /// Fold one event into the state
+    fn apply(&mut self, event: &Event);
+}
+
+/// Component state as of a position in the event log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventSnapshot {
+    /// Number of events appended before the snapshot
+    pub sequence: u64,
+    /// Last event the state includes
+    pub last_event_id: Option<String>,
+    /// When the snapshot was taken
+    pub taken_at: DateTime<Utc>,
+    /// Serialized `Snapshottable::State`
+    pub state: serde_json::Value,
+}
+
 /// Event store for persistence and replay
 pub struct EventStore {
     /// In-memory event log
     events: RwLock<Vec<Event>>,
     /// Maximum events to keep in memory
     max_events: usize,
+    /// Events dropped from the front of the log to stay within `max_events`
+    trimmed: AtomicU64,
     /// Snapshot interval
     snapshot_interval: u64,
     /// Event count since last snapshot
     events_since_snapshot: RwLock<u64>,
+    /// Most recent snapshot
+    latest_snapshot: RwLock<Option<EventSnapshot>>,
 }
 
 impl EventStore {
@@ -835,11 +989,19 @@ impl EventStore {
         Self {
             events: RwLock::new(Vec::new()),
             max_events,
+            trimmed: AtomicU64::new(0),
             snapshot_interval: 1000,
             events_since_snapshot: RwLock::new(0),
+            latest_snapshot: RwLock::new(None),
         }
     }
 
+    /// Suggest a snapshot every `interval` events
+    pub fn with_snapshot_interval(mut self, interval: u64) -> Self {
+        self.snapshot_interval = interval;
+        self
+    }
+
     /// Append an event to the store
     pub async fn append(&self, event: Event) -> Result<(), EventError> {
         let mut events = self.events.write().await;
@@ -849,6 +1011,7 @@ impl EventStore {
         if events.len() > self.max_events {
             let drain_count = events.len() - self.max_events;
             events.drain(0..drain_count);
+            self.trimmed.fetch_add(drain_count as u64, Ordering::SeqCst);
         }
 
         // Update snapshot counter
@@ -858,6 +1021,93 @@ impl EventStore {
         Ok(())
     }
 
+    /// Whether `snapshot_interval` events have arrived since the last snapshot
+    pub async fn should_snapshot(&self) -> bool {
+        *self.events_since_snapshot.read().await >= self.snapshot_interval
+    }
+
+    /// Record a snapshot of `state`
+    ///
+    /// `state` must have seen every event appended so far: recovery restores
+    /// it and replays only the events appended after this call.
+    pub async fn snapshot<S: Snapshottable>(&self, state: &S) -> Result<EventSnapshot, EventError> {
+        let state = serde_json::to_value(state.snapshot_state())
+            .map_err(|e| EventError::SerializationError(e.to_string()))?;
+
+        // Hold the log so no event slips in between the sequence and the reset
+        let events = self.events.read().await;
+        let snapshot = EventSnapshot {
+            sequence: self.trimmed.load(Ordering::SeqCst) + events.len() as u64,
+            last_event_id: events.last().map(|e| e.id.clone()),
+            taken_at: Utc::now(),
+            state,
+        };
+        *self.events_since_snapshot.write().await = 0;
+        *self.latest_snapshot.write().await = Some(snapshot.clone());
+        Ok(snapshot)
+    }
+
+    /// Most recent snapshot, if any
+    pub async fn latest_snapshot(&self) -> Option<EventSnapshot> {
+        self.latest_snapshot.read().await.clone()
+    }
+
+    /// Events appended after `sequence`
+    ///
+    /// Fails if some of them were already trimmed from the log.
+    pub async fn events_since(&self, sequence: u64) -> Result<Vec<Event>, EventError> {
+        let events = self.events.read().await;
+        let trimmed = self.trimmed.load(Ordering::SeqCst);
+        if sequence < trimmed {
+            return Err(EventError::ValidationError(format!(
+                "Events {}..{} were trimmed from the store",
+                sequence, trimmed
+            )));
+        }
+        let start = ((sequence - trimmed) as usize).min(events.len());
+        Ok(events[start..].to_vec())
+    }
+
+    /// Rebuild a component from the latest snapshot plus the events after it
+    ///
+    /// Without a snapshot the component starts from its default and every
+    /// stored event is replayed.
+    pub async fn recover<S: Snapshottable + Default>(&self) -> Result<S, EventError> {
+        let (mut state, sequence) = match self.latest_snapshot().await {
+            Some(snapshot) => {
+                let saved = serde_json::from_value(snapshot.state)
+                    .map_err(|e| EventError::SerializationError(e.to_string()))?;
+                (S::from_snapshot(saved), snapshot.sequence)
+            }
+            None => (S::default(), self.trimmed.load(Ordering::SeqCst)),
+        };
+        for event in self.events_since(sequence).await? {
+            state.apply(&event);
+        }
+        Ok(state)
+    }
+
+    /// Append the events in a JSON Lines file written by `JsonlFileSink`
+    ///
+    /// Load rotated files oldest first to keep events in order. Returns the
+    /// number of events loaded.
+    pub async fn load_jsonl(&self, path: impl AsRef<std::path::Path>) -> Result<usize, EventError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| EventError::Io(e.to_string()))?;
+        let mut loaded = 0;
+        for (line_no, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let event: Event = serde_json::from_str(line).map_err(|e| {
+                EventError::SerializationError(format!("{}:{}: {}", path.display(), line_no + 1, e))
+            })?;
+            self.append(event).await?;
+            loaded += 1;
+        }
+        Ok(loaded)
+    }
+
     /// Get events in time range
     pub async fn get_events(
         &self,
@@ -906,9 +1156,13 @@ impl EventStore {
         self.events.read().await.len()
     }
 
-    /// Clear all events
+    /// Clear all events and the snapshot taken from them
     pub async fn clear(&self) {
-        self.events.write().await.clear();
+        let mut events = self.events.write().await;
+        events.clear();
+        self.trimmed.store(0, Ordering::SeqCst);
+        *self.events_since_snapshot.write().await = 0;
+        *self.latest_snapshot.write().await = None;
     }
 
     /// Replay events through an event bus
@@ -921,9 +1175,11 @@ impl EventStore {
         let events = self.get_events(from, to).await;
         let mut all_results = Vec::new();
 
+        // Derived events happened at their cause's time, not at replay time
         for event in events {
+            let timestamp = event.timestamp;
             let results = bus.publish(event).await?;
-            all_results.extend(results);
+            all_results.extend(results.into_iter().map(|e| e.with_timestamp(timestamp)));
         }
 
         Ok(all_results)
@@ -966,6 +1222,10 @@ pub struct EventEngine {
     pub store: Arc<EventStore>,
     /// Configuration
     config: EventEngineConfig,
+    /// Time source for engine events and the heartbeat
+    clock: Arc<dyn Clock>,
+    /// Where every published event is also written
+    sinks: Vec<Arc<dyn EventSink>>,
     /// Shutdown signal sender
     shutdown_tx: Option<mpsc::Sender<()>>,
 }
@@ -980,10 +1240,29 @@ impl EventEngine {
             bus,
             store,
             config,
+            clock: Arc::new(SystemClock),
+            sinks: Vec::new(),
             shutdown_tx: None,
         }
     }
 
+    /// Use `clock` instead of wall-clock time, e.g. a `ManualClock` in backtests
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Also write every published event, including derived ones, to `sink`
+    pub fn with_sink(mut self, sink: Arc<dyn EventSink>) -> Self {
+        self.sinks.push(sink);
+        self
+    }
+
+    /// The engine's time source
+    pub fn clock(&self) -> Arc<dyn Clock> {
+        self.clock.clone()
+    }
+
     /// Register an event handler
     pub async fn register_handler(&self, handler: Arc<dyn EventHandler>) {
         self.bus.register_handler(handler).await;
@@ -995,6 +1274,7 @@ impl EventEngine {
         if self.config.enable_persistence {
             self.store.append(event.clone()).await?;
         }
+        write_to_sinks(&self.sinks, &event);
 
         // Publish to bus
         let results = self.bus.publish(event).await?;
@@ -1005,10 +1285,31 @@ impl EventEngine {
                 self.store.append(result.clone()).await?;
             }
         }
+        for result in &results {
+            write_to_sinks(&self.sinks, result);
+        }
 
         Ok(results)
     }
 
+    /// Publish an event and, breadth-first, every event its handlers derive
+    ///
+    /// Returns all derived events in the order they were published.
+    pub async fn publish_cascade(&self, event: Event) -> Result<Vec<Event>, EventError> {
+        let mut queue = std::collections::VecDeque::from([event]);
+        let mut derived = Vec::new();
+        while let Some(event) = queue.pop_front() {
+            if self.config.enable_persistence {
+                self.store.append(event.clone()).await?;
+            }
+            write_to_sinks(&self.sinks, &event);
+            let results = self.bus.publish(event).await?;
+            derived.extend(results.iter().cloned());
+            queue.extend(results);
+        }
+        Ok(derived)
+    }
+
     /// Start the engine with heartbeat
     pub async fn start(&self) -> mpsc::Receiver<()> {
         self.bus.start().await;
@@ -1018,7 +1319,8 @@ impl EventEngine {
             EventType::System,
             "EventEngine",
             EventPayload::System(SystemPayload::startup("EventEngine")),
-        );
+        )
+        .with_timestamp(self.clock.now());
         let _ = self.publish(startup).await;
 
         // Create shutdown channel
@@ -1029,11 +1331,22 @@ impl EventEngine {
         let store = self.store.clone();
         let interval = self.config.heartbeat_interval_ms;
         let enable_persistence = self.config.enable_persistence;
+        let sinks = self.sinks.clone();
+        let clock = self.clock.clone();
+        // Ticks are scheduled from the start time so they do not drift with
+        // handler latency; ticks missed while stalled are skipped
+        let period = chrono::Duration::milliseconds(interval as i64);
+        let mut next = clock.now();
 
         tokio::spawn(async move {
             let mut tick = 0u64;
             loop {
-                tokio::time::sleep(tokio::time::Duration::from_millis(interval)).await;
+                next += period;
+                clock.sleep_until(next).await;
+                let now = clock.now();
+                if now - next >= period {
+                    next = now;
+                }
 
                 if !bus.is_running().await {
                     break;
@@ -1044,11 +1357,13 @@ impl EventEngine {
                     EventType::Timer,
                     "EventEngine",
                     EventPayload::Timer(TimerPayload::new("heartbeat", interval, tick)),
-                );
+                )
+                .with_timestamp(now);
 
                 if enable_persistence {
                     let _ = store.append(heartbeat.clone()).await;
                 }
+                write_to_sinks(&sinks, &heartbeat);
                 let _ = bus.publish(heartbeat).await;
             }
         });
@@ -1063,7 +1378,8 @@ impl EventEngine {
             EventType::System,
             "EventEngine",
             EventPayload::System(SystemPayload::shutdown("EventEngine")),
-        );
+        )
+        .with_timestamp(self.clock.now());
         let _ = self.publish(shutdown).await;
 
         self.bus.stop().await;
@@ -1083,6 +1399,15 @@ impl EventEngine {
     }
 }
 
+/// Tee an event to the engine's sinks; a failing sink is logged, not fatal
+fn write_to_sinks(sinks: &[Arc<dyn EventSink>], event: &Event) {
+    for sink in sinks {
+        if let Err(e) = sink.write(event) {
+            tracing::warn!("Failed to write event {} to sink: {}", event.id, e);
+        }
+    }
+}
+
 /// Event engine metrics
 #[derive(Debug, Clone)]
 pub struct EventEngineMetrics {
@@ -1332,6 +1657,183 @@ mod tests {
         assert_eq!(correlated.len(), 3);
     }
 
+    /// Net filled quantity per symbol
+    #[derive(Debug, Default, PartialEq)]
+    struct FillBook(HashMap<String, Decimal>);
+
+    impl Snapshottable for FillBook {
+        type State = HashMap<String, Decimal>;
+
+        fn snapshot_state(&self) -> Self::State {
+            self.0.clone()
+        }
+
+        fn from_snapshot(state: Self::State) -> Self {
+            Self(state)
+        }
+
+        fn apply(&mut self, event: &Event) {
+            if let EventPayload::Fill(fill) = &event.payload {
+                let signed = match fill.side {
+                    OrderSide::Buy => fill.quantity,
+                    OrderSide::Sell => -fill.quantity,
+                };
+                *self.0.entry(fill.symbol.clone()).or_default() += signed;
+            }
+        }
+    }
+
+    fn fill_event(symbol: &str, side: OrderSide, quantity: Decimal) -> Event {
+        let fill = FillPayload::new("order", symbol, side, quantity, dec!(0.5));
+        Event::new(EventType::Fill, "test", EventPayload::Fill(fill))
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_recovery_matches_full_replay() {
+        let store = EventStore::new(100).with_snapshot_interval(3);
+        let mut live = FillBook::default();
+        let fills = [
+            ("yes", OrderSide::Buy, dec!(10)),
+            ("no", OrderSide::Buy, dec!(5)),
+            ("yes", OrderSide::Sell, dec!(4)),
+            ("no", OrderSide::Sell, dec!(1)),
+            ("yes", OrderSide::Buy, dec!(2)),
+        ];
+
+        for (i, (symbol, side, qty)) in fills.into_iter().enumerate() {
+            let event = fill_event(symbol, side, qty);
+            live.apply(&event);
+            store.append(event).await.unwrap();
+            if i == 2 {
+                assert!(store.should_snapshot().await);
+                let snapshot = store.snapshot(&live).await.unwrap();
+                assert_eq!(snapshot.sequence, 3);
+                assert!(!store.should_snapshot().await);
+            }
+        }
+
+        // Only the two fills after the snapshot are replayed
+        let snapshot = store.latest_snapshot().await.unwrap();
+        assert_eq!(store.events_since(snapshot.sequence).await.unwrap().len(), 2);
+
+        let recovered: FillBook = store.recover().await.unwrap();
+        let mut replayed = FillBook::default();
+        for event in store.events_since(0).await.unwrap() {
+            replayed.apply(&event);
+        }
+        assert_eq!(recovered, replayed);
+        assert_eq!(recovered, live);
+        assert_eq!(recovered.0["yes"], dec!(8));
+        assert_eq!(recovered.0["no"], dec!(4));
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_survives_trimming() {
+        let store = EventStore::new(3);
+        let mut live = FillBook::default();
+        for _ in 0..4 {
+            let event = fill_event("yes", OrderSide::Buy, dec!(1));
+            live.apply(&event);
+            store.append(event).await.unwrap();
+        }
+        store.snapshot(&live).await.unwrap();
+
+        // The log keeps only 3 events, but the snapshot covers the rest
+        for _ in 0..2 {
+            let event = fill_event("yes", OrderSide::Buy, dec!(1));
+            live.apply(&event);
+            store.append(event).await.unwrap();
+        }
+        let recovered: FillBook = store.recover().await.unwrap();
+        assert_eq!(recovered, live);
+        assert_eq!(recovered.0["yes"], dec!(6));
+
+        // Events before the retained window can no longer be replayed
+        assert!(store.events_since(1).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_follows_manual_clock() {
+        let start = Utc::now() - chrono::Duration::days(30);
+        let clock = Arc::new(ManualClock::new(start));
+        let config = EventEngineConfig { heartbeat_interval_ms: 60_000, ..Default::default() };
+        let engine = EventEngine::new(config).with_clock(clock.clone());
+        let mut rx = engine.bus.subscribe();
+        let _shutdown = engine.start().await;
+
+        let startup = rx.recv().await.unwrap();
+        assert_eq!(startup.timestamp, start);
+
+        for tick in 1..=3u64 {
+            clock.advance(std::time::Duration::from_secs(60));
+            // A minute of simulated time, not of real time
+            let heartbeat = tokio::time::timeout(std::time::Duration::from_secs(5), rx.recv())
+                .await
+                .expect("heartbeat should fire without real sleeping")
+                .unwrap();
+            let EventPayload::Timer(timer) = &heartbeat.payload else {
+                panic!("expected a heartbeat");
+            };
+            assert_eq!(timer.tick_count, tick);
+            assert_eq!(heartbeat.timestamp, start + chrono::Duration::minutes(tick as i64));
+        }
+
+        // Half an interval is not enough for another tick
+        clock.advance(std::time::Duration::from_secs(30));
+        assert!(tokio::time::timeout(std::time::Duration::from_millis(50), rx.recv()).await.is_err());
+        engine.bus.stop().await;
+    }
+
+    #[tokio::test]
+    async fn test_replay_preserves_original_timestamps() {
+        struct Echo;
+
+        #[async_trait::async_trait]
+        impl EventHandler for Echo {
+            fn name(&self) -> &str {
+                "Echo"
+            }
+
+            fn handles(&self) -> Vec<EventType> {
+                vec![EventType::MarketData]
+            }
+
+            async fn handle(&self, event: &Event) -> Result<Vec<Event>, EventError> {
+                let signal = SignalPayload::new("BTC", 1, dec!(1), "echo");
+                Ok(vec![event.create_child(EventType::Signal, "echo", EventPayload::Signal(signal))])
+            }
+        }
+
+        let store = EventStore::new(100);
+        let bus = EventBus::new(100);
+        bus.register_handler(Arc::new(Echo)).await;
+
+        let then = Utc::now() - chrono::Duration::days(7);
+        for i in 0..3 {
+            let payload = MarketDataPayload::price_update("BTC", dec!(100), dec!(101), "test");
+            let event = Event::new(EventType::MarketData, "test", EventPayload::MarketData(payload))
+                .with_timestamp(then + chrono::Duration::seconds(i));
+            store.append(event).await.unwrap();
+        }
+
+        let derived = store.replay(&bus, then, Utc::now()).await.unwrap();
+        let stamps: Vec<_> = derived.iter().map(|e| e.timestamp).collect();
+        let expected: Vec<_> = (0..3).map(|i| then + chrono::Duration::seconds(i)).collect();
+        assert_eq!(stamps, expected);
+    }
+
+    #[test]
+    fn test_market_data_clock_skew() {
+        let exchange = Utc::now();
+        let mut payload = MarketDataPayload::price_update("BTC", dec!(100), dec!(101), "binance");
+        assert_eq!(payload.clock_skew(exchange), None);
+
+        payload.exchange_timestamp = Some(exchange);
+        let skew = payload.clock_skew(exchange + chrono::Duration::milliseconds(250));
+        assert_eq!(skew, Some(chrono::Duration::milliseconds(250)));
+        assert!(payload.clock_skew(exchange - chrono::Duration::seconds(1)).unwrap() < chrono::Duration::zero());
+    }
+
     #[test]
     fn test_orderbook_payload() {
         let bids = vec![(dec!(50000), dec!(1.5)), (dec!(49999), dec!(2.0))];
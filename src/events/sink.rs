@@ -0,0 +1,129 @@
+//! Event sinks for offline analysis
+//!
+//! `EventEngine` tees every event it publishes to its sinks. `JsonlFileSink`
+//! appends them to a JSON Lines file, one event per line, rotating it once
+//! it grows past a size limit; `EventStore::load_jsonl` reads such a file
+//! back for `replay`.
+
+use super::{Event, EventError};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Rotate the log once it reaches this many bytes
+const DEFAULT_MAX_BYTES: u64 = 64 * 1024 * 1024;
+/// Rotated files kept besides the live one
+const DEFAULT_MAX_FILES: usize = 5;
+
+/// Destination for published events
+pub trait EventSink: Send + Sync {
+    /// Record one event
+    fn write(&self, event: &Event) -> Result<(), EventError>;
+}
+
+/// The open log file and its size
+struct OpenLog {
+    file: File,
+    len: u64,
+}
+
+/// Appends events to a JSON Lines file with size-based rotation
+///
+/// When a write would take the file past `max_bytes`, `events.jsonl` is
+/// renamed to `events.jsonl.1` (shifting older ones to `.2`, `.3`, ...,
+/// dropping the oldest beyond `max_files`) and a fresh file is started.
+pub struct JsonlFileSink {
+    path: PathBuf,
+    max_bytes: u64,
+    max_files: usize,
+    log: Mutex<OpenLog>,
+}
+
+impl JsonlFileSink {
+    /// Append to `path`, creating it and its directory if needed
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, EventError> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        let log = open_log(&path)?;
+        Ok(Self {
+            path,
+            max_bytes: DEFAULT_MAX_BYTES,
+            max_files: DEFAULT_MAX_FILES,
+            log: Mutex::new(log),
+        })
+    }
+
+    /// Size at which the file is rotated
+    pub fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Number of rotated files to keep
+    pub fn with_max_files(mut self, max_files: usize) -> Self {
+        self.max_files = max_files;
+        self
+    }
+
+    /// The live file followed by the rotated ones that exist, newest first
+    ///
+    /// Load them in reverse to get events in publication order.
+    pub fn files(&self) -> Vec<PathBuf> {
+        std::iter::once(self.path.clone())
+            .chain((1..=self.max_files).map(|n| rotated_path(&self.path, n)))
+            .filter(|p| p.exists())
+            .collect()
+    }
+
+    fn rotate(&self, log: &mut OpenLog) -> Result<(), EventError> {
+        log.file.flush().map_err(io_error)?;
+        if self.max_files == 0 {
+            std::fs::remove_file(&self.path).map_err(io_error)?;
+        } else {
+            for n in (1..self.max_files).rev() {
+                let from = rotated_path(&self.path, n);
+                if from.exists() {
+                    std::fs::rename(&from, rotated_path(&self.path, n + 1)).map_err(io_error)?;
+                }
+            }
+            std::fs::rename(&self.path, rotated_path(&self.path, 1)).map_err(io_error)?;
+        }
+        *log = open_log(&self.path)?;
+        Ok(())
+    }
+}
+
+impl EventSink for JsonlFileSink {
+    fn write(&self, event: &Event) -> Result<(), EventError> {
+        let mut line = serde_json::to_vec(event).map_err(|e| EventError::SerializationError(e.to_string()))?;
+        line.push(b'\n');
+
+        let mut log = self.log.lock().unwrap();
+        if log.len > 0 && log.len + line.len() as u64 > self.max_bytes {
+            self.rotate(&mut log)?;
+        }
+        log.file.write_all(&line).map_err(io_error)?;
+        log.len += line.len() as u64;
+        Ok(())
+    }
+}
+
+/// `events.jsonl` -> `events.jsonl.<n>`
+fn rotated_path(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+fn open_log(path: &Path) -> Result<OpenLog, EventError> {
+    let file = OpenOptions::new().create(true).append(true).open(path).map_err(io_error)?;
+    let len = file.metadata().map_err(io_error)?.len();
+    Ok(OpenLog { file, len })
+}
+
+fn io_error(e: std::io::Error) -> EventError {
+    EventError::Io(e.to_string())
+}
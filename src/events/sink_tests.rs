@@ -0,0 +1,121 @@
+//! Tests for the JSONL event log
+
+use super::{
+    Event, EventEngine, EventEngineConfig, EventError, EventHandler, EventPayload, EventSink, EventStore,
+    EventType, JsonlFileSink, MarketDataPayload, SignalPayload,
+};
+use chrono::{Duration, TimeZone, Utc};
+use rust_decimal_macros::dec;
+use std::sync::Arc;
+
+/// Turns every price update into a signal caused by it
+struct SignalOnPrice;
+
+#[async_trait::async_trait]
+impl EventHandler for SignalOnPrice {
+    fn name(&self) -> &str {
+        "signal_on_price"
+    }
+
+    fn handles(&self) -> Vec<EventType> {
+        vec![EventType::MarketData]
+    }
+
+    async fn handle(&self, event: &Event) -> Result<Vec<Event>, EventError> {
+        let signal = Event::new(
+            EventType::Signal,
+            "signal_on_price",
+            EventPayload::Signal(SignalPayload::new("t1", 1, dec!(0.8), "test")),
+        )
+        .with_timestamp(event.timestamp)
+        .with_correlation_id(&event.id)
+        .with_causation_id(&event.id);
+        Ok(vec![signal])
+    }
+}
+
+fn price(n: i64) -> Event {
+    let start = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+    let payload = MarketDataPayload::price_update("t1", dec!(0.50), dec!(0.52), "test");
+    Event::new(EventType::MarketData, "test", EventPayload::MarketData(payload))
+        .with_timestamp(start + Duration::seconds(n))
+}
+
+#[tokio::test]
+async fn test_jsonl_round_trip_through_engine() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("logs/events.jsonl");
+    let sink = Arc::new(JsonlFileSink::open(&path).unwrap());
+    let engine = EventEngine::new(EventEngineConfig::default()).with_sink(sink);
+    engine.register_handler(Arc::new(SignalOnPrice)).await;
+
+    let sent = [price(0), price(1)];
+    for event in &sent {
+        engine.publish(event.clone()).await.unwrap();
+    }
+
+    let store = EventStore::new(100);
+    assert_eq!(store.load_jsonl(&path).await.unwrap(), 4);
+
+    // Each price update followed by the signal it caused, fields intact
+    let start = Utc.with_ymd_and_hms(2024, 5, 1, 0, 0, 0).unwrap();
+    let loaded = store.get_events(start, start + Duration::days(1)).await;
+    assert_eq!(loaded.len(), 4);
+    for (pair, sent) in loaded.chunks(2).zip(&sent) {
+        assert_eq!(
+            serde_json::to_value(&pair[0]).unwrap(),
+            serde_json::to_value(sent).unwrap()
+        );
+        assert!(matches!(pair[1].event_type, EventType::Signal));
+        assert_eq!(pair[1].causation_id.as_deref(), Some(sent.id.as_str()));
+    }
+
+    // Reloaded events replay through a bus like live ones
+    let replay = EventEngine::new(EventEngineConfig::default());
+    replay.register_handler(Arc::new(SignalOnPrice)).await;
+    let derived = store.replay(&replay.bus, start, start + Duration::days(1)).await.unwrap();
+    assert_eq!(derived.len(), 2);
+}
+
+#[tokio::test]
+async fn test_jsonl_sink_rotates_by_size() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("events.jsonl");
+    let line_len = serde_json::to_vec(&price(0)).unwrap().len() as u64 + 1;
+    // Two events per file, at most two rotated files
+    let sink = JsonlFileSink::open(&path)
+        .unwrap()
+        .with_max_bytes(line_len * 2)
+        .with_max_files(2);
+
+    let sent: Vec<Event> = (0..7).map(price).collect();
+    for event in &sent {
+        sink.write(event).unwrap();
+    }
+
+    // 7 events: the oldest 2 rotated away, then .2, .1 and the live file
+    let files = sink.files();
+    assert_eq!(files.len(), 3);
+    assert_eq!(files[0], path);
+
+    let store = EventStore::new(100);
+    let mut loaded = 0;
+    for file in files.iter().rev() {
+        loaded += store.load_jsonl(file).await.unwrap();
+    }
+    assert_eq!(loaded, 5);
+    let ids: Vec<String> = store.events_since(0).await.unwrap().into_iter().map(|e| e.id).collect();
+    let expected: Vec<String> = sent[2..].iter().map(|e| e.id.clone()).collect();
+    assert_eq!(ids, expected);
+}
+
+#[tokio::test]
+async fn test_load_jsonl_reports_bad_line() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("events.jsonl");
+    let good = serde_json::to_string(&price(0)).unwrap();
+    std::fs::write(&path, format!("{}\nnot json\n", good)).unwrap();
+
+    let err = EventStore::new(100).load_jsonl(&path).await.unwrap_err();
+    assert!(matches!(err, EventError::SerializationError(msg) if msg.contains("events.jsonl:2")));
+}
@@ -210,6 +210,7 @@ impl ArbitrageExecutor {
             price: opp.yes_ask,
             size,
             order_type: OrderType::GTC,
+            client_order_id: None,
         };
 
         let no_order = Order {
@@ -218,6 +219,7 @@ impl ArbitrageExecutor {
             price: opp.no_ask,
             size,
             order_type: OrderType::GTC,
+            client_order_id: None,
         };
 
         // Execute both orders concurrently
@@ -0,0 +1,200 @@
+//! Circuit breaker for order execution
+//!
+//! When the CLOB keeps failing (bad credentials, API outage) there is no
+//! point sending every new signal to it. After `failure_threshold`
+//! consecutive failures within `window` the breaker opens and `Executor`
+//! rejects signals immediately. Once `cooldown` has passed it half-opens:
+//! the next order is let through as a probe, closing the breaker if it
+//! succeeds and reopening it if it fails.
+
+use crate::error::{BotError, Result};
+use crate::events::{Event, EventBus, EventPayload, EventPriority, EventType, RiskEventType, RiskPayload, RiskSeverity};
+use crate::notify::{AlertPriority, Notifier};
+use async_trait::async_trait;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Thresholds for `CircuitBreaker`
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the breaker
+    pub failure_threshold: u32,
+    /// Failures older than this no longer count
+    pub window: Duration,
+    /// How long the breaker stays open before probing
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            window: Duration::from_secs(300),
+            cooldown: Duration::from_secs(600),
+        }
+    }
+}
+
+/// Breaker state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    /// Orders go through
+    Closed,
+    /// Orders are rejected until the cooldown ends
+    Open,
+    /// Cooldown over; the next order decides
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct Inner {
+    state: BreakerState,
+    /// Times of the current run of failures
+    failures: VecDeque<Instant>,
+    opened_at: Option<Instant>,
+}
+
+/// Tracks consecutive execution failures
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    inner: Mutex<Inner>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            inner: Mutex::new(Inner {
+                state: BreakerState::Closed,
+                failures: VecDeque::new(),
+                opened_at: None,
+            }),
+        }
+    }
+
+    pub fn state(&self) -> BreakerState {
+        self.inner.lock().unwrap().state
+    }
+
+    /// Fail fast while open; half-opens once the cooldown has passed
+    pub fn check(&self) -> Result<()> {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.state != BreakerState::Open {
+            return Ok(());
+        }
+        let opened_at = inner.opened_at.unwrap_or_else(Instant::now);
+        let elapsed = opened_at.elapsed();
+        if elapsed >= self.config.cooldown {
+            tracing::info!("Circuit breaker half-open, probing with the next order");
+            inner.state = BreakerState::HalfOpen;
+            return Ok(());
+        }
+        Err(BotError::Execution(format!(
+            "Circuit breaker open after {} consecutive failures, retrying in {}s",
+            inner.failures.len(),
+            (self.config.cooldown - elapsed).as_secs()
+        )))
+    }
+
+    /// An order went through; closes the breaker
+    pub fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.state != BreakerState::Closed {
+            tracing::info!("Circuit breaker closed");
+        }
+        inner.state = BreakerState::Closed;
+        inner.failures.clear();
+        inner.opened_at = None;
+    }
+
+    /// An order failed; returns the alert if this opened the breaker
+    pub fn record_failure(&self, error: &BotError) -> Option<RiskPayload> {
+        let mut inner = self.inner.lock().unwrap();
+        let now = Instant::now();
+        inner.failures.push_back(now);
+        while inner
+            .failures
+            .front()
+            .is_some_and(|t| now.duration_since(*t) > self.config.window)
+        {
+            inner.failures.pop_front();
+        }
+
+        let trip = match inner.state {
+            BreakerState::Closed => inner.failures.len() >= self.config.failure_threshold as usize,
+            BreakerState::HalfOpen => true,
+            BreakerState::Open => false,
+        };
+        if !trip {
+            return None;
+        }
+
+        inner.state = BreakerState::Open;
+        inner.opened_at = Some(now);
+        let failures = inner.failures.len();
+        tracing::error!("Circuit breaker opened after {} consecutive failures: {}", failures, error);
+        Some(RiskPayload::circuit_breaker(
+            failures as u32,
+            self.config.failure_threshold,
+            self.config.cooldown,
+            &error.to_string(),
+        ))
+    }
+}
+
+/// Whether an error means the CLOB itself is failing
+///
+/// Risk-limit rejections and empty books are decisions about one signal,
+/// not signs of an outage, so they do not count towards the breaker.
+pub fn is_execution_failure(error: &BotError) -> bool {
+    matches!(
+        error.root(),
+        BotError::Api(_)
+            | BotError::Http { .. }
+            | BotError::Auth(_)
+            | BotError::Network(_)
+            | BotError::Connect(_)
+            | BotError::Timeout(_)
+            | BotError::RateLimited { .. }
+            | BotError::OrderRejected(_)
+    )
+}
+
+/// Receives the alert sent when the breaker opens
+#[async_trait]
+pub trait RiskAlertSink: Send + Sync {
+    async fn risk_alert(&self, alert: &RiskPayload) -> Result<()>;
+}
+
+#[async_trait]
+impl RiskAlertSink for Notifier {
+    async fn risk_alert(&self, alert: &RiskPayload) -> Result<()> {
+        let title = match alert.risk_type {
+            RiskEventType::CircuitBreaker => "Circuit breaker",
+            RiskEventType::DeadMansSwitch => "Dead man's switch",
+            RiskEventType::TakeProfit => "Take profit",
+            RiskEventType::StopLoss => "Stop loss",
+            _ => "Risk",
+        };
+        let priority = match alert.severity {
+            RiskSeverity::Critical | RiskSeverity::Emergency => AlertPriority::Critical,
+            _ => AlertPriority::Normal,
+        };
+        self.risk_alert_with_priority(title, &alert.message, priority).await
+    }
+}
+
+#[async_trait]
+impl RiskAlertSink for EventBus {
+    async fn risk_alert(&self, alert: &RiskPayload) -> Result<()> {
+        let event = Event::new(EventType::Risk, "executor", EventPayload::Risk(alert.clone()))
+            .with_priority(EventPriority::Critical);
+        self.publish(event)
+            .await
+            .map(|_| ())
+            .map_err(|e| BotError::Internal(e.to_string()))
+    }
+}
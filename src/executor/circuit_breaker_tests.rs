@@ -0,0 +1,159 @@
+//! Tests for the execution circuit breaker
+
+use super::circuit_breaker::*;
+use super::test_support::{signal, RecordingAlerts};
+use super::Executor;
+use crate::client::mock::{ClobClientTrait, MockClobClient};
+use crate::client::OrderBook;
+use crate::config::RiskConfig;
+use crate::error::{BotError, Result};
+use crate::events::{RiskAction, RiskEventType};
+use crate::types::{Order, OrderStatus, Position, Side};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Mock CLOB that rejects every order while `down` is set
+#[derive(Default)]
+struct FlakyClob {
+    inner: MockClobClient,
+    down: AtomicBool,
+    submits: AtomicU32,
+}
+
+#[async_trait]
+impl ClobClientTrait for FlakyClob {
+    async fn get_balance(&self) -> Result<Decimal> {
+        self.inner.get_balance().await
+    }
+
+    async fn get_order_book(&self, token_id: &str) -> Result<OrderBook> {
+        self.inner.get_order_book(token_id).await
+    }
+
+    async fn place_order(&self, order: &Order) -> Result<OrderStatus> {
+        self.submits.fetch_add(1, Ordering::SeqCst);
+        if self.down.load(Ordering::SeqCst) {
+            return Err(BotError::Auth("invalid api key".into()));
+        }
+        self.inner.place_order(order).await
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        self.inner.cancel_order(order_id).await
+    }
+
+    async fn get_open_orders(&self) -> Result<Vec<OrderStatus>> {
+        self.inner.get_open_orders().await
+    }
+
+    async fn get_positions(&self) -> Result<Vec<Position>> {
+        self.inner.get_positions().await
+    }
+}
+
+fn config() -> CircuitBreakerConfig {
+    CircuitBreakerConfig {
+        failure_threshold: 3,
+        window: Duration::from_secs(60),
+        cooldown: Duration::from_secs(120),
+    }
+}
+
+fn executor(alerts: Arc<RecordingAlerts>) -> Executor<FlakyClob> {
+    let clob = FlakyClob::default();
+    clob.down.store(true, Ordering::SeqCst);
+    Executor::new(clob, RiskConfig::default())
+        .with_submit_retry(Duration::from_secs(1), 1)
+        .with_circuit_breaker(config())
+        .with_risk_alerts(alerts)
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_consecutive_failures_open_breaker() {
+    let alerts = Arc::new(RecordingAlerts::default());
+    let executor = executor(alerts.clone());
+
+    for _ in 0..2 {
+        assert!(matches!(executor.execute(&signal(Side::Buy, dec!(0.55)), dec!(1000)).await.unwrap_err().root(), BotError::Auth(_)));
+        assert_eq!(executor.breaker_state(), BreakerState::Closed);
+    }
+    assert!(executor.execute(&signal(Side::Buy, dec!(0.55)), dec!(1000)).await.is_err());
+    assert_eq!(executor.breaker_state(), BreakerState::Open);
+
+    let sent = alerts.0.lock().unwrap().clone();
+    assert_eq!(sent.len(), 1);
+    assert_eq!(sent[0].risk_type, RiskEventType::CircuitBreaker);
+    assert_eq!(sent[0].action, RiskAction::HaltTrading);
+    assert!(sent[0].message.contains("invalid api key"));
+
+    // Open: rejected without reaching the CLOB
+    let err = executor.execute(&signal(Side::Buy, dec!(0.55)), dec!(1000)).await.unwrap_err();
+    assert!(matches!(err.root(), BotError::Execution(_)));
+    assert_eq!(executor.clob.submits.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_success_after_cooldown_closes_breaker() {
+    let alerts = Arc::new(RecordingAlerts::default());
+    let executor = executor(alerts.clone());
+    for _ in 0..3 {
+        let _ = executor.execute(&signal(Side::Buy, dec!(0.55)), dec!(1000)).await;
+    }
+    assert_eq!(executor.breaker_state(), BreakerState::Open);
+
+    tokio::time::advance(Duration::from_secs(60)).await;
+    assert!(matches!(executor.execute(&signal(Side::Buy, dec!(0.55)), dec!(1000)).await.unwrap_err().root(), BotError::Execution(_)));
+
+    // Cooldown over and the CLOB is back: the probe goes through
+    tokio::time::advance(Duration::from_secs(61)).await;
+    executor.clob.down.store(false, Ordering::SeqCst);
+    assert!(executor.execute(&signal(Side::Buy, dec!(0.55)), dec!(1000)).await.unwrap().is_some());
+    assert_eq!(executor.breaker_state(), BreakerState::Closed);
+    assert_eq!(executor.clob.submits.load(Ordering::SeqCst), 4);
+    assert_eq!(alerts.0.lock().unwrap().len(), 1);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_failed_probe_reopens_breaker() {
+    let alerts = Arc::new(RecordingAlerts::default());
+    let executor = executor(alerts.clone());
+    for _ in 0..3 {
+        let _ = executor.execute(&signal(Side::Buy, dec!(0.55)), dec!(1000)).await;
+    }
+
+    tokio::time::advance(Duration::from_secs(121)).await;
+    assert!(matches!(executor.execute(&signal(Side::Buy, dec!(0.55)), dec!(1000)).await.unwrap_err().root(), BotError::Auth(_)));
+    assert_eq!(executor.breaker_state(), BreakerState::Open);
+    assert_eq!(alerts.0.lock().unwrap().len(), 2);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_failures_outside_window_do_not_trip() {
+    let breaker = CircuitBreaker::new(config());
+    let err = BotError::Api("503".into());
+
+    assert!(breaker.record_failure(&err).is_none());
+    assert!(breaker.record_failure(&err).is_none());
+    tokio::time::advance(Duration::from_secs(61)).await;
+    assert!(breaker.record_failure(&err).is_none());
+    assert_eq!(breaker.state(), BreakerState::Closed);
+
+    // A success resets the run
+    breaker.record_success();
+    assert!(breaker.record_failure(&err).is_none());
+    assert!(breaker.record_failure(&err).is_none());
+    assert!(breaker.record_failure(&err).is_some());
+    assert_eq!(breaker.state(), BreakerState::Open);
+}
+
+#[test]
+fn test_only_clob_failures_count() {
+    assert!(is_execution_failure(&BotError::Auth("bad key".into())));
+    assert!(is_execution_failure(&BotError::Timeout("slow".into())));
+    assert!(!is_execution_failure(&BotError::RiskLimit("max exposure".into())));
+    assert!(!is_execution_failure(&BotError::Execution("No asks available".into())));
+}
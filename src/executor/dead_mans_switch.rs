@@ -0,0 +1,86 @@
+//! Halting trading when monitoring goes quiet
+//!
+//! A dedicated monitoring task (`Monitor::kick_every`) `kick`s the switch on
+//! the configured interval. If the kicks stop for longer than the timeout,
+//! the trading loop is running without anyone watching it: `watch` trips a
+//! shared halt flag that `Executor` checks before every trade and sends an
+//! emergency alert. A later kick re-arms the switch.
+
+use super::circuit_breaker::RiskAlertSink;
+use crate::events::RiskPayload;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Heartbeat watchdog for the monitoring side of the bot
+pub struct DeadMansSwitch {
+    timeout: Duration,
+    last_kick: Mutex<Instant>,
+    tripped: Arc<AtomicBool>,
+}
+
+impl DeadMansSwitch {
+    /// Switch that trips after `timeout` without a kick, counting from now
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            last_kick: Mutex::new(Instant::now()),
+            tripped: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Record a heartbeat, re-arming the switch if it had tripped
+    pub fn kick(&self) {
+        *self.last_kick.lock().unwrap() = Instant::now();
+        if self.tripped.swap(false, Ordering::SeqCst) {
+            tracing::info!("Monitoring heartbeat resumed, trading re-enabled");
+        }
+    }
+
+    /// How long the switch waits for a kick before tripping
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Whether trading is halted
+    pub fn is_tripped(&self) -> bool {
+        self.tripped.load(Ordering::SeqCst)
+    }
+
+    /// Flag that is set while trading is halted, for `Executor`
+    pub fn halt_flag(&self) -> Arc<AtomicBool> {
+        self.tripped.clone()
+    }
+
+    /// Trip the switch if the last kick is older than the timeout
+    ///
+    /// Returns the alert to send when this call tripped it.
+    pub fn check(&self) -> Option<RiskPayload> {
+        let silent_for = self.last_kick.lock().unwrap().elapsed();
+        if silent_for <= self.timeout || self.tripped.swap(true, Ordering::SeqCst) {
+            return None;
+        }
+        tracing::error!(
+            "No monitoring heartbeat for {}s, halting trading",
+            silent_for.as_secs()
+        );
+        Some(RiskPayload::dead_mans_switch(silent_for, self.timeout))
+    }
+
+    /// Check for missed heartbeats forever, alerting each time the switch trips
+    pub async fn watch(&self, alerts: Option<Arc<dyn RiskAlertSink>>) {
+        let every = (self.timeout / 4).max(Duration::from_millis(100));
+        loop {
+            tokio::time::sleep(every).await;
+            let Some(alert) = self.check() else {
+                continue;
+            };
+            if let Some(alerts) = &alerts {
+                if let Err(e) = alerts.risk_alert(&alert).await {
+                    tracing::warn!("Failed to send dead man's switch alert: {}", e);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! Tests for the monitoring dead man's switch
+
+use super::circuit_breaker::RiskAlertSink;
+use super::test_support::{signal, RecordingAlerts};
+use super::{DeadMansSwitch, Executor};
+use crate::client::mock::MockClobClient;
+use crate::config::RiskConfig;
+use crate::error::BotError;
+use crate::events::{RiskAction, RiskEventType, RiskSeverity};
+use crate::monitor::Monitor;
+use crate::types::Side;
+use rust_decimal_macros::dec;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[tokio::test(start_paused = true)]
+async fn test_missed_heartbeats_block_execution() {
+    let switch = Arc::new(DeadMansSwitch::new(Duration::from_secs(60)));
+    let monitor = Monitor::new(100).with_dead_mans_switch(switch.clone());
+    let executor = Executor::new(MockClobClient::new(), RiskConfig::default()).with_dead_mans_switch(&switch);
+    let alerts = Arc::new(RecordingAlerts::default());
+    let watcher = {
+        let switch = switch.clone();
+        let alerts: Arc<dyn RiskAlertSink> = alerts.clone();
+        tokio::spawn(async move { switch.watch(Some(alerts)).await })
+    };
+
+    // The monitoring task kicks on its interval, keeping trading enabled
+    let monitor = Arc::new(monitor);
+    let interval = Arc::new(Mutex::new(Duration::from_secs(40)));
+    let spawn_kicker = || {
+        let monitor = monitor.clone();
+        let interval = interval.clone();
+        tokio::spawn(async move { monitor.kick_every(|| *interval.lock().unwrap()).await })
+    };
+    let kicker = spawn_kicker();
+    tokio::time::sleep(Duration::from_secs(150)).await;
+    assert!(!switch.is_tripped());
+    assert!(executor.execute(&signal(Side::Buy, dec!(0.55)), dec!(1000)).await.unwrap().is_some());
+
+    // Logging stats no longer counts as a heartbeat
+    kicker.abort();
+    for _ in 0..3 {
+        tokio::time::sleep(Duration::from_secs(30)).await;
+        monitor.log_stats().await;
+    }
+    assert!(switch.is_tripped());
+    let err = executor.execute(&signal(Side::Buy, dec!(0.55)), dec!(1000)).await.unwrap_err();
+    assert!(matches!(err.root(), BotError::RiskLimit(_)));
+
+    // One emergency alert, not one per check
+    tokio::time::sleep(Duration::from_secs(120)).await;
+    {
+        let sent = alerts.0.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].risk_type, RiskEventType::DeadMansSwitch);
+        assert_eq!(sent[0].severity, RiskSeverity::Emergency);
+        assert_eq!(sent[0].action, RiskAction::HaltTrading);
+    }
+
+    // The task coming back re-arms the switch, at the interval read on each tick
+    *interval.lock().unwrap() = Duration::from_secs(5);
+    let kicker = spawn_kicker();
+    tokio::time::sleep(Duration::from_secs(6)).await;
+    assert!(!switch.is_tripped());
+    assert!(executor.execute(&signal(Side::Buy, dec!(0.55)), dec!(1000)).await.unwrap().is_some());
+
+    kicker.abort();
+    watcher.abort();
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_check_trips_only_after_timeout() {
+    let switch = DeadMansSwitch::new(Duration::from_secs(30));
+
+    tokio::time::advance(Duration::from_secs(29)).await;
+    assert!(switch.check().is_none());
+
+    tokio::time::advance(Duration::from_secs(2)).await;
+    let alert = switch.check().unwrap();
+    assert_eq!(alert.threshold, dec!(30));
+    assert!(switch.is_tripped());
+    assert!(switch.check().is_none());
+
+    switch.kick();
+    assert!(!switch.is_tripped());
+}
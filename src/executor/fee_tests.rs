@@ -0,0 +1,139 @@
+//! Tests for fee computation in the executor
+
+use super::test_support::{signal, FillableClob};
+use super::{ExecutionStyle, Executor};
+use crate::client::mock::{ClobClientTrait, MockClobClient};
+use crate::client::{OrderBook, OrderBookLevel};
+use crate::config::{FeeModel, RiskConfig};
+use crate::error::Result;
+use crate::events::{EventBus, EventPayload, FillPayload};
+use crate::types::{Order, OrderStatus, Position, Side};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::sync::Arc;
+
+/// Exchange that fills every order at its limit and reports a $1 fee
+struct FeeReportingClob;
+
+#[async_trait]
+impl ClobClientTrait for FeeReportingClob {
+    async fn get_balance(&self) -> Result<Decimal> {
+        Ok(dec!(10000))
+    }
+
+    async fn get_order_book(&self, _token_id: &str) -> Result<OrderBook> {
+        Ok(book())
+    }
+
+    async fn place_order(&self, order: &Order) -> Result<OrderStatus> {
+        Ok(OrderStatus {
+            order_id: "o1".to_string(),
+            status: "MATCHED".to_string(),
+            filled_size: order.size,
+            remaining_size: Decimal::ZERO,
+            avg_price: Some(order.price),
+            fee: Decimal::ONE,
+        })
+    }
+
+    async fn cancel_order(&self, _order_id: &str) -> Result<()> {
+        Ok(())
+    }
+
+    async fn get_open_orders(&self) -> Result<Vec<OrderStatus>> {
+        Ok(Vec::new())
+    }
+
+    async fn get_positions(&self) -> Result<Vec<Position>> {
+        Ok(Vec::new())
+    }
+}
+
+fn book() -> OrderBook {
+    OrderBook {
+        bids: vec![OrderBookLevel { price: dec!(0.50), size: dec!(1000) }],
+        asks: vec![OrderBookLevel { price: dec!(0.52), size: dec!(1000) }],
+    }
+}
+
+fn maker_taker() -> FeeModel {
+    FeeModel::MakerTaker { maker_bps: dec!(5), taker_bps: dec!(20) }
+}
+
+fn published_fills(rx: &mut tokio::sync::broadcast::Receiver<crate::events::Event>) -> Vec<FillPayload> {
+    let mut fills = Vec::new();
+    while let Ok(event) = rx.try_recv() {
+        if let EventPayload::Fill(fill) = event.payload {
+            fills.push(fill);
+        }
+    }
+    fills
+}
+
+#[tokio::test]
+async fn test_crossing_fill_pays_taker_rate_and_realizes_net_pnl() {
+    let bus = Arc::new(EventBus::new(16));
+    let mut rx = bus.subscribe();
+    let mut clob = MockClobClient::new();
+    clob.set_order_book("t1", book());
+    let executor = Executor::new(clob, RiskConfig::default())
+        .with_fee_model(maker_taker())
+        .with_event_bus(bus);
+
+    // Buy 100 at the 0.52 ask, sell them at the 0.50 bid
+    let buy = executor.execute_shares(&signal(Side::Buy, dec!(0.50)), dec!(100), dec!(10000)).await.unwrap().unwrap();
+    let sell = executor.execute_shares(&signal(Side::Sell, dec!(0.50)), dec!(100), dec!(10000)).await.unwrap().unwrap();
+
+    // 20 bps of $52 and of $50
+    assert_eq!(buy.fee, dec!(0.104));
+    assert_eq!(sell.fee, dec!(0.100));
+
+    let fills = published_fills(&mut rx);
+    assert_eq!(fills.len(), 2);
+    assert!(fills.iter().all(|f| !f.is_maker));
+    assert_eq!(fills[0].commission, dec!(0.104));
+    assert_eq!(fills[0].realized_pnl, None);
+    // -$2.00 on price, less both fees
+    assert_eq!(fills[1].realized_pnl, Some(dec!(-2.204)));
+}
+
+#[tokio::test]
+async fn test_resting_order_pays_maker_rate() {
+    let bus = Arc::new(EventBus::new(16));
+    let mut rx = bus.subscribe();
+    let passive = Executor::new(FillableClob::default(), RiskConfig::default())
+        .with_fee_model(maker_taker())
+        .with_execution_style(ExecutionStyle::Passive { aggressiveness: Decimal::ZERO })
+        .with_event_bus(bus);
+
+    // Joins the 0.48 bid; nothing is charged until it fills
+    let trade = passive.execute_shares(&signal(Side::Buy, dec!(0.50)), dec!(100), dec!(10000)).await.unwrap();
+    assert!(trade.is_none());
+    assert!(published_fills(&mut rx).is_empty());
+
+    // 40 shares fill: 5 bps of $19.20
+    passive.clob.fill("o1", dec!(40));
+    passive.refresh_resting_orders().await.unwrap();
+    let trades = passive.take_late_fills().await;
+    assert_eq!(trades.len(), 1);
+    assert_eq!(trades[0].price, dec!(0.48));
+    assert_eq!(trades[0].fee, dec!(0.0096));
+
+    let fills = published_fills(&mut rx);
+    assert_eq!(fills.len(), 1);
+    assert!(fills[0].is_maker);
+    assert_eq!(fills[0].commission, dec!(0.0096));
+}
+
+#[tokio::test]
+async fn test_zero_fee_model_overrides_reported_fee() {
+    let reported = Executor::new(FeeReportingClob, RiskConfig::default());
+    let zero = Executor::new(FeeReportingClob, RiskConfig::default()).with_fee_model(FeeModel::default());
+
+    let trade = reported.execute_shares(&signal(Side::Buy, dec!(0.50)), dec!(100), dec!(10000)).await.unwrap().unwrap();
+    assert_eq!(trade.fee, Decimal::ONE);
+
+    let trade = zero.execute_shares(&signal(Side::Buy, dec!(0.50)), dec!(100), dec!(10000)).await.unwrap().unwrap();
+    assert_eq!(trade.fee, Decimal::ZERO);
+}
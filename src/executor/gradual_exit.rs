@@ -383,6 +383,7 @@ impl GradualExitExecutor {
             price: limit_price,
             size: action.sell_size,
             order_type: crate::types::OrderType::GTC,
+            client_order_id: None,
         };
 
         info!(
@@ -411,6 +412,8 @@ impl GradualExitExecutor {
             size: action.sell_size,
             fee: Decimal::ZERO,
             timestamp: chrono::Utc::now(),
+            client_order_id: None,
+            correlation_id: None,
         };
 
         Ok(Some(trade))
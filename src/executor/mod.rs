@@ -7,11 +7,21 @@ pub mod slippage_predictor;
 pub mod price_optimizer;
 pub mod twap_vwap;
 pub mod latency_optimizer;
+pub mod position_manager;
+pub mod circuit_breaker;
+pub mod dead_mans_switch;
+pub mod mode;
 
 pub use arbitrage_executor::{ArbitrageExecutor, ArbitrageExecutorConfig, ArbitrageResult, ArbitrageMetrics};
 pub use slippage_predictor::{SlippagePredictor, SlippageConfig, SlippagePrediction, OrderBook, OrderSide};
 pub use price_optimizer::{PriceOptimizer, PriceOptimizerConfig, PriceRecommendation, ExecutionUrgency, RecommendedOrderType};
 pub use twap_vwap::{ExecutionEngine, ExecutionConfig, ExecutionAlgorithm, ExecutionSummary, default_crypto_volume_profile};
+pub use circuit_breaker::{BreakerState, CircuitBreaker, CircuitBreakerConfig, RiskAlertSink};
+pub use dead_mans_switch::DeadMansSwitch;
+pub use mode::ExecutionMode;
+pub use position_manager::{
+    BookMarks, ExitManager, ExitOutcome, ExitPlan, ExitTracker, ExitTrigger, MarkSource, OrderBookMarks, PositionManagerTask,
+};
 pub use latency_optimizer::{
     LatencyOptimizer, LatencyConfig, LatencyStats, LatencySummary,
     LocalOrderBook, OrderBookDelta, PriceLevel,
@@ -25,60 +35,524 @@ mod tests;
 mod smart_executor_tests;
 #[cfg(test)]
 mod gradual_exit_tests;
+#[cfg(test)]
+mod position_manager_tests;
+#[cfg(test)]
+mod circuit_breaker_tests;
+#[cfg(test)]
+mod mode_tests;
+#[cfg(test)]
+mod partial_fill_tests;
+#[cfg(test)]
+mod dead_mans_switch_tests;
+#[cfg(test)]
+mod fee_tests;
+#[cfg(test)]
+mod self_trade_tests;
+#[cfg(test)]
+mod staleness_tests;
+#[cfg(test)]
+pub(crate) mod test_support;
 
-use crate::client::ClobClient;
-use crate::config::RiskConfig;
-use crate::error::{BotError, Result};
-use crate::types::{Order, OrderType, Signal, Trade};
+use crate::client::mock::ClobClientTrait;
+use crate::client::{ClobClient, OrderBookMaintainer};
+use crate::config::{FeeModel, RiskConfig};
+use crate::error::{BotError, Result, ResultExt};
+use crate::events::{Event, EventBus, EventPayload, EventPriority, EventType, FillPayload};
+use crate::strategy::TradeThrottle;
+use crate::types::{Order, OrderStatus, OrderType, Position, Side, Signal, Trade};
 use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::RwLock;
 
+/// How long to wait for the CLOB to acknowledge an order
+const DEFAULT_SUBMIT_TIMEOUT: Duration = Duration::from_secs(10);
+/// Submission attempts before giving up on a timed-out order
+const DEFAULT_SUBMIT_ATTEMPTS: u32 = 3;
+/// Polymarket's default price increment
+const DEFAULT_TICK_SIZE: Decimal = dec!(0.01);
+/// Order sizes are whole hundredths of a share
+const SIZE_INCREMENT: Decimal = dec!(0.01);
+
+/// Round `price` to a multiple of `tick` away from the opposite side of
+/// the book: buyers round down and sellers up, so the order never pays
+/// more or receives less than the unrounded price
+pub fn round_to_tick(price: Decimal, tick: Decimal, side: Side) -> Decimal {
+    if tick <= Decimal::ZERO {
+        return price;
+    }
+    let ticks = price / tick;
+    let ticks = match side {
+        Side::Buy => ticks.floor(),
+        Side::Sell => ticks.ceil(),
+    };
+    ticks * tick
+}
+
+/// An order of ours that may still be waiting on the book
+#[derive(Debug, Clone)]
+struct RestingOrder {
+    /// Signal the order was placed for, to book later fills against
+    signal: Signal,
+    client_order_id: Option<String>,
+    token_id: String,
+    side: Side,
+    price: Decimal,
+    /// Size as submitted
+    order_size: Decimal,
+    /// Size still unfilled
+    size: Decimal,
+}
+
+impl RestingOrder {
+    fn new(signal: &Signal, order: &Order, size: Decimal) -> Self {
+        Self {
+            signal: signal.clone(),
+            client_order_id: order.client_order_id.clone(),
+            token_id: order.token_id.clone(),
+            side: order.side,
+            price: order.price,
+            order_size: order.size,
+            size,
+        }
+    }
+
+    /// Whether an order on `side` at `price` would match against this one
+    fn crossed_by(&self, token_id: &str, side: Side, price: Decimal) -> bool {
+        self.token_id == token_id && crosses(self.side, self.price, side, price)
+    }
+}
+
+/// Whether an order on `side` at `price` would match one resting on
+/// `resting_side` at `resting_price` in the same token
+fn crosses(resting_side: Side, resting_price: Decimal, side: Side, price: Decimal) -> bool {
+    match (resting_side, side) {
+        (Side::Buy, Side::Sell) => price <= resting_price,
+        (Side::Sell, Side::Buy) => price >= resting_price,
+        _ => false,
+    }
+}
+
+/// Price and size increments for one token's orders
+#[derive(Debug, Clone, Copy)]
+struct OrderIncrements {
+    tick_size: Decimal,
+    min_size: Decimal,
+}
+
+/// How `Executor` prices its limit orders
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExecutionStyle {
+    /// Take the best opposite-side quote
+    Cross,
+    /// Post inside the spread; see `Executor::compute_limit_price`
+    Passive { aggressiveness: Decimal },
+}
+
+/// What `Executor` does with the unfilled rest of a partially filled order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PartialFillPolicy {
+    /// Leave the remainder resting at its limit price
+    #[default]
+    Wait,
+    /// Cancel the remainder and resubmit it at the current price, at most
+    /// `max_chases` times
+    ///
+    /// Chasing stops, and the remainder is cancelled, once the new price no
+    /// longer beats the signal's model probability.
+    Reprice { max_chases: u32 },
+    /// Cancel the remainder and keep what filled
+    CancelRemainder,
+}
+
+/// Fills accumulated across an order and its re-priced remainders
+#[derive(Debug, Default)]
+struct FillTotals {
+    size: Decimal,
+    notional: Decimal,
+    fee: Decimal,
+}
+
+impl FillTotals {
+    fn add(&mut self, size: Decimal, price: Decimal, fee: Decimal) {
+        self.size += size;
+        self.notional += size * price;
+        self.fee += fee;
+    }
+
+    fn avg_price(&self) -> Option<Decimal> {
+        (self.size > Decimal::ZERO).then(|| self.notional / self.size)
+    }
+}
+
+/// See `Executor::compute_limit_price`
+fn passive_price(book: &crate::client::OrderBook, side: Side, aggressiveness: Decimal, tick_size: Decimal) -> Option<Decimal> {
+    let (join, cross) = match side {
+        Side::Buy => (book.best_bid(), book.best_ask()),
+        Side::Sell => (book.best_ask(), book.best_bid()),
+    };
+    let (join, cross) = match (join, cross) {
+        (Some(join), Some(cross)) => (join, cross),
+        (Some(price), None) | (None, Some(price)) => return Some(price),
+        (None, None) => return None,
+    };
+
+    let aggressiveness = aggressiveness.clamp(Decimal::ZERO, Decimal::ONE);
+    Some(round_to_tick(join + (cross - join) * aggressiveness, tick_size, side))
+}
+
 /// Trade executor with risk management
-pub struct Executor {
-    pub clob: ClobClient,
+///
+/// Generic over the CLOB client so the same execution path can run against
+/// `MockClobClient` in tests.
+pub struct Executor<C = ClobClient> {
+    pub clob: C,
     risk_config: RiskConfig,
     positions: RwLock<HashMap<String, Decimal>>, // token_id -> size
     daily_pnl: RwLock<Decimal>,
+    submit_timeout: Duration,
+    submit_attempts: u32,
+    breaker: CircuitBreaker,
+    alerts: Option<Arc<dyn RiskAlertSink>>,
+    style: ExecutionStyle,
+    tick_size: Decimal,
+    partial_fill_policy: PartialFillPolicy,
+    resting: RwLock<HashMap<String, RestingOrder>>, // order_id -> unfilled order
+    /// Fills on resting orders not yet handed out by `take_late_fills`
+    late_fills: RwLock<Vec<Trade>>,
+    events: Option<Arc<EventBus>>,
+    /// Set by a `DeadMansSwitch` when monitoring stops
+    halted: Option<Arc<AtomicBool>>,
+    /// Cleared to pause trading, e.g. by the control API
+    trading_enabled: Option<Arc<AtomicBool>>,
+    /// Bot-wide limit on how often trades are submitted
+    throttle: Option<Arc<TradeThrottle>>,
+    /// Fee schedule; `None` books the fee the CLOB reports
+    fee_model: Option<FeeModel>,
+    /// Entry price net of fees per token, for realized P&L on closing fills
+    cost_basis: RwLock<HashMap<String, Position>>,
+    /// Per-token increments from `register_market`
+    increments: RwLock<HashMap<String, OrderIncrements>>,
+    /// Books kept current from the market websocket, read before the CLOB
+    order_books: Option<Arc<RwLock<OrderBookMaintainer>>>,
 }
 
-impl Executor {
-    pub fn new(clob: ClobClient, risk_config: RiskConfig) -> Self {
+impl<C: ClobClientTrait> Executor<C> {
+    pub fn new(clob: C, risk_config: RiskConfig) -> Self {
         Self {
             clob,
             risk_config,
             positions: RwLock::new(HashMap::new()),
             daily_pnl: RwLock::new(Decimal::ZERO),
+            submit_timeout: DEFAULT_SUBMIT_TIMEOUT,
+            submit_attempts: DEFAULT_SUBMIT_ATTEMPTS,
+            breaker: CircuitBreaker::new(CircuitBreakerConfig::default()),
+            alerts: None,
+            style: ExecutionStyle::Cross,
+            tick_size: DEFAULT_TICK_SIZE,
+            partial_fill_policy: PartialFillPolicy::default(),
+            resting: RwLock::new(HashMap::new()),
+            late_fills: RwLock::new(Vec::new()),
+            events: None,
+            halted: None,
+            trading_enabled: None,
+            throttle: None,
+            fee_model: None,
+            cost_basis: RwLock::new(HashMap::new()),
+            increments: RwLock::new(HashMap::new()),
+            order_books: None,
+        }
+    }
+
+    /// Price orders passively or by crossing the spread
+    pub fn with_execution_style(mut self, style: ExecutionStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Price increment limit prices are rounded to, for tokens of markets
+    /// that were not registered or gave no tick size
+    pub fn with_tick_size(mut self, tick_size: Decimal) -> Self {
+        self.tick_size = tick_size;
+        self
+    }
+
+    /// Per-attempt timeout and number of attempts for order submission
+    pub fn with_submit_retry(mut self, timeout: Duration, attempts: u32) -> Self {
+        self.submit_timeout = timeout;
+        self.submit_attempts = attempts.max(1);
+        self
+    }
+
+    /// Thresholds for halting execution on repeated CLOB failures
+    pub fn with_circuit_breaker(mut self, config: CircuitBreakerConfig) -> Self {
+        self.breaker = CircuitBreaker::new(config);
+        self
+    }
+
+    /// Where to send the alert when the circuit breaker opens
+    pub fn with_risk_alerts(mut self, alerts: Arc<dyn RiskAlertSink>) -> Self {
+        self.alerts = Some(alerts);
+        self
+    }
+
+    /// How to handle the remainder of partially filled orders
+    pub fn with_partial_fill_policy(mut self, policy: PartialFillPolicy) -> Self {
+        self.partial_fill_policy = policy;
+        self
+    }
+
+    /// Publish a `Fill` event for every fill, including partial ones
+    pub fn with_event_bus(mut self, events: Arc<EventBus>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Refuse to trade while the switch is tripped
+    pub fn with_dead_mans_switch(mut self, switch: &DeadMansSwitch) -> Self {
+        self.halted = Some(switch.halt_flag());
+        self
+    }
+
+    /// Refuse to trade while `enabled` is false
+    pub fn with_trading_switch(mut self, enabled: Arc<AtomicBool>) -> Self {
+        self.trading_enabled = Some(enabled);
+        self
+    }
+
+    /// Price from websocket-maintained books where they have the token
+    pub fn with_order_books(mut self, books: Arc<RwLock<OrderBookMaintainer>>) -> Self {
+        self.order_books = Some(books);
+        self
+    }
+
+    /// Refuse to trade faster than the throttle allows
+    pub fn with_trade_throttle(mut self, throttle: Arc<TradeThrottle>) -> Self {
+        self.throttle = Some(throttle);
+        self
+    }
+
+    /// Compute fees from this schedule instead of taking them from the CLOB
+    pub fn with_fee_model(mut self, fees: FeeModel) -> Self {
+        self.fee_model = Some(fees);
+        self
+    }
+
+    /// Record a market's tick size and minimum order size for its tokens
+    pub async fn register_market(&self, market: &crate::types::Market) {
+        let increments = OrderIncrements {
+            tick_size: market.tick_size.unwrap_or(self.tick_size),
+            min_size: market.min_size.unwrap_or(Decimal::ZERO),
+        };
+        let mut registered = self.increments.write().await;
+        for outcome in &market.outcomes {
+            registered.insert(outcome.token_id.clone(), increments);
         }
+        self.clob.register_market(market);
+    }
+
+    /// Risk limits this executor enforces
+    pub fn risk_config(&self) -> &RiskConfig {
+        &self.risk_config
+    }
+
+    /// Current circuit breaker state
+    pub fn breaker_state(&self) -> BreakerState {
+        self.breaker.state()
     }
 
     /// Execute a trading signal
+    ///
+    /// Fails fast without contacting the CLOB while the circuit breaker is
+    /// open, the dead man's switch has tripped, trading is paused or the
+    /// trade throttle is saturated.
     pub async fn execute(&self, signal: &Signal, portfolio_value: Decimal) -> Result<Option<Trade>> {
-        // Pre-trade risk checks
-        self.check_risk_limits(signal, portfolio_value).await?;
-
         // Calculate actual order size
         let size_usd = signal.suggested_size * portfolio_value;
         let size_shares = size_usd / signal.market_probability;
+        self.execute_shares(signal, size_shares, portfolio_value).await
+    }
 
-        // Get current market price for limit order
-        let book = self.clob.get_order_book(&signal.token_id).await?;
-        let limit_price = match signal.side {
-            crate::types::Side::Buy => book
-                .best_ask()
-                .ok_or_else(|| BotError::Execution("No asks available".into()))?,
-            crate::types::Side::Sell => book
-                .best_bid()
-                .ok_or_else(|| BotError::Execution("No bids available".into()))?,
+    /// Execute a signal whose order has already been sized, in shares
+    ///
+    /// Errors carry the signal's market id as context.
+    pub async fn execute_shares(
+        &self,
+        signal: &Signal,
+        size_shares: Decimal,
+        portfolio_value: Decimal,
+    ) -> Result<Option<Trade>> {
+        self.execute_checked(signal, size_shares, portfolio_value)
+            .await
+            .context_market(&signal.market_id)
+    }
+
+    /// Close a position with a fill-or-kill order against the touch
+    ///
+    /// Unlike `execute` this still runs while trading is paused or halted,
+    /// since closing only takes risk off. The fill is booked like any other;
+    /// `None` means the order was killed without filling.
+    pub async fn close_position(&self, position: &Position) -> Result<Option<Trade>> {
+        let side = match position.side {
+            Side::Buy => Side::Sell,
+            Side::Sell => Side::Buy,
+        };
+        let book = self
+            .order_book(&position.token_id)
+            .await
+            .context_operation("get_order_book")
+            .context_market(&position.market_id)?;
+        let price = match side {
+            Side::Sell => book.best_bid(),
+            Side::Buy => book.best_ask(),
+        }
+        .unwrap_or(position.current_price);
+
+        let client_order_id = format!("close-{}", uuid::Uuid::new_v4().simple());
+        let order = Order {
+            token_id: position.token_id.clone(),
+            side,
+            price,
+            size: position.size,
+            order_type: OrderType::FOK,
+            client_order_id: Some(client_order_id.clone()),
+        };
+        tracing::info!("Closing {} {} shares @ {:.4}", position.token_id, position.size, price);
+        let status = self
+            .submit(&order)
+            .await
+            .context_order(client_order_id)
+            .context_operation("place_order")
+            .context_market(&position.market_id)?;
+        if status.filled_size <= Decimal::ZERO {
+            return Ok(None);
+        }
+
+        // Book the fill as if a signal had asked for the close
+        let signal = Signal {
+            market_id: position.market_id.clone(),
+            token_id: position.token_id.clone(),
+            side,
+            model_probability: price,
+            market_probability: price,
+            edge: Decimal::ZERO,
+            net_edge: Decimal::ZERO,
+            confidence: Decimal::ONE,
+            suggested_size: Decimal::ZERO,
+            timestamp: chrono::Utc::now(),
+            max_staleness: None,
         };
+        let mut fills = FillTotals::default();
+        self.record_fill(&signal, &status, price, false, &mut fills).await;
+        self.update_position(&position.token_id, side, fills.size).await;
+
+        Ok(Some(Trade {
+            id: uuid::Uuid::new_v4().to_string(),
+            order_id: status.order_id,
+            token_id: position.token_id.clone(),
+            market_id: position.market_id.clone(),
+            side,
+            price: fills.avg_price().unwrap_or(price),
+            size: fills.size,
+            fee: fills.fee,
+            timestamp: chrono::Utc::now(),
+            client_order_id: order.client_order_id,
+            correlation_id: None,
+        }))
+    }
+
+    async fn execute_checked(
+        &self,
+        signal: &Signal,
+        size_shares: Decimal,
+        portfolio_value: Decimal,
+    ) -> Result<Option<Trade>> {
+        if self.halted.as_ref().is_some_and(|h| h.load(Ordering::SeqCst)) {
+            return Err(BotError::RiskLimit("Trading halted: monitoring heartbeat lost".into()));
+        }
+        if self.trading_enabled.as_ref().is_some_and(|e| !e.load(Ordering::SeqCst)) {
+            return Err(BotError::RiskLimit("Trading paused".into()));
+        }
+        if let Some(reason) = self.throttle.as_ref().and_then(|t| t.check()) {
+            return Err(BotError::RiskLimit(format!("Trade throttled: {}", reason)));
+        }
+
+        // Pre-trade risk checks
+        self.check_risk_limits(signal, size_shares * signal.market_probability, portfolio_value)
+            .await?;
+        self.breaker.check()?;
+
+        let result = self.place_for_signal(signal, size_shares).await;
+        match &result {
+            Ok(trade) => {
+                self.breaker.record_success();
+                if let (Some(throttle), Some(_)) = (&self.throttle, trade) {
+                    throttle.record();
+                }
+            }
+            Err(e) if circuit_breaker::is_execution_failure(e) => {
+                if let Some(alert) = self.breaker.record_failure(e) {
+                    if let Some(alerts) = &self.alerts {
+                        if let Err(e) = alerts.risk_alert(&alert).await {
+                            tracing::warn!("Failed to send circuit breaker alert: {}", e);
+                        }
+                    }
+                }
+            }
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// The maintained book for a token, or a fresh one from the CLOB while
+    /// there is none (no snapshot yet, or awaiting a resync)
+    async fn order_book(&self, token_id: &str) -> Result<crate::types::OrderBook> {
+        if let Some(books) = &self.order_books {
+            if let Some(book) = books.read().await.book(token_id) {
+                return Ok(book);
+            }
+        }
+        self.clob.get_order_book(token_id).await
+    }
+
+    /// Price and submit the order for a signal
+    async fn place_for_signal(&self, signal: &Signal, size_shares: Decimal) -> Result<Option<Trade>> {
+        // Get current market price for limit order
+        let book = self.order_book(&signal.token_id).await.context_operation("get_order_book")?;
+        if let Some(reason) = self.stale_reason(signal, &book, chrono::Utc::now()) {
+            tracing::info!("Skipping signal for {}: {}", signal.market_id, reason);
+            return Ok(None);
+        }
+        let increments = self.increments_for(&signal.token_id).await;
+        let limit_price = self.limit_price(&book, signal.side, increments.tick_size)?;
+        if let Err(e) = self.refresh_resting_orders().await {
+            tracing::warn!("Cannot refresh resting orders: {}", e);
+        }
+        self.check_self_trade(signal, limit_price).await?;
+        // Round down so the order never exceeds its budget
+        let size_shares = (size_shares / SIZE_INCREMENT).floor() * SIZE_INCREMENT;
+        if size_shares <= Decimal::ZERO || size_shares < increments.min_size {
+            return Err(BotError::Validation(format!(
+                "Order size {} is below the market minimum of {} shares",
+                size_shares, increments.min_size
+            )));
+        }
 
         // Create and place order
+        // One key per signal: retries in `submit` reuse it, while a later
+        // signal always gets its own and never a cached status
+        let client_order_id = uuid::Uuid::new_v4().simple().to_string();
         let order = Order {
             token_id: signal.token_id.clone(),
             side: signal.side,
             price: limit_price,
             size: size_shares,
             order_type: OrderType::GTC,
+            client_order_id: Some(client_order_id.clone()),
         };
 
         tracing::info!(
@@ -92,27 +566,428 @@ impl Executor {
             limit_price
         );
 
-        let order_status = self.clob.place_order(&order).await?;
+        let order_status = self
+            .submit(&order)
+            .await
+            .context_order(client_order_id)
+            .context_operation("place_order")?;
+
+        // Book only what the client reports filled (paper trading, the live
+        // CLOB's `sizeMatched`); an order that matched nothing on arrival is
+        // tracked as resting and its fills booked by `refresh_resting_orders`
+        let order_id = order_status.order_id.clone();
+        if order_status.filled_size <= Decimal::ZERO {
+            if order_status.remaining_size > Decimal::ZERO {
+                tracing::info!("Order {} is resting unfilled @ {:.4}", order_id, limit_price);
+                self.resting
+                    .write()
+                    .await
+                    .insert(order_id, RestingOrder::new(signal, &order, order_status.remaining_size));
+            }
+            return Ok(None);
+        }
+        let mut fills = FillTotals::default();
+        self.record_fill(signal, &order_status, limit_price, false, &mut fills).await;
+        if order_status.remaining_size > Decimal::ZERO {
+            self.work_remainder(signal, order.clone(), order_status, &mut fills).await;
+        }
+        let fill_size = fills.size;
+        let fill_price = fills.avg_price().unwrap_or(limit_price);
 
         // Update positions
-        self.update_position(&signal.token_id, signal.side, size_shares)
+        self.update_position(&signal.token_id, signal.side, fill_size)
             .await;
 
         Ok(Some(Trade {
             id: uuid::Uuid::new_v4().to_string(),
-            order_id: order_status.order_id,
+            order_id,
             token_id: signal.token_id.clone(),
             market_id: signal.market_id.clone(),
             side: signal.side,
-            price: limit_price,
-            size: size_shares,
-            fee: Decimal::ZERO, // TODO: Calculate fee
+            price: fill_price,
+            size: fill_size,
+            fee: fills.fee,
             timestamp: chrono::Utc::now(),
+            client_order_id: order.client_order_id,
+            correlation_id: None,
         }))
     }
 
+    /// Increments for a token, falling back to the executor's tick size
+    async fn increments_for(&self, token_id: &str) -> OrderIncrements {
+        self.increments.read().await.get(token_id).copied().unwrap_or(OrderIncrements {
+            tick_size: self.tick_size,
+            min_size: Decimal::ZERO,
+        })
+    }
+
+    /// Why a signal is too old or its price too far gone to trade, judged
+    /// against the order book fetched just before submitting
+    fn stale_reason(
+        &self,
+        signal: &Signal,
+        book: &crate::client::OrderBook,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<String> {
+        let max_age = signal.max_staleness.or_else(|| {
+            self.risk_config
+                .max_signal_age_secs
+                .map(|secs| chrono::Duration::seconds(secs as i64))
+        });
+        if let Some(max_age) = max_age {
+            let age = now - signal.timestamp;
+            if age > max_age {
+                return Some(format!(
+                    "signal is {}s old, limit {}s",
+                    age.num_seconds(),
+                    max_age.num_seconds()
+                ));
+            }
+        }
+
+        let max_move = self.risk_config.max_price_move?;
+        let price = book.mid().or_else(|| book.best_bid()).or_else(|| book.best_ask())?;
+        let moved = (price - signal.market_probability).abs();
+        (moved > max_move).then(|| {
+            format!(
+                "price moved from {:.4} to {:.4}, more than {}",
+                signal.market_probability, price, max_move
+            )
+        })
+    }
+
+    /// Refuse an order that would trade against ourselves
+    ///
+    /// That is an order crossing one of our open orders on the token, or a
+    /// buy of one outcome while holding another outcome of the same market,
+    /// which only pays the spread to offset it. Open orders are asked of
+    /// the CLOB every time, so orders left by an earlier run count too; a
+    /// client that can't list them falls back to the resting orders this
+    /// executor placed, as of the last `refresh_resting_orders`.
+    async fn check_self_trade(&self, signal: &Signal, price: Decimal) -> Result<()> {
+        let crossed: Vec<String> = match self.clob.get_open_orders_for(&signal.token_id).await {
+            Ok(open) => open
+                .iter()
+                .filter(|o| crosses(o.side, o.price, signal.side, price))
+                .map(|o| o.order_id.clone())
+                .collect(),
+            Err(e) => {
+                tracing::debug!("Checking self-trades against known resting orders only: {}", e);
+                self.resting
+                    .read()
+                    .await
+                    .iter()
+                    .filter(|(_, resting)| resting.crossed_by(&signal.token_id, signal.side, price))
+                    .map(|(id, _)| id.clone())
+                    .collect()
+            }
+        };
+        if !crossed.is_empty() {
+            tracing::warn!(
+                "Refusing {:?} {} @ {:.4}: would cross own resting order {}",
+                signal.side,
+                signal.token_id,
+                price,
+                crossed.join(", ")
+            );
+            return Err(BotError::RiskLimit(format!(
+                "Self-trade prevented: order would cross own resting order {}",
+                crossed.join(", ")
+            )));
+        }
+
+        if signal.side != Side::Buy {
+            return Ok(());
+        }
+        let positions = match self.clob.get_positions().await {
+            Ok(positions) => positions,
+            Err(e) => {
+                tracing::warn!("Cannot check positions for self-trades: {}", e);
+                return Ok(());
+            }
+        };
+        let offset = positions.iter().find(|p| {
+            p.market_id == signal.market_id
+                && p.token_id != signal.token_id
+                && p.side == Side::Buy
+                && p.size > Decimal::ZERO
+        });
+        if let Some(held) = offset {
+            tracing::warn!(
+                "Refusing buy of {}: would offset own {} shares of {} in {}",
+                signal.token_id,
+                held.size,
+                held.token_id,
+                signal.market_id
+            );
+            return Err(BotError::RiskLimit(format!(
+                "Self-trade prevented: buying {} would offset own position in {}",
+                signal.token_id, held.token_id
+            )));
+        }
+        Ok(())
+    }
+
+    /// Limit price for the configured execution style, on `tick_size`
+    fn limit_price(&self, book: &crate::client::OrderBook, side: Side, tick_size: Decimal) -> Result<Decimal> {
+        let price = match (self.style, side) {
+            (ExecutionStyle::Passive { aggressiveness }, side) => passive_price(book, side, aggressiveness, tick_size)
+                .ok_or_else(|| BotError::Execution("Empty order book".into())),
+            (ExecutionStyle::Cross, Side::Buy) => book
+                .best_ask()
+                .ok_or_else(|| BotError::Execution("No asks available".into())),
+            (ExecutionStyle::Cross, Side::Sell) => book
+                .best_bid()
+                .ok_or_else(|| BotError::Execution("No bids available".into())),
+        }?;
+        Ok(round_to_tick(price, tick_size, side))
+    }
+
+    /// Apply the partial fill policy to an order's unfilled remainder
+    ///
+    /// Whatever is still unfilled afterwards is left resting and tracked in
+    /// `resting_orders`. Failures here are logged rather than returned so
+    /// the portion that already filled is still booked.
+    async fn work_remainder(&self, signal: &Signal, mut order: Order, mut status: OrderStatus, fills: &mut FillTotals) {
+        let base_id = order.client_order_id.clone().unwrap_or_default();
+        let mut chases = 0;
+        while status.remaining_size > Decimal::ZERO {
+            let max_chases = match self.partial_fill_policy {
+                PartialFillPolicy::Wait => break,
+                PartialFillPolicy::CancelRemainder => {
+                    self.cancel_remainder(signal, &order, &status).await;
+                    return;
+                }
+                PartialFillPolicy::Reprice { max_chases } => max_chases,
+            };
+            if chases >= max_chases {
+                break;
+            }
+
+            let price = match self.order_book(&signal.token_id).await {
+                Ok(book) => self.limit_price(&book, signal.side, self.increments_for(&signal.token_id).await.tick_size),
+                Err(e) => Err(e),
+            };
+            let price = match price {
+                Ok(price) => price,
+                Err(e) => {
+                    tracing::warn!("Cannot re-price remainder of {}: {}", status.order_id, e);
+                    break;
+                }
+            };
+            let still_has_edge = match signal.side {
+                Side::Buy => price < signal.model_probability,
+                Side::Sell => price > signal.model_probability,
+            };
+            if !still_has_edge {
+                tracing::info!(
+                    "Market moved to {:.4} past model {:.4}, cancelling remainder of {}",
+                    price,
+                    signal.model_probability,
+                    status.order_id
+                );
+                self.cancel_remainder(signal, &order, &status).await;
+                return;
+            }
+            if !self.cancel_remainder(signal, &order, &status).await {
+                break;
+            }
+
+            chases += 1;
+            order = Order {
+                price,
+                size: status.remaining_size,
+                client_order_id: Some(format!("{}-r{}", base_id, chases)),
+                ..order
+            };
+            tracing::info!(
+                "Re-pricing {:.2} remaining shares of {} @ {:.4} (chase {}/{})",
+                order.size,
+                signal.token_id,
+                price,
+                chases,
+                max_chases
+            );
+            status = match self.submit(&order).await {
+                Ok(status) => status,
+                Err(e) => {
+                    tracing::warn!("Re-priced order for {} failed: {}", signal.token_id, e);
+                    return;
+                }
+            };
+            if status.filled_size > Decimal::ZERO {
+                self.record_fill(signal, &status, price, false, fills).await;
+            }
+        }
+        if status.remaining_size > Decimal::ZERO {
+            self.resting
+                .write()
+                .await
+                .insert(status.order_id, RestingOrder::new(signal, &order, status.remaining_size));
+        }
+    }
+
+    /// Cancel an order's unfilled remainder; false if the cancel failed
+    ///
+    /// A remainder that could not be cancelled stays in `resting_orders`.
+    async fn cancel_remainder(&self, signal: &Signal, order: &Order, status: &OrderStatus) -> bool {
+        match self.clob.cancel_order(&status.order_id).await {
+            Ok(()) => {
+                self.resting.write().await.remove(&status.order_id);
+                true
+            }
+            Err(e) => {
+                tracing::warn!("Failed to cancel remainder of {}: {}", status.order_id, e);
+                self.resting
+                    .write()
+                    .await
+                    .insert(status.order_id.clone(), RestingOrder::new(signal, order, status.remaining_size));
+                false
+            }
+        }
+    }
+
+    /// Add a (possibly partial) fill to the totals and publish it
+    ///
+    /// Fills reported straight back from a submission matched on arrival,
+    /// so they are taker fills; fills on a resting order are maker fills.
+    async fn record_fill(
+        &self,
+        signal: &Signal,
+        status: &OrderStatus,
+        limit_price: Decimal,
+        is_maker: bool,
+        fills: &mut FillTotals,
+    ) {
+        let price = status.avg_price.unwrap_or(limit_price);
+        let fee = self.fill_fee(status.filled_size, price, is_maker, status.fee);
+        fills.add(status.filled_size, price, fee);
+        let realized_pnl = self.book_cost(signal, status.filled_size, price, fee).await;
+
+        let Some(events) = &self.events else { return };
+        let side = match signal.side {
+            Side::Buy => crate::events::OrderSide::Buy,
+            Side::Sell => crate::events::OrderSide::Sell,
+        };
+        let mut fill = FillPayload::new(&status.order_id, &signal.token_id, side, status.filled_size, price);
+        fill.commission = fee;
+        fill.is_maker = is_maker;
+        fill.realized_pnl = realized_pnl;
+        let event = Event::new(EventType::Fill, "executor", EventPayload::Fill(fill))
+            .with_priority(EventPriority::Critical);
+        if let Err(e) = events.publish(event).await {
+            tracing::warn!("Failed to publish fill for {}: {}", status.order_id, e);
+        }
+    }
+
+    /// Fee for a fill: from the fee model if there is one, else as reported
+    fn fill_fee(&self, size: Decimal, price: Decimal, is_maker: bool, reported: Decimal) -> Decimal {
+        match &self.fee_model {
+            Some(fees) => fees.fee(size, price, is_maker),
+            None => reported,
+        }
+    }
+
+    /// Update the signal token's cost basis for a fill
+    ///
+    /// Returns the P&L, net of entry and exit fees, when the fill closed
+    /// some of a position.
+    async fn book_cost(&self, signal: &Signal, size: Decimal, price: Decimal, fee: Decimal) -> Option<Decimal> {
+        let mut basis = self.cost_basis.write().await;
+        let position = basis.entry(signal.token_id.clone()).or_insert_with(|| Position {
+            token_id: signal.token_id.clone(),
+            market_id: signal.market_id.clone(),
+            side: signal.side,
+            size: Decimal::ZERO,
+            avg_entry_price: Decimal::ZERO,
+            current_price: price,
+            unrealized_pnl: Decimal::ZERO,
+        });
+        let closes = position.size > Decimal::ZERO && position.side != signal.side;
+        let realized = position.apply_fill(signal.side, size, price, fee);
+        if position.size.is_zero() {
+            basis.remove(&signal.token_id);
+        }
+        closes.then_some(realized)
+    }
+
+    /// Limit price between joining the queue and crossing the spread
+    ///
+    /// `aggressiveness` 0 joins the best same-side quote, 1 takes the best
+    /// opposite-side quote, and values in between interpolate across the
+    /// spread. The result is rounded to the tick size away from the opposite
+    /// side, so it never crosses further than asked. With only one side of
+    /// the book present that side's quote is used; `None` if both are empty.
+    pub fn compute_limit_price(&self, book: &crate::client::OrderBook, side: Side, aggressiveness: Decimal) -> Option<Decimal> {
+        passive_price(book, side, aggressiveness, self.tick_size)
+    }
+
+    /// Place an order, retrying submissions that failed transiently
+    ///
+    /// Rate-limited attempts wait out the server's back-off first; errors
+    /// that are not `is_retryable` are returned straight away.
+    ///
+    /// Only a failure that never reached the CLOB (connection refused, 429)
+    /// is resent as is. After a timeout or 5xx the CLOB may already hold
+    /// the order, so it is looked up by client order id first and resent
+    /// only if the CLOB says it never arrived; if the lookup itself fails
+    /// the original error is returned rather than risk a second order.
+    async fn submit(&self, order: &Order) -> Result<OrderStatus> {
+        let mut attempt = 1;
+        loop {
+            let err = match tokio::time::timeout(self.submit_timeout, self.clob.place_order(order)).await {
+                Ok(Ok(status)) => return Ok(status),
+                Ok(Err(e)) if e.is_retryable() => e,
+                Ok(Err(e)) => return Err(e),
+                Err(_) => BotError::Timeout(format!(
+                    "Order submission timed out after {}ms",
+                    self.submit_timeout.as_millis()
+                )),
+            };
+            if attempt >= self.submit_attempts {
+                return Err(err);
+            }
+            if !err.never_reached_server() {
+                match self.find_submitted(order).await {
+                    Ok(Some(status)) => {
+                        tracing::info!(
+                            "Order {} reached the CLOB despite {}",
+                            order.client_order_id.as_deref().unwrap_or("-"),
+                            err
+                        );
+                        return Ok(status);
+                    }
+                    Ok(None) => {}
+                    Err(lookup) => {
+                        tracing::warn!("Not resending order after {}: {}", err, lookup);
+                        return Err(err);
+                    }
+                }
+            }
+            tracing::warn!(
+                "Order {} attempt {} failed ({}), retrying",
+                order.client_order_id.as_deref().unwrap_or("-"),
+                attempt,
+                err
+            );
+            if let Some(delay) = err.retry_after() {
+                tokio::time::sleep(delay).await;
+            }
+            attempt += 1;
+        }
+    }
+
+    /// The CLOB's copy of a submitted order, `None` if it never arrived
+    async fn find_submitted(&self, order: &Order) -> Result<Option<OrderStatus>> {
+        let key = order
+            .client_order_id
+            .as_deref()
+            .ok_or_else(|| BotError::Execution("Order has no client order id to look up".into()))?;
+        self.clob.find_order(key).await
+    }
+
     /// Check all risk limits before trading
-    async fn check_risk_limits(&self, signal: &Signal, portfolio_value: Decimal) -> Result<()> {
+    async fn check_risk_limits(&self, signal: &Signal, size_usd: Decimal, portfolio_value: Decimal) -> Result<()> {
         // Check daily loss limit
         let daily_pnl = *self.daily_pnl.read().await;
         let max_loss = self.risk_config.max_daily_loss_pct * portfolio_value;
@@ -136,7 +1011,7 @@ impl Executor {
 
         // Check total exposure
         let total_exposure: Decimal = positions.values().sum();
-        let new_exposure = total_exposure + signal.suggested_size * portfolio_value;
+        let new_exposure = total_exposure + size_usd;
         let max_exposure = self.risk_config.max_exposure_pct * portfolio_value;
         if new_exposure > max_exposure {
             return Err(BotError::RiskLimit(format!(
@@ -185,4 +1060,100 @@ impl Executor {
     pub async fn get_positions(&self) -> HashMap<String, Decimal> {
         self.positions.read().await.clone()
     }
+
+    /// Book fills on resting orders and forget those the CLOB no longer
+    /// lists as open, because they filled or were cancelled
+    ///
+    /// An open order whose unfilled size shrank has filled the difference.
+    /// An order that is gone is looked up by client order id for fills
+    /// since the last refresh; if the lookup fails it is forgotten unbooked.
+    /// Booked fills are collected for `take_late_fills`.
+    pub async fn refresh_resting_orders(&self) -> Result<()> {
+        if self.resting.read().await.is_empty() {
+            return Ok(());
+        }
+        let open = self.clob.get_open_orders().await?;
+        let mut filled = Vec::new();
+        let mut gone = Vec::new();
+        self.resting.write().await.retain(|id, resting| match open.iter().find(|o| &o.order_id == id) {
+            Some(status) => {
+                if status.remaining_size > Decimal::ZERO && status.remaining_size < resting.size {
+                    filled.push((id.clone(), resting.clone(), resting.size - status.remaining_size));
+                    resting.size = status.remaining_size;
+                }
+                true
+            }
+            None => {
+                gone.push((id.clone(), resting.clone()));
+                false
+            }
+        });
+
+        for (id, resting) in gone {
+            let Some(key) = resting.client_order_id.as_deref() else { continue };
+            match self.clob.find_order(key).await {
+                Ok(Some(status)) => {
+                    let booked = resting.order_size - resting.size;
+                    let size = status.filled_size - booked;
+                    if size > Decimal::ZERO {
+                        filled.push((id, resting, size));
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => tracing::warn!("Cannot look up fills of resting order {}: {}", id, e),
+            }
+        }
+
+        for (id, resting, size) in filled {
+            let trade = self.book_resting_fill(&id, &resting, size).await;
+            self.late_fills.write().await.push(trade);
+        }
+        Ok(())
+    }
+
+    /// Book `size` shares filled on a resting order at its limit price
+    async fn book_resting_fill(&self, order_id: &str, resting: &RestingOrder, size: Decimal) -> Trade {
+        tracing::info!("Resting order {} filled {:.2} shares @ {:.4}", order_id, size, resting.price);
+        let status = OrderStatus {
+            order_id: order_id.to_string(),
+            status: "MATCHED".to_string(),
+            filled_size: size,
+            remaining_size: Decimal::ZERO,
+            avg_price: None,
+            fee: Decimal::ZERO,
+        };
+        let mut fills = FillTotals::default();
+        self.record_fill(&resting.signal, &status, resting.price, true, &mut fills).await;
+        self.update_position(&resting.token_id, resting.side, fills.size).await;
+
+        Trade {
+            id: uuid::Uuid::new_v4().to_string(),
+            order_id: order_id.to_string(),
+            token_id: resting.token_id.clone(),
+            market_id: resting.signal.market_id.clone(),
+            side: resting.side,
+            price: resting.price,
+            size: fills.size,
+            fee: fills.fee,
+            timestamp: chrono::Utc::now(),
+            client_order_id: resting.client_order_id.clone(),
+            correlation_id: None,
+        }
+    }
+
+    /// Trades booked on resting orders since the last call
+    pub async fn take_late_fills(&self) -> Vec<Trade> {
+        std::mem::take(&mut *self.late_fills.write().await)
+    }
+
+    /// Partially filled orders left on the book, by order id, with their
+    /// unfilled size
+    pub async fn resting_orders(&self) -> HashMap<String, Decimal> {
+        self.resting
+            .read()
+            .await
+            .iter()
+            .map(|(id, order)| (id.clone(), order.size))
+            .collect()
+    }
 }
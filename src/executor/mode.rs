@@ -0,0 +1,103 @@
+//! Live or paper order routing
+//!
+//! `Executor` runs the same sizing, risk checks and fill booking in both
+//! modes; only the final CLOB calls differ. In paper mode orders go to a
+//! `PaperClobClient` that reads books from the live client but never sends
+//! it an order.
+
+use crate::client::mock::ClobClientTrait;
+use crate::client::{ClobClient, OrderBook, PaperClobClient};
+use crate::config::{FeeModel, PaperConfig};
+use crate::error::Result;
+use crate::types::{Market, OpenOrder, Order, OrderStatus, Position};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+
+/// Where `Executor` sends orders
+pub enum ExecutionMode<C = ClobClient> {
+    /// Orders go to the exchange
+    Live(C),
+    /// Orders are filled against `C`'s books in a virtual account
+    Paper(PaperClobClient<C>),
+}
+
+impl<C: ClobClientTrait> ExecutionMode<C> {
+    /// Paper mode for dry runs, live otherwise
+    pub fn from_config(clob: C, dry_run: bool, paper: &PaperConfig) -> Self {
+        if dry_run {
+            Self::Paper(PaperClobClient::from_config(clob, paper))
+        } else {
+            Self::Live(clob)
+        }
+    }
+
+    /// Fee schedule for paper fills; live fills are charged by the exchange
+    pub fn with_fee_model(self, fees: FeeModel) -> Self {
+        match self {
+            Self::Paper(paper) => Self::Paper(paper.with_fee_model(fees)),
+            live => live,
+        }
+    }
+
+    pub fn is_paper(&self) -> bool {
+        matches!(self, Self::Paper(_))
+    }
+
+    /// The paper account, in paper mode
+    pub fn paper(&self) -> Option<&PaperClobClient<C>> {
+        match self {
+            Self::Paper(paper) => Some(paper),
+            Self::Live(_) => None,
+        }
+    }
+
+    fn client(&self) -> &dyn ClobClientTrait {
+        match self {
+            Self::Live(clob) => clob,
+            Self::Paper(paper) => paper,
+        }
+    }
+}
+
+#[async_trait]
+impl<C: ClobClientTrait> ClobClientTrait for ExecutionMode<C> {
+    async fn get_balance(&self) -> Result<Decimal> {
+        self.client().get_balance().await
+    }
+
+    async fn get_order_book(&self, token_id: &str) -> Result<OrderBook> {
+        self.client().get_order_book(token_id).await
+    }
+
+    async fn place_order(&self, order: &Order) -> Result<OrderStatus> {
+        self.client().place_order(order).await
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        self.client().cancel_order(order_id).await
+    }
+
+    async fn get_open_orders(&self) -> Result<Vec<OrderStatus>> {
+        self.client().get_open_orders().await
+    }
+
+    async fn get_positions(&self) -> Result<Vec<Position>> {
+        self.client().get_positions().await
+    }
+
+    async fn get_open_orders_for(&self, token_id: &str) -> Result<Vec<OpenOrder>> {
+        self.client().get_open_orders_for(token_id).await
+    }
+
+    async fn find_order(&self, client_order_id: &str) -> Result<Option<OrderStatus>> {
+        self.client().find_order(client_order_id).await
+    }
+
+    fn register_market(&self, market: &Market) {
+        self.client().register_market(market)
+    }
+
+    async fn cancel_all(&self) -> Result<usize> {
+        self.client().cancel_all().await
+    }
+}
@@ -0,0 +1,68 @@
+//! Tests for live/paper execution routing
+
+use super::test_support::signal;
+use super::{ExecutionMode, Executor};
+use crate::client::mock::{ClobClientTrait, MockClobClient};
+use crate::client::{OrderBook, OrderBookLevel};
+use crate::config::{PaperConfig, RiskConfig};
+use crate::types::Side;
+use rust_decimal_macros::dec;
+
+/// Mock exchange with a 0.50/0.52 book for t1
+fn live_clob() -> MockClobClient {
+    let mut clob = MockClobClient::new().with_balance(dec!(5000));
+    clob.set_order_book(
+        "t1",
+        OrderBook {
+            bids: vec![OrderBookLevel { price: dec!(0.50), size: dec!(1000) }],
+            asks: vec![OrderBookLevel { price: dec!(0.52), size: dec!(1000) }],
+        },
+    );
+    clob
+}
+
+fn paper_config() -> PaperConfig {
+    PaperConfig { starting_balance: dec!(1000), fee_rate: dec!(0.01) }
+}
+
+#[tokio::test]
+async fn test_paper_mode_books_a_paper_trade() {
+    let live = live_clob();
+    let live_state = live.state();
+    let executor = Executor::new(ExecutionMode::from_config(live, true, &paper_config()), RiskConfig::default());
+    assert!(executor.clob.is_paper());
+
+    let trade = executor.execute(&signal(Side::Buy, dec!(0.52)), dec!(1000)).await.unwrap().unwrap();
+
+    // $50 at the 0.52 ask, rounded down to whole hundredths of a share,
+    // filled by the paper account
+    assert_eq!(trade.market_id, "m1");
+    assert_eq!(trade.price, dec!(0.52));
+    assert_eq!(trade.size, dec!(96.15));
+    assert_eq!(trade.fee, dec!(0.49998));
+    let paper = executor.clob.paper().unwrap().state();
+    assert_eq!(paper.read().unwrap().orders.len(), 1);
+    assert_eq!(executor.clob.get_balance().await.unwrap(), dec!(949.50202));
+
+    // The live client only supplied the book
+    assert!(live_state.read().unwrap().orders.is_empty());
+    assert_eq!(live_state.read().unwrap().balance, dec!(5000));
+}
+
+#[tokio::test]
+async fn test_live_mode_submits_to_the_clob() {
+    let live = live_clob();
+    let live_state = live.state();
+    let executor = Executor::new(ExecutionMode::from_config(live, false, &paper_config()), RiskConfig::default());
+    assert!(!executor.clob.is_paper());
+    assert!(executor.clob.paper().is_none());
+
+    let trade = executor.execute(&signal(Side::Buy, dec!(0.52)), dec!(1000)).await.unwrap().unwrap();
+
+    let orders = live_state.read().unwrap().orders.clone();
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].token_id, "t1");
+    assert_eq!(orders[0].price, dec!(0.52));
+    assert_eq!(trade.order_id, orders[0].order_id);
+    assert_eq!(trade.client_order_id, orders[0].client_order_id);
+}
@@ -0,0 +1,241 @@
+//! Tests for partial fill handling
+
+use super::test_support::{signal, FillableClob};
+use super::{Executor, PartialFillPolicy};
+use crate::client::mock::ClobClientTrait;
+use crate::client::{OrderBook, OrderBookLevel};
+use crate::config::RiskConfig;
+use crate::error::Result;
+use crate::events::{EventBus, EventPayload};
+use crate::types::{Order, OrderStatus, Position, Side};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::sync::{Arc, Mutex};
+
+/// Exchange that fills a scripted fraction of each order and moves its
+/// book to the next scripted one after every placement
+struct PartialFillClob {
+    book: Mutex<OrderBook>,
+    next_books: Mutex<Vec<OrderBook>>,
+    fill_fractions: Mutex<Vec<Decimal>>,
+    placed: Mutex<Vec<Order>>,
+    cancelled: Mutex<Vec<String>>,
+}
+
+impl PartialFillClob {
+    /// `fill_fractions` and `next_books` are consumed front to back
+    fn new(book: OrderBook, next_books: Vec<OrderBook>, fill_fractions: Vec<Decimal>) -> Self {
+        Self {
+            book: Mutex::new(book),
+            next_books: Mutex::new(next_books.into_iter().rev().collect()),
+            fill_fractions: Mutex::new(fill_fractions.into_iter().rev().collect()),
+            placed: Mutex::new(Vec::new()),
+            cancelled: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl ClobClientTrait for PartialFillClob {
+    async fn get_balance(&self) -> Result<Decimal> {
+        Ok(dec!(10000))
+    }
+
+    async fn get_order_book(&self, _token_id: &str) -> Result<OrderBook> {
+        Ok(self.book.lock().unwrap().clone())
+    }
+
+    async fn place_order(&self, order: &Order) -> Result<OrderStatus> {
+        let fraction = self.fill_fractions.lock().unwrap().pop().unwrap_or(Decimal::ONE);
+        let mut placed = self.placed.lock().unwrap();
+        placed.push(order.clone());
+        if let Some(book) = self.next_books.lock().unwrap().pop() {
+            *self.book.lock().unwrap() = book;
+        }
+
+        let filled = order.size * fraction;
+        Ok(OrderStatus {
+            order_id: format!("o{}", placed.len()),
+            status: if fraction < Decimal::ONE { "PARTIALLY_FILLED" } else { "FILLED" }.to_string(),
+            filled_size: filled,
+            remaining_size: order.size - filled,
+            avg_price: Some(order.price),
+            fee: Decimal::ZERO,
+        })
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        self.cancelled.lock().unwrap().push(order_id.to_string());
+        Ok(())
+    }
+
+    async fn get_open_orders(&self) -> Result<Vec<OrderStatus>> {
+        Ok(Vec::new())
+    }
+
+    async fn get_positions(&self) -> Result<Vec<Position>> {
+        Ok(Vec::new())
+    }
+}
+
+fn book(bid: Decimal, ask: Decimal) -> OrderBook {
+    OrderBook {
+        bids: vec![OrderBookLevel { price: bid, size: dec!(1000) }],
+        asks: vec![OrderBookLevel { price: ask, size: dec!(1000) }],
+    }
+}
+
+fn executor(clob: PartialFillClob, policy: PartialFillPolicy) -> Executor<PartialFillClob> {
+    Executor::new(clob, RiskConfig::default()).with_partial_fill_policy(policy)
+}
+
+#[tokio::test]
+async fn test_reprice_completes_partially_filled_order() {
+    // 40% fills at 0.50, then the ask moves to 0.52 and the re-priced
+    // remainder fills completely
+    let clob = PartialFillClob::new(
+        book(dec!(0.48), dec!(0.50)),
+        vec![book(dec!(0.50), dec!(0.52))],
+        vec![dec!(0.4), dec!(1)],
+    );
+    let bus = Arc::new(EventBus::new(16));
+    let mut fills = bus.subscribe();
+    let executor = executor(clob, PartialFillPolicy::Reprice { max_chases: 2 }).with_event_bus(bus);
+
+    let trade = executor
+        .execute_shares(&signal(Side::Buy, dec!(0.50)), dec!(100), dec!(1000))
+        .await
+        .unwrap()
+        .unwrap();
+
+    // 40 @ 0.50 + 60 @ 0.52
+    assert_eq!(trade.size, dec!(100));
+    assert_eq!(trade.price, dec!(0.512));
+    assert_eq!(trade.order_id, "o1");
+    assert_eq!(executor.get_positions().await["t1"], dec!(100));
+    assert!(executor.resting_orders().await.is_empty());
+
+    let placed = executor.clob.placed.lock().unwrap().clone();
+    assert_eq!(placed.len(), 2);
+    assert_eq!(placed[1].price, dec!(0.52));
+    assert_eq!(placed[1].size, dec!(60));
+    assert_eq!(placed[1].client_order_id, Some(format!("{}-r1", placed[0].client_order_id.as_ref().unwrap())));
+    assert_eq!(*executor.clob.cancelled.lock().unwrap(), vec!["o1".to_string()]);
+
+    // One fill event per partial
+    let mut published = Vec::new();
+    while let Ok(event) = fills.try_recv() {
+        if let EventPayload::Fill(fill) = event.payload {
+            published.push((fill.order_id, fill.quantity, fill.price));
+        }
+    }
+    assert_eq!(
+        published,
+        vec![
+            ("o1".to_string(), dec!(40), dec!(0.50)),
+            ("o2".to_string(), dec!(60), dec!(0.52)),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_reprice_stops_when_market_moves_past_model() {
+    // After the partial fill the ask jumps above the 0.70 model probability
+    let clob = PartialFillClob::new(
+        book(dec!(0.48), dec!(0.50)),
+        vec![book(dec!(0.70), dec!(0.72))],
+        vec![dec!(0.4)],
+    );
+    let executor = executor(clob, PartialFillPolicy::Reprice { max_chases: 3 });
+
+    let trade = executor
+        .execute_shares(&signal(Side::Buy, dec!(0.50)), dec!(100), dec!(1000))
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(trade.size, dec!(40));
+    assert_eq!(executor.clob.placed.lock().unwrap().len(), 1);
+    assert_eq!(*executor.clob.cancelled.lock().unwrap(), vec!["o1".to_string()]);
+    assert!(executor.resting_orders().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_reprice_leaves_last_remainder_resting_after_max_chases() {
+    let clob = PartialFillClob::new(
+        book(dec!(0.48), dec!(0.50)),
+        Vec::new(),
+        vec![dec!(0.4), dec!(0.5)],
+    );
+    let executor = executor(clob, PartialFillPolicy::Reprice { max_chases: 1 });
+
+    let trade = executor
+        .execute_shares(&signal(Side::Buy, dec!(0.50)), dec!(100), dec!(1000))
+        .await
+        .unwrap()
+        .unwrap();
+
+    // 40, then half of the remaining 60
+    assert_eq!(trade.size, dec!(70));
+    assert_eq!(executor.resting_orders().await["o2"], dec!(30));
+}
+
+#[tokio::test]
+async fn test_wait_and_cancel_remainder_policies() {
+    let partial = || PartialFillClob::new(book(dec!(0.48), dec!(0.50)), Vec::new(), vec![dec!(0.4)]);
+
+    let waiting = executor(partial(), PartialFillPolicy::Wait);
+    let trade = waiting.execute_shares(&signal(Side::Buy, dec!(0.50)), dec!(100), dec!(1000)).await.unwrap().unwrap();
+    assert_eq!(trade.size, dec!(40));
+    assert_eq!(waiting.resting_orders().await["o1"], dec!(60));
+    assert!(waiting.clob.cancelled.lock().unwrap().is_empty());
+
+    let cancelling = executor(partial(), PartialFillPolicy::CancelRemainder);
+    let trade = cancelling.execute_shares(&signal(Side::Buy, dec!(0.50)), dec!(100), dec!(1000)).await.unwrap().unwrap();
+    assert_eq!(trade.size, dec!(40));
+    assert_eq!(cancelling.get_positions().await["t1"], dec!(40));
+    assert!(cancelling.resting_orders().await.is_empty());
+    assert_eq!(*cancelling.clob.cancelled.lock().unwrap(), vec!["o1".to_string()]);
+}
+
+#[tokio::test]
+async fn test_resting_orders_are_forgotten_once_no_longer_open() {
+    // o1 rests 60 shares; the stub CLOB then lists no open orders, as if
+    // the remainder filled
+    let clob = PartialFillClob::new(book(dec!(0.48), dec!(0.50)), Vec::new(), vec![dec!(0.4)]);
+    let executor = executor(clob, PartialFillPolicy::Wait);
+    executor.execute_shares(&signal(Side::Buy, dec!(0.50)), dec!(100), dec!(1000)).await.unwrap().unwrap();
+    assert_eq!(executor.resting_orders().await["o1"], dec!(60));
+
+    executor.refresh_resting_orders().await.unwrap();
+    assert!(executor.resting_orders().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_unfilled_order_is_booked_only_as_it_fills() {
+    let executor = Executor::new(FillableClob::default(), RiskConfig::default());
+    let trade = executor.execute_shares(&signal(Side::Buy, dec!(0.50)), dec!(100), dec!(1000)).await.unwrap();
+    assert!(trade.is_none());
+    assert!(executor.get_positions().await.is_empty());
+    assert_eq!(executor.resting_orders().await["o1"], dec!(100));
+
+    // A partial fill shrinks the open order
+    executor.clob.fill("o1", dec!(30));
+    executor.refresh_resting_orders().await.unwrap();
+    assert_eq!(executor.resting_orders().await["o1"], dec!(70));
+    assert_eq!(executor.get_positions().await["t1"], dec!(30));
+    let late = executor.take_late_fills().await;
+    assert_eq!(late.len(), 1);
+    assert_eq!((late[0].order_id.as_str(), late[0].size, late[0].price), ("o1", dec!(30), dec!(0.50)));
+
+    // The rest fills and the order leaves the open list; only the 70
+    // not yet booked is
+    executor.clob.fill("o1", dec!(70));
+    executor.refresh_resting_orders().await.unwrap();
+    assert!(executor.resting_orders().await.is_empty());
+    assert_eq!(executor.get_positions().await["t1"], dec!(100));
+    let late = executor.take_late_fills().await;
+    assert_eq!(late.iter().map(|t| t.size).collect::<Vec<_>>(), vec![dec!(70)]);
+    assert!(executor.take_late_fills().await.is_empty());
+}
@@ -0,0 +1,377 @@
+//! Take-profit / stop-loss management for open positions
+//!
+//! A `PositionManagerTask` watches the mark of one open position and sends a
+//! closing order once its stop or target is reached. Targets and stops come
+//! from the signal that opened the position (`SignalPayload::target_price`
+//! and `stop_loss`); the stop can also trail the best price seen.
+//!
+//! Marks are read from any `MarkSource`: a channel fed by the caller, or the
+//! websocket order books through `OrderBookMarks`.
+
+use crate::client::mock::ClobClientTrait;
+use crate::client::OrderBookManager;
+use crate::config::ExitConfig;
+use crate::error::Result;
+use crate::events::SignalPayload;
+use crate::monitor::Monitor;
+use crate::types::{Order, OrderStatus, OrderType, Position, Side, Trade};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// Exit levels for a position
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ExitPlan {
+    /// Close once the mark reaches this price in our favour
+    pub target_price: Option<Decimal>,
+    /// Close once the mark reaches this price against us
+    pub stop_loss: Option<Decimal>,
+    /// Keep the stop this far behind the best mark seen
+    pub trailing_distance: Option<Decimal>,
+}
+
+impl ExitPlan {
+    /// Target and stop from the signal that opened the position
+    pub fn from_signal(signal: &SignalPayload) -> Self {
+        Self {
+            target_price: signal.target_price,
+            stop_loss: signal.stop_loss,
+            trailing_distance: None,
+        }
+    }
+
+    /// Levels at configured distances from `entry`, mirrored for shorts
+    pub fn from_entry(side: Side, entry: Decimal, config: &ExitConfig) -> Self {
+        let (up, down) = match side {
+            Side::Buy => (Decimal::ONE, -Decimal::ONE),
+            Side::Sell => (-Decimal::ONE, Decimal::ONE),
+        };
+        Self {
+            target_price: config.take_profit_pct.map(|pct| entry * (Decimal::ONE + up * pct)),
+            stop_loss: config.stop_loss_pct.map(|pct| entry * (Decimal::ONE + down * pct)),
+            trailing_distance: config.trailing_pct.map(|pct| entry * pct),
+        }
+    }
+
+    /// No level that could ever close the position
+    pub fn is_empty(&self) -> bool {
+        self.target_price.is_none() && self.stop_loss.is_none() && self.trailing_distance.is_none()
+    }
+
+    /// Trail the stop `distance` behind the best mark
+    pub fn with_trailing(mut self, distance: Decimal) -> Self {
+        self.trailing_distance = Some(distance);
+        self
+    }
+}
+
+/// Why a position was closed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitTrigger {
+    StopLoss,
+    TakeProfit,
+    /// Stop hit after trailing moved it
+    TrailingStop,
+}
+
+/// Stop/target state for one position, updated mark by mark
+#[derive(Debug, Clone)]
+pub struct ExitTracker {
+    plan: ExitPlan,
+    is_long: bool,
+    stop: Option<Decimal>,
+    /// Best mark seen so far (highest for longs, lowest for shorts)
+    best: Option<Decimal>,
+    trailed: bool,
+}
+
+impl ExitTracker {
+    pub fn new(position: &Position, plan: ExitPlan) -> Self {
+        Self {
+            is_long: position.side == Side::Buy,
+            stop: plan.stop_loss,
+            best: None,
+            trailed: false,
+            plan,
+        }
+    }
+
+    /// Current stop level
+    pub fn stop(&self) -> Option<Decimal> {
+        self.stop
+    }
+
+    /// Feed a new mark; returns the trigger if the position should close
+    pub fn on_mark(&mut self, mark: Decimal) -> Option<ExitTrigger> {
+        let improved = match self.best {
+            None => true,
+            Some(best) if self.is_long => mark > best,
+            Some(best) => mark < best,
+        };
+        if improved {
+            self.best = Some(mark);
+            self.ratchet(mark);
+        }
+
+        if let Some(stop) = self.stop {
+            let hit = if self.is_long { mark <= stop } else { mark >= stop };
+            if hit {
+                return Some(if self.trailed { ExitTrigger::TrailingStop } else { ExitTrigger::StopLoss });
+            }
+        }
+
+        if let Some(target) = self.plan.target_price {
+            let hit = if self.is_long { mark >= target } else { mark <= target };
+            if hit {
+                return Some(ExitTrigger::TakeProfit);
+            }
+        }
+
+        None
+    }
+
+    /// Move the trailing stop behind a new best mark, never loosening it
+    fn ratchet(&mut self, best: Decimal) {
+        let Some(distance) = self.plan.trailing_distance else {
+            return;
+        };
+        let candidate = if self.is_long { best - distance } else { best + distance };
+        let tighter = match self.stop {
+            None => true,
+            Some(stop) if self.is_long => candidate > stop,
+            Some(stop) => candidate < stop,
+        };
+        if tighter {
+            if self.stop.is_some() {
+                self.trailed = true;
+            }
+            self.stop = Some(candidate);
+        }
+    }
+}
+
+/// Stream of marks for one token
+#[async_trait]
+pub trait MarkSource: Send {
+    /// Next mark, or `None` once the feed has ended
+    async fn next_mark(&mut self) -> Option<Decimal>;
+}
+
+#[async_trait]
+impl MarkSource for mpsc::Receiver<Decimal> {
+    async fn next_mark(&mut self) -> Option<Decimal> {
+        self.recv().await
+    }
+}
+
+/// Marks polled from the websocket order books (mid of the best bid/ask)
+pub struct OrderBookMarks {
+    books: Arc<OrderBookManager>,
+    token_id: String,
+    interval: tokio::time::Interval,
+}
+
+impl OrderBookMarks {
+    pub fn new(books: Arc<OrderBookManager>, token_id: &str, poll_every: Duration) -> Self {
+        Self {
+            books,
+            token_id: token_id.to_string(),
+            interval: tokio::time::interval(poll_every),
+        }
+    }
+}
+
+#[async_trait]
+impl MarkSource for OrderBookMarks {
+    async fn next_mark(&mut self) -> Option<Decimal> {
+        loop {
+            self.interval.tick().await;
+            if let Some((bid, ask)) = self.books.get_bbo(&self.token_id).await {
+                return Some((bid + ask) / Decimal::TWO);
+            }
+        }
+    }
+}
+
+/// Marks polled from the CLOB's REST order book (mid of the best bid/ask)
+pub struct BookMarks<C> {
+    clob: C,
+    token_id: String,
+    interval: tokio::time::Interval,
+}
+
+impl<C: ClobClientTrait> BookMarks<C> {
+    pub fn new(clob: C, token_id: &str, poll_every: Duration) -> Self {
+        Self {
+            clob,
+            token_id: token_id.to_string(),
+            interval: tokio::time::interval(poll_every),
+        }
+    }
+}
+
+#[async_trait]
+impl<C: ClobClientTrait> MarkSource for BookMarks<C> {
+    async fn next_mark(&mut self) -> Option<Decimal> {
+        loop {
+            self.interval.tick().await;
+            match self.clob.get_order_book(&self.token_id).await {
+                Ok(book) => {
+                    if let (Some(bid), Some(ask)) = (book.best_bid(), book.best_ask()) {
+                        return Some((bid + ask) / Decimal::TWO);
+                    }
+                }
+                Err(e) => tracing::debug!("No mark for {}: {}", self.token_id, e),
+            }
+        }
+    }
+}
+
+/// Result of a position being closed
+#[derive(Debug, Clone)]
+pub struct ExitOutcome {
+    pub trigger: ExitTrigger,
+    /// Mark that triggered the exit
+    pub mark: Decimal,
+    pub order: OrderStatus,
+}
+
+/// Watches one open position and closes it at its stop or target
+pub struct PositionManagerTask<C> {
+    clob: C,
+    position: Position,
+    tracker: ExitTracker,
+    monitor: Option<Arc<Monitor>>,
+    /// Client order id of the closing order
+    exit_key: String,
+}
+
+impl<C: ClobClientTrait> PositionManagerTask<C> {
+    pub fn new(clob: C, position: Position, plan: ExitPlan) -> Self {
+        let tracker = ExitTracker::new(&position, plan);
+        Self {
+            clob,
+            position,
+            tracker,
+            monitor: None,
+            exit_key: format!("exit-{}", uuid::Uuid::new_v4()),
+        }
+    }
+
+    /// Also report every mark to the monitor
+    pub fn with_monitor(mut self, monitor: Arc<Monitor>) -> Self {
+        self.monitor = Some(monitor);
+        self
+    }
+
+    /// Run until an exit triggers or the marks end
+    ///
+    /// Returns `None` if the feed ended with the position still open.
+    pub async fn run(mut self, mut marks: impl MarkSource) -> Result<Option<ExitOutcome>> {
+        while let Some(mark) = marks.next_mark().await {
+            if let Some(monitor) = &self.monitor {
+                monitor.update_mark(&self.position.token_id, mark).await;
+            }
+
+            let Some(trigger) = self.tracker.on_mark(mark) else {
+                continue;
+            };
+
+            tracing::info!(
+                token_id = %self.position.token_id,
+                "{:?} at {} (stop {:?}), closing {}",
+                trigger,
+                mark,
+                self.tracker.stop(),
+                self.position.size
+            );
+            let order = self.close(mark).await?;
+            return Ok(Some(ExitOutcome { trigger, mark, order }));
+        }
+        Ok(None)
+    }
+
+    /// Send a fill-or-kill order against the opposite side of the book
+    async fn close(&self, mark: Decimal) -> Result<OrderStatus> {
+        let side = match self.position.side {
+            Side::Buy => Side::Sell,
+            Side::Sell => Side::Buy,
+        };
+        let book = self.clob.get_order_book(&self.position.token_id).await?;
+        let price = match side {
+            Side::Sell => book.best_bid(),
+            Side::Buy => book.best_ask(),
+        }
+        .unwrap_or(mark);
+
+        let order = Order {
+            token_id: self.position.token_id.clone(),
+            side,
+            price,
+            size: self.position.size,
+            order_type: OrderType::FOK,
+            client_order_id: Some(self.exit_key.clone()),
+        };
+        self.clob.place_order(&order).await
+    }
+}
+
+/// Starts a `PositionManagerTask` for each position the bot opens
+pub struct ExitManager {
+    clob: Arc<dyn ClobClientTrait>,
+    config: ExitConfig,
+    monitor: Option<Arc<Monitor>>,
+}
+
+impl ExitManager {
+    pub fn new(clob: Arc<dyn ClobClientTrait>, config: ExitConfig) -> Self {
+        Self { clob, config, monitor: None }
+    }
+
+    /// Report every mark the tasks read to the monitor
+    pub fn with_monitor(mut self, monitor: Arc<Monitor>) -> Self {
+        self.monitor = Some(monitor);
+        self
+    }
+
+    /// Watch the position `trade` opened until its stop or target is hit
+    ///
+    /// Sells close positions rather than open them, so only buys are
+    /// watched. Returns `None` when nothing was spawned.
+    pub fn watch(&self, trade: &Trade) -> Option<tokio::task::JoinHandle<Option<ExitOutcome>>> {
+        if trade.side != Side::Buy || trade.size <= Decimal::ZERO {
+            return None;
+        }
+        let plan = ExitPlan::from_entry(trade.side, trade.price, &self.config);
+        if plan.is_empty() {
+            return None;
+        }
+
+        let position = Position {
+            token_id: trade.token_id.clone(),
+            market_id: trade.market_id.clone(),
+            side: trade.side,
+            size: trade.size,
+            avg_entry_price: trade.price,
+            current_price: trade.price,
+            unrealized_pnl: Decimal::ZERO,
+        };
+        let mut task = PositionManagerTask::new(self.clob.clone(), position, plan);
+        if let Some(monitor) = &self.monitor {
+            task = task.with_monitor(monitor.clone());
+        }
+        let marks = BookMarks::new(self.clob.clone(), &trade.token_id, Duration::from_secs(self.config.poll_secs));
+        let token_id = trade.token_id.clone();
+        Some(tokio::spawn(async move {
+            match task.run(marks).await {
+                Ok(outcome) => outcome,
+                Err(e) => {
+                    tracing::error!("Exit for {} failed: {}", token_id, e);
+                    None
+                }
+            }
+        }))
+    }
+}
@@ -0,0 +1,212 @@
+//! Tests for PositionManagerTask
+
+use super::position_manager::*;
+use crate::client::mock::MockClobClient;
+use crate::client::{OrderBook, OrderBookLevel};
+use crate::config::ExitConfig;
+use crate::events::SignalPayload;
+use crate::types::{Position, Side, Trade};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+fn long_position(size: Decimal) -> Position {
+    Position {
+        token_id: "token_yes".to_string(),
+        market_id: "m1".to_string(),
+        side: Side::Buy,
+        size,
+        avg_entry_price: dec!(0.50),
+        current_price: dec!(0.50),
+        unrealized_pnl: Decimal::ZERO,
+    }
+}
+
+fn plan(target: Decimal, stop: Decimal) -> ExitPlan {
+    let mut signal = SignalPayload::new("token_yes", 1, dec!(0.8), "test");
+    signal.target_price = Some(target);
+    signal.stop_loss = Some(stop);
+    ExitPlan::from_signal(&signal)
+}
+
+/// Feed `prices` through a task and return its outcome
+async fn drive(
+    clob: &Arc<MockClobClient>,
+    position: Position,
+    plan: ExitPlan,
+    prices: &[Decimal],
+) -> Option<ExitOutcome> {
+    let (tx, rx) = mpsc::channel(prices.len().max(1));
+    for price in prices {
+        tx.send(*price).await.unwrap();
+    }
+    drop(tx);
+    PositionManagerTask::new(clob.clone(), position, plan).run(rx).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_stop_loss_hit_closes_position() {
+    let clob = Arc::new(MockClobClient::new());
+    let state = clob.state();
+
+    let outcome = drive(
+        &clob,
+        long_position(dec!(100)),
+        plan(dec!(0.60), dec!(0.45)),
+        &[dec!(0.52), dec!(0.48), dec!(0.44), dec!(0.40)],
+    )
+    .await
+    .expect("stop should trigger");
+
+    assert_eq!(outcome.trigger, ExitTrigger::StopLoss);
+    assert_eq!(outcome.mark, dec!(0.44));
+
+    let orders = state.read().unwrap().orders.clone();
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].side, Side::Sell);
+    assert_eq!(orders[0].size, dec!(100));
+    // Sold into the best bid of the default mock book
+    assert_eq!(orders[0].price, dec!(0.54));
+    assert!(orders[0].client_order_id.as_deref().unwrap().starts_with("exit-"));
+}
+
+#[tokio::test]
+async fn test_target_hit_takes_profit() {
+    let clob = Arc::new(MockClobClient::new());
+    let state = clob.state();
+
+    let outcome = drive(
+        &clob,
+        long_position(dec!(50)),
+        plan(dec!(0.60), dec!(0.45)),
+        &[dec!(0.55), dec!(0.58), dec!(0.61)],
+    )
+    .await
+    .expect("target should trigger");
+
+    assert_eq!(outcome.trigger, ExitTrigger::TakeProfit);
+    assert_eq!(outcome.mark, dec!(0.61));
+    assert_eq!(outcome.order.filled_size, dec!(50));
+    assert_eq!(state.read().unwrap().orders.len(), 1);
+}
+
+#[tokio::test]
+async fn test_no_exit_while_between_levels() {
+    let clob = Arc::new(MockClobClient::new());
+    let outcome = drive(
+        &clob,
+        long_position(dec!(10)),
+        plan(dec!(0.60), dec!(0.45)),
+        &[dec!(0.50), dec!(0.55), dec!(0.46)],
+    )
+    .await;
+
+    assert!(outcome.is_none());
+    assert!(clob.state().read().unwrap().orders.is_empty());
+}
+
+#[tokio::test]
+async fn test_trailing_stop_ratchets_up() {
+    let clob = Arc::new(MockClobClient::new());
+    let trailing = ExitPlan {
+        target_price: None,
+        stop_loss: Some(dec!(0.45)),
+        trailing_distance: None,
+    }
+    .with_trailing(dec!(0.05));
+
+    let outcome = drive(
+        &clob,
+        long_position(dec!(20)),
+        trailing,
+        // Stop moves 0.45 -> 0.50 -> 0.55 and stays there on the pullback
+        &[dec!(0.50), dec!(0.55), dec!(0.60), dec!(0.58), dec!(0.54)],
+    )
+    .await
+    .expect("trailing stop should trigger");
+
+    assert_eq!(outcome.trigger, ExitTrigger::TrailingStop);
+    assert_eq!(outcome.mark, dec!(0.54));
+}
+
+#[test]
+fn test_tracker_short_position() {
+    let mut position = long_position(dec!(10));
+    position.side = Side::Sell;
+    let mut tracker = ExitTracker::new(&position, plan(dec!(0.40), dec!(0.55)).with_trailing(dec!(0.05)));
+
+    assert_eq!(tracker.on_mark(dec!(0.48)), None);
+    assert_eq!(tracker.stop(), Some(dec!(0.53)));
+    assert_eq!(tracker.on_mark(dec!(0.51)), None);
+    assert_eq!(tracker.stop(), Some(dec!(0.53)));
+    assert_eq!(tracker.on_mark(dec!(0.53)), Some(ExitTrigger::TrailingStop));
+}
+
+#[test]
+fn test_plan_from_entry() {
+    let config = ExitConfig {
+        take_profit_pct: Some(dec!(0.20)),
+        stop_loss_pct: Some(dec!(0.10)),
+        trailing_pct: Some(dec!(0.05)),
+        ..Default::default()
+    };
+    let long = ExitPlan::from_entry(Side::Buy, dec!(0.50), &config);
+    assert_eq!(long.target_price, Some(dec!(0.60)));
+    assert_eq!(long.stop_loss, Some(dec!(0.45)));
+    assert_eq!(long.trailing_distance, Some(dec!(0.025)));
+
+    let short = ExitPlan::from_entry(Side::Sell, dec!(0.50), &config);
+    assert_eq!(short.target_price, Some(dec!(0.40)));
+    assert_eq!(short.stop_loss, Some(dec!(0.55)));
+
+    assert!(ExitPlan::from_entry(Side::Buy, dec!(0.50), &ExitConfig::default()).is_empty());
+}
+
+fn buy_trade(side: Side) -> Trade {
+    Trade {
+        id: "t1".to_string(),
+        order_id: "o1".to_string(),
+        token_id: "token_yes".to_string(),
+        market_id: "m1".to_string(),
+        side,
+        price: dec!(0.50),
+        size: dec!(100),
+        fee: Decimal::ZERO,
+        timestamp: chrono::Utc::now(),
+        client_order_id: None,
+        correlation_id: None,
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_exit_manager_closes_position_from_book_marks() {
+    let mut clob = MockClobClient::new();
+    clob.set_order_book(
+        "token_yes",
+        OrderBook {
+            bids: vec![OrderBookLevel { price: dec!(0.40), size: dec!(500) }],
+            asks: vec![OrderBookLevel { price: dec!(0.42), size: dec!(500) }],
+        },
+    );
+    let clob = Arc::new(clob);
+    let config = ExitConfig {
+        take_profit_pct: Some(dec!(0.20)),
+        stop_loss_pct: Some(dec!(0.10)),
+        ..Default::default()
+    };
+    let manager = ExitManager::new(clob.clone(), config.clone());
+
+    // Mid of 0.41 is under the 0.45 stop
+    let outcome = manager.watch(&buy_trade(Side::Buy)).unwrap().await.unwrap().expect("stop should trigger");
+    assert_eq!(outcome.trigger, ExitTrigger::StopLoss);
+    assert_eq!(outcome.mark, dec!(0.41));
+    let orders = clob.state().read().unwrap().orders.clone();
+    assert_eq!(orders.len(), 1);
+    assert_eq!(orders[0].side, Side::Sell);
+    assert_eq!(orders[0].price, dec!(0.40));
+
+    // Sells close positions, and no levels means nothing to watch
+    assert!(manager.watch(&buy_trade(Side::Sell)).is_none());
+    assert!(ExitManager::new(clob, ExitConfig::default()).watch(&buy_trade(Side::Buy)).is_none());
+}
@@ -0,0 +1,212 @@
+//! Tests for self-trade prevention
+
+use super::test_support::signal_for;
+use super::{ExecutionStyle, Executor};
+use crate::client::mock::ClobClientTrait;
+use crate::client::{OrderBook, OrderBookLevel};
+use crate::config::RiskConfig;
+use crate::error::{BotError, Result};
+use crate::types::{OpenOrder, Order, OrderStatus, Position, Side};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::sync::Mutex;
+
+/// Exchange that leaves every order resting on a 0.50/0.52 book until it
+/// is cancelled
+#[derive(Default)]
+struct RestingClob {
+    placed: Mutex<Vec<Order>>,
+    open: Mutex<Vec<String>>,
+    /// Open orders placed by someone else on the account, e.g. an earlier run
+    foreign: Mutex<Vec<OpenOrder>>,
+    positions: Mutex<Vec<Position>>,
+    /// Fail `get_open_orders_for` like a client that cannot list by token
+    unlisted: bool,
+}
+
+#[async_trait]
+impl ClobClientTrait for RestingClob {
+    async fn get_balance(&self) -> Result<Decimal> {
+        Ok(dec!(10000))
+    }
+
+    async fn get_order_book(&self, _token_id: &str) -> Result<OrderBook> {
+        Ok(OrderBook {
+            bids: vec![OrderBookLevel { price: dec!(0.50), size: dec!(500) }],
+            asks: vec![OrderBookLevel { price: dec!(0.52), size: dec!(500) }],
+        })
+    }
+
+    async fn place_order(&self, order: &Order) -> Result<OrderStatus> {
+        let mut placed = self.placed.lock().unwrap();
+        placed.push(order.clone());
+        let order_id = format!("o{}", placed.len());
+        self.open.lock().unwrap().push(order_id.clone());
+        Ok(OrderStatus {
+            order_id,
+            status: "LIVE".to_string(),
+            filled_size: Decimal::ZERO,
+            remaining_size: order.size,
+            avg_price: None,
+            fee: Decimal::ZERO,
+        })
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        self.open.lock().unwrap().retain(|id| id != order_id);
+        Ok(())
+    }
+
+    async fn get_open_orders(&self) -> Result<Vec<OrderStatus>> {
+        let open = self.open.lock().unwrap();
+        let placed = self.placed.lock().unwrap();
+        Ok(open
+            .iter()
+            .map(|id| OrderStatus {
+                order_id: id.clone(),
+                status: "LIVE".to_string(),
+                filled_size: Decimal::ZERO,
+                remaining_size: placed[id[1..].parse::<usize>().unwrap() - 1].size,
+                avg_price: None,
+                fee: Decimal::ZERO,
+            })
+            .collect())
+    }
+
+    async fn get_open_orders_for(&self, token_id: &str) -> Result<Vec<OpenOrder>> {
+        if self.unlisted {
+            return Err(BotError::Execution("Cannot list open orders".to_string()));
+        }
+        let open = self.open.lock().unwrap();
+        let placed = self.placed.lock().unwrap();
+        let own = placed.iter().enumerate().filter_map(|(i, order)| {
+            let order_id = format!("o{}", i + 1);
+            open.contains(&order_id).then(|| OpenOrder {
+                order_id,
+                token_id: order.token_id.clone(),
+                side: order.side,
+                price: order.price,
+                remaining_size: order.size,
+            })
+        });
+        let foreign = self.foreign.lock().unwrap();
+        Ok(own.chain(foreign.iter().cloned()).filter(|o| o.token_id == token_id).collect())
+    }
+
+    async fn get_positions(&self) -> Result<Vec<Position>> {
+        Ok(self.positions.lock().unwrap().clone())
+    }
+}
+
+/// Executor with a buy for 100 shares of t1 resting at the 0.50 bid
+async fn with_resting_buy() -> Executor<RestingClob> {
+    resting_buy_on(RestingClob::default()).await
+}
+
+async fn resting_buy_on(clob: RestingClob) -> Executor<RestingClob> {
+    let executor = Executor::new(clob, RiskConfig::default())
+        .with_execution_style(ExecutionStyle::Passive { aggressiveness: Decimal::ZERO });
+    let trade = executor.execute_shares(&signal_for("t1", Side::Buy, dec!(0.50)), dec!(100), dec!(10000)).await.unwrap();
+    assert!(trade.is_none());
+    assert_eq!(executor.resting_orders().await["o1"], dec!(100));
+    executor
+}
+
+#[tokio::test]
+async fn test_sell_crossing_own_resting_buy_is_refused() {
+    let executor = with_resting_buy().await.with_execution_style(ExecutionStyle::Cross);
+
+    // Crossing sells at the 0.50 bid, which is our own buy
+    let err = executor
+        .execute_shares(&signal_for("t1", Side::Sell, dec!(0.50)), dec!(100), dec!(10000))
+        .await
+        .unwrap_err();
+    assert!(matches!(err.root(), BotError::RiskLimit(msg) if msg.contains("o1")));
+    assert_eq!(executor.resting_orders().await.len(), 1);
+}
+
+#[tokio::test]
+async fn test_non_crossing_orders_are_allowed() {
+    let executor = with_resting_buy().await;
+
+    // Joining the 0.52 ask rests above our bid
+    executor.execute_shares(&signal_for("t1", Side::Sell, dec!(0.50)), dec!(100), dec!(10000)).await.unwrap();
+    // Another token is unaffected
+    let executor = executor.with_execution_style(ExecutionStyle::Cross);
+    executor.execute_shares(&signal_for("t2", Side::Sell, dec!(0.50)), dec!(100), dec!(10000)).await.unwrap();
+
+    let resting = executor.resting_orders().await;
+    assert_eq!(resting.len(), 3);
+    assert_eq!((resting["o2"], resting["o3"]), (dec!(100), dec!(100)));
+}
+
+#[tokio::test]
+async fn test_resting_order_no_longer_open_is_forgotten() {
+    let executor = with_resting_buy().await.with_execution_style(ExecutionStyle::Cross);
+    // Filled or cancelled away from the bot
+    executor.clob.open.lock().unwrap().clear();
+
+    executor.execute_shares(&signal_for("t1", Side::Sell, dec!(0.50)), dec!(100), dec!(10000)).await.unwrap();
+    let resting = executor.resting_orders().await;
+    assert!(!resting.contains_key("o1"));
+    assert_eq!(resting["o2"], dec!(100));
+}
+
+#[tokio::test]
+async fn test_known_resting_orders_are_checked_when_the_client_cannot_list() {
+    let executor = resting_buy_on(RestingClob { unlisted: true, ..Default::default() })
+        .await
+        .with_execution_style(ExecutionStyle::Cross);
+
+    let err = executor
+        .execute_shares(&signal_for("t1", Side::Sell, dec!(0.50)), dec!(100), dec!(10000))
+        .await
+        .unwrap_err();
+    assert!(matches!(err.root(), BotError::RiskLimit(msg) if msg.contains("o1")));
+}
+
+#[tokio::test]
+async fn test_order_resting_from_an_earlier_run_is_checked() {
+    let clob = RestingClob::default();
+    clob.foreign.lock().unwrap().push(OpenOrder {
+        order_id: "earlier".to_string(),
+        token_id: "t1".to_string(),
+        side: Side::Buy,
+        price: dec!(0.50),
+        remaining_size: dec!(40),
+    });
+    let executor = Executor::new(clob, RiskConfig::default());
+    assert!(executor.resting_orders().await.is_empty());
+
+    let err = executor
+        .execute_shares(&signal_for("t1", Side::Sell, dec!(0.50)), dec!(100), dec!(10000))
+        .await
+        .unwrap_err();
+    assert!(matches!(err.root(), BotError::RiskLimit(msg) if msg.contains("earlier")));
+    assert!(executor.resting_orders().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_buying_against_own_position_in_the_market_is_refused() {
+    let clob = RestingClob::default();
+    clob.positions.lock().unwrap().push(Position {
+        token_id: "t1".to_string(),
+        market_id: "m1".to_string(),
+        side: Side::Buy,
+        size: dec!(100),
+        avg_entry_price: dec!(0.50),
+        current_price: dec!(0.50),
+        unrealized_pnl: Decimal::ZERO,
+    });
+    let executor = Executor::new(clob, RiskConfig::default());
+
+    let err = executor
+        .execute_shares(&signal_for("t2", Side::Buy, dec!(0.50)), dec!(100), dec!(10000))
+        .await
+        .unwrap_err();
+    assert!(matches!(err.root(), BotError::RiskLimit(msg) if msg.contains("t1")));
+    // Adding to the position held is fine
+    executor.execute_shares(&signal_for("t1", Side::Buy, dec!(0.50)), dec!(100), dec!(10000)).await.unwrap();
+    assert_eq!(executor.resting_orders().await.into_keys().collect::<Vec<_>>(), vec!["o1".to_string()]);
+}
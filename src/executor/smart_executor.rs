@@ -192,7 +192,11 @@ impl SmartExecutor {
                 }
                 Err(e) => {
                     warn!("Attempt {} failed: {}", attempt, e);
+                    let retryable = e.is_retryable();
                     last_error = Some(e);
+                    if !retryable {
+                        break;
+                    }
                 }
             }
 
@@ -257,6 +261,7 @@ impl SmartExecutor {
             price: limit_price,
             size,
             order_type: OrderType::GTC,
+            client_order_id: None,
         };
 
         info!(
@@ -286,6 +291,8 @@ impl SmartExecutor {
                 size: filled_status.filled_size,
                 fee: Decimal::ZERO,
                 timestamp: chrono::Utc::now(),
+                client_order_id: None,
+                correlation_id: None,
             };
 
             Ok(ExecutionResult {
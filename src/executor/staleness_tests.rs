@@ -0,0 +1,89 @@
+//! Tests for the stale signal guard
+
+use super::test_support::signal;
+use super::Executor;
+use crate::client::mock::MockClobClient;
+use crate::client::{OrderBook, OrderBookLevel};
+use crate::config::RiskConfig;
+use crate::types::{Side, Signal};
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+fn clob_with_book(bid: Decimal, ask: Decimal) -> MockClobClient {
+    let mut clob = MockClobClient::new();
+    clob.set_order_book(
+        "t1",
+        OrderBook {
+            bids: vec![OrderBookLevel { price: bid, size: dec!(1000) }],
+            asks: vec![OrderBookLevel { price: ask, size: dec!(1000) }],
+        },
+    );
+    clob
+}
+
+fn guarded(clob: MockClobClient) -> Executor<MockClobClient> {
+    let risk = RiskConfig {
+        max_signal_age_secs: Some(60),
+        max_price_move: Some(dec!(0.03)),
+        ..RiskConfig::default()
+    };
+    Executor::new(clob, risk)
+}
+
+#[tokio::test]
+async fn test_trade_skipped_when_price_moved_past_tolerance() {
+    // The book ran from 0.50/0.52 to 0.55/0.57 while other markets were analyzed
+    let clob = clob_with_book(dec!(0.55), dec!(0.57));
+    let state = clob.state();
+    let executor = guarded(clob);
+
+    let trade = executor.execute(&signal(Side::Buy, dec!(0.51)), dec!(1000)).await.unwrap();
+    assert!(trade.is_none());
+    assert!(state.read().unwrap().orders.is_empty());
+}
+
+#[tokio::test]
+async fn test_trade_placed_when_price_within_tolerance() {
+    let clob = clob_with_book(dec!(0.52), dec!(0.54));
+    let state = clob.state();
+    let executor = guarded(clob);
+
+    let trade = executor.execute(&signal(Side::Buy, dec!(0.51)), dec!(1000)).await.unwrap().unwrap();
+    assert_eq!(trade.price, dec!(0.54));
+    assert_eq!(state.read().unwrap().orders.len(), 1);
+}
+
+#[tokio::test]
+async fn test_expired_signal_skipped() {
+    let executor = guarded(clob_with_book(dec!(0.50), dec!(0.52)));
+    let old = Signal {
+        timestamp: Utc::now() - Duration::minutes(5),
+        ..signal(Side::Buy, dec!(0.51))
+    };
+    assert!(executor.execute(&old, dec!(1000)).await.unwrap().is_none());
+
+    // A signal's own limit overrides the executor's
+    let patient = Signal {
+        max_staleness: Some(Duration::minutes(10)),
+        ..old.clone()
+    };
+    assert!(executor.execute(&patient, dec!(1000)).await.unwrap().is_some());
+    let hasty = Signal {
+        timestamp: Utc::now() - Duration::seconds(20),
+        max_staleness: Some(Duration::seconds(10)),
+        ..signal(Side::Buy, dec!(0.51))
+    };
+    assert!(executor.execute(&hasty, dec!(1000)).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_guard_off_by_default() {
+    let clob = clob_with_book(dec!(0.60), dec!(0.62));
+    let executor = Executor::new(clob, RiskConfig::default());
+    let old = Signal {
+        timestamp: Utc::now() - Duration::hours(1),
+        ..signal(Side::Buy, dec!(0.51))
+    };
+    assert!(executor.execute(&old, dec!(1000)).await.unwrap().is_some());
+}
@@ -0,0 +1,131 @@
+//! Fixtures shared by the executor tests
+
+use super::RiskAlertSink;
+use crate::client::mock::ClobClientTrait;
+use crate::client::{OrderBook, OrderBookLevel};
+use crate::error::Result;
+use crate::events::RiskPayload;
+use crate::types::{Order, OrderStatus, Position, Side, Signal};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::sync::Mutex;
+
+/// Signal on token t1 of market m1 with a 0.70 model probability
+///
+/// Override other fields with struct update syntax.
+pub(crate) fn signal(side: Side, market_probability: Decimal) -> Signal {
+    signal_for("t1", side, market_probability)
+}
+
+/// `signal` on another token of market m1
+pub(crate) fn signal_for(token_id: &str, side: Side, market_probability: Decimal) -> Signal {
+    let model_probability = dec!(0.70);
+    let edge = (model_probability - market_probability).abs();
+    Signal {
+        market_id: "m1".to_string(),
+        token_id: token_id.to_string(),
+        side,
+        model_probability,
+        market_probability,
+        edge,
+        net_edge: edge,
+        confidence: dec!(0.80),
+        suggested_size: dec!(0.05),
+        timestamp: chrono::Utc::now(),
+        max_staleness: None,
+    }
+}
+
+/// Risk alert sink that keeps every alert it is sent
+#[derive(Default)]
+pub(crate) struct RecordingAlerts(pub(crate) Mutex<Vec<RiskPayload>>);
+
+#[async_trait]
+impl RiskAlertSink for RecordingAlerts {
+    async fn risk_alert(&self, alert: &RiskPayload) -> Result<()> {
+        self.0.lock().unwrap().push(alert.clone());
+        Ok(())
+    }
+}
+
+/// Exchange with a 0.48/0.50 book that fills nothing on arrival: every
+/// order rests until the test calls `fill`
+#[derive(Default)]
+pub(crate) struct FillableClob {
+    /// Orders placed, with the size filled and whether still open
+    orders: Mutex<Vec<(Order, Decimal, bool)>>,
+}
+
+impl FillableClob {
+    /// Match `size` more shares of order `order_id` ("o1" is the first placed)
+    pub(crate) fn fill(&self, order_id: &str, size: Decimal) {
+        let mut orders = self.orders.lock().unwrap();
+        let (order, filled, open) = &mut orders[Self::index(order_id)];
+        *filled += size;
+        *open = *filled < order.size;
+    }
+
+    fn index(order_id: &str) -> usize {
+        order_id.trim_start_matches('o').parse::<usize>().unwrap() - 1
+    }
+
+    fn status(index: usize, (order, filled, open): &(Order, Decimal, bool)) -> OrderStatus {
+        OrderStatus {
+            order_id: format!("o{}", index + 1),
+            status: if *open { "LIVE" } else { "MATCHED" }.to_string(),
+            filled_size: *filled,
+            remaining_size: if *open { order.size - filled } else { Decimal::ZERO },
+            avg_price: None,
+            fee: Decimal::ZERO,
+        }
+    }
+}
+
+#[async_trait]
+impl ClobClientTrait for FillableClob {
+    async fn get_balance(&self) -> Result<Decimal> {
+        Ok(dec!(10000))
+    }
+
+    async fn get_order_book(&self, _token_id: &str) -> Result<OrderBook> {
+        Ok(OrderBook {
+            bids: vec![OrderBookLevel { price: dec!(0.48), size: dec!(1000) }],
+            asks: vec![OrderBookLevel { price: dec!(0.50), size: dec!(1000) }],
+        })
+    }
+
+    async fn place_order(&self, order: &Order) -> Result<OrderStatus> {
+        let mut orders = self.orders.lock().unwrap();
+        orders.push((order.clone(), Decimal::ZERO, true));
+        Ok(Self::status(orders.len() - 1, &orders[orders.len() - 1]))
+    }
+
+    async fn cancel_order(&self, order_id: &str) -> Result<()> {
+        self.orders.lock().unwrap()[Self::index(order_id)].2 = false;
+        Ok(())
+    }
+
+    async fn get_open_orders(&self) -> Result<Vec<OrderStatus>> {
+        let orders = self.orders.lock().unwrap();
+        Ok(orders
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, _, open))| *open)
+            .map(|(i, placed)| Self::status(i, placed))
+            .collect())
+    }
+
+    async fn get_positions(&self) -> Result<Vec<Position>> {
+        Ok(Vec::new())
+    }
+
+    async fn find_order(&self, client_order_id: &str) -> Result<Option<OrderStatus>> {
+        let orders = self.orders.lock().unwrap();
+        Ok(orders
+            .iter()
+            .enumerate()
+            .find(|(_, (order, _, _))| order.client_order_id.as_deref() == Some(client_order_id))
+            .map(|(i, placed)| Self::status(i, placed)))
+    }
+}
@@ -3,6 +3,7 @@
 #[cfg(test)]
 mod tests {
     use crate::config::RiskConfig;
+    use crate::executor::test_support::signal;
     use crate::types::{Order, OrderType, Side, Signal};
     use chrono::Utc;
     use rust_decimal::Decimal;
@@ -24,6 +25,7 @@ mod tests {
             price: dec!(0.55),
             size: dec!(100),
             order_type: OrderType::GTC,
+            client_order_id: None,
         };
         
         assert_eq!(order.side, Side::Buy);
@@ -38,6 +40,7 @@ mod tests {
             price: dec!(0.65),
             size: dec!(50),
             order_type: OrderType::FOK,
+            client_order_id: None,
         };
         
         assert_eq!(order.side, Side::Sell);
@@ -46,17 +49,7 @@ mod tests {
 
     #[test]
     fn test_signal_size_calculation() {
-        let signal = Signal {
-            market_id: "m1".to_string(),
-            token_id: "t1".to_string(),
-            side: Side::Buy,
-            model_probability: dec!(0.70),
-            market_probability: dec!(0.55),
-            edge: dec!(0.15),
-            confidence: dec!(0.80),
-            suggested_size: dec!(0.05), // 5% of portfolio
-            timestamp: Utc::now(),
-        };
+        let signal = signal(Side::Buy, dec!(0.55));
         
         let portfolio_value = dec!(1000);
         let size_usd = signal.suggested_size * portfolio_value;
@@ -145,6 +138,7 @@ mod tests {
             price: dec!(0.5),
             size: dec!(10),
             order_type: OrderType::GTD,
+            client_order_id: None,
         };
         assert_eq!(order.order_type, OrderType::GTD);
     }
@@ -158,9 +152,11 @@ mod tests {
             model_probability: dec!(0.75),
             market_probability: dec!(0.60),
             edge: dec!(0.15),
+            net_edge: dec!(0.15),
             confidence: dec!(0.85),
             suggested_size: dec!(0.03),
             timestamp: Utc::now(),
+            max_staleness: None,
         };
         
         assert!(signal.edge > Decimal::ZERO);
@@ -175,9 +171,11 @@ mod tests {
             model_probability: dec!(0.25),
             market_probability: dec!(0.40),
             edge: dec!(-0.15),
+            net_edge: dec!(-0.15),
             confidence: dec!(0.80),
             suggested_size: dec!(0.02),
             timestamp: Utc::now(),
+            max_staleness: None,
         };
         
         assert!(signal.edge < Decimal::ZERO);
@@ -198,4 +196,388 @@ mod tests {
         let current_positions = 10;
         assert!(current_positions >= config.max_open_positions);
     }
+
+    /// Forwards to the mock but loses the response of the first submission
+    struct LostResponseClob {
+        inner: crate::client::mock::MockClobClient,
+        submits: std::sync::atomic::AtomicU32,
+    }
+
+    #[async_trait::async_trait]
+    impl crate::client::mock::ClobClientTrait for LostResponseClob {
+        async fn get_balance(&self) -> crate::error::Result<Decimal> {
+            self.inner.get_balance().await
+        }
+
+        async fn get_order_book(&self, token_id: &str) -> crate::error::Result<crate::client::OrderBook> {
+            self.inner.get_order_book(token_id).await
+        }
+
+        async fn place_order(&self, order: &Order) -> crate::error::Result<crate::types::OrderStatus> {
+            let status = self.inner.place_order(order).await?;
+            if self.submits.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0 {
+                // Accepted by the exchange, but the caller never hears back
+                std::future::pending::<()>().await;
+            }
+            Ok(status)
+        }
+
+        async fn cancel_order(&self, order_id: &str) -> crate::error::Result<()> {
+            self.inner.cancel_order(order_id).await
+        }
+
+        async fn get_open_orders(&self) -> crate::error::Result<Vec<crate::types::OrderStatus>> {
+            self.inner.get_open_orders().await
+        }
+
+        async fn get_positions(&self) -> crate::error::Result<Vec<crate::types::Position>> {
+            self.inner.get_positions().await
+        }
+
+        async fn find_order(&self, client_order_id: &str) -> crate::error::Result<Option<crate::types::OrderStatus>> {
+            self.inner.find_order(client_order_id).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_submit_timeout_retry_places_one_order() {
+        use crate::client::mock::{ClobClientTrait, MockClobClient};
+        use crate::executor::Executor;
+        use std::sync::atomic::Ordering;
+
+        let inner = MockClobClient::new();
+        let state = inner.state();
+        let clob = LostResponseClob { inner, submits: Default::default() };
+        let executor = Executor::new(clob, RiskConfig::default())
+            .with_submit_retry(std::time::Duration::from_millis(50), 3);
+
+        let signal = signal(Side::Buy, dec!(0.55));
+
+        let trade = executor.execute(&signal, dec!(1000)).await.unwrap().unwrap();
+
+        // The timed-out order was found on the exchange instead of resent
+        assert_eq!(executor.clob.submits.load(Ordering::SeqCst), 1);
+        let orders = state.read().unwrap().orders.clone();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(trade.order_id, orders[0].order_id);
+
+        assert!(orders[0].client_order_id.is_some());
+        assert_eq!(trade.client_order_id, orders[0].client_order_id);
+        assert_eq!(executor.clob.get_balance().await.unwrap(), dec!(1000) - trade.price * trade.size);
+    }
+
+    #[tokio::test]
+    async fn test_each_signal_gets_its_own_order() {
+        use crate::client::mock::MockClobClient;
+        use crate::executor::Executor;
+
+        let clob = MockClobClient::new();
+        let state = clob.state();
+        let executor = Executor::new(clob, RiskConfig::default());
+        let signal = Signal { suggested_size: dec!(0.02), ..signal(Side::Buy, dec!(0.55)) };
+        // A second signal moments later for the same token and side
+        let next = Signal { timestamp: signal.timestamp + chrono::Duration::seconds(1), ..signal.clone() };
+
+        let first = executor.execute(&signal, dec!(1000)).await.unwrap().unwrap();
+        let second = executor.execute(&next, dec!(1000)).await.unwrap().unwrap();
+
+        let orders = state.read().unwrap().orders.clone();
+        assert_eq!(orders.len(), 2);
+        assert_ne!(first.client_order_id, second.client_order_id);
+        assert_ne!(first.order_id, second.order_id);
+    }
+
+    /// Forwards to the mock after failing with each queued error in turn
+    struct FailingClob {
+        inner: crate::client::mock::MockClobClient,
+        failures: std::sync::Mutex<Vec<crate::error::BotError>>,
+        submits: std::sync::atomic::AtomicU32,
+        /// Place the order with the mock before reporting the failure
+        accept_before_failing: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl crate::client::mock::ClobClientTrait for FailingClob {
+        async fn get_balance(&self) -> crate::error::Result<Decimal> {
+            self.inner.get_balance().await
+        }
+
+        async fn get_order_book(&self, token_id: &str) -> crate::error::Result<crate::client::OrderBook> {
+            self.inner.get_order_book(token_id).await
+        }
+
+        async fn place_order(&self, order: &Order) -> crate::error::Result<crate::types::OrderStatus> {
+            self.submits.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            let failure = self.failures.lock().unwrap().pop();
+            match failure {
+                Some(e) if self.accept_before_failing => {
+                    self.inner.place_order(order).await?;
+                    Err(e)
+                }
+                Some(e) => Err(e),
+                None => self.inner.place_order(order).await,
+            }
+        }
+
+        async fn cancel_order(&self, order_id: &str) -> crate::error::Result<()> {
+            self.inner.cancel_order(order_id).await
+        }
+
+        async fn get_open_orders(&self) -> crate::error::Result<Vec<crate::types::OrderStatus>> {
+            self.inner.get_open_orders().await
+        }
+
+        async fn get_positions(&self) -> crate::error::Result<Vec<crate::types::Position>> {
+            self.inner.get_positions().await
+        }
+
+        async fn find_order(&self, client_order_id: &str) -> crate::error::Result<Option<crate::types::OrderStatus>> {
+            self.inner.find_order(client_order_id).await
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_submit_retries_only_retryable_errors() {
+        use crate::error::BotError;
+        use crate::executor::Executor;
+        use std::sync::atomic::Ordering;
+
+        let signal = signal(Side::Buy, dec!(0.55));
+        let executor = |failures: Vec<BotError>| {
+            let clob = FailingClob {
+                inner: crate::client::mock::MockClobClient::new(),
+                failures: std::sync::Mutex::new(failures),
+                submits: Default::default(),
+                accept_before_failing: false,
+            };
+            Executor::new(clob, RiskConfig::default()).with_submit_retry(std::time::Duration::from_secs(5), 3)
+        };
+
+        // Popped from the back: rate limited, then a 503 the exchange never
+        // saw, then accepted
+        let transient = executor(vec![
+            BotError::Http { status: 503, message: "unavailable".into() },
+            BotError::RateLimited { retry_after_secs: 2 },
+        ]);
+        let started = tokio::time::Instant::now();
+        assert!(transient.execute(&signal, dec!(1000)).await.unwrap().is_some());
+        assert_eq!(transient.clob.submits.load(Ordering::SeqCst), 3);
+        assert!(started.elapsed() >= std::time::Duration::from_secs(2));
+
+        let permanent = executor(vec![BotError::Validation("tick size".into())]);
+        let err = permanent.execute(&signal, dec!(1000)).await.unwrap_err();
+        assert!(matches!(err.root(), BotError::Validation(_)));
+        assert_eq!(permanent.clob.submits.load(Ordering::SeqCst), 1);
+
+        let context = err.context().unwrap();
+        assert_eq!(context.market_id.as_deref(), Some("m1"));
+        assert_eq!(context.order_id.as_ref().map(String::len), Some(32));
+        assert_eq!(context.operation.as_deref(), Some("place_order"));
+    }
+
+    #[tokio::test]
+    async fn test_ambiguous_failure_is_not_resent_blindly() {
+        use crate::error::BotError;
+        use crate::executor::Executor;
+        use std::sync::atomic::Ordering;
+
+        let signal = signal(Side::Buy, dec!(0.55));
+
+        // The exchange took the order, then answered 503
+        let inner = crate::client::mock::MockClobClient::new();
+        let state = inner.state();
+        let clob = FailingClob {
+            inner,
+            failures: std::sync::Mutex::new(vec![BotError::Http { status: 503, message: "unavailable".into() }]),
+            submits: Default::default(),
+            accept_before_failing: true,
+        };
+        let executor = Executor::new(clob, RiskConfig::default());
+        let trade = executor.execute(&signal, dec!(1000)).await.unwrap().unwrap();
+        assert_eq!(executor.clob.submits.load(Ordering::SeqCst), 1);
+        let orders = state.read().unwrap().orders.clone();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(trade.order_id, orders[0].order_id);
+    }
+
+    fn sample_book() -> crate::client::OrderBook {
+        use crate::client::OrderBookLevel;
+        crate::client::OrderBook {
+            bids: vec![
+                OrderBookLevel { price: dec!(0.50), size: dec!(200) },
+                OrderBookLevel { price: dec!(0.49), size: dec!(500) },
+            ],
+            asks: vec![
+                OrderBookLevel { price: dec!(0.55), size: dec!(150) },
+                OrderBookLevel { price: dec!(0.56), size: dec!(400) },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_compute_limit_price() {
+        use crate::client::mock::MockClobClient;
+        use crate::executor::Executor;
+
+        let executor = Executor::new(MockClobClient::new(), RiskConfig::default());
+        let book = sample_book();
+        let price = |side, aggressiveness| executor.compute_limit_price(&book, side, aggressiveness).unwrap();
+
+        // 0 joins our side of the book, 1 crosses
+        assert_eq!(price(Side::Buy, dec!(0)), dec!(0.50));
+        assert_eq!(price(Side::Buy, dec!(1)), dec!(0.55));
+        assert_eq!(price(Side::Sell, dec!(0)), dec!(0.55));
+        assert_eq!(price(Side::Sell, dec!(1)), dec!(0.50));
+
+        // Mid-spread 0.525 is off-tick: buyers round down, sellers up
+        assert_eq!(price(Side::Buy, dec!(0.5)), dec!(0.52));
+        assert_eq!(price(Side::Sell, dec!(0.5)), dec!(0.53));
+
+        // Out-of-range aggressiveness is clamped
+        assert_eq!(price(Side::Buy, dec!(2)), dec!(0.55));
+        assert_eq!(price(Side::Buy, dec!(-1)), dec!(0.50));
+    }
+
+    #[test]
+    fn test_compute_limit_price_tick_size_and_one_sided_book() {
+        use crate::client::mock::MockClobClient;
+        use crate::executor::Executor;
+
+        let executor = Executor::new(MockClobClient::new(), RiskConfig::default()).with_tick_size(dec!(0.001));
+        let mut book = sample_book();
+        assert_eq!(executor.compute_limit_price(&book, Side::Buy, dec!(0.5)), Some(dec!(0.525)));
+
+        book.bids.clear();
+        assert_eq!(executor.compute_limit_price(&book, Side::Buy, dec!(0)), Some(dec!(0.55)));
+        book.asks.clear();
+        assert_eq!(executor.compute_limit_price(&book, Side::Buy, dec!(0)), None);
+    }
+
+    #[tokio::test]
+    async fn test_passive_style_posts_inside_spread() {
+        use crate::client::mock::MockClobClient;
+        use crate::executor::{ExecutionStyle, Executor};
+
+        let mut clob = MockClobClient::new();
+        clob.set_order_book("t1", sample_book());
+        let state = clob.state();
+        let executor = Executor::new(clob, RiskConfig::default())
+            .with_execution_style(ExecutionStyle::Passive { aggressiveness: dec!(0.5) });
+
+        let signal = signal(Side::Buy, dec!(0.55));
+        executor.execute(&signal, dec!(1000)).await.unwrap();
+
+        let orders = state.read().unwrap().orders.clone();
+        assert_eq!(orders[0].price, dec!(0.52));
+    }
+
+    #[tokio::test]
+    async fn test_maintained_book_prices_before_the_clob() {
+        use crate::client::mock::MockClobClient;
+        use crate::client::{OrderBookLevel, OrderBookMaintainer};
+        use crate::executor::Executor;
+        use std::sync::Arc;
+
+        let mut clob = MockClobClient::new();
+        clob.set_order_book("t1", sample_book());
+        let state = clob.state();
+        let books = Arc::new(tokio::sync::RwLock::new(OrderBookMaintainer::new()));
+        let executor = Executor::new(clob, RiskConfig::default()).with_order_books(books.clone());
+
+        let signal = signal(Side::Buy, dec!(0.55));
+        // No maintained book yet: priced from the CLOB's
+        executor.execute(&signal, dec!(1000)).await.unwrap();
+
+        let mut book = sample_book();
+        book.asks[0] = OrderBookLevel { price: dec!(0.53), size: dec!(150) };
+        books.write().await.apply_snapshot("t1", &book, 1);
+        executor.execute(&signal, dec!(1000)).await.unwrap();
+
+        let prices: Vec<Decimal> = state.read().unwrap().orders.iter().map(|o| o.price).collect();
+        assert_eq!(prices, vec![dec!(0.55), dec!(0.53)]);
+    }
+
+    #[test]
+    fn test_round_to_tick() {
+        use crate::executor::round_to_tick;
+
+        // A computed 0.5137 is off both the default and the fine tick
+        assert_eq!(round_to_tick(dec!(0.5137), dec!(0.01), Side::Buy), dec!(0.51));
+        assert_eq!(round_to_tick(dec!(0.5137), dec!(0.01), Side::Sell), dec!(0.52));
+        assert_eq!(round_to_tick(dec!(0.5137), dec!(0.001), Side::Buy), dec!(0.513));
+        assert_eq!(round_to_tick(dec!(0.5137), dec!(0.001), Side::Sell), dec!(0.514));
+        assert_eq!(round_to_tick(dec!(0.52), dec!(0.01), Side::Buy), dec!(0.52));
+    }
+
+    fn increments_market(tick_size: Option<Decimal>, min_size: Option<Decimal>) -> crate::types::Market {
+        crate::types::Market {
+            id: "m1".to_string(),
+            question: "Q?".to_string(),
+            description: None,
+            end_date: None,
+            volume: dec!(0),
+            liquidity: dec!(0),
+            outcomes: vec![crate::types::Outcome {
+                token_id: "t1".to_string(),
+                outcome: "Yes".to_string(),
+                price: dec!(0.51),
+            }],
+            active: true,
+            closed: false,
+            category: None,
+            tags: Vec::new(),
+            tick_size,
+            min_size,
+        }
+    }
+
+    fn increments_signal(suggested_size: Decimal) -> Signal {
+        Signal { suggested_size, ..signal(Side::Buy, dec!(0.51)) }
+    }
+
+    #[tokio::test]
+    async fn test_orders_rounded_to_market_increments() {
+        use crate::client::mock::MockClobClient;
+        use crate::client::{OrderBook, OrderBookLevel};
+        use crate::executor::{ExecutionStyle, Executor};
+
+        let mut clob = MockClobClient::new();
+        // Just over halfway across this spread prices at 0.5137
+        clob.set_order_book(
+            "t1",
+            OrderBook {
+                bids: vec![OrderBookLevel { price: dec!(0.500), size: dec!(500) }],
+                asks: vec![OrderBookLevel { price: dec!(0.527), size: dec!(500) }],
+            },
+        );
+        let state = clob.state();
+        let executor = Executor::new(clob, RiskConfig::default())
+            .with_execution_style(ExecutionStyle::Passive { aggressiveness: dec!(0.5074) });
+        executor.register_market(&increments_market(Some(dec!(0.001)), Some(dec!(5)))).await;
+
+        executor.execute(&increments_signal(dec!(0.05)), dec!(1000)).await.unwrap();
+
+        let order = state.read().unwrap().orders[0].clone();
+        assert_eq!(order.price, dec!(0.513));
+        // $50 / 0.51 = 98.039... shares, rounded down
+        assert_eq!(order.size, dec!(98.03));
+    }
+
+    #[tokio::test]
+    async fn test_order_below_min_size_rejected() {
+        use crate::client::mock::MockClobClient;
+        use crate::error::BotError;
+        use crate::executor::Executor;
+
+        let mut clob = MockClobClient::new();
+        clob.set_order_book("t1", sample_book());
+        let state = clob.state();
+        let executor = Executor::new(clob, RiskConfig::default());
+        executor.register_market(&increments_market(None, Some(dec!(15)))).await;
+
+        // $5 buys 9.8 shares, under the 15-share minimum
+        let err = executor.execute(&increments_signal(dec!(0.005)), dec!(1000)).await.unwrap_err();
+        assert!(matches!(err.root(), BotError::Validation(_)));
+        assert!(state.read().unwrap().orders.is_empty());
+    }
 }
@@ -131,6 +131,18 @@ pub struct FusionConfig {
     pub max_signal_age_secs: u64,
     /// Conflict resolution strategy
     pub conflict_strategy: ConflictStrategy,
+    /// How far each outcome batch moves a source's accuracy towards its
+    /// hit rate (0.0 to 1.0)
+    #[serde(default = "default_accuracy_learning_rate")]
+    pub accuracy_learning_rate: f64,
+    /// Largest fractional change in a source's effective weight per
+    /// outcome batch; `None` leaves it uncapped
+    #[serde(default)]
+    pub max_weight_step: Option<f64>,
+}
+
+fn default_accuracy_learning_rate() -> f64 {
+    0.2
 }
 
 impl Default for FusionConfig {
@@ -155,6 +167,8 @@ impl Default for FusionConfig {
             regime_adjustments: Self::default_regime_adjustments(),
             max_signal_age_secs: 900, // 15 minutes
             conflict_strategy: ConflictStrategy::WeightedMajority,
+            accuracy_learning_rate: default_accuracy_learning_rate(),
+            max_weight_step: None,
         }
     }
 }
@@ -209,7 +223,8 @@ pub enum EnsembleMethod {
     BayesianFusion,
     /// Take the signal with highest confidence
     MaxConfidence,
-    /// Stack meta-learner (uses historical accuracy)
+    /// Stack meta-learner; every method now weighs sources by historical
+    /// accuracy, so this is the same as `WeightedAverage`
     Stacking,
 }
 
@@ -258,6 +273,23 @@ pub struct SignalContribution {
     pub decay_factor: f64,
 }
 
+/// Realized result of a trade, credited to a source that called it
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TradeOutcome {
+    pub source: SignalSource,
+    /// Direction the source called
+    pub predicted: f64,
+    /// Direction the market resolved, in the same terms as `predicted`
+    pub realized: f64,
+}
+
+impl TradeOutcome {
+    /// Whether the source called the direction right
+    pub fn correct(&self) -> bool {
+        self.predicted * self.realized > 0.0
+    }
+}
+
 /// Multi-Factor Fusion Engine
 pub struct FusionEngine {
     config: FusionConfig,
@@ -292,6 +324,32 @@ impl FusionEngine {
         self.historical_accuracy.insert(source, accuracy.clamp(0.0, 1.0));
     }
 
+    /// Learn source accuracy from realized trade outcomes
+    ///
+    /// Each source's accuracy moves `accuracy_learning_rate` of the way
+    /// towards its hit rate in the batch, bounded by `max_weight_step`.
+    /// Sources with no accuracy yet start from 0.5, which leaves their
+    /// weight unchanged.
+    pub fn ingest_outcomes(&mut self, outcomes: &[TradeOutcome]) {
+        let mut tally: HashMap<SignalSource, (u32, u32)> = HashMap::new();
+        for outcome in outcomes {
+            let (hits, total) = tally.entry(outcome.source).or_default();
+            *hits += u32::from(outcome.correct());
+            *total += 1;
+        }
+
+        for (source, (hits, total)) in tally {
+            let current = self.historical_accuracy.get(&source).copied().unwrap_or(0.5);
+            let hit_rate = f64::from(hits) / f64::from(total);
+            let mut accuracy = current + self.config.accuracy_learning_rate * (hit_rate - current);
+            // Weight is proportional to accuracy, so cap the relative change
+            if let Some(step) = self.config.max_weight_step {
+                accuracy = accuracy.clamp(current * (1.0 - step), current * (1.0 + step));
+            }
+            self.update_accuracy(source, accuracy);
+        }
+    }
+
     /// Add a new signal to the fusion engine
     pub fn add_signal(&mut self, signal: Signal) {
         // Remove old signals from the same source
@@ -321,6 +379,10 @@ impl FusionEngine {
     }
 
     /// Get effective weight for a signal source in current regime
+    ///
+    /// Historical accuracy scales the weight under every ensemble method,
+    /// not just `Stacking`, so learned outcomes take effect with the
+    /// default method. A source with no accuracy yet keeps its base weight.
     fn get_effective_weight(&self, source: SignalSource) -> f64 {
         let base_weight = self.config.source_weights.get(&source).copied().unwrap_or(1.0);
         
@@ -331,12 +393,8 @@ impl FusionEngine {
             .copied()
             .unwrap_or(1.0);
 
-        // Apply historical accuracy if available (stacking)
-        let accuracy_factor = if self.config.ensemble_method == EnsembleMethod::Stacking {
-            self.historical_accuracy.get(&source).copied().unwrap_or(0.5) * 2.0
-        } else {
-            1.0
-        };
+        // Apply historical accuracy; an unknown source counts as a coin flip
+        let accuracy_factor = self.historical_accuracy.get(&source).copied().unwrap_or(0.5) * 2.0;
 
         base_weight * regime_factor * accuracy_factor
     }
@@ -385,6 +443,36 @@ impl FusionEngine {
         }
     }
 
+    /// Weighted contribution of each signal
+    fn weigh(&self, signals: &[&Signal]) -> Vec<SignalContribution> {
+        signals.iter()
+            .map(|s| {
+                let decay = self.calculate_decay(s);
+                let weight = self.get_effective_weight(s.source) * s.confidence * decay;
+                SignalContribution {
+                    source: s.source,
+                    weight,
+                    direction: s.direction,
+                    decay_factor: decay,
+                }
+            })
+            .collect()
+    }
+
+    /// Contributions behind a trade taken on `signal`
+    ///
+    /// `signal` joins the live signals in place of any from its source and
+    /// is not kept. Unlike `fuse`, this never abstains, so a trade is always
+    /// credited to at least the source that called it.
+    pub fn contributions_with(&self, signal: Signal) -> Vec<SignalContribution> {
+        let mut signals: Vec<&Signal> = self.filter_signals()
+            .into_iter()
+            .filter(|s| s.source != signal.source)
+            .collect();
+        signals.push(&signal);
+        self.weigh(&signals)
+    }
+
     /// Fuse all signals into a trading decision
     pub fn fuse(&self) -> FusedDecision {
         let signals = self.filter_signals();
@@ -412,18 +500,7 @@ impl FusionEngine {
         let consensus = self.calculate_consensus(&signals);
 
         // Build contributions with weights
-        let contributions: Vec<SignalContribution> = signals.iter()
-            .map(|s| {
-                let decay = self.calculate_decay(s);
-                let weight = self.get_effective_weight(s.source) * s.confidence * decay;
-                SignalContribution {
-                    source: s.source,
-                    weight,
-                    direction: s.direction,
-                    decay_factor: decay,
-                }
-            })
-            .collect();
+        let contributions = self.weigh(&signals);
 
         // Apply ensemble method
         let (direction, confidence) = match self.config.ensemble_method {
@@ -890,6 +967,69 @@ mod tests {
         assert!(decision.direction > 0.0);
     }
 
+    #[test]
+    fn test_ingest_outcomes_downweights_wrong_source() {
+        let mut engine = FusionEngine::new();
+        let before = engine.get_effective_weight(SignalSource::Sentiment);
+        let ml_before = engine.get_effective_weight(SignalSource::MLPredictor);
+
+        // Sentiment called up every time the market went down
+        let wrong = TradeOutcome {
+            source: SignalSource::Sentiment,
+            predicted: 0.8,
+            realized: -1.0,
+        };
+        for _ in 0..3 {
+            engine.ingest_outcomes(&[wrong; 5]);
+        }
+
+        // 0.5 -> 0.4 -> 0.32 -> 0.256
+        let after = engine.get_effective_weight(SignalSource::Sentiment);
+        assert!((after / before - 0.512).abs() < 1e-9);
+        // Sources with no outcomes keep their weight
+        assert_eq!(engine.get_effective_weight(SignalSource::MLPredictor), ml_before);
+
+        // A right call pulls it back up
+        engine.ingest_outcomes(&[TradeOutcome { realized: 1.0, ..wrong }]);
+        assert!(engine.get_effective_weight(SignalSource::Sentiment) > after);
+    }
+
+    #[test]
+    fn test_accuracy_weighs_sources_under_every_method() {
+        use EnsembleMethod::*;
+        let wrong = TradeOutcome { source: SignalSource::Sentiment, predicted: 1.0, realized: -1.0 };
+        for method in [SimpleAverage, WeightedAverage, MajorityVote, BayesianFusion, MaxConfidence, Stacking] {
+            let mut engine = FusionEngine::with_config(FusionConfig { ensemble_method: method, ..FusionConfig::default() });
+            // No accuracy yet: the base weight, as before outcomes were learned
+            let base = engine.config.source_weights.get(&SignalSource::Sentiment).copied().unwrap_or(1.0);
+            assert_eq!(engine.get_effective_weight(SignalSource::Sentiment), base, "{:?}", method);
+
+            engine.ingest_outcomes(&[wrong]);
+            assert!(engine.get_effective_weight(SignalSource::Sentiment) < base, "{:?}", method);
+        }
+    }
+
+    #[test]
+    fn test_ingest_outcomes_caps_weight_step() {
+        let mut engine = FusionEngine::with_config(FusionConfig {
+            accuracy_learning_rate: 1.0,
+            max_weight_step: Some(0.1),
+            ..FusionConfig::default()
+        });
+        let before = engine.get_effective_weight(SignalSource::OnChain);
+
+        let wrong = TradeOutcome {
+            source: SignalSource::OnChain,
+            predicted: -0.5,
+            realized: 1.0,
+        };
+        engine.ingest_outcomes(&[wrong; 10]);
+
+        // A full step would zero the weight; the cap allows 10%
+        let after = engine.get_effective_weight(SignalSource::OnChain);
+        assert!((after / before - 0.9).abs() < 1e-9);
+    }
+
     #[test]
     fn test_abstain_strategy() {
         let mut engine = FusionEngine::with_config(FusionConfig {
@@ -943,4 +1083,23 @@ mod tests {
             .find(|c| c.source == SignalSource::MLPredictor);
         assert!(ml_contrib.is_some());
     }
+
+    #[test]
+    fn test_contributions_with_trade_signal() {
+        let mut engine = FusionEngine::new();
+        engine.add_signal(SignalBuilder::new(SignalSource::MLPredictor).direction(-0.4).build());
+        engine.add_signal(SignalBuilder::new(SignalSource::FundingRate).direction(0.2).build());
+
+        // One signal is below min_consensus_signals but still credited
+        let alone = FusionEngine::new().contributions_with(SignalBuilder::new(SignalSource::Sentiment).build());
+        assert_eq!(alone.len(), 1);
+
+        // The trade's signal stands in for the live one from its source
+        let contributions =
+            engine.contributions_with(SignalBuilder::new(SignalSource::MLPredictor).direction(0.9).build());
+        assert_eq!(contributions.len(), 2);
+        let ml = contributions.iter().find(|c| c.source == SignalSource::MLPredictor).unwrap();
+        assert!((ml.direction - 0.9).abs() < 1e-9);
+        assert_eq!(engine.fuse().contributions.iter().find(|c| c.source == SignalSource::MLPredictor).unwrap().direction, -0.4);
+    }
 }
@@ -7,6 +7,8 @@
 
 pub mod source;
 pub mod telegram;
+#[cfg(feature = "telegram-userbot")]
+pub mod mtproto;
 pub mod twitter;
 pub mod processor;
 
@@ -0,0 +1,300 @@
+//! MTProto `TelegramConnector` built on grammers
+//!
+//! Connects as a user account with the configured `api_id`/`api_hash`, so
+//! the session must be signed in once with `polymarket-bot telegram-login`
+//! before the ingester can stream. Chat ids are in Bot API form (`-100…` for
+//! channels and supergroups), matching `watch_chats`.
+//!
+//! The session file keeps the home datacenter, its auth key and the update
+//! state as JSON. The peer cache is not kept; messages are matched by chat
+//! id, which needs no access hash.
+
+use super::telegram::{TelegramConnection, TelegramConnector, TelegramMessage};
+use super::TelegramIngesterConfig;
+use crate::error::{BotError, Result};
+use async_trait::async_trait;
+use grammers_client::client::{UpdateStream, UpdatesConfiguration};
+use grammers_client::update::Update;
+use grammers_client::{Client, SignInError};
+use grammers_mtsender::{SenderPool, SenderPoolFatHandle};
+use grammers_session::types::{ChannelState, DcOption, PeerId, PeerInfo, UpdateState, UpdatesState};
+use grammers_session::updates::UpdatesLike;
+use grammers_session::{BoxFuture, Session, SessionData};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Opens grammers sessions from `session_file`
+#[derive(Debug, Default, Clone, Copy)]
+pub struct MtprotoConnector;
+
+#[async_trait]
+impl TelegramConnector for MtprotoConnector {
+    async fn connect(&self, config: &TelegramIngesterConfig) -> Result<Box<dyn TelegramConnection>> {
+        let (client, pool) = open_client(config)?;
+        if !client.is_authorized().await.map_err(api_error)? {
+            pool.handle.quit();
+            return Err(BotError::Config(format!(
+                "Telegram session {} is not signed in; run `polymarket-bot telegram-login`",
+                config.session_file
+            )));
+        }
+
+        // Only new messages matter, so don't replay what was missed
+        let updates = client
+            .stream_updates(pool.updates, UpdatesConfiguration { catch_up: false, ..Default::default() })
+            .await
+            .map_err(|e| BotError::Api(format!("Telegram updates: {}", e)))?;
+        Ok(Box::new(MtprotoConnection { updates, handle: pool.handle }))
+    }
+}
+
+/// Sign the session in, prompting for the login code (and 2FA password)
+pub async fn sign_in_interactive(config: &TelegramIngesterConfig, phone: &str) -> Result<()> {
+    let (client, pool) = open_client(config)?;
+    let result = async {
+        if client.is_authorized().await.map_err(api_error)? {
+            return Ok(());
+        }
+        let token = client.request_login_code(phone, &config.api_hash).await.map_err(api_error)?;
+        let code = prompt("Login code: ")?;
+        match client.sign_in(&token, code.trim()).await {
+            Ok(_) => Ok(()),
+            Err(SignInError::PasswordRequired(password_token)) => {
+                let password = prompt("2FA password: ")?;
+                client
+                    .check_password(password_token, password.trim())
+                    .await
+                    .map(|_| ())
+                    .map_err(|e| BotError::Api(format!("Telegram sign-in: {}", e)))
+            }
+            Err(e) => Err(BotError::Api(format!("Telegram sign-in: {}", e))),
+        }
+    }
+    .await;
+    pool.handle.quit();
+    result
+}
+
+/// Parts of the sender pool the caller still needs after spawning its runner
+struct PoolParts {
+    handle: SenderPoolFatHandle,
+    updates: tokio::sync::mpsc::UnboundedReceiver<UpdatesLike>,
+}
+
+fn open_client(config: &TelegramIngesterConfig) -> Result<(Client, PoolParts)> {
+    let session = Arc::new(FileSession::open(&config.session_file)?);
+    let SenderPool { runner, updates, handle } = SenderPool::new(session, config.api_id);
+    let client = Client::new(handle.clone());
+    tokio::spawn(runner.run());
+    Ok((client, PoolParts { handle, updates }))
+}
+
+fn prompt(label: &str) -> Result<String> {
+    use std::io::Write;
+    print!("{}", label);
+    let mut line = String::new();
+    std::io::stdout()
+        .flush()
+        .and_then(|_| std::io::stdin().read_line(&mut line))
+        .map_err(|e| BotError::Internal(format!("Failed to read {}: {}", label.trim_end_matches(": "), e)))?;
+    Ok(line)
+}
+
+fn api_error(e: grammers_mtsender::InvocationError) -> BotError {
+    BotError::Api(format!("Telegram: {}", e))
+}
+
+/// Signed-in session streaming updates
+struct MtprotoConnection {
+    updates: UpdateStream,
+    handle: SenderPoolFatHandle,
+}
+
+#[async_trait]
+impl TelegramConnection for MtprotoConnection {
+    async fn next_message(&mut self) -> Result<Option<TelegramMessage>> {
+        loop {
+            let update = match self.updates.next().await {
+                Ok(update) => update,
+                Err(grammers_mtsender::InvocationError::Dropped) => return Ok(None),
+                Err(e) => return Err(api_error(e)),
+            };
+            let Update::NewMessage(message) = update else {
+                continue;
+            };
+            if message.outgoing() {
+                continue;
+            }
+            let Some(chat_id) = message.peer_id().bot_api_dialog_id() else {
+                continue;
+            };
+            let sender = match message.sender() {
+                Some(peer) => peer.username().map(str::to_string).unwrap_or_else(|| peer_label(peer.id())),
+                None => message.sender_id().map(peer_label).unwrap_or_else(|| chat_id.to_string()),
+            };
+            return Ok(Some(TelegramMessage {
+                chat_id,
+                message_id: message.id() as i64,
+                sender,
+                text: message.text().to_string(),
+                date: message.date(),
+            }));
+        }
+    }
+}
+
+impl Drop for MtprotoConnection {
+    fn drop(&mut self) {
+        // Stops the runner task so a reconnect starts a fresh pool
+        self.handle.quit();
+    }
+}
+
+fn peer_label(id: PeerId) -> String {
+    id.bot_api_dialog_id().map(|id| id.to_string()).unwrap_or_else(|| "self".to_string())
+}
+
+/// What `FileSession` writes to disk
+#[derive(Serialize, Deserialize)]
+struct StoredSession {
+    home_dc: i32,
+    dc_options: Vec<DcOption>,
+    updates_state: UpdatesState,
+}
+
+/// grammers session kept in memory and saved as JSON on every change
+struct FileSession {
+    path: PathBuf,
+    data: Mutex<SessionData>,
+}
+
+impl FileSession {
+    /// Load `path`, or start a fresh session if it does not exist yet
+    fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let mut data = SessionData::default();
+        if path.exists() {
+            let raw = std::fs::read_to_string(&path)
+                .map_err(|e| BotError::Internal(format!("Failed to read Telegram session: {}", e)))?;
+            let stored: StoredSession = serde_json::from_str(&raw)?;
+            data.home_dc = stored.home_dc;
+            data.dc_options.extend(stored.dc_options.into_iter().map(|dc| (dc.id, dc)));
+            data.updates_state = stored.updates_state;
+        }
+        Ok(Self { path, data: Mutex::new(data) })
+    }
+
+    fn data(&self) -> std::io::Result<MutexGuard<'_, SessionData>> {
+        self.data.lock().map_err(|_| std::io::Error::other("session lock is poisoned"))
+    }
+
+    /// Apply `change` and write the result to disk
+    fn update(&self, change: impl FnOnce(&mut SessionData)) -> std::io::Result<()> {
+        let stored = {
+            let mut data = self.data()?;
+            change(&mut data);
+            StoredSession {
+                home_dc: data.home_dc,
+                dc_options: data.dc_options.values().cloned().collect(),
+                updates_state: data.updates_state.clone(),
+            }
+        };
+        if let Some(dir) = self.path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&self.path, serde_json::to_string(&stored)?)
+    }
+}
+
+impl Session for FileSession {
+    type Error = std::io::Error;
+
+    fn home_dc_id(&self) -> std::io::Result<i32> {
+        Ok(self.data()?.home_dc)
+    }
+
+    fn set_home_dc_id(&self, dc_id: i32) -> BoxFuture<'_, std::io::Result<()>> {
+        Box::pin(async move { self.update(|data| data.home_dc = dc_id) })
+    }
+
+    fn dc_option(&self, dc_id: i32) -> std::io::Result<Option<DcOption>> {
+        Ok(self.data()?.dc_options.get(&dc_id).cloned())
+    }
+
+    fn set_dc_option(&self, dc_option: &DcOption) -> BoxFuture<'_, std::io::Result<()>> {
+        let dc_option = dc_option.clone();
+        Box::pin(async move {
+            self.update(|data| {
+                data.dc_options.insert(dc_option.id, dc_option);
+            })
+        })
+    }
+
+    fn peer(&self, peer: PeerId) -> BoxFuture<'_, std::io::Result<Option<PeerInfo>>> {
+        Box::pin(async move { Ok(self.data()?.peer_infos.get(&peer).cloned()) })
+    }
+
+    fn cache_peer(&self, peer: &PeerInfo) -> BoxFuture<'_, std::io::Result<()>> {
+        let peer = peer.clone();
+        Box::pin(async move {
+            self.data()?
+                .peer_infos
+                .entry(peer.id())
+                .or_insert_with(|| peer.clone())
+                .extend_info(&peer);
+            Ok(())
+        })
+    }
+
+    fn updates_state(&self) -> BoxFuture<'_, std::io::Result<UpdatesState>> {
+        Box::pin(async move { Ok(self.data()?.updates_state.clone()) })
+    }
+
+    fn set_update_state(&self, update: UpdateState) -> BoxFuture<'_, std::io::Result<()>> {
+        Box::pin(async move {
+            self.update(|data| match update {
+                UpdateState::All(state) => data.updates_state = state,
+                UpdateState::Primary { pts, date, seq } => {
+                    data.updates_state.pts = pts;
+                    data.updates_state.date = date;
+                    data.updates_state.seq = seq;
+                }
+                UpdateState::Secondary { qts } => data.updates_state.qts = qts,
+                UpdateState::Channel { id, pts } => {
+                    data.updates_state.channels.retain(|c| c.id != id);
+                    data.updates_state.channels.push(ChannelState { id, pts });
+                }
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_file_session_survives_reopen() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tg/userbot.session");
+
+        let session = FileSession::open(&path).unwrap();
+        let mut dc = session.dc_option(2).unwrap().unwrap();
+        dc.auth_key = Some([7; 256]);
+        session.set_dc_option(&dc).await.unwrap();
+        session.set_home_dc_id(2).await.unwrap();
+        session.set_update_state(UpdateState::Primary { pts: 10, date: 20, seq: 3 }).await.unwrap();
+        session.set_update_state(UpdateState::Channel { id: 99, pts: 5 }).await.unwrap();
+        drop(session);
+
+        let reopened = FileSession::open(&path).unwrap();
+        assert_eq!(reopened.home_dc_id().unwrap(), 2);
+        assert_eq!(reopened.dc_option(2).unwrap().unwrap().auth_key, Some([7; 256]));
+        // Datacenters never written keep their built-in addresses
+        assert!(reopened.dc_option(4).unwrap().is_some());
+        let state = reopened.updates_state().await.unwrap();
+        assert_eq!((state.pts, state.date, state.seq), (10, 20, 3));
+        assert_eq!(state.channels, vec![ChannelState { id: 99, pts: 5 }]);
+    }
+}
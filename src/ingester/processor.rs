@@ -6,34 +6,113 @@
 use super::{ActionType, ParsedSignal, RawSignal, SignalDirection};
 use crate::config::LlmConfig;
 use crate::error::{BotError, Result};
+use async_trait::async_trait;
 use chrono::{Duration, Utc};
 use reqwest::Client;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use tokio::sync::mpsc;
 
-/// Signal processor using LLM for extraction
-pub struct SignalProcessor {
+/// Sends a prompt to an LLM and returns its reply (allows mocking)
+#[async_trait]
+pub trait LlmCompletion: Send + Sync {
+    async fn complete(&self, prompt: &str) -> Result<String>;
+}
+
+/// OpenAI-compatible chat completions endpoint
+pub struct HttpLlm {
     http: Client,
     llm_config: LlmConfig,
+}
+
+impl HttpLlm {
+    pub fn new(llm_config: LlmConfig) -> Self {
+        Self {
+            http: Client::new(),
+            llm_config,
+        }
+    }
+}
+
+#[async_trait]
+impl LlmCompletion for HttpLlm {
+    async fn complete(&self, prompt: &str) -> Result<String> {
+        let (base_url, model) = match self.llm_config.provider.to_lowercase().as_str() {
+            "deepseek" => (
+                "https://api.deepseek.com".to_string(),
+                self.llm_config.model.clone().unwrap_or_else(|| "deepseek-chat".to_string()),
+            ),
+            "openai" | "gpt" => (
+                self.llm_config.base_url.clone().unwrap_or_else(|| "https://api.openai.com".to_string()),
+                self.llm_config.model.clone().unwrap_or_else(|| "gpt-4o-mini".to_string()),
+            ),
+            "ollama" => (
+                self.llm_config.base_url.clone().unwrap_or_else(|| "http://localhost:11434".to_string()),
+                self.llm_config.model.clone().unwrap_or_else(|| "qwen2.5:14b".to_string()),
+            ),
+            _ => (
+                self.llm_config.base_url.clone().unwrap_or_else(|| "https://api.deepseek.com".to_string()),
+                self.llm_config.model.clone().unwrap_or_else(|| "deepseek-chat".to_string()),
+            ),
+        };
+
+        let request = serde_json::json!({
+            "model": model,
+            "messages": [{"role": "user", "content": prompt}],
+            "response_format": {"type": "json_object"}
+        });
+
+        let mut req = self.http
+            .post(format!("{}/v1/chat/completions", base_url))
+            .header("content-type", "application/json");
+
+        if !self.llm_config.api_key.is_empty() {
+            req = req.header("Authorization", format!("Bearer {}", self.llm_config.api_key.expose()));
+        }
+
+        let resp: serde_json::Value = req.json(&request).send().await?.json().await?;
+
+        resp["choices"][0]["message"]["content"]
+            .as_str()
+            .map(|s| s.to_string())
+            .ok_or_else(|| BotError::Api("Empty LLM response".into()))
+    }
+}
+
+/// Signal processor using LLM for extraction
+pub struct SignalProcessor {
+    llm: Arc<dyn LlmCompletion>,
     /// Aggregation window in seconds
     aggregation_window: i64,
     /// Minimum confidence to emit signal
     min_confidence: f64,
     /// Minimum aggregate score to emit
     min_agg_score: f64,
+    /// Word overlap (0-1) above which two messages count as duplicates
+    dedup_similarity: f64,
 }
 
 impl SignalProcessor {
     pub fn new(llm_config: LlmConfig) -> Self {
+        Self::with_llm(Arc::new(HttpLlm::new(llm_config)))
+    }
+
+    /// Use a custom LLM backend
+    pub fn with_llm(llm: Arc<dyn LlmCompletion>) -> Self {
         Self {
-            http: Client::new(),
-            llm_config,
+            llm,
             aggregation_window: 300, // 5 minutes
             min_confidence: 0.5,
             min_agg_score: 0.6,
+            dedup_similarity: 0.8,
         }
     }
 
+    pub fn with_dedup_similarity(mut self, similarity: f64) -> Self {
+        self.dedup_similarity = similarity.clamp(0.0, 1.0);
+        self
+    }
+
     pub fn with_thresholds(mut self, min_confidence: f64, min_agg_score: f64) -> Self {
         self.min_confidence = min_confidence;
         self.min_agg_score = min_agg_score;
@@ -45,6 +124,34 @@ impl SignalProcessor {
         self
     }
 
+    /// Turn a batch of raw messages into one signal per token
+    ///
+    /// Near-identical messages are collapsed to the one from the most trusted
+    /// author before any LLM calls. The rest are extracted, grouped by token
+    /// and aggregated; tokens whose aggregate score is below the threshold
+    /// are dropped. Output is sorted by token.
+    pub async fn process(&self, batch: Vec<RawSignal>) -> Vec<ParsedSignal> {
+        let unique = dedup_raw(batch, self.dedup_similarity);
+
+        let mut by_token: HashMap<String, Vec<ExtractedSignal>> = HashMap::new();
+        for raw in &unique {
+            match self.extract_signal(raw).await {
+                Ok(Some(extracted)) => by_token.entry(extracted.token.clone()).or_default().push(extracted),
+                Ok(None) => tracing::debug!("No signal extracted from: {}", raw.source_id),
+                Err(e) => tracing::warn!("Failed to extract signal from {}: {}", raw.source_id, e),
+            }
+        }
+
+        let mut signals: Vec<ParsedSignal> = by_token
+            .into_iter()
+            .filter(|(_, extracted)| is_ready(extracted))
+            .filter_map(|(token, extracted)| aggregate(&token, &extracted))
+            .filter(|p| p.agg_score >= self.min_agg_score)
+            .collect();
+        signals.sort_by(|a, b| a.token.cmp(&b.token));
+        signals
+    }
+
     /// Run the processing pipeline
     pub async fn run(
         &self,
@@ -148,53 +255,11 @@ Only valid JSON, no other text."#,
             raw.content
         );
 
-        let response = self.call_llm(&prompt).await?;
+        let response = self.llm.complete(&prompt).await?;
         let parsed = self.parse_llm_response(&response, raw)?;
         Ok(parsed)
     }
 
-    async fn call_llm(&self, prompt: &str) -> Result<String> {
-        let (base_url, model) = match self.llm_config.provider.to_lowercase().as_str() {
-            "deepseek" => (
-                "https://api.deepseek.com".to_string(),
-                self.llm_config.model.clone().unwrap_or_else(|| "deepseek-chat".to_string()),
-            ),
-            "openai" | "gpt" => (
-                self.llm_config.base_url.clone().unwrap_or_else(|| "https://api.openai.com".to_string()),
-                self.llm_config.model.clone().unwrap_or_else(|| "gpt-4o-mini".to_string()),
-            ),
-            "ollama" => (
-                self.llm_config.base_url.clone().unwrap_or_else(|| "http://localhost:11434".to_string()),
-                self.llm_config.model.clone().unwrap_or_else(|| "qwen2.5:14b".to_string()),
-            ),
-            _ => (
-                self.llm_config.base_url.clone().unwrap_or_else(|| "https://api.deepseek.com".to_string()),
-                self.llm_config.model.clone().unwrap_or_else(|| "deepseek-chat".to_string()),
-            ),
-        };
-
-        let request = serde_json::json!({
-            "model": model,
-            "messages": [{"role": "user", "content": prompt}],
-            "response_format": {"type": "json_object"}
-        });
-
-        let mut req = self.http
-            .post(format!("{}/v1/chat/completions", base_url))
-            .header("content-type", "application/json");
-
-        if !self.llm_config.api_key.is_empty() {
-            req = req.header("Authorization", format!("Bearer {}", self.llm_config.api_key));
-        }
-
-        let resp: serde_json::Value = req.json(&request).send().await?.json().await?;
-
-        resp["choices"][0]["message"]["content"]
-            .as_str()
-            .map(|s| s.to_string())
-            .ok_or_else(|| BotError::Api("Empty LLM response".into()))
-    }
-
     fn parse_llm_response(&self, response: &str, raw: &RawSignal) -> Result<Option<ExtractedSignal>> {
         // Extract JSON from response
         let json_str = if response.contains('{') {
@@ -260,74 +325,125 @@ Only valid JSON, no other text."#,
             return None;
         }
 
-        // Need at least 2 signals for aggregation, or 1 high-confidence signal
-        if recent.len() < 2 {
-            let first = &recent[0];
-            if first.confidence < 0.8 || first.raw.author_trust < 0.7 {
-                // Put back if not ready to aggregate
-                buffer.insert(token.to_string(), recent);
-                return None;
-            }
+        if !is_ready(&recent) {
+            // Put back if not ready to aggregate
+            buffer.insert(token.to_string(), recent);
+            return None;
         }
 
-        // Count directions
-        let mut bullish_score = 0.0;
-        let mut bearish_score = 0.0;
-        let mut sources = Vec::new();
-        let mut best_timeframe = String::from("1h");
-        let mut best_confidence = 0.0;
-        let mut best_reasoning = String::new();
-        let mut best_action = ActionType::Info;
-
-        for s in &recent {
-            let weight = s.confidence * s.raw.author_trust;
-            match s.direction {
-                SignalDirection::Bullish => bullish_score += weight,
-                SignalDirection::Bearish => bearish_score += weight,
-                SignalDirection::Neutral => {}
-            }
-            sources.push(s.raw.clone());
-            
-            // Track best signal
-            if s.confidence > best_confidence {
-                best_confidence = s.confidence;
-                best_timeframe = s.timeframe.clone();
-                best_reasoning = s.reasoning.clone();
-                best_action = s.action;
-            }
-        }
+        aggregate(token, &recent)
+    }
+}
 
-        let total_score = bullish_score + bearish_score;
-        if total_score < 0.3 {
-            return None;
+/// Need at least 2 signals for aggregation, or 1 high-confidence signal
+fn is_ready(signals: &[ExtractedSignal]) -> bool {
+    match signals {
+        [] => false,
+        [only] => only.confidence >= 0.8 && only.raw.author_trust >= 0.7,
+        _ => true,
+    }
+}
+
+/// Combine extracted signals for one token
+///
+/// Each signal votes with `confidence * author_trust`. The winning side's
+/// share of the vote is the aggregate score (plus a bonus per extra author)
+/// and also scales confidence, so conflicting authors lower both.
+fn aggregate(token: &str, signals: &[ExtractedSignal]) -> Option<ParsedSignal> {
+    let mut bullish_score = 0.0;
+    let mut bearish_score = 0.0;
+    for s in signals {
+        let weight = s.confidence * s.raw.author_trust;
+        match s.direction {
+            SignalDirection::Bullish => bullish_score += weight,
+            SignalDirection::Bearish => bearish_score += weight,
+            SignalDirection::Neutral => {}
         }
+    }
 
-        let (direction, agg_score) = if bullish_score > bearish_score {
-            (SignalDirection::Bullish, bullish_score / total_score)
-        } else if bearish_score > bullish_score {
-            (SignalDirection::Bearish, bearish_score / total_score)
-        } else {
-            (SignalDirection::Neutral, 0.5)
-        };
+    let total_score = bullish_score + bearish_score;
+    if total_score < 0.3 {
+        return None;
+    }
 
-        // Multi-source bonus
-        let unique_authors: std::collections::HashSet<_> = 
-            sources.iter().map(|s| &s.author).collect();
-        let multi_source_bonus = (unique_authors.len() as f64 - 1.0) * 0.1;
-        let final_score = (agg_score + multi_source_bonus).min(1.0);
+    let (direction, agg_score) = if bullish_score > bearish_score {
+        (SignalDirection::Bullish, bullish_score / total_score)
+    } else if bearish_score > bullish_score {
+        (SignalDirection::Bearish, bearish_score / total_score)
+    } else {
+        (SignalDirection::Neutral, 0.5)
+    };
+
+    // Details come from the most confident signal on the winning side
+    let winners: Vec<&ExtractedSignal> = signals
+        .iter()
+        .filter(|s| direction == SignalDirection::Neutral || s.direction == direction)
+        .collect();
+    let best = winners
+        .iter()
+        .max_by(|a, b| a.confidence.total_cmp(&b.confidence))?;
+
+    // Trust-weighted confidence of the winners, discounted by disagreement
+    let trust: f64 = winners.iter().map(|s| s.raw.author_trust).sum();
+    let confidence = if trust > 0.0 {
+        winners.iter().map(|s| s.confidence * s.raw.author_trust).sum::<f64>() / trust
+    } else {
+        best.confidence
+    };
+
+    let sources: Vec<RawSignal> = signals.iter().map(|s| s.raw.clone()).collect();
+
+    // Multi-source bonus
+    let unique_authors: HashSet<_> = sources.iter().map(|s| &s.author).collect();
+    let multi_source_bonus = (unique_authors.len() as f64 - 1.0) * 0.1;
+    let final_score = (agg_score + multi_source_bonus).min(1.0);
+
+    Some(ParsedSignal {
+        token: token.to_string(),
+        direction,
+        timeframe: best.timeframe.clone(),
+        confidence: confidence * agg_score,
+        reasoning: best.reasoning.clone(),
+        action_type: best.action,
+        sources,
+        agg_score: final_score,
+        timestamp: Utc::now(),
+    })
+}
 
-        Some(ParsedSignal {
-            token: token.to_string(),
-            direction,
-            timeframe: best_timeframe,
-            confidence: best_confidence,
-            reasoning: best_reasoning,
-            action_type: best_action,
-            sources,
-            agg_score: final_score,
-            timestamp: Utc::now(),
-        })
+/// Lowercased alphanumeric words of a message
+fn message_words(content: &str) -> HashSet<String> {
+    content
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Word-set Jaccard similarity of two messages
+fn similarity(a: &HashSet<String>, b: &HashSet<String>) -> f64 {
+    let union = a.union(b).count();
+    if union == 0 {
+        return 1.0;
+    }
+    a.intersection(b).count() as f64 / union as f64
+}
+
+/// Drop near-duplicate messages, keeping the most trusted author's copy
+fn dedup_raw(mut batch: Vec<RawSignal>, threshold: f64) -> Vec<RawSignal> {
+    batch.sort_by(|a, b| b.author_trust.total_cmp(&a.author_trust));
+
+    let mut kept: Vec<(HashSet<String>, RawSignal)> = Vec::new();
+    for raw in batch {
+        let words = message_words(&raw.content);
+        if kept.iter().any(|(seen, _)| similarity(seen, &words) >= threshold) {
+            tracing::debug!("Dropping duplicate message {} from {}", raw.source_id, raw.author);
+            continue;
+        }
+        kept.push((words, raw));
     }
+    kept.into_iter().map(|(_, raw)| raw).collect()
 }
 
 /// Intermediate extracted signal before aggregation
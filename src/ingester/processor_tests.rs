@@ -3,19 +3,134 @@
 #[cfg(test)]
 mod tests {
     use super::super::{RawSignal, ParsedSignal, SignalDirection, ActionType};
-    use super::super::processor::SignalProcessor;
+    use super::super::processor::{LlmCompletion, SignalProcessor};
     use crate::config::LlmConfig;
+    use crate::error::Result;
+    use async_trait::async_trait;
     use chrono::Utc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
 
     fn create_llm_config() -> LlmConfig {
         LlmConfig {
             provider: "deepseek".to_string(),
-            api_key: "test-key".to_string(),
+            api_key: "test-key".into(),
             model: Some("deepseek-chat".to_string()),
             base_url: None,
         }
     }
 
+    /// Replies with the canned JSON whose key appears in the prompt (ignoring case)
+    struct ScriptedLlm {
+        replies: Vec<(&'static str, &'static str)>,
+        calls: AtomicUsize,
+    }
+
+    impl ScriptedLlm {
+        fn new(replies: Vec<(&'static str, &'static str)>) -> Arc<Self> {
+            Arc::new(Self { replies, calls: AtomicUsize::new(0) })
+        }
+    }
+
+    #[async_trait]
+    impl LlmCompletion for ScriptedLlm {
+        async fn complete(&self, prompt: &str) -> Result<String> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let prompt = prompt.to_lowercase();
+            let reply = self
+                .replies
+                .iter()
+                .find(|(key, _)| prompt.contains(key))
+                .map(|(_, reply)| *reply)
+                .unwrap_or(r#"{"token": null}"#);
+            Ok(reply.to_string())
+        }
+    }
+
+    fn raw(source_id: &str, author: &str, trust: f64, content: &str) -> RawSignal {
+        RawSignal {
+            source: "telegram".to_string(),
+            source_id: source_id.to_string(),
+            content: content.to_string(),
+            author: author.to_string(),
+            author_trust: trust,
+            timestamp: Utc::now(),
+            metadata: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_process_dedups_near_identical_messages() {
+        let llm = ScriptedLlm::new(vec![
+            ("breaking out", r#"{"token": "btc", "direction": "bullish", "timeframe": "4h", "confidence": 0.9, "action": "entry", "reasoning": "breakout"}"#),
+            ("weather", r#"{"token": null}"#),
+        ]);
+        let processor = SignalProcessor::with_llm(llm.clone());
+
+        let signals = processor
+            .process(vec![
+                raw("1", "copycat", 0.5, "BTC breaking out, target 100k!"),
+                raw("2", "whale", 0.9, "btc BREAKING OUT target 100k 🚀"),
+                raw("3", "random", 0.4, "Nice weather today"),
+            ])
+            .await;
+
+        // One LLM call per unique message
+        assert_eq!(llm.calls.load(Ordering::SeqCst), 2);
+        assert_eq!(signals.len(), 1);
+
+        let btc = &signals[0];
+        assert_eq!(btc.token, "BTC");
+        assert_eq!(btc.direction, SignalDirection::Bullish);
+        assert_eq!(btc.timeframe, "4h");
+        assert_eq!(btc.action_type, ActionType::Entry);
+        // The most trusted author's copy is kept
+        assert_eq!(btc.sources.len(), 1);
+        assert_eq!(btc.sources[0].author, "whale");
+        assert!((btc.confidence - 0.9).abs() < 1e-9);
+        assert!((btc.agg_score - 1.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_process_conflicting_authors_lower_confidence() {
+        let llm = ScriptedLlm::new(vec![
+            ("pump", r#"{"token": "ETH", "direction": "bullish", "timeframe": "1h", "confidence": 0.9, "action": "entry", "reasoning": "pump"}"#),
+            ("rally", r#"{"token": "ETH", "direction": "bullish", "timeframe": "1h", "confidence": 0.9, "action": "entry", "reasoning": "rally"}"#),
+            ("dump", r#"{"token": "ETH", "direction": "bearish", "timeframe": "1h", "confidence": 0.8, "action": "entry", "reasoning": "dump"}"#),
+        ]);
+        let processor = SignalProcessor::with_llm(llm);
+
+        let agreeing = processor
+            .process(vec![
+                raw("1", "alice", 0.9, "ETH pump incoming"),
+                raw("2", "bob", 0.6, "ETH rally about to start"),
+            ])
+            .await;
+        let conflicting = processor
+            .process(vec![
+                raw("1", "alice", 0.9, "ETH pump incoming"),
+                raw("3", "carol", 0.6, "ETH dump about to start"),
+            ])
+            .await;
+
+        assert_eq!(agreeing.len(), 1);
+        assert_eq!(conflicting.len(), 1);
+        let (agreeing, conflicting) = (&agreeing[0], &conflicting[0]);
+
+        // Trust-weighted vote still favours the more trusted bull
+        assert_eq!(conflicting.direction, SignalDirection::Bullish);
+        assert_eq!(conflicting.sources.len(), 2);
+
+        // 0.81 bullish vs 0.48 bearish
+        let share = 0.81 / (0.81 + 0.48);
+        assert!((conflicting.confidence - 0.9 * share).abs() < 1e-9);
+        assert!((conflicting.agg_score - (share + 0.1)).abs() < 1e-9);
+
+        assert!((agreeing.confidence - 0.9).abs() < 1e-9);
+        assert!(conflicting.confidence < agreeing.confidence);
+        assert!(conflicting.agg_score < agreeing.agg_score);
+    }
+
     #[test]
     fn test_signal_processor_creation() {
         let config = create_llm_config();
@@ -1,33 +1,124 @@
 //! Telegram group monitoring
 //!
-//! Uses grammers (MTProto) to monitor Telegram groups for trading signals.
-//! Requires user authentication (not bot API) to access group messages.
+//! Monitors Telegram groups for trading signals through a user session
+//! (MTProto, not the bot API), which is needed to read group messages. The
+//! MTProto client itself sits behind `TelegramConnector` (see `mtproto` for
+//! the grammers one, behind the `telegram-userbot` feature); `TelegramSource`
+//! filters the configured chats, scores authors and reconnects with
+//! exponential backoff whenever the session drops.
 
 use super::{RawSignal, SignalSource, TelegramIngesterConfig};
 use crate::error::Result;
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 
+/// Initial delay before reconnecting
+const INITIAL_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+/// Upper bound for the reconnect delay
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// Trust for authors missing from the trust map
+const DEFAULT_AUTHOR_TRUST: f64 = 0.3;
+
+/// New message received on a Telegram session
+#[derive(Debug, Clone)]
+pub struct TelegramMessage {
+    pub chat_id: i64,
+    pub message_id: i64,
+    /// Sender username, or id when the sender has no username
+    pub sender: String,
+    pub text: String,
+    pub date: DateTime<Utc>,
+}
+
+/// Open Telegram session delivering new messages
+#[async_trait]
+pub trait TelegramConnection: Send {
+    /// Wait for the next message; `Ok(None)` means the session was closed
+    async fn next_message(&mut self) -> Result<Option<TelegramMessage>>;
+}
+
+/// Opens Telegram sessions (e.g. an MTProto client using the session file)
+#[async_trait]
+pub trait TelegramConnector: Send + Sync {
+    async fn connect(&self, config: &TelegramIngesterConfig) -> Result<Box<dyn TelegramConnection>>;
+}
+
 /// Telegram group monitor
 pub struct TelegramSource {
     config: TelegramIngesterConfig,
-    #[allow(dead_code)]
-    author_trust: std::collections::HashMap<String, f64>,
+    author_trust: HashMap<String, f64>,
+    connector: Arc<dyn TelegramConnector>,
+    initial_reconnect_delay: Duration,
+    max_reconnect_delay: Duration,
 }
 
 impl TelegramSource {
     pub fn new(
         config: TelegramIngesterConfig,
-        #[allow(dead_code)]
-    author_trust: std::collections::HashMap<String, f64>,
+        author_trust: HashMap<String, f64>,
+        connector: Arc<dyn TelegramConnector>,
     ) -> Self {
-        Self { config, author_trust }
+        Self {
+            config,
+            author_trust,
+            connector,
+            initial_reconnect_delay: INITIAL_RECONNECT_DELAY,
+            max_reconnect_delay: MAX_RECONNECT_DELAY,
+        }
+    }
+
+    /// Override the reconnect backoff bounds
+    pub fn with_reconnect_delay(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_reconnect_delay = initial;
+        self.max_reconnect_delay = max.max(initial);
+        self
     }
 
-    #[allow(dead_code)]
     fn get_trust(&self, author: &str) -> f64 {
-        self.author_trust.get(author).copied().unwrap_or(0.3)
+        self.author_trust.get(author).copied().unwrap_or(DEFAULT_AUTHOR_TRUST)
+    }
+
+    /// Convert a message from a watched chat into a `RawSignal`
+    fn to_signal(&self, msg: TelegramMessage) -> Option<RawSignal> {
+        if !self.config.watch_chats.contains(&msg.chat_id) || msg.text.trim().is_empty() {
+            return None;
+        }
+        Some(RawSignal {
+            source: "telegram".to_string(),
+            source_id: format!("{}:{}", msg.chat_id, msg.message_id),
+            author_trust: self.get_trust(&msg.sender),
+            author: msg.sender,
+            content: msg.text,
+            timestamp: msg.date,
+            metadata: Some(serde_json::json!({
+                "chat_id": msg.chat_id,
+                "message_id": msg.message_id
+            })),
+        })
+    }
+
+    /// Stream one session until it drops
+    ///
+    /// Returns `Ok(true)` if the receiver went away and the source should stop.
+    async fn stream_session(
+        &self,
+        conn: &mut dyn TelegramConnection,
+        tx: &mpsc::Sender<RawSignal>,
+    ) -> Result<bool> {
+        while let Some(msg) = conn.next_message().await? {
+            let Some(signal) = self.to_signal(msg) else {
+                continue;
+            };
+            if tx.send(signal).await.is_err() {
+                return Ok(true);
+            }
+        }
+        Ok(false)
     }
 }
 
@@ -37,46 +128,38 @@ impl SignalSource for TelegramSource {
         "telegram"
     }
 
-    async fn run(&self, _tx: mpsc::Sender<RawSignal>) -> Result<()> {
-        // Note: Full grammers implementation requires:
-        // 1. Session management (login flow)
-        // 2. MTProto connection
-        // 3. Update handling
-        //
-        // For now, we'll use a polling approach with the Bot API
-        // or implement full userbot later
-        
+    async fn run(&self, tx: mpsc::Sender<RawSignal>) -> Result<()> {
         tracing::info!(
             "Telegram source starting, monitoring {} chats",
             self.config.watch_chats.len()
         );
 
-        // Placeholder: In production, replace with grammers client
-        // Example grammers usage:
-        //
-        // let client = Client::connect(Config {
-        //     session: Session::load_file_or_create(&self.config.session_file)?,
-        //     api_id: self.config.api_id,
-        //     api_hash: self.config.api_hash.clone(),
-        //     params: Default::default(),
-        // }).await?;
-        //
-        // while let Some(update) = client.next_update().await? {
-        //     if let Update::NewMessage(msg) = update {
-        //         if self.config.watch_chats.contains(&msg.chat().id()) {
-        //             let signal = RawSignal { ... };
-        //             tx.send(signal).await?;
-        //         }
-        //     }
-        // }
-
-        // For development: simulate with interval
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
-        
+        let mut delay = self.initial_reconnect_delay;
+
         loop {
-            interval.tick().await;
-            tracing::debug!("Telegram source heartbeat");
-            // In production, this would be replaced by actual message handling
+            match self.connector.connect(&self.config).await {
+                Ok(mut conn) => {
+                    tracing::info!("Telegram session connected");
+                    delay = self.initial_reconnect_delay;
+                    match self.stream_session(conn.as_mut(), &tx).await {
+                        Ok(true) => {
+                            tracing::warn!("Signal channel closed, stopping Telegram source");
+                            return Ok(());
+                        }
+                        Ok(false) => tracing::warn!("Telegram session closed"),
+                        Err(e) => tracing::error!("Telegram session error: {}", e),
+                    }
+                }
+                Err(e) => tracing::error!("Telegram connect failed: {}", e),
+            }
+
+            if tx.is_closed() {
+                return Ok(());
+            }
+
+            tracing::info!("Reconnecting to Telegram in {}ms...", delay.as_millis());
+            tokio::time::sleep(delay).await;
+            delay = (delay * 2).min(self.max_reconnect_delay);
         }
     }
 }
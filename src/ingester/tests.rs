@@ -251,4 +251,204 @@ mod tests {
         let cloned = action.clone();
         assert_eq!(action, cloned);
     }
+
+    mod telegram {
+        use super::super::super::telegram::{
+            TelegramConnection, TelegramConnector, TelegramMessage, TelegramSource,
+        };
+        use super::super::super::*;
+        use crate::error::{BotError, Result};
+        use async_trait::async_trait;
+        use chrono::Utc;
+        use std::collections::{HashMap, VecDeque};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::{Arc, Mutex};
+        use std::time::Duration;
+        use tokio::sync::mpsc;
+
+        fn message(chat_id: i64, message_id: i64, sender: &str, text: &str) -> TelegramMessage {
+            TelegramMessage {
+                chat_id,
+                message_id,
+                sender: sender.to_string(),
+                text: text.to_string(),
+                date: Utc::now(),
+            }
+        }
+
+        struct FakeConnection(VecDeque<TelegramMessage>);
+
+        #[async_trait]
+        impl TelegramConnection for FakeConnection {
+            async fn next_message(&mut self) -> Result<Option<TelegramMessage>> {
+                Ok(self.0.pop_front())
+            }
+        }
+
+        /// Hands out one scripted session per connect, then fails
+        struct FakeConnector {
+            sessions: Mutex<VecDeque<Vec<TelegramMessage>>>,
+            connects: AtomicUsize,
+        }
+
+        #[async_trait]
+        impl TelegramConnector for FakeConnector {
+            async fn connect(&self, _config: &TelegramIngesterConfig) -> Result<Box<dyn TelegramConnection>> {
+                self.connects.fetch_add(1, Ordering::SeqCst);
+                match self.sessions.lock().unwrap().pop_front() {
+                    Some(messages) => Ok(Box::new(FakeConnection(messages.into()))),
+                    None => Err(BotError::Api("offline".into())),
+                }
+            }
+        }
+
+        #[tokio::test]
+        async fn test_telegram_source_streams_and_reconnects() {
+            let connector = Arc::new(FakeConnector {
+                sessions: Mutex::new(VecDeque::from(vec![
+                    vec![message(100, 1, "alpha_caller", "BTC long here")],
+                    vec![
+                        message(999, 2, "alpha_caller", "not a watched chat"),
+                        message(200, 3, "stranger", "ETH looks weak"),
+                    ],
+                ])),
+                connects: AtomicUsize::new(0),
+            });
+            let config = TelegramIngesterConfig {
+                api_id: 12345,
+                api_hash: "hash".to_string(),
+                session_file: "test.session".to_string(),
+                watch_chats: vec![100, 200],
+            };
+            let trust = HashMap::from([("alpha_caller".to_string(), 0.9)]);
+            let source = TelegramSource::new(config, trust, connector.clone())
+                .with_reconnect_delay(Duration::from_millis(1), Duration::from_millis(5));
+            assert_eq!(source.name(), "telegram");
+
+            let (tx, mut rx) = mpsc::channel(10);
+            let handle = tokio::spawn(async move { source.run(tx).await });
+
+            let first = rx.recv().await.unwrap();
+            assert_eq!(first.source, "telegram");
+            assert_eq!(first.source_id, "100:1");
+            assert_eq!(first.author, "alpha_caller");
+            assert_eq!(first.content, "BTC long here");
+            assert_eq!(first.author_trust, 0.9);
+
+            // Arrives on the second session, after the unwatched chat is skipped
+            let second = rx.recv().await.unwrap();
+            assert_eq!(second.source, "telegram");
+            assert_eq!(second.source_id, "200:3");
+            assert_eq!(second.author, "stranger");
+            assert_eq!(second.author_trust, 0.3);
+            assert!(connector.connects.load(Ordering::SeqCst) >= 2);
+
+            // Keeps retrying after connect failures until the receiver goes away
+            drop(rx);
+            tokio::time::timeout(Duration::from_secs(5), handle)
+                .await
+                .expect("source stops once the channel is closed")
+                .unwrap()
+                .unwrap();
+        }
+    }
+
+    mod twitter {
+        use super::super::super::twitter::TwitterSource;
+        use super::super::super::*;
+        use crate::error::{BotError, Result};
+        use crate::sentiment::twitter_client::{Tweet, UserTimeline};
+        use async_trait::async_trait;
+        use std::collections::HashMap;
+        use std::sync::Arc;
+        use std::time::Duration;
+        use tokio::sync::mpsc;
+
+        fn tweet(id: &str, author_id: &str, username: &str, text: &str) -> Tweet {
+            Tweet {
+                id: id.to_string(),
+                text: text.to_string(),
+                author_id: author_id.to_string(),
+                author_username: username.to_string(),
+                created_at: 1_700_000_000,
+                retweet_count: 0,
+                like_count: 0,
+                reply_count: 0,
+                quote_count: 0,
+            }
+        }
+
+        struct FakeTimeline(HashMap<String, Vec<Tweet>>);
+
+        #[async_trait]
+        impl UserTimeline for FakeTimeline {
+            async fn user_tweets(&self, user_id: &str, _max_results: u32) -> Result<Vec<Tweet>> {
+                self.0
+                    .get(user_id)
+                    .cloned()
+                    .ok_or(BotError::RateLimited { retry_after_secs: 3600 })
+            }
+        }
+
+        fn config(bearer_token: Option<&str>) -> TwitterIngesterConfig {
+            TwitterIngesterConfig {
+                bearer_token: bearer_token.map(str::to_string),
+                watch_users: vec!["1".to_string(), "2".to_string()],
+                keywords: vec!["btc".to_string(), "Polymarket".to_string()],
+            }
+        }
+
+        #[tokio::test]
+        async fn test_twitter_source_filters_keywords_and_assigns_trust() {
+            let timeline = FakeTimeline(HashMap::from([
+                (
+                    "1".to_string(),
+                    vec![
+                        tweet("a", "1", "whale", "BTC to 100k this week"),
+                        tweet("b", "1", "whale", "Good morning everyone"),
+                    ],
+                ),
+                (
+                    "2".to_string(),
+                    vec![
+                        tweet("c", "2", "", "New polymarket odds look mispriced"),
+                        // Already emitted for user 1
+                        tweet("a", "1", "whale", "BTC to 100k this week"),
+                    ],
+                ),
+            ]));
+            let trust = HashMap::from([("whale".to_string(), 0.9), ("2".to_string(), 0.6)]);
+            let source = TwitterSource::new(config(Some("token")), trust)
+                .with_timeline(Arc::new(timeline))
+                .with_poll_interval(Duration::from_secs(3600));
+
+            let (tx, mut rx) = mpsc::channel(10);
+            let handle = tokio::spawn(async move { source.run(tx).await });
+
+            let first = rx.recv().await.unwrap();
+            assert_eq!(first.source, "twitter");
+            assert_eq!(first.source_id, "a");
+            assert_eq!(first.author, "whale");
+            assert_eq!(first.author_trust, 0.9);
+            assert_eq!(first.timestamp.timestamp(), 1_700_000_000);
+
+            // "Good morning" has no keyword; the author without a username
+            // falls back to the user id for both name and trust
+            let second = rx.recv().await.unwrap();
+            assert_eq!(second.source_id, "c");
+            assert_eq!(second.author, "2");
+            assert_eq!(second.author_trust, 0.6);
+
+            // Nothing else arrives before the next poll
+            assert!(tokio::time::timeout(Duration::from_millis(50), rx.recv()).await.is_err());
+            handle.abort();
+        }
+
+        #[tokio::test]
+        async fn test_twitter_source_without_token_is_noop() {
+            let source = TwitterSource::new(config(None), HashMap::new());
+            let (tx, _rx) = mpsc::channel(1);
+            assert!(source.run(tx).await.is_ok());
+        }
+    }
 }
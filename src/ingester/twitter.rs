@@ -4,66 +4,107 @@
 //! Supports both API v2 and RSS fallback.
 
 use super::{RawSignal, SignalSource, TwitterIngesterConfig};
-use crate::error::Result;
+use crate::error::{BotError, Result};
+use crate::sentiment::twitter_client::{Tweet, TwitterClient, UserTimeline};
 use async_trait::async_trait;
-use chrono::Utc;
-use serde::Deserialize;
+use chrono::{TimeZone, Utc};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::mpsc;
 
+/// Tweets requested per user per poll
+const TWEETS_PER_POLL: u32 = 10;
+
 /// Twitter API v2 source
+///
+/// Polls the timelines of `watch_users` through the shared sentiment
+/// `TwitterClient`. When the API reports the rate limit is exhausted the
+/// source waits for the window to reset before polling again.
 pub struct TwitterSource {
     config: TwitterIngesterConfig,
-    http: reqwest::Client,
-    author_trust: std::collections::HashMap<String, f64>,
+    timeline: Arc<dyn UserTimeline>,
+    author_trust: HashMap<String, f64>,
+    poll_interval: Duration,
 }
 
 impl TwitterSource {
     pub fn new(
         config: TwitterIngesterConfig,
-        author_trust: std::collections::HashMap<String, f64>,
+        author_trust: HashMap<String, f64>,
     ) -> Self {
+        let client = TwitterClient::new(config.bearer_token.clone());
         Self {
             config,
-            http: reqwest::Client::new(),
+            timeline: Arc::new(client),
             author_trust,
+            poll_interval: Duration::from_secs(60),
         }
     }
 
-    fn get_trust(&self, author: &str) -> f64 {
-        self.author_trust.get(author).copied().unwrap_or(0.3)
+    /// Read timelines from a different client
+    pub fn with_timeline(mut self, timeline: Arc<dyn UserTimeline>) -> Self {
+        self.timeline = timeline;
+        self
     }
 
-    async fn fetch_user_tweets(&self, user_id: &str) -> Result<Vec<Tweet>> {
-        let bearer = self.config.bearer_token.as_ref()
-            .ok_or_else(|| crate::error::BotError::Config("Twitter bearer token required".into()))?;
-
-        let url = format!(
-            "https://api.twitter.com/2/users/{}/tweets?max_results=10&tweet.fields=created_at,author_id",
-            user_id
-        );
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
 
-        let resp = self.http
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", bearer))
-            .send()
-            .await?;
+    /// Trust by username, then by user id
+    fn get_trust(&self, tweet: &Tweet) -> f64 {
+        self.author_trust
+            .get(&tweet.author_username)
+            .or_else(|| self.author_trust.get(&tweet.author_id))
+            .copied()
+            .unwrap_or(0.3)
+    }
 
-        let data: TwitterResponse = resp.json().await?;
-        Ok(data.data.unwrap_or_default())
+    fn matches_keywords(&self, text: &str) -> bool {
+        if self.config.keywords.is_empty() {
+            return true;
+        }
+        let text_lower = text.to_lowercase();
+        self.config
+            .keywords
+            .iter()
+            .any(|k| text_lower.contains(&k.to_lowercase()))
     }
-}
 
-#[derive(Debug, Deserialize)]
-struct TwitterResponse {
-    data: Option<Vec<Tweet>>,
-}
+    /// Convert a tweet into a `RawSignal` if it passes the keyword filter
+    fn to_signal(&self, user_id: &str, tweet: Tweet) -> Option<RawSignal> {
+        if !self.matches_keywords(&tweet.text) {
+            return None;
+        }
 
-#[derive(Debug, Deserialize)]
-struct Tweet {
-    id: String,
-    text: String,
-    author_id: Option<String>,
-    created_at: Option<String>,
+        let author_trust = self.get_trust(&tweet);
+        let author = if tweet.author_username.is_empty() {
+            tweet.author_id.clone()
+        } else {
+            tweet.author_username.clone()
+        };
+        let timestamp = Utc
+            .timestamp_opt(tweet.created_at as i64, 0)
+            .single()
+            .unwrap_or_else(Utc::now);
+
+        Some(RawSignal {
+            source: "twitter".to_string(),
+            source_id: tweet.id,
+            content: tweet.text,
+            author,
+            author_trust,
+            timestamp,
+            metadata: Some(serde_json::json!({
+                "user_id": user_id,
+                "author_id": tweet.author_id,
+                "likes": tweet.like_count,
+                "retweets": tweet.retweet_count
+            })),
+        })
+    }
 }
 
 #[async_trait]
@@ -73,50 +114,33 @@ impl SignalSource for TwitterSource {
     }
 
     async fn run(&self, tx: mpsc::Sender<RawSignal>) -> Result<()> {
+        if self.config.bearer_token.is_none() {
+            tracing::info!("Twitter source disabled: no bearer token configured");
+            return Ok(());
+        }
+
         tracing::info!(
             "Twitter source starting, monitoring {} users",
             self.config.watch_users.len()
         );
 
-        let mut seen_ids: std::collections::HashSet<String> = std::collections::HashSet::new();
-        let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+        let mut seen_ids: HashSet<String> = HashSet::new();
+        let mut interval = tokio::time::interval(self.poll_interval);
 
         loop {
             interval.tick().await;
 
             for user_id in &self.config.watch_users {
-                match self.fetch_user_tweets(user_id).await {
+                match self.timeline.user_tweets(user_id, TWEETS_PER_POLL).await {
                     Ok(tweets) => {
                         for tweet in tweets {
                             // Skip if already seen
-                            if seen_ids.contains(&tweet.id) {
+                            if !seen_ids.insert(tweet.id.clone()) {
                                 continue;
                             }
-                            seen_ids.insert(tweet.id.clone());
-
-                            // Filter by keywords if configured
-                            if !self.config.keywords.is_empty() {
-                                let text_lower = tweet.text.to_lowercase();
-                                let has_keyword = self.config.keywords
-                                    .iter()
-                                    .any(|k| text_lower.contains(&k.to_lowercase()));
-                                if !has_keyword {
-                                    continue;
-                                }
-                            }
 
-                            let author = tweet.author_id.clone().unwrap_or_else(|| user_id.clone());
-                            let signal = RawSignal {
-                                source: "twitter".to_string(),
-                                source_id: tweet.id,
-                                content: tweet.text,
-                                author: author.clone(),
-                                author_trust: self.get_trust(&author),
-                                timestamp: Utc::now(),
-                                metadata: Some(serde_json::json!({
-                                    "user_id": user_id,
-                                    "created_at": tweet.created_at
-                                })),
+                            let Some(signal) = self.to_signal(user_id, tweet) else {
+                                continue;
                             };
 
                             if tx.send(signal).await.is_err() {
@@ -125,6 +149,12 @@ impl SignalSource for TwitterSource {
                             }
                         }
                     }
+                    Err(BotError::RateLimited { retry_after_secs }) => {
+                        tracing::warn!("Twitter rate limit reached, pausing for {}s", retry_after_secs);
+                        tokio::time::sleep(Duration::from_secs(retry_after_secs)).await;
+                        interval.reset();
+                        break;
+                    }
                     Err(e) => {
                         tracing::warn!("Failed to fetch tweets for {}: {}", user_id, e);
                     }
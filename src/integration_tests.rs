@@ -24,30 +24,48 @@ mod tests {
             polymarket: PolymarketConfig {
                 clob_url: "https://clob.polymarket.com".to_string(),
                 gamma_url: "https://gamma-api.polymarket.com".to_string(),
-                private_key: "test_key".to_string(),
+                private_key: "test_key".into(),
                 funder_address: None,
                 chain_id: 137,
                 signature_type: 0,
+                auto_approve: false,
+                rpc_url: "https://polygon-rpc.com".to_string(),
             },
             strategy: StrategyConfig::default(),
             risk: RiskConfig::default(),
             database: DatabaseConfig { path: "test.db".to_string() },
             llm: Some(LlmConfig {
                 provider: "deepseek".to_string(),
-                api_key: "sk-test".to_string(),
+                api_key: "sk-test".into(),
                 model: None,
                 base_url: None,
             }),
             telegram: Some(TelegramConfig {
-                bot_token: "123:abc".to_string(),
+                bot_token: "123:abc".into(),
                 chat_id: "12345".to_string(),
                 notify_signals: true,
                 notify_trades: true,
                 notify_errors: true,
                 notify_daily: true,
+                heartbeat_hours: 0,
             }),
+            webhooks: Vec::new(),
+            templates: Default::default(),
+            notify_throttle: None,
+            pnl_alerts: None,
+            exits: None,
             ingester: None,
             copy_trade: None,
+            paper: Default::default(),
+            metrics: None,
+            api: None,
+            logging: Default::default(),
+            fees: None,
+            throttle: Default::default(),
+            binance_model: None,
+            base_rate_model: None,
+            microstructure_model: None,
+            symbols: Default::default(),
         };
         
         assert_eq!(config.polymarket.chain_id, 137);
@@ -77,7 +95,7 @@ mod tests {
             id: "btc-up-down".to_string(),
             question: "Bitcoin Up or Down?".to_string(),
             description: Some("Hourly BTC direction".to_string()),
-            end_date: Some(Utc::now()),
+            end_date: Some(Utc::now() + chrono::Duration::hours(1)),
             volume: dec!(50000),
             liquidity: dec!(25000),
             outcomes: vec![
@@ -94,6 +112,10 @@ mod tests {
             ],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         };
         
         let prediction = Prediction {
@@ -124,9 +146,11 @@ mod tests {
             model_probability: dec!(0.70),
             market_probability: dec!(0.55),
             edge: dec!(0.15),
+            net_edge: dec!(0.15),
             confidence: dec!(0.80),
             suggested_size: dec!(0.05),
             timestamp: Utc::now(),
+            max_staleness: None,
         };
         
         // Convert signal to order
@@ -140,6 +164,7 @@ mod tests {
             price: signal.market_probability + dec!(0.01), // Limit price with buffer
             size: size_shares,
             order_type: OrderType::GTC,
+            client_order_id: None,
         };
         
         assert_eq!(order.side, Side::Buy);
@@ -154,6 +179,7 @@ mod tests {
             price: dec!(0.55),
             size: dec!(100),
             order_type: OrderType::GTC,
+            client_order_id: None,
         };
         
         // Simulate order filled
@@ -163,6 +189,7 @@ mod tests {
             filled_size: dec!(100),
             remaining_size: dec!(0),
             avg_price: Some(dec!(0.55)),
+            fee: Decimal::ZERO,
         };
         
         // Create trade record
@@ -176,6 +203,8 @@ mod tests {
             size: order_status.filled_size,
             fee: dec!(0.25),
             timestamp: Utc::now(),
+            client_order_id: None,
+            correlation_id: None,
         };
         
         assert_eq!(trade.size, dec!(100));
@@ -14,6 +14,8 @@
 //! ```
 
 pub mod analysis;
+#[cfg(feature = "api")]
+pub mod api;
 pub mod arbitrage;
 pub mod backtest;
 pub mod client;
@@ -25,6 +27,9 @@ pub mod execution_quality;
 pub mod executor;
 pub mod fusion;
 pub mod ingester;
+pub mod logging;
+#[cfg(feature = "metrics")]
+pub mod metrics;
 pub mod ml;
 pub mod model;
 pub mod monitor;
@@ -37,6 +42,7 @@ pub mod regime;
 pub mod risk;
 pub mod routing;
 pub mod scanner;
+pub mod shutdown;
 pub mod sentiment;
 pub mod signal_decay;
 pub mod stat_arb;
@@ -0,0 +1,108 @@
+//! Tracing subscriber setup
+//!
+//! Text mode is the usual human-readable output. JSON mode writes one object
+//! per line including the current span and its parents, so span fields such
+//! as `market_id` and `correlation_id` appear on every line logged inside
+//! them and a signal can be followed through to its order and fill.
+
+use crate::config::{LogFormat, LoggingConfig};
+use tracing::Subscriber;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::util::{SubscriberInitExt, TryInitError};
+use tracing_subscriber::{layer::SubscriberExt, EnvFilter, Layer, Registry};
+
+/// `RUST_LOG` if set, otherwise the configured level
+pub fn env_filter(config: &LoggingConfig) -> EnvFilter {
+    if std::env::var_os(EnvFilter::DEFAULT_ENV).is_some() {
+        return EnvFilter::from_default_env();
+    }
+    match &config.level {
+        Some(level) => EnvFilter::new(level),
+        None => EnvFilter::from_default_env(),
+    }
+}
+
+/// Build a subscriber writing to `writer` in the configured format
+pub fn subscriber<W>(config: &LoggingConfig, filter: EnvFilter, writer: W) -> impl Subscriber + Send + Sync
+where
+    W: for<'a> MakeWriter<'a> + Send + Sync + 'static,
+{
+    let fmt = match config.format {
+        LogFormat::Text => tracing_subscriber::fmt::layer().with_writer(writer).boxed(),
+        LogFormat::Json => tracing_subscriber::fmt::layer()
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .with_writer(writer)
+            .boxed(),
+    };
+    Registry::default().with(fmt).with(filter)
+}
+
+/// Install the global subscriber, logging to stdout
+pub fn init(config: &LoggingConfig) -> Result<(), TryInitError> {
+    subscriber(config, env_filter(config), std::io::stdout).try_init()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn capture(format: LogFormat, log: impl FnOnce()) -> String {
+        let buffer = Buffer::default();
+        let config = LoggingConfig { format, level: None };
+        let writer = buffer.clone();
+        let subscriber = subscriber(&config, EnvFilter::new("info"), move || writer.clone());
+        tracing::subscriber::with_default(subscriber, log);
+        let bytes = buffer.0.lock().unwrap().clone();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    #[test]
+    fn test_json_lines_include_span_fields() {
+        let output = capture(LogFormat::Json, || {
+            let span = tracing::info_span!("signal", market_id = "m1", correlation_id = "corr-1");
+            span.in_scope(|| {
+                tracing::info!(edge = 0.07, "Placing order");
+                tracing::debug!("filtered out");
+            });
+        });
+
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|l| serde_json::from_str(l).expect("each line is JSON"))
+            .collect();
+        assert_eq!(lines.len(), 1);
+
+        let line = &lines[0];
+        assert_eq!(line["level"], "INFO");
+        assert_eq!(line["fields"]["message"], "Placing order");
+        assert_eq!(line["fields"]["edge"], 0.07);
+        assert_eq!(line["span"]["name"], "signal");
+        assert_eq!(line["span"]["market_id"], "m1");
+        assert_eq!(line["span"]["correlation_id"], "corr-1");
+        assert_eq!(line["spans"][0]["correlation_id"], "corr-1");
+    }
+
+    #[test]
+    fn test_text_format_is_not_json() {
+        let output = capture(LogFormat::Text, || tracing::info!("hello"));
+        assert!(output.contains("hello"));
+        assert!(serde_json::from_str::<serde_json::Value>(output.trim()).is_err());
+    }
+}
@@ -5,19 +5,26 @@
 use chrono::Timelike;
 use clap::{Parser, Subcommand};
 use polymarket_bot::{
-    client::PolymarketClient,
-    config::Config,
-    executor::Executor,
+    client::{mock::ClobClientTrait, Allowance, ClobClient, GammaClient, OrderBookMaintainer, PolymarketClient},
+    config::{Config, ConfigWatcher, TelegramConfig},
+    data::SymbolMapper,
+    events::{
+        handlers::{market_data_event, ExecutionHandler, RiskHandler, SignalHandler},
+        EventEngine, EventEngineConfig, EventType, JsonlFileSink,
+    },
+    executor::{DeadMansSwitch, ExecutionMode, Executor, ExitManager, RiskAlertSink},
+    fusion::{self, FusionEngine},
     ingester::{
         processor::SignalProcessor,
         telegram::TelegramBotSource,
         twitter::{TwitterSource, TwitterRssSource},
         ParsedSignal, RawSignal, SignalSource,
     },
-    model::{EnsembleModel, LlmModel, ProbabilityModel},
-    monitor::Monitor,
-    notify::Notifier,
+    model::{BaseRateModel, BinanceMomentumModel, EnsembleModel, LlmModel, MicrostructureModel, ProbabilityModel},
+    monitor::{MarketStateConfig, MarketStateMonitor, Monitor, SettlementMonitor, DEFAULT_RESOLUTIONS_PER_POLL},
+    notify::{run_heartbeat, HeartbeatStatus, Notifier, NotifyThrottle, WebhookChannel},
     risk::RiskManager,
+    shutdown::graceful_shutdown,
     storage::Database,
     strategy::{
         SignalGenerator,
@@ -25,14 +32,32 @@ use polymarket_bot::{
         crypto_hf::{CryptoHfStrategy, CryptoPriceTracker},
         realtime::{RealtimeEngine, start_binance_feed},
         signal_filter::SignalFilter,
+        run_loop, run_scan, scan_filters, FilterChain, LoopPass, MarketSkipCache, ModelSignalProvider, RunLimits, ScanContext, SignalProvider,
+        TradeThrottle,
     },
     telegram::{TelegramBot, CommandHandler, BotCommand},
+    testing::{load_history, EnhancedDryRun, EnhancedDryRunConfig},
+    utils::{parse_duration, truncate_chars},
+};
+#[cfg(feature = "telegram-userbot")]
+use polymarket_bot::{
+    config::TelegramUserbotConfig,
+    ingester::{
+        mtproto::{sign_in_interactive, MtprotoConnector},
+        telegram::TelegramSource,
+        TelegramIngesterConfig,
+    },
 };
+use polymarket_bot::types::{Market, Signal};
 use rust_decimal::Decimal;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+/// Event-driven mode writes every event here, rotating by size
+const EVENT_LOG_PATH: &str = "logs/events.jsonl";
 
 #[derive(Parser)]
 #[command(name = "polymarket-bot")]
@@ -53,6 +78,15 @@ enum Commands {
         /// Dry run mode (no actual trades)
         #[arg(long)]
         dry_run: bool,
+        /// Exit after this many loop passes
+        #[arg(long)]
+        max_iterations: Option<u32>,
+        /// Exit after this long, e.g. 90s, 30m, 2h
+        #[arg(long, value_parser = parse_duration)]
+        duration: Option<Duration>,
+        /// React to WebSocket book updates instead of scanning on a timer
+        #[arg(long)]
+        event_driven: bool,
     },
     /// Show market data
     Markets {
@@ -71,45 +105,85 @@ enum Commands {
     Report,
     /// Test Telegram notification
     TestNotify,
+    /// Sign the Telegram userbot session in for the signal ingester
+    #[cfg(feature = "telegram-userbot")]
+    TelegramLogin {
+        /// Phone number in international format, e.g. +15551234567
+        phone: String,
+    },
+    /// Backtest the strategy on historical market snapshots
+    Backtest {
+        /// Historical data file (.csv or .jsonl)
+        data: PathBuf,
+        /// Simulator overrides, e.g. "initial_balance=500,simulate_failures=false"
+        #[arg(long)]
+        config_overrides: Option<String>,
+        /// Where to write the equity curve CSV
+        #[arg(long, default_value = "backtest_equity.csv")]
+        equity_out: PathBuf,
+    },
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
-    // Initialize logging
-    tracing_subscriber::registry()
-        .with(tracing_subscriber::fmt::layer())
-        .with(tracing_subscriber::EnvFilter::from_default_env())
-        .init();
-
     let cli = Cli::parse();
 
     // Load configuration
     let config = Config::load(&cli.config)?;
 
+    // Initialize logging
+    polymarket_bot::logging::init(&config.logging)?;
+
     match cli.command {
-        Commands::Run { dry_run } => run_bot(config, dry_run).await,
+        Commands::Run { dry_run, max_iterations, duration, event_driven } => {
+            let limits = RunLimits { max_iterations, duration };
+            if event_driven {
+                run_event_driven(config, dry_run, limits).await
+            } else {
+                run_bot(config, &cli.config, dry_run, limits).await
+            }
+        }
         Commands::Markets { limit } => show_markets(config, limit).await,
         Commands::Analyze { market_id } => analyze_market(config, &market_id).await,
         Commands::Status => show_status(config).await,
         Commands::Report => send_report(config).await,
         Commands::TestNotify => test_notify(config).await,
+        #[cfg(feature = "telegram-userbot")]
+        Commands::TelegramLogin { phone } => telegram_login(config, &phone).await,
+        Commands::Backtest { data, config_overrides, equity_out } => {
+            run_backtest(config, &data, config_overrides.as_deref(), &equity_out).await
+        }
     }
 }
 
-async fn run_bot(config: Config, dry_run: bool) -> anyhow::Result<()> {
+async fn run_bot(
+    config: Config,
+    config_path: &str,
+    dry_run: bool,
+    limits: RunLimits,
+) -> anyhow::Result<()> {
     tracing::info!("Starting Polymarket trading bot");
 
     if dry_run {
         tracing::warn!("Running in DRY RUN mode - no actual trades will be executed");
     }
 
-    // Initialize Telegram notifier
-    let notifier = if let Some(tg) = &config.telegram {
-        Notifier::new(tg.bot_token.clone(), tg.chat_id.clone())
+    // Initialize notifier: Telegram plus any webhooks
+    let mut notifier = if let Some(tg) = &config.telegram {
+        Notifier::new(tg.bot_token.expose().to_string(), tg.chat_id.clone())
     } else {
-        tracing::warn!("Telegram not configured, notifications disabled");
         Notifier::disabled()
-    };
+    }
+    .with_templates(config.templates.clone());
+    if let Some(limits) = &config.notify_throttle {
+        notifier = notifier.with_throttle(Arc::new(NotifyThrottle::new(limits.clone())));
+    }
+    for webhook in &config.webhooks {
+        notifier = notifier.with_channel(Arc::new(WebhookChannel::from_config(webhook)));
+    }
+    if !notifier.is_enabled() {
+        tracing::warn!("No Telegram or webhooks configured, notifications disabled");
+    }
 
     // Send startup notification
     if let Err(e) = notifier.startup(dry_run).await {
@@ -122,23 +196,34 @@ async fn run_bot(config: Config, dry_run: bool) -> anyhow::Result<()> {
     // Skip CLOB auth in dry-run mode (not needed for reading markets)
     if !dry_run {
         client.clob.initialize().await?;
+        ensure_collateral_allowance(&client.clob).await?;
     } else {
         tracing::info!("Skipping CLOB authentication in dry-run mode");
     }
 
     let db = Arc::new(Database::connect(&config.database.path).await?);
-    let monitor = Monitor::new(1000);
+    // Trading halts if the monitoring task misses about three scan intervals
+    let heartbeat = Arc::new(DeadMansSwitch::new(Duration::from_secs(
+        config.strategy.scan_interval_secs * 3 + 60,
+    )));
+    let mut monitor = Monitor::new(1000).with_dead_mans_switch(heartbeat.clone());
+    if let Some(pnl_alerts) = config.pnl_alerts.clone() {
+        monitor = monitor.with_pnl_alerts(pnl_alerts).with_risk_alerts(Arc::new(notifier.clone()));
+    }
+    let monitor = Arc::new(monitor);
+    // Sources credited with each trade, for attribution
+    let fusion = Arc::new(tokio::sync::RwLock::new(FusionEngine::new()));
 
     // Initialize command handler for Telegram
     let cmd_handler = Arc::new(CommandHandler::new(config.clone(), notifier.clone()));
 
     // Create command channel
-    let (cmd_tx, mut cmd_rx) = mpsc::channel::<BotCommand>(100);
+    let (cmd_tx, cmd_rx) = mpsc::channel::<BotCommand>(100);
 
     // Start Telegram command listener if configured
     if let Some(tg) = &config.telegram {
         let telegram_bot = Arc::new(TelegramBot::new(
-            tg.bot_token.clone(),
+            tg.bot_token.expose().to_string(),
             tg.chat_id.clone(),
             cmd_tx,
         ));
@@ -152,21 +237,33 @@ async fn run_bot(config: Config, dry_run: bool) -> anyhow::Result<()> {
     }
 
     // Initialize model
-    let mut model = EnsembleModel::new();
-    if let Some(llm_config) = &config.llm {
-        match LlmModel::from_config(llm_config) {
-            Ok(llm) => {
-                tracing::info!("LLM model initialized: {}", llm.name());
-                model.add_model(Box::new(llm), Decimal::new(70, 2)); // 70% weight
-            }
-            Err(e) => {
-                tracing::warn!("Failed to initialize LLM model: {}", e);
-            }
-        }
+    let model = build_model(&config, &db, &client.clob);
+
+    // Strategy and risk settings can be reloaded with SIGHUP
+    let config_watcher = Arc::new(ConfigWatcher::new(config_path, &config));
+    #[cfg(unix)]
+    if let Err(e) = config_watcher.clone().spawn_sighup() {
+        tracing::warn!("Config hot-reload unavailable: {}", e);
+    }
+    let applied_config_generation = config_watcher.generation();
+
+    // Kick the dead man's switch from its own task, on the reloadable scan interval
+    {
+        let monitor = monitor.clone();
+        let strategy = config_watcher.strategy();
+        let longest = heartbeat.timeout() / 3;
+        tokio::spawn(async move {
+            monitor
+                .kick_every(|| Duration::from_secs(strategy.load().scan_interval_secs).min(longest))
+                .await
+        });
     }
 
     // Initialize strategy
-    let signal_gen = SignalGenerator::new(config.strategy.clone(), config.risk.clone());
+    // Price momentum per market, fed by the scan, for the anti-chase gate
+    let market_state = Arc::new(MarketStateMonitor::new(MarketStateConfig::default()));
+    let signal_gen = SignalGenerator::with_shared_config(config_watcher.strategy(), config_watcher.risk())
+        .with_market_state(market_state.clone());
     let crypto_strategy = CryptoHfStrategy::default();
     let mut crypto_tracker = CryptoPriceTracker::new();
     let signal_filter = SignalFilter::new();
@@ -185,7 +282,9 @@ async fn run_bot(config: Config, dry_run: bool) -> anyhow::Result<()> {
 
     // Initialize real-time engine with WebSocket feed
     let (rt_signal_tx, _rt_signal_rx) = tokio::sync::mpsc::channel(100);
-    let realtime_engine = Arc::new(RealtimeEngine::new(rt_signal_tx));
+    let realtime_engine = Arc::new(
+        RealtimeEngine::new(rt_signal_tx).with_symbols(SymbolMapper::from_config(&config.symbols)),
+    );
     
     // Start Binance WebSocket feed in background
     let rt_engine_clone = realtime_engine.clone();
@@ -195,9 +294,128 @@ async fn run_bot(config: Config, dry_run: bool) -> anyhow::Result<()> {
         }
     });
     
-    let executor = Arc::new(Executor::new(client.clob.clone(), config.risk.clone()));
-    let tg_config = config.telegram.clone();
+    // Dry-run trades through a paper account so fills, fees and balance are
+    // simulated on the same execution path as live trading
+    if dry_run {
+        tracing::info!(
+            "Paper trading with ${:.2} virtual balance, {}% fee",
+            config.paper.starting_balance,
+            config.fee_model().rate(false) * Decimal::ONE_HUNDRED
+        );
+    }
+    let fees = config.fee_model();
+    let mode = Arc::new(ExecutionMode::from_config(client.clob.clone(), dry_run, &config.paper).with_fee_model(fees));
     let notifier = Arc::new(notifier);
+    // Cleared by POST /pause on the control API
+    let trading_enabled = Arc::new(AtomicBool::new(true));
+    // Halt on repeated CLOB failures and say so on Telegram
+    let executor = Arc::new(
+        Executor::new(mode, config.risk.clone())
+            .with_fee_model(fees)
+            .with_risk_alerts(notifier.clone())
+            .with_dead_mans_switch(&heartbeat)
+            .with_trading_switch(trading_enabled.clone())
+            .with_trade_throttle(Arc::new(TradeThrottle::new(config.throttle.clone()))),
+    );
+    {
+        let heartbeat = heartbeat.clone();
+        let alerts: Arc<dyn RiskAlertSink> = notifier.clone();
+        tokio::spawn(async move { heartbeat.watch(Some(alerts)).await });
+    }
+
+    // Take-profit / stop-loss on every position the scan opens
+    let exits = config
+        .exits
+        .clone()
+        .map(|exits| ExitManager::new(executor.clob.clone(), exits).with_monitor(monitor.clone()));
+
+    // Close out positions in resolved markets
+    {
+        let settlement = SettlementMonitor::new(client.gamma.clone())
+            .with_database(db.clone())
+            .with_notifier(notifier.clone());
+        let executor = executor.clone();
+        tokio::spawn(async move {
+            settlement.run(&executor.clob, Duration::from_secs(5 * 60)).await;
+        });
+    }
+    spawn_resolution_recorder(&config, &client.gamma, &db);
+
+    // Down-weight fusion sources that keep calling settled trades wrong
+    {
+        let db = db.clone();
+        let fusion = fusion.clone();
+        tokio::spawn(async move {
+            let mut learned_until = None;
+            let mut interval = tokio::time::interval(Duration::from_secs(15 * 60));
+            loop {
+                interval.tick().await;
+                match db.source_outcomes_since(learned_until).await {
+                    Ok((outcomes, latest)) => {
+                        if !outcomes.is_empty() {
+                            tracing::info!("Learning fusion source accuracy from {} outcomes", outcomes.len());
+                            fusion.write().await.ingest_outcomes(&outcomes);
+                        }
+                        learned_until = latest.or(learned_until);
+                    }
+                    Err(e) => tracing::warn!("Failed to load fusion source outcomes: {}", e),
+                }
+            }
+        });
+    }
+
+    #[cfg(feature = "metrics")]
+    if let Some(metrics) = &config.metrics {
+        let sources = Arc::new(polymarket_bot::metrics::MetricsSources {
+            monitor: Some(monitor.clone()),
+            clob: Some(executor.clob.clone()),
+            fusion: Some(fusion.clone()),
+            ..Default::default()
+        });
+        match format!("{}:{}", metrics.bind, metrics.port).parse() {
+            Ok(addr) => {
+                tokio::spawn(async move {
+                    if let Err(e) = polymarket_bot::metrics::start_metrics_server(sources, addr).await {
+                        tracing::error!("Metrics server error: {}", e);
+                    }
+                });
+            }
+            Err(e) => tracing::warn!("Invalid metrics address {}:{}: {}", metrics.bind, metrics.port, e),
+        }
+    }
+    #[cfg(not(feature = "metrics"))]
+    if config.metrics.is_some() {
+        tracing::warn!("[metrics] is configured but this build lacks the `metrics` feature");
+    }
+
+    // Markets from the latest scan, served by the control API
+    let scan_candidates = Arc::new(tokio::sync::RwLock::new(Vec::new()));
+    #[cfg(feature = "api")]
+    if let Some(api) = &config.api {
+        let state = Arc::new(polymarket_bot::api::ApiState {
+            executor: executor.clone(),
+            trading_enabled: trading_enabled.clone(),
+            risk_manager: Some(risk_manager.clone()),
+            candidates: scan_candidates.clone(),
+            db: Some(db.clone()),
+            token: api.token.clone(),
+        });
+        match format!("{}:{}", api.bind, api.port).parse() {
+            Ok(addr) => {
+                tokio::spawn(async move {
+                    if let Err(e) = polymarket_bot::api::start_api_server(state, addr).await {
+                        tracing::error!("Control API error: {}", e);
+                    }
+                });
+            }
+            Err(e) => tracing::warn!("Invalid API address {}:{}: {}", api.bind, api.port, e),
+        }
+    }
+    #[cfg(not(feature = "api"))]
+    if config.api.is_some() {
+        tracing::warn!("[api] is configured but this build lacks the `api` feature");
+    }
+    let tg_config = config.telegram.clone();
 
     tracing::info!("Bot initialized with real-time WebSocket feed...");
 
@@ -213,9 +431,29 @@ async fn run_bot(config: Config, dry_run: bool) -> anyhow::Result<()> {
             let (raw_tx, raw_rx) = mpsc::channel::<RawSignal>(500);
             
             // Start signal sources
+            #[cfg(feature = "telegram-userbot")]
+            if let Some(userbot) = &ingester_config.telegram_userbot {
+                let source = TelegramSource::new(
+                    userbot_ingester_config(userbot),
+                    ingester_config.author_trust.clone(),
+                    Arc::new(MtprotoConnector),
+                );
+                let tx = raw_tx.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = source.run(tx).await {
+                        tracing::error!("Telegram userbot source error: {}", e);
+                    }
+                });
+                tracing::info!("Telegram userbot source started");
+            }
+            #[cfg(not(feature = "telegram-userbot"))]
+            if ingester_config.telegram_userbot.is_some() {
+                tracing::warn!("[ingester.telegram_userbot] is configured but this build lacks the `telegram-userbot` feature");
+            }
+
             if let Some(tg_bot_config) = &ingester_config.telegram_bot {
                 let source = TelegramBotSource::new(
-                    tg_bot_config.bot_token.clone(),
+                    tg_bot_config.bot_token.expose().to_string(),
                     tg_bot_config.channels.clone(),
                 );
                 let tx = raw_tx.clone();
@@ -231,7 +469,7 @@ async fn run_bot(config: Config, dry_run: bool) -> anyhow::Result<()> {
                 if twitter_config.bearer_token.is_some() {
                     let source = TwitterSource::new(
                         polymarket_bot::ingester::TwitterIngesterConfig {
-                            bearer_token: twitter_config.bearer_token.clone(),
+                            bearer_token: twitter_config.bearer_token.as_ref().map(|t| t.expose().to_string()),
                             watch_users: twitter_config.user_ids.clone(),
                             keywords: twitter_config.keywords.clone(),
                         },
@@ -476,216 +714,535 @@ async fn run_bot(config: Config, dry_run: bool) -> anyhow::Result<()> {
         });
     }
 
-    // Main trading loop
-    loop {
+    // Spawn heartbeat task
+    let heartbeat_hours = tg_config.as_ref().map(|c| c.heartbeat_hours).unwrap_or(0);
+    if heartbeat_hours > 0 {
+        let notifier_clone = notifier.clone();
+        let db_clone = db.clone();
+        let client_clone = client.clone();
+
+        tokio::spawn(async move {
+            let every = Duration::from_secs(heartbeat_hours * 60 * 60);
+            run_heartbeat(&notifier_clone, every, || async {
+                HeartbeatStatus {
+                    balance: client_clone.clob.get_balance().await.unwrap_or(Decimal::ZERO),
+                    open_positions: client_clone.clob.get_positions().await.map(|p| p.len()).unwrap_or(0),
+                    trades_today: db_clone.get_daily_stats().await.map(|s| s.total_trades).unwrap_or(0),
+                }
+            })
+            .await;
+        });
+    }
+
+    let mut pass = LivePass {
+        config_watcher: &config_watcher,
+        applied_config_generation,
+        cmd_rx,
+        cmd_handler: &cmd_handler,
+        client: &client,
+        db: &db,
+        trading_enabled: &trading_enabled,
+        executor: &executor,
+        tg_config: tg_config.as_ref(),
+        notifier: &notifier,
+        signal_gen: &signal_gen,
+        monitor: &monitor,
+        risk_manager: &risk_manager,
+        crypto_tracker,
+        realtime_engine: &realtime_engine,
+        crypto_strategy: &crypto_strategy,
+        model: &model,
+        signal_filter: &signal_filter,
+        // Markets whose price and volume have not moved skip the model
+        skip_cache: MarketSkipCache::new(),
+        scan_filters: scan_filters(&config.strategy),
+        market_state: &market_state,
+        scan_candidates: &scan_candidates,
+        scan_concurrency: config.strategy.scan_concurrency,
+        exits: exits.as_ref(),
+        fusion: &fusion,
+        totals: RunTotals::default(),
+    };
+
+    // Ctrl-C or the --duration deadline interrupts whichever wait the loop
+    // is in, then we shut down
+    let summary = run_loop(&mut pass, limits, tokio::signal::ctrl_c()).await?;
+    let totals = pass.totals;
+    let stop_reason = summary.stop_reason;
+
+    tracing::info!("Shutting down ({}), cleaning up...", stop_reason);
+    if let Err(e) = risk_manager.lock().await.save_state() {
+        tracing::warn!("Failed to save risk state: {}", e);
+    }
+    let report = graceful_shutdown(&executor.clob, Some(&db), &*notifier, &stop_reason, dry_run).await;
+    if !report.errors.is_empty() {
+        tracing::warn!("Shutdown finished with errors: {:?}", report.errors);
+    }
+
+    println!("\n🏁 Run Summary\n");
+    println!("Stopped: {}", stop_reason);
+    println!("Iterations: {}", summary.iterations);
+    println!("Scans: {}", totals.scans);
+    println!("Elapsed: {}s", summary.elapsed.as_secs());
+    println!("Signals: {}", totals.signals);
+    println!("Trades: {}", totals.trades);
+    println!("Skipped unchanged: {}", totals.skipped);
+    println!("Abstained: {}", totals.abstained);
+    println!("Daily P&L: ${:.2}", risk_manager.lock().await.daily_pnl());
+    println!("Unrealized P&L: ${:.2}", monitor.unrealized_pnl().await);
+    println!("Max drawdown: {:.2}%", risk_manager.lock().await.max_drawdown() * Decimal::ONE_HUNDRED);
+    println!("Orders cancelled: {}", report.cancelled_orders);
+    if let Some(paper) = executor.clob.paper() {
+        let state = paper.state();
+        let state = state.read().unwrap();
+        println!(
+            "Paper balance: ${:.2} (fees ${:.2}, realized P&L ${:.2}, {} open positions)",
+            state.balance,
+            state.fees_paid,
+            state.realized_pnl,
+            state.positions.len()
+        );
+    }
+
+    Ok(())
+}
+
+/// Ensemble of the configured probability models
+fn build_model(config: &Config, db: &Arc<Database>, clob: &ClobClient) -> EnsembleModel {
+    let mut model = EnsembleModel::new();
+    if let Some(llm_config) = &config.llm {
+        match LlmModel::from_config(llm_config) {
+            Ok(llm) => {
+                tracing::info!("LLM model initialized: {}", llm.name());
+                model.add_model(Box::new(llm), Decimal::new(70, 2)); // 70% weight
+            }
+            Err(e) => {
+                tracing::warn!("Failed to initialize LLM model: {}", e);
+            }
+        }
+    }
+    if let Some(binance) = &config.binance_model {
+        tracing::info!("Binance momentum model enabled (weight {})", binance.weight);
+        let binance_model = BinanceMomentumModel::new()
+            .with_kline_limit(binance.kline_limit)
+            .with_symbols(SymbolMapper::from_config(&config.symbols));
+        model.add_model(Box::new(binance_model), binance.weight);
+    }
+    if let Some(base_rate) = &config.base_rate_model {
+        tracing::info!("Base rate model enabled (weight {})", base_rate.weight);
+        let base_rate_model = BaseRateModel::with_config(db.clone(), base_rate.model_config());
+        model.add_model(Box::new(base_rate_model), base_rate.weight);
+    }
+    if let Some(micro) = &config.microstructure_model {
+        tracing::info!("Microstructure model enabled (weight {})", micro.weight);
+        let micro_model = MicrostructureModel::with_config(clob.clone(), micro.model_config());
+        model.add_model(Box::new(micro_model), micro.weight);
+    }
+    model
+}
+
+/// Record how recently closed markets resolved, every half hour, in live
+/// and dry-run alike, so base rates cover markets the bot never held
+fn spawn_resolution_recorder(config: &Config, gamma: &GammaClient, db: &Arc<Database>) {
+    let limit = config
+        .base_rate_model
+        .as_ref()
+        .map_or(DEFAULT_RESOLUTIONS_PER_POLL, |b| b.resolutions_per_poll);
+    let resolutions = SettlementMonitor::new(gamma.clone()).with_database(db.clone());
+    tokio::spawn(async move {
+        resolutions.run_resolutions(Duration::from_secs(30 * 60), limit).await;
+    });
+}
+
+/// Refuse to trade live unless the exchange may spend the whole balance
+async fn ensure_collateral_allowance(clob: &ClobClient) -> anyhow::Result<()> {
+    let balance = clob.get_balance().await?;
+    let approvals = clob
+        .ensure_allowance(balance)
+        .await
+        .map_err(|e| anyhow::anyhow!("Cannot trade live: {}", e))?;
+    if !approvals.is_empty() {
+        tracing::info!("Sent {} USDC approval(s): {}", approvals.len(), approvals.join(", "));
+    }
+    Ok(())
+}
+
+/// Event-driven trading: book updates from the market WebSocket flow through
+/// the signal, risk and execution handlers as they arrive
+///
+/// `--max-iterations` counts price updates rather than scans.
+async fn run_event_driven(config: Config, dry_run: bool, limits: RunLimits) -> anyhow::Result<()> {
+    tracing::info!("Starting Polymarket trading bot (event-driven)");
+    if dry_run {
+        tracing::warn!("Running in DRY RUN mode - no actual trades will be executed");
+    }
+
+    let client = PolymarketClient::new(config.polymarket.clone()).await?;
+    if !dry_run {
+        client.clob.initialize().await?;
+        ensure_collateral_allowance(&client.clob).await?;
+    }
+    let db = Arc::new(Database::connect(&config.database.path).await?);
+    spawn_resolution_recorder(&config, &client.gamma, &db);
+
+    let model = build_model(&config, &db, &client.clob);
+
+    let fees = config.fee_model();
+    let mode = Arc::new(ExecutionMode::from_config(client.clob.clone(), dry_run, &config.paper).with_fee_model(fees));
+    // Local books rebuilt from the market stream, for pricing and liquidity
+    let books = Arc::new(tokio::sync::RwLock::new(OrderBookMaintainer::new()));
+    let executor = Arc::new(
+        Executor::new(mode, config.risk.clone())
+            .with_fee_model(fees)
+            .with_trade_throttle(Arc::new(TradeThrottle::new(config.throttle.clone())))
+            .with_order_books(books.clone()),
+    );
+    let signal_gen = Arc::new(SignalGenerator::new(config.strategy.clone(), config.risk.clone()));
+    let risk_manager = Arc::new(tokio::sync::Mutex::new(RiskManager::new(config.risk.clone())));
+    let balance = executor.clob.get_balance().await?;
+    signal_gen.set_bankroll(balance);
+    // The risk handler sizes orders against the marked portfolio value
+    risk_manager.lock().await.mark_to_market(balance);
+
+    let mut engine = EventEngine::new(EventEngineConfig::default());
+    // Keep the event chains on disk for offline analysis and replay
+    match JsonlFileSink::open(EVENT_LOG_PATH) {
+        Ok(sink) => engine = engine.with_sink(Arc::new(sink)),
+        Err(e) => tracing::warn!("Event log {} disabled: {}", EVENT_LOG_PATH, e),
+    }
+    let clock = engine.clock();
+
+    let markets = client.gamma.get_top_markets(20).await?;
+    let signal_handler = Arc::new(
+        SignalHandler::new(Arc::new(model), signal_gen.clone(), markets.clone()).with_clock(clock.clone()),
+    );
+    let token_ids = signal_handler.token_ids().await;
+    if token_ids.is_empty() {
+        anyhow::bail!("No markets with a Yes token to subscribe to");
+    }
+
+    engine.register_handler(signal_handler).await;
+    engine
+        .register_handler(Arc::new(
+            RiskHandler::new(risk_manager.clone(), Arc::new(SignalFilter::new())).with_clock(clock.clone()),
+        ))
+        .await;
+    let mut execution = ExecutionHandler::new(executor.clone(), risk_manager.clone())
+        .with_database(db.clone())
+        .with_markets(markets)
+        .with_clock(clock.clone());
+    if let Some(exits) = config.exits.clone() {
+        execution = execution.with_exits(Arc::new(ExitManager::new(executor.clob.clone(), exits)));
+    }
+    engine.register_handler(Arc::new(execution)).await;
+
+    tracing::info!("Subscribing to {} markets", token_ids.len());
+    let mut stream = client.market_stream(token_ids).await?.with_books(books.clone());
+
+    let shutdown = tokio::signal::ctrl_c();
+    tokio::pin!(shutdown);
+    let started = std::time::Instant::now();
+    let deadline = limits.duration.map(|d| tokio::time::Instant::now() + d);
+    let mut balance_refresh = tokio::time::interval(Duration::from_secs(60));
+    let mut updates = 0u32;
+    let mut total_signals = 0usize;
+    let mut total_trades = 0usize;
+
+    let stop_reason = loop {
+        let until = deadline.unwrap_or_else(|| tokio::time::Instant::now() + Duration::from_secs(3600));
+        let update = tokio::select! {
+            update = stream.recv() => update,
+            _ = balance_refresh.tick() => {
+                match executor.clob.get_balance().await {
+                    Ok(balance) => {
+                        signal_gen.set_bankroll(balance);
+                        risk_manager.lock().await.mark_to_market(balance);
+                    }
+                    Err(e) => tracing::warn!("Failed to refresh balance: {}", e),
+                }
+                continue;
+            }
+            _ = tokio::time::sleep_until(until) => {
+                if deadline.is_some() {
+                    break "Run duration elapsed".to_string();
+                }
+                continue;
+            }
+            _ = &mut shutdown => break "Ctrl-C".to_string(),
+        };
+        let Some(update) = update else {
+            break "Market stream closed".to_string();
+        };
+
+        // A gap dropped the book; rebuild it from a REST snapshot
+        if !books.read().await.pending_resyncs().is_empty() {
+            if let Err(e) = books.write().await.resync(executor.clob.as_ref()).await {
+                tracing::warn!("Order book resync failed: {}", e);
+            }
+        }
+
+        let book = books.read().await.book(&update.token_id);
+        let derived = engine.publish_cascade(market_data_event(&update, book.as_ref(), clock.as_ref())).await?;
+        total_signals += derived
+            .iter()
+            .filter(|e| matches!(e.event_type, EventType::Signal))
+            .count();
+        total_trades += derived.iter().filter(|e| matches!(e.event_type, EventType::Fill)).count();
+
+        updates += 1;
+        if let Some(reason) = limits.reached(updates, started.elapsed()) {
+            break reason;
+        }
+    };
+
+    tracing::info!("Shutting down ({}), cleaning up...", stop_reason);
+    if let Err(e) = risk_manager.lock().await.save_state() {
+        tracing::warn!("Failed to save risk state: {}", e);
+    }
+    let report = graceful_shutdown(&executor.clob, Some(&db), &Notifier::disabled(), &stop_reason, dry_run).await;
+    if !report.errors.is_empty() {
+        tracing::warn!("Shutdown finished with errors: {:?}", report.errors);
+    }
+
+    println!("\n🏁 Run Summary\n");
+    println!("Stopped: {}", stop_reason);
+    println!("Price updates: {}", updates);
+    println!("Elapsed: {}s", started.elapsed().as_secs());
+    println!("Signals: {}", total_signals);
+    println!("Trades: {}", total_trades);
+    println!("Daily P&L: ${:.2}", risk_manager.lock().await.daily_pnl());
+
+    Ok(())
+}
+
+/// Running counts for the run summary
+#[derive(Debug, Clone, Copy, Default)]
+struct RunTotals {
+    scans: usize,
+    signals: usize,
+    trades: usize,
+    skipped: usize,
+    abstained: usize,
+}
+
+/// Everything one pass of the live trading loop touches
+struct LivePass<'a> {
+    config_watcher: &'a ConfigWatcher,
+    applied_config_generation: u64,
+    cmd_rx: mpsc::Receiver<BotCommand>,
+    cmd_handler: &'a CommandHandler,
+    client: &'a PolymarketClient,
+    db: &'a Database,
+    trading_enabled: &'a AtomicBool,
+    executor: &'a Executor<Arc<ExecutionMode>>,
+    tg_config: Option<&'a TelegramConfig>,
+    notifier: &'a Notifier,
+    signal_gen: &'a SignalGenerator,
+    monitor: &'a Monitor,
+    risk_manager: &'a tokio::sync::Mutex<RiskManager>,
+    crypto_tracker: CryptoPriceTracker,
+    realtime_engine: &'a RealtimeEngine,
+    crypto_strategy: &'a CryptoHfStrategy,
+    model: &'a EnsembleModel,
+    signal_filter: &'a SignalFilter,
+    skip_cache: MarketSkipCache,
+    scan_filters: FilterChain,
+    market_state: &'a MarketStateMonitor,
+    scan_candidates: &'a tokio::sync::RwLock<Vec<Market>>,
+    scan_concurrency: usize,
+    exits: Option<&'a ExitManager>,
+    fusion: &'a tokio::sync::RwLock<FusionEngine>,
+    totals: RunTotals,
+}
+
+impl LivePass<'_> {
+    fn notify_errors(&self) -> bool {
+        self.tg_config.map(|c| c.notify_errors).unwrap_or(false)
+    }
+}
+
+#[async_trait::async_trait(?Send)]
+impl LoopPass for LivePass<'_> {
+    async fn run_pass(&mut self) -> polymarket_bot::error::Result<Duration> {
+        // Pick up reloaded risk limits
+        if self.config_watcher.generation() != self.applied_config_generation {
+            self.applied_config_generation = self.config_watcher.generation();
+            let risk = self.config_watcher.risk().load_full();
+            self.risk_manager.lock().await.apply_config((*risk).clone());
+            // New thresholds can change the verdict on unchanged markets
+            self.skip_cache.clear();
+            self.scan_filters = scan_filters(&self.config_watcher.strategy().load());
+        }
+        let scan_interval = Duration::from_secs(self.config_watcher.strategy().load().scan_interval_secs);
+
         // Process any pending Telegram commands
-        while let Ok(cmd) = cmd_rx.try_recv() {
-            cmd_handler.handle(cmd, &client, &db).await;
+        while let Ok(cmd) = self.cmd_rx.try_recv() {
+            self.cmd_handler.handle(cmd, self.client, self.db).await;
         }
 
-        // Check if trading is paused
-        if cmd_handler.is_paused().await {
+        // Check if trading is paused (Telegram /pause or the control API)
+        if self.cmd_handler.is_paused().await || !self.trading_enabled.load(Ordering::SeqCst) {
             tracing::info!("Trading paused, waiting...");
-            tokio::time::sleep(Duration::from_secs(10)).await;
-            continue;
+            return Ok(Duration::from_secs(10));
         }
 
-        // Get portfolio value (use simulated balance in dry-run mode)
-        let balance = if dry_run {
-            Decimal::new(1000, 0)  // $1000 simulated balance
-        } else {
-            match executor.clob.get_balance().await {
-                Ok(b) => b,
-                Err(e) => {
-                    tracing::error!("Failed to get balance: {}", e);
-                    if tg_config.as_ref().map(|c| c.notify_errors).unwrap_or(false) {
-                        let _ = notifier.error("Balance fetch", &e.to_string()).await;
-                    }
-                    tokio::time::sleep(Duration::from_secs(60)).await;
-                    continue;
+        // Get portfolio value (the paper account's balance in dry-run mode)
+        let balance = match self.executor.clob.get_balance().await {
+            Ok(b) => b,
+            Err(e) => {
+                tracing::error!("Failed to get balance: {}", e);
+                if self.notify_errors() {
+                    let _ = self.notifier.error("Balance fetch", &e).await;
                 }
+                return Ok(Duration::from_secs(60));
             }
         };
 
         tracing::info!("Current balance: ${:.2}", balance);
+        self.signal_gen.set_bankroll(balance);
+
+        // Mark-to-market value drives the daily loss limit and drawdown
+        let portfolio_value = self.monitor.portfolio_value(balance).await;
+        self.risk_manager.lock().await.mark_to_market(portfolio_value);
+
+        // Update crypto prices for HF strategy
+        if let Err(e) = self.crypto_tracker.update_prices().await {
+            tracing::debug!("Failed to update crypto prices: {}", e);
+        }
 
-        // Get top markets + crypto markets
-        let mut markets = match client.gamma.get_top_markets(20).await {
-            Ok(m) => m,
+        // Crypto markets use real-time WebSocket data, everything else the LLM
+        let signals = LiveSignalProvider {
+            realtime_engine: self.realtime_engine,
+            crypto_strategy: self.crypto_strategy,
+            crypto_tracker: &self.crypto_tracker,
+            model: ModelSignalProvider { model: self.model, signal_gen: self.signal_gen },
+        };
+        let scan = ScanContext {
+            gamma: &self.client.gamma,
+            executor: self.executor,
+            signals: &signals,
+            risk_manager: self.risk_manager,
+            signal_filter: self.signal_filter,
+            filters: &self.scan_filters,
+            skip_cache: Some(&self.skip_cache),
+            market_state: Some(self.market_state),
+            concurrency: self.scan_concurrency,
+            fusion: Some(self.fusion),
+        };
+
+        let report = match run_scan(&scan, balance).await {
+            Ok(r) => r,
             Err(e) => {
                 tracing::error!("Failed to get markets: {}", e);
-                if tg_config.as_ref().map(|c| c.notify_errors).unwrap_or(false) {
-                    let _ = notifier.error("Market fetch", &e.to_string()).await;
+                if self.notify_errors() {
+                    let _ = self.notifier.error("Market fetch", &e).await;
                 }
-                tokio::time::sleep(Duration::from_secs(60)).await;
-                continue;
+                return Ok(Duration::from_secs(60));
             }
         };
+        self.totals.scans += 1;
+        self.totals.signals += report.signals.len();
+        self.totals.trades += report.trades.len();
+        self.totals.skipped += report.skipped_unchanged;
+        self.totals.abstained += report.abstentions.len();
+        *self.scan_candidates.write().await = report.markets.clone();
 
-        // Also fetch crypto markets (BTC/ETH Up/Down)
-        match client.gamma.get_crypto_markets().await {
-            Ok(crypto_markets) => {
-                tracing::info!("Found {} crypto markets", crypto_markets.len());
-                markets.extend(crypto_markets);
+        // Net Yes and No fills per market and re-mark open positions at the
+        // latest prices
+        for market in &report.markets {
+            self.monitor.register_market(market).await;
+            for outcome in &market.outcomes {
+                self.monitor.update_mark(&outcome.token_id, outcome.price).await;
             }
-            Err(e) => {
-                tracing::warn!("Failed to fetch crypto markets: {}", e);
+        }
+
+        if let Some(reason) = &report.blocked {
+            self.monitor.log_stats().await;
+            if self.notify_errors() {
+                let _ = self.notifier.send(&format!("⚠️ Trading paused: {}", reason)).await;
             }
+            return Ok(scan_interval);
         }
 
-        // Update crypto prices for HF strategy
-        if let Err(e) = crypto_tracker.update_prices().await {
-            tracing::debug!("Failed to update crypto prices: {}", e);
+        let question = |market_id: &str| {
+            report.market(market_id).map(|m| m.question.clone()).unwrap_or_default()
+        };
+
+        // Send signal notifications
+        if self.tg_config.map(|c| c.notify_signals).unwrap_or(false) {
+            for signal in &report.signals {
+                let _ = self.notifier.signal_found(signal, &question(&signal.market_id)).await;
+            }
         }
 
-        tracing::info!("Scanning {} markets...", markets.len());
-        
-        // Check risk limits before trading
-        {
-            let rm = risk_manager.lock().await;
-            match rm.can_trade() {
-                polymarket_bot::risk::RiskCheckResult::Blocked { reason } => {
-                    tracing::warn!("⚠️ Trading blocked: {}", reason);
-                    if tg_config.as_ref().map(|c| c.notify_errors).unwrap_or(false) {
-                        let _ = notifier.send(&format!("⚠️ Trading paused: {}", reason)).await;
-                    }
-                    tokio::time::sleep(Duration::from_secs(config.strategy.scan_interval_secs)).await;
-                    continue;
+        for trade in &report.trades {
+            self.db.save_trade(trade).await?;
+            if let Some(sources) = report.sources_for(&trade.id) {
+                if let Err(e) = self.db.save_trade_sources(&trade.id, sources).await {
+                    tracing::warn!("Failed to save sources of trade {}: {}", trade.id, e);
                 }
-                polymarket_bot::risk::RiskCheckResult::Allowed => {}
             }
-        }
+            self.monitor.record_fill(trade).await;
+            if let Some(exits) = self.exits {
+                exits.watch(trade);
+            }
+            let _ = self.cmd_handler.check_risk_limits(Decimal::ZERO).await;
 
-        // Analyze each market
-        for market in &markets {
-            // Check if this is a crypto Up/Down market
-            let is_crypto_market = CryptoHfStrategy::is_crypto_hf_market(market).is_some();
-            
-            // Update volatility data for risk manager
-            if let Some(price) = market.yes_price() {
-                let mut rm = risk_manager.lock().await;
-                rm.update_volatility(&market.id, price);
-                rm.update_correlation(&market.id, price, chrono::Utc::now().timestamp());
+            // Send trade notification
+            if self.tg_config.map(|c| c.notify_trades).unwrap_or(false) {
+                let _ = self.notifier.trade_executed(trade, &question(&trade.market_id)).await;
             }
-            
-            // Skip low liquidity markets (lower threshold for crypto markets)
-            let min_liquidity = if is_crypto_market {
-                Decimal::new(1000, 0)  // $1,000 for crypto markets
-            } else {
-                Decimal::new(10000, 0) // $10,000 for regular markets
-            };
-            
-            if market.liquidity < min_liquidity {
-                continue;
+        }
+
+        if self.notify_errors() {
+            for (_, e) in &report.failures {
+                let _ = self.notifier.error("Trade execution", e).await;
             }
+        }
 
-            // Generate signal: use real-time engine for crypto markets, LLM for others
-            let signal = if is_crypto_market {
-                // Use real-time WebSocket data for crypto markets
-                realtime_engine.generate_signal(market).await
-                    .or_else(|| crypto_strategy.generate_signal(market, &crypto_tracker))
-            } else {
-                // Use LLM prediction for regular markets
-                let prediction = match model.predict(market).await {
-                    Ok(p) => p,
-                    Err(e) => {
-                        tracing::debug!("Model failed for {}: {}", market.id, e);
-                        continue;
-                    }
-                };
-                signal_gen.generate(market, &prediction)
-            };
-
-            if let Some(signal) = signal {
-                // Apply signal filter with dynamic cooldown
-                // Crypto markets: 2 min cooldown (fast trading)
-                // Other markets: 15 min cooldown
-                if !signal_filter.deduplicator.can_trade_dynamic(&market.id, is_crypto_market) {
-                    tracing::debug!("Skipping {} - cooldown active", market.id);
-                    continue;
-                }
-                
-                // Mark as traded to prevent duplicates
-                signal_filter.deduplicator.mark_traded(&market.id);
-                
-                tracing::info!(
-                    "Signal: {} {} | Model: {:.1}% vs Market: {:.1}% | Edge: {:.1}%",
-                    match signal.side {
-                        polymarket_bot::types::Side::Buy => "BUY",
-                        polymarket_bot::types::Side::Sell => "SELL",
-                    },
-                    market.question,
-                    signal.model_probability * Decimal::ONE_HUNDRED,
-                    signal.market_probability * Decimal::ONE_HUNDRED,
-                    signal.edge * Decimal::ONE_HUNDRED
-                );
+        // Log stats periodically
+        self.monitor.log_stats().await;
+        self.notifier.flush().await;
 
-                // Send signal notification
-                if tg_config.as_ref().map(|c| c.notify_signals).unwrap_or(false) {
-                    let _ = notifier.signal_found(&signal, &market.question).await;
-                }
+        tracing::info!("Sleeping for {} seconds...", scan_interval.as_secs());
+        Ok(scan_interval)
+    }
+}
 
-                if dry_run {
-                    // Simulate trade in dry-run mode
-                    let sim_size = signal.suggested_size * balance;
-                    let potential_profit = sim_size * signal.edge;
-                    tracing::info!(
-                        "📝 SIMULATED: Would {} ${:.2} on {} @ {:.1}% (potential: ${:.2})",
-                        match signal.side {
-                            polymarket_bot::types::Side::Buy => "BUY",
-                            polymarket_bot::types::Side::Sell => "SELL",
-                        },
-                        sim_size,
-                        market.question.chars().take(40).collect::<String>(),
-                        signal.market_probability * Decimal::ONE_HUNDRED,
-                        potential_profit
-                    );
-                } else {
-                    match executor.execute(&signal, balance).await {
-                        Ok(Some(trade)) => {
-                            tracing::info!("Trade executed: {}", trade.id);
-                            db.save_trade(&trade).await?;
-
-                            // Update PnL tracking for risk management
-                            {
-                                let mut rm = risk_manager.lock().await;
-                                rm.pnl_tracker.set_starting_balance(balance);
-                                // Record trade fee as immediate cost (actual P&L comes later on close)
-                                rm.record_trade(-trade.fee);
-                                tracing::debug!(
-                                    "Risk: Daily P&L = ${:.2}, remaining budget = ${:.2}",
-                                    rm.daily_pnl(),
-                                    rm.pnl_tracker.remaining_loss_budget().unwrap_or(Decimal::ZERO)
-                                );
-                            }
-                            let _ = cmd_handler.check_risk_limits(Decimal::ZERO).await;
+/// Signal routing for the live loop: real-time and HF strategies for crypto
+/// Up/Down markets, the LLM ensemble for everything else
+struct LiveSignalProvider<'a> {
+    realtime_engine: &'a RealtimeEngine,
+    crypto_strategy: &'a CryptoHfStrategy,
+    crypto_tracker: &'a CryptoPriceTracker,
+    model: ModelSignalProvider<'a>,
+}
 
-                            // Send trade notification
-                            if tg_config.as_ref().map(|c| c.notify_trades).unwrap_or(false) {
-                                let _ = notifier.trade_executed(&trade, &market.question).await;
-                            }
-                        }
-                        Ok(None) => {}
-                        Err(e) => {
-                            tracing::error!("Execution failed: {}", e);
-                            if tg_config.as_ref().map(|c| c.notify_errors).unwrap_or(false) {
-                                let _ = notifier.error("Trade execution", &e.to_string()).await;
-                            }
-                        }
-                    }
-                }
-            }
+#[async_trait::async_trait]
+impl SignalProvider for LiveSignalProvider<'_> {
+    async fn signal_for(&self, market: &Market) -> Option<Signal> {
+        if CryptoHfStrategy::is_crypto_hf_market(market).is_some() {
+            self.realtime_engine.generate_signal(market).await
+                .or_else(|| self.crypto_strategy.generate_signal(market, self.crypto_tracker))
+        } else {
+            self.model.signal_for(market).await
         }
+    }
 
-        // Log stats periodically
-        monitor.log_stats().await;
+    fn exclusion(&self, market: &Market) -> Option<String> {
+        self.model.exclusion(market)
+    }
 
-        // Wait before next scan
-        tracing::info!(
-            "Sleeping for {} seconds...",
-            config.strategy.scan_interval_secs
-        );
-        tokio::time::sleep(Duration::from_secs(config.strategy.scan_interval_secs)).await;
+    fn abstention(&self, signal: &Signal) -> Option<String> {
+        self.model.abstention(signal)
+    }
+
+    fn source(&self, market: &Market) -> Option<fusion::SignalSource> {
+        if CryptoHfStrategy::is_crypto_hf_market(market).is_some() {
+            Some(fusion::SignalSource::TechnicalAnalysis)
+        } else {
+            self.model.source(market)
+        }
     }
 }
 
@@ -701,11 +1258,7 @@ async fn show_markets(config: Config, limit: usize) -> anyhow::Result<()> {
         let yes = market.yes_price().unwrap_or(Decimal::ZERO);
         let no = market.no_price().unwrap_or(Decimal::ZERO);
 
-        let question = if market.question.len() > 47 {
-            format!("{}...", &market.question[..47])
-        } else {
-            market.question.clone()
-        };
+        let question = truncate_chars(&market.question, 47);
 
         println!(
             "{:<50} {:>7.0}% {:>7.0}% ${:>10.0}",
@@ -770,6 +1323,7 @@ async fn show_status(config: Config) -> anyhow::Result<()> {
     let open_orders = client.clob.get_open_orders().await?;
 
     println!("\n💰 Account Status\n");
+    println!("Address: {}", client.trading_address());
     println!("Balance: ${:.2} USDC", balance);
     println!("Open Orders: {}", open_orders.len());
 
@@ -790,7 +1344,8 @@ async fn send_report(config: Config) -> anyhow::Result<()> {
     let tg_config = config.telegram.as_ref()
         .ok_or_else(|| anyhow::anyhow!("Telegram not configured in config.toml"))?;
     
-    let notifier = Notifier::new(tg_config.bot_token.clone(), tg_config.chat_id.clone());
+    let notifier = Notifier::new(tg_config.bot_token.expose().to_string(), tg_config.chat_id.clone())
+        .with_templates(config.templates.clone());
     
     // Get account status
     let client = PolymarketClient::new(config.polymarket).await?;
@@ -808,14 +1363,94 @@ async fn send_report(config: Config) -> anyhow::Result<()> {
     Ok(())
 }
 
+async fn run_backtest(
+    config: Config,
+    data: &std::path::Path,
+    config_overrides: Option<&str>,
+    equity_out: &std::path::Path,
+) -> anyhow::Result<()> {
+    let snapshots = load_history(data)?;
+    if snapshots.is_empty() {
+        anyhow::bail!("No snapshots in {}", data.display());
+    }
+    println!("\n📼 Backtesting {} snapshots from {}\n", snapshots.len(), data.display());
+
+    // Replay the whole file unless a `steps` override shortens it
+    let mut sim_config = EnhancedDryRunConfig { steps: u32::MAX, ..Default::default() };
+    if let Some(overrides) = config_overrides {
+        sim_config.apply_overrides(overrides)?;
+    }
+
+    let mut sim = EnhancedDryRun::new(sim_config)
+        .with_strategy(config.strategy, config.risk)
+        .with_history(snapshots);
+    let result = sim.run().await?;
+
+    println!("{}", sim.generate_report(&result));
+
+    result.write_equity_curve(equity_out)?;
+    println!("Equity curve written to {}", equity_out.display());
+
+    Ok(())
+}
+
 async fn test_notify(config: Config) -> anyhow::Result<()> {
     let tg_config = config.telegram.as_ref()
         .ok_or_else(|| anyhow::anyhow!("Telegram not configured in config.toml"))?;
     
-    let notifier = Notifier::new(tg_config.bot_token.clone(), tg_config.chat_id.clone());
+    let notifier = Notifier::new(tg_config.bot_token.expose().to_string(), tg_config.chat_id.clone());
     
     notifier.send("🧪 <b>Test Notification</b>\n\nIf you see this, Telegram integration is working!").await?;
     
     println!("✅ Test notification sent!");
     Ok(())
 }
+
+/// Ingester settings for the userbot session
+#[cfg(feature = "telegram-userbot")]
+fn userbot_ingester_config(userbot: &TelegramUserbotConfig) -> TelegramIngesterConfig {
+    TelegramIngesterConfig {
+        api_id: userbot.api_id,
+        api_hash: userbot.api_hash.expose().to_string(),
+        session_file: userbot.session_file.clone(),
+        watch_chats: userbot.watch_chats.clone(),
+    }
+}
+
+#[cfg(feature = "telegram-userbot")]
+async fn telegram_login(config: Config, phone: &str) -> anyhow::Result<()> {
+    let Some(userbot) = config.ingester.as_ref().and_then(|i| i.telegram_userbot.as_ref()) else {
+        anyhow::bail!("[ingester.telegram_userbot] is not configured");
+    };
+    let settings = userbot_ingester_config(userbot);
+    sign_in_interactive(&settings, phone).await?;
+    println!("✅ Telegram session saved to {}", settings.session_file);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_accepts_bounds() {
+        let cli = Cli::try_parse_from([
+            "polymarket-bot", "run", "--dry-run", "--max-iterations", "2", "--duration", "30m",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Commands::Run { dry_run, max_iterations, duration, .. } => {
+                assert!(dry_run);
+                assert_eq!(max_iterations, Some(2));
+                assert_eq!(duration, Some(Duration::from_secs(1800)));
+            }
+            _ => panic!("expected Run"),
+        }
+    }
+
+    #[test]
+    fn test_run_rejects_bad_duration() {
+        assert!(Cli::try_parse_from(["polymarket-bot", "run", "--duration", "soon"]).is_err());
+    }
+}
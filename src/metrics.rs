@@ -0,0 +1,208 @@
+//! Prometheus metrics endpoint
+//!
+//! Serves `/metrics` in the Prometheus text format. Every scrape reads the
+//! live components through shared handles, so there is no separate metrics
+//! state to keep in sync. Components that are not running are left as
+//! `None` and their metrics are omitted.
+//!
+//! Only built with the `metrics` feature.
+
+use crate::client::mock::ClobClientTrait;
+use crate::events::EventEngine;
+use crate::fusion::FusionEngine;
+use crate::monitor::Monitor;
+use axum::{extract::State, http::header, response::IntoResponse, routing::get, Router};
+use rust_decimal::Decimal;
+use std::fmt::Write;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+
+/// Live components read on every scrape
+#[derive(Default, Clone)]
+pub struct MetricsSources {
+    pub monitor: Option<Arc<Monitor>>,
+    pub events: Option<Arc<EventEngine>>,
+    /// Balance and open positions
+    pub clob: Option<Arc<dyn ClobClientTrait>>,
+    pub fusion: Option<Arc<RwLock<FusionEngine>>>,
+}
+
+/// Prometheus text exposition builder
+#[derive(Default)]
+struct Exposition {
+    out: String,
+}
+
+impl Exposition {
+    fn header(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.out, "# HELP {} {}", name, help);
+        let _ = writeln!(self.out, "# TYPE {} {}", name, kind);
+    }
+
+    fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: impl std::fmt::Display) {
+        if labels.is_empty() {
+            let _ = writeln!(self.out, "{} {}", name, value);
+            return;
+        }
+        let labels: Vec<String> = labels
+            .iter()
+            .map(|(k, v)| format!("{}=\"{}\"", k, v.replace('\\', "\\\\").replace('"', "\\\"")))
+            .collect();
+        let _ = writeln!(self.out, "{}{{{}}} {}", name, labels.join(","), value);
+    }
+
+    fn metric(&mut self, name: &str, kind: &str, help: &str, value: impl std::fmt::Display) {
+        self.header(name, kind, help);
+        self.sample(name, &[], value);
+    }
+}
+
+/// Render all available metrics
+pub async fn render(sources: &MetricsSources) -> String {
+    let mut exp = Exposition::default();
+
+    if let Some(monitor) = &sources.monitor {
+        let stats = monitor.get_stats().await;
+        exp.metric("polybot_trades_total", "counter", "Trades recorded by the monitor", stats.total_trades);
+        exp.metric("polybot_winning_trades_total", "counter", "Trades closed with a profit", stats.winning_trades);
+        exp.metric("polybot_losing_trades_total", "counter", "Trades closed with a loss", stats.losing_trades);
+        exp.metric("polybot_win_rate", "gauge", "Winning trades over all trades", stats.win_rate);
+        exp.metric("polybot_pnl_usd", "gauge", "Total realized PnL", stats.total_pnl);
+        exp.metric("polybot_unrealized_pnl_usd", "gauge", "Unrealized PnL of open positions", monitor.unrealized_pnl().await);
+    }
+
+    if let Some(events) = &sources.events {
+        let metrics = events.get_metrics().await;
+        exp.header("polybot_events_total", "counter", "Events published on the bus by type");
+        let mut counts: Vec<_> = metrics.event_counts.iter().collect();
+        counts.sort();
+        for (event_type, count) in counts {
+            exp.sample("polybot_events_total", &[("event_type", event_type)], count);
+        }
+        exp.metric("polybot_event_store_size", "gauge", "Events held in the event store", metrics.store_size);
+        exp.metric("polybot_event_engine_running", "gauge", "1 if the event engine is running", u8::from(metrics.is_running));
+    }
+
+    if let Some(clob) = &sources.clob {
+        match clob.get_balance().await {
+            Ok(balance) => exp.metric("polybot_balance_usd", "gauge", "Available balance", balance),
+            Err(e) => tracing::debug!("Metrics: balance unavailable: {}", e),
+        }
+        match clob.get_positions().await {
+            Ok(positions) => {
+                let open = positions.iter().filter(|p| p.size != Decimal::ZERO).count();
+                exp.metric("polybot_open_positions", "gauge", "Open positions", open);
+            }
+            Err(e) => tracing::debug!("Metrics: positions unavailable: {}", e),
+        }
+    }
+
+    if let Some(fusion) = &sources.fusion {
+        let decision = fusion.read().await.fuse();
+        exp.header("polybot_fusion_weight", "gauge", "Weight of each source in the latest fused decision");
+        for c in &decision.contributions {
+            exp.sample("polybot_fusion_weight", &[("source", &format!("{:?}", c.source))], c.weight);
+        }
+        exp.header("polybot_fusion_direction", "gauge", "Direction reported by each source (-1 to 1)");
+        for c in &decision.contributions {
+            exp.sample("polybot_fusion_direction", &[("source", &format!("{:?}", c.source))], c.direction);
+        }
+        exp.metric("polybot_fusion_confidence", "gauge", "Confidence of the latest fused decision", decision.confidence);
+    }
+
+    exp.out
+}
+
+async fn metrics_handler(State(sources): State<Arc<MetricsSources>>) -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        render(&sources).await,
+    )
+}
+
+/// Router serving `/metrics`
+pub fn create_router(sources: Arc<MetricsSources>) -> Router {
+    Router::new()
+        .route("/metrics", get(metrics_handler))
+        .with_state(sources)
+}
+
+/// Start the metrics server
+pub async fn start_metrics_server(
+    sources: Arc<MetricsSources>,
+    addr: std::net::SocketAddr,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    tracing::info!("Metrics server listening on http://{}/metrics", listener.local_addr()?);
+    axum::serve(listener, create_router(sources)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mock::MockClobClient;
+    use crate::events::EventEngineConfig;
+    use crate::fusion::{SignalBuilder, SignalSource};
+    use crate::monitor::TradeRecord;
+    use rust_decimal_macros::dec;
+
+    async fn sources() -> MetricsSources {
+        let monitor = Arc::new(Monitor::new(10));
+        monitor
+            .record_trade(TradeRecord {
+                timestamp: chrono::Utc::now(),
+                market_id: "m1".to_string(),
+                side: "BUY".to_string(),
+                size: dec!(10),
+                price: dec!(0.50),
+                pnl: Some(dec!(2.5)),
+            })
+            .await;
+
+        let mut fusion = FusionEngine::new();
+        fusion.add_signal(SignalBuilder::new(SignalSource::MLPredictor).direction(0.6).confidence(0.8).build());
+        fusion.add_signal(SignalBuilder::new(SignalSource::Sentiment).direction(0.2).confidence(0.5).build());
+
+        MetricsSources {
+            monitor: Some(monitor),
+            events: Some(Arc::new(EventEngine::new(EventEngineConfig::default()))),
+            clob: Some(Arc::new(MockClobClient::new().with_balance(dec!(1234.5)))),
+            fusion: Some(Arc::new(RwLock::new(fusion))),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_render_omits_missing_components() {
+        assert!(render(&MetricsSources::default()).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_metrics_endpoint() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = create_router(Arc::new(sources().await));
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+
+        let resp = reqwest::get(format!("http://{}/metrics", addr)).await.unwrap();
+        assert!(resp.status().is_success());
+        let body = resp.text().await.unwrap();
+
+        for expected in [
+            "polybot_trades_total 1",
+            "polybot_winning_trades_total 1",
+            "polybot_pnl_usd 2.5",
+            "polybot_balance_usd 1234.5",
+            "polybot_open_positions 0",
+            "polybot_event_store_size 0",
+            "polybot_event_engine_running 0",
+            "# TYPE polybot_fusion_weight gauge",
+            "polybot_fusion_direction{source=\"MLPredictor\"} 0.6",
+            "polybot_fusion_direction{source=\"Sentiment\"} 0.2",
+        ] {
+            assert!(body.contains(expected), "missing `{}` in:\n{}", expected, body);
+        }
+    }
+}
@@ -52,7 +52,7 @@ struct CalibrationSample {
 /// Probability calibrator
 pub struct ProbabilityCalibrator {
     method: CalibrationMethod,
-    // Platt scaling parameters: P(y=1|x) = 1 / (1 + exp(A*x + B))
+    // Platt scaling parameters: P(y=1|x) = sigmoid(A * logit(x) + B)
     platt_a: Decimal,
     platt_b: Decimal,
     // Temperature scaling
@@ -71,7 +71,8 @@ impl ProbabilityCalibrator {
     pub fn new(method: CalibrationMethod) -> Self {
         Self {
             method,
-            platt_a: dec!(-1.0),
+            // Identity mapping until fitted
+            platt_a: dec!(1.0),
             platt_b: Decimal::ZERO,
             temperature: dec!(1.0),
             isotonic_bins: Self::default_isotonic_bins(),
@@ -132,6 +133,27 @@ impl ProbabilityCalibrator {
         }
     }
     
+    /// Fit from a batch of logged (prediction, outcome) pairs
+    pub fn fit(&mut self, samples: &[(Decimal, bool)]) {
+        for &(predicted, actual) in samples {
+            self.training_samples.push_back(CalibrationSample { predicted, actual });
+            if self.training_samples.len() > self.max_samples {
+                self.training_samples.pop_front();
+            }
+        }
+        self.samples_seen += samples.len();
+        self.refit();
+    }
+    
+    /// Reliability diagram of calibrated outputs over the training samples
+    pub fn reliability_diagram(&self, n_bins: usize) -> Vec<ReliabilityBin> {
+        let pairs: Vec<(Decimal, bool)> = self.training_samples
+            .iter()
+            .map(|s| (self.calibrate(s.predicted).calibrated_probability, s.actual))
+            .collect();
+        reliability_diagram(&pairs, n_bins)
+    }
+    
     /// Manually trigger refitting
     pub fn refit(&mut self) {
         match self.method {
@@ -146,8 +168,8 @@ impl ProbabilityCalibrator {
     
     /// Platt scaling calibration
     fn platt_calibrate(&self, raw: Decimal) -> Decimal {
-        // Sigmoid: 1 / (1 + exp(A*x + B))
-        let logit = self.platt_a * raw + self.platt_b;
+        // Sigmoid over the raw logit: 1 / (1 + exp(-(A*logit(x) + B)))
+        let logit = self.platt_a * prob_to_logit(raw) + self.platt_b;
         sigmoid(logit)
     }
     
@@ -199,31 +221,36 @@ impl ProbabilityCalibrator {
             return;
         }
         
-        // Simplified gradient descent for A and B
-        let mut a = self.platt_a;
-        let mut b = self.platt_b;
-        let learning_rate = dec!(0.1);
-        
-        for _ in 0..100 {
-            let mut grad_a = Decimal::ZERO;
-            let mut grad_b = Decimal::ZERO;
+        // Gradient descent on log-loss, in f64 for speed
+        let xs: Vec<(f64, f64)> = self.training_samples
+            .iter()
+            .map(|s| {
+                let x = decimal_to_f64(prob_to_logit(s.predicted));
+                (x, if s.actual { 1.0 } else { 0.0 })
+            })
+            .collect();
+        let n = xs.len() as f64;
+        let mut a = decimal_to_f64(self.platt_a);
+        let mut b = decimal_to_f64(self.platt_b);
+        let learning_rate = 0.5;
+        
+        for _ in 0..500 {
+            let mut grad_a = 0.0;
+            let mut grad_b = 0.0;
             
-            for sample in &self.training_samples {
-                let pred = sigmoid(a * sample.predicted + b);
-                let target = if sample.actual { dec!(1.0) } else { dec!(0.0) };
+            for &(x, target) in &xs {
+                let pred = 1.0 / (1.0 + (-(a * x + b)).exp());
                 let error = pred - target;
-                
-                grad_a += error * sample.predicted;
+                grad_a += error * x;
                 grad_b += error;
             }
             
-            let n = Decimal::from(self.training_samples.len() as i64);
             a -= learning_rate * grad_a / n;
             b -= learning_rate * grad_b / n;
         }
         
-        self.platt_a = a;
-        self.platt_b = b;
+        self.platt_a = f64_to_decimal(a);
+        self.platt_b = f64_to_decimal(b);
     }
     
     /// Fit isotonic regression
@@ -234,36 +261,39 @@ impl ProbabilityCalibrator {
         
         // Sort samples by predicted probability
         let mut sorted: Vec<_> = self.training_samples.iter().cloned().collect();
-        sorted.sort_by(|a, b| a.predicted.partial_cmp(&b.predicted).unwrap());
+        sorted.sort_by_key(|s| s.predicted);
         
         // Pool Adjacent Violators Algorithm (PAVA)
-        let n_bins = 10;
-        let bin_size = sorted.len() / n_bins;
-        
-        self.isotonic_bins.clear();
-        
-        for i in 0..n_bins {
-            let start = i * bin_size;
-            let end = if i == n_bins - 1 { sorted.len() } else { (i + 1) * bin_size };
-            
-            if start >= end {
-                continue;
+        // Blocks are (upper threshold, positive count, sample count); ties share a block
+        let mut blocks: Vec<(Decimal, Decimal, Decimal)> = Vec::new();
+        
+        for sample in sorted {
+            let positive = if sample.actual { Decimal::ONE } else { Decimal::ZERO };
+            match blocks.last_mut() {
+                Some(last) if last.0 == sample.predicted => {
+                    last.1 += positive;
+                    last.2 += Decimal::ONE;
+                }
+                _ => blocks.push((sample.predicted, positive, Decimal::ONE)),
             }
             
-            let bin_samples = &sorted[start..end];
-            let threshold = bin_samples.last().unwrap().predicted;
-            let positive_count = bin_samples.iter().filter(|s| s.actual).count();
-            let calibrated = Decimal::from(positive_count as i64) / Decimal::from(bin_samples.len() as i64);
-            
-            // Enforce monotonicity
-            let calibrated = if let Some(&(_, prev_cal)) = self.isotonic_bins.last() {
-                calibrated.max(prev_cal)
-            } else {
-                calibrated
-            };
-            
-            self.isotonic_bins.push((threshold, calibrated));
+            // Merge backwards while the block means decrease
+            while blocks.len() >= 2 {
+                let n = blocks.len();
+                let (_, prev_pos, prev_count) = blocks[n - 2];
+                let (threshold, pos, count) = blocks[n - 1];
+                if prev_pos / prev_count <= pos / count {
+                    break;
+                }
+                blocks.pop();
+                blocks[n - 2] = (threshold, prev_pos + pos, prev_count + count);
+            }
         }
+        
+        self.isotonic_bins = blocks
+            .into_iter()
+            .map(|(threshold, pos, count)| (threshold, pos / count))
+            .collect();
     }
     
     /// Fit temperature scaling
@@ -403,6 +433,60 @@ pub struct CalibrationStats {
     pub temperature: Decimal,
 }
 
+/// One bucket of a reliability diagram
+#[derive(Debug, Clone)]
+pub struct ReliabilityBin {
+    /// Lower edge of the predicted-probability bucket
+    pub lower: Decimal,
+    /// Upper edge of the predicted-probability bucket
+    pub upper: Decimal,
+    /// Number of samples in the bucket
+    pub count: usize,
+    /// Mean predicted probability in the bucket
+    pub mean_predicted: Decimal,
+    /// Realized frequency of the event in the bucket
+    pub observed_rate: Decimal,
+}
+
+impl ReliabilityBin {
+    /// Signed gap between prediction and reality (positive = overconfident)
+    pub fn gap(&self) -> Decimal {
+        self.mean_predicted - self.observed_rate
+    }
+}
+
+/// Bucket (prediction, outcome) pairs into equal-width bins; empty bins are skipped
+pub fn reliability_diagram(samples: &[(Decimal, bool)], n_bins: usize) -> Vec<ReliabilityBin> {
+    if n_bins == 0 {
+        return Vec::new();
+    }
+    
+    let width = Decimal::ONE / Decimal::from(n_bins as i64);
+    let mut bins: Vec<(Decimal, usize, usize)> = vec![(Decimal::ZERO, 0, 0); n_bins];
+    
+    for &(predicted, actual) in samples {
+        let idx = decimal_to_usize((predicted.max(Decimal::ZERO) / width).floor()).min(n_bins - 1);
+        bins[idx].0 += predicted;
+        bins[idx].1 += if actual { 1 } else { 0 };
+        bins[idx].2 += 1;
+    }
+    
+    bins.into_iter()
+        .enumerate()
+        .filter(|(_, (_, _, count))| *count > 0)
+        .map(|(i, (sum, positives, count))| {
+            let n = Decimal::from(count as i64);
+            ReliabilityBin {
+                lower: width * Decimal::from(i as i64),
+                upper: width * Decimal::from(i as i64 + 1),
+                count,
+                mean_predicted: sum / n,
+                observed_rate: Decimal::from(positives as i64) / n,
+            }
+        })
+        .collect()
+}
+
 /// Sigmoid function
 fn sigmoid(x: Decimal) -> Decimal {
     // 1 / (1 + exp(-x))
@@ -503,8 +587,70 @@ mod tests {
         }
         
         // After training, high predictions should be pulled down
-        let before_a = dec!(-1.0);
+        let before_a = dec!(1.0);
         assert_ne!(cal.platt_a, before_a);
+        assert!(cal.calibrate(dec!(0.8)).calibrated_probability < dec!(0.8));
+    }
+    
+    #[test]
+    fn test_platt_identity_before_fit() {
+        let cal = ProbabilityCalibrator::with_platt_scaling();
+        let result = cal.calibrate(dec!(0.7)).calibrated_probability;
+        assert!((result - dec!(0.7)).abs() < dec!(0.001));
+    }
+    
+    /// Scores that claim 0.9/0.7 but realize 0.7/0.58
+    fn overconfident_samples() -> Vec<(Decimal, bool)> {
+        let mut samples = Vec::new();
+        for i in 0..100 {
+            samples.push((dec!(0.9), i < 70));
+            samples.push((dec!(0.7), i < 58));
+            samples.push((dec!(0.3), i < 42));
+            samples.push((dec!(0.1), i < 30));
+        }
+        samples
+    }
+    
+    #[test]
+    fn test_platt_fit_corrects_overconfidence() {
+        let samples = overconfident_samples();
+        let mut cal = ProbabilityCalibrator::with_platt_scaling();
+        cal.fit(&samples);
+        
+        let high = cal.calibrate(dec!(0.9)).calibrated_probability;
+        let mid = cal.calibrate(dec!(0.7)).calibrated_probability;
+        assert!((high - dec!(0.7)).abs() < (dec!(0.9) - dec!(0.7)), "0.9 -> {}", high);
+        assert!((mid - dec!(0.58)).abs() < (dec!(0.7) - dec!(0.58)), "0.7 -> {}", mid);
+        assert!(high > mid);
+    }
+    
+    #[test]
+    fn test_isotonic_fit_corrects_overconfidence() {
+        let samples = overconfident_samples();
+        let mut cal = ProbabilityCalibrator::with_isotonic();
+        cal.fit(&samples);
+        
+        let high = cal.calibrate(dec!(0.9)).calibrated_probability;
+        assert!(high < dec!(0.85), "0.9 -> {}", high);
+    }
+    
+    #[test]
+    fn test_reliability_diagram() {
+        let samples = overconfident_samples();
+        let raw = reliability_diagram(&samples, 10);
+        
+        assert_eq!(raw.len(), 4);
+        assert_eq!(raw.iter().map(|b| b.count).sum::<usize>(), samples.len());
+        let top = raw.last().unwrap();
+        assert_eq!(top.mean_predicted, dec!(0.9));
+        assert_eq!(top.observed_rate, dec!(0.7));
+        assert!(top.gap() > Decimal::ZERO);
+        
+        let mut cal = ProbabilityCalibrator::with_platt_scaling();
+        cal.fit(&samples);
+        let raw_gap: Decimal = raw.iter().map(|b| b.gap().abs()).sum();
+        let cal_gap: Decimal = cal.reliability_diagram(10).iter().map(|b| b.gap().abs()).sum();
+        assert!(cal_gap < raw_gap);
     }
     
     #[test]
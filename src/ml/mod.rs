@@ -17,7 +17,7 @@ pub mod predictor;
 mod tests;
 
 pub use features::{FeatureExtractor, MarketFeatures, FeatureConfig};
-pub use calibration::{ProbabilityCalibrator, CalibrationMethod, CalibrationResult};
+pub use calibration::{ProbabilityCalibrator, CalibrationMethod, CalibrationResult, CalibrationStats, ReliabilityBin, reliability_diagram};
 pub use ensemble::{EnsemblePredictor, ModelPrediction, EnsembleConfig, EnsembleMethod};
 pub use factors::{MultiFactorFusion, Factor, FactorWeight, FusionConfig, FusionResult, FactorCategory};
-pub use predictor::{MLPredictor, MLPredictorConfig, MLPredictionResult, MarketDataInput, KlineData, FeatureSummary};
+pub use predictor::{MLPredictor, MLPredictorConfig, MLPredictionResult, MarketDataInput, KlineData, FeatureSummary, is_down_market, is_up_market, sentiment_symbol};
@@ -20,9 +20,11 @@ use std::collections::HashMap;
 use chrono::Utc;
 
 use super::features::{FeatureExtractor, FeatureConfig};
-use super::calibration::{ProbabilityCalibrator, CalibrationMethod};
+use super::calibration::{ProbabilityCalibrator, CalibrationMethod, CalibrationStats, ReliabilityBin};
 use super::ensemble::{EnsemblePredictor, ModelPrediction, EnsembleConfig, EnsembleMethod};
 use super::factors::{MultiFactorFusion, Factor, FactorCategory, FusionConfig};
+use crate::fusion::Signal as FusionSignal;
+use crate::sentiment::SentimentSignal;
 
 /// Market data input for prediction
 #[derive(Debug, Clone)]
@@ -33,16 +35,69 @@ pub struct MarketDataInput {
     pub price: f64,
     /// OHLCV data for feature extraction
     pub klines: Vec<KlineData>,
-    /// Current order book depth (optional)
+    /// Order book imbalance, -1 (ask-heavy) to 1 (bid-heavy) (optional)
+    ///
+    /// See `OrderBookSnapshot::depth_weighted_imbalance`.
     pub orderbook_imbalance: Option<f64>,
     /// Recent volume
     pub volume_24h: f64,
-    /// External sentiment score (optional)
+    /// External sentiment score, -1 (bearish) to 1 (bullish) (optional)
     pub sentiment_score: Option<f64>,
+    /// Confidence of the sentiment score (0-1)
+    ///
+    /// `None` means the score is already confidence-weighted and is used as-is.
+    /// Scores below `MLPredictorConfig::min_sentiment_confidence` are dropped
+    /// entirely, contributing nothing rather than pulling the ensemble to 0.5.
+    pub sentiment_confidence: Option<f64>,
+    /// Perp funding tilt, -1 (bearish) to 1 (bullish) (optional)
+    ///
+    /// See `data::funding_signal`; crowded longs read as bearish.
+    pub funding_tilt: Option<f64>,
     /// Market question context
     pub question: String,
 }
 
+impl MarketDataInput {
+    /// Attach a signal from `SentimentEngine::analyze_asset`
+    ///
+    /// The score is the signal's `trading_bias()`; its confidence gates whether
+    /// the predictor uses it at all.
+    pub fn with_sentiment(mut self, signal: &SentimentSignal) -> Self {
+        self.sentiment_score = Some(signal.trading_bias());
+        self.sentiment_confidence = Some(signal.confidence);
+        self
+    }
+
+    /// Attach a `SignalSource::FundingRate` signal from the fusion layer
+    pub fn with_funding(mut self, signal: &FusionSignal) -> Self {
+        self.funding_tilt = Some(signal.direction);
+        self
+    }
+}
+
+/// Map a Binance pair (e.g., "BTCUSDT") to its sentiment asset symbol ("BTC")
+pub fn sentiment_symbol(binance_symbol: &str) -> &str {
+    ["USDT", "USDC", "BUSD", "USD"]
+        .iter()
+        .find_map(|quote| binance_symbol.strip_suffix(quote))
+        .filter(|base| !base.is_empty())
+        .unwrap_or(binance_symbol)
+}
+
+/// Whether a market's Yes outcome is the price going up
+///
+/// Anything else is read as a "down" market.
+pub fn is_up_market(question: &str) -> bool {
+    let q = question.to_lowercase();
+    q.contains("go up") || q.contains("上涨") || (q.contains("up or down") && !q.contains("go down"))
+}
+
+/// Whether a market's Yes outcome is the price going down
+pub fn is_down_market(question: &str) -> bool {
+    let q = question.to_lowercase();
+    q.contains("go down") || q.contains("下跌")
+}
+
 /// Single kline data point
 #[derive(Debug, Clone)]
 pub struct KlineData {
@@ -54,11 +109,35 @@ pub struct KlineData {
     pub volume: f64,
 }
 
+impl KlineData {
+    /// Parse one row of Binance's `/api/v3/klines` response
+    ///
+    /// Prices come as strings; unparseable fields read as zero.
+    pub fn from_binance_row(row: &[serde_json::Value]) -> Self {
+        let field = |i: usize| {
+            row.get(i)
+                .and_then(|v| v.as_str())
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(0.0)
+        };
+        Self {
+            timestamp: row.first().and_then(|v| v.as_i64()).unwrap_or(0),
+            open: field(1),
+            high: field(2),
+            low: field(3),
+            close: field(4),
+            volume: field(5),
+        }
+    }
+}
+
 /// ML prediction result
 #[derive(Debug, Clone)]
 pub struct MLPredictionResult {
-    /// Predicted probability of "Up" outcome
+    /// Predicted probability of "Up" outcome (calibrated)
     pub up_probability: f64,
+    /// Uncalibrated probability; log this with the outcome for calibration
+    pub raw_probability: f64,
     /// Confidence in the prediction (0-1)
     pub confidence: f64,
     /// Individual factor signals
@@ -83,6 +162,12 @@ pub struct FeatureSummary {
     pub momentum_1h: f64,
     pub volume_trend: f64,
     pub volatility: f64,
+    /// Order book imbalance (None when unavailable or disabled)
+    pub orderbook_imbalance: Option<f64>,
+    /// Sentiment score (None when unavailable, low-confidence or disabled)
+    pub sentiment: Option<f64>,
+    /// Funding tilt (None when unavailable or disabled)
+    pub funding: Option<f64>,
 }
 
 /// Configuration for ML predictor
@@ -94,6 +179,10 @@ pub struct MLPredictorConfig {
     pub use_sentiment: bool,
     /// Enable orderbook analysis
     pub use_orderbook: bool,
+    /// Enable the perp funding tilt
+    pub use_funding: bool,
+    /// Minimum sentiment confidence for the sentiment feature to be used
+    pub min_sentiment_confidence: f64,
     /// Calibration method
     pub calibration_method: CalibrationMethod,
     /// Ensemble method
@@ -132,6 +221,8 @@ impl Default for MLPredictorConfig {
             use_technical: true,
             use_sentiment: true,
             use_orderbook: true,
+            use_funding: true,
+            min_sentiment_confidence: 0.3,
             calibration_method: CalibrationMethod::PlattScaling,
             ensemble_method: EnsembleMethod::WeightedAverage,
             factor_weights: FactorWeights::default(),
@@ -177,7 +268,7 @@ impl MLPredictor {
         let model_predictions = self.generate_model_predictions(data, &features);
         
         // Create factors for fusion
-        let factors = self.create_factors(data, &features);
+        let factors = self.create_factors(&features);
         
         // Combine predictions using ensemble
         let ensemble_result = self.ensemble.predict(&model_predictions);
@@ -225,6 +316,7 @@ impl MLPredictor {
         
         MLPredictionResult {
             up_probability: calibrated_prob,
+            raw_probability: raw_prob,
             confidence,
             factor_signals,
             recommended_side,
@@ -234,8 +326,64 @@ impl MLPredictor {
         }
     }
 
+    /// Record a resolved prediction to refine calibration online
+    ///
+    /// `raw_probability` must be `MLPredictionResult::raw_probability`,
+    /// not the calibrated output.
+    pub fn record_outcome(&mut self, raw_probability: f64, went_up: bool) {
+        self.calibrator.add_sample(
+            Decimal::from_f64(raw_probability).unwrap_or(dec!(0.5)),
+            went_up,
+        );
+    }
+
+    /// Fit calibration from logged (raw probability, went up) pairs
+    pub fn fit_calibration(&mut self, history: &[(f64, bool)]) {
+        let samples: Vec<(Decimal, bool)> = history
+            .iter()
+            .map(|&(p, up)| (Decimal::from_f64(p).unwrap_or(dec!(0.5)), up))
+            .collect();
+        self.calibrator.fit(&samples);
+    }
+
+    /// Reliability diagram of calibrated predictions vs realized outcomes
+    pub fn reliability_diagram(&self, n_bins: usize) -> Vec<ReliabilityBin> {
+        self.calibrator.reliability_diagram(n_bins)
+    }
+
+    /// Current calibration parameters and error estimate
+    pub fn calibration_stats(&self) -> CalibrationStats {
+        self.calibrator.stats()
+    }
+
     /// Extract features from market data
     fn extract_features(&self, data: &MarketDataInput) -> FeatureSummary {
+        let orderbook_imbalance = if self.config.use_orderbook {
+            data.orderbook_imbalance
+                .filter(|v| v.is_finite())
+                .map(|v| v.clamp(-1.0, 1.0))
+        } else {
+            None
+        };
+
+        let sentiment = if self.config.use_sentiment {
+            let confident = data.sentiment_confidence
+                .is_none_or(|c| c >= self.config.min_sentiment_confidence);
+            data.sentiment_score
+                .filter(|v| v.is_finite() && confident)
+                .map(|v| v.clamp(-1.0, 1.0))
+        } else {
+            None
+        };
+
+        let funding = if self.config.use_funding {
+            data.funding_tilt
+                .filter(|v| v.is_finite())
+                .map(|v| v.clamp(-1.0, 1.0))
+        } else {
+            None
+        };
+
         if data.klines.is_empty() {
             return FeatureSummary {
                 rsi: 50.0,
@@ -245,6 +393,9 @@ impl MLPredictor {
                 momentum_1h: 0.0,
                 volume_trend: 0.0,
                 volatility: 0.02,
+                orderbook_imbalance,
+                sentiment,
+                funding,
             };
         }
 
@@ -261,6 +412,9 @@ impl MLPredictor {
             momentum_1h: self.calculate_momentum(&closes),
             volume_trend: self.calculate_volume_trend(&volumes),
             volatility: self.calculate_volatility(&closes),
+            orderbook_imbalance,
+            sentiment,
+            funding,
         }
     }
 
@@ -514,6 +668,42 @@ impl MLPredictor {
             metadata: None,
         });
 
+        // Sentiment prediction (absent when the signal was missing or low-confidence)
+        if let Some(sentiment) = features.sentiment {
+            predictions.push(ModelPrediction {
+                model_id: "sentiment_model".to_string(),
+                probability: Decimal::from_f64(0.5 + sentiment * 0.2).unwrap_or(dec!(0.5)),
+                confidence: Decimal::from_f64(sentiment.abs() * 0.8).unwrap_or(dec!(0.5)),
+                uncertainty: Some(dec!(0.2)),
+                timestamp: now,
+                metadata: None,
+            });
+        }
+
+        // Order book imbalance prediction (bid-heavy book → buying pressure)
+        if let Some(imbalance) = features.orderbook_imbalance {
+            predictions.push(ModelPrediction {
+                model_id: "orderbook_model".to_string(),
+                probability: Decimal::from_f64(0.5 + imbalance * 0.2).unwrap_or(dec!(0.5)),
+                confidence: Decimal::from_f64(imbalance.abs() * 0.8).unwrap_or(dec!(0.5)),
+                uncertainty: Some(dec!(0.15)),
+                timestamp: now,
+                metadata: None,
+            });
+        }
+
+        // Funding prediction (crowded longs → expect down)
+        if let Some(funding) = features.funding {
+            predictions.push(ModelPrediction {
+                model_id: "funding_model".to_string(),
+                probability: Decimal::from_f64(0.5 + funding * 0.2).unwrap_or(dec!(0.5)),
+                confidence: Decimal::from_f64(funding.abs() * 0.8).unwrap_or(dec!(0.5)),
+                uncertainty: Some(dec!(0.2)),
+                timestamp: now,
+                metadata: None,
+            });
+        }
+
         // ADX trend strength adjustment
         if features.adx > 25.0 {
             // Strong trend - boost momentum prediction confidence
@@ -545,7 +735,7 @@ impl MLPredictor {
     }
 
     /// Create factors for fusion
-    fn create_factors(&self, data: &MarketDataInput, features: &FeatureSummary) -> Vec<Factor> {
+    fn create_factors(&self, features: &FeatureSummary) -> Vec<Factor> {
         let mut factors = Vec::new();
         let now = Utc::now();
 
@@ -598,7 +788,7 @@ impl MLPredictor {
         });
 
         // Sentiment factor (if available)
-        if let Some(sentiment) = data.sentiment_score {
+        if let Some(sentiment) = features.sentiment {
             factors.push(Factor {
                 id: "sentiment".to_string(),
                 name: "Sentiment".to_string(),
@@ -611,7 +801,7 @@ impl MLPredictor {
         }
 
         // Orderbook factor (if available)
-        if let Some(imbalance) = data.orderbook_imbalance {
+        if let Some(imbalance) = features.orderbook_imbalance {
             factors.push(Factor {
                 id: "orderbook".to_string(),
                 name: "Order Book Imbalance".to_string(),
@@ -623,6 +813,19 @@ impl MLPredictor {
             });
         }
 
+        // Funding factor (if available)
+        if let Some(funding) = features.funding {
+            factors.push(Factor {
+                id: "funding".to_string(),
+                name: "Perp Funding".to_string(),
+                category: FactorCategory::Microstructure,
+                value: Decimal::from_f64(funding).unwrap_or(dec!(0)),
+                signal: Decimal::from_f64(funding * 0.1).unwrap_or(dec!(0)),
+                confidence: Decimal::from_f64(funding.abs()).unwrap_or(dec!(0.5)),
+                timestamp: now,
+            });
+        }
+
         factors
     }
 
@@ -660,13 +863,7 @@ impl MLPredictor {
 
     /// Determine recommended side and edge
     fn determine_side(&self, up_prob: f64, market_price: f64, question: &str) -> (String, f64) {
-        let q = question.to_lowercase();
-        
-        // Parse market direction from question
-        let is_up_market = q.contains("go up") || q.contains("上涨") || 
-                          (q.contains("up or down") && !q.contains("go down"));
-        
-        if is_up_market {
+        if is_up_market(question) {
             // Market asks about "up"
             if up_prob > 0.5 {
                 let edge = up_prob - market_price;
@@ -731,6 +928,8 @@ mod tests {
             orderbook_imbalance: Some(0.1),
             volume_24h: 50000000.0,
             sentiment_score: Some(0.3),
+            sentiment_confidence: None,
+            funding_tilt: None,
             question: "Will Bitcoin go up?".to_string(),
         };
 
@@ -765,6 +964,8 @@ mod tests {
             orderbook_imbalance: Some(0.1),
             volume_24h: 50000000.0,
             sentiment_score: Some(0.3),
+            sentiment_confidence: None,
+            funding_tilt: None,
             question: "Will Bitcoin go up in the next hour?".to_string(),
         };
 
@@ -837,6 +1038,8 @@ mod tests {
             orderbook_imbalance: None,
             volume_24h: 0.0,
             sentiment_score: None,
+            sentiment_confidence: None,
+            funding_tilt: None,
             question: "Will Bitcoin go up?".to_string(),
         };
 
@@ -885,6 +1088,166 @@ mod tests {
         assert!(ema > 10.0); // Higher than simple average of 1-20
     }
 
+    #[test]
+    fn test_bid_skewed_book_raises_up_probability() {
+        let predictor = MLPredictor::new(MLPredictorConfig::default());
+        let make_data = |imbalance: Option<f64>| MarketDataInput {
+            symbol: "BTCUSDT".to_string(),
+            price: 85000.0,
+            klines: create_test_klines(),
+            orderbook_imbalance: imbalance,
+            volume_24h: 50000000.0,
+            sentiment_score: None,
+            sentiment_confidence: None,
+            funding_tilt: None,
+            question: "Will Bitcoin go up?".to_string(),
+        };
+
+        let neutral = predictor.predict(&make_data(None), 0.5);
+        let bid_heavy = predictor.predict(&make_data(Some(0.9)), 0.5);
+        let ask_heavy = predictor.predict(&make_data(Some(-0.9)), 0.5);
+
+        assert_eq!(bid_heavy.features.orderbook_imbalance, Some(0.9));
+        assert!(bid_heavy.up_probability > neutral.up_probability);
+        assert!(ask_heavy.up_probability < neutral.up_probability);
+
+        // Disabled orderbook analysis ignores the input
+        let predictor = MLPredictor::new(MLPredictorConfig {
+            use_orderbook: false,
+            ..Default::default()
+        });
+        let ignored = predictor.predict(&make_data(Some(0.9)), 0.5);
+        assert_eq!(ignored.features.orderbook_imbalance, None);
+    }
+
+    #[test]
+    fn test_sentiment_nudges_only_when_confident() {
+        let predictor = MLPredictor::new(MLPredictorConfig::default());
+        let make_data = |score: Option<f64>, confidence: Option<f64>| MarketDataInput {
+            symbol: "BTCUSDT".to_string(),
+            price: 85000.0,
+            klines: create_test_klines(),
+            orderbook_imbalance: None,
+            volume_24h: 50000000.0,
+            sentiment_score: score,
+            sentiment_confidence: confidence,
+            funding_tilt: None,
+            question: "Will Bitcoin go up?".to_string(),
+        };
+
+        let neutral = predictor.predict(&make_data(None, None), 0.5);
+        let bullish = predictor.predict(&make_data(Some(0.9), Some(0.9)), 0.5);
+        let unsure = predictor.predict(&make_data(Some(0.9), Some(0.1)), 0.5);
+
+        assert!(bullish.up_probability > neutral.up_probability);
+        assert_eq!(bullish.features.sentiment, Some(0.9));
+        // Low confidence contributes nothing at all
+        assert_eq!(unsure.features.sentiment, None);
+        assert_eq!(unsure.up_probability, neutral.up_probability);
+    }
+
+    #[test]
+    fn test_crowded_longs_lower_up_probability() {
+        use crate::arbitrage::{Exchange, FundingRate};
+
+        let predictor = MLPredictor::new(MLPredictorConfig::default());
+        let data = MarketDataInput {
+            symbol: "BTCUSDT".to_string(),
+            price: 85000.0,
+            klines: create_test_klines(),
+            orderbook_imbalance: None,
+            volume_24h: 50000000.0,
+            sentiment_score: None,
+            sentiment_confidence: None,
+            funding_tilt: None,
+            question: "Will Bitcoin go up?".to_string(),
+        };
+        let funding = crate::data::funding_signal(&FundingRate {
+            exchange: Exchange::Binance,
+            symbol: "BTCUSDT".to_string(),
+            rate: dec!(0.001),
+            predicted_rate: None,
+            timestamp: 0,
+            next_funding_time: 0,
+            interval_hours: 8,
+        });
+
+        let neutral = predictor.predict(&data, 0.5);
+        let crowded = predictor.predict(&data.clone().with_funding(&funding), 0.5);
+
+        assert!(crowded.features.funding.unwrap() < 0.0);
+        assert!(crowded.up_probability < neutral.up_probability);
+    }
+
+    #[test]
+    fn test_with_sentiment_signal() {
+        let signal = SentimentSignal {
+            symbol: "BTC".to_string(),
+            score: 0.6,
+            confidence: 0.9,
+            tweet_count: 80,
+            kol_sentiment: 0.4,
+            trend: 0.1,
+            timestamp: 0,
+        };
+        let data = MarketDataInput {
+            symbol: "BTCUSDT".to_string(),
+            price: 85000.0,
+            klines: vec![],
+            orderbook_imbalance: None,
+            volume_24h: 0.0,
+            sentiment_score: None,
+            sentiment_confidence: None,
+            funding_tilt: None,
+            question: "Will Bitcoin go up?".to_string(),
+        }
+        .with_sentiment(&signal);
+
+        assert_eq!(data.sentiment_score, Some(signal.trading_bias()));
+        assert_eq!(data.sentiment_confidence, Some(0.9));
+        assert_eq!(sentiment_symbol("BTCUSDT"), "BTC");
+        assert_eq!(sentiment_symbol("ETHUSDC"), "ETH");
+        assert_eq!(sentiment_symbol("USDT"), "USDT");
+    }
+
+    #[test]
+    fn test_calibration_pulls_overconfident_predictions_in() {
+        let mut predictor = MLPredictor::new(MLPredictorConfig::default());
+        let data = MarketDataInput {
+            symbol: "BTCUSDT".to_string(),
+            price: 85000.0,
+            klines: create_test_klines(),
+            orderbook_imbalance: None,
+            volume_24h: 50000000.0,
+            sentiment_score: None,
+            sentiment_confidence: None,
+            funding_tilt: None,
+            question: "Will Bitcoin go up?".to_string(),
+        };
+
+        let before = predictor.predict(&data, 0.5);
+        assert!((before.up_probability - before.raw_probability).abs() < 0.01);
+
+        // Scores that claimed 80% / 20% only realized 58% / 42%
+        let mut history = Vec::new();
+        for i in 0..100 {
+            history.push((0.8, i < 58));
+            history.push((0.2, i < 42));
+        }
+        predictor.fit_calibration(&history);
+
+        let high = predictor.calibrator.calibrate(dec!(0.8)).calibrated_probability.to_f64().unwrap();
+        assert!((high - 0.58).abs() < 0.05, "0.8 calibrated to {}", high);
+
+        let after = predictor.predict(&data, 0.5);
+        assert_eq!(after.raw_probability, before.raw_probability);
+        assert!((after.up_probability - 0.5).abs() <= (before.up_probability - 0.5).abs());
+
+        let diagram = predictor.reliability_diagram(10);
+        assert_eq!(diagram.iter().map(|b| b.count).sum::<usize>(), 200);
+        assert!(diagram.iter().all(|b| b.gap().abs() < dec!(0.05)));
+    }
+
     #[test]
     fn test_factor_creation() {
         let predictor = MLPredictor::new(MLPredictorConfig::default());
@@ -896,18 +1259,12 @@ mod tests {
             momentum_1h: 0.5,
             volume_trend: 10.0,
             volatility: 0.02,
-        };
-        let data = MarketDataInput {
-            symbol: "BTCUSDT".to_string(),
-            price: 85000.0,
-            klines: vec![],
             orderbook_imbalance: Some(0.2),
-            volume_24h: 50000000.0,
-            sentiment_score: Some(0.4),
-            question: "Will Bitcoin go up?".to_string(),
+            sentiment: Some(0.4),
+            funding: None,
         };
 
-        let factors = predictor.create_factors(&data, &features);
+        let factors = predictor.create_factors(&features);
         
         // Should have at least technical, momentum, volume, volatility
         assert!(factors.len() >= 4);
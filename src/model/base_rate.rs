@@ -0,0 +1,107 @@
+//! Base-rate model from resolved markets
+//!
+//! Many markets recur in the same shape ("will X happen by date Y"), and how
+//! often similar markets resolved Yes is a strong prior on its own.
+//! `BaseRateModel` looks up the market's category, then each of its tags,
+//! among the resolved markets in the `Database` and predicts the Yes rate
+//! of the first group with enough samples, with confidence growing with the
+//! number of resolved samples.
+
+use super::{Prediction, ProbabilityModel};
+use crate::error::{BotError, Result};
+use crate::storage::Database;
+use crate::types::Market;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::sync::Arc;
+
+/// Sample size settings for `BaseRateModel`
+#[derive(Debug, Clone)]
+pub struct BaseRateConfig {
+    /// Fewest resolved markets to predict from
+    pub min_samples: u64,
+    /// Samples at which confidence reaches half of `max_confidence`
+    pub half_confidence_samples: u64,
+    /// Confidence approached as the sample grows
+    pub max_confidence: Decimal,
+}
+
+impl Default for BaseRateConfig {
+    fn default() -> Self {
+        Self {
+            min_samples: 10,
+            half_confidence_samples: 30,
+            max_confidence: dec!(0.8),
+        }
+    }
+}
+
+/// Probability model predicting the Yes rate of similar resolved markets
+pub struct BaseRateModel {
+    db: Arc<Database>,
+    config: BaseRateConfig,
+}
+
+impl BaseRateModel {
+    pub fn new(db: Arc<Database>) -> Self {
+        Self::with_config(db, BaseRateConfig::default())
+    }
+
+    pub fn with_config(db: Arc<Database>, config: BaseRateConfig) -> Self {
+        Self { db, config }
+    }
+
+    /// Confidence for a base rate drawn from `samples` resolved markets
+    ///
+    /// Grows as `samples / (samples + half_confidence_samples)` towards
+    /// `max_confidence`.
+    pub fn confidence(&self, samples: u64) -> Decimal {
+        let samples = Decimal::from(samples);
+        let half = Decimal::from(self.config.half_confidence_samples);
+        if samples + half <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+        self.config.max_confidence * samples / (samples + half)
+    }
+}
+
+#[async_trait]
+impl ProbabilityModel for BaseRateModel {
+    async fn predict(&self, market: &Market) -> Result<Prediction> {
+        if market.category.is_none() && market.tags.is_empty() {
+            return Err(BotError::Strategy(format!("No category or tags for {}", market.id)));
+        }
+
+        let mut largest = 0;
+        let groups = market.category.iter().map(|c| (c, true)).chain(market.tags.iter().map(|t| (t, false)));
+        for (group, is_category) in groups {
+            let stats = if is_category {
+                self.db.resolution_stats_by_category(group).await?
+            } else {
+                self.db.resolution_stats_by_tag(group).await?
+            };
+            largest = largest.max(stats.resolved);
+            if stats.resolved < self.config.min_samples {
+                continue;
+            }
+
+            return Ok(Prediction {
+                probability: stats.yes_rate().unwrap_or(dec!(0.5)),
+                confidence: self.confidence(stats.resolved),
+                reasoning: format!(
+                    "Base rate: {} of {} resolved {} markets resolved Yes",
+                    stats.yes_resolved, stats.resolved, group
+                ),
+            });
+        }
+        Err(BotError::Strategy(format!(
+            "Only {} similar resolved markets for {}, need {}",
+            largest, market.id, self.config.min_samples
+        )))
+    }
+
+    fn name(&self) -> &str {
+        "BaseRate"
+    }
+}
@@ -0,0 +1,291 @@
+//! Binance momentum model for crypto up/down markets
+//!
+//! Maps the market question to a Binance pair with a `SymbolMapper`, fetches its hourly klines
+//! and runs them through `MLPredictor`. The predictor's up probability is
+//! the Yes probability for an "up" market and its complement for a "down"
+//! one. Markets that are not about a tracked coin moving up or down get no
+//! prediction, so the ensemble leaves this model out for them.
+
+use super::{Prediction, ProbabilityModel};
+use crate::data::SymbolMapper;
+use crate::error::{BotError, Result};
+use crate::ml::{is_down_market, is_up_market, KlineData, MLPredictor, MLPredictorConfig, MarketDataInput};
+use crate::types::{Market, OutcomeMatcher};
+use async_trait::async_trait;
+use rust_decimal::prelude::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+use std::time::Duration;
+
+const BINANCE_API_URL: &str = "https://api.binance.com";
+/// Hourly klines fetched per prediction
+pub const DEFAULT_KLINE_LIMIT: usize = 50;
+/// Fewer klines than this are too short for the technical features
+const MIN_KLINES: usize = 26;
+
+/// Source of hourly klines (allows mocking)
+#[async_trait]
+pub trait KlineSource: Send + Sync {
+    /// The latest `limit` hourly klines for `symbol`, oldest first
+    async fn hourly_klines(&self, symbol: &str, limit: usize) -> Result<Vec<KlineData>>;
+}
+
+/// Fetches klines from the Binance spot API
+pub struct BinanceKlineClient {
+    http: reqwest::Client,
+    base_url: String,
+}
+
+impl BinanceKlineClient {
+    pub fn new() -> Self {
+        Self::with_base_url(BINANCE_API_URL)
+    }
+
+    /// Point at a different host, e.g. a local mock
+    pub fn with_base_url(base_url: &str) -> Self {
+        Self {
+            http: reqwest::Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .unwrap_or_default(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+}
+
+impl Default for BinanceKlineClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl KlineSource for BinanceKlineClient {
+    async fn hourly_klines(&self, symbol: &str, limit: usize) -> Result<Vec<KlineData>> {
+        let url = format!(
+            "{}/api/v3/klines?symbol={}&interval=1h&limit={}",
+            self.base_url, symbol, limit
+        );
+        let resp = self.http.get(&url).send().await?;
+        if !resp.status().is_success() {
+            return Err(BotError::from_status(resp.status(), format!("Binance klines for {}", symbol)));
+        }
+        let rows: Vec<Vec<serde_json::Value>> = resp.json().await?;
+        Ok(rows.iter().map(|row| KlineData::from_binance_row(row)).collect())
+    }
+}
+
+/// Probability model for crypto up/down markets driven by Binance klines
+pub struct BinanceMomentumModel<K: KlineSource = BinanceKlineClient> {
+    klines: K,
+    predictor: MLPredictor,
+    kline_limit: usize,
+    symbols: SymbolMapper,
+}
+
+impl BinanceMomentumModel {
+    pub fn new() -> Self {
+        Self::with_source(BinanceKlineClient::new())
+    }
+}
+
+impl Default for BinanceMomentumModel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: KlineSource> BinanceMomentumModel<K> {
+    /// Read klines from `klines` instead of Binance
+    pub fn with_source(klines: K) -> Self {
+        Self {
+            klines,
+            predictor: MLPredictor::new(MLPredictorConfig::default()),
+            kline_limit: DEFAULT_KLINE_LIMIT,
+            symbols: SymbolMapper::default(),
+        }
+    }
+
+    /// Fetch this many hourly klines per prediction
+    pub fn with_kline_limit(mut self, limit: usize) -> Self {
+        self.kline_limit = limit.max(MIN_KLINES);
+        self
+    }
+
+    /// Map market questions to Binance pairs with `symbols`
+    pub fn with_symbols(mut self, symbols: SymbolMapper) -> Self {
+        self.symbols = symbols;
+        self
+    }
+}
+
+#[async_trait]
+impl<K: KlineSource> ProbabilityModel for BinanceMomentumModel<K> {
+    async fn predict(&self, market: &Market) -> Result<Prediction> {
+        let up_market = is_up_market(&market.question);
+        if !up_market && !is_down_market(&market.question) {
+            return Err(BotError::Strategy(format!("Not an up/down market: {}", market.question)));
+        }
+        let symbol = self
+            .symbols
+            .symbol_for(&market.question)
+            .ok_or_else(|| BotError::Strategy(format!("No Binance pair for: {}", market.question)))?;
+
+        let klines = self.klines.hourly_klines(symbol, self.kline_limit).await?;
+        if klines.len() < MIN_KLINES {
+            return Err(BotError::Strategy(format!(
+                "Only {} klines for {}, need {}",
+                klines.len(),
+                symbol,
+                MIN_KLINES
+            )));
+        }
+        let yes_price = market
+            .find_outcome("yes", &OutcomeMatcher::default())
+            .and_then(|o| o.price.to_f64())
+            .unwrap_or(0.5);
+        let data = MarketDataInput {
+            symbol: symbol.to_string(),
+            price: klines.last().map(|k| k.close).unwrap_or(0.0),
+            volume_24h: klines.iter().rev().take(24).map(|k| k.volume).sum(),
+            klines,
+            orderbook_imbalance: None,
+            sentiment_score: None,
+            sentiment_confidence: None,
+            funding_tilt: None,
+            question: market.question.clone(),
+        };
+
+        let result = self.predictor.predict(&data, yes_price);
+        let up = result.up_probability.clamp(0.0, 1.0);
+        let yes = if up_market { up } else { 1.0 - up };
+        let decimal = |v: f64| Decimal::from_f64(v).unwrap_or(Decimal::new(5, 1)).round_dp(4);
+
+        Ok(Prediction {
+            probability: decimal(yes),
+            confidence: decimal(result.confidence),
+            reasoning: format!(
+                "{} up {:.0}% (RSI {:.0}, 1h momentum {:.2}%)",
+                symbol,
+                up * 100.0,
+                result.features.rsi,
+                result.features.momentum_1h
+            ),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "BinanceMomentum"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Outcome;
+    use rust_decimal_macros::dec;
+
+    /// Klines replayed from memory
+    struct FixedKlines(Vec<KlineData>);
+
+    #[async_trait]
+    impl KlineSource for FixedKlines {
+        async fn hourly_klines(&self, _symbol: &str, limit: usize) -> Result<Vec<KlineData>> {
+            let skip = self.0.len().saturating_sub(limit);
+            Ok(self.0[skip..].to_vec())
+        }
+    }
+
+    /// Hourly closes moving `step` percent an hour with a small wobble
+    fn trending(step: f64) -> Vec<KlineData> {
+        let mut close = 50_000.0;
+        (0..50)
+            .map(|i| {
+                let open = close;
+                close *= 1.0 + step / 100.0 + if i % 3 == 0 { -0.001 } else { 0.0005 };
+                KlineData {
+                    timestamp: i * 3_600_000,
+                    open,
+                    high: open.max(close) * 1.001,
+                    low: open.min(close) * 0.999,
+                    close,
+                    volume: 100.0 + i as f64,
+                }
+            })
+            .collect()
+    }
+
+    fn market(question: &str) -> Market {
+        Market {
+            id: "m1".to_string(),
+            question: question.to_string(),
+            description: None,
+            end_date: None,
+            volume: dec!(1000),
+            liquidity: dec!(1000),
+            outcomes: vec![
+                Outcome { token_id: "up".to_string(), outcome: "Up".to_string(), price: dec!(0.5) },
+                Outcome { token_id: "down".to_string(), outcome: "Down".to_string(), price: dec!(0.5) },
+            ],
+            active: true,
+            closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prediction_follows_predictor_direction() {
+        let klines = trending(0.4);
+        let model = BinanceMomentumModel::with_source(FixedKlines(klines.clone()));
+        let up_market = market("Bitcoin Up or Down - 3PM ET");
+
+        let expected = MLPredictor::new(MLPredictorConfig::default()).predict(
+            &MarketDataInput {
+                symbol: "BTCUSDT".to_string(),
+                price: klines.last().unwrap().close,
+                volume_24h: klines.iter().rev().take(24).map(|k| k.volume).sum(),
+                klines,
+                orderbook_imbalance: None,
+                sentiment_score: None,
+                sentiment_confidence: None,
+                funding_tilt: None,
+                question: up_market.question.clone(),
+            },
+            0.5,
+        );
+        let up = model.predict(&up_market).await.unwrap();
+        assert_eq!(up.probability, Decimal::from_f64(expected.up_probability).unwrap().round_dp(4));
+        assert_ne!(up.probability, dec!(0.5));
+        assert!(up.reasoning.starts_with("BTCUSDT up"));
+
+        // The same klines price "down" as the complement
+        let down = model.predict(&market("Will Bitcoin go down this hour?")).await.unwrap();
+        assert_eq!(down.probability, Decimal::ONE - up.probability);
+        assert_eq!(down.confidence, up.confidence);
+    }
+
+    #[tokio::test]
+    async fn test_prediction_depends_on_klines() {
+        let question = market("Ethereum Up or Down - 3PM ET");
+        let rising = BinanceMomentumModel::with_source(FixedKlines(trending(0.4)));
+        let falling = BinanceMomentumModel::with_source(FixedKlines(trending(-0.4)));
+        assert_ne!(
+            rising.predict(&question).await.unwrap().probability,
+            falling.predict(&question).await.unwrap().probability
+        );
+    }
+
+    #[tokio::test]
+    async fn test_non_crypto_or_short_history_has_no_prediction() {
+        let model = BinanceMomentumModel::with_source(FixedKlines(trending(0.4)));
+        assert!(model.predict(&market("Will it rain tomorrow?")).await.is_err());
+        assert!(model.predict(&market("Will Bitcoin hit $100k?")).await.is_err());
+        // "up" inside another word is not an up/down market
+        assert!(model.predict(&market("Will the Bitcoin upgrade ship this month?")).await.is_err());
+
+        let short = BinanceMomentumModel::with_source(FixedKlines(trending(0.4)[..10].to_vec()));
+        assert!(short.predict(&market("Bitcoin Up or Down")).await.is_err());
+    }
+}
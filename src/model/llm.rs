@@ -110,15 +110,15 @@ impl LlmModel {
     pub fn from_config(config: &crate::config::LlmConfig) -> Result<Self> {
         let provider = match config.provider.to_lowercase().as_str() {
             "deepseek" => LlmProvider::DeepSeek {
-                api_key: config.api_key.clone(),
+                api_key: config.api_key.expose().to_string(),
                 model: config.model.clone().unwrap_or_else(|| "deepseek-chat".to_string()),
             },
             "anthropic" | "claude" => LlmProvider::Anthropic {
-                api_key: config.api_key.clone(),
+                api_key: config.api_key.expose().to_string(),
                 model: config.model.clone().unwrap_or_else(|| "claude-sonnet-4-20250514".to_string()),
             },
             "openai" | "gpt" => LlmProvider::OpenAI {
-                api_key: config.api_key.clone(),
+                api_key: config.api_key.expose().to_string(),
                 model: config.model.clone().unwrap_or_else(|| "gpt-4o-mini".to_string()),
                 base_url: config.base_url.clone().unwrap_or_else(|| "https://api.openai.com".to_string()),
             },
@@ -128,7 +128,7 @@ impl LlmModel {
                 base_url: config.base_url.clone().unwrap_or_else(|| "http://localhost:11434".to_string()),
             },
             "compatible" | "custom" => LlmProvider::Compatible {
-                api_key: if config.api_key.is_empty() { None } else { Some(config.api_key.clone()) },
+                api_key: if config.api_key.is_empty() { None } else { Some(config.api_key.expose().to_string()) },
                 model: config.model.clone().ok_or_else(|| BotError::Config("model required for compatible provider".into()))?,
                 base_url: config.base_url.clone().ok_or_else(|| BotError::Config("base_url required for compatible provider".into()))?,
             },
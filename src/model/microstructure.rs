@@ -0,0 +1,167 @@
+//! Order book microstructure model
+//!
+//! Estimates the Yes probability from the market itself: order book
+//! imbalance on the Yes token, recent price momentum and the bid/ask spread.
+//! No LLM or external data is needed, so it is a cheap member for the
+//! ensemble.
+
+use super::{Prediction, ProbabilityModel};
+use crate::client::mock::ClobClientTrait;
+use crate::client::OrderBook;
+use crate::error::{BotError, Result};
+use crate::types::Market;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+/// Weights and scales for `MicrostructureModel`
+#[derive(Debug, Clone)]
+pub struct MicrostructureConfig {
+    /// Weight of book imbalance in the combined signal
+    pub imbalance_weight: Decimal,
+    /// Weight of price momentum in the combined signal
+    pub momentum_weight: Decimal,
+    /// Largest shift away from the market price (probability points)
+    pub max_shift: Decimal,
+    /// Book levels per side counted towards imbalance
+    pub depth_levels: usize,
+    /// Price observations kept per market for momentum
+    pub momentum_window: usize,
+    /// Price change treated as full-strength momentum
+    pub momentum_scale: Decimal,
+    /// Spread at which the signal and confidence drop to zero
+    pub wide_spread: Decimal,
+    /// Confidence with a tight spread
+    pub base_confidence: Decimal,
+}
+
+impl Default for MicrostructureConfig {
+    fn default() -> Self {
+        Self {
+            imbalance_weight: dec!(0.6),
+            momentum_weight: dec!(0.4),
+            max_shift: dec!(0.05),
+            depth_levels: 5,
+            momentum_window: 10,
+            momentum_scale: dec!(0.05),
+            wide_spread: dec!(0.10),
+            base_confidence: dec!(0.5),
+        }
+    }
+}
+
+/// Probability model driven by order book imbalance, momentum and spread
+///
+/// Price history is collected from the markets passed to `predict`, so
+/// momentum only kicks in after a market has been seen a few times.
+pub struct MicrostructureModel<C> {
+    books: C,
+    config: MicrostructureConfig,
+    history: Mutex<HashMap<String, VecDeque<Decimal>>>,
+}
+
+impl<C: ClobClientTrait> MicrostructureModel<C> {
+    pub fn new(books: C) -> Self {
+        Self::with_config(books, MicrostructureConfig::default())
+    }
+
+    pub fn with_config(books: C, config: MicrostructureConfig) -> Self {
+        Self {
+            books,
+            config,
+            history: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record an observed Yes price, e.g. to seed history from storage
+    pub fn record_price(&self, market_id: &str, price: Decimal) {
+        let mut history = self.history.lock().unwrap();
+        let prices = history.entry(market_id.to_string()).or_default();
+        prices.push_back(price);
+        while prices.len() > self.config.momentum_window.max(2) {
+            prices.pop_front();
+        }
+    }
+
+    /// Bid-minus-ask depth over total depth, in [-1, 1]
+    fn imbalance(&self, book: &OrderBook) -> Decimal {
+        let depth = |levels: &[crate::client::OrderBookLevel]| -> Decimal {
+            levels.iter().take(self.config.depth_levels).map(|l| l.size).sum()
+        };
+        let bids = depth(&book.bids);
+        let asks = depth(&book.asks);
+        let total = bids + asks;
+        if total.is_zero() {
+            return Decimal::ZERO;
+        }
+        (bids - asks) / total
+    }
+
+    /// Price change across the history window, scaled to [-1, 1]
+    fn momentum(&self, market_id: &str) -> Decimal {
+        let history = self.history.lock().unwrap();
+        let Some(prices) = history.get(market_id).filter(|p| p.len() >= 2) else {
+            return Decimal::ZERO;
+        };
+        if self.config.momentum_scale.is_zero() {
+            return Decimal::ZERO;
+        }
+        let change = prices[prices.len() - 1] - prices[0];
+        (change / self.config.momentum_scale).clamp(-Decimal::ONE, Decimal::ONE)
+    }
+
+    /// 1 for a locked book, falling to 0 at `wide_spread`
+    fn tightness(&self, book: &OrderBook) -> Decimal {
+        match book.spread() {
+            Some(spread) if self.config.wide_spread > Decimal::ZERO => {
+                (Decimal::ONE - spread.max(Decimal::ZERO) / self.config.wide_spread).max(Decimal::ZERO)
+            }
+            _ => Decimal::ZERO,
+        }
+    }
+}
+
+#[async_trait]
+impl<C: ClobClientTrait> ProbabilityModel for MicrostructureModel<C> {
+    async fn predict(&self, market: &Market) -> Result<Prediction> {
+        let yes = market
+            .outcomes
+            .iter()
+            .find(|o| o.outcome.eq_ignore_ascii_case("yes"))
+            .ok_or_else(|| BotError::Strategy(format!("Market {} has no Yes outcome", market.id)))?;
+
+        self.record_price(&market.id, yes.price);
+        let book = self.books.get_order_book(&yes.token_id).await?;
+
+        let imbalance = self.imbalance(&book);
+        let momentum = self.momentum(&market.id);
+        let tightness = self.tightness(&book);
+
+        let total_weight = self.config.imbalance_weight + self.config.momentum_weight;
+        let signal = if total_weight.is_zero() {
+            Decimal::ZERO
+        } else {
+            (self.config.imbalance_weight * imbalance + self.config.momentum_weight * momentum)
+                / total_weight
+        };
+        let shift = signal * self.config.max_shift * tightness;
+        let probability = (yes.price + shift).clamp(dec!(0.01), dec!(0.99));
+
+        Ok(Prediction {
+            probability,
+            confidence: self.config.base_confidence * tightness,
+            reasoning: format!(
+                "Imbalance {:+.2}, momentum {:+.2}, spread {}",
+                imbalance,
+                momentum,
+                book.spread().map(|s| s.to_string()).unwrap_or_else(|| "n/a".to_string())
+            ),
+        })
+    }
+
+    fn name(&self) -> &str {
+        "Microstructure"
+    }
+}
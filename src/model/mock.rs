@@ -0,0 +1,77 @@
+//! Canned stand-in for the LLM model
+//!
+//! Answers from a table keyed by market id, falling back to a closure,
+//! with no API key or network. Lets dry-runs and tests drive the full
+//! pipeline deterministically.
+
+use super::{Prediction, ProbabilityModel};
+use crate::error::{BotError, Result};
+use crate::types::Market;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+type Responder = Box<dyn Fn(&Market) -> Option<Prediction> + Send + Sync>;
+
+/// Probability model returning canned predictions
+///
+/// A market with no canned prediction fails like an unreachable LLM, so
+/// the ensemble leaves it out of the blend.
+#[derive(Default)]
+pub struct MockLlmModel {
+    predictions: HashMap<String, Prediction>,
+    fallback: Option<Responder>,
+    calls: Mutex<HashMap<String, u32>>,
+}
+
+impl MockLlmModel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Canned predictions by market id
+    pub fn with_predictions(predictions: HashMap<String, Prediction>) -> Self {
+        Self { predictions, ..Self::default() }
+    }
+
+    /// Answer `market_id` with `prediction`
+    pub fn with_prediction(mut self, market_id: impl Into<String>, prediction: Prediction) -> Self {
+        self.predictions.insert(market_id.into(), prediction);
+        self
+    }
+
+    /// Answer markets without a canned prediction from `f`
+    pub fn with_fallback<F>(mut self, f: F) -> Self
+    where
+        F: Fn(&Market) -> Option<Prediction> + Send + Sync + 'static,
+    {
+        self.fallback = Some(Box::new(f));
+        self
+    }
+
+    /// Total predictions requested, including failed ones
+    pub fn call_count(&self) -> u32 {
+        self.calls.lock().unwrap().values().sum()
+    }
+
+    /// Predictions requested for one market
+    pub fn calls_for(&self, market_id: &str) -> u32 {
+        self.calls.lock().unwrap().get(market_id).copied().unwrap_or(0)
+    }
+}
+
+#[async_trait]
+impl ProbabilityModel for MockLlmModel {
+    async fn predict(&self, market: &Market) -> Result<Prediction> {
+        *self.calls.lock().unwrap().entry(market.id.clone()).or_default() += 1;
+        self.predictions
+            .get(&market.id)
+            .cloned()
+            .or_else(|| self.fallback.as_ref().and_then(|f| f(market)))
+            .ok_or_else(|| BotError::Internal(format!("No canned prediction for {}", market.id)))
+    }
+
+    fn name(&self) -> &str {
+        "MockLLM"
+    }
+}
@@ -3,15 +3,23 @@
 //! This module contains various models to estimate the "true" probability
 //! of market outcomes, which can be compared to market prices to find edge.
 
+mod base_rate;
+mod binance_momentum;
 mod llm;
+mod microstructure;
+mod mock;
 mod sentiment;
 #[cfg(test)]
 mod tests;
 
+pub use base_rate::{BaseRateConfig, BaseRateModel};
+pub use binance_momentum::{BinanceKlineClient, BinanceMomentumModel, KlineSource, DEFAULT_KLINE_LIMIT};
 pub use llm::{LlmModel, LlmProvider};
+pub use microstructure::{MicrostructureConfig, MicrostructureModel};
+pub use mock::MockLlmModel;
 pub use sentiment::SentimentModel;
 
-use crate::error::Result;
+use crate::error::{BotError, Result};
 use crate::types::Market;
 use async_trait::async_trait;
 use rust_decimal::Decimal;
@@ -37,6 +45,17 @@ pub trait ProbabilityModel: Send + Sync {
     fn name(&self) -> &str;
 }
 
+#[async_trait]
+impl<M: ProbabilityModel + ?Sized> ProbabilityModel for std::sync::Arc<M> {
+    async fn predict(&self, market: &Market) -> Result<Prediction> {
+        (**self).predict(market).await
+    }
+
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+}
+
 /// Ensemble model combining multiple models
 pub struct EnsembleModel {
     models: Vec<(Box<dyn ProbabilityModel>, Decimal)>, // (model, weight)
@@ -47,10 +66,17 @@ impl EnsembleModel {
         Self { models: Vec::new() }
     }
 
+    /// Add a model with a relative weight; weights need not sum to 1 and a
+    /// non-positive weight leaves the model out
     pub fn add_model(&mut self, model: Box<dyn ProbabilityModel>, weight: Decimal) {
         self.models.push((model, weight));
     }
 
+    /// Weighted blend of the models that answer for `market`
+    ///
+    /// Weights are renormalized over the models that succeed, so a failing
+    /// model hands its share to the others rather than counting as zero.
+    /// Fails only if every model fails.
     pub async fn predict(&self, market: &Market) -> Result<Prediction> {
         if self.models.is_empty() {
             return Ok(Prediction {
@@ -60,41 +86,67 @@ impl EnsembleModel {
             });
         }
 
-        let mut total_weight = Decimal::ZERO;
-        let mut weighted_prob = Decimal::ZERO;
-        let mut weighted_conf = Decimal::ZERO;
-        let mut reasons = Vec::new();
-
+        let mut answered = Vec::new();
+        let mut failures = Vec::new();
         for (model, weight) in &self.models {
+            if *weight <= Decimal::ZERO {
+                continue;
+            }
             match model.predict(market).await {
-                Ok(pred) => {
-                    weighted_prob += pred.probability * weight;
-                    weighted_conf += pred.confidence * weight;
-                    total_weight += weight;
-                    reasons.push(format!("{}: {:.0}%", model.name(), pred.probability * Decimal::ONE_HUNDRED));
-                }
+                Ok(pred) => answered.push((model.name(), pred, *weight)),
                 Err(e) => {
                     tracing::warn!("Model {} failed: {}", model.name(), e);
+                    failures.push(format!("{}: {}", model.name(), e));
                 }
             }
         }
 
-        if total_weight == Decimal::ZERO {
-            return Ok(Prediction {
-                probability: Decimal::new(50, 2),
-                confidence: Decimal::ZERO,
-                reasoning: "All models failed".to_string(),
-            });
+        let total_weight: Decimal = answered.iter().map(|(_, _, weight)| *weight).sum();
+        if total_weight.is_zero() {
+            return Err(BotError::Strategy(format!(
+                "All models failed for {}: {}",
+                market.id,
+                failures.join("; ")
+            )));
+        }
+
+        let mut probability = Decimal::ZERO;
+        let mut confidence = Decimal::ZERO;
+        let mut reasons = Vec::new();
+        for (name, pred, weight) in &answered {
+            let share = weight / total_weight;
+            probability += pred.probability * share;
+            confidence += pred.confidence * share;
+            reasons.push(format!(
+                "{}: {:.0}% (weight {:.0}%)",
+                name,
+                pred.probability * Decimal::ONE_HUNDRED,
+                share * Decimal::ONE_HUNDRED
+            ));
+        }
+        if !failures.is_empty() {
+            reasons.push(format!("{} failed", failures.len()));
         }
 
         Ok(Prediction {
-            probability: weighted_prob / total_weight,
-            confidence: weighted_conf / total_weight,
+            probability,
+            confidence,
             reasoning: reasons.join("; "),
         })
     }
 }
 
+#[async_trait]
+impl ProbabilityModel for EnsembleModel {
+    async fn predict(&self, market: &Market) -> Result<Prediction> {
+        EnsembleModel::predict(self, market).await
+    }
+
+    fn name(&self) -> &str {
+        "Ensemble"
+    }
+}
+
 impl Default for EnsembleModel {
     fn default() -> Self {
         Self::new()
@@ -3,9 +3,15 @@
 #[cfg(test)]
 mod tests {
     use super::super::llm::{LlmModel, LlmProvider};
+    use super::super::microstructure::{MicrostructureConfig, MicrostructureModel};
+    use super::super::base_rate::{BaseRateConfig, BaseRateModel};
+    use super::super::{EnsembleModel, MockLlmModel, Prediction, ProbabilityModel};
+    use crate::client::mock::MockClobClient;
+    use crate::client::{OrderBook, OrderBookLevel};
     use crate::config::LlmConfig;
     use crate::types::{Market, Outcome};
     use chrono::Utc;
+    use rust_decimal::Decimal;
     use rust_decimal_macros::dec;
 
     #[test]
@@ -105,7 +111,7 @@ mod tests {
     fn test_llm_model_from_config_deepseek() {
         let config = LlmConfig {
             provider: "deepseek".to_string(),
-            api_key: "sk-test".to_string(),
+            api_key: "sk-test".into(),
             model: None,
             base_url: None,
         };
@@ -117,7 +123,7 @@ mod tests {
     fn test_llm_model_from_config_anthropic() {
         let config = LlmConfig {
             provider: "anthropic".to_string(),
-            api_key: "sk-test".to_string(),
+            api_key: "sk-test".into(),
             model: Some("claude-3".to_string()),
             base_url: None,
         };
@@ -129,7 +135,7 @@ mod tests {
     fn test_llm_model_from_config_claude_alias() {
         let config = LlmConfig {
             provider: "claude".to_string(),
-            api_key: "sk-test".to_string(),
+            api_key: "sk-test".into(),
             model: None,
             base_url: None,
         };
@@ -141,7 +147,7 @@ mod tests {
     fn test_llm_model_from_config_openai() {
         let config = LlmConfig {
             provider: "openai".to_string(),
-            api_key: "sk-test".to_string(),
+            api_key: "sk-test".into(),
             model: Some("gpt-4".to_string()),
             base_url: Some("https://api.openai.com".to_string()),
         };
@@ -153,7 +159,7 @@ mod tests {
     fn test_llm_model_from_config_gpt_alias() {
         let config = LlmConfig {
             provider: "gpt".to_string(),
-            api_key: "sk-test".to_string(),
+            api_key: "sk-test".into(),
             model: None,
             base_url: None,
         };
@@ -165,7 +171,7 @@ mod tests {
     fn test_llm_model_from_config_ollama() {
         let config = LlmConfig {
             provider: "ollama".to_string(),
-            api_key: "".to_string(),
+            api_key: "".into(),
             model: None,
             base_url: None,
         };
@@ -177,7 +183,7 @@ mod tests {
     fn test_llm_model_from_config_compatible() {
         let config = LlmConfig {
             provider: "compatible".to_string(),
-            api_key: "".to_string(),
+            api_key: "".into(),
             model: Some("custom-model".to_string()),
             base_url: Some("http://localhost:8000".to_string()),
         };
@@ -189,7 +195,7 @@ mod tests {
     fn test_llm_model_from_config_custom_alias() {
         let config = LlmConfig {
             provider: "custom".to_string(),
-            api_key: "test-key".to_string(),
+            api_key: "test-key".into(),
             model: Some("custom-model".to_string()),
             base_url: Some("http://localhost:8000".to_string()),
         };
@@ -201,7 +207,7 @@ mod tests {
     fn test_llm_model_from_config_unknown_provider() {
         let config = LlmConfig {
             provider: "unknown".to_string(),
-            api_key: "test".to_string(),
+            api_key: "test".into(),
             model: None,
             base_url: None,
         };
@@ -213,7 +219,7 @@ mod tests {
     fn test_llm_model_from_config_compatible_missing_model() {
         let config = LlmConfig {
             provider: "compatible".to_string(),
-            api_key: "".to_string(),
+            api_key: "".into(),
             model: None,
             base_url: Some("http://localhost:8000".to_string()),
         };
@@ -225,7 +231,7 @@ mod tests {
     fn test_llm_model_from_config_compatible_missing_base_url() {
         let config = LlmConfig {
             provider: "compatible".to_string(),
-            api_key: "".to_string(),
+            api_key: "".into(),
             model: Some("model".to_string()),
             base_url: None,
         };
@@ -237,7 +243,7 @@ mod tests {
     fn test_llm_model_from_config_case_insensitive() {
         let config = LlmConfig {
             provider: "DEEPSEEK".to_string(),
-            api_key: "test".to_string(),
+            api_key: "test".into(),
             model: None,
             base_url: None,
         };
@@ -268,6 +274,10 @@ mod tests {
             ],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         }
     }
 
@@ -277,4 +287,244 @@ mod tests {
         assert_eq!(market.yes_price(), Some(dec!(0.65)));
         assert_eq!(market.no_price(), Some(dec!(0.35)));
     }
+
+    fn market_at(yes_price: rust_decimal::Decimal) -> Market {
+        let mut market = create_test_market();
+        market.outcomes[0].price = yes_price;
+        market.outcomes[1].price = rust_decimal::Decimal::ONE - yes_price;
+        market
+    }
+
+    fn book_client(bid_size: rust_decimal::Decimal, ask_size: rust_decimal::Decimal, spread: rust_decimal::Decimal) -> MockClobClient {
+        let mut client = MockClobClient::new();
+        client.set_order_book(
+            "yes-token",
+            OrderBook {
+                bids: vec![OrderBookLevel { price: dec!(0.60), size: bid_size }],
+                asks: vec![OrderBookLevel { price: dec!(0.60) + spread, size: ask_size }],
+            },
+        );
+        client
+    }
+
+    #[tokio::test]
+    async fn test_microstructure_bid_heavy_uptrend_above_market() {
+        let model = MicrostructureModel::new(book_client(dec!(900), dec!(100), dec!(0.01)));
+        for price in [dec!(0.55), dec!(0.57), dec!(0.59)] {
+            model.predict(&market_at(price)).await.unwrap();
+        }
+
+        let market = market_at(dec!(0.61));
+        let prediction = model.predict(&market).await.unwrap();
+
+        assert!(prediction.probability > market.yes_price().unwrap());
+        assert!(prediction.confidence > dec!(0));
+        assert_eq!(model.name(), "Microstructure");
+    }
+
+    #[tokio::test]
+    async fn test_microstructure_ask_heavy_downtrend_below_market() {
+        let model = MicrostructureModel::new(book_client(dec!(100), dec!(900), dec!(0.01)));
+        model.record_price("test-id", dec!(0.70));
+
+        let market = market_at(dec!(0.65));
+        let prediction = model.predict(&market).await.unwrap();
+
+        assert!(prediction.probability < dec!(0.65));
+    }
+
+    #[tokio::test]
+    async fn test_microstructure_wide_spread_has_no_edge() {
+        let model = MicrostructureModel::new(book_client(dec!(900), dec!(100), dec!(0.20)));
+
+        let prediction = model.predict(&market_at(dec!(0.65))).await.unwrap();
+
+        assert_eq!(prediction.probability, dec!(0.65));
+        assert_eq!(prediction.confidence, dec!(0));
+    }
+
+    #[tokio::test]
+    async fn test_microstructure_weights_are_configurable() {
+        let config = MicrostructureConfig {
+            imbalance_weight: dec!(0),
+            momentum_weight: dec!(1),
+            ..Default::default()
+        };
+        let model = MicrostructureModel::with_config(book_client(dec!(900), dec!(100), dec!(0)), config);
+
+        // Imbalance is ignored and there is no history yet
+        let prediction = model.predict(&market_at(dec!(0.65))).await.unwrap();
+        assert_eq!(prediction.probability, dec!(0.65));
+
+        // Full-strength momentum shifts by max_shift
+        let prediction = model.predict(&market_at(dec!(0.70))).await.unwrap();
+        assert_eq!(prediction.probability, dec!(0.75));
+    }
+
+    /// A resolved market in `category`, won by Yes or No
+    fn resolved_market(id: &str, category: &str, yes_won: bool) -> Market {
+        let mut market = create_test_market();
+        market.id = id.to_string();
+        market.category = Some(category.to_string());
+        market.closed = true;
+        market.outcomes[0].price = if yes_won { dec!(1) } else { dec!(0) };
+        market.outcomes[1].price = dec!(1) - market.outcomes[0].price;
+        market
+    }
+
+    async fn resolved_db(dir: &tempfile::TempDir) -> std::sync::Arc<crate::storage::Database> {
+        let db = crate::storage::Database::connect(dir.path().join("bot.db")).await.unwrap();
+        // Weather: 3 of 12 resolved Yes; Politics: 1 of 2
+        for i in 0..12 {
+            let market = resolved_market(&format!("weather-{}", i), "Weather", i < 3);
+            let winner = crate::monitor::settlement::winning_outcome(&market).unwrap().clone();
+            assert!(db.record_resolution(&market, &winner).await.unwrap());
+        }
+        for (i, yes_won) in [true, false].into_iter().enumerate() {
+            let market = resolved_market(&format!("politics-{}", i), "Politics", yes_won);
+            let winner = crate::monitor::settlement::winning_outcome(&market).unwrap().clone();
+            db.record_resolution(&market, &winner).await.unwrap();
+        }
+        std::sync::Arc::new(db)
+    }
+
+    #[tokio::test]
+    async fn test_base_rate_from_resolved_markets() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = resolved_db(&dir).await;
+
+        let stats = db.resolution_stats_by_category("weather").await.unwrap();
+        assert_eq!((stats.resolved, stats.yes_resolved), (12, 3));
+
+        let model = BaseRateModel::new(db);
+        let mut market = create_test_market();
+        market.category = Some("Weather".to_string());
+        let prediction = model.predict(&market).await.unwrap();
+        assert_eq!(prediction.probability, dec!(0.25));
+        assert_eq!(prediction.confidence, model.confidence(12));
+        assert_eq!(model.name(), "BaseRate");
+    }
+
+    #[tokio::test]
+    async fn test_base_rate_confidence_grows_with_samples() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = BaseRateConfig { min_samples: 2, half_confidence_samples: 10, max_confidence: dec!(0.8) };
+        let model = BaseRateModel::with_config(resolved_db(&dir).await, config);
+
+        assert_eq!(model.confidence(10), dec!(0.4));
+        assert!(model.confidence(2) < model.confidence(12));
+        assert!(model.confidence(1000) < dec!(0.8));
+
+        let mut market = create_test_market();
+        market.category = Some("Politics".to_string());
+        let prediction = model.predict(&market).await.unwrap();
+        assert_eq!(prediction.probability, dec!(0.5));
+        assert_eq!(prediction.confidence, model.confidence(2));
+    }
+
+    #[tokio::test]
+    async fn test_base_rate_needs_category_and_samples() {
+        let dir = tempfile::tempdir().unwrap();
+        let model = BaseRateModel::new(resolved_db(&dir).await);
+
+        // No category, too few samples, unseen category
+        assert!(model.predict(&create_test_market()).await.is_err());
+        let mut market = create_test_market();
+        market.category = Some("Politics".to_string());
+        assert!(model.predict(&market).await.is_err());
+        market.category = Some("Sports".to_string());
+        assert!(model.predict(&market).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_base_rate_falls_back_to_tags() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = resolved_db(&dir).await;
+        for i in 0..10 {
+            let mut market = resolved_market(&format!("fed-{}", i), "Economy", i < 7);
+            market.category = None;
+            market.tags = vec!["Fed".to_string()];
+            let winner = crate::monitor::settlement::winning_outcome(&market).unwrap().clone();
+            db.record_resolution(&market, &winner).await.unwrap();
+        }
+        let model = BaseRateModel::new(db);
+
+        // Too few Politics samples, so the Fed tag's rate is used
+        let mut market = create_test_market();
+        market.category = Some("Politics".to_string());
+        market.tags = vec!["Sports".to_string(), "fed".to_string()];
+        let prediction = model.predict(&market).await.unwrap();
+        assert_eq!(prediction.probability, dec!(0.7));
+        assert!(prediction.reasoning.contains("fed"));
+    }
+
+    fn canned(probability: Decimal, confidence: Decimal) -> Prediction {
+        Prediction { probability, confidence, reasoning: "canned".to_string() }
+    }
+
+    #[tokio::test]
+    async fn test_mock_llm_blends_into_ensemble() {
+        let llm = std::sync::Arc::new(
+            MockLlmModel::new()
+                .with_prediction("test-id", canned(dec!(0.80), dec!(0.90)))
+                .with_fallback(|market| market.yes_price().map(|p| canned(p, dec!(0.30)))),
+        );
+        let mut ensemble = EnsembleModel::new();
+        ensemble.add_model(Box::new(llm.clone()), dec!(0.70));
+        ensemble.add_model(Box::new(MockLlmModel::new().with_prediction("test-id", canned(dec!(0.60), dec!(0.50)))), dec!(0.30));
+
+        let prediction = ensemble.predict(&create_test_market()).await.unwrap();
+        // 0.7 * 0.80 + 0.3 * 0.60
+        assert_eq!(prediction.probability, dec!(0.74));
+        assert_eq!(prediction.confidence, dec!(0.78));
+        assert!(prediction.reasoning.contains("MockLLM: 80%"));
+
+        // The second model has nothing for this market and drops out
+        let mut other = create_test_market();
+        other.id = "other".to_string();
+        let prediction = ensemble.predict(&other).await.unwrap();
+        assert_eq!(prediction.probability, dec!(0.65));
+        assert_eq!(prediction.confidence, dec!(0.30));
+
+        assert_eq!(llm.call_count(), 2);
+        assert_eq!(llm.calls_for("test-id"), 1);
+        assert_eq!(llm.calls_for("missing"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_mock_llm_without_answer_fails() {
+        let model = MockLlmModel::new();
+        assert!(model.predict(&create_test_market()).await.is_err());
+        assert_eq!(model.call_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_ensemble_renormalizes_over_surviving_models() {
+        let mut ensemble = EnsembleModel::new();
+        ensemble.add_model(Box::new(MockLlmModel::new()), dec!(0.70));
+        ensemble.add_model(
+            Box::new(MockLlmModel::new().with_prediction("test-id", canned(dec!(0.62), dec!(0.40)))),
+            dec!(0.30),
+        );
+
+        // The failing model's 70% goes to the survivor, not to a zero
+        let prediction = ensemble.predict(&create_test_market()).await.unwrap();
+        assert_eq!(prediction.probability, dec!(0.62));
+        assert_eq!(prediction.confidence, dec!(0.40));
+        assert!(prediction.reasoning.contains("MockLLM: 62% (weight 100%)"), "{}", prediction.reasoning);
+        assert!(prediction.reasoning.contains("1 failed"));
+    }
+
+    #[tokio::test]
+    async fn test_ensemble_fails_only_when_every_model_fails() {
+        let mut ensemble = EnsembleModel::new();
+        ensemble.add_model(Box::new(MockLlmModel::new()), dec!(0.70));
+        ensemble.add_model(Box::new(MockLlmModel::new()), dec!(0.30));
+        let err = ensemble.predict(&create_test_market()).await.unwrap_err();
+        assert!(err.to_string().contains("All models failed"), "{}", err);
+
+        // With nothing configured there is nothing to fail
+        let empty = EnsembleModel::new().predict(&create_test_market()).await.unwrap();
+        assert_eq!(empty.confidence, Decimal::ZERO);
+    }
 }
@@ -2,6 +2,10 @@
 
 pub mod dashboard;
 pub mod market_state;
+pub mod netting;
+pub mod pnl_alerts;
+pub mod ratios;
+pub mod settlement;
 
 pub use dashboard::{
     DashboardState, DashboardMetrics, TradeEntry, PositionEntry, AlertEntry,
@@ -12,18 +16,94 @@ pub use market_state::{
     MarketStateMonitor, MarketStateConfig, MarketState, VolatilityRegime,
     TradingRecommendation, Alert, AlertType, AlertSeverity, Anomaly, AnomalyType
 };
+pub use netting::{net_exposures, Leg, NetExposure, OutcomeTokens};
+pub use pnl_alerts::PnlAlertTracker;
+pub use settlement::{Settlement, SettlementMonitor, SettlementNotifier, DEFAULT_RESOLUTIONS_PER_POLL};
 
 #[cfg(test)]
 mod tests;
 
 use rust_decimal::Decimal;
-use std::collections::VecDeque;
-use tokio::sync::RwLock;
+use crate::config::PnlAlertConfig;
+use crate::executor::{DeadMansSwitch, RiskAlertSink};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Mutex, RwLock};
 
 /// Performance monitor
 pub struct Monitor {
     trades: RwLock<VecDeque<TradeRecord>>,
     max_history: usize,
+    /// Net position per market, built from recorded trades
+    positions: RwLock<HashMap<String, MarketPosition>>,
+    /// Token id -> market id for fills recorded with `record_fill`
+    tokens: RwLock<HashMap<String, String>>,
+    /// Markets whose Yes and No fills are netted into one position
+    outcome_tokens: RwLock<OutcomeTokens>,
+    /// Kicked on every `log_stats`
+    heartbeat: Option<Arc<DeadMansSwitch>>,
+    /// Checked on every `update_mark`
+    pnl_alerts: Option<Mutex<PnlAlertTracker>>,
+    /// Receive take-profit and stop-loss alerts
+    risk_alerts: Vec<Arc<dyn RiskAlertSink>>,
+}
+
+/// Net position in one market, average-cost accounting
+#[derive(Debug, Clone, Default)]
+struct MarketPosition {
+    /// Signed size: positive long, negative short
+    size: Decimal,
+    avg_entry: Decimal,
+    mark: Decimal,
+    realized_pnl: Decimal,
+}
+
+impl MarketPosition {
+    fn apply_fill(&mut self, signed_size: Decimal, price: Decimal) {
+        if self.size.is_zero() || self.size.is_sign_positive() == signed_size.is_sign_positive() {
+            let total = self.size.abs() + signed_size.abs();
+            self.avg_entry = (self.avg_entry * self.size.abs() + price * signed_size.abs()) / total;
+            self.size += signed_size;
+        } else {
+            let closing = signed_size.abs().min(self.size.abs());
+            let direction = if self.size.is_sign_positive() { Decimal::ONE } else { -Decimal::ONE };
+            self.realized_pnl += (price - self.avg_entry) * closing * direction;
+            self.size += signed_size;
+            if self.size.is_zero() {
+                self.avg_entry = Decimal::ZERO;
+            } else if self.size.is_sign_positive() != direction.is_sign_positive() {
+                // Flipped through zero: the remainder opened at this price
+                self.avg_entry = price;
+            }
+        }
+        self.mark = price;
+    }
+
+    fn summary(&self, market_id: &str) -> MarketSummary {
+        MarketSummary {
+            market_id: market_id.to_string(),
+            position: self.size,
+            avg_entry: self.avg_entry,
+            mark: self.mark,
+            unrealized_pnl: (self.mark - self.avg_entry) * self.size,
+            realized_pnl: self.realized_pnl,
+        }
+    }
+}
+
+/// Live position and PnL for one market
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketSummary {
+    pub market_id: String,
+    /// Signed size: positive long, negative short
+    pub position: Decimal,
+    /// Average entry price of the open position (0 when flat)
+    pub avg_entry: Decimal,
+    /// Latest mark, or the last fill price if never marked
+    pub mark: Decimal,
+    pub unrealized_pnl: Decimal,
+    pub realized_pnl: Decimal,
 }
 
 #[derive(Debug, Clone)]
@@ -45,6 +125,10 @@ pub struct PerformanceStats {
     pub total_pnl: Decimal,
     pub avg_pnl_per_trade: Decimal,
     pub sharpe_ratio: Option<Decimal>,
+    /// Annualized mean return over downside deviation (see `ratios`)
+    pub sortino_ratio: Option<Decimal>,
+    /// Annualized return over maximum drawdown (see `ratios`)
+    pub calmar_ratio: Option<Decimal>,
 }
 
 impl Monitor {
@@ -52,10 +136,49 @@ impl Monitor {
         Self {
             trades: RwLock::new(VecDeque::with_capacity(max_history)),
             max_history,
+            positions: RwLock::new(HashMap::new()),
+            tokens: RwLock::new(HashMap::new()),
+            outcome_tokens: RwLock::new(OutcomeTokens::new()),
+            heartbeat: None,
+            pnl_alerts: None,
+            risk_alerts: Vec::new(),
         }
     }
 
+    /// Kick `switch` each time stats are logged, so trading halts if the
+    /// monitor stops reporting
+    pub fn with_dead_mans_switch(mut self, switch: Arc<DeadMansSwitch>) -> Self {
+        self.heartbeat = Some(switch);
+        self
+    }
+
+    /// Alert when a marked position crosses a take-profit or stop-loss
+    /// threshold
+    pub fn with_pnl_alerts(mut self, config: PnlAlertConfig) -> Self {
+        self.pnl_alerts = Some(Mutex::new(PnlAlertTracker::new(config)));
+        self
+    }
+
+    /// Also send PnL threshold alerts to `alerts`
+    pub fn with_risk_alerts(mut self, alerts: Arc<dyn RiskAlertSink>) -> Self {
+        self.risk_alerts.push(alerts);
+        self
+    }
+
+    /// Record a fill; `side` is "BUY" or "SELL" (case-insensitive)
     pub async fn record_trade(&self, record: TradeRecord) {
+        let signed_size = if record.side.eq_ignore_ascii_case("sell") {
+            -record.size
+        } else {
+            record.size
+        };
+        self.positions
+            .write()
+            .await
+            .entry(record.market_id.clone())
+            .or_default()
+            .apply_fill(signed_size, record.price);
+
         let mut trades = self.trades.write().await;
         if trades.len() >= self.max_history {
             trades.pop_front();
@@ -102,9 +225,145 @@ impl Monitor {
             total_pnl,
             avg_pnl_per_trade: avg_pnl,
             sharpe_ratio: None, // TODO: Calculate Sharpe ratio
+            sortino_ratio: None,
+            calmar_ratio: None,
+        }
+    }
+
+    /// Net the market's Yes and No fills into one position
+    ///
+    /// Fills of its No token are then booked as the equivalent Yes fill
+    /// (buying No at `p` sells Yes at `1 - p`), and the market's summary
+    /// reports the net Yes exposure. Register before recording its fills.
+    pub async fn register_market(&self, market: &crate::types::Market) {
+        let mut outcome_tokens = self.outcome_tokens.write().await;
+        outcome_tokens.register(market);
+        let mut tokens = self.tokens.write().await;
+        for outcome in &market.outcomes {
+            if outcome_tokens.leg(&outcome.token_id).is_some() {
+                tokens.insert(outcome.token_id.clone(), market.id.clone());
+            }
+        }
+    }
+
+    /// Record an executed trade, remembering its token for `update_mark`
+    pub async fn record_fill(&self, trade: &crate::types::Trade) {
+        self.tokens
+            .write()
+            .await
+            .insert(trade.token_id.clone(), trade.market_id.clone());
+        let (side, price) = self
+            .outcome_tokens
+            .read()
+            .await
+            .to_yes_terms(&trade.token_id, trade.side, trade.price);
+        self.record_trade(TradeRecord {
+            timestamp: trade.timestamp,
+            market_id: trade.market_id.clone(),
+            side: match side {
+                crate::types::Side::Buy => "BUY".to_string(),
+                crate::types::Side::Sell => "SELL".to_string(),
+            },
+            size: trade.size,
+            price,
+            pnl: None,
+        })
+        .await;
+    }
+
+    /// Mark an open position to a new price
+    ///
+    /// `id` is the traded token id (for fills recorded with `record_fill`) or
+    /// the market id. Prices for anything without a position are ignored.
+    /// A registered market's No price marks its position at `1 - price`.
+    /// Crossed PnL thresholds are alerted (see `with_pnl_alerts`).
+    pub async fn update_mark(&self, id: &str, price: Decimal) {
+        let market_id = self.tokens.read().await.get(id).cloned();
+        let market_id = market_id.as_deref().unwrap_or(id);
+        let (_, price) = self
+            .outcome_tokens
+            .read()
+            .await
+            .to_yes_terms(id, crate::types::Side::Buy, price);
+        let summary = match self.positions.write().await.get_mut(market_id) {
+            Some(position) => {
+                position.mark = price;
+                position.summary(market_id)
+            }
+            None => return,
+        };
+        self.check_pnl_thresholds(&summary).await;
+    }
+
+    async fn check_pnl_thresholds(&self, summary: &MarketSummary) {
+        let Some(tracker) = &self.pnl_alerts else { return };
+        let alerts = tracker.lock().await.check(summary);
+        for alert in &alerts {
+            tracing::info!("{}", alert.message);
+            for sink in &self.risk_alerts {
+                if let Err(e) = sink.risk_alert(alert).await {
+                    tracing::warn!("Failed to send PnL alert: {}", e);
+                }
+            }
+        }
+    }
+
+    /// Unrealized PnL across all open positions at their latest marks
+    pub async fn unrealized_pnl(&self) -> Decimal {
+        self.positions
+            .read()
+            .await
+            .values()
+            .map(|p| (p.mark - p.avg_entry) * p.size)
+            .sum()
+    }
+
+    /// Cash plus the marked value of open positions
+    pub async fn portfolio_value(&self, balance: Decimal) -> Decimal {
+        let positions: Decimal = self
+            .positions
+            .read()
+            .await
+            .values()
+            .map(|p| p.size * p.mark)
+            .sum();
+        balance + positions
+    }
+
+    /// Position and PnL for a market that has recorded trades
+    pub async fn market_summary(&self, market_id: &str) -> Option<MarketSummary> {
+        self.positions
+            .read()
+            .await
+            .get(market_id)
+            .map(|p| p.summary(market_id))
+    }
+
+    /// Summaries for every traded market, sorted by market id
+    pub async fn all_summaries(&self) -> Vec<MarketSummary> {
+        let positions = self.positions.read().await;
+        let mut summaries: Vec<_> = positions.iter().map(|(id, p)| p.summary(id)).collect();
+        summaries.sort_by(|a, b| a.market_id.cmp(&b.market_id));
+        summaries
+    }
+
+    /// Kick the dead man's switch every `interval()`, apart from the trading loop
+    ///
+    /// `interval` is called before each wait so reloaded config applies.
+    /// Returns at once when no switch is attached.
+    pub async fn kick_every(&self, interval: impl Fn() -> Duration) {
+        let Some(switch) = &self.heartbeat else {
+            return;
+        };
+        loop {
+            tokio::time::sleep(interval()).await;
+            // Reading the stats proves the monitor's state isn't wedged
+            self.get_stats().await;
+            switch.kick();
         }
     }
 
+    /// Log a performance summary
     pub async fn log_stats(&self) {
         let stats = self.get_stats().await;
         tracing::info!(
@@ -0,0 +1,156 @@
+//! Netting Yes and No positions in the same market
+//!
+//! The Yes and No tokens of a binary market are complementary: exactly one
+//! pays $1 at resolution, so a No share bought at `p` is a short Yes share
+//! entered at `1 - p`. Tracked as separate token positions, a Buy-No after
+//! a Buy-Yes looks like a second bet rather than a reversal of the first.
+//! Here positions are expressed in Yes terms so each market reports one net
+//! directional exposure.
+
+use crate::types::{Market, Position, Side};
+use rust_decimal::Decimal;
+use std::collections::{BTreeMap, HashMap};
+
+/// Which token of a binary market a token is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Leg {
+    Yes,
+    No,
+}
+
+/// Yes and No tokens of binary markets
+#[derive(Debug, Clone, Default)]
+pub struct OutcomeTokens {
+    /// Token id -> (market id, leg)
+    legs: HashMap<String, (String, Leg)>,
+}
+
+impl OutcomeTokens {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_markets<'a>(markets: impl IntoIterator<Item = &'a Market>) -> Self {
+        let mut tokens = Self::new();
+        for market in markets {
+            tokens.register(market);
+        }
+        tokens
+    }
+
+    /// Remember a market's Yes and No tokens
+    ///
+    /// Markets without both a Yes and a No outcome are ignored.
+    pub fn register(&mut self, market: &Market) {
+        let (Some(yes), Some(no)) = (market.outcome("yes"), market.outcome("no")) else {
+            return;
+        };
+        self.legs.insert(yes.token_id.clone(), (market.id.clone(), Leg::Yes));
+        self.legs.insert(no.token_id.clone(), (market.id.clone(), Leg::No));
+    }
+
+    /// Market and leg of a registered token
+    pub fn leg(&self, token_id: &str) -> Option<(&str, Leg)> {
+        self.legs.get(token_id).map(|(market_id, leg)| (market_id.as_str(), *leg))
+    }
+
+    /// A fill of `token_id` as the equivalent fill of the market's Yes token
+    ///
+    /// Buying No at `p` is selling Yes at `1 - p` and vice versa. Yes and
+    /// unregistered tokens are returned unchanged.
+    pub fn to_yes_terms(&self, token_id: &str, side: Side, price: Decimal) -> (Side, Decimal) {
+        match self.leg(token_id) {
+            Some((_, Leg::No)) => (opposite(side), Decimal::ONE - price),
+            _ => (side, price),
+        }
+    }
+}
+
+/// One market's positions netted into a single Yes exposure
+#[derive(Debug, Clone, PartialEq)]
+pub struct NetExposure {
+    pub market_id: String,
+    /// Net Yes shares: positive is long Yes, negative is long No
+    pub net_yes: Decimal,
+    /// Average entry of the net exposure as a Yes price (0 when flat)
+    pub avg_entry: Decimal,
+    /// Shares held on both sides that cancel out; each such Yes/No pair
+    /// pays exactly $1 at resolution
+    pub offset: Decimal,
+    /// P&L fixed by the offsetting pairs whatever the market resolves to
+    pub locked_pnl: Decimal,
+}
+
+/// Long and short Yes-equivalent shares with their entry value
+#[derive(Debug, Default)]
+struct Sides {
+    long: Decimal,
+    long_value: Decimal,
+    short: Decimal,
+    short_value: Decimal,
+}
+
+impl Sides {
+    fn avg(value: Decimal, size: Decimal) -> Decimal {
+        if size.is_zero() {
+            Decimal::ZERO
+        } else {
+            value / size
+        }
+    }
+}
+
+/// Net every registered market's Yes and No positions, sorted by market id
+///
+/// Positions in tokens `tokens` does not know are left out.
+pub fn net_exposures(positions: &[Position], tokens: &OutcomeTokens) -> Vec<NetExposure> {
+    let mut markets: BTreeMap<&str, Sides> = BTreeMap::new();
+    for position in positions.iter().filter(|p| p.size > Decimal::ZERO) {
+        let Some((market_id, _)) = tokens.leg(&position.token_id) else {
+            continue;
+        };
+        let (side, price) = tokens.to_yes_terms(&position.token_id, position.side, position.avg_entry_price);
+        let sides = markets.entry(market_id).or_default();
+        match side {
+            Side::Buy => {
+                sides.long += position.size;
+                sides.long_value += position.size * price;
+            }
+            Side::Sell => {
+                sides.short += position.size;
+                sides.short_value += position.size * price;
+            }
+        }
+    }
+
+    markets
+        .into_iter()
+        .map(|(market_id, sides)| {
+            let long_entry = Sides::avg(sides.long_value, sides.long);
+            let short_entry = Sides::avg(sides.short_value, sides.short);
+            let offset = sides.long.min(sides.short);
+            let net_yes = sides.long - sides.short;
+            let avg_entry = if net_yes > Decimal::ZERO {
+                long_entry
+            } else if net_yes < Decimal::ZERO {
+                short_entry
+            } else {
+                Decimal::ZERO
+            };
+            NetExposure {
+                market_id: market_id.to_string(),
+                net_yes,
+                avg_entry,
+                offset,
+                locked_pnl: offset * (short_entry - long_entry),
+            }
+        })
+        .collect()
+}
+
+fn opposite(side: Side) -> Side {
+    match side {
+        Side::Buy => Side::Sell,
+        Side::Sell => Side::Buy,
+    }
+}
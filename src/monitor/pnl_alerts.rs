@@ -0,0 +1,85 @@
+//! Take-profit and stop-loss alerts
+//!
+//! Settlement notifications arrive only once a market resolves. The
+//! tracker watches each open position's unrealized return as it is marked
+//! and raises an alert the first time it crosses `take_profit_pct` or
+//! `-stop_loss_pct`. A fired threshold re-arms only once the return falls
+//! back `hysteresis_pct` inside it, so a price hovering at the boundary
+//! alerts once.
+
+use super::MarketSummary;
+use crate::config::PnlAlertConfig;
+use crate::events::RiskPayload;
+use rust_decimal::Decimal;
+use std::collections::HashMap;
+
+/// Thresholds already alerted for one position
+#[derive(Debug, Clone, Copy, Default)]
+struct Fired {
+    profit: bool,
+    loss: bool,
+}
+
+/// Tracks which positions have crossed their PnL thresholds
+#[derive(Debug)]
+pub struct PnlAlertTracker {
+    config: PnlAlertConfig,
+    fired: HashMap<String, Fired>,
+}
+
+impl PnlAlertTracker {
+    pub fn new(config: PnlAlertConfig) -> Self {
+        Self {
+            config,
+            fired: HashMap::new(),
+        }
+    }
+
+    /// Alerts for thresholds `summary` crossed since the last check
+    ///
+    /// A flat position forgets its state, so a new position alerts afresh.
+    pub fn check(&mut self, summary: &MarketSummary) -> Vec<RiskPayload> {
+        let Some(ret) = return_on_cost(summary) else {
+            self.fired.remove(&summary.market_id);
+            return Vec::new();
+        };
+        let take_profit = self.config.take_profit_pct;
+        let stop_loss = -self.config.stop_loss_pct;
+        let hysteresis = self.config.hysteresis_pct;
+        let fired = self.fired.entry(summary.market_id.clone()).or_default();
+        let mut alerts = Vec::new();
+
+        if ret >= take_profit {
+            if !fired.profit {
+                fired.profit = true;
+                alerts.push(RiskPayload::position_pnl(&summary.market_id, ret, take_profit));
+            }
+        } else if ret < take_profit - hysteresis {
+            fired.profit = false;
+        }
+
+        if ret <= stop_loss {
+            if !fired.loss {
+                fired.loss = true;
+                alerts.push(RiskPayload::position_pnl(&summary.market_id, ret, stop_loss));
+            }
+        } else if ret > stop_loss + hysteresis {
+            fired.loss = false;
+        }
+
+        alerts
+    }
+}
+
+/// Unrealized PnL over the cost of the open position
+///
+/// A short Yes position is held as No, which cost `1 - avg_entry` a share.
+fn return_on_cost(summary: &MarketSummary) -> Option<Decimal> {
+    let unit_cost = if summary.position > Decimal::ZERO {
+        summary.avg_entry
+    } else {
+        Decimal::ONE - summary.avg_entry
+    };
+    let cost = unit_cost * summary.position.abs();
+    (!cost.is_zero()).then(|| summary.unrealized_pnl / cost)
+}
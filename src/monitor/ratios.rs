@@ -0,0 +1,74 @@
+//! Risk-adjusted return ratios
+//!
+//! Both work on a series of periodic returns (0.01 = +1%). Sortino
+//! annualizes with `periods_per_year`, e.g. `DAYS_PER_YEAR` for daily
+//! returns, and Calmar over the years the returns span. Polymarket trades
+//! every day, so a year is 365 days, not 252.
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+/// Periods per year for a daily return series
+pub const DAYS_PER_YEAR: u32 = 365;
+
+/// Annualized Sortino ratio: mean return over downside deviation
+///
+/// Downside deviation is the root mean square of the negative returns
+/// over all periods. `None` with fewer than two returns or no losing
+/// period to measure risk by.
+pub fn sortino_ratio(returns: &[Decimal], periods_per_year: u32) -> Option<Decimal> {
+    if returns.len() < 2 {
+        return None;
+    }
+    let n = Decimal::from(returns.len());
+    let mean = returns.iter().sum::<Decimal>() / n;
+    let downside = returns
+        .iter()
+        .filter(|r| r.is_sign_negative())
+        .map(|r| r * r)
+        .sum::<Decimal>()
+        / n;
+    let downside_dev = downside.to_f64()?.sqrt();
+    if downside_dev <= 0.0 {
+        return None;
+    }
+    let ratio = mean.to_f64()? / downside_dev * f64::from(periods_per_year).sqrt();
+    Decimal::from_f64_retain(ratio)
+}
+
+/// Largest peak-to-trough fall of the equity compounded from `returns`,
+/// as a fraction of the peak
+///
+/// Equity is tracked relative to its running peak, so long winning
+/// streaks cannot overflow.
+pub fn max_drawdown(returns: &[Decimal]) -> Decimal {
+    let mut below_peak = Decimal::ONE;
+    let mut worst = Decimal::ZERO;
+    for r in returns {
+        below_peak = (below_peak * (Decimal::ONE + r)).min(Decimal::ONE);
+        worst = worst.max(Decimal::ONE - below_peak);
+    }
+    worst
+}
+
+/// Calmar ratio: compound annual return over maximum drawdown
+///
+/// `years` is the time the returns span, e.g. from the first settlement
+/// to the last, so gaps between periods count. Growth is compounded in
+/// `f64`. `None` with no returns, no time elapsed or no drawdown to
+/// divide by.
+pub fn calmar_ratio(returns: &[Decimal], years: f64) -> Option<Decimal> {
+    let drawdown = max_drawdown(returns);
+    if returns.is_empty() || drawdown.is_zero() || years <= 0.0 {
+        return None;
+    }
+    let growth = returns
+        .iter()
+        .map(|r| (Decimal::ONE + r).to_f64())
+        .product::<Option<f64>>()?;
+    let annual = growth.max(0.0).powf(1.0 / years) - 1.0;
+    if !annual.is_finite() {
+        return None;
+    }
+    Decimal::from_f64_retain(annual / drawdown.to_f64()?)
+}
@@ -0,0 +1,295 @@
+//! Settlement of positions in resolved markets
+//!
+//! `SettlementMonitor` polls Gamma for the markets of open positions. Once a
+//! market has resolved, each position in it is closed out at the payout
+//! price (the winning outcome pays $1 a share, every other outcome $0), the
+//! realized P&L is recorded in the database, and a `Fill` event and a
+//! notification are sent.
+//!
+//! It also records how every recently closed market on Gamma resolved,
+//! held or not, so `BaseRateModel` learns from the whole market.
+
+use crate::client::mock::{ClobClientTrait, GammaClientTrait};
+use crate::error::Result;
+use crate::events::{Event, EventBus, EventPayload, EventPriority, EventType, FillPayload, OrderSide};
+use crate::notify::Notifier;
+use crate::storage::Database;
+use crate::types::{Market, Outcome, Position, Side};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::collections::{BTreeMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Price at or above which a closed market's outcome counts as the winner
+const RESOLVED_PRICE: Decimal = dec!(0.99);
+
+/// Closed markets `run_resolutions` checks per poll unless configured
+pub const DEFAULT_RESOLUTIONS_PER_POLL: usize = 500;
+
+/// A position closed out by market resolution
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settlement {
+    pub market_id: String,
+    pub question: String,
+    pub token_id: String,
+    /// Name of the outcome the market resolved to
+    pub winning_outcome: String,
+    pub side: Side,
+    pub size: Decimal,
+    pub entry_price: Decimal,
+    /// $1 if the position's token won, $0 otherwise
+    pub payout_price: Decimal,
+    pub realized_pnl: Decimal,
+    pub settled_at: DateTime<Utc>,
+}
+
+impl Settlement {
+    /// Whether the position was on the right side of the resolution
+    pub fn won(&self) -> bool {
+        (self.payout_price == Decimal::ONE) == (self.side == Side::Buy)
+    }
+}
+
+/// The outcome a closed market resolved to, if it has resolved
+///
+/// Gamma reports a resolved market as closed with the winning outcome
+/// priced at 1. A closed market without a clear winner has not settled yet.
+pub fn winning_outcome(market: &Market) -> Option<&Outcome> {
+    if !market.closed {
+        return None;
+    }
+    let mut winners = market.outcomes.iter().filter(|o| o.price >= RESOLVED_PRICE);
+    match (winners.next(), winners.next()) {
+        (Some(winner), None) => Some(winner),
+        _ => None,
+    }
+}
+
+/// Receives settlement notifications (allows mocking)
+#[async_trait]
+pub trait SettlementNotifier: Send + Sync {
+    async fn settlement(&self, settlement: &Settlement) -> Result<()>;
+}
+
+#[async_trait]
+impl SettlementNotifier for Notifier {
+    async fn settlement(&self, settlement: &Settlement) -> Result<()> {
+        Notifier::settlement(self, settlement).await
+    }
+}
+
+/// Closes out positions in markets that have resolved
+pub struct SettlementMonitor<G> {
+    gamma: G,
+    db: Option<Arc<Database>>,
+    events: Option<Arc<EventBus>>,
+    notifier: Option<Arc<dyn SettlementNotifier>>,
+    /// Token ids already settled, so positions the exchange still reports
+    /// until redemption are not settled twice
+    settled: Mutex<HashSet<String>>,
+}
+
+impl<G: GammaClientTrait> SettlementMonitor<G> {
+    pub fn new(gamma: G) -> Self {
+        Self {
+            gamma,
+            db: None,
+            events: None,
+            notifier: None,
+            settled: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Record settlements in the database
+    pub fn with_database(mut self, db: Arc<Database>) -> Self {
+        self.db = Some(db);
+        self
+    }
+
+    /// Publish a `Fill` event for every settlement
+    pub fn with_event_bus(mut self, events: Arc<EventBus>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Where to send settlement notifications
+    pub fn with_notifier(mut self, notifier: Arc<dyn SettlementNotifier>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    /// Settle the open positions whose markets have resolved
+    ///
+    /// Markets that cannot be fetched and settlements that cannot be saved
+    /// are logged and retried on the next check. Returns the settlements
+    /// made by this call.
+    pub async fn check(&self, positions: &[Position]) -> Vec<Settlement> {
+        let mut by_market: BTreeMap<&str, Vec<&Position>> = BTreeMap::new();
+        {
+            let settled = self.settled.lock().unwrap();
+            for position in positions {
+                if position.size > Decimal::ZERO && !settled.contains(&position.token_id) {
+                    by_market.entry(position.market_id.as_str()).or_default().push(position);
+                }
+            }
+        }
+
+        let mut settlements = Vec::new();
+        for (market_id, positions) in by_market {
+            let market = match self.gamma.get_market(market_id).await {
+                Ok(market) => market,
+                Err(e) => {
+                    tracing::warn!("Failed to check resolution of {}: {}", market_id, e);
+                    continue;
+                }
+            };
+            let Some(winner) = winning_outcome(&market) else {
+                continue;
+            };
+            if let Some(db) = &self.db {
+                // Feeds base rates for similar markets
+                if let Err(e) = db.record_resolution(&market, winner).await {
+                    tracing::warn!("Failed to record resolution of {}: {}", market.id, e);
+                }
+            }
+
+            for position in positions {
+                let settlement = settle(&market, winner, position);
+                if let Some(db) = &self.db {
+                    match db.record_settlement(&settlement).await {
+                        Ok(true) => {}
+                        Ok(false) => {
+                            // Settled before a restart; nothing left to report
+                            self.settled.lock().unwrap().insert(settlement.token_id.clone());
+                            continue;
+                        }
+                        Err(e) => {
+                            tracing::warn!("Failed to record settlement of {}: {}", settlement.token_id, e);
+                            continue;
+                        }
+                    }
+                }
+                self.settled.lock().unwrap().insert(settlement.token_id.clone());
+                tracing::info!(
+                    "Settled {} {} of {} at ${} ({}): P&L ${:.2}",
+                    settlement.size,
+                    settlement.token_id,
+                    settlement.market_id,
+                    settlement.payout_price,
+                    settlement.winning_outcome,
+                    settlement.realized_pnl
+                );
+                self.publish(&settlement).await;
+                if let Some(notifier) = &self.notifier {
+                    if let Err(e) = notifier.settlement(&settlement).await {
+                        tracing::warn!("Failed to send settlement notification: {}", e);
+                    }
+                }
+                settlements.push(settlement);
+            }
+        }
+        settlements
+    }
+
+    /// Check the exchange's open positions every `every`, forever
+    pub async fn run<C: ClobClientTrait>(&self, clob: &C, every: Duration) {
+        let mut interval = tokio::time::interval(every);
+        loop {
+            interval.tick().await;
+            match clob.get_positions().await {
+                Ok(positions) => {
+                    self.check(&positions).await;
+                }
+                Err(e) => tracing::warn!("Failed to fetch positions for settlement: {}", e),
+            }
+        }
+    }
+
+    /// Record the resolutions of the `limit` most recently closed markets
+    ///
+    /// Needs a database; markets already recorded or without a winner yet
+    /// are skipped. Returns how many resolutions were new.
+    pub async fn record_resolutions(&self, limit: usize) -> Result<usize> {
+        let Some(db) = &self.db else {
+            return Ok(0);
+        };
+        let mut recorded = 0;
+        for market in self.gamma.get_closed_markets(limit).await? {
+            let Some(winner) = winning_outcome(&market) else {
+                continue;
+            };
+            match db.record_resolution(&market, winner).await {
+                Ok(true) => recorded += 1,
+                Ok(false) => {}
+                Err(e) => tracing::warn!("Failed to record resolution of {}: {}", market.id, e),
+            }
+        }
+        Ok(recorded)
+    }
+
+    /// Record recently closed markets' resolutions every `every`, forever
+    pub async fn run_resolutions(&self, every: Duration, limit: usize) {
+        let mut interval = tokio::time::interval(every);
+        loop {
+            interval.tick().await;
+            match self.record_resolutions(limit).await {
+                Ok(0) => {}
+                Ok(n) => tracing::info!("Recorded {} market resolutions", n),
+                Err(e) => tracing::warn!("Failed to fetch closed markets: {}", e),
+            }
+        }
+    }
+
+    async fn publish(&self, settlement: &Settlement) {
+        let Some(events) = &self.events else { return };
+        let side = match settlement.side {
+            Side::Buy => OrderSide::Sell,
+            Side::Sell => OrderSide::Buy,
+        };
+        let mut fill = FillPayload::new(
+            &settlement_order_id(&settlement.market_id),
+            &settlement.token_id,
+            side,
+            settlement.size,
+            settlement.payout_price,
+        );
+        fill.executed_at = settlement.settled_at;
+        fill.realized_pnl = Some(settlement.realized_pnl);
+        let event = Event::new(EventType::Fill, "settlement", EventPayload::Fill(fill))
+            .with_priority(EventPriority::Critical);
+        if let Err(e) = events.publish(event).await {
+            tracing::warn!("Failed to publish settlement of {}: {}", settlement.token_id, e);
+        }
+    }
+}
+
+/// Order id recorded for the closing trade of a settlement
+pub fn settlement_order_id(market_id: &str) -> String {
+    format!("settlement-{}", market_id)
+}
+
+/// Close a position at its resolution payout
+fn settle(market: &Market, winner: &Outcome, position: &Position) -> Settlement {
+    let payout_price = if position.token_id == winner.token_id { Decimal::ONE } else { Decimal::ZERO };
+    let closing_side = match position.side {
+        Side::Buy => Side::Sell,
+        Side::Sell => Side::Buy,
+    };
+    let realized_pnl = position.clone().apply_fill(closing_side, position.size, payout_price, Decimal::ZERO);
+
+    Settlement {
+        market_id: market.id.clone(),
+        question: market.question.clone(),
+        token_id: position.token_id.clone(),
+        winning_outcome: winner.outcome.clone(),
+        side: position.side,
+        size: position.size,
+        entry_price: position.avg_entry_price,
+        payout_price,
+        realized_pnl,
+        settled_at: Utc::now(),
+    }
+}
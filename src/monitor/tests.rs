@@ -73,6 +73,8 @@ mod tests {
             total_pnl: dec!(500),
             avg_pnl_per_trade: dec!(50),
             sharpe_ratio: Some(dec!(1.5)),
+            sortino_ratio: None,
+            calmar_ratio: None,
         };
         
         let cloned = stats.clone();
@@ -237,4 +239,462 @@ mod tests {
         assert_eq!(stats.total_pnl, dec!(30));
         assert_eq!(stats.avg_pnl_per_trade, dec!(10));
     }
+
+    fn fill(market_id: &str, side: &str, size: Decimal, price: Decimal) -> TradeRecord {
+        TradeRecord {
+            timestamp: Utc::now(),
+            market_id: market_id.to_string(),
+            side: side.to_string(),
+            size,
+            price,
+            pnl: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_market_summary_unrealized_pnl() {
+        let monitor = Monitor::new(10);
+        monitor.record_trade(fill("m1", "BUY", dec!(100), dec!(0.40))).await;
+        monitor.record_trade(fill("m1", "BUY", dec!(100), dec!(0.50))).await;
+        monitor.update_mark("m1", dec!(0.60)).await;
+
+        let summary = monitor.market_summary("m1").await.unwrap();
+        assert_eq!(summary.position, dec!(200));
+        assert_eq!(summary.avg_entry, dec!(0.45));
+        assert_eq!(summary.mark, dec!(0.60));
+        // (0.60 - 0.45) * 200
+        assert_eq!(summary.unrealized_pnl, dec!(30));
+        assert_eq!(summary.realized_pnl, Decimal::ZERO);
+        assert!(monitor.market_summary("m2").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_market_summary_partial_close_realizes_pnl() {
+        let monitor = Monitor::new(10);
+        monitor.record_trade(fill("m1", "BUY", dec!(200), dec!(0.45))).await;
+        monitor.record_trade(fill("m1", "SELL", dec!(50), dec!(0.55))).await;
+        monitor.update_mark("m1", dec!(0.50)).await;
+
+        let summary = monitor.market_summary("m1").await.unwrap();
+        assert_eq!(summary.position, dec!(150));
+        assert_eq!(summary.avg_entry, dec!(0.45));
+        // (0.55 - 0.45) * 50
+        assert_eq!(summary.realized_pnl, dec!(5));
+        // (0.50 - 0.45) * 150
+        assert_eq!(summary.unrealized_pnl, dec!(7.5));
+    }
+
+    #[tokio::test]
+    async fn test_market_summary_flip_and_flat() {
+        let monitor = Monitor::new(10);
+        monitor.record_trade(fill("m1", "BUY", dec!(100), dec!(0.50))).await;
+        monitor.record_trade(fill("m1", "sell", dec!(150), dec!(0.40))).await;
+
+        let summary = monitor.market_summary("m1").await.unwrap();
+        assert_eq!(summary.position, dec!(-50));
+        assert_eq!(summary.avg_entry, dec!(0.40));
+        assert_eq!(summary.realized_pnl, dec!(-10));
+
+        monitor.update_mark("m1", dec!(0.30)).await;
+        // Short gains as the price falls
+        assert_eq!(monitor.market_summary("m1").await.unwrap().unrealized_pnl, dec!(5));
+
+        monitor.record_trade(fill("m1", "BUY", dec!(50), dec!(0.30))).await;
+        let summary = monitor.market_summary("m1").await.unwrap();
+        assert_eq!(summary.position, Decimal::ZERO);
+        assert_eq!(summary.unrealized_pnl, Decimal::ZERO);
+        assert_eq!(summary.realized_pnl, dec!(-5));
+    }
+
+    #[tokio::test]
+    async fn test_all_summaries_sorted() {
+        let monitor = Monitor::new(10);
+        monitor.record_trade(fill("m2", "BUY", dec!(10), dec!(0.50))).await;
+        monitor.record_trade(fill("m1", "BUY", dec!(10), dec!(0.50))).await;
+
+        let ids: Vec<_> = monitor.all_summaries().await.into_iter().map(|s| s.market_id).collect();
+        assert_eq!(ids, vec!["m1", "m2"]);
+    }
+
+    #[tokio::test]
+    async fn test_update_mark_by_token_sequence() {
+        let monitor = Monitor::new(10);
+        monitor.record_fill(&crate::types::Trade {
+            id: "t1".to_string(),
+            order_id: "o1".to_string(),
+            token_id: "m1_yes".to_string(),
+            market_id: "m1".to_string(),
+            side: crate::types::Side::Buy,
+            price: dec!(0.50),
+            size: dec!(100),
+            fee: Decimal::ZERO,
+            timestamp: Utc::now(),
+            client_order_id: None,
+            correlation_id: None,
+        }).await;
+
+        let mut unrealized = Vec::new();
+        for mark in [dec!(0.55), dec!(0.60), dec!(0.45)] {
+            monitor.update_mark("m1_yes", mark).await;
+            unrealized.push(monitor.unrealized_pnl().await);
+        }
+        assert_eq!(unrealized, vec![dec!(5), dec!(10), dec!(-5)]);
+
+        // Cash of 950 after paying 50, position now worth 45
+        assert_eq!(monitor.portfolio_value(dec!(950)).await, dec!(995));
+        // Unknown tokens are ignored
+        monitor.update_mark("other", dec!(0.99)).await;
+        assert_eq!(monitor.unrealized_pnl().await, dec!(-5));
+    }
+
+    fn resolved_market(yes_price: rust_decimal::Decimal, closed: bool) -> crate::types::Market {
+        use crate::types::{Market, Outcome};
+        Market {
+            id: "election".to_string(),
+            question: "Will the incumbent win?".to_string(),
+            description: None,
+            end_date: None,
+            volume: dec!(100000),
+            liquidity: dec!(50000),
+            outcomes: vec![
+                Outcome { outcome: "Yes".to_string(), token_id: "yes-token".to_string(), price: yes_price },
+                Outcome { outcome: "No".to_string(), token_id: "no-token".to_string(), price: Decimal::ONE - yes_price },
+            ],
+            active: false,
+            closed,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
+        }
+    }
+
+    fn position(token_id: &str, entry: rust_decimal::Decimal) -> crate::types::Position {
+        crate::types::Position {
+            token_id: token_id.to_string(),
+            market_id: "election".to_string(),
+            side: crate::types::Side::Buy,
+            size: dec!(100),
+            avg_entry_price: entry,
+            current_price: entry,
+            unrealized_pnl: Decimal::ZERO,
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingNotifier {
+        settlements: std::sync::Mutex<Vec<super::super::Settlement>>,
+    }
+
+    #[async_trait::async_trait]
+    impl super::super::SettlementNotifier for RecordingNotifier {
+        async fn settlement(&self, settlement: &super::super::Settlement) -> crate::error::Result<()> {
+            self.settlements.lock().unwrap().push(settlement.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_winning_outcome_needs_a_closed_market() {
+        use super::super::settlement::winning_outcome;
+
+        assert_eq!(winning_outcome(&resolved_market(dec!(1), true)).unwrap().outcome, "Yes");
+        assert_eq!(winning_outcome(&resolved_market(dec!(0), true)).unwrap().outcome, "No");
+        // Trading at 1 but not closed yet, and closed without a winner
+        assert!(winning_outcome(&resolved_market(dec!(1), false)).is_none());
+        assert!(winning_outcome(&resolved_market(dec!(0.5), true)).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_settlement_monitor_closes_out_resolved_market() {
+        use super::super::SettlementMonitor;
+        use crate::client::mock::MockGammaClient;
+        use crate::events::{EventBus, EventPayload};
+        use crate::storage::Database;
+        use std::sync::Arc;
+
+        let gamma = MockGammaClient::new().with_markets(vec![resolved_market(dec!(1), true)]);
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::connect(dir.path().join("bot.db")).await.unwrap());
+        let bus = Arc::new(EventBus::new(16));
+        let mut events = bus.subscribe();
+        let notifier = Arc::new(RecordingNotifier::default());
+        let monitor = SettlementMonitor::new(gamma)
+            .with_database(db.clone())
+            .with_event_bus(bus)
+            .with_notifier(notifier.clone());
+
+        // Long Yes at 0.60 wins $40; long No at 0.35 loses $35
+        let positions = vec![position("yes-token", dec!(0.60)), position("no-token", dec!(0.35))];
+        let settlements = monitor.check(&positions).await;
+
+        assert_eq!(settlements.len(), 2);
+        let yes = settlements.iter().find(|s| s.token_id == "yes-token").unwrap();
+        assert!(yes.won());
+        assert_eq!(yes.winning_outcome, "Yes");
+        assert_eq!(yes.payout_price, dec!(1));
+        assert_eq!(yes.realized_pnl, dec!(40));
+        let no = settlements.iter().find(|s| s.token_id == "no-token").unwrap();
+        assert!(!no.won());
+        assert_eq!(no.payout_price, dec!(0));
+        assert_eq!(no.realized_pnl, dec!(-35));
+
+        // Database holds the realized P&L and the closing trades
+        assert_eq!(db.settled_pnl().await.unwrap(), dec!(5));
+        let trades = db.get_recent_trades(10).await.unwrap();
+        assert_eq!(trades.len(), 2);
+        assert!(trades.iter().all(|t| t.side == crate::types::Side::Sell && t.order_id == "settlement-election"));
+
+        assert_eq!(notifier.settlements.lock().unwrap().len(), 2);
+        let mut pnls = Vec::new();
+        while let Ok(event) = events.try_recv() {
+            if let EventPayload::Fill(fill) = event.payload {
+                pnls.push(fill.realized_pnl.unwrap());
+            }
+        }
+        pnls.sort();
+        assert_eq!(pnls, vec![dec!(-35), dec!(40)]);
+
+        // Positions still reported by the exchange are not settled twice
+        assert!(monitor.check(&positions).await.is_empty());
+        assert_eq!(db.settled_pnl().await.unwrap(), dec!(5));
+    }
+
+    #[tokio::test]
+    async fn test_settlement_monitor_ignores_open_markets() {
+        use super::super::SettlementMonitor;
+        use crate::client::mock::MockGammaClient;
+
+        let gamma = MockGammaClient::new().with_markets(vec![resolved_market(dec!(0.97), false)]);
+        let monitor = SettlementMonitor::new(gamma);
+
+        assert!(monitor.check(&[position("yes-token", dec!(0.60))]).await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_records_resolutions_of_markets_never_held() {
+        use super::super::SettlementMonitor;
+        use crate::client::mock::MockGammaClient;
+        use crate::storage::Database;
+        use std::sync::Arc;
+
+        let categorized = |id: &str, yes_price, closed| crate::types::Market {
+            id: id.to_string(),
+            category: Some("politics".to_string()),
+            ..resolved_market(yes_price, closed)
+        };
+        let gamma = MockGammaClient::new().with_markets(vec![
+            categorized("won", dec!(1), true),
+            categorized("lost", dec!(0), true),
+            // Closed without a winner yet, and still open
+            categorized("pending", dec!(0.5), true),
+            categorized("open", dec!(1), false),
+        ]);
+        let dir = tempfile::tempdir().unwrap();
+        let db = Arc::new(Database::connect(dir.path().join("bot.db")).await.unwrap());
+        let monitor = SettlementMonitor::new(gamma).with_database(db.clone());
+
+        assert_eq!(monitor.record_resolutions(100).await.unwrap(), 2);
+        let stats = db.resolution_stats_by_category("politics").await.unwrap();
+        assert_eq!((stats.resolved, stats.yes_resolved), (2, 1));
+        // Already recorded
+        assert_eq!(monitor.record_resolutions(100).await.unwrap(), 0);
+    }
+
+    fn fill_of(token_id: &str, side: crate::types::Side, price: rust_decimal::Decimal) -> crate::types::Trade {
+        crate::types::Trade {
+            id: format!("{}-{}", token_id, price),
+            order_id: "o1".to_string(),
+            token_id: token_id.to_string(),
+            market_id: "election".to_string(),
+            side,
+            price,
+            size: dec!(100),
+            fee: Decimal::ZERO,
+            timestamp: Utc::now(),
+            client_order_id: None,
+            correlation_id: None,
+        }
+    }
+
+    #[test]
+    fn test_yes_and_no_positions_net_per_market() {
+        use crate::monitor::{net_exposures, OutcomeTokens};
+
+        let tokens = OutcomeTokens::from_markets([&resolved_market(dec!(0.6), false)]);
+
+        // 100 Yes at 0.60 and 100 No at 0.38: flat, with $2 locked in
+        let hedged = net_exposures(&[position("yes-token", dec!(0.60)), position("no-token", dec!(0.38))], &tokens);
+        assert_eq!(hedged.len(), 1);
+        assert_eq!(hedged[0].market_id, "election");
+        assert_eq!(hedged[0].net_yes, Decimal::ZERO);
+        assert_eq!(hedged[0].offset, dec!(100));
+        assert_eq!(hedged[0].locked_pnl, dec!(2));
+
+        // 50 more Yes leave a 50-share long at the Yes entry
+        let mut long_yes = position("yes-token", dec!(0.60));
+        long_yes.size = dec!(150);
+        let net = net_exposures(&[long_yes, position("no-token", dec!(0.38))], &tokens);
+        assert_eq!(net[0].net_yes, dec!(50));
+        assert_eq!(net[0].avg_entry, dec!(0.60));
+        assert_eq!(net[0].offset, dec!(100));
+
+        // No alone is a short Yes entered at 1 - price; unknown tokens are skipped
+        let short = net_exposures(&[position("no-token", dec!(0.38)), position("other", dec!(0.5))], &tokens);
+        assert_eq!(short.len(), 1);
+        assert_eq!(short[0].net_yes, dec!(-100));
+        assert_eq!(short[0].avg_entry, dec!(0.62));
+        assert_eq!(short[0].locked_pnl, Decimal::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_monitor_nets_no_fills_against_yes() {
+        use crate::types::Side;
+
+        let monitor = Monitor::new(10);
+        monitor.register_market(&resolved_market(dec!(0.6), false)).await;
+
+        // Long No after long Yes closes the position instead of adding to it
+        monitor.record_fill(&fill_of("yes-token", Side::Buy, dec!(0.60))).await;
+        monitor.record_fill(&fill_of("no-token", Side::Buy, dec!(0.38))).await;
+        let summary = monitor.market_summary("election").await.unwrap();
+        assert_eq!(summary.position, Decimal::ZERO);
+        assert_eq!(summary.realized_pnl, dec!(2));
+
+        // A lone No position is short Yes, marked from the No price
+        monitor.record_fill(&fill_of("no-token", Side::Buy, dec!(0.40))).await;
+        monitor.update_mark("no-token", dec!(0.30)).await;
+        let summary = monitor.market_summary("election").await.unwrap();
+        assert_eq!(summary.position, dec!(-100));
+        assert_eq!(summary.unrealized_pnl, dec!(-10));
+        // Yes at 0.70 is the same mark
+        monitor.update_mark("yes-token", dec!(0.70)).await;
+        assert_eq!(monitor.unrealized_pnl().await, dec!(-10));
+    }
+
+    use crate::executor::test_support::RecordingAlerts;
+
+    async fn monitor_with_pnl_alerts() -> (Monitor, std::sync::Arc<RecordingAlerts>) {
+        let alerts = std::sync::Arc::new(RecordingAlerts::default());
+        let monitor = Monitor::new(100)
+            .with_pnl_alerts(crate::config::PnlAlertConfig::default())
+            .with_risk_alerts(alerts.clone());
+        monitor
+            .record_trade(TradeRecord {
+                timestamp: Utc::now(),
+                market_id: "m1".to_string(),
+                side: "BUY".to_string(),
+                size: dec!(100),
+                price: dec!(0.50),
+                pnl: None,
+            })
+            .await;
+        (monitor, alerts)
+    }
+
+    #[tokio::test]
+    async fn test_take_profit_alerts_once_across_oscillation() {
+        use crate::events::RiskEventType;
+        let (monitor, alerts) = monitor_with_pnl_alerts().await;
+
+        // +20% crosses the threshold; hovering around it stays quiet
+        for mark in [dec!(0.55), dec!(0.60), dec!(0.59), dec!(0.61), dec!(0.58), dec!(0.62)] {
+            monitor.update_mark("m1", mark).await;
+        }
+        {
+            let sent = alerts.0.lock().unwrap();
+            assert_eq!(sent.len(), 1);
+            assert_eq!(sent[0].risk_type, RiskEventType::TakeProfit);
+            assert_eq!(sent[0].symbol.as_deref(), Some("m1"));
+            assert_eq!(sent[0].current_value, dec!(0.2));
+        }
+
+        // Back down past the hysteresis band re-arms it
+        monitor.update_mark("m1", dec!(0.56)).await;
+        monitor.update_mark("m1", dec!(0.60)).await;
+        assert_eq!(alerts.0.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_stop_loss_alerts_once_down_and_back() {
+        use crate::events::{RiskEventType, RiskSeverity};
+        let (monitor, alerts) = monitor_with_pnl_alerts().await;
+
+        // -10% fires, recovering to -8% is inside the band, -12% stays quiet
+        for mark in [dec!(0.45), dec!(0.46), dec!(0.44), dec!(0.50)] {
+            monitor.update_mark("m1", mark).await;
+        }
+
+        let sent = alerts.0.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].risk_type, RiskEventType::StopLoss);
+        assert_eq!(sent[0].severity, RiskSeverity::Warning);
+        assert_eq!(sent[0].threshold, dec!(-0.10));
+    }
+
+    #[tokio::test]
+    async fn test_short_position_return_uses_no_cost() {
+        let alerts = std::sync::Arc::new(RecordingAlerts::default());
+        let monitor = Monitor::new(100)
+            .with_pnl_alerts(crate::config::PnlAlertConfig::default())
+            .with_risk_alerts(alerts.clone());
+        monitor
+            .record_trade(TradeRecord {
+                timestamp: Utc::now(),
+                market_id: "m1".to_string(),
+                side: "SELL".to_string(),
+                size: dec!(100),
+                price: dec!(0.80),
+                pnl: None,
+            })
+            .await;
+
+        // Held as No bought at 0.20: Yes falling to 0.76 is +20% on cost
+        monitor.update_mark("m1", dec!(0.77)).await;
+        assert!(alerts.0.lock().unwrap().is_empty());
+        monitor.update_mark("m1", dec!(0.76)).await;
+        assert_eq!(alerts.0.lock().unwrap()[0].current_value, dec!(0.2));
+    }
+
+    #[test]
+    fn test_sortino_and_calmar_hand_computed() {
+        use crate::monitor::ratios::{calmar_ratio, max_drawdown, sortino_ratio};
+        let returns = [dec!(0.10), dec!(-0.05), dec!(0.02), dec!(-0.03)];
+        let close = |a: Decimal, b: Decimal| (a - b).abs() < dec!(0.000001);
+
+        // Mean 0.01, downside deviation sqrt((0.0025 + 0.0009) / 4)
+        // = 0.0291548, annualized over 4 periods by sqrt(4)
+        assert!(close(sortino_ratio(&returns, 4).unwrap(), dec!(0.6859943)));
+        assert!(close(sortino_ratio(&returns, 365).unwrap(), dec!(6.5529517)));
+
+        // Equity 1.1 -> 1.045 -> 1.0659 -> 1.033923: peak 1.1, trough
+        // 1.033923, a 6.007% drawdown against a 3.3923% return in a year
+        assert_eq!(max_drawdown(&returns), dec!(0.06007));
+        assert!(close(calmar_ratio(&returns, 1.0).unwrap(), dec!(0.5647245)));
+        // Over two years the annual return is sqrt(1.033923) - 1
+        assert!(close(calmar_ratio(&returns, 2.0).unwrap(), dec!(0.2800074)));
+    }
+
+    #[test]
+    fn test_ratios_survive_extreme_returns() {
+        use crate::monitor::ratios::{calmar_ratio, max_drawdown};
+        // Compounding 1000 doublings overflows `Decimal`
+        let mut returns = vec![Decimal::ONE; 1000];
+        returns.push(dec!(-0.5));
+        assert_eq!(max_drawdown(&returns), dec!(0.5));
+        // A ratio too large for `Decimal` is no ratio at all
+        assert_eq!(calmar_ratio(&returns, 1.0), None);
+        assert!(calmar_ratio(&returns, 100.0).unwrap() > Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_ratios_need_risk_to_measure() {
+        use crate::monitor::ratios::{calmar_ratio, sortino_ratio};
+        let gains = [dec!(0.01), dec!(0.02)];
+        assert_eq!(sortino_ratio(&gains, 365), None);
+        assert_eq!(calmar_ratio(&gains, 1.0), None);
+        assert_eq!(calmar_ratio(&[dec!(0.1), dec!(-0.1)], 0.0), None);
+        assert_eq!(sortino_ratio(&[dec!(-0.01)], 365), None);
+        assert_eq!(calmar_ratio(&[], 1.0), None);
+    }
 }
@@ -0,0 +1,198 @@
+//! Notification delivery channels
+//!
+//! `Notifier` formats messages as Telegram HTML and hands them to a
+//! `MultiNotifier`, which delivers each one to every configured channel.
+//! Channels that cannot render HTML convert it themselves.
+
+use crate::config::{WebhookConfig, WebhookKind};
+use crate::error::{BotError, Result};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Markup a message body is written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextFormat {
+    Html,
+    Markdown,
+}
+
+/// Somewhere notifications can be delivered
+#[async_trait]
+pub trait NotificationChannel: Send + Sync {
+    /// Short name used in logs, e.g. "telegram"
+    fn name(&self) -> &str;
+
+    /// Deliver one message
+    async fn send(&self, text: &str, format: TextFormat) -> Result<()>;
+}
+
+/// Telegram Bot API `sendMessage`
+pub struct TelegramChannel {
+    http: Client,
+    bot_token: String,
+    chat_id: String,
+}
+
+#[derive(Debug, Serialize)]
+struct TelegramMessage<'a> {
+    chat_id: &'a str,
+    text: &'a str,
+    parse_mode: &'a str,
+}
+
+impl TelegramChannel {
+    pub fn new(bot_token: String, chat_id: String) -> Self {
+        Self {
+            http: Client::new(),
+            bot_token,
+            chat_id,
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for TelegramChannel {
+    fn name(&self) -> &str {
+        "telegram"
+    }
+
+    async fn send(&self, text: &str, format: TextFormat) -> Result<()> {
+        let url = format!("https://api.telegram.org/bot{}/sendMessage", self.bot_token);
+        let msg = TelegramMessage {
+            chat_id: &self.chat_id,
+            text,
+            parse_mode: match format {
+                TextFormat::Html => "HTML",
+                TextFormat::Markdown => "Markdown",
+            },
+        };
+
+        let response = self.http.post(&url).json(&msg).send().await?;
+        if !response.status().is_success() {
+            let error_text = response.text().await.unwrap_or_default();
+            tracing::error!("Telegram send failed: {}", error_text);
+        }
+        Ok(())
+    }
+}
+
+/// Discord or Slack style incoming webhook taking a JSON body
+pub struct WebhookChannel {
+    http: Client,
+    url: String,
+    kind: WebhookKind,
+}
+
+impl WebhookChannel {
+    pub fn new(url: impl Into<String>, kind: WebhookKind) -> Self {
+        Self {
+            http: Client::new(),
+            url: url.into(),
+            kind,
+        }
+    }
+
+    pub fn from_config(config: &WebhookConfig) -> Self {
+        Self::new(config.url.expose(), config.kind)
+    }
+
+    /// JSON body for `text`
+    pub fn payload(&self, text: &str, format: TextFormat) -> serde_json::Value {
+        let text = match format {
+            TextFormat::Html => html_to_markdown(text),
+            TextFormat::Markdown => text.to_string(),
+        };
+        match self.kind {
+            WebhookKind::Discord => serde_json::json!({ "content": text }),
+            WebhookKind::Slack => serde_json::json!({ "text": text }),
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for WebhookChannel {
+    fn name(&self) -> &str {
+        match self.kind {
+            WebhookKind::Discord => "discord",
+            WebhookKind::Slack => "slack",
+        }
+    }
+
+    async fn send(&self, text: &str, format: TextFormat) -> Result<()> {
+        let response = self.http.post(&self.url).json(&self.payload(text, format)).send().await?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(BotError::Api(format!("{} webhook returned {}: {}", self.name(), status, error_text)));
+        }
+        Ok(())
+    }
+}
+
+/// Rewrite the Telegram HTML subset used by `Notifier` as Markdown
+fn html_to_markdown(text: &str) -> String {
+    text.replace("<b>", "**")
+        .replace("</b>", "**")
+        .replace("<code>", "`")
+        .replace("</code>", "`")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+/// Delivers every message to all of its channels
+///
+/// A channel that fails is logged and skipped; `send` only errors when
+/// every channel failed.
+#[derive(Clone, Default)]
+pub struct MultiNotifier {
+    channels: Vec<Arc<dyn NotificationChannel>>,
+}
+
+impl MultiNotifier {
+    pub fn new(channels: Vec<Arc<dyn NotificationChannel>>) -> Self {
+        Self { channels }
+    }
+
+    /// Also deliver to `channel`
+    pub fn with_channel(mut self, channel: Arc<dyn NotificationChannel>) -> Self {
+        self.channels.push(channel);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.channels.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.channels.len()
+    }
+}
+
+#[async_trait]
+impl NotificationChannel for MultiNotifier {
+    fn name(&self) -> &str {
+        "multi"
+    }
+
+    async fn send(&self, text: &str, format: TextFormat) -> Result<()> {
+        let results =
+            futures_util::future::join_all(self.channels.iter().map(|c| c.send(text, format))).await;
+
+        let mut failures = 0;
+        let mut last_error = None;
+        for (channel, result) in self.channels.iter().zip(results) {
+            if let Err(e) = result {
+                tracing::warn!("Notification via {} failed: {}", channel.name(), e);
+                failures += 1;
+                last_error = Some(e);
+            }
+        }
+        match last_error {
+            Some(e) if failures == self.channels.len() => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
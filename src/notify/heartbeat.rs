@@ -0,0 +1,36 @@
+//! Liveness heartbeat
+//!
+//! A quiet bot and a dead one look the same from the chat. The heartbeat
+//! sends a one-line status every `heartbeat_hours` so silence means
+//! something is wrong.
+
+use super::Notifier;
+use rust_decimal::Decimal;
+use std::future::Future;
+use std::time::Duration;
+
+/// Snapshot reported by each heartbeat
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HeartbeatStatus {
+    pub balance: Decimal,
+    pub open_positions: usize,
+    pub trades_today: usize,
+}
+
+/// Send a heartbeat built by `status` every `every`, forever
+///
+/// The first one goes out one period after start; the startup message
+/// already covers time zero.
+pub async fn run_heartbeat<F, Fut>(notifier: &Notifier, every: Duration, mut status: F)
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = HeartbeatStatus>,
+{
+    let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + every, every);
+    loop {
+        interval.tick().await;
+        if let Err(e) = notifier.heartbeat(&status().await).await {
+            tracing::warn!("Failed to send heartbeat: {}", e);
+        }
+    }
+}
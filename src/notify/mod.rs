@@ -1,226 +1,288 @@
-//! Telegram notification module
+//! Notification module
 //!
-//! Sends trading signals, executions, and alerts to Telegram.
+//! Sends trading signals, executions, and alerts to Telegram and any
+//! configured webhooks (see `channel`).
 
 #[cfg(test)]
 mod tests;
 
-use crate::error::Result;
+pub mod channel;
+pub mod heartbeat;
+pub mod template;
+pub mod throttle;
+
+pub use channel::{MultiNotifier, NotificationChannel, TelegramChannel, TextFormat, WebhookChannel};
+pub use heartbeat::{run_heartbeat, HeartbeatStatus};
+pub use template::TemplateSet;
+pub use throttle::{AlertPriority, NotifyThrottle, ThrottleSummary};
+
+use crate::error::{BotError, Result};
 use crate::types::{Signal, Side, Trade};
-use crate::monitor::PerformanceStats;
-use reqwest::Client;
+use crate::utils::truncate_chars;
+use crate::monitor::{PerformanceStats, Settlement};
 use rust_decimal::Decimal;
-use serde::Serialize;
+use std::sync::Arc;
 
-/// Telegram notifier
-#[derive(Clone)]
+/// Formats bot notifications and fans them out to its channels
+#[derive(Clone, Default)]
 pub struct Notifier {
-    http: Client,
-    bot_token: String,
-    chat_id: String,
-    enabled: bool,
-}
-
-#[derive(Debug, Serialize)]
-struct TelegramMessage {
-    chat_id: String,
-    text: String,
-    parse_mode: String,
+    channels: MultiNotifier,
+    templates: Arc<TemplateSet>,
+    throttle: Option<Arc<NotifyThrottle>>,
 }
 
 impl Notifier {
+    /// Notifier sending to a Telegram chat
     pub fn new(bot_token: String, chat_id: String) -> Self {
-        Self {
-            http: Client::new(),
-            bot_token,
-            chat_id,
-            enabled: true,
-        }
+        Self::disabled().with_channel(Arc::new(TelegramChannel::new(bot_token, chat_id)))
     }
 
-    /// Create a disabled notifier (for when Telegram is not configured)
+    /// Create a notifier with no channels (for when nothing is configured)
     pub fn disabled() -> Self {
+        Self::default()
+    }
+
+    /// Notifier sending to the given channels
+    pub fn from_channels(channels: MultiNotifier) -> Self {
         Self {
-            http: Client::new(),
-            bot_token: String::new(),
-            chat_id: String::new(),
-            enabled: false,
+            channels,
+            ..Default::default()
         }
     }
 
+    /// Coalesce repeated alerts and cap the message rate
+    pub fn with_throttle(mut self, throttle: Arc<NotifyThrottle>) -> Self {
+        self.throttle = Some(throttle);
+        self
+    }
+
+    /// Render messages from these templates instead of the built-in ones
+    pub fn with_templates(mut self, templates: TemplateSet) -> Self {
+        self.templates = Arc::new(templates);
+        self
+    }
+
+    /// Also deliver to `channel`
+    pub fn with_channel(mut self, channel: Arc<dyn NotificationChannel>) -> Self {
+        self.channels = self.channels.with_channel(channel);
+        self
+    }
+
+    /// Whether any channel is configured
+    pub fn is_enabled(&self) -> bool {
+        !self.channels.is_empty()
+    }
+
     /// Send a raw message (HTML format)
     pub async fn send(&self, text: &str) -> Result<()> {
-        self.send_with_format(text, "HTML").await
+        self.channels.send(text, TextFormat::Html).await
     }
 
     /// Send a raw message (Markdown format)
     pub async fn send_raw(&self, text: &str) -> Result<()> {
-        self.send_with_format(text, "Markdown").await
+        self.channels.send(text, TextFormat::Markdown).await
     }
 
-    /// Send a message with specific parse mode
-    async fn send_with_format(&self, text: &str, parse_mode: &str) -> Result<()> {
-        if !self.enabled {
+    /// Send an alert of type `kind`, unless the throttle holds it back
+    ///
+    /// Raw `send`s, such as command replies, are never throttled.
+    async fn send_alert(&self, kind: &str, priority: AlertPriority, text: &str) -> Result<()> {
+        let Some(throttle) = &self.throttle else {
+            return self.send(text).await;
+        };
+        self.flush().await;
+        if !throttle.admit(kind, priority) {
+            tracing::debug!("Throttled {} notification", kind);
             return Ok(());
         }
+        self.send(text).await
+    }
 
-        let url = format!(
-            "https://api.telegram.org/bot{}/sendMessage",
-            self.bot_token
-        );
-
-        let msg = TelegramMessage {
-            chat_id: self.chat_id.clone(),
-            text: text.to_string(),
-            parse_mode: parse_mode.to_string(),
+    /// Send summaries of alerts the throttle held back in closed windows
+    ///
+    /// Also runs before every alert; call it periodically so a summary is
+    /// not left waiting for the next one.
+    pub async fn flush(&self) {
+        let Some(throttle) = &self.throttle else {
+            return;
         };
-
-        let response = self.http.post(&url).json(&msg).send().await?;
-        
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            tracing::error!("Telegram send failed: {}", error_text);
+        for summary in throttle.due_summaries() {
+            if let Err(e) = self.send(&self.summary_text(&summary)).await {
+                tracing::warn!("Failed to send notification summary: {}", e);
+            }
         }
+    }
 
-        Ok(())
+    /// Body of a throttle summary
+    fn summary_text(&self, summary: &ThrottleSummary) -> String {
+        self.templates.render(
+            &self.templates.throttle_summary,
+            &[
+                ("count", summary.suppressed.to_string()),
+                ("kind", summary.kind.clone()),
+                ("alerts", if summary.suppressed == 1 { "alert" } else { "alerts" }.to_string()),
+                ("window_secs", summary.window_secs.to_string()),
+            ],
+        )
     }
 
     /// Notify about a trading signal found
     pub async fn signal_found(&self, signal: &Signal, market_question: &str) -> Result<()> {
-        let side_emoji = match signal.side {
-            Side::Buy => "🟢",
-            Side::Sell => "🔴",
-        };
-
-        let side_text = match signal.side {
-            Side::Buy => "BUY",
-            Side::Sell => "SELL",
-        };
-
-        let text = format!(
-            "{} <b>Signal Found</b>\n\n\
-            📊 <b>{}</b>\n\n\
-            Direction: {} {}\n\
-            Model: <code>{:.1}%</code>\n\
-            Market: <code>{:.1}%</code>\n\
-            Edge: <code>{:+.1}%</code>\n\
-            Confidence: <code>{:.0}%</code>\n\
-            Size: <code>{:.1}%</code> of portfolio",
-            side_emoji,
-            truncate(market_question, 100),
-            side_emoji,
-            side_text,
-            signal.model_probability * Decimal::ONE_HUNDRED,
-            signal.market_probability * Decimal::ONE_HUNDRED,
-            signal.edge * Decimal::ONE_HUNDRED,
-            signal.confidence * Decimal::ONE_HUNDRED,
-            signal.suggested_size * Decimal::ONE_HUNDRED,
+        let pct = |v: Decimal| format!("{:.1}", v * Decimal::ONE_HUNDRED);
+        let text = self.templates.render(
+            &self.templates.signal_found,
+            &[
+                ("side_emoji", side_emoji(signal.side).to_string()),
+                ("side", side_text(signal.side).to_string()),
+                ("question", truncate_chars(market_question, 100)),
+                ("market_id", signal.market_id.clone()),
+                ("token_id", signal.token_id.clone()),
+                ("model_pct", pct(signal.model_probability)),
+                ("market_pct", pct(signal.market_probability)),
+                ("edge_pct", format!("{:+.1}", signal.edge * Decimal::ONE_HUNDRED)),
+                ("net_edge_pct", format!("{:+.1}", signal.net_edge * Decimal::ONE_HUNDRED)),
+                ("confidence_pct", format!("{:.0}", signal.confidence * Decimal::ONE_HUNDRED)),
+                ("size_pct", pct(signal.suggested_size)),
+            ],
         );
 
-        self.send(&text).await
+        self.send_alert("signal", AlertPriority::Normal, &text).await
     }
 
-    /// Notify about trade execution
+    /// Notify about trade execution; every fill is reported, so these are
+    /// never throttled
     pub async fn trade_executed(&self, trade: &Trade, market_question: &str) -> Result<()> {
-        let side_emoji = match trade.side {
-            Side::Buy => "🟢",
-            Side::Sell => "🔴",
-        };
+        self.send_alert("trade", AlertPriority::Critical, &self.trade_text(trade, market_question)).await
+    }
 
-        let text = format!(
-            "✅ <b>Trade Executed</b>\n\n\
-            📊 {}\n\n\
-            {} {} @ <code>${:.4}</code>\n\
-            Size: <code>${:.2}</code>\n\
-            Fee: <code>${:.4}</code>\n\
-            Order ID: <code>{}</code>",
-            truncate(market_question, 80),
-            side_emoji,
-            match trade.side {
-                Side::Buy => "BOUGHT",
-                Side::Sell => "SOLD",
-            },
-            trade.price,
-            trade.size,
-            trade.fee,
-            &trade.order_id[..8],
-        );
+    /// Body of a trade notification
+    fn trade_text(&self, trade: &Trade, market_question: &str) -> String {
+        self.templates.render(
+            &self.templates.trade_executed,
+            &[
+                ("side_emoji", side_emoji(trade.side).to_string()),
+                ("side", side_text(trade.side).to_string()),
+                (
+                    "action",
+                    match trade.side {
+                        Side::Buy => "BOUGHT",
+                        Side::Sell => "SOLD",
+                    }
+                    .to_string(),
+                ),
+                ("question", truncate_chars(market_question, 80)),
+                ("market_id", trade.market_id.clone()),
+                ("token_id", trade.token_id.clone()),
+                ("price", format!("{:.4}", trade.price)),
+                ("size", format!("{:.2}", trade.size)),
+                ("fee", format!("{:.4}", trade.fee)),
+                ("order_id", trade.order_id.chars().take(8).collect()),
+            ],
+        )
+    }
 
-        self.send(&text).await
+    /// Notify that a position was closed out by market resolution; never
+    /// throttled, like trades
+    pub async fn settlement(&self, settlement: &Settlement) -> Result<()> {
+        self.send_alert("settlement", AlertPriority::Critical, &self.settlement_text(settlement)).await
     }
 
-    /// Notify about an error
-    pub async fn error(&self, context: &str, error: &str) -> Result<()> {
-        let text = format!(
-            "⚠️ <b>Error</b>\n\n\
-            Context: {}\n\
-            Error: <code>{}</code>",
-            context,
-            truncate(error, 200),
-        );
+    /// Body of a settlement notification
+    fn settlement_text(&self, settlement: &Settlement) -> String {
+        let (emoji, result) = if settlement.won() { ("🏆", "WON") } else { ("💸", "LOST") };
+        self.templates.render(
+            &self.templates.settlement,
+            &[
+                ("result_emoji", emoji.to_string()),
+                ("result", result.to_string()),
+                ("question", truncate_chars(&settlement.question, 80)),
+                ("market_id", settlement.market_id.clone()),
+                ("winning_outcome", settlement.winning_outcome.clone()),
+                ("size", format!("{:.2}", settlement.size)),
+                ("entry_price", format!("{:.4}", settlement.entry_price)),
+                ("payout_price", settlement.payout_price.to_string()),
+                ("pnl", format!("{:+.2}", settlement.realized_pnl)),
+            ],
+        )
+    }
 
-        self.send(&text).await
+    /// Notify about an error, listing the market and order it concerns
+    pub async fn error(&self, context: &str, error: &BotError) -> Result<()> {
+        self.send_alert(context, AlertPriority::Normal, &error_text(context, error)).await
     }
 
     /// Send daily performance report
     pub async fn daily_report(&self, stats: &PerformanceStats, balance: Decimal) -> Result<()> {
         let pnl_emoji = if stats.total_pnl >= Decimal::ZERO { "📈" } else { "📉" };
-
-        let text = format!(
-            "📊 <b>Daily Report</b>\n\n\
-            💰 Balance: <code>${:.2}</code>\n\
-            {} PnL: <code>{:+.2}</code>\n\n\
-            Trades: {}\n\
-            Win Rate: <code>{:.1}%</code>\n\
-            Avg PnL/Trade: <code>{:+.2}</code>",
-            balance,
-            pnl_emoji,
-            stats.total_pnl,
-            stats.total_trades,
-            stats.win_rate * Decimal::ONE_HUNDRED,
-            stats.avg_pnl_per_trade,
+        let text = self.templates.render(
+            &self.templates.daily_report,
+            &[
+                ("pnl_emoji", pnl_emoji.to_string()),
+                ("balance", format!("{:.2}", balance)),
+                ("pnl", format!("{:+.2}", stats.total_pnl)),
+                ("trades", stats.total_trades.to_string()),
+                ("win_rate_pct", format!("{:.1}", stats.win_rate * Decimal::ONE_HUNDRED)),
+                ("avg_pnl", format!("{:+.2}", stats.avg_pnl_per_trade)),
+            ],
         );
 
-        self.send(&text).await
+        self.send_alert("daily report", AlertPriority::Critical, &text).await
     }
 
     /// Notify bot startup
     pub async fn startup(&self, dry_run: bool) -> Result<()> {
         let mode = if dry_run { "DRY RUN 🧪" } else { "LIVE 🔥" };
-        let text = format!(
-            "🤖 <b>Polymarket Bot Started</b>\n\n\
-            Mode: {}\n\
-            Time: {}",
-            mode,
-            chrono::Utc::now().format("%Y-%m-%d %H:%M UTC"),
+        let text = self.templates.render(
+            &self.templates.startup,
+            &[("mode", mode.to_string()), ("time", now_text())],
         );
 
-        self.send(&text).await
+        self.send_alert("startup", AlertPriority::Critical, &text).await
     }
 
     /// Notify bot shutdown
     pub async fn shutdown(&self, reason: &str) -> Result<()> {
-        let text = format!(
-            "🛑 <b>Bot Stopped</b>\n\n\
-            Reason: {}\n\
-            Time: {}",
-            reason,
-            chrono::Utc::now().format("%Y-%m-%d %H:%M UTC"),
+        let text = self.templates.render(
+            &self.templates.shutdown,
+            &[("reason", reason.to_string()), ("time", now_text())],
         );
 
-        self.send(&text).await
+        self.send_alert("shutdown", AlertPriority::Critical, &text).await
+    }
+
+    /// One-line "still alive" status, separate from the daily report
+    pub async fn heartbeat(&self, status: &HeartbeatStatus) -> Result<()> {
+        let text = self.templates.render(
+            &self.templates.heartbeat,
+            &[
+                ("balance", format!("{:.2}", status.balance)),
+                ("open_positions", status.open_positions.to_string()),
+                ("trades_today", status.trades_today.to_string()),
+                ("time", now_text()),
+            ],
+        );
+
+        self.send_alert("heartbeat", AlertPriority::Critical, &text).await
     }
 
     /// Risk alert (e.g., daily loss limit hit)
     pub async fn risk_alert(&self, alert_type: &str, message: &str) -> Result<()> {
-        let text = format!(
-            "🚨 <b>Risk Alert: {}</b>\n\n\
-            {}",
-            alert_type,
-            message,
+        self.risk_alert_with_priority(alert_type, message, AlertPriority::Normal).await
+    }
+
+    /// Risk alert; critical ones are never throttled
+    pub async fn risk_alert_with_priority(
+        &self,
+        alert_type: &str,
+        message: &str,
+        priority: AlertPriority,
+    ) -> Result<()> {
+        let text = self.templates.render(
+            &self.templates.risk_alert,
+            &[("alert_type", alert_type.to_string()), ("message", message.to_string())],
         );
 
-        self.send(&text).await
+        self.send_alert(alert_type, priority, &text).await
     }
 
     /// Notify about arbitrage opportunity found
@@ -238,14 +300,14 @@ impl Notifier {
             YES: ${:.3} | NO: ${:.3}\n\
             Spread: {:.2}%\n\
             Est. Profit: ${:.4}",
-            truncate(market, 50),
+            truncate_chars(market, 50),
             yes_price,
             no_price,
             spread * Decimal::from(100),
             profit,
         );
 
-        self.send(&text).await
+        self.send_alert("arbitrage", AlertPriority::Normal, &text).await
     }
 
     /// Notify about arbitrage execution result
@@ -274,13 +336,13 @@ impl Notifier {
             Profit: {}\n\
             Latency: {}ms{}",
             status,
-            truncate(market, 40),
+            truncate_chars(market, 40),
             profit_text,
             latency_ms,
             error_text,
         );
 
-        self.send(&text).await
+        self.send_alert("arbitrage execution", AlertPriority::Normal, &text).await
     }
 
     /// Send crypto 15m market status update
@@ -307,14 +369,42 @@ impl Notifier {
             signal,
         );
 
-        self.send(&text).await
+        self.send_alert(&format!("{} status", symbol), AlertPriority::Normal, &text).await
     }
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() > max_len {
-        format!("{}...", &s[..max_len])
-    } else {
-        s.to_string()
+fn side_emoji(side: Side) -> &'static str {
+    match side {
+        Side::Buy => "🟢",
+        Side::Sell => "🔴",
+    }
+}
+
+fn side_text(side: Side) -> &'static str {
+    match side {
+        Side::Buy => "BUY",
+        Side::Sell => "SELL",
+    }
+}
+
+fn now_text() -> String {
+    chrono::Utc::now().format("%Y-%m-%d %H:%M UTC").to_string()
+}
+
+/// Body of an error notification
+fn error_text(context: &str, error: &BotError) -> String {
+    let mut text = format!("⚠️ <b>Error</b>\n\nContext: {}\n", context);
+    if let Some(ctx) = error.context() {
+        if let Some(market_id) = &ctx.market_id {
+            text.push_str(&format!("Market: <code>{}</code>\n", market_id));
+        }
+        if let Some(order_id) = &ctx.order_id {
+            text.push_str(&format!("Order: <code>{}</code>\n", order_id));
+        }
+        if let Some(operation) = &ctx.operation {
+            text.push_str(&format!("Operation: {}\n", operation));
+        }
     }
+    text.push_str(&format!("Error: <code>{}</code>", truncate_chars(&error.root().to_string(), 200)));
+    text
 }
@@ -0,0 +1,162 @@
+//! Notification message templates
+//!
+//! Each message body is a template with `{name}` placeholders that
+//! `Notifier` fills from the `Signal`, `Trade`, `Settlement`,
+//! `PerformanceStats` or throttle summary it is reporting. Templates come from the
+//! `[templates]` config section; any left out use the built-in English
+//! ones. Unknown placeholders are left as written.
+
+use serde::Deserialize;
+
+/// Message bodies for each notification, in Telegram HTML
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct TemplateSet {
+    /// Keep emoji in rendered messages; `false` strips them
+    pub emoji: bool,
+    /// `{side_emoji}` `{side}` `{question}` `{market_id}` `{token_id}`
+    /// `{model_pct}` `{market_pct}` `{edge_pct}` `{net_edge_pct}`
+    /// `{confidence_pct}` `{size_pct}`
+    pub signal_found: String,
+    /// `{side_emoji}` `{side}` `{action}` `{question}` `{market_id}`
+    /// `{token_id}` `{price}` `{size}` `{fee}` `{order_id}`
+    pub trade_executed: String,
+    /// `{result_emoji}` `{result}` `{question}` `{market_id}`
+    /// `{winning_outcome}` `{size}` `{entry_price}` `{payout_price}` `{pnl}`
+    pub settlement: String,
+    /// `{pnl_emoji}` `{balance}` `{pnl}` `{trades}` `{win_rate_pct}`
+    /// `{avg_pnl}`
+    pub daily_report: String,
+    /// `{mode}` `{time}`
+    pub startup: String,
+    /// `{reason}` `{time}`
+    pub shutdown: String,
+    /// `{balance}` `{open_positions}` `{trades_today}` `{time}`
+    pub heartbeat: String,
+    /// `{alert_type}` `{message}`
+    pub risk_alert: String,
+    /// Alerts the throttle held back: `{count}` `{kind}` `{alerts}`
+    /// ("alert" or "alerts") `{window_secs}`
+    pub throttle_summary: String,
+}
+
+const SIGNAL_FOUND: &str = "{side_emoji} <b>Signal Found</b>\n\n\
+    📊 <b>{question}</b>\n\n\
+    Direction: {side_emoji} {side}\n\
+    Model: <code>{model_pct}%</code>\n\
+    Market: <code>{market_pct}%</code>\n\
+    Edge: <code>{edge_pct}%</code>\n\
+    Confidence: <code>{confidence_pct}%</code>\n\
+    Size: <code>{size_pct}%</code> of portfolio";
+
+const TRADE_EXECUTED: &str = "✅ <b>Trade Executed</b>\n\n\
+    📊 {question}\n\n\
+    {side_emoji} {action} @ <code>${price}</code>\n\
+    Size: <code>${size}</code>\n\
+    Fee: <code>${fee}</code>\n\
+    Order ID: <code>{order_id}</code>";
+
+const SETTLEMENT: &str = "{result_emoji} <b>Market Settled: {result}</b>\n\n\
+    📊 {question}\n\n\
+    Resolved: <b>{winning_outcome}</b>\n\
+    Size: <code>{size}</code> @ <code>${entry_price}</code> → <code>${payout_price}</code>\n\
+    PnL: <code>{pnl}</code>";
+
+const DAILY_REPORT: &str = "📊 <b>Daily Report</b>\n\n\
+    💰 Balance: <code>${balance}</code>\n\
+    {pnl_emoji} PnL: <code>{pnl}</code>\n\n\
+    Trades: {trades}\n\
+    Win Rate: <code>{win_rate_pct}%</code>\n\
+    Avg PnL/Trade: <code>{avg_pnl}</code>";
+
+const STARTUP: &str = "🤖 <b>Polymarket Bot Started</b>\n\n\
+    Mode: {mode}\n\
+    Time: {time}";
+
+const SHUTDOWN: &str = "🛑 <b>Bot Stopped</b>\n\n\
+    Reason: {reason}\n\
+    Time: {time}";
+
+const HEARTBEAT: &str = "💓 Alive | Balance: <code>${balance}</code> | \
+    Positions: {open_positions} | Trades today: {trades_today}";
+
+const RISK_ALERT: &str = "🚨 <b>Risk Alert: {alert_type}</b>\n\n\
+    {message}";
+
+const THROTTLE_SUMMARY: &str = "🔕 {count} more {kind} {alerts} in last {window_secs}s";
+
+impl Default for TemplateSet {
+    fn default() -> Self {
+        Self {
+            emoji: true,
+            signal_found: SIGNAL_FOUND.to_string(),
+            trade_executed: TRADE_EXECUTED.to_string(),
+            settlement: SETTLEMENT.to_string(),
+            daily_report: DAILY_REPORT.to_string(),
+            startup: STARTUP.to_string(),
+            shutdown: SHUTDOWN.to_string(),
+            heartbeat: HEARTBEAT.to_string(),
+            risk_alert: RISK_ALERT.to_string(),
+            throttle_summary: THROTTLE_SUMMARY.to_string(),
+        }
+    }
+}
+
+impl TemplateSet {
+    /// Fill `template`'s placeholders from `vars`
+    pub fn render(&self, template: &str, vars: &[(&str, String)]) -> String {
+        let text = fill(template, vars);
+        if self.emoji {
+            text
+        } else {
+            strip_emoji(&text)
+        }
+    }
+}
+
+/// Replace each `{name}` with its value in a single pass
+fn fill(template: &str, vars: &[(&str, String)]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after
+            .find('}')
+            .and_then(|end| vars.iter().find(|(name, _)| *name == &after[..end]).map(|(_, v)| (end, v)));
+        match value {
+            Some((end, v)) => {
+                out.push_str(v);
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn is_emoji(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F000..=0x1FAFF | 0x2600..=0x27BF | 0x2B00..=0x2BFF | 0x23E9..=0x23FA | 0xFE0F | 0x200D
+    )
+}
+
+/// Drop emoji and the spaces they leave behind
+fn strip_emoji(text: &str) -> String {
+    text.split('\n')
+        .map(|line| {
+            let stripped: String = line.chars().filter(|c| !is_emoji(*c)).collect();
+            if stripped.len() == line.len() {
+                stripped
+            } else {
+                stripped.trim().replace("  ", " ")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -2,7 +2,12 @@
 
 #[cfg(test)]
 mod tests {
-    use super::super::Notifier;
+    use super::super::{
+        run_heartbeat, AlertPriority, HeartbeatStatus, MultiNotifier, NotificationChannel, Notifier, NotifyThrottle, TemplateSet, TextFormat, ThrottleSummary,
+        WebhookChannel,
+    };
+    use crate::config::{NotifyThrottleConfig, WebhookKind};
+    use std::sync::Arc;
     use crate::types::{Signal, Side, Trade};
     use crate::monitor::PerformanceStats;
     use chrono::Utc;
@@ -38,9 +43,11 @@ mod tests {
             model_probability: dec!(0.70),
             market_probability: dec!(0.55),
             edge: dec!(0.15),
+            net_edge: dec!(0.15),
             confidence: dec!(0.80),
             suggested_size: dec!(100),
             timestamp: Utc::now(),
+            max_staleness: None,
         };
         
         assert_eq!(signal.side, Side::Buy);
@@ -56,9 +63,11 @@ mod tests {
             model_probability: dec!(0.30),
             market_probability: dec!(0.45),
             edge: dec!(-0.15),
+            net_edge: dec!(-0.15),
             confidence: dec!(0.75),
             suggested_size: dec!(50),
             timestamp: Utc::now(),
+            max_staleness: None,
         };
         
         assert_eq!(signal.side, Side::Sell);
@@ -76,6 +85,8 @@ mod tests {
             size: dec!(100),
             fee: dec!(0.50),
             timestamp: Utc::now(),
+            client_order_id: None,
+            correlation_id: None,
         };
         
         assert_eq!(trade.price, dec!(0.55));
@@ -91,6 +102,8 @@ mod tests {
             total_pnl: dec!(5000),
             avg_pnl_per_trade: dec!(50),
             sharpe_ratio: Some(dec!(1.8)),
+            sortino_ratio: None,
+            calmar_ratio: None,
         };
         
         assert_eq!(stats.total_trades, 100);
@@ -149,9 +162,11 @@ mod tests {
             model_probability: dec!(0.85),
             market_probability: dec!(0.70),
             edge: dec!(0.15),
+            net_edge: dec!(0.15),
             confidence: dec!(0.90),
             suggested_size: dec!(200),
             timestamp: Utc::now(),
+            max_staleness: None,
         };
         
         assert!(signal.confidence >= dec!(0.90));
@@ -166,9 +181,11 @@ mod tests {
             model_probability: dec!(0.55),
             market_probability: dec!(0.50),
             edge: dec!(0.05),
+            net_edge: dec!(0.05),
             confidence: dec!(0.50),
             suggested_size: dec!(20),
             timestamp: Utc::now(),
+            max_staleness: None,
         };
         
         assert!(signal.confidence <= dec!(0.50));
@@ -188,4 +205,337 @@ mod tests {
         let result = notifier.send_raw("test message").await;
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_error_text_lists_context() {
+        use crate::error::BotError;
+
+        let err = BotError::Execution("No asks available".into())
+            .context_order("key-1")
+            .context_market("m1");
+        let text = super::super::error_text("Trade execution", &err);
+        assert!(text.contains("Context: Trade execution"));
+        assert!(text.contains("Market: <code>m1</code>"));
+        assert!(text.contains("Order: <code>key-1</code>"));
+        assert!(!text.contains("Operation"));
+        assert!(text.contains("Error: <code>Execution error: No asks available</code>"));
+    }
+
+    /// Records what it is sent, or fails every send
+    #[derive(Default)]
+    struct MockChannel {
+        fail: bool,
+        sent: std::sync::Mutex<Vec<(String, TextFormat)>>,
+    }
+
+    impl MockChannel {
+        fn failing() -> Self {
+            Self { fail: true, ..Default::default() }
+        }
+
+        fn sent(&self) -> Vec<(String, TextFormat)> {
+            self.sent.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl NotificationChannel for MockChannel {
+        fn name(&self) -> &str {
+            "mock"
+        }
+
+        async fn send(&self, text: &str, format: TextFormat) -> crate::error::Result<()> {
+            if self.fail {
+                return Err(crate::error::BotError::Api("channel down".into()));
+            }
+            self.sent.lock().unwrap().push((text.to_string(), format));
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_multi_notifier_delivers_to_all_channels() {
+        let telegram = Arc::new(MockChannel::default());
+        let discord = Arc::new(MockChannel::default());
+        let multi = MultiNotifier::new(vec![telegram.clone(), discord.clone()]);
+
+        multi.send("<b>hello</b>", TextFormat::Html).await.unwrap();
+
+        assert_eq!(telegram.sent(), vec![("<b>hello</b>".to_string(), TextFormat::Html)]);
+        assert_eq!(discord.sent(), telegram.sent());
+    }
+
+    #[tokio::test]
+    async fn test_multi_notifier_failure_does_not_block_others() {
+        let down = Arc::new(MockChannel::failing());
+        let up = Arc::new(MockChannel::default());
+        let notifier = Notifier::from_channels(MultiNotifier::new(vec![down.clone(), up.clone()]));
+
+        notifier.risk_alert("Daily loss", "Limit reached").await.unwrap();
+
+        let sent = up.sent();
+        assert_eq!(sent.len(), 1);
+        assert!(sent[0].0.contains("Risk Alert: Daily loss"));
+
+        // Only an error when nothing got through
+        let all_down = MultiNotifier::new(vec![down.clone(), Arc::new(MockChannel::failing())]);
+        assert!(all_down.send("x", TextFormat::Html).await.is_err());
+    }
+
+    #[test]
+    fn test_notifier_channels() {
+        assert!(!Notifier::disabled().is_enabled());
+        assert!(Notifier::new("token".to_string(), "chat".to_string()).is_enabled());
+        let multi = MultiNotifier::default().with_channel(Arc::new(MockChannel::default()));
+        assert_eq!(multi.len(), 1);
+    }
+
+    #[test]
+    fn test_webhook_payload() {
+        let html = "🚨 <b>Risk Alert</b>\nPnL: <code>-5.00</code> &amp; falling";
+
+        let discord = WebhookChannel::new("http://localhost/hook", WebhookKind::Discord);
+        assert_eq!(
+            discord.payload(html, TextFormat::Html),
+            serde_json::json!({ "content": "🚨 **Risk Alert**\nPnL: `-5.00` & falling" })
+        );
+
+        let slack = WebhookChannel::new("http://localhost/hook", WebhookKind::Slack);
+        assert_eq!(slack.payload("*raw*", TextFormat::Markdown), serde_json::json!({ "text": "*raw*" }));
+    }
+
+    #[tokio::test]
+    async fn test_webhook_reports_http_errors() {
+        use axum::{http::StatusCode, routing::post, Json, Router};
+
+        let received = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let router = {
+            let received = received.clone();
+            Router::new()
+                .route(
+                    "/ok",
+                    post(move |Json(body): Json<serde_json::Value>| async move {
+                        received.lock().unwrap().push(body);
+                        StatusCode::NO_CONTENT
+                    }),
+                )
+                .route("/gone", post(|| async { StatusCode::NOT_FOUND }))
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+
+        let ok = WebhookChannel::new(format!("http://{}/ok", addr), WebhookKind::Discord);
+        ok.send("<b>up</b>", TextFormat::Html).await.unwrap();
+        assert_eq!(received.lock().unwrap()[0], serde_json::json!({ "content": "**up**" }));
+
+        let gone = WebhookChannel::new(format!("http://{}/gone", addr), WebhookKind::Slack);
+        assert!(gone.send("x", TextFormat::Html).await.is_err());
+    }
+
+    fn trade() -> Trade {
+        Trade {
+            id: "trade1".to_string(),
+            order_id: "0xabcdef1234567890".to_string(),
+            token_id: "token1".to_string(),
+            market_id: "market1".to_string(),
+            side: Side::Sell,
+            price: dec!(0.55),
+            size: dec!(100),
+            fee: dec!(0.50),
+            timestamp: Utc::now(),
+            client_order_id: None,
+            correlation_id: None,
+        }
+    }
+
+    #[test]
+    fn test_trade_template_substitutes_placeholders() {
+        let templates = TemplateSet {
+            trade_executed: "{action} {size} @ {price} ({side}, fee {fee}) on {question} [{order_id}] {unknown}".to_string(),
+            ..Default::default()
+        };
+        let notifier = Notifier::disabled().with_templates(templates);
+
+        let text = notifier.trade_text(&trade(), "Will it rain?");
+        assert_eq!(text, "SOLD 100.00 @ 0.5500 (SELL, fee 0.5000) on Will it rain? [0xabcdef] {unknown}");
+    }
+
+    #[test]
+    fn test_default_trade_template() {
+        let text = Notifier::disabled().trade_text(&trade(), "Will it rain?");
+        assert_eq!(
+            text,
+            "✅ <b>Trade Executed</b>\n\n📊 Will it rain?\n\n🔴 SOLD @ <code>$0.5500</code>\n\
+             Size: <code>$100.00</code>\nFee: <code>$0.5000</code>\nOrder ID: <code>0xabcdef</code>"
+        );
+    }
+
+    #[test]
+    fn test_templates_without_emoji() {
+        let templates = TemplateSet { emoji: false, ..Default::default() };
+        let text = Notifier::disabled().with_templates(templates).trade_text(&trade(), "Rain?");
+        assert!(text.starts_with("<b>Trade Executed</b>\n\nRain?\n\nSOLD @"), "{}", text);
+    }
+
+    #[test]
+    fn test_templates_from_config() {
+        let templates: TemplateSet = toml::from_str(
+            r#"
+            emoji = false
+            risk_alert = "Alerte : {alert_type} - {message}"
+            "#,
+        )
+        .unwrap();
+        assert!(!templates.emoji);
+        assert_eq!(
+            templates.render(&templates.risk_alert, &[("alert_type", "Perte".into()), ("message", "Limite".into())]),
+            "Alerte : Perte - Limite"
+        );
+        // Unset templates keep the built-in text
+        assert_eq!(templates.startup, TemplateSet::default().startup);
+    }
+
+    fn notify_throttle(window_secs: u64, max_per_minute: u32, burst: u32) -> NotifyThrottle {
+        NotifyThrottle::new(NotifyThrottleConfig { window_secs, max_per_minute, burst })
+    }
+
+    #[test]
+    fn test_throttle_coalesces_repeated_alerts() {
+        let throttle = notify_throttle(60, 0, 1);
+        let start = Utc::now();
+
+        assert!(throttle.admit_at("volatility", AlertPriority::Normal, start));
+        for i in 1..=12 {
+            let at = start + chrono::Duration::seconds(i * 4);
+            assert!(!throttle.admit_at("volatility", AlertPriority::Normal, at));
+        }
+        // Other alert types have their own window
+        assert!(throttle.admit_at("liquidity", AlertPriority::Normal, start + chrono::Duration::seconds(5)));
+
+        assert!(throttle.due_summaries_at(start + chrono::Duration::seconds(59)).is_empty());
+        assert_eq!(
+            throttle.due_summaries_at(start + chrono::Duration::seconds(65)),
+            vec![ThrottleSummary { kind: "volatility".to_string(), suppressed: 12, window_secs: 60 }]
+        );
+        // Reported once, and the next alert opens a new window
+        assert!(throttle.due_summaries_at(start + chrono::Duration::seconds(70)).is_empty());
+        assert!(throttle.admit_at("volatility", AlertPriority::Normal, start + chrono::Duration::seconds(70)));
+    }
+
+    #[test]
+    fn test_throttle_rate_limit_with_burst() {
+        let throttle = notify_throttle(0, 6, 2);
+        let start = Utc::now();
+
+        assert!(throttle.admit_at("a", AlertPriority::Normal, start));
+        assert!(throttle.admit_at("b", AlertPriority::Normal, start));
+        assert!(!throttle.admit_at("c", AlertPriority::Normal, start));
+        assert!(throttle.admit_at("c", AlertPriority::Critical, start));
+
+        // Six a minute refills one every ten seconds
+        assert!(throttle.admit_at("d", AlertPriority::Normal, start + chrono::Duration::seconds(10)));
+        assert!(!throttle.admit_at("e", AlertPriority::Normal, start + chrono::Duration::seconds(10)));
+
+        let summaries = throttle.due_summaries_at(start + chrono::Duration::seconds(70));
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0], ThrottleSummary { kind: "c".to_string(), suppressed: 1, window_secs: 60 });
+    }
+
+    #[tokio::test]
+    async fn test_critical_alerts_bypass_throttle() {
+        let channel = Arc::new(MockChannel::default());
+        let notifier = Notifier::from_channels(MultiNotifier::new(vec![channel.clone()]))
+            .with_throttle(Arc::new(notify_throttle(60, 0, 1)));
+
+        for _ in 0..5 {
+            notifier.risk_alert("Volatility", "BTC moved 8%").await.unwrap();
+        }
+        assert_eq!(channel.sent().len(), 1);
+
+        for _ in 0..3 {
+            notifier
+                .risk_alert_with_priority("Volatility", "Circuit open", AlertPriority::Critical)
+                .await
+                .unwrap();
+        }
+        notifier.shutdown("test").await.unwrap();
+        // Raw sends, e.g. command replies, are never throttled
+        notifier.send("pong").await.unwrap();
+        notifier.send("pong").await.unwrap();
+
+        let sent = channel.sent();
+        assert_eq!(sent.len(), 7);
+        assert!(sent[1..4].iter().all(|(text, _)| text.contains("Circuit open")));
+        assert!(sent[4].0.contains("Bot Stopped"));
+    }
+
+    #[tokio::test]
+    async fn test_trades_bypass_throttle_and_summaries_use_templates() {
+        let channel = Arc::new(MockChannel::default());
+        let templates = TemplateSet {
+            throttle_summary: "{count} {kind} {alerts} held back".to_string(),
+            ..Default::default()
+        };
+        let notifier = Notifier::from_channels(MultiNotifier::new(vec![channel.clone()]))
+            .with_templates(templates)
+            .with_throttle(Arc::new(notify_throttle(1, 0, 1)));
+
+        for _ in 0..3 {
+            notifier.trade_executed(&trade(), "Will it happen?").await.unwrap();
+        }
+        notifier.risk_alert("Volatility", "BTC moved 8%").await.unwrap();
+        notifier.risk_alert("Volatility", "BTC moved 9%").await.unwrap();
+        assert_eq!(channel.sent().len(), 4);
+
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+        notifier.flush().await;
+        let sent = channel.sent();
+        assert_eq!(sent.len(), 5);
+        assert_eq!(sent[4].0, "1 Volatility alert held back");
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat_text() {
+        let channel = Arc::new(MockChannel::default());
+        let notifier = Notifier::from_channels(MultiNotifier::new(vec![channel.clone()]));
+
+        let status = HeartbeatStatus {
+            balance: dec!(1234.5),
+            open_positions: 3,
+            trades_today: 7,
+        };
+        notifier.heartbeat(&status).await.unwrap();
+
+        assert_eq!(
+            channel.sent()[0].0,
+            "💓 Alive | Balance: <code>$1234.50</code> | Positions: 3 | Trades today: 7"
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_heartbeat_fires_at_configured_cadence() {
+        let channel = Arc::new(MockChannel::default());
+        let notifier = Notifier::from_channels(MultiNotifier::new(vec![channel.clone()]));
+        let hour = std::time::Duration::from_secs(60 * 60);
+
+        let task = tokio::spawn(async move {
+            run_heartbeat(&notifier, 2 * hour, || async { HeartbeatStatus::default() }).await;
+        });
+
+        // Nothing at start; the startup message covers that
+        tokio::time::sleep(hour).await;
+        assert!(channel.sent().is_empty());
+
+        tokio::time::sleep(hour + std::time::Duration::from_secs(1)).await;
+        assert_eq!(channel.sent().len(), 1);
+
+        tokio::time::sleep(4 * hour).await;
+        assert_eq!(channel.sent().len(), 3);
+        assert!(channel.sent().iter().all(|(text, _)| text.contains("Alive")));
+
+        task.abort();
+    }
 }
@@ -0,0 +1,149 @@
+//! Notification throttling
+//!
+//! A volatile hour can fire the same alert dozens of times a minute.
+//! `NotifyThrottle` lets the first alert of each type through, counts the
+//! repeats within `window_secs` and reports them as one summary once the
+//! window closes. On top of that a token bucket caps the overall rate at
+//! `max_per_minute` after an initial `burst`; alerts it holds back are
+//! counted into the same summaries. Critical alerts bypass both.
+
+use crate::config::NotifyThrottleConfig;
+use chrono::{DateTime, Duration, Utc};
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Whether an alert may be throttled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertPriority {
+    Normal,
+    /// Always sent immediately
+    Critical,
+}
+
+/// Alerts of one type held back in a window that has closed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThrottleSummary {
+    pub kind: String,
+    pub suppressed: u32,
+    pub window_secs: i64,
+}
+
+/// Alerts of one type seen in the current window
+struct KindWindow {
+    started: DateTime<Utc>,
+    suppressed: u32,
+}
+
+struct State {
+    /// Messages that may be sent right now
+    tokens: f64,
+    refilled_at: DateTime<Utc>,
+    windows: HashMap<String, KindWindow>,
+}
+
+/// Coalesces repeated alerts and limits the notification rate
+pub struct NotifyThrottle {
+    config: NotifyThrottleConfig,
+    state: Mutex<State>,
+}
+
+impl NotifyThrottle {
+    pub fn new(config: NotifyThrottleConfig) -> Self {
+        let tokens = f64::from(config.burst.max(1));
+        Self {
+            config,
+            state: Mutex::new(State {
+                tokens,
+                refilled_at: Utc::now(),
+                windows: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Whether an alert of `kind` may be sent now
+    pub fn admit(&self, kind: &str, priority: AlertPriority) -> bool {
+        self.admit_at(kind, priority, Utc::now())
+    }
+
+    /// Whether an alert of `kind` may be sent at `now`
+    ///
+    /// A refused alert is counted towards its type's next summary.
+    pub fn admit_at(&self, kind: &str, priority: AlertPriority, now: DateTime<Utc>) -> bool {
+        if priority == AlertPriority::Critical {
+            return true;
+        }
+        let window = self.window();
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(open) = state.windows.get_mut(kind) {
+            if now - open.started < window {
+                open.suppressed += 1;
+                return false;
+            }
+        }
+
+        let allowed = self.take_token(&mut state, now);
+        if self.config.window_secs > 0 || !allowed {
+            state.windows.insert(
+                kind.to_string(),
+                KindWindow {
+                    started: now,
+                    suppressed: u32::from(!allowed),
+                },
+            );
+        }
+        allowed
+    }
+
+    /// Summaries for windows that have closed with suppressed alerts
+    pub fn due_summaries(&self) -> Vec<ThrottleSummary> {
+        self.due_summaries_at(Utc::now())
+    }
+
+    /// Summaries for windows closed by `now`, which are then forgotten
+    pub fn due_summaries_at(&self, now: DateTime<Utc>) -> Vec<ThrottleSummary> {
+        let window = self.window();
+        let mut state = self.state.lock().unwrap();
+        let mut closed: Vec<ThrottleSummary> = Vec::new();
+        state.windows.retain(|kind, open| {
+            if now - open.started < window {
+                return true;
+            }
+            if open.suppressed > 0 {
+                closed.push(ThrottleSummary {
+                    kind: kind.clone(),
+                    suppressed: open.suppressed,
+                    window_secs: window.num_seconds(),
+                });
+            }
+            false
+        });
+        closed.sort_by(|a, b| a.kind.cmp(&b.kind));
+        closed
+    }
+
+    /// How long repeats are folded together; without coalescing, alerts
+    /// refused by the rate limit are still held for a minute
+    fn window(&self) -> Duration {
+        match self.config.window_secs {
+            0 => Duration::minutes(1),
+            secs => Duration::seconds(secs as i64),
+        }
+    }
+
+    fn take_token(&self, state: &mut State, now: DateTime<Utc>) -> bool {
+        if self.config.max_per_minute == 0 {
+            return true;
+        }
+        let capacity = f64::from(self.config.burst.max(1));
+        let elapsed = (now - state.refilled_at).num_milliseconds().max(0) as f64 / 1000.0;
+        state.tokens = (state.tokens + elapsed * f64::from(self.config.max_per_minute) / 60.0).min(capacity);
+        state.refilled_at = now;
+        if state.tokens >= 1.0 {
+            state.tokens -= 1.0;
+            true
+        } else {
+            false
+        }
+    }
+}
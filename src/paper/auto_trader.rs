@@ -391,6 +391,10 @@ mod tests {
             liquidity: dec!(5000),
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         }
     }
 
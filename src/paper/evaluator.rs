@@ -513,6 +513,10 @@ mod tests {
             liquidity: dec!(10000),
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         }
     }
 
@@ -615,6 +619,10 @@ mod tests {
             liquidity: dec!(0),
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         };
         
         let characteristics = evaluator.analyze_characteristics(&market, dec!(0.5), dec!(0.5));
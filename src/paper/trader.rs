@@ -548,6 +548,10 @@ mod tests {
             liquidity: dec!(10000),
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         }
     }
 
@@ -4,8 +4,10 @@
 //! - Flash crash detection (rapid price drops)
 //! - Volatility spike detection
 //! - Liquidity crisis detection
+//! - Spread blowout detection
 //! - Automatic circuit breakers
 
+use super::liquidity_monitor::OrderBookSnapshot;
 use chrono::{DateTime, Duration, Utc};
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
@@ -28,6 +30,11 @@ pub struct BlackSwanConfig {
     pub auto_close_on_event: bool,
     /// Reduce position size multiplier during elevated risk
     pub elevated_risk_size_multiplier: Decimal,
+    /// Lift flash-crash protection early once price is back within this
+    /// fraction of the pre-crash level (e.g., 0.05 = within 5%)
+    pub early_recovery_band: Decimal,
+    /// Spread multiple over baseline that flags a blowout (e.g., 3.0 = 3x normal spread)
+    pub spread_blowout_multiplier: Decimal,
 }
 
 impl Default for BlackSwanConfig {
@@ -40,6 +47,8 @@ impl Default for BlackSwanConfig {
             protection_cooldown_secs: 3600,             // 1 hour cooldown
             auto_close_on_event: true,
             elevated_risk_size_multiplier: dec!(0.25),  // 25% of normal size
+            early_recovery_band: dec!(0.05),            // Within 5% of pre-crash
+            spread_blowout_multiplier: dec!(3.0),       // 3x normal spread
         }
     }
 }
@@ -66,6 +75,12 @@ pub enum BlackSwanEvent {
         previous_liquidity: Decimal,
         current_liquidity: Decimal,
     },
+    /// Bid-ask spread blew out relative to its baseline
+    SpreadBlowout {
+        market_id: String,
+        current_spread: Decimal,
+        normal_spread: Decimal,
+    },
     /// Multiple correlated markets crashing
     CorrelatedCrash {
         market_ids: Vec<String>,
@@ -121,6 +136,10 @@ pub struct BlackSwanProtector {
     event_history: Vec<(DateTime<Utc>, BlackSwanEvent)>,
     /// Markets currently flagged as high risk
     high_risk_markets: HashMap<String, DateTime<Utc>>,
+    /// Window high before each flash crash, used for recovery detection
+    pre_crash_prices: HashMap<String, Decimal>,
+    /// Baseline spread per market (running average, sample count)
+    baseline_spread: HashMap<String, (Decimal, usize)>,
 }
 
 impl BlackSwanProtector {
@@ -138,6 +157,8 @@ impl BlackSwanProtector {
             },
             event_history: Vec::new(),
             high_risk_markets: HashMap::new(),
+            pre_crash_prices: HashMap::new(),
+            baseline_spread: HashMap::new(),
         }
     }
 
@@ -165,6 +186,9 @@ impl BlackSwanProtector {
             history.pop_front();
         }
         
+        // A bounce back can lift protection before the cooldown expires
+        self.check_recovery(market_id);
+        
         // Check for various black swan conditions
         if let Some(event) = self.check_flash_crash(market_id) {
             return Some(event);
@@ -181,6 +205,52 @@ impl BlackSwanProtector {
         None
     }
 
+    /// Update from an order book snapshot
+    ///
+    /// Runs the regular price/liquidity checks using the mid price and total
+    /// depth, then checks whether the spread has blown out.
+    pub fn update_with_order_book(&mut self, snapshot: &OrderBookSnapshot) -> Option<BlackSwanEvent> {
+        let event = self.update(&snapshot.market_id, snapshot.mid_price, Some(snapshot.total_depth()));
+        if event.is_some() {
+            return event;
+        }
+        
+        let spread = snapshot.spread()?;
+        self.check_spread_blowout(&snapshot.market_id, spread)
+    }
+
+    /// Check for spread blowout (spread far above its baseline)
+    fn check_spread_blowout(&mut self, market_id: &str, spread: Decimal) -> Option<BlackSwanEvent> {
+        let (baseline, samples) = self.baseline_spread
+            .entry(market_id.to_string())
+            .or_insert((spread, 0));
+        
+        if *samples >= self.config.min_data_points
+            && *baseline > Decimal::ZERO
+            && spread >= *baseline * self.config.spread_blowout_multiplier
+        {
+            let event = BlackSwanEvent::SpreadBlowout {
+                market_id: market_id.to_string(),
+                current_spread: spread,
+                normal_spread: *baseline,
+            };
+            
+            // Keep the baseline untouched so a persistent blowout stays flagged;
+            // mark as high risk but don't trigger full protection
+            self.high_risk_markets.insert(market_id.to_string(), Utc::now());
+            self.event_history.push((Utc::now(), event.clone()));
+            
+            return Some(event);
+        }
+        
+        // Update baseline with exponential moving average
+        let alpha = dec!(0.1);
+        *baseline = *baseline * (Decimal::ONE - alpha) + spread * alpha;
+        *samples += 1;
+        
+        None
+    }
+
     /// Check for flash crash (rapid price drop)
     fn check_flash_crash(&mut self, market_id: &str) -> Option<BlackSwanEvent> {
         let history = self.price_history.get(market_id)?;
@@ -218,6 +288,7 @@ impl BlackSwanProtector {
                 duration_secs: self.config.flash_crash_window_secs,
             };
             
+            self.pre_crash_prices.insert(market_id.to_string(), max_price);
             self.trigger_protection(&event);
             return Some(event);
         }
@@ -225,6 +296,44 @@ impl BlackSwanProtector {
         None
     }
 
+    /// Lift flash-crash protection if the crashed market has recovered
+    ///
+    /// Clears protection when the latest price is back within
+    /// `early_recovery_band` of the pre-crash high, regardless of cooldown.
+    /// Returns true if protection was lifted.
+    pub fn check_recovery(&mut self, market_id: &str) -> bool {
+        let crashed_here = matches!(
+            &self.protection_state.event,
+            Some(BlackSwanEvent::FlashCrash { market_id: id, .. }) if id == market_id
+        );
+        if !self.protection_state.is_active || !crashed_here {
+            return false;
+        }
+        
+        let Some(&pre_crash) = self.pre_crash_prices.get(market_id) else {
+            return false;
+        };
+        let Some(current) = self.price_history.get(market_id).and_then(|h| h.back()) else {
+            return false;
+        };
+        
+        let recovery_level = pre_crash * (Decimal::ONE - self.config.early_recovery_band);
+        if current.price < recovery_level {
+            return false;
+        }
+        
+        self.protection_state = ProtectionState {
+            is_active: false,
+            triggered_at: None,
+            event: None,
+            action_taken: None,
+            expires_at: None,
+        };
+        self.high_risk_markets.remove(market_id);
+        self.pre_crash_prices.remove(market_id);
+        true
+    }
+
     /// Check for volatility spike
     fn check_volatility_spike(&mut self, market_id: &str) -> Option<BlackSwanEvent> {
         let history = self.price_history.get(market_id)?;
@@ -403,7 +512,7 @@ impl BlackSwanProtector {
                     }
                 }
             },
-            BlackSwanEvent::VolatilitySpike { .. } => {
+            BlackSwanEvent::VolatilitySpike { .. } | BlackSwanEvent::SpreadBlowout { .. } => {
                 ProtectionAction::ReduceExposure {
                     multiplier: self.config.elevated_risk_size_multiplier,
                 }
@@ -548,6 +657,7 @@ impl BlackSwanProtector {
             expires_at: None,
         };
         self.high_risk_markets.clear();
+        self.pre_crash_prices.clear();
     }
 
     /// Clear all data for a market
@@ -555,12 +665,15 @@ impl BlackSwanProtector {
         self.price_history.remove(market_id);
         self.baseline_volatility.remove(market_id);
         self.high_risk_markets.remove(market_id);
+        self.pre_crash_prices.remove(market_id);
+        self.baseline_spread.remove(market_id);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::risk::OrderBookLevel;
 
     fn make_protector() -> BlackSwanProtector {
         let config = BlackSwanConfig {
@@ -571,10 +684,23 @@ mod tests {
             protection_cooldown_secs: 3600,
             auto_close_on_event: true,
             elevated_risk_size_multiplier: dec!(0.25),
+            early_recovery_band: dec!(0.05),
+            spread_blowout_multiplier: dec!(3.0),
         };
         BlackSwanProtector::new(config)
     }
 
+    fn make_book(market_id: &str, mid: Decimal, spread: Decimal) -> OrderBookSnapshot {
+        let half = mid * spread / dec!(2);
+        OrderBookSnapshot {
+            market_id: market_id.to_string(),
+            timestamp: Utc::now(),
+            bids: vec![OrderBookLevel { price: mid - half, size: dec!(1000) }],
+            asks: vec![OrderBookLevel { price: mid + half, size: dec!(1000) }],
+            mid_price: mid,
+        }
+    }
+
     #[test]
     fn test_new_protector() {
         let protector = make_protector();
@@ -841,4 +967,97 @@ mod tests {
             assert!(event.is_none());
         }
     }
+
+    #[test]
+    fn test_flash_crash_from_mid_window_peak() {
+        let mut protector = make_protector();
+        
+        // Peak sits in the middle of the window, not at either end
+        for price in [dec!(0.60), dec!(0.70), dec!(0.80), dec!(0.75), dec!(0.70)] {
+            assert!(protector.update("market1", price, None).is_none());
+        }
+        
+        match protector.update("market1", dec!(0.64), None) {
+            Some(BlackSwanEvent::FlashCrash { drop_percent, .. }) => {
+                assert_eq!(drop_percent, dec!(0.20)); // from 0.80, not 0.60 or 0.70
+            }
+            other => panic!("Expected flash crash, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_early_recovery_lifts_protection() {
+        let mut protector = make_protector();
+        
+        for _ in 0..5 {
+            protector.update("market1", dec!(0.80), None);
+        }
+        let event = protector.update("market1", dec!(0.60), None);
+        assert!(matches!(event, Some(BlackSwanEvent::FlashCrash { .. })));
+        assert!(protector.protection_state().is_active);
+        let expires_at = protector.protection_state().expires_at.unwrap();
+        
+        // Partial bounce is not enough (needs >= 0.76)
+        protector.update("market1", dec!(0.70), None);
+        assert!(protector.protection_state().is_active);
+        
+        // Back within 5% of the pre-crash high
+        protector.update("market1", dec!(0.78), None);
+        assert!(!protector.protection_state().is_active);
+        assert!(Utc::now() < expires_at);
+        assert!(protector.can_trade());
+        assert!(!protector.check_recovery("market1"));
+    }
+
+    #[test]
+    fn test_recovery_ignores_other_markets() {
+        let mut protector = make_protector();
+        
+        for _ in 0..5 {
+            protector.update("market1", dec!(0.80), None);
+            protector.update("market2", dec!(0.50), None);
+        }
+        protector.update("market1", dec!(0.60), None);
+        assert!(protector.protection_state().is_active);
+        
+        protector.update("market2", dec!(0.50), None);
+        assert!(!protector.check_recovery("market2"));
+        assert!(protector.protection_state().is_active);
+    }
+
+    #[test]
+    fn test_spread_blowout_detection() {
+        let mut protector = make_protector();
+        
+        // Flat price, tight spread
+        for _ in 0..10 {
+            let event = protector.update_with_order_book(&make_book("market1", dec!(0.50), dec!(0.01)));
+            assert!(event.is_none());
+        }
+        
+        // Spread explodes to 8x while price stays put
+        let event = protector.update_with_order_book(&make_book("market1", dec!(0.50), dec!(0.08)));
+        match event {
+            Some(BlackSwanEvent::SpreadBlowout { current_spread, normal_spread, .. }) => {
+                assert!(current_spread > normal_spread * dec!(3));
+            }
+            other => panic!("Expected spread blowout, got {:?}", other),
+        }
+        
+        // High risk for this market, but no global halt
+        assert!(protector.should_avoid_market("market1"));
+        assert_eq!(protector.get_size_multiplier("market1"), dec!(0.25));
+        assert!(!protector.protection_state().is_active);
+        assert!(protector.can_trade());
+        assert!(protector.get_size_multiplier("market2") == Decimal::ONE);
+    }
+
+    #[test]
+    fn test_spread_blowout_needs_baseline() {
+        let mut protector = make_protector();
+        
+        protector.update_with_order_book(&make_book("market1", dec!(0.50), dec!(0.01)));
+        let event = protector.update_with_order_book(&make_book("market1", dec!(0.50), dec!(0.08)));
+        assert!(event.is_none());
+    }
 }
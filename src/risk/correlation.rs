@@ -2,11 +2,14 @@
 //!
 //! Detects correlation between markets to avoid overexposure
 //! to correlated positions.
+//!
+//! Correlation is measured on log-returns, not price levels: prices are
+//! bucketed by timestamp, aligned on the buckets both markets share, and
+//! the returns between consecutive shared buckets are correlated.
 
-use crate::utils::sqrt_decimal;
-use rust_decimal::Decimal;
+use rust_decimal::{Decimal, MathematicalOps};
 use rust_decimal_macros::dec;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{BTreeMap, HashMap, VecDeque};
 
 /// Market correlation detection and management
 pub struct CorrelationDetector {
@@ -18,6 +21,10 @@ pub struct CorrelationDetector {
     correlation_threshold: Decimal,
     /// Maximum data points to keep
     max_history: usize,
+    /// Timestamp bucket width used to align series
+    bucket_secs: i64,
+    /// Minimum aligned returns required for a correlation
+    min_overlap: usize,
 }
 
 /// Correlation matrix between markets
@@ -44,14 +51,28 @@ impl CorrelationDetector {
             correlation_cache: CorrelationMatrix::default(),
             correlation_threshold: Decimal::try_from(correlation_threshold).unwrap_or(dec!(0.7)),
             max_history: 100,
+            bucket_secs: 1,
+            min_overlap: 5,
         }
     }
 
+    /// Align timestamps into buckets of this width (e.g., 60 for minute bars)
+    pub fn with_bucket_secs(mut self, bucket_secs: i64) -> Self {
+        self.bucket_secs = bucket_secs.max(1);
+        self
+    }
+
+    /// Minimum number of aligned returns before a correlation is reported
+    pub fn with_min_overlap(mut self, min_overlap: usize) -> Self {
+        self.min_overlap = min_overlap.max(2);
+        self
+    }
+
     /// Add a price point for a market
     pub fn add_price_point(&mut self, market_id: &str, price: Decimal, timestamp: i64) {
         let history = self.price_history
             .entry(market_id.to_string())
-            .or_insert_with(VecDeque::new);
+            .or_default();
         
         history.push_back((timestamp, price));
         
@@ -134,33 +155,36 @@ impl CorrelationDetector {
         }
     }
 
-    /// Calculate Pearson correlation coefficient between two markets
+    /// Calculate Pearson correlation of aligned log-returns between two markets
     fn calculate_correlation(&mut self, market_a: &str, market_b: &str) {
-        let Some(history_a) = self.price_history.get(market_a) else {
-            return;
-        };
-        let Some(history_b) = self.price_history.get(market_b) else {
+        let key = self.make_key(market_a, market_b);
+        let (Some(history_a), Some(history_b)) =
+            (self.price_history.get(market_a), self.price_history.get(market_b))
+        else {
             return;
         };
 
-        // Find overlapping timestamps
-        let times_a: HashMap<i64, Decimal> = history_a.iter().copied().collect();
-        let times_b: HashMap<i64, Decimal> = history_b.iter().copied().collect();
+        let buckets_a = self.bucketize(history_a);
+        let buckets_b = self.bucketize(history_b);
 
-        let mut pairs: Vec<(Decimal, Decimal)> = Vec::new();
-        for (ts, price_a) in &times_a {
-            if let Some(&price_b) = times_b.get(ts) {
-                pairs.push((*price_a, price_b));
-            }
-        }
+        // Prices on the buckets both markets share, in time order
+        let aligned: Vec<(Decimal, Decimal)> = buckets_a
+            .iter()
+            .filter_map(|(bucket, price_a)| buckets_b.get(bucket).map(|price_b| (*price_a, *price_b)))
+            .collect();
 
-        // Need at least 5 pairs for meaningful correlation
-        if pairs.len() < 5 {
+        // Returns between consecutive shared buckets cover the same interval for both
+        let pairs: Vec<(Decimal, Decimal)> = aligned
+            .windows(2)
+            .filter_map(|w| Some((log_return(w[0].0, w[1].0)?, log_return(w[0].1, w[1].1)?)))
+            .collect();
+
+        if pairs.len() < self.min_overlap {
+            self.correlation_cache.correlations.remove(&key);
             return;
         }
 
         let correlation = self.pearson_correlation(&pairs);
-        let key = self.make_key(market_a, market_b);
 
         self.correlation_cache.correlations.insert(
             key,
@@ -174,30 +198,40 @@ impl CorrelationDetector {
         );
     }
 
+    /// Collapse a price history into time buckets, keeping the last price per bucket
+    fn bucketize(&self, history: &VecDeque<(i64, Decimal)>) -> BTreeMap<i64, Decimal> {
+        history
+            .iter()
+            .map(|(ts, price)| (ts.div_euclid(self.bucket_secs), *price))
+            .collect()
+    }
+
     /// Calculate Pearson correlation coefficient
     fn pearson_correlation(&self, pairs: &[(Decimal, Decimal)]) -> Decimal {
         let n = Decimal::from(pairs.len() as i64);
-        
-        let sum_x: Decimal = pairs.iter().map(|(x, _)| *x).sum();
-        let sum_y: Decimal = pairs.iter().map(|(_, y)| *y).sum();
-        let sum_xy: Decimal = pairs.iter().map(|(x, y)| *x * *y).sum();
-        let sum_x2: Decimal = pairs.iter().map(|(x, _)| *x * *x).sum();
-        let sum_y2: Decimal = pairs.iter().map(|(_, y)| *y * *y).sum();
-
-        let numerator = n * sum_xy - sum_x * sum_y;
-        let denominator_sq = (n * sum_x2 - sum_x * sum_x) * (n * sum_y2 - sum_y * sum_y);
-
-        if denominator_sq <= Decimal::ZERO {
-            return Decimal::ZERO;
+        let mean_x = pairs.iter().map(|(x, _)| *x).sum::<Decimal>() / n;
+        let mean_y = pairs.iter().map(|(_, y)| *y).sum::<Decimal>() / n;
+
+        let mut cov = Decimal::ZERO;
+        let mut var_x = Decimal::ZERO;
+        let mut var_y = Decimal::ZERO;
+        for (x, y) in pairs {
+            let dx = *x - mean_x;
+            let dy = *y - mean_y;
+            cov += dx * dy;
+            var_x += dx * dx;
+            var_y += dy * dy;
         }
 
-        // Approximate square root
-        let denominator = sqrt_decimal(denominator_sq);
+        let denominator = match (var_x.sqrt(), var_y.sqrt()) {
+            (Some(sx), Some(sy)) => sx * sy,
+            _ => return Decimal::ZERO,
+        };
         if denominator == Decimal::ZERO {
             return Decimal::ZERO;
         }
 
-        numerator / denominator
+        (cov / denominator).max(dec!(-1)).min(Decimal::ONE)
     }
 
     /// Create a canonical key for the correlation cache
@@ -210,6 +244,14 @@ impl CorrelationDetector {
     }
 }
 
+/// Log-return between two prices (None for non-positive prices)
+fn log_return(from: Decimal, to: Decimal) -> Option<Decimal> {
+    if from <= Decimal::ZERO || to <= Decimal::ZERO {
+        return None;
+    }
+    (to / from).checked_ln()
+}
+
 impl CorrelationMatrix {
     /// Get the number of correlation entries
     pub fn len(&self) -> usize {
@@ -265,14 +307,21 @@ mod tests {
         assert!(corr > dec!(0.9)); // Should be close to 1.0
     }
 
+    /// Multiplicative moves with no trend, so returns are genuinely mixed
+    const MOVES: [Decimal; 10] = [
+        dec!(1.10), dec!(0.95), dec!(1.04), dec!(0.90), dec!(1.08),
+        dec!(0.97), dec!(1.02), dec!(0.93), dec!(1.06), dec!(0.99),
+    ];
+
     #[test]
     fn test_perfect_negative_correlation() {
         let mut detector = CorrelationDetector::new(0.7);
         
         // Two markets moving opposite
-        for i in 1..=10 {
-            let price1 = Decimal::from(i) / dec!(10);
-            let price2 = Decimal::ONE - price1;
+        let (mut price1, mut price2) = (dec!(0.5), dec!(0.5));
+        for (i, m) in MOVES.iter().enumerate() {
+            price1 *= *m;
+            price2 /= *m;
             detector.add_price_point("market1", price1, i as i64);
             detector.add_price_point("market2", price2, i as i64);
         }
@@ -382,6 +431,78 @@ mod tests {
         assert_eq!(detector.price_history.get("market1").unwrap().len(), 10);
     }
 
+    #[test]
+    fn test_returns_not_levels() {
+        let mut detector = CorrelationDetector::new(0.7);
+        
+        // Both trend upward, but their period-to-period moves are orthogonal:
+        // market1 alternates up/down-ish, market2 moves in pairs
+        let (mut price1, mut price2) = (dec!(0.3), dec!(0.3));
+        for i in 0..13 {
+            price1 *= if i % 2 == 0 { dec!(1.05) } else { dec!(1.01) };
+            price2 *= if (i / 2) % 2 == 0 { dec!(1.05) } else { dec!(1.01) };
+            detector.add_price_point("market1", price1, i as i64);
+            detector.add_price_point("market2", price2, i as i64);
+        }
+        
+        // Price levels are strongly correlated; returns are not
+        let corr = detector.get_correlation("market1", "market2").unwrap();
+        assert!(corr.abs() < dec!(0.2), "corr = {}", corr);
+        assert!(!detector.are_correlated("market1", "market2"));
+    }
+
+    #[test]
+    fn test_independent_series() {
+        let mut detector = CorrelationDetector::new(0.7);
+        
+        let (mut price1, mut price2) = (dec!(0.5), dec!(0.5));
+        for (i, m) in MOVES.iter().enumerate() {
+            price1 *= *m;
+            // Shift the move pattern by five steps for market2
+            price2 *= MOVES[(i + 5) % MOVES.len()];
+            detector.add_price_point("market1", price1, i as i64);
+            detector.add_price_point("market2", price2, i as i64);
+        }
+        
+        let corr = detector.get_correlation("market1", "market2").unwrap();
+        assert!(corr.abs() < dec!(0.7), "corr = {}", corr);
+        assert_eq!(detector.get_correlation_penalty("market2", &["market1".to_string()]), Decimal::ONE);
+    }
+
+    #[test]
+    fn test_misaligned_timestamps() {
+        let mut detector = CorrelationDetector::new(0.7).with_bucket_secs(60);
+        
+        // Same moves, sampled a few seconds apart, plus one extra market1 tick
+        let mut price = dec!(0.5);
+        for (i, m) in MOVES.iter().enumerate() {
+            price *= *m;
+            let minute = i as i64 * 60;
+            detector.add_price_point("market1", price, minute + 2);
+            detector.add_price_point("market2", price, minute + 41);
+        }
+        detector.add_price_point("market1", dec!(0.9), 10_000);
+        
+        let corr = detector.get_correlation("market1", "market2").unwrap();
+        assert!(corr > dec!(0.99), "corr = {}", corr);
+        assert_eq!(detector.get_matrix().iter().next().unwrap().sample_count, 9);
+    }
+
+    #[test]
+    fn test_small_overlap_returns_none() {
+        let mut detector = CorrelationDetector::new(0.7);
+        
+        // Plenty of points each, but only three shared timestamps
+        let mut price = dec!(0.5);
+        for (i, m) in MOVES.iter().enumerate() {
+            price *= *m;
+            detector.add_price_point("market1", price, i as i64);
+            detector.add_price_point("market2", price, i as i64 + 7);
+        }
+        
+        assert!(detector.get_correlation("market1", "market2").is_none());
+    }
+
     #[test]
     fn test_correlation_matrix() {
         let matrix = CorrelationMatrix::default();
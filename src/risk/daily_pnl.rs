@@ -1,8 +1,10 @@
 //! Daily P&L Tracking with Loss Limits
 
+use crate::error::{BotError, Result};
 use chrono::Utc;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::path::Path;
 
 /// Tracks daily profit and loss with automatic reset
 #[derive(Debug, Clone)]
@@ -13,6 +15,12 @@ pub struct DailyPnlTracker {
     max_loss_pct: Decimal,
     /// Starting balance for the day (set on first trade)
     starting_balance: Option<Decimal>,
+    /// Latest mark-to-market portfolio value
+    portfolio_value: Option<Decimal>,
+    /// Highest portfolio value seen today
+    peak_value: Option<Decimal>,
+    /// Largest peak-to-trough drop today (0.10 = 10%)
+    max_drawdown: Decimal,
 }
 
 /// Serializable state for persistence
@@ -47,6 +55,15 @@ impl DailyPnlState {
     }
 }
 
+/// On-disk format: the day's state plus the balance the loss limit is measured against
+#[derive(Serialize, Deserialize)]
+struct PersistedPnl {
+    #[serde(flatten)]
+    state: DailyPnlState,
+    #[serde(default)]
+    starting_balance: Option<Decimal>,
+}
+
 impl Default for DailyPnlState {
     fn default() -> Self {
         Self::new()
@@ -60,9 +77,17 @@ impl DailyPnlTracker {
             state: DailyPnlState::new(),
             max_loss_pct,
             starting_balance: None,
+            portfolio_value: None,
+            peak_value: None,
+            max_drawdown: Decimal::ZERO,
         }
     }
 
+    /// Change the daily loss limit (e.g., after a config reload)
+    pub fn set_max_loss_pct(&mut self, max_loss_pct: Decimal) {
+        self.max_loss_pct = max_loss_pct;
+    }
+
     /// Set the starting balance for percentage calculations
     pub fn set_starting_balance(&mut self, balance: Decimal) {
         if self.starting_balance.is_none() {
@@ -90,8 +115,53 @@ impl DailyPnlTracker {
         }
     }
 
+    /// Record the current portfolio value (cash plus marked positions)
+    ///
+    /// The first value of the day becomes the starting balance. Unrealized
+    /// losses count towards the daily loss limit from here on.
+    pub fn mark_to_market(&mut self, portfolio_value: Decimal) {
+        self.check_and_reset_day();
+        self.set_starting_balance(portfolio_value);
+        self.portfolio_value = Some(portfolio_value);
+
+        let peak = self.peak_value.map_or(portfolio_value, |p| p.max(portfolio_value));
+        self.peak_value = Some(peak);
+        if peak > Decimal::ZERO {
+            self.max_drawdown = self.max_drawdown.max((peak - portfolio_value) / peak);
+        }
+    }
+
+    /// Latest mark-to-market portfolio value, if marked
+    pub fn portfolio_value(&self) -> Option<Decimal> {
+        self.portfolio_value
+    }
+
+    /// Portfolio value change since the start of the day, if marked
+    pub fn mark_to_market_pnl(&self) -> Option<Decimal> {
+        Some(self.portfolio_value? - self.starting_balance?)
+    }
+
+    /// Drop from today's peak portfolio value (0.10 = 10%)
+    pub fn current_drawdown(&self) -> Decimal {
+        match (self.peak_value, self.portfolio_value) {
+            (Some(peak), Some(value)) if peak > Decimal::ZERO => (peak - value) / peak,
+            _ => Decimal::ZERO,
+        }
+    }
+
+    /// Largest peak-to-trough drop today (0.10 = 10%)
+    pub fn max_drawdown(&self) -> Decimal {
+        self.max_drawdown
+    }
+
     /// Check if daily loss limit has been reached
     pub fn is_limit_reached(&self) -> bool {
+        if let (Some(value), Some(starting)) = (self.portfolio_value, self.starting_balance) {
+            if starting > Decimal::ZERO && (starting - value) / starting >= self.max_loss_pct {
+                return true;
+            }
+        }
+
         if self.state.realized_pnl >= Decimal::ZERO {
             return false;
         }
@@ -135,10 +205,50 @@ impl DailyPnlTracker {
         }
     }
 
+    /// Save the current state (including starting balance) to a JSON file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let persisted = PersistedPnl {
+            state: self.state.clone(),
+            starting_balance: self.starting_balance,
+        };
+        let json = serde_json::to_string_pretty(&persisted)?;
+
+        if let Some(parent) = path.as_ref().parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| BotError::Internal(format!("Failed to create P&L state dir: {}", e)))?;
+            }
+        }
+        std::fs::write(path, json)
+            .map_err(|e| BotError::Internal(format!("Failed to write P&L state: {}", e)))
+    }
+
+    /// Load state from a JSON file written by `save`
+    ///
+    /// A state from a previous day is discarded and the tracker reset instead.
+    /// Returns whether the stored state was restored.
+    pub fn load<P: AsRef<Path>>(&mut self, path: P) -> Result<bool> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| BotError::Internal(format!("Failed to read P&L state: {}", e)))?;
+        let persisted: PersistedPnl = serde_json::from_str(&content)?;
+
+        if !persisted.state.is_current_day() {
+            self.reset();
+            return Ok(false);
+        }
+
+        self.state = persisted.state;
+        self.starting_balance = persisted.starting_balance;
+        Ok(true)
+    }
+
     /// Reset for a new day
     pub fn reset(&mut self) {
         self.state = DailyPnlState::new();
         self.starting_balance = None;
+        self.portfolio_value = None;
+        self.peak_value = None;
+        self.max_drawdown = Decimal::ZERO;
     }
 
     /// Get win rate as a percentage
@@ -255,4 +365,84 @@ mod tests {
         
         assert_eq!(new_tracker.current_pnl(), Decimal::new(100, 0));
     }
+
+    #[test]
+    fn test_save_load_same_day() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pnl.json");
+
+        let mut tracker = DailyPnlTracker::new(Decimal::new(10, 2));
+        tracker.set_starting_balance(Decimal::new(1000, 0));
+        tracker.record_pnl(Decimal::new(-80, 0));
+        tracker.record_pnl(Decimal::new(-20, 0));
+        assert!(tracker.is_limit_reached());
+        tracker.save(&path).unwrap();
+
+        // A restarted tracker keeps the loss limit engaged
+        let mut restored = DailyPnlTracker::new(Decimal::new(10, 2));
+        assert!(restored.load(&path).unwrap());
+        assert_eq!(restored.current_pnl(), Decimal::new(-100, 0));
+        assert_eq!(restored.state.trade_count, 2);
+        assert_eq!(restored.remaining_loss_budget(), Some(Decimal::ZERO));
+        assert!(restored.is_limit_reached());
+    }
+
+    #[test]
+    fn test_load_prior_day_resets() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("pnl.json");
+
+        let mut tracker = DailyPnlTracker::new(Decimal::new(10, 2));
+        tracker.set_starting_balance(Decimal::new(1000, 0));
+        tracker.record_pnl(Decimal::new(-150, 0));
+        tracker.state.date = "2020-01-01".to_string();
+        tracker.save(&path).unwrap();
+
+        let mut restored = DailyPnlTracker::new(Decimal::new(10, 2));
+        restored.record_pnl(Decimal::new(5, 0));
+        assert!(!restored.load(&path).unwrap());
+        assert_eq!(restored.current_pnl(), Decimal::ZERO);
+        assert_eq!(restored.state.trade_count, 0);
+        assert!(restored.state.is_current_day());
+        assert!(restored.remaining_loss_budget().is_none());
+        assert!(!restored.is_limit_reached());
+    }
+
+    #[test]
+    fn test_load_missing_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut tracker = DailyPnlTracker::new(Decimal::new(10, 2));
+        assert!(tracker.load(dir.path().join("missing.json")).is_err());
+    }
+
+    #[test]
+    fn test_mark_to_market_drawdown() {
+        let mut tracker = DailyPnlTracker::new(Decimal::new(10, 2));
+        for value in [1000, 1100, 1045, 1080] {
+            tracker.mark_to_market(Decimal::from(value));
+        }
+
+        assert_eq!(tracker.mark_to_market_pnl(), Some(Decimal::from(80)));
+        // Peak 1100 -> trough 1045
+        assert_eq!(tracker.max_drawdown(), Decimal::new(5, 2));
+        assert_eq!(tracker.current_drawdown(), Decimal::from(20) / Decimal::from(1100));
+        assert!(!tracker.is_limit_reached());
+    }
+
+    #[test]
+    fn test_unrealized_loss_hits_limit() {
+        let mut tracker = DailyPnlTracker::new(Decimal::new(10, 2));
+        tracker.mark_to_market(Decimal::from(1000));
+        tracker.mark_to_market(Decimal::from(905));
+        assert!(!tracker.is_limit_reached());
+
+        // No realized loss, but marked 10% down
+        tracker.mark_to_market(Decimal::from(900));
+        assert_eq!(tracker.current_pnl(), Decimal::ZERO);
+        assert!(tracker.is_limit_reached());
+
+        tracker.reset();
+        assert_eq!(tracker.max_drawdown(), Decimal::ZERO);
+        assert!(!tracker.is_limit_reached());
+    }
 }
@@ -6,6 +6,7 @@
 //! - Slippage estimation
 //! - Liquidity score calculation for position sizing
 
+use crate::types::{walk_levels, OrderBook};
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
@@ -47,12 +48,8 @@ impl Default for LiquidityConfig {
     }
 }
 
-/// Order book level
-#[derive(Debug, Clone)]
-pub struct OrderBookLevel {
-    pub price: Decimal,
-    pub size: Decimal,
-}
+/// Order book level, the same type the CLOB client's `OrderBook` uses
+pub use crate::types::OrderBookLevel;
 
 /// Order book snapshot
 #[derive(Debug, Clone)]
@@ -65,6 +62,20 @@ pub struct OrderBookSnapshot {
 }
 
 impl OrderBookSnapshot {
+    /// Snapshot of a book fetched from the CLOB, taken now
+    ///
+    /// `mid_price` is zero when either side of the book is empty.
+    pub fn from_book(market_id: impl Into<String>, book: OrderBook) -> Self {
+        let mid_price = book.mid().unwrap_or(Decimal::ZERO);
+        Self {
+            market_id: market_id.into(),
+            timestamp: Utc::now(),
+            bids: book.bids,
+            asks: book.asks,
+            mid_price,
+        }
+    }
+
     /// Calculate bid-ask spread
     pub fn spread(&self) -> Option<Decimal> {
         let best_bid = self.bids.first()?.price;
@@ -92,6 +103,12 @@ impl OrderBookSnapshot {
         self.bid_depth() + self.ask_depth()
     }
 
+    /// Total size (shares) available to fill on one side of the book
+    pub fn fillable_size(&self, is_buy: bool) -> Decimal {
+        let levels = if is_buy { &self.asks } else { &self.bids };
+        levels.iter().map(|l| l.size).sum()
+    }
+
     /// Estimate slippage for a given order size
     pub fn estimate_slippage(&self, size: Decimal, is_buy: bool) -> Decimal {
         let levels = if is_buy { &self.asks } else { &self.bids };
@@ -100,21 +117,7 @@ impl OrderBookSnapshot {
             return dec!(1); // 100% slippage (can't fill)
         }
         
-        let mut remaining = size;
-        let mut weighted_price = Decimal::ZERO;
-        let mut filled = Decimal::ZERO;
-        
-        for level in levels {
-            if remaining <= Decimal::ZERO {
-                break;
-            }
-            
-            let fill_size = remaining.min(level.size);
-            weighted_price += level.price * fill_size;
-            filled += fill_size;
-            remaining -= fill_size;
-        }
-        
+        let (filled, weighted_price) = walk_levels(levels, size);
         if filled == Decimal::ZERO {
             return dec!(1);
         }
@@ -141,6 +144,35 @@ impl OrderBookSnapshot {
         
         (bid_depth - ask_depth) / total
     }
+
+    /// Depth-weighted imbalance over the top `levels` of each side (-1 to 1)
+    ///
+    /// Level `i` (0 = best) is weighted by `1 / (i + 1)` so liquidity near
+    /// the touch dominates resting orders deep in the book.
+    pub fn depth_weighted_imbalance(&self, levels: usize) -> Decimal {
+        let weighted = |side: &[OrderBookLevel]| -> Decimal {
+            side.iter()
+                .take(levels)
+                .enumerate()
+                .map(|(i, l)| l.price * l.size / Decimal::from(i as i64 + 1))
+                .sum()
+        };
+        let bid_depth = weighted(&self.bids);
+        let ask_depth = weighted(&self.asks);
+        let total = bid_depth + ask_depth;
+
+        if total == Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+
+        (bid_depth - ask_depth) / total
+    }
+}
+
+impl From<OrderBookSnapshot> for OrderBook {
+    fn from(snapshot: OrderBookSnapshot) -> Self {
+        Self { bids: snapshot.bids, asks: snapshot.asks }
+    }
 }
 
 /// Liquidity assessment result
@@ -215,6 +247,11 @@ impl LiquidityMonitor {
         }
     }
 
+    /// Update with a book fetched from the CLOB
+    pub fn update_from_book(&mut self, market_id: &str, book: OrderBook) -> LiquidityAssessment {
+        self.update_order_book(OrderBookSnapshot::from_book(market_id, book))
+    }
+
     /// Update with new order book data
     pub fn update_order_book(&mut self, snapshot: OrderBookSnapshot) -> LiquidityAssessment {
         let market_id = snapshot.market_id.clone();
@@ -471,6 +508,9 @@ impl LiquidityMonitor {
     }
 
     /// Get maximum recommended position size based on liquidity
+    ///
+    /// The smaller of the size hitting `target_slippage` and the total size
+    /// resting on the ask side, so the book can actually absorb it.
     pub fn max_position_size(&self, market_id: &str, target_slippage: Decimal) -> Option<Decimal> {
         let ob = self.order_books.get(market_id)?;
         
@@ -489,7 +529,8 @@ impl LiquidityMonitor {
             }
         }
         
-        Some(low)
+        // Slippage only covers the filled part, so cap at what the book holds
+        Some(low.min(ob.fillable_size(true)))
     }
 
     /// Get all markets with low liquidity
@@ -554,6 +595,55 @@ mod tests {
         LiquidityMonitor::new(LiquidityConfig::default())
     }
 
+    #[test]
+    fn test_clob_book_converts_to_snapshot() {
+        let book = crate::client::OrderBook {
+            bids: vec![
+                OrderBookLevel { price: dec!(0.50), size: dec!(2000) },
+                OrderBookLevel { price: dec!(0.49), size: dec!(3000) },
+            ],
+            asks: vec![
+                OrderBookLevel { price: dec!(0.51), size: dec!(2000) },
+                OrderBookLevel { price: dec!(0.52), size: dec!(3000) },
+            ],
+        };
+
+        let snapshot = OrderBookSnapshot::from_book("market1", book.clone());
+        assert_eq!(snapshot.market_id, "market1");
+        assert_eq!(snapshot.mid_price, dec!(0.505));
+        assert_eq!(snapshot.bid_depth(), dec!(1000) + dec!(1470));
+        assert_eq!(snapshot.fillable_size(true), dec!(5000));
+
+        let round_trip = OrderBook::from(snapshot);
+        assert_eq!(round_trip.microprice(), book.microprice());
+
+        let mut monitor = make_monitor();
+        let assessment = monitor.update_from_book("market1", book);
+        assert_eq!(assessment.total_depth, dec!(1000) + dec!(1470) + dec!(1020) + dec!(1560));
+        assert!(monitor.get_assessment("market1").is_some());
+
+        // One-sided books have no mid
+        let one_sided = OrderBook { bids: vec![], asks: vec![OrderBookLevel { price: dec!(0.51), size: dec!(10) }] };
+        assert_eq!(OrderBookSnapshot::from_book("market2", one_sided).mid_price, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_depth_weighted_imbalance() {
+        let ob = make_order_book(
+            "test",
+            vec![(0.50, 1000.0), (0.49, 100.0)],
+            vec![(0.52, 100.0), (0.53, 100.0), (0.90, 5000.0)],
+        );
+
+        // Deep ask wall outweighs bids in the raw imbalance...
+        assert!(ob.imbalance() < Decimal::ZERO);
+        // ...but is ignored beyond the level cutoff
+        assert!(ob.depth_weighted_imbalance(2) > dec!(0.5));
+
+        let empty = make_order_book("empty", vec![], vec![]);
+        assert_eq!(empty.depth_weighted_imbalance(5), Decimal::ZERO);
+    }
+
     #[test]
     fn test_order_book_spread() {
         let ob = make_order_book("test", vec![(0.49, 100.0)], vec![(0.51, 100.0)]);
@@ -798,6 +888,33 @@ mod tests {
         assert!(max_size.unwrap() > Decimal::ZERO);
     }
 
+    #[test]
+    fn test_max_position_size_capped_by_depth() {
+        let mut monitor = make_monitor();
+        
+        // Shallow single-level book: slippage stays zero at any size
+        let ob = make_order_book("market1",
+            vec![(0.49, 50.0)],
+            vec![(0.51, 40.0)]
+        );
+        let total_ask_size = ob.fillable_size(true);
+        assert_eq!(total_ask_size, dec!(40));
+        monitor.update_order_book(ob);
+        
+        let max_size = monitor.max_position_size("market1", dec!(0.01)).unwrap();
+        assert!(max_size <= total_ask_size);
+        assert_eq!(max_size, total_ask_size);
+        
+        // Wider tolerance on a two-level book still can't exceed resting size
+        let ob = make_order_book("market2",
+            vec![(0.49, 50.0)],
+            vec![(0.51, 40.0), (0.52, 30.0)]
+        );
+        monitor.update_order_book(ob);
+        let max_size = monitor.max_position_size("market2", dec!(0.50)).unwrap();
+        assert!(max_size <= dec!(70));
+    }
+
     #[test]
     fn test_low_liquidity_markets() {
         let mut monitor = make_monitor();
@@ -23,7 +23,7 @@ mod trailing_stop;
 mod tests;
 
 pub use daily_pnl::{DailyPnlTracker, DailyPnlState};
-pub use volatility_sizer::{VolatilityPositionSizer, VolatilityConfig};
+pub use volatility_sizer::{VolatilityPositionSizer, VolatilityConfig, VolatilityEstimator};
 pub use correlation::{CorrelationDetector, CorrelationMatrix, MarketCorrelation};
 pub use position_manager::{DynamicPositionManager, PositionSizeRequest, PositionSizeResult};
 pub use black_swan::{
@@ -46,6 +46,7 @@ pub use trailing_stop::{
 
 use crate::config::RiskConfig;
 use crate::types::{Market, Position, Signal};
+use chrono::{DateTime, Duration, Utc};
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 
@@ -60,6 +61,19 @@ pub struct RiskManager {
     pub liquidity_monitor: LiquidityMonitor,
     pub correlation_risk: CorrelationRiskManager,
     pub trailing_stop: TrailingStopManager,
+    /// Losing trades in a row since the last win
+    consecutive_losses: u32,
+    /// Active cooldown after a loss streak
+    loss_cooldown: Option<LossCooldown>,
+}
+
+/// Cooldown imposed after `max_consecutive_losses` losing trades
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LossCooldown {
+    /// Blocked until the given time
+    Until(DateTime<Utc>),
+    /// Blocked until `reset_daily` is called
+    UntilDailyReset,
 }
 
 impl RiskManager {
@@ -71,8 +85,23 @@ impl RiskManager {
         let correlation_risk_config = CorrelationRiskConfig::default();
         let trailing_stop_config = TrailingStopConfig::default();
         
+        let mut pnl_tracker = DailyPnlTracker::new(config.max_daily_loss_pct);
+        if let Some(path) = config.pnl_state_path.as_deref() {
+            if std::path::Path::new(path).exists() {
+                match pnl_tracker.load(path) {
+                    Ok(true) => tracing::info!(
+                        "Restored daily P&L from {}: ${:.2}",
+                        path,
+                        pnl_tracker.current_pnl()
+                    ),
+                    Ok(false) => tracing::info!("Discarded stale daily P&L state in {}", path),
+                    Err(e) => tracing::warn!("Failed to load daily P&L state: {}", e),
+                }
+            }
+        }
+        
         Self {
-            pnl_tracker: DailyPnlTracker::new(config.max_daily_loss_pct),
+            pnl_tracker,
             volatility_sizer: VolatilityPositionSizer::new(volatility_config),
             correlation_detector: CorrelationDetector::new(0.7), // 70% correlation threshold
             position_manager: DynamicPositionManager::new(config.clone()),
@@ -80,10 +109,31 @@ impl RiskManager {
             liquidity_monitor: LiquidityMonitor::new(liquidity_config),
             correlation_risk: CorrelationRiskManager::new(correlation_risk_config),
             trailing_stop: TrailingStopManager::new(trailing_stop_config),
+            consecutive_losses: 0,
+            loss_cooldown: None,
             config,
         }
     }
 
+    /// Use a custom black swan configuration
+    pub fn with_black_swan_config(mut self, config: BlackSwanConfig) -> Self {
+        self.black_swan_protector = BlackSwanProtector::new(config);
+        self
+    }
+
+    /// Use a custom liquidity monitoring configuration
+    pub fn with_liquidity_config(mut self, config: LiquidityConfig) -> Self {
+        self.liquidity_monitor = LiquidityMonitor::new(config);
+        self
+    }
+
+    /// Swap in new risk limits, keeping P&L, streak and market state
+    pub fn apply_config(&mut self, config: RiskConfig) {
+        self.pnl_tracker.set_max_loss_pct(config.max_daily_loss_pct);
+        self.position_manager = DynamicPositionManager::new(config.clone());
+        self.config = config;
+    }
+
     /// Check if trading is allowed based on all risk constraints
     pub fn can_trade(&self) -> RiskCheckResult {
         // Check daily loss limit
@@ -93,6 +143,28 @@ impl RiskManager {
             };
         }
 
+        // Check loss-streak cooldown
+        match self.loss_cooldown {
+            Some(LossCooldown::Until(until)) if Utc::now() < until => {
+                return RiskCheckResult::Blocked {
+                    reason: format!(
+                        "Cooldown after {} consecutive losses until {}",
+                        self.config.max_consecutive_losses,
+                        until.format("%H:%M:%S UTC")
+                    ),
+                };
+            }
+            Some(LossCooldown::UntilDailyReset) => {
+                return RiskCheckResult::Blocked {
+                    reason: format!(
+                        "Cooldown after {} consecutive losses until daily reset",
+                        self.config.max_consecutive_losses
+                    ),
+                };
+            }
+            _ => {}
+        }
+
         // Check black swan protection
         if !self.black_swan_protector.can_trade() {
             return RiskCheckResult::Blocked {
@@ -169,8 +241,13 @@ impl RiskManager {
             })
             .collect();
         
+        let existing_markets: Vec<String> = current_positions
+            .iter()
+            .map(|p| p.market_id.clone())
+            .collect();
         let correlation_multiplier = self.correlation_risk
-            .get_size_multiplier(&market.id, &position_infos);
+            .get_size_multiplier(&market.id, &position_infos)
+            .min(self.correlation_detector.get_correlation_penalty(&market.id, &existing_markets));
         let final_size = size_after_liq * correlation_multiplier;
 
         // Ensure minimum viable size
@@ -178,12 +255,61 @@ impl RiskManager {
             return None;
         }
 
+        // Hard cap on exposure to the correlated cluster this market belongs to
+        let cluster_exposure = self.correlated_exposure(&market.id, current_positions);
+        if cluster_exposure + final_size > balance * self.config.max_correlated_exposure_pct {
+            return None;
+        }
+
         Some(final_size)
     }
 
+    /// Exposure held in `market_id` and in markets correlated with it above threshold
+    pub fn correlated_exposure(&self, market_id: &str, positions: &[Position]) -> Decimal {
+        positions
+            .iter()
+            .filter(|p| {
+                p.market_id == market_id
+                    || self.correlation_detector.are_correlated(market_id, &p.market_id)
+            })
+            .map(|p| p.size * p.current_price)
+            .sum()
+    }
+
     /// Record a trade execution for P&L tracking
+    ///
+    /// Losses extend the current losing streak; a win resets it. Reaching
+    /// `max_consecutive_losses` starts a cooldown. Break-even trades are neutral.
     pub fn record_trade(&mut self, pnl: Decimal) {
         self.pnl_tracker.record_pnl(pnl);
+        self.persist_pnl();
+
+        if pnl > Decimal::ZERO {
+            self.consecutive_losses = 0;
+        } else if pnl < Decimal::ZERO {
+            self.consecutive_losses += 1;
+            let limit = self.config.max_consecutive_losses;
+            if limit > 0 && self.consecutive_losses >= limit {
+                self.consecutive_losses = 0;
+                self.loss_cooldown = Some(match self.config.loss_cooldown_mins {
+                    0 => LossCooldown::UntilDailyReset,
+                    mins => LossCooldown::Until(Utc::now() + Duration::minutes(mins as i64)),
+                });
+            }
+        }
+    }
+
+    /// Current losing streak
+    pub fn consecutive_losses(&self) -> u32 {
+        self.consecutive_losses
+    }
+
+    /// Active loss-streak cooldown, if any
+    pub fn loss_cooldown(&self) -> Option<LossCooldown> {
+        match self.loss_cooldown {
+            Some(LossCooldown::Until(until)) if Utc::now() >= until => None,
+            other => other,
+        }
     }
 
     /// Update volatility data for a market
@@ -202,6 +328,11 @@ impl RiskManager {
         self.black_swan_protector.update(market_id, price, liquidity)
     }
 
+    /// Update black swan monitoring from an order book (adds spread blowout detection)
+    pub fn update_black_swan_order_book(&mut self, snapshot: &OrderBookSnapshot) -> Option<BlackSwanEvent> {
+        self.black_swan_protector.update_with_order_book(snapshot)
+    }
+
     /// Update liquidity monitoring
     pub fn update_liquidity(&mut self, snapshot: OrderBookSnapshot) -> LiquidityAssessment {
         self.liquidity_monitor.update_order_book(snapshot)
@@ -212,10 +343,39 @@ impl RiskManager {
         self.pnl_tracker.current_pnl()
     }
 
+    /// Feed the latest mark-to-market portfolio value into the daily loss
+    /// limit and drawdown tracking
+    pub fn mark_to_market(&mut self, portfolio_value: Decimal) {
+        self.pnl_tracker.mark_to_market(portfolio_value);
+    }
+
+    /// Largest drawdown from today's peak portfolio value
+    pub fn max_drawdown(&self) -> Decimal {
+        self.pnl_tracker.max_drawdown()
+    }
+
     /// Reset daily trackers (call at start of new day)
     pub fn reset_daily(&mut self) {
         self.pnl_tracker.reset();
         self.black_swan_protector.clear_protection();
+        self.consecutive_losses = 0;
+        self.loss_cooldown = None;
+        self.persist_pnl();
+    }
+
+    /// Save daily P&L to `pnl_state_path`, if configured
+    pub fn save_state(&self) -> crate::error::Result<()> {
+        match self.config.pnl_state_path.as_deref() {
+            Some(path) => self.pnl_tracker.save(path),
+            None => Ok(()),
+        }
+    }
+
+    /// Persist after every change so an unclean shutdown loses nothing
+    fn persist_pnl(&self) {
+        if let Err(e) = self.save_state() {
+            tracing::warn!("Failed to persist daily P&L state: {}", e);
+        }
     }
 
     /// Calculate total exposure from positions
@@ -243,6 +243,7 @@ mod tests {
             max_daily_loss_pct: dec!(0.10),     // 10%
             min_balance_reserve: dec!(100),     // $100
             max_open_positions: 10,
+            ..RiskConfig::default()
         }
     }
 
@@ -14,6 +14,7 @@ fn test_risk_config() -> RiskConfig {
         max_daily_loss_pct: dec!(0.10),
         min_balance_reserve: dec!(100),
         max_open_positions: 5,
+        ..RiskConfig::default()
     }
 }
 
@@ -39,6 +40,10 @@ fn test_market() -> Market {
         ],
         active: true,
         closed: false,
+        tags: Vec::new(),
+        category: None,
+        tick_size: None,
+        min_size: None,
     }
 }
 
@@ -50,9 +55,11 @@ fn test_signal() -> Signal {
         model_probability: dec!(0.55),
         market_probability: dec!(0.45),
         edge: dec!(0.10),
+        net_edge: dec!(0.10),
         confidence: dec!(0.75),
         suggested_size: dec!(50),
         timestamp: Utc::now(),
+        max_staleness: None,
     }
 }
 
@@ -230,6 +237,54 @@ fn test_risk_manager_correlation_adjustment() {
     assert!(size_with_corr.unwrap_or(Decimal::ZERO) <= size_no_corr.unwrap_or(Decimal::ZERO));
 }
 
+#[test]
+fn test_risk_manager_correlated_exposure_cap() {
+    let mut manager = RiskManager::new(RiskConfig {
+        max_correlated_exposure_pct: dec!(0.20),
+        max_exposure_pct: dec!(0.90),
+        ..test_risk_config()
+    });
+    let market = test_market();
+    let signal = test_signal();
+    
+    // Three open markets and the new one all move together; "other" does not
+    let moves = [dec!(1.10), dec!(0.95), dec!(1.04), dec!(0.90), dec!(1.08), dec!(0.97), dec!(1.02)];
+    let mut price = dec!(0.5);
+    let mut other = dec!(0.5);
+    for (i, m) in moves.iter().enumerate() {
+        price *= *m;
+        other *= moves[(i + 3) % moves.len()];
+        for id in ["cluster-a", "cluster-b", "cluster-c", market.id.as_str()] {
+            manager.update_correlation(id, price, i as i64);
+        }
+        manager.update_correlation("other", other, i as i64);
+    }
+    
+    // 3 x $65 = $195 of a $200 cluster cap already used
+    let cluster: Vec<Position> = ["cluster-a", "cluster-b", "cluster-c"]
+        .iter()
+        .map(|id| Position {
+            market_id: id.to_string(),
+            size: dec!(130),
+            current_price: dec!(0.50),
+            ..test_position()
+        })
+        .collect();
+    assert_eq!(manager.correlated_exposure(&market.id, &cluster), dec!(195));
+    assert!(manager.calculate_position_size(&signal, &market, dec!(1000), &cluster[..2]).is_some());
+    assert!(manager.calculate_position_size(&signal, &market, dec!(1000), &cluster).is_none());
+    
+    // The same exposure in an uncorrelated market does not count toward the cluster
+    let unrelated = vec![Position {
+        market_id: "other".to_string(),
+        size: dec!(390),
+        current_price: dec!(0.50),
+        ..test_position()
+    }];
+    assert_eq!(manager.correlated_exposure(&market.id, &unrelated), Decimal::ZERO);
+    assert!(manager.calculate_position_size(&signal, &market, dec!(1000), &unrelated).is_some());
+}
+
 #[test]
 fn test_risk_manager_record_trade() {
     let mut manager = RiskManager::new(test_risk_config());
@@ -252,6 +307,173 @@ fn test_risk_manager_reset_daily() {
     assert_eq!(manager.daily_pnl(), Decimal::ZERO);
 }
 
+#[test]
+fn test_risk_manager_loss_streak_cooldown() {
+    let mut manager = RiskManager::new(RiskConfig {
+        max_consecutive_losses: 3,
+        loss_cooldown_mins: 30,
+        ..test_risk_config()
+    });
+    manager.pnl_tracker.set_starting_balance(dec!(10000));
+    
+    // Two losses, a win resets the streak
+    manager.record_trade(dec!(-5));
+    manager.record_trade(dec!(-5));
+    assert_eq!(manager.consecutive_losses(), 2);
+    manager.record_trade(dec!(8));
+    assert_eq!(manager.consecutive_losses(), 0);
+    
+    // Break-even neither counts nor resets
+    manager.record_trade(dec!(-5));
+    manager.record_trade(Decimal::ZERO);
+    manager.record_trade(dec!(-5));
+    assert!(matches!(manager.can_trade(), RiskCheckResult::Allowed));
+    
+    // Third loss in a row triggers the cooldown
+    manager.record_trade(dec!(-5));
+    match manager.can_trade() {
+        RiskCheckResult::Blocked { reason } => assert!(reason.contains("consecutive losses")),
+        RiskCheckResult::Allowed => panic!("Expected cooldown after 3 losses"),
+    }
+    assert!(matches!(manager.loss_cooldown(), Some(LossCooldown::Until(_))));
+    assert!(manager.calculate_position_size(&test_signal(), &test_market(), dec!(1000), &[]).is_none());
+    
+    // Expired cooldown allows trading again
+    manager.loss_cooldown = Some(LossCooldown::Until(Utc::now() - chrono::Duration::seconds(1)));
+    assert!(matches!(manager.can_trade(), RiskCheckResult::Allowed));
+    assert_eq!(manager.loss_cooldown(), None);
+}
+
+#[test]
+fn test_risk_manager_loss_cooldown_until_daily_reset() {
+    let mut manager = RiskManager::new(RiskConfig {
+        max_consecutive_losses: 2,
+        loss_cooldown_mins: 0,
+        ..test_risk_config()
+    });
+    manager.pnl_tracker.set_starting_balance(dec!(10000));
+    
+    manager.record_trade(dec!(-5));
+    manager.record_trade(dec!(-5));
+    assert_eq!(manager.loss_cooldown(), Some(LossCooldown::UntilDailyReset));
+    assert!(matches!(manager.can_trade(), RiskCheckResult::Blocked { .. }));
+    
+    manager.reset_daily();
+    assert!(matches!(manager.can_trade(), RiskCheckResult::Allowed));
+}
+
+#[test]
+fn test_risk_manager_loss_cooldown_disabled() {
+    let mut manager = RiskManager::new(RiskConfig {
+        max_consecutive_losses: 0,
+        ..test_risk_config()
+    });
+    manager.pnl_tracker.set_starting_balance(dec!(10000));
+    
+    for _ in 0..10 {
+        manager.record_trade(dec!(-1));
+    }
+    assert!(matches!(manager.can_trade(), RiskCheckResult::Allowed));
+}
+
+#[test]
+fn test_risk_manager_pnl_survives_restart() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = RiskConfig {
+        pnl_state_path: Some(dir.path().join("pnl.json").to_string_lossy().into_owned()),
+        max_consecutive_losses: 0,
+        ..test_risk_config()
+    };
+    
+    let mut manager = RiskManager::new(config.clone());
+    manager.pnl_tracker.set_starting_balance(dec!(1000));
+    manager.record_trade(dec!(-100));
+    assert!(matches!(manager.can_trade(), RiskCheckResult::Blocked { .. }));
+    drop(manager);
+    
+    let restarted = RiskManager::new(config);
+    assert_eq!(restarted.daily_pnl(), dec!(-100));
+    assert!(matches!(restarted.can_trade(), RiskCheckResult::Blocked { .. }));
+}
+
+fn fast_black_swan_config() -> BlackSwanConfig {
+    BlackSwanConfig {
+        min_data_points: 3,
+        ..BlackSwanConfig::default()
+    }
+}
+
+#[test]
+fn test_risk_manager_flash_crash_blocks_sizing() {
+    let mut manager = RiskManager::new(test_risk_config())
+        .with_black_swan_config(fast_black_swan_config());
+    manager.pnl_tracker.set_starting_balance(dec!(10000));
+    let market = test_market();
+    
+    assert!(manager.calculate_position_size(&test_signal(), &market, dec!(1000), &[]).is_some());
+    
+    // 40% drop inside the window triggers a close on this market
+    manager.update_black_swan(&market.id, dec!(0.50), None);
+    manager.update_black_swan(&market.id, dec!(0.50), None);
+    let event = manager.update_black_swan(&market.id, dec!(0.30), None);
+    assert!(matches!(event, Some(BlackSwanEvent::FlashCrash { .. })));
+    
+    assert!(manager.calculate_position_size(&test_signal(), &market, dec!(1000), &[]).is_none());
+}
+
+#[test]
+fn test_risk_manager_black_swan_halt_blocks_can_trade() {
+    let mut manager = RiskManager::new(test_risk_config())
+        .with_black_swan_config(fast_black_swan_config());
+    manager.pnl_tracker.set_starting_balance(dec!(10000));
+    
+    // Three markets sliding 10% together halt trading without a single flash crash
+    let ids: Vec<String> = (0..3).map(|i| format!("m{}", i)).collect();
+    for id in &ids {
+        manager.update_black_swan(id, dec!(0.50), None);
+        manager.update_black_swan(id, dec!(0.45), None);
+    }
+    let positions: Vec<Position> = ids
+        .iter()
+        .map(|id| Position { market_id: id.clone(), ..test_position() })
+        .collect();
+    let event = manager.check_correlated_crash(&positions);
+    assert!(matches!(event, Some(BlackSwanEvent::CorrelatedCrash { .. })));
+    
+    match manager.can_trade() {
+        RiskCheckResult::Blocked { reason } => assert!(reason.contains("Black swan")),
+        RiskCheckResult::Allowed => panic!("Expected black swan halt"),
+    }
+    assert!(manager.calculate_position_size(&test_signal(), &test_market(), dec!(1000), &[]).is_none());
+}
+
+#[test]
+fn test_risk_manager_low_liquidity_shrinks_size() {
+    let mut manager = RiskManager::new(test_risk_config());
+    manager.pnl_tracker.set_starting_balance(dec!(10000));
+    let market = test_market();
+    
+    let baseline = manager
+        .calculate_position_size(&test_signal(), &market, dec!(1000), &[])
+        .unwrap();
+    
+    // Thin but tradeable book: 1% spread, ~$5k depth
+    let assessment = manager.update_liquidity(OrderBookSnapshot {
+        market_id: market.id.clone(),
+        timestamp: Utc::now(),
+        bids: vec![OrderBookLevel { price: dec!(0.50), size: dec!(5000) }],
+        asks: vec![OrderBookLevel { price: dec!(0.505), size: dec!(5000) }],
+        mid_price: dec!(0.5025),
+    });
+    assert!(assessment.tradeable);
+    assert!(assessment.size_multiplier < Decimal::ONE);
+    
+    let reduced = manager
+        .calculate_position_size(&test_signal(), &market, dec!(1000), &[])
+        .unwrap();
+    assert!(reduced < baseline, "reduced {} should be below baseline {}", reduced, baseline);
+}
+
 // =============================================================================
 // RiskCheckResult Tests
 // =============================================================================
@@ -388,3 +610,21 @@ fn test_scenario_exposure_buildup() {
     let size5 = size5.unwrap();
     assert!(size5 <= dec!(100)); // Limited by remaining exposure
 }
+
+#[test]
+fn test_apply_config_keeps_pnl_and_updates_limit() {
+    let mut rm = RiskManager::new(test_risk_config());
+    rm.pnl_tracker.set_starting_balance(dec!(1000));
+    rm.record_trade(dec!(-60));
+    assert!(matches!(rm.can_trade(), RiskCheckResult::Allowed));
+
+    // Tighten the daily loss limit to 5%: the existing -$60 now breaches it
+    rm.apply_config(RiskConfig {
+        max_daily_loss_pct: dec!(0.05),
+        ..test_risk_config()
+    });
+
+    assert_eq!(rm.daily_pnl(), dec!(-60));
+    assert_eq!(rm.config.max_daily_loss_pct, dec!(0.05));
+    assert!(matches!(rm.can_trade(), RiskCheckResult::Blocked { .. }));
+}
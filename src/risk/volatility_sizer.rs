@@ -3,12 +3,25 @@
 //! Adjusts position sizes based on market volatility:
 //! - High volatility → smaller positions
 //! - Low volatility → larger positions (up to max)
+//!
+//! Volatility is estimated either as the rolling standard deviation of
+//! returns (SMA) or as an exponentially weighted moving average (EWMA)
+//! that reacts faster to regime shifts.
 
 use crate::utils::sqrt_decimal;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use std::collections::{HashMap, VecDeque};
 
+/// Volatility estimator
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VolatilityEstimator {
+    /// Equal-weighted standard deviation of returns over the window
+    Sma,
+    /// RiskMetrics-style EWMA: var = λ·var + (1-λ)·r²
+    Ewma { lambda: Decimal },
+}
+
 /// Configuration for volatility-based sizing
 #[derive(Debug, Clone)]
 pub struct VolatilityConfig {
@@ -20,6 +33,10 @@ pub struct VolatilityConfig {
     pub min_multiplier: Decimal,
     /// Maximum size multiplier (e.g., 1.5 = 150% of base)
     pub max_multiplier: Decimal,
+    /// Volatility estimator
+    pub estimator: VolatilityEstimator,
+    /// Price points required before the multiplier departs from 1.0
+    pub min_samples: usize,
 }
 
 impl Default for VolatilityConfig {
@@ -29,6 +46,8 @@ impl Default for VolatilityConfig {
             target_volatility: dec!(0.30), // 30% annualized
             min_multiplier: dec!(0.25),
             max_multiplier: dec!(1.5),
+            estimator: VolatilityEstimator::Sma,
+            min_samples: 5,
         }
     }
 }
@@ -46,6 +65,10 @@ pub struct VolatilityPositionSizer {
 struct VolatilityData {
     /// Calculated volatility (standard deviation of returns)
     volatility: Decimal,
+    /// EWMA variance state (EWMA estimator only)
+    ewma_variance: Option<Decimal>,
+    /// Price points observed since tracking began
+    samples: usize,
     /// Timestamp of last update
     last_update: i64,
 }
@@ -63,8 +86,9 @@ impl VolatilityPositionSizer {
     pub fn add_price_point(&mut self, market_id: &str, price: Decimal) {
         let history = self.price_history
             .entry(market_id.to_string())
-            .or_insert_with(VecDeque::new);
+            .or_default();
         
+        let previous = history.back().copied();
         history.push_back(price);
         
         // Keep only window_size points
@@ -72,24 +96,56 @@ impl VolatilityPositionSizer {
             history.pop_front();
         }
 
-        // Recalculate volatility if we have enough data
-        if history.len() >= 2 {
-            self.recalculate_volatility(market_id);
+        let data = self.volatility_cache
+            .entry(market_id.to_string())
+            .or_insert(VolatilityData {
+                volatility: Decimal::ZERO,
+                ewma_variance: None,
+                samples: 0,
+                last_update: 0,
+            });
+        data.samples += 1;
+        data.last_update = chrono::Utc::now().timestamp();
+
+        match self.config.estimator {
+            VolatilityEstimator::Sma => self.recalculate_volatility(market_id),
+            VolatilityEstimator::Ewma { lambda } => {
+                if let Some(prev) = previous.filter(|p| *p != Decimal::ZERO) {
+                    let r = (price - prev) / prev;
+                    let variance = match data.ewma_variance {
+                        Some(v) => lambda * v + (Decimal::ONE - lambda) * r * r,
+                        None => r * r,
+                    };
+                    data.ewma_variance = Some(variance);
+                    data.volatility = sqrt_decimal(variance);
+                }
+            }
         }
     }
 
     /// Get the position size multiplier for a market
+    ///
+    /// Neutral (1.0) until `min_samples` price points have been seen,
+    /// then follows `multiplier_for_volatility`.
     pub fn get_size_multiplier(&self, market_id: &str) -> Decimal {
-        let Some(vol_data) = self.volatility_cache.get(market_id) else {
-            return Decimal::ONE; // No data, use base size
-        };
+        match self.volatility_cache.get(market_id) {
+            Some(vol_data) if vol_data.samples >= self.config.min_samples.max(2) => {
+                self.multiplier_for_volatility(vol_data.volatility)
+            }
+            _ => Decimal::ONE, // Not enough data, use base size
+        }
+    }
 
-        if vol_data.volatility == Decimal::ZERO {
+    /// The sizing curve: `target / vol`, clamped to `[min, max]` multiplier
+    ///
+    /// Zero volatility maps to the max multiplier.
+    pub fn multiplier_for_volatility(&self, volatility: Decimal) -> Decimal {
+        if volatility <= Decimal::ZERO {
             return self.config.max_multiplier;
         }
 
         // Inverse volatility scaling: high vol = small multiplier
-        let multiplier = self.config.target_volatility / vol_data.volatility;
+        let multiplier = self.config.target_volatility / volatility;
         
         // Clamp to configured range
         multiplier
@@ -97,9 +153,12 @@ impl VolatilityPositionSizer {
             .min(self.config.max_multiplier)
     }
 
-    /// Get the current volatility for a market
+    /// Get the current volatility for a market (None until two prices are seen)
     pub fn get_volatility(&self, market_id: &str) -> Option<Decimal> {
-        self.volatility_cache.get(market_id).map(|d| d.volatility)
+        self.volatility_cache
+            .get(market_id)
+            .filter(|d| d.samples >= 2)
+            .map(|d| d.volatility)
     }
 
     /// Check if a market is in high volatility regime
@@ -152,15 +211,9 @@ impl VolatilityPositionSizer {
         let std_dev = sqrt_decimal(variance);
 
         // Annualize (assuming ~250 trading days, but we use raw for now)
-        let volatility = std_dev;
-
-        self.volatility_cache.insert(
-            market_id.to_string(),
-            VolatilityData {
-                volatility,
-                last_update: chrono::Utc::now().timestamp(),
-            },
-        );
+        if let Some(data) = self.volatility_cache.get_mut(market_id) {
+            data.volatility = std_dev;
+        }
     }
 
     /// Clear data for a specific market
@@ -237,6 +290,7 @@ mod tests {
             target_volatility: dec!(0.10),
             min_multiplier: dec!(0.25),
             max_multiplier: dec!(1.5),
+            ..VolatilityConfig::default()
         };
         let mut sizer = VolatilityPositionSizer::new(config);
         
@@ -261,6 +315,7 @@ mod tests {
             target_volatility: dec!(0.30),
             min_multiplier: dec!(0.25),
             max_multiplier: dec!(1.5),
+            ..VolatilityConfig::default()
         };
         let mut sizer = VolatilityPositionSizer::new(config);
         
@@ -327,6 +382,7 @@ mod tests {
             target_volatility: dec!(0.10),
             min_multiplier: dec!(0.25),
             max_multiplier: dec!(1.5),
+            ..VolatilityConfig::default()
         };
         let mut sizer = VolatilityPositionSizer::new(config);
         
@@ -344,4 +400,86 @@ mod tests {
         
         assert!(sizer.is_high_volatility("wild_market"));
     }
+
+    #[test]
+    fn test_min_samples_guard() {
+        let config = VolatilityConfig {
+            min_samples: 10,
+            ..VolatilityConfig::default()
+        };
+        let mut sizer = VolatilityPositionSizer::new(config);
+        
+        // Wild swings, but too few samples to trust
+        for price in [dec!(0.50), dec!(0.80), dec!(0.30), dec!(0.75)] {
+            sizer.add_price_point("market1", price);
+        }
+        assert!(sizer.get_volatility("market1").unwrap() > Decimal::ZERO);
+        assert_eq!(sizer.get_size_multiplier("market1"), Decimal::ONE);
+        
+        for price in [dec!(0.25), dec!(0.70), dec!(0.30), dec!(0.80), dec!(0.20), dec!(0.75)] {
+            sizer.add_price_point("market1", price);
+        }
+        assert!(sizer.get_size_multiplier("market1") < Decimal::ONE);
+    }
+
+    #[test]
+    fn test_multiplier_curve() {
+        let sizer = VolatilityPositionSizer::new(VolatilityConfig {
+            target_volatility: dec!(0.10),
+            min_multiplier: dec!(0.25),
+            max_multiplier: dec!(1.5),
+            ..VolatilityConfig::default()
+        });
+        
+        assert_eq!(sizer.multiplier_for_volatility(Decimal::ZERO), dec!(1.5));
+        assert_eq!(sizer.multiplier_for_volatility(dec!(0.05)), dec!(1.5));
+        assert_eq!(sizer.multiplier_for_volatility(dec!(0.10)), Decimal::ONE);
+        assert_eq!(sizer.multiplier_for_volatility(dec!(0.20)), dec!(0.5));
+        assert_eq!(sizer.multiplier_for_volatility(dec!(1.0)), dec!(0.25));
+        
+        // Monotonically non-increasing in volatility
+        let mut prev = dec!(1.5);
+        for i in 1..50 {
+            let m = sizer.multiplier_for_volatility(Decimal::from(i) / dec!(100));
+            assert!(m <= prev);
+            prev = m;
+        }
+    }
+
+    #[test]
+    fn test_ewma_reacts_to_spike_faster_than_sma() {
+        let base = VolatilityConfig {
+            window_size: 30,
+            target_volatility: dec!(0.01),
+            min_multiplier: dec!(0.01),
+            min_samples: 2,
+            ..VolatilityConfig::default()
+        };
+        let mut sma = VolatilityPositionSizer::new(base.clone());
+        let mut ewma = VolatilityPositionSizer::new(VolatilityConfig {
+            estimator: VolatilityEstimator::Ewma { lambda: dec!(0.8) },
+            ..base
+        });
+        
+        // Calm regime: alternating ±0.2% moves
+        let mut price = dec!(0.50);
+        for i in 0..25 {
+            price += if i % 2 == 0 { dec!(0.001) } else { dec!(-0.001) };
+            sma.add_price_point("m", price);
+            ewma.add_price_point("m", price);
+        }
+        let sma_calm = sma.get_volatility("m").unwrap();
+        let ewma_calm = ewma.get_volatility("m").unwrap();
+        
+        // Three violent moves
+        for p in [dec!(0.60), dec!(0.48), dec!(0.58)] {
+            sma.add_price_point("m", p);
+            ewma.add_price_point("m", p);
+        }
+        let sma_ratio = sma.get_volatility("m").unwrap() / sma_calm;
+        let ewma_ratio = ewma.get_volatility("m").unwrap() / ewma_calm;
+        
+        assert!(ewma_ratio > sma_ratio, "EWMA x{} vs SMA x{}", ewma_ratio, sma_ratio);
+        assert!(ewma.get_size_multiplier("m") < sma.get_size_multiplier("m"));
+    }
 }
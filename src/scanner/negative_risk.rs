@@ -9,6 +9,7 @@
 use super::{ScannerConfig};
 use crate::client::clob::ClobClient;
 use crate::error::Result;
+use crate::utils::truncate_chars;
 use chrono::{DateTime, Utc};
 use reqwest::Client;
 use rust_decimal::Decimal;
@@ -243,11 +244,7 @@ impl NegativeRiskScanner {
 
 /// Extract outcome name from question
 fn extract_outcome_name(question: &str) -> String {
-    if question.len() > 30 {
-        format!("{}...", &question[..30])
-    } else {
-        question.to_string()
-    }
+    truncate_chars(question, 30)
 }
 
 #[cfg(test)]
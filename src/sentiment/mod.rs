@@ -11,7 +11,7 @@ pub mod twitter_client;
 pub mod sentiment_analyzer;
 pub mod kol_tracker;
 
-pub use twitter_client::TwitterClient;
+pub use twitter_client::{TwitterClient, UserTimeline};
 pub use sentiment_analyzer::{SentimentAnalyzer, SentimentResult, SentimentScore};
 pub use kol_tracker::{KolTracker, KolProfile, InfluenceWeight};
 
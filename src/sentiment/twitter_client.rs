@@ -3,10 +3,16 @@
 //! Provides methods to fetch tweets for crypto sentiment analysis.
 //! Supports both Twitter API v2 and mock data for testing.
 
+use crate::error::{BotError, Result};
+use async_trait::async_trait;
+use serde::Deserialize;
 use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+/// Twitter API v2 base URL
+const API_BASE_URL: &str = "https://api.twitter.com";
+
 /// A tweet from Twitter
 #[derive(Debug, Clone)]
 pub struct Tweet {
@@ -44,10 +50,20 @@ struct RateLimiter {
     last_request: u64,
 }
 
+/// Recent tweets posted by a user
+#[async_trait]
+pub trait UserTimeline: Send + Sync {
+    /// Latest tweets from `user_id`, newest first
+    ///
+    /// Fails with `BotError::RateLimited` while the rate limit window is exhausted.
+    async fn user_tweets(&self, user_id: &str, max_results: u32) -> Result<Vec<Tweet>>;
+}
+
 /// Twitter API client
 pub struct TwitterClient {
     bearer_token: Option<String>,
-    /// HTTP client (placeholder - would use reqwest in production)
+    http: reqwest::Client,
+    base_url: String,
     rate_limiter: Arc<RwLock<RateLimiter>>,
     /// Tweet cache to reduce API calls
     cache: Arc<RwLock<HashMap<String, Vec<Tweet>>>>,
@@ -60,6 +76,8 @@ impl TwitterClient {
     pub fn new(bearer_token: Option<String>) -> Self {
         Self {
             bearer_token,
+            http: reqwest::Client::new(),
+            base_url: API_BASE_URL.to_string(),
             rate_limiter: Arc::new(RwLock::new(RateLimiter {
                 remaining: 450, // Twitter API v2 default
                 reset_at: 0,
@@ -70,6 +88,12 @@ impl TwitterClient {
         }
     }
 
+    /// Point at a different API host (e.g. a test server)
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
     /// Search for crypto-related tweets
     pub async fn search_crypto_tweets(&self, symbol: &str, max_results: u32) -> Vec<Tweet> {
         let now = std::time::SystemTime::now()
@@ -237,9 +261,20 @@ impl TwitterClient {
     }
 
     /// Get tweets from a specific user
+    ///
+    /// Uses the API when a bearer token is set (empty on failure), otherwise
+    /// generates mock data.
     pub async fn get_user_tweets(&self, user_id: &str, max_results: u32) -> Vec<Tweet> {
-        // In production, call GET /2/users/:id/tweets
-        // For now, generate mock data
+        if self.bearer_token.is_some() {
+            return match self.fetch_user_tweets(user_id, max_results).await {
+                Ok(tweets) => tweets,
+                Err(e) => {
+                    tracing::warn!("Failed to fetch tweets for {}: {}", user_id, e);
+                    Vec::new()
+                }
+            };
+        }
+
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
@@ -260,6 +295,102 @@ impl TwitterClient {
             .collect()
     }
 
+    /// Call GET /2/users/:id/tweets, honouring the rate limit headers
+    pub async fn fetch_user_tweets(&self, user_id: &str, max_results: u32) -> Result<Vec<Tweet>> {
+        let bearer = self
+            .bearer_token
+            .as_ref()
+            .ok_or_else(|| BotError::Config("Twitter bearer token required".into()))?;
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        {
+            let limiter = self.rate_limiter.read().await;
+            if limiter.remaining == 0 && now < limiter.reset_at {
+                return Err(BotError::RateLimited {
+                    retry_after_secs: limiter.reset_at - now,
+                });
+            }
+        }
+
+        let url = format!(
+            "{}/2/users/{}/tweets?max_results={}&tweet.fields=created_at,public_metrics,author_id&expansions=author_id&user.fields=username",
+            self.base_url,
+            user_id,
+            // The endpoint accepts 5..=100
+            max_results.clamp(5, 100)
+        );
+        let resp = self
+            .http
+            .get(&url)
+            .header("Authorization", format!("Bearer {}", bearer))
+            .send()
+            .await?;
+
+        let header_u64 = |name: &str| {
+            resp.headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.parse::<u64>().ok())
+        };
+        let remaining = header_u64("x-rate-limit-remaining");
+        let reset_at = header_u64("x-rate-limit-reset");
+        {
+            let mut limiter = self.rate_limiter.write().await;
+            limiter.last_request = now;
+            match remaining {
+                Some(remaining) => limiter.remaining = remaining.min(u32::MAX as u64) as u32,
+                None => limiter.remaining = limiter.remaining.saturating_sub(1),
+            }
+            if let Some(reset_at) = reset_at {
+                limiter.reset_at = reset_at;
+            }
+        }
+
+        if resp.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
+            return Err(BotError::RateLimited {
+                retry_after_secs: reset_at.map(|r| r.saturating_sub(now)).unwrap_or(60),
+            });
+        }
+        if !resp.status().is_success() {
+            return Err(BotError::Api(format!("Twitter API returned {}", resp.status())));
+        }
+
+        let body: UserTweetsResponse = resp.json().await?;
+        let usernames: HashMap<String, String> = body
+            .includes
+            .map(|i| i.users.into_iter().map(|u| (u.id, u.username)).collect())
+            .unwrap_or_default();
+
+        Ok(body
+            .data
+            .unwrap_or_default()
+            .into_iter()
+            .map(|t| {
+                let author_id = t.author_id.unwrap_or_else(|| user_id.to_string());
+                let metrics = t.public_metrics.unwrap_or_default();
+                Tweet {
+                    id: t.id,
+                    text: t.text,
+                    author_username: usernames.get(&author_id).cloned().unwrap_or_default(),
+                    author_id,
+                    created_at: t
+                        .created_at
+                        .and_then(|c| chrono::DateTime::parse_from_rfc3339(&c).ok())
+                        .map(|c| c.timestamp().max(0) as u64)
+                        .unwrap_or(now),
+                    retweet_count: metrics.retweet_count,
+                    like_count: metrics.like_count,
+                    reply_count: metrics.reply_count,
+                    quote_count: metrics.quote_count,
+                }
+            })
+            .collect())
+    }
+
     /// Check if API is available (has valid token)
     pub fn is_api_available(&self) -> bool {
         self.bearer_token.is_some()
@@ -278,6 +409,52 @@ impl TwitterClient {
     }
 }
 
+#[async_trait]
+impl UserTimeline for TwitterClient {
+    async fn user_tweets(&self, user_id: &str, max_results: u32) -> Result<Vec<Tweet>> {
+        self.fetch_user_tweets(user_id, max_results).await
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct UserTweetsResponse {
+    data: Option<Vec<ApiTweet>>,
+    includes: Option<ApiIncludes>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiTweet {
+    id: String,
+    text: String,
+    author_id: Option<String>,
+    created_at: Option<String>,
+    public_metrics: Option<ApiMetrics>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ApiMetrics {
+    #[serde(default)]
+    retweet_count: u32,
+    #[serde(default)]
+    like_count: u32,
+    #[serde(default)]
+    reply_count: u32,
+    #[serde(default)]
+    quote_count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiIncludes {
+    #[serde(default)]
+    users: Vec<ApiUser>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiUser {
+    id: String,
+    username: String,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,182 @@
+//! Graceful shutdown
+//!
+//! Runs when the main loop receives Ctrl-C: cancel resting orders, close the
+//! database, then tell Telegram the bot stopped. Each step runs even if an
+//! earlier one failed, and failures are collected in the report.
+
+use crate::client::mock::ClobClientTrait;
+use crate::error::Result;
+use crate::notify::Notifier;
+use crate::storage::Database;
+use async_trait::async_trait;
+
+/// Receives the shutdown notification (allows mocking)
+#[async_trait]
+pub trait ShutdownNotifier: Send + Sync {
+    async fn shutdown(&self, reason: &str) -> Result<()>;
+}
+
+#[async_trait]
+impl ShutdownNotifier for Notifier {
+    async fn shutdown(&self, reason: &str) -> Result<()> {
+        Notifier::shutdown(self, reason).await
+    }
+}
+
+/// What the shutdown sequence did
+#[derive(Debug, Default)]
+pub struct ShutdownReport {
+    /// Orders cancelled (always 0 in dry-run mode)
+    pub cancelled_orders: usize,
+    /// Steps that failed, in order
+    pub errors: Vec<String>,
+}
+
+/// Run the shutdown sequence
+///
+/// Orders are left alone in dry-run mode since none were placed.
+pub async fn graceful_shutdown<C: ClobClientTrait>(
+    clob: &C,
+    db: Option<&Database>,
+    notifier: &dyn ShutdownNotifier,
+    reason: &str,
+    dry_run: bool,
+) -> ShutdownReport {
+    let mut report = ShutdownReport::default();
+
+    if !dry_run {
+        match clob.cancel_all().await {
+            Ok(n) => {
+                tracing::info!("Cancelled {} open orders", n);
+                report.cancelled_orders = n;
+            }
+            Err(e) => {
+                tracing::error!("Failed to cancel open orders: {}", e);
+                report.errors.push(format!("cancel orders: {}", e));
+            }
+        }
+    }
+
+    if let Some(db) = db {
+        db.close().await;
+    }
+
+    if let Err(e) = notifier.shutdown(reason).await {
+        tracing::warn!("Failed to send shutdown notification: {}", e);
+        report.errors.push(format!("notify: {}", e));
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::mock::{MockClobClient, MockOrder};
+    use crate::client::OrderBook;
+    use crate::error::BotError;
+    use crate::types::{Order, OrderStatus, Position, Side};
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+    use std::sync::Mutex;
+
+    #[derive(Default)]
+    struct RecordingNotifier {
+        reasons: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl ShutdownNotifier for RecordingNotifier {
+        async fn shutdown(&self, reason: &str) -> Result<()> {
+            self.reasons.lock().unwrap().push(reason.to_string());
+            Ok(())
+        }
+    }
+
+    fn clob_with_open_orders(n: usize) -> MockClobClient {
+        let clob = MockClobClient::new();
+        {
+            let state = clob.state();
+            let mut state = state.write().unwrap();
+            for i in 0..n {
+                state.orders.push(MockOrder {
+                    order_id: format!("open_{}", i),
+                    token_id: "token".to_string(),
+                    side: Side::Buy,
+                    price: dec!(0.50),
+                    size: dec!(10),
+                    status: "OPEN".to_string(),
+                    timestamp: chrono::Utc::now(),
+                    client_order_id: None,
+                });
+            }
+        }
+        clob
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_cancels_orders_and_notifies() {
+        let clob = clob_with_open_orders(2);
+        let notifier = RecordingNotifier::default();
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::connect(dir.path().join("bot.db")).await.unwrap();
+
+        let report = graceful_shutdown(&clob, Some(&db), &notifier, "Ctrl-C", false).await;
+
+        assert_eq!(report.cancelled_orders, 2);
+        assert!(report.errors.is_empty());
+        assert!(clob.get_open_orders().await.unwrap().is_empty());
+        assert_eq!(*notifier.reasons.lock().unwrap(), vec!["Ctrl-C".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_dry_run_leaves_orders() {
+        let clob = clob_with_open_orders(1);
+        let notifier = RecordingNotifier::default();
+
+        let report = graceful_shutdown(&clob, None, &notifier, "Ctrl-C", true).await;
+
+        assert_eq!(report.cancelled_orders, 0);
+        assert_eq!(clob.get_open_orders().await.unwrap().len(), 1);
+        assert_eq!(notifier.reasons.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_notifies_even_if_cancel_fails() {
+        let clob = MockClobClient::new();
+        let notifier = RecordingNotifier::default();
+
+        // Wrap the mock so only cancel_all fails
+        struct FailingClob(MockClobClient);
+        #[async_trait]
+        impl ClobClientTrait for FailingClob {
+            async fn get_balance(&self) -> Result<Decimal> {
+                self.0.get_balance().await
+            }
+            async fn get_order_book(&self, token_id: &str) -> Result<OrderBook> {
+                self.0.get_order_book(token_id).await
+            }
+            async fn place_order(&self, order: &Order) -> Result<OrderStatus> {
+                self.0.place_order(order).await
+            }
+            async fn cancel_order(&self, order_id: &str) -> Result<()> {
+                self.0.cancel_order(order_id).await
+            }
+            async fn get_open_orders(&self) -> Result<Vec<OrderStatus>> {
+                self.0.get_open_orders().await
+            }
+            async fn get_positions(&self) -> Result<Vec<Position>> {
+                self.0.get_positions().await
+            }
+            async fn cancel_all(&self) -> Result<usize> {
+                Err(BotError::Api("cancel-all unavailable".into()))
+            }
+        }
+
+        let report = graceful_shutdown(&FailingClob(clob), None, &notifier, "Ctrl-C", false).await;
+
+        assert_eq!(report.errors.len(), 1);
+        assert!(report.errors[0].contains("cancel orders"));
+        assert_eq!(notifier.reasons.lock().unwrap().len(), 1);
+    }
+}
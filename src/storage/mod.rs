@@ -7,10 +7,14 @@ pub mod cache;
 mod tests;
 
 use crate::error::Result;
-use crate::monitor::PerformanceStats;
-use crate::types::Trade;
+use crate::fusion::{SignalContribution, SignalSource, TradeOutcome};
+use crate::monitor::settlement::{settlement_order_id, Settlement};
+use crate::monitor::{ratios, PerformanceStats};
+use crate::types::{Market, Outcome, OutcomeMatcher, Side, Trade};
+use chrono::{DateTime, SecondsFormat, Utc};
 use rust_decimal::Decimal;
 use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
+use std::collections::HashMap;
 use std::path::Path;
 
 /// Database for storing trades and state
@@ -34,6 +38,11 @@ impl Database {
         Ok(db)
     }
 
+    /// Flush pending writes and close all connections
+    pub async fn close(&self) {
+        self.pool.close().await;
+    }
+
     /// Run database migrations
     async fn run_migrations(&self) -> Result<()> {
         sqlx::query(
@@ -47,13 +56,22 @@ impl Database {
                 price TEXT NOT NULL,
                 size TEXT NOT NULL,
                 fee TEXT NOT NULL,
-                timestamp TEXT NOT NULL
+                timestamp TEXT NOT NULL,
+                client_order_id TEXT,
+                correlation_id TEXT
             )
             "#,
         )
         .execute(&self.pool)
         .await?;
 
+        // Databases created before these columns were recorded
+        self.add_column_if_missing("trades", "client_order_id", "TEXT").await?;
+        self.add_column_if_missing("trades", "correlation_id", "TEXT").await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_trades_correlation_id ON trades (correlation_id)")
+            .execute(&self.pool)
+            .await?;
+
         sqlx::query(
             r#"
             CREATE TABLE IF NOT EXISTS positions (
@@ -68,6 +86,24 @@ impl Database {
         .execute(&self.pool)
         .await?;
 
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS settlements (
+                token_id TEXT PRIMARY KEY,
+                market_id TEXT NOT NULL,
+                winning_outcome TEXT NOT NULL,
+                side TEXT NOT NULL,
+                size TEXT NOT NULL,
+                entry_price TEXT NOT NULL,
+                payout_price TEXT NOT NULL,
+                realized_pnl TEXT NOT NULL,
+                settled_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
         sqlx::query(
             r#"
             CREATE TABLE IF NOT EXISTS market_cache (
@@ -80,6 +116,80 @@ impl Database {
         .execute(&self.pool)
         .await?;
 
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS resolved_markets (
+                market_id TEXT PRIMARY KEY,
+                question TEXT NOT NULL,
+                category TEXT,
+                winning_outcome TEXT NOT NULL,
+                yes_won INTEGER NOT NULL,
+                resolved_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_resolved_markets_category ON resolved_markets (category)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS market_tags (
+                market_id TEXT NOT NULL,
+                tag TEXT NOT NULL COLLATE NOCASE,
+                PRIMARY KEY (market_id, tag)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        sqlx::query("CREATE INDEX IF NOT EXISTS idx_market_tags_tag ON market_tags (tag)")
+            .execute(&self.pool)
+            .await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS trade_sources (
+                trade_id TEXT NOT NULL,
+                source TEXT NOT NULL,
+                weight TEXT NOT NULL,
+                direction TEXT NOT NULL DEFAULT '0',
+                PRIMARY KEY (trade_id, source)
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+        self.add_column_if_missing("trade_sources", "direction", "TEXT NOT NULL DEFAULT '0'").await?;
+
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS traded_markets (
+                market_id TEXT PRIMARY KEY,
+                traded_at TEXT NOT NULL,
+                expires_at TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn add_column_if_missing(&self, table: &str, column: &str, decl: &str) -> Result<()> {
+        let exists: bool = sqlx::query_scalar("SELECT COUNT(*) > 0 FROM pragma_table_info(?) WHERE name = ?")
+            .bind(table)
+            .bind(column)
+            .fetch_one(&self.pool)
+            .await?;
+        if !exists {
+            sqlx::query(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, decl))
+                .execute(&self.pool)
+                .await?;
+        }
         Ok(())
     }
 
@@ -87,8 +197,8 @@ impl Database {
     pub async fn save_trade(&self, trade: &Trade) -> Result<()> {
         sqlx::query(
             r#"
-            INSERT INTO trades (id, order_id, token_id, market_id, side, price, size, fee, timestamp)
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            INSERT INTO trades (id, order_id, token_id, market_id, side, price, size, fee, timestamp, client_order_id, correlation_id)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&trade.id)
@@ -100,17 +210,383 @@ impl Database {
         .bind(trade.size.to_string())
         .bind(trade.fee.to_string())
         .bind(trade.timestamp.to_rfc3339())
+        .bind(&trade.client_order_id)
+        .bind(&trade.correlation_id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Tag a trade with the signal sources of the decision behind it
+    ///
+    /// Several contributions from one source are stored as one, with their
+    /// weights summed and their directions averaged by weight. Tagging a
+    /// trade again replaces its sources.
+    pub async fn save_trade_sources(&self, trade_id: &str, contributions: &[SignalContribution]) -> Result<()> {
+        // Source, summed weight, and weight times direction
+        let mut weights: Vec<(String, f64, f64)> = Vec::new();
+        for contribution in contributions {
+            let source = serde_json::to_string(&contribution.source)?;
+            let moment = contribution.weight * contribution.direction;
+            match weights.iter_mut().find(|(s, ..)| *s == source) {
+                Some((_, weight, total)) => {
+                    *weight += contribution.weight;
+                    *total += moment;
+                }
+                None => weights.push((source, contribution.weight, moment)),
+            }
+        }
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("DELETE FROM trade_sources WHERE trade_id = ?")
+            .bind(trade_id)
+            .execute(&mut *tx)
+            .await?;
+        for (source, weight, moment) in weights {
+            let direction = if weight > 0.0 { moment / weight } else { 0.0 };
+            sqlx::query("INSERT INTO trade_sources (trade_id, source, weight, direction) VALUES (?, ?, ?, ?)")
+                .bind(trade_id)
+                .bind(source)
+                .bind(weight.to_string())
+                .bind(direction.to_string())
+                .execute(&mut *tx)
+                .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Realized PnL and win rate of the tagged trades of each signal source
+    ///
+    /// A trade is realized once its token has settled; its PnL is the move
+    /// to the payout price less fees. Every source behind a trade is
+    /// credited with its full PnL, and with a share by weight in
+    /// `weighted_pnl`. Sorted by realized PnL, best first.
+    pub async fn attribution_by_source(&self) -> Result<Vec<SourceAttribution>> {
+        let rows: Vec<SourceTradeRow> = sqlx::query_as(
+            r#"
+            SELECT ts.trade_id, ts.source, ts.weight, t.side, t.price, t.size, t.fee, s.payout_price
+            FROM trade_sources ts
+            JOIN trades t ON t.id = ts.trade_id
+            LEFT JOIN (
+                SELECT token_id, MAX(payout_price) AS payout_price FROM settlements GROUP BY token_id
+            ) s ON s.token_id = t.token_id
+            ORDER BY ts.trade_id
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut total_weight: HashMap<&str, Decimal> = HashMap::new();
+        for row in &rows {
+            *total_weight.entry(&row.trade_id).or_default() += row.weight.parse::<Decimal>().unwrap_or_default();
+        }
+
+        let mut by_source: Vec<SourceAttribution> = Vec::new();
+        for row in &rows {
+            let Ok(source) = serde_json::from_str::<SignalSource>(&row.source) else {
+                continue;
+            };
+            let index = match by_source.iter().position(|a| a.source == source) {
+                Some(index) => index,
+                None => {
+                    by_source.push(SourceAttribution::new(source));
+                    by_source.len() - 1
+                }
+            };
+            let entry = &mut by_source[index];
+            entry.trades += 1;
+
+            let (Some(payout), Ok(price), Ok(size), Ok(fee)) = (
+                row.payout_price.as_deref().and_then(|p| p.parse::<Decimal>().ok()),
+                row.price.parse::<Decimal>(),
+                row.size.parse::<Decimal>(),
+                row.fee.parse::<Decimal>(),
+            ) else {
+                continue;
+            };
+            let gross = (payout - price) * size;
+            let pnl = if row.side == "Sell" { -gross } else { gross } - fee;
+            let share = match total_weight.get(row.trade_id.as_str()) {
+                Some(total) if *total > Decimal::ZERO => row.weight.parse::<Decimal>().unwrap_or_default() / total,
+                _ => Decimal::ZERO,
+            };
+
+            entry.settled += 1;
+            if pnl > Decimal::ZERO {
+                entry.wins += 1;
+            }
+            entry.realized_pnl += pnl;
+            entry.weighted_pnl += pnl * share;
+        }
+
+        by_source.sort_by_key(|a| std::cmp::Reverse(a.realized_pnl));
+        Ok(by_source)
+    }
+
+    /// What each source called on tagged trades settled after `since`
+    ///
+    /// Directions are calls on the traded token, so a source was right if
+    /// it called positive and the token paid out. Returns the outcomes with
+    /// the latest settlement time among them, to pass back as `since` next
+    /// time so each settlement is learned from once.
+    pub async fn source_outcomes_since(
+        &self,
+        since: Option<DateTime<Utc>>,
+    ) -> Result<(Vec<TradeOutcome>, Option<DateTime<Utc>>)> {
+        let since = since.map(|t| t.to_rfc3339()).unwrap_or_default();
+        let rows: Vec<(String, String, String, String)> = sqlx::query_as(
+            r#"
+            SELECT ts.source, ts.direction, s.payout_price, s.settled_at
+            FROM trade_sources ts
+            JOIN trades t ON t.id = ts.trade_id
+            JOIN (
+                SELECT token_id, MAX(payout_price) AS payout_price, MAX(settled_at) AS settled_at
+                FROM settlements GROUP BY token_id
+            ) s ON s.token_id = t.token_id
+            WHERE s.settled_at > ?
+            ORDER BY s.settled_at
+            "#,
+        )
+        .bind(since)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut outcomes = Vec::new();
+        let mut latest = None;
+        for (source, direction, payout, settled_at) in rows {
+            latest = DateTime::parse_from_rfc3339(&settled_at).ok().map(|t| t.with_timezone(&Utc)).or(latest);
+            let (Ok(source), Ok(predicted), Ok(payout)) = (
+                serde_json::from_str::<SignalSource>(&source),
+                direction.parse::<f64>(),
+                payout.parse::<Decimal>(),
+            ) else {
+                continue;
+            };
+            let realized = if payout > Decimal::new(5, 1) { 1.0 } else { -1.0 };
+            outcomes.push(TradeOutcome { source, predicted, realized });
+        }
+        Ok((outcomes, latest))
+    }
+
+    /// Record a settlement, its closing trade, and drop the settled position
+    ///
+    /// Returns false, changing nothing, if the token was already settled.
+    pub async fn record_settlement(&self, settlement: &Settlement) -> Result<bool> {
+        let mut tx = self.pool.begin().await?;
+
+        let inserted = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO settlements (token_id, market_id, winning_outcome, side, size, entry_price, payout_price, realized_pnl, settled_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&settlement.token_id)
+        .bind(&settlement.market_id)
+        .bind(&settlement.winning_outcome)
+        .bind(format!("{:?}", settlement.side))
+        .bind(settlement.size.to_string())
+        .bind(settlement.entry_price.to_string())
+        .bind(settlement.payout_price.to_string())
+        .bind(settlement.realized_pnl.to_string())
+        .bind(settlement.settled_at.to_rfc3339())
+        .execute(&mut *tx)
+        .await?
+        .rows_affected()
+            > 0;
+        if !inserted {
+            return Ok(false);
+        }
+
+        let closing_side = match settlement.side {
+            Side::Buy => Side::Sell,
+            Side::Sell => Side::Buy,
+        };
+        sqlx::query(
+            r#"
+            INSERT INTO trades (id, order_id, token_id, market_id, side, price, size, fee, timestamp, client_order_id)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(uuid::Uuid::new_v4().to_string())
+        .bind(settlement_order_id(&settlement.market_id))
+        .bind(&settlement.token_id)
+        .bind(&settlement.market_id)
+        .bind(format!("{:?}", closing_side))
+        .bind(settlement.payout_price.to_string())
+        .bind(settlement.size.to_string())
+        .bind(Decimal::ZERO.to_string())
+        .bind(settlement.settled_at.to_rfc3339())
+        .bind(format!("settle-{}", settlement.token_id))
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query("DELETE FROM positions WHERE token_id = ?")
+            .bind(&settlement.token_id)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// Total P&L realized by settlements
+    pub async fn settled_pnl(&self) -> Result<Decimal> {
+        let pnls: Vec<String> = sqlx::query_scalar("SELECT realized_pnl FROM settlements")
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(pnls.iter().filter_map(|p| p.parse::<Decimal>().ok()).sum())
+    }
+
+    /// Remember that a market was traded, so a restart doesn't trade it again
+    ///
+    /// The record lapses at `expires_at`, after which the market counts as
+    /// untraded. Marking a market again refreshes both timestamps.
+    pub async fn mark_traded(&self, market_id: &str, expires_at: DateTime<Utc>) -> Result<()> {
+        self.mark_traded_at(market_id, Utc::now(), expires_at).await
+    }
+
+    async fn mark_traded_at(&self, market_id: &str, traded_at: DateTime<Utc>, expires_at: DateTime<Utc>) -> Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO traded_markets (market_id, traded_at, expires_at) VALUES (?, ?, ?)
+            ON CONFLICT (market_id) DO UPDATE SET traded_at = excluded.traded_at, expires_at = excluded.expires_at
+            "#,
+        )
+        .bind(market_id)
+        .bind(sortable_time(traded_at))
+        .bind(sortable_time(expires_at))
         .execute(&self.pool)
         .await?;
+        Ok(())
+    }
+
+    /// Whether a market was traded within `window` and its record has not expired
+    pub async fn was_traded_recently(&self, market_id: &str, window: chrono::Duration) -> Result<bool> {
+        let now = Utc::now();
+        let traded: bool = sqlx::query_scalar(
+            "SELECT COUNT(*) > 0 FROM traded_markets WHERE market_id = ? AND traded_at >= ? AND expires_at > ?",
+        )
+        .bind(market_id)
+        .bind(sortable_time(now - window))
+        .bind(sortable_time(now))
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(traded)
+    }
 
+    /// Markets traded within `window` whose records have not expired
+    pub async fn recently_traded_markets(&self, window: chrono::Duration) -> Result<Vec<String>> {
+        let now = Utc::now();
+        let ids = sqlx::query_scalar(
+            "SELECT market_id FROM traded_markets WHERE traded_at >= ? AND expires_at > ? ORDER BY traded_at",
+        )
+        .bind(sortable_time(now - window))
+        .bind(sortable_time(now))
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(ids)
+    }
+
+    /// Delete expired traded-market records, returning how many went
+    pub async fn purge_expired_traded(&self) -> Result<u64> {
+        let result = sqlx::query("DELETE FROM traded_markets WHERE expires_at <= ?")
+            .bind(sortable_time(Utc::now()))
+            .execute(&self.pool)
+            .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Replace the stored tags of a market
+    pub async fn save_market_tags(&self, market: &Market) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        replace_tags(&mut tx, market).await?;
+        tx.commit().await?;
         Ok(())
     }
 
+    /// Tags stored for a market, in insertion order
+    pub async fn market_tags(&self, market_id: &str) -> Result<Vec<String>> {
+        let tags = sqlx::query_scalar("SELECT tag FROM market_tags WHERE market_id = ? ORDER BY rowid")
+            .bind(market_id)
+            .fetch_all(&self.pool)
+            .await?;
+        Ok(tags)
+    }
+
+    /// Remember how a market resolved, and its tags, for base rates
+    ///
+    /// Returns false, changing nothing, if the market was already recorded.
+    pub async fn record_resolution(&self, market: &Market, winner: &Outcome) -> Result<bool> {
+        let yes_won = market
+            .find_outcome("yes", &OutcomeMatcher::default())
+            .is_some_and(|yes| yes.token_id == winner.token_id);
+        let mut tx = self.pool.begin().await?;
+        let inserted = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO resolved_markets (market_id, question, category, winning_outcome, yes_won, resolved_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&market.id)
+        .bind(&market.question)
+        .bind(&market.category)
+        .bind(&winner.outcome)
+        .bind(yes_won)
+        .bind(chrono::Utc::now().to_rfc3339())
+        .execute(&mut *tx)
+        .await?
+        .rows_affected()
+            > 0;
+        if !inserted {
+            return Ok(false);
+        }
+        replace_tags(&mut tx, market).await?;
+        tx.commit().await?;
+        Ok(true)
+    }
+
+    /// How often resolved markets in a category (case-insensitive) resolved Yes
+    pub async fn resolution_stats_by_category(&self, category: &str) -> Result<ResolutionStats> {
+        let (resolved, yes_resolved): (i64, Option<i64>) = sqlx::query_as(
+            "SELECT COUNT(*), SUM(yes_won) FROM resolved_markets WHERE category = ? COLLATE NOCASE",
+        )
+        .bind(category)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(ResolutionStats {
+            resolved: resolved as u64,
+            yes_resolved: yes_resolved.unwrap_or(0) as u64,
+        })
+    }
+
+    /// How often resolved markets carrying a tag (case-insensitive) resolved Yes
+    ///
+    /// A tag no resolved market carries gives empty stats.
+    pub async fn resolution_stats_by_tag(&self, tag: &str) -> Result<ResolutionStats> {
+        let (resolved, yes_resolved): (i64, Option<i64>) = sqlx::query_as(
+            r#"
+            SELECT COUNT(*), SUM(r.yes_won)
+            FROM resolved_markets r
+            JOIN market_tags t ON t.market_id = r.market_id
+            WHERE t.tag = ?
+            "#,
+        )
+        .bind(tag)
+        .fetch_one(&self.pool)
+        .await?;
+        Ok(ResolutionStats {
+            resolved: resolved as u64,
+            yes_resolved: yes_resolved.unwrap_or(0) as u64,
+        })
+    }
+
     /// Get recent trades
     pub async fn get_recent_trades(&self, limit: i64) -> Result<Vec<Trade>> {
         let rows = sqlx::query_as::<_, TradeRow>(
             r#"
-            SELECT id, order_id, token_id, market_id, side, price, size, fee, timestamp
+            SELECT id, order_id, token_id, market_id, side, price, size, fee, timestamp, client_order_id, correlation_id
             FROM trades
             ORDER BY timestamp DESC
             LIMIT ?
@@ -123,13 +599,30 @@ impl Database {
         Ok(rows.into_iter().filter_map(|r| r.try_into().ok()).collect())
     }
 
+    /// Trades produced by one event chain, oldest first
+    pub async fn get_trades_by_correlation(&self, correlation_id: &str) -> Result<Vec<Trade>> {
+        let rows = sqlx::query_as::<_, TradeRow>(
+            r#"
+            SELECT id, order_id, token_id, market_id, side, price, size, fee, timestamp, client_order_id, correlation_id
+            FROM trades
+            WHERE correlation_id = ?
+            ORDER BY timestamp ASC
+            "#,
+        )
+        .bind(correlation_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().filter_map(|r| r.try_into().ok()).collect())
+    }
+
     /// Get daily performance stats
     pub async fn get_daily_stats(&self) -> Result<PerformanceStats> {
         let today = chrono::Utc::now().format("%Y-%m-%d").to_string();
         
         let rows = sqlx::query_as::<_, TradeRow>(
             r#"
-            SELECT id, order_id, token_id, market_id, side, price, size, fee, timestamp
+            SELECT id, order_id, token_id, market_id, side, price, size, fee, timestamp, client_order_id, correlation_id
             FROM trades
             WHERE timestamp LIKE ?
             ORDER BY timestamp DESC
@@ -144,6 +637,10 @@ impl Database {
         let total_trades = trades.len();
         // Note: PnL calculation requires position tracking - simplified here
         let total_pnl = Decimal::ZERO; // TODO: Calculate from closed positions
+        // Risk-adjusted ratios need the whole history, not just today
+        let returns = self.daily_settlement_returns().await?;
+        let years = self.settlement_span().await?.num_seconds() as f64
+            / (f64::from(ratios::DAYS_PER_YEAR) * 24.0 * 60.0 * 60.0);
         
         Ok(PerformanceStats {
             total_trades,
@@ -153,8 +650,132 @@ impl Database {
             total_pnl,
             avg_pnl_per_trade: Decimal::ZERO,
             sharpe_ratio: None,
+            sortino_ratio: ratios::sortino_ratio(&returns, ratios::DAYS_PER_YEAR),
+            calmar_ratio: ratios::calmar_ratio(&returns, years),
+        })
+    }
+
+    /// Time from the first settlement to the last, zero with fewer than two
+    pub async fn settlement_span(&self) -> Result<chrono::Duration> {
+        let (first, last): (Option<String>, Option<String>) =
+            sqlx::query_as("SELECT MIN(settled_at), MAX(settled_at) FROM settlements")
+                .fetch_one(&self.pool)
+                .await?;
+        let parse = |t: Option<String>| t.and_then(|t| DateTime::parse_from_rfc3339(&t).ok());
+        Ok(match (parse(first), parse(last)) {
+            (Some(first), Some(last)) => last - first,
+            _ => chrono::Duration::zero(),
         })
     }
+
+    /// Realized return of each day with settlements, oldest first
+    ///
+    /// A day's return is its settled PnL over the cost of the positions
+    /// settled that day.
+    pub async fn daily_settlement_returns(&self) -> Result<Vec<Decimal>> {
+        let rows: Vec<(String, String, String, String, String)> = sqlx::query_as(
+            "SELECT side, size, entry_price, realized_pnl, settled_at FROM settlements ORDER BY settled_at",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut days: Vec<(String, Decimal, Decimal)> = Vec::new();
+        for (side, size, entry_price, pnl, settled_at) in rows {
+            let (Ok(size), Ok(entry_price), Ok(pnl)) =
+                (size.parse::<Decimal>(), entry_price.parse::<Decimal>(), pnl.parse::<Decimal>())
+            else {
+                continue;
+            };
+            // A short paid 1 - entry per share for the other side
+            let unit_cost = if side == "Sell" { Decimal::ONE - entry_price } else { entry_price };
+            let day = settled_at.get(..10).unwrap_or(&settled_at).to_string();
+            match days.last_mut() {
+                Some((last, cost, total)) if *last == day => {
+                    *cost += unit_cost * size;
+                    *total += pnl;
+                }
+                _ => days.push((day, unit_cost * size, pnl)),
+            }
+        }
+
+        Ok(days
+            .into_iter()
+            .filter(|(_, cost, _)| *cost > Decimal::ZERO)
+            .map(|(_, cost, pnl)| pnl / cost)
+            .collect())
+    }
+}
+
+/// RFC 3339 with fixed millisecond precision and a `Z` suffix, so stored
+/// times compare correctly as strings
+fn sortable_time(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+/// Replace a market's rows in `market_tags` within a transaction
+async fn replace_tags(tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>, market: &Market) -> Result<()> {
+    sqlx::query("DELETE FROM market_tags WHERE market_id = ?")
+        .bind(&market.id)
+        .execute(&mut **tx)
+        .await?;
+    for tag in &market.tags {
+        sqlx::query("INSERT OR IGNORE INTO market_tags (market_id, tag) VALUES (?, ?)")
+            .bind(&market.id)
+            .bind(tag)
+            .execute(&mut **tx)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Resolution counts for a group of similar markets
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ResolutionStats {
+    /// Markets that resolved
+    pub resolved: u64,
+    /// Of those, markets that resolved Yes
+    pub yes_resolved: u64,
+}
+
+impl ResolutionStats {
+    /// Share of markets that resolved Yes, if any resolved
+    pub fn yes_rate(&self) -> Option<Decimal> {
+        (self.resolved > 0).then(|| Decimal::from(self.yes_resolved) / Decimal::from(self.resolved))
+    }
+}
+
+/// Realized performance of the trades one signal source contributed to
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceAttribution {
+    pub source: SignalSource,
+    /// Tagged trades, settled or not
+    pub trades: u64,
+    /// Of those, trades whose token has settled
+    pub settled: u64,
+    /// Settled trades that made money
+    pub wins: u64,
+    /// PnL of every settled trade the source contributed to
+    pub realized_pnl: Decimal,
+    /// PnL split between each trade's sources by weight
+    pub weighted_pnl: Decimal,
+}
+
+impl SourceAttribution {
+    fn new(source: SignalSource) -> Self {
+        Self {
+            source,
+            trades: 0,
+            settled: 0,
+            wins: 0,
+            realized_pnl: Decimal::ZERO,
+            weighted_pnl: Decimal::ZERO,
+        }
+    }
+
+    /// Share of settled trades that made money, if any settled
+    pub fn win_rate(&self) -> Option<Decimal> {
+        (self.settled > 0).then(|| Decimal::from(self.wins) / Decimal::from(self.settled))
+    }
 }
 
 #[derive(Debug, sqlx::FromRow)]
@@ -168,14 +789,27 @@ struct TradeRow {
     size: String,
     fee: String,
     timestamp: String,
+    client_order_id: Option<String>,
+    correlation_id: Option<String>,
+}
+
+/// A tagged trade with its source and, once settled, its payout
+#[derive(Debug, sqlx::FromRow)]
+struct SourceTradeRow {
+    trade_id: String,
+    source: String,
+    weight: String,
+    side: String,
+    price: String,
+    size: String,
+    fee: String,
+    payout_price: Option<String>,
 }
 
 impl TryFrom<TradeRow> for Trade {
     type Error = anyhow::Error;
 
     fn try_from(row: TradeRow) -> std::result::Result<Self, Self::Error> {
-        use crate::types::Side;
-
         Ok(Trade {
             id: row.id,
             order_id: row.order_id,
@@ -190,6 +824,8 @@ impl TryFrom<TradeRow> for Trade {
             size: row.size.parse()?,
             fee: row.fee.parse()?,
             timestamp: row.timestamp.parse()?,
+            client_order_id: row.client_order_id,
+            correlation_id: row.correlation_id,
         })
     }
 }
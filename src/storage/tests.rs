@@ -2,6 +2,7 @@
 
 #[cfg(test)]
 mod tests {
+    use crate::storage::Database;
     use crate::types::{Trade, Side};
     use chrono::Utc;
     use rust_decimal_macros::dec;
@@ -18,6 +19,8 @@ mod tests {
             size: dec!(100),
             fee: dec!(0.50),
             timestamp: Utc::now(),
+            client_order_id: None,
+            correlation_id: None,
         };
         
         assert_eq!(trade.id, "trade1");
@@ -37,6 +40,8 @@ mod tests {
             size: dec!(200),
             fee: dec!(1.00),
             timestamp: Utc::now(),
+            client_order_id: None,
+            correlation_id: None,
         };
         
         let json = serde_json::to_string(&trade).unwrap();
@@ -107,4 +112,326 @@ mod tests {
         let net_profit = gross_profit - fee;
         assert_eq!(net_profit, dec!(45));
     }
+
+    fn correlated_trade(id: &str, correlation_id: Option<&str>, at: chrono::DateTime<Utc>) -> Trade {
+        Trade {
+            id: id.to_string(),
+            order_id: format!("order-{}", id),
+            token_id: "token1".to_string(),
+            market_id: "market1".to_string(),
+            side: Side::Buy,
+            price: dec!(0.55),
+            size: dec!(100),
+            fee: dec!(0.10),
+            timestamp: at,
+            client_order_id: None,
+            correlation_id: correlation_id.map(str::to_string),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_trades_by_correlation_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::connect(dir.path().join("bot.db")).await.unwrap();
+        let now = Utc::now();
+        db.save_trade(&correlated_trade("t2", Some("chain-a"), now)).await.unwrap();
+        db.save_trade(&correlated_trade("t1", Some("chain-a"), now - chrono::Duration::seconds(5))).await.unwrap();
+        db.save_trade(&correlated_trade("t3", Some("chain-b"), now)).await.unwrap();
+        db.save_trade(&correlated_trade("t4", None, now)).await.unwrap();
+
+        let trades = db.get_trades_by_correlation("chain-a").await.unwrap();
+        let ids: Vec<&str> = trades.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["t1", "t2"]);
+        assert!(trades.iter().all(|t| t.correlation_id.as_deref() == Some("chain-a")));
+        assert!(db.get_trades_by_correlation("chain-c").await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_correlation_id_added_to_existing_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bot.db");
+        {
+            let pool = sqlx::SqlitePool::connect(&format!("sqlite:{}?mode=rwc", path.display())).await.unwrap();
+            sqlx::query(
+                "CREATE TABLE trades (id TEXT PRIMARY KEY, order_id TEXT NOT NULL, token_id TEXT NOT NULL, \
+                 market_id TEXT NOT NULL, side TEXT NOT NULL, price TEXT NOT NULL, size TEXT NOT NULL, \
+                 fee TEXT NOT NULL, timestamp TEXT NOT NULL)",
+            )
+            .execute(&pool)
+            .await
+            .unwrap();
+            pool.close().await;
+        }
+
+        let db = Database::connect(&path).await.unwrap();
+        db.save_trade(&correlated_trade("t1", Some("chain-a"), Utc::now())).await.unwrap();
+        assert_eq!(db.get_trades_by_correlation("chain-a").await.unwrap().len(), 1);
+    }
+
+    fn resolved(id: &str, tags: &[&str], yes_won: bool) -> (crate::types::Market, crate::types::Outcome) {
+        use crate::types::{Market, Outcome};
+        let outcome = |name: &str, won: bool| Outcome {
+            token_id: format!("{}-{}", id, name),
+            outcome: name.to_string(),
+            price: if won { dec!(1) } else { dec!(0) },
+        };
+        let market = Market {
+            id: id.to_string(),
+            question: format!("{}?", id),
+            description: None,
+            end_date: None,
+            volume: dec!(0),
+            liquidity: dec!(0),
+            outcomes: vec![outcome("Yes", yes_won), outcome("No", !yes_won)],
+            active: false,
+            closed: true,
+            category: None,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            tick_size: None,
+            min_size: None,
+        };
+        let winner = market.outcomes[if yes_won { 0 } else { 1 }].clone();
+        (market, winner)
+    }
+
+    #[tokio::test]
+    async fn test_resolution_stats_by_tag() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::connect(dir.path().join("bot.db")).await.unwrap();
+        for (id, tags, yes_won) in [
+            ("fed-1", &["Fed", "Economy"][..], true),
+            ("fed-2", &["Fed"][..], false),
+            ("fed-3", &["fed"][..], false),
+            ("cpi-1", &["Economy"][..], true),
+            ("untagged", &[][..], true),
+        ] {
+            let (market, winner) = resolved(id, tags, yes_won);
+            assert!(db.record_resolution(&market, &winner).await.unwrap());
+        }
+
+        let fed = db.resolution_stats_by_tag("FED").await.unwrap();
+        assert_eq!((fed.resolved, fed.yes_resolved), (3, 1));
+        let economy = db.resolution_stats_by_tag("economy").await.unwrap();
+        assert_eq!(economy.yes_rate(), Some(dec!(1)));
+
+        // Markets without tags are stored but never match a tag
+        let none = db.resolution_stats_by_tag("sports").await.unwrap();
+        assert_eq!(none, crate::storage::ResolutionStats::default());
+        assert_eq!(none.yes_rate(), None);
+        assert!(db.market_tags("untagged").await.unwrap().is_empty());
+        assert_eq!(db.market_tags("fed-1").await.unwrap(), vec!["Fed", "Economy"]);
+
+        // A second resolution of the same market changes nothing
+        let (market, winner) = resolved("fed-2", &["Fed"], true);
+        assert!(!db.record_resolution(&market, &winner).await.unwrap());
+        assert_eq!(db.resolution_stats_by_tag("fed").await.unwrap().yes_resolved, 1);
+    }
+
+    fn settlement(token_id: &str, side: Side, size: rust_decimal::Decimal, entry: rust_decimal::Decimal, pnl: rust_decimal::Decimal, day: u32) -> crate::monitor::Settlement {
+        use chrono::TimeZone;
+        crate::monitor::Settlement {
+            market_id: format!("m-{}", token_id),
+            question: "Q?".to_string(),
+            token_id: token_id.to_string(),
+            winning_outcome: "Yes".to_string(),
+            side,
+            size,
+            entry_price: entry,
+            payout_price: rust_decimal::Decimal::ONE,
+            realized_pnl: pnl,
+            settled_at: Utc.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_daily_stats_risk_adjusted_ratios() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::connect(dir.path().join("bot.db")).await.unwrap();
+        assert_eq!(db.get_daily_stats().await.unwrap().sortino_ratio, None);
+
+        // Daily returns on cost of +10%, -5%, +2%, -3%
+        for s in [
+            settlement("a", Side::Buy, dec!(200), dec!(0.5), dec!(10), 1),
+            settlement("b", Side::Buy, dec!(100), dec!(0.5), dec!(-6), 2),
+            // Short at 0.8 cost 0.2 a share
+            settlement("c", Side::Sell, dec!(250), dec!(0.8), dec!(1), 2),
+            settlement("d", Side::Buy, dec!(200), dec!(0.5), dec!(2), 3),
+            settlement("e", Side::Buy, dec!(200), dec!(0.5), dec!(-3), 5),
+        ] {
+            db.record_settlement(&s).await.unwrap();
+        }
+
+        let returns = db.daily_settlement_returns().await.unwrap();
+        assert_eq!(returns, vec![dec!(0.1), dec!(-0.05), dec!(0.02), dec!(-0.03)]);
+        let stats = db.get_daily_stats().await.unwrap();
+        let close = |a: rust_decimal::Decimal, b: rust_decimal::Decimal| (a - b).abs() < dec!(0.0001);
+
+        // Mean 0.01 over downside deviation sqrt((0.0025 + 0.0009) / 4),
+        // times sqrt(365)
+        assert!(close(stats.sortino_ratio.unwrap(), dec!(6.55295)));
+        // 1.033923 growth over the 4 days from the first settlement to the
+        // last is 1.033923^(365/4) - 1 = 19.99172 a year, over the 6.007%
+        // drop from the 1.1 peak
+        assert!(close(stats.calmar_ratio.unwrap(), dec!(332.80698)));
+    }
+
+    fn contribution(source: crate::fusion::SignalSource, weight: f64) -> crate::fusion::SignalContribution {
+        crate::fusion::SignalContribution {
+            source,
+            weight,
+            direction: 1.0,
+            decay_factor: 1.0,
+        }
+    }
+
+    fn buy(id: &str, token_id: &str, price: rust_decimal::Decimal, size: rust_decimal::Decimal) -> Trade {
+        Trade {
+            id: id.to_string(),
+            order_id: format!("o-{}", id),
+            token_id: token_id.to_string(),
+            market_id: format!("m-{}", token_id),
+            side: Side::Buy,
+            price,
+            size,
+            fee: rust_decimal::Decimal::ZERO,
+            timestamp: Utc::now(),
+            client_order_id: None,
+            correlation_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_attribution_by_source() {
+        use crate::fusion::SignalSource::{MLPredictor, OrderBookImbalance, Sentiment};
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::connect(dir.path().join("bot.db")).await.unwrap();
+
+        // t1 wins $40 on "win", t2 loses $50 on "lose", t3 is still open
+        for (trade, sources) in [
+            (buy("t1", "win", dec!(0.60), dec!(100)), vec![contribution(MLPredictor, 3.0), contribution(Sentiment, 1.0)]),
+            (buy("t2", "lose", dec!(0.50), dec!(100)), vec![contribution(Sentiment, 1.0), contribution(Sentiment, 1.0)]),
+            (buy("t3", "open", dec!(0.50), dec!(10)), vec![contribution(OrderBookImbalance, 1.0)]),
+        ] {
+            db.save_trade(&trade).await.unwrap();
+            db.save_trade_sources(&trade.id, &sources).await.unwrap();
+        }
+        let mut won = settlement("win", Side::Buy, dec!(100), dec!(0.60), dec!(40), 1);
+        won.payout_price = dec!(1);
+        let mut lost = settlement("lose", Side::Buy, dec!(100), dec!(0.50), dec!(-50), 1);
+        lost.payout_price = dec!(0);
+        db.record_settlement(&won).await.unwrap();
+        db.record_settlement(&lost).await.unwrap();
+
+        let attribution = db.attribution_by_source().await.unwrap();
+        let sources: Vec<_> = attribution.iter().map(|a| a.source).collect();
+        assert_eq!(sources, vec![MLPredictor, OrderBookImbalance, Sentiment]);
+
+        let ml = &attribution[0];
+        assert_eq!((ml.trades, ml.settled, ml.wins), (1, 1, 1));
+        assert_eq!(ml.realized_pnl, dec!(40));
+        assert_eq!(ml.weighted_pnl, dec!(30));
+
+        // Unsettled trades count but carry no PnL
+        let book = &attribution[1];
+        assert_eq!((book.trades, book.settled), (1, 0));
+        assert_eq!(book.win_rate(), None);
+
+        // Sentiment backed the winner and the loser: net negative
+        let sentiment = &attribution[2];
+        assert_eq!((sentiment.trades, sentiment.settled, sentiment.wins), (2, 2, 1));
+        assert_eq!(sentiment.realized_pnl, dec!(-10));
+        // A quarter of t1's weight, all of t2's
+        assert_eq!(sentiment.weighted_pnl, dec!(10) - dec!(50));
+        assert_eq!(sentiment.win_rate(), Some(dec!(0.5)));
+    }
+
+    #[tokio::test]
+    async fn test_source_outcomes_since() {
+        use crate::fusion::SignalSource::{MLPredictor, Sentiment};
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::connect(dir.path().join("bot.db")).await.unwrap();
+
+        let bearish = crate::fusion::SignalContribution { direction: -0.5, ..contribution(Sentiment, 1.0) };
+        for (trade, sources) in [
+            (buy("t1", "win", dec!(0.60), dec!(100)), vec![contribution(MLPredictor, 1.0), bearish]),
+            (buy("t2", "lose", dec!(0.50), dec!(100)), vec![contribution(MLPredictor, 1.0)]),
+        ] {
+            db.save_trade(&trade).await.unwrap();
+            db.save_trade_sources(&trade.id, &sources).await.unwrap();
+        }
+        let won = settlement("win", Side::Buy, dec!(100), dec!(0.60), dec!(40), 1);
+        db.record_settlement(&won).await.unwrap();
+
+        let (outcomes, latest) = db.source_outcomes_since(None).await.unwrap();
+        assert_eq!(outcomes.len(), 2);
+        assert!(outcomes.iter().all(|o| o.realized > 0.0));
+        assert!(outcomes.iter().find(|o| o.source == MLPredictor).unwrap().correct());
+        assert!(!outcomes.iter().find(|o| o.source == Sentiment).unwrap().correct());
+        assert_eq!(latest, Some(won.settled_at));
+
+        // Already learned from; only the later settlement comes back
+        assert!(db.source_outcomes_since(latest).await.unwrap().0.is_empty());
+        let mut lost = settlement("lose", Side::Buy, dec!(100), dec!(0.50), dec!(-50), 2);
+        lost.payout_price = dec!(0);
+        db.record_settlement(&lost).await.unwrap();
+        let (outcomes, _) = db.source_outcomes_since(latest).await.unwrap();
+        assert_eq!(outcomes.len(), 1);
+        assert_eq!((outcomes[0].source, outcomes[0].correct()), (MLPredictor, false));
+    }
+
+    #[tokio::test]
+    async fn test_traded_markets_survive_reconnect() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bot.db");
+        let hour = chrono::Duration::hours(1);
+        {
+            let db = Database::connect(&path).await.unwrap();
+            assert!(!db.was_traded_recently("m1", hour).await.unwrap());
+            db.mark_traded("m1", Utc::now() + hour).await.unwrap();
+            db.close().await;
+        }
+
+        let db = Database::connect(&path).await.unwrap();
+        assert!(db.was_traded_recently("m1", hour).await.unwrap());
+        assert!(!db.was_traded_recently("m2", hour).await.unwrap());
+        assert_eq!(db.recently_traded_markets(hour).await.unwrap(), vec!["m1"]);
+    }
+
+    #[tokio::test]
+    async fn test_traded_recency_window() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::connect(dir.path().join("bot.db")).await.unwrap();
+        let now = Utc::now();
+        let far_future = now + chrono::Duration::days(1);
+        db.mark_traded_at("old", now - chrono::Duration::hours(3), far_future).await.unwrap();
+        db.mark_traded_at("new", now - chrono::Duration::minutes(10), far_future).await.unwrap();
+
+        assert!(db.was_traded_recently("new", chrono::Duration::hours(1)).await.unwrap());
+        assert!(!db.was_traded_recently("old", chrono::Duration::hours(1)).await.unwrap());
+        assert!(db.was_traded_recently("old", chrono::Duration::hours(4)).await.unwrap());
+        assert_eq!(db.recently_traded_markets(chrono::Duration::hours(4)).await.unwrap(), vec!["old", "new"]);
+
+        // Marking again refreshes the trade time
+        db.mark_traded("old", far_future).await.unwrap();
+        assert!(db.was_traded_recently("old", chrono::Duration::hours(1)).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_traded_expiry() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = Database::connect(dir.path().join("bot.db")).await.unwrap();
+        let now = Utc::now();
+        let day = chrono::Duration::days(1);
+        db.mark_traded_at("hourly", now - chrono::Duration::minutes(90), now - chrono::Duration::minutes(30)).await.unwrap();
+        db.mark_traded_at("daily", now - chrono::Duration::minutes(90), now + day).await.unwrap();
+
+        // An expired record no longer blocks the market, even inside the window
+        assert!(!db.was_traded_recently("hourly", day).await.unwrap());
+        assert!(db.was_traded_recently("daily", day).await.unwrap());
+        assert_eq!(db.recently_traded_markets(day).await.unwrap(), vec!["daily"]);
+
+        assert_eq!(db.purge_expired_traded().await.unwrap(), 1);
+        assert_eq!(db.purge_expired_traded().await.unwrap(), 0);
+        assert!(db.was_traded_recently("daily", day).await.unwrap());
+    }
 }
@@ -0,0 +1,56 @@
+//! Capital budgeting across the signals of one scan
+//!
+//! Each signal is sized on its own by Kelly, so several passing in the same
+//! scan can together ask for more than the portfolio should deploy.
+//! `allocate` trims the set to fit the risk limits before anything is
+//! executed.
+
+use crate::config::RiskConfig;
+use crate::types::{Position, Signal};
+use rust_decimal::Decimal;
+
+/// Fit a scan's signals into what the open positions leave of the budget
+///
+/// Signals are taken in the order given, so pass them best-first (see
+/// `rank_opportunities`). A signal on a token already held adds to that
+/// position; any other needs one of the `max_open_positions` slots `open`
+/// does not fill, and the rest are dropped. The positions' market value
+/// counts against `max_exposure_pct` of `balance`; if the kept signals ask
+/// for more than what is left, every size is scaled down by the same factor
+/// so the total lands exactly on it.
+///
+/// Returns each kept signal with `suggested_size` set to its allocated
+/// fraction of `balance`, alongside that allocation in USD.
+pub fn allocate(signals: Vec<Signal>, balance: Decimal, risk: &RiskConfig, open: &[Position]) -> Vec<(Signal, Decimal)> {
+    let mut free_slots = risk.max_open_positions.saturating_sub(open.len());
+    let signals: Vec<Signal> = signals
+        .into_iter()
+        .filter(|signal| {
+            if open.iter().any(|p| p.token_id == signal.token_id) {
+                return true;
+            }
+            let kept = free_slots > 0;
+            free_slots = free_slots.saturating_sub(1);
+            kept
+        })
+        .collect();
+
+    let exposure: Decimal = open.iter().map(|p| p.size * p.current_price).sum();
+    let used = if balance > Decimal::ZERO { exposure / balance } else { Decimal::ZERO };
+    let cap = (risk.max_exposure_pct - used).max(Decimal::ZERO);
+    if cap.is_zero() {
+        return Vec::new();
+    }
+
+    let requested: Decimal = signals.iter().map(|s| s.suggested_size.max(Decimal::ZERO)).sum();
+    let scale = if requested > cap { cap / requested } else { Decimal::ONE };
+
+    signals
+        .into_iter()
+        .map(|mut signal| {
+            signal.suggested_size *= scale;
+            let size_usd = signal.suggested_size * balance;
+            (signal, size_usd)
+        })
+        .collect()
+}
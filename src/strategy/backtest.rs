@@ -489,9 +489,11 @@ impl BacktestStrategy for MomentumStrategy {
                 model_probability: candle.close,
                 market_probability: candle.close,
                 edge: momentum,
+                net_edge: momentum,
                 confidence: dec!(0.7),
                 suggested_size: dec!(100),
                 timestamp: candle.timestamp,
+                max_staleness: None,
             })
         } else if momentum < -self.threshold && position.is_some() {
             Some(Signal {
@@ -501,9 +503,11 @@ impl BacktestStrategy for MomentumStrategy {
                 model_probability: candle.close,
                 market_probability: candle.close,
                 edge: momentum.abs(),
+                net_edge: momentum.abs(),
                 confidence: dec!(0.7),
                 suggested_size: dec!(100),
                 timestamp: candle.timestamp,
+                max_staleness: None,
             })
         } else {
             None
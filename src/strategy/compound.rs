@@ -93,7 +93,8 @@ impl CompoundStrategy {
 
     /// Generate signal with compound-optimized sizing
     pub fn generate(&self, market: &Market, prediction: &Prediction, current_balance: Decimal) -> Option<Signal> {
-        let market_prob = market.yes_price()?;
+        let yes = market.find_outcome("yes", &self.config.outcome_matching)?;
+        let market_prob = yes.price;
         let model_prob = prediction.probability;
         let edge = model_prob - market_prob;
 
@@ -108,11 +109,7 @@ impl CompoundStrategy {
             return None;
         }
 
-        let yes_token = market
-            .outcomes
-            .iter()
-            .find(|o| o.outcome.to_lowercase() == "yes")
-            .map(|o| o.token_id.clone())?;
+        let yes_token = yes.token_id.clone();
 
         let (side, token_id, effective_prob) = if edge > Decimal::ZERO {
             (Side::Buy, yes_token, model_prob)
@@ -141,9 +138,11 @@ impl CompoundStrategy {
             model_probability: model_prob,
             market_probability: market_prob,
             edge,
+            net_edge: edge,
             confidence: prediction.confidence,
             suggested_size,
             timestamp: Utc::now(),
+            max_staleness: None,
         })
     }
 
@@ -13,9 +13,24 @@ mod tests {
             min_confidence: dec!(0.60),
             kelly_fraction: dec!(0.35),
             scan_interval_secs: 180,
+            scan_concurrency: 4,
             model_update_interval_secs: 900,
             compound_enabled: true,
             compound_sqrt_scaling: true,
+            fee_rate: dec!(0),
+            slippage_estimate: dec!(0),
+            max_price_deviation: dec!(0.05),
+            min_liquidity: dec!(10000),
+            crypto_min_liquidity: dec!(1000),
+            min_minutes_to_resolution: 0,
+            max_minutes_to_resolution: None,
+            size_decay_minutes: 0,
+            outcome_matching: Default::default(),
+            allowlist: Vec::new(),
+            denylist: Vec::new(),
+            categories: Vec::new(),
+            anti_chase: Default::default(),
+            abstain_below_confidence: None,
         };
         
         let risk = RiskConfig {
@@ -24,6 +39,7 @@ mod tests {
             max_daily_loss_pct: dec!(0.12),
             min_balance_reserve: dec!(25),
             max_open_positions: 12,
+            ..RiskConfig::default()
         };
         
         (strategy, risk)
@@ -305,9 +305,11 @@ impl CopySignal {
             model_probability: market_probability, // Use current market price
             market_probability,
             edge: Decimal::ZERO, // We're copying, not analyzing
+            net_edge: Decimal::ZERO,
             confidence: Decimal::try_from(self.trader.win_rate).unwrap_or(Decimal::new(7, 1)),
             suggested_size: self.suggested_size,
             timestamp: self.timestamp,
+            max_staleness: None,
         }
     }
 }
@@ -412,10 +412,12 @@ impl CryptoHfStrategy {
             side,
             model_probability: model_prob,
             market_probability: market_prob,
-            edge: net_edge, // 使用净 edge
+            edge: gross_edge,
+            net_edge,
             confidence: trend_signal.confidence,
             suggested_size: size / dec!(100), // As fraction of portfolio
             timestamp: Utc::now(),
+            max_staleness: None,
         })
     }
 }
@@ -476,6 +478,10 @@ mod tests {
             outcomes: vec![],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         };
         
         let info = CryptoHfStrategy::is_crypto_hf_market(&market);
@@ -495,6 +501,10 @@ mod tests {
             outcomes: vec![],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         };
         
         let info = CryptoHfStrategy::is_crypto_hf_market(&market);
@@ -514,6 +524,10 @@ mod tests {
             outcomes: vec![],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         };
         
         let info = CryptoHfStrategy::is_crypto_hf_market(&market);
@@ -533,6 +547,10 @@ mod tests {
             outcomes: vec![],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         };
         
         let info = CryptoHfStrategy::is_crypto_hf_market(&market);
@@ -552,6 +570,10 @@ mod tests {
             outcomes: vec![],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         };
         
         let info = CryptoHfStrategy::is_crypto_hf_market(&market);
@@ -616,6 +638,10 @@ mod tests {
             outcomes: vec![],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         };
         
         let info = CryptoHfStrategy::is_crypto_hf_market(&market).unwrap();
@@ -635,6 +661,10 @@ mod tests {
             outcomes: vec![],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         };
         
         let info = CryptoHfStrategy::is_crypto_hf_market(&market).unwrap();
@@ -654,6 +684,10 @@ mod tests {
             outcomes: vec![],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         };
         
         let info = CryptoHfStrategy::is_crypto_hf_market(&market).unwrap();
@@ -685,6 +719,10 @@ mod tests {
             ],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         };
         
         let info = CryptoHfStrategy::is_crypto_hf_market(&market);
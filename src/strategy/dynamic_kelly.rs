@@ -6,7 +6,9 @@
 //! 3. Market volatility
 //! 4. Account drawdown level
 //! 5. Confidence in the edge estimate
+//! 6. Volatility regime from the market state monitor
 
+use crate::monitor::{MarketState, VolatilityRegime};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
 use rust_decimal_macros::dec;
@@ -28,6 +30,8 @@ pub struct DynamicKellyConfig {
     pub drawdown_reduction_start: Decimal,
     /// Drawdown level for minimum size (e.g., 0.10 = 10%)
     pub drawdown_reduction_full: Decimal,
+    /// Size multiplier at and beyond `drawdown_reduction_full` (e.g., 0.5 = half size)
+    pub drawdown_floor: Decimal,
     /// Win streak multiplier per consecutive win (e.g., 1.05 = +5% per win)
     pub win_streak_multiplier: Decimal,
     /// Loss streak reduction per consecutive loss (e.g., 0.85 = -15% per loss)  
@@ -47,6 +51,7 @@ impl Default for DynamicKellyConfig {
             lookback_trades: 10,                  // Look at last 10 trades
             drawdown_reduction_start: dec!(0.03), // Start reducing at 3% drawdown
             drawdown_reduction_full: dec!(0.08),  // Full reduction at 8% drawdown
+            drawdown_floor: dec!(0.50),           // Half size at full reduction
             win_streak_multiplier: dec!(1.08),    // +8% per consecutive win
             loss_streak_multiplier: dec!(0.80),   // -20% per consecutive loss
             max_streak_multiplier: dec!(1.30),    // Max +30% from win streak
@@ -71,6 +76,8 @@ pub struct MarketContext {
     pub liquidity_score: Decimal,
     /// Time pressure (0 = plenty of time, 1 = urgent)
     pub time_pressure: Decimal,
+    /// Volatility regime, if the market is being monitored
+    pub regime: Option<VolatilityRegime>,
 }
 
 impl Default for MarketContext {
@@ -79,6 +86,19 @@ impl Default for MarketContext {
             volatility: dec!(0.05),      // 5% default volatility
             liquidity_score: dec!(1.0),  // Assume full liquidity
             time_pressure: dec!(0.0),    // No time pressure
+            regime: None,
+        }
+    }
+}
+
+impl From<&MarketState> for MarketContext {
+    /// Regime and liquidity from the monitor; its volatility is annualized,
+    /// so the return volatility keeps its default
+    fn from(state: &MarketState) -> Self {
+        Self {
+            liquidity_score: state.liquidity_score,
+            regime: Some(state.volatility_regime),
+            ..Self::default()
         }
     }
 }
@@ -161,6 +181,12 @@ impl DynamicKelly {
             let time_mult = Decimal::ONE - (ctx.time_pressure * dec!(0.3)); // Up to -30%
             adjustments.time_multiplier = time_mult;
             fraction = fraction * time_mult;
+
+            // Volatility regime adjustment
+            if let Some(regime) = ctx.regime {
+                adjustments.regime_multiplier = regime.kelly_multiplier();
+                fraction *= regime.kelly_multiplier();
+            }
         }
 
         // 6. Confidence adjustment
@@ -183,9 +209,15 @@ impl DynamicKelly {
         fraction = fraction
             .max(self.config.min_fraction)
             .min(self.config.max_fraction);
+
+        // 9. Regime cap: never more than the regime's share of base Kelly,
+        // however much the streak would add (overrides the floor)
+        if let Some(regime) = market_context.and_then(|ctx| ctx.regime) {
+            fraction = fraction.min(self.config.base_fraction * regime.max_position_multiplier());
+        }
         adjustments.final_fraction = fraction;
 
-        // 9. Calculate final position size
+        // 10. Calculate final position size
         let position_size = full_kelly * fraction;
         
         // Build reasoning before moving adjustments
@@ -238,20 +270,24 @@ impl DynamicKelly {
     }
 
     /// Calculate drawdown-based multiplier
+    ///
+    /// Tapers linearly from 1 at `drawdown_reduction_start` to
+    /// `drawdown_floor` at `drawdown_reduction_full`.
     fn calculate_drawdown_multiplier(&self, drawdown: Decimal) -> Decimal {
         if drawdown <= self.config.drawdown_reduction_start {
             return dec!(1.0);
         }
 
+        let floor = self.config.drawdown_floor.clamp(Decimal::ZERO, Decimal::ONE);
         if drawdown >= self.config.drawdown_reduction_full {
-            return dec!(0.5); // Minimum 50% at full drawdown
+            return floor;
         }
 
         // Linear interpolation between start and full
         let range = self.config.drawdown_reduction_full - self.config.drawdown_reduction_start;
         let progress = (drawdown - self.config.drawdown_reduction_start) / range;
         
-        dec!(1.0) - (progress * dec!(0.5)) // 100% -> 50%
+        dec!(1.0) - (progress * (dec!(1.0) - floor))
     }
 
     /// Calculate volatility-based multiplier
@@ -298,7 +334,7 @@ impl DynamicKelly {
         if value > *peak {
             *peak = value;
             *drawdown = dec!(0);
-        } else {
+        } else if *peak > Decimal::ZERO {
             *drawdown = (*peak - value) / *peak;
         }
     }
@@ -337,6 +373,9 @@ impl DynamicKelly {
         if adj.budget_multiplier != dec!(1) {
             parts.push(format!("budget:{:.0}%", adj.budget_multiplier * dec!(100)));
         }
+        if !adj.regime_multiplier.is_zero() && adj.regime_multiplier != dec!(1) {
+            parts.push(format!("regime:{:.0}%", adj.regime_multiplier * dec!(100)));
+        }
         
         if parts.is_empty() {
             format!("Kelly @ {:.0}%", adj.final_fraction * dec!(100))
@@ -367,6 +406,8 @@ pub struct KellyAdjustments {
     pub time_multiplier: Decimal,
     pub confidence_multiplier: Decimal,
     pub budget_multiplier: Decimal,
+    /// Zero when no regime was given
+    pub regime_multiplier: Decimal,
     pub final_fraction: Decimal,
 }
 
@@ -514,6 +555,7 @@ mod tests {
             volatility: dec!(0.15), // 15% volatility (high)
             liquidity_score: dec!(1.0),
             time_pressure: dec!(0),
+            regime: None,
         };
         
         let result = kelly.calculate_position_size(
@@ -582,6 +624,7 @@ mod tests {
             volatility: dec!(0.10),
             liquidity_score: dec!(0.7),
             time_pressure: dec!(0.5),
+            regime: None,
         };
         
         let result = kelly.calculate_position_size(
@@ -643,6 +686,7 @@ mod tests {
             volatility: dec!(0.05),
             liquidity_score: dec!(0.4), // Low liquidity
             time_pressure: dec!(0),
+            regime: None,
         };
         
         let result = kelly.calculate_position_size(
@@ -657,4 +701,102 @@ mod tests {
         assert!(result.adjustments.liquidity_multiplier < dec!(1));
         assert!(result.adjustments.liquidity_multiplier >= dec!(0.3));
     }
+
+    fn regime_size(kelly: &DynamicKelly, regime: VolatilityRegime) -> KellyResult {
+        let ctx = MarketContext { regime: Some(regime), ..MarketContext::default() };
+        kelly.calculate_position_size(dec!(0.60), dec!(0.50), dec!(0.80), dec!(1.0), Some(&ctx))
+    }
+
+    #[test]
+    fn test_extreme_regime_cuts_size() {
+        let kelly = make_kelly();
+
+        let medium = regime_size(&kelly, VolatilityRegime::Medium);
+        let extreme = regime_size(&kelly, VolatilityRegime::Extreme);
+        assert_eq!(medium.adjustments.regime_multiplier, dec!(1.0));
+        assert_eq!(extreme.adjustments.regime_multiplier, dec!(0.4));
+        assert!(extreme.position_size < medium.position_size * dec!(0.4));
+
+        // Capped at 30% of base Kelly, below the 0.4x multiplier alone
+        assert_eq!(extreme.effective_fraction, dec!(0.25) * dec!(0.3));
+        assert!(extreme.reasoning.contains("regime:40%"));
+    }
+
+    #[test]
+    fn test_regime_cap_overrides_win_streak() {
+        let kelly = make_kelly();
+        for _ in 0..5 {
+            kelly.record_trade(dec!(100));
+        }
+
+        let high = regime_size(&kelly, VolatilityRegime::High);
+        assert!(high.adjustments.streak_multiplier > dec!(1));
+        assert_eq!(high.effective_fraction, dec!(0.25) * dec!(0.6));
+
+        let low = regime_size(&kelly, VolatilityRegime::Low);
+        assert!(low.position_size > high.position_size);
+    }
+
+    #[test]
+    fn test_market_context_from_state() {
+        let state = MarketState {
+            market_id: "m1".to_string(),
+            current_price: dec!(0.5),
+            volatility_regime: VolatilityRegime::High,
+            volatility_pct: dec!(75),
+            momentum: crate::monitor::market_state::Momentum {
+                price_change_pct: dec!(0),
+                strength: dec!(0),
+                accelerating: false,
+            },
+            liquidity_score: dec!(0.6),
+            last_update: chrono::Utc::now(),
+            alerts: Vec::new(),
+            anomalies: Vec::new(),
+        };
+
+        let ctx = MarketContext::from(&state);
+        assert_eq!(ctx.regime, Some(VolatilityRegime::High));
+        assert_eq!(ctx.liquidity_score, dec!(0.6));
+        assert_eq!(ctx.volatility, MarketContext::default().volatility);
+    }
+
+    #[test]
+    fn test_size_tapers_with_drawdown() {
+        let config = DynamicKellyConfig {
+            drawdown_reduction_start: dec!(0),
+            drawdown_reduction_full: dec!(0.30),
+            drawdown_floor: dec!(0.25),
+            ..DynamicKellyConfig::default()
+        };
+
+        let sizes: Vec<Decimal> = [dec!(10000), dec!(9000), dec!(7500)]
+            .into_iter()
+            .map(|equity| {
+                let kelly = DynamicKelly::new(config.clone(), dec!(10000));
+                kelly.update_account_value(equity);
+                kelly
+                    .calculate_position_size(dec!(0.60), dec!(0.50), dec!(0.80), dec!(1.0), None)
+                    .position_size
+            })
+            .collect();
+
+        // 0%, 10% and 25% below peak
+        assert!(sizes[0] > sizes[1] && sizes[1] > sizes[2], "{:?}", sizes);
+        // 10% of the way to a 30% max drawdown is a third of the taper
+        assert_eq!(sizes[1] / sizes[0], dec!(1) - dec!(0.75) / dec!(3));
+
+        // Past the max drawdown the floor holds
+        let kelly = DynamicKelly::new(config, dec!(10000));
+        kelly.update_account_value(dec!(5000));
+        let floor = kelly.calculate_position_size(dec!(0.60), dec!(0.50), dec!(0.80), dec!(1.0), None);
+        assert_eq!(floor.adjustments.drawdown_multiplier, dec!(0.25));
+    }
+
+    #[test]
+    fn test_zero_peak_does_not_panic() {
+        let kelly = DynamicKelly::new(DynamicKellyConfig::default(), dec!(0));
+        kelly.update_account_value(dec!(0));
+        assert_eq!(kelly.get_stats().current_drawdown, dec!(0));
+    }
 }
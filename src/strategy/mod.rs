@@ -14,6 +14,12 @@ pub mod arbitrage;
 pub mod volatility_adaptive;
 pub mod signal_aggregator;
 pub mod performance_monitor;
+pub mod scan;
+pub mod scan_filter;
+pub mod ranking;
+pub mod allocation;
+pub mod skip_cache;
+pub mod throttle;
 
 #[cfg(test)]
 mod tests;
@@ -21,6 +27,8 @@ mod tests;
 mod compound_tests;
 #[cfg(test)]
 mod copy_trade_tests;
+#[cfg(test)]
+mod throttle_tests;
 
 pub use compound::CompoundStrategy;
 pub use copy_trade::{CopyTrader, CopySignal, TopTrader, CopyTradeConfig};
@@ -31,40 +39,205 @@ pub use enhanced_filter::{EnhancedSignalFilter, EnhancedFilterConfig, SignalCand
 pub use arbitrage::{ArbitrageDetector, ArbitrageOpportunity, ArbitrageConfig, ArbitrageType, MarketData as ArbMarketData};
 pub use volatility_adaptive::{VolatilityAdaptiveExits, VolatilityTracker, VolatilityRegime, AdaptiveParams, AtrTrailingStop};
 pub use signal_aggregator::{SignalAggregator, AggregatedDecision, SignalSource, SignalType, SignalDirection, AggregatorConfig, SignalBuilder};
+pub use allocation::allocate;
+pub use ranking::{rank_opportunities, score_opportunity, RankedOpportunity};
+pub use skip_cache::MarketSkipCache;
+pub use throttle::TradeThrottle;
+pub use scan::{run_loop, run_scan, scan_filters, LoopPass, LoopSummary, ModelSignalProvider, RunLimits, ScanContext, ScanReport, SignalProvider};
+pub use scan_filter::{Allowlist, FilterChain, FilterOutcome, HasMappableSymbol, MinLiquidity, NotAlreadyTraded, ScanFilter, SettlementWindow, SkipReason};
 pub use performance_monitor::{PerformanceMonitor, PerformanceMetrics, CompletedTrade, MarketRegime, PerformanceAlert, AlertType, AlertSeverity, MonitorConfig, RegimeAdjustments};
 
-use crate::config::{RiskConfig, StrategyConfig};
+use crate::config::{AntiChaseConfig, RiskConfig, StrategyConfig};
 use crate::model::Prediction;
-use crate::types::{Market, Side, Signal};
-use chrono::Utc;
+use crate::monitor::market_state::{MarketStateMonitor, Momentum};
+use crate::risk::LiquidityMonitor;
+use crate::types::{Market, OutcomeMatcher, Side, Signal};
+use arc_swap::ArcSwap;
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 
 pub use crypto_hf::{CryptoHfStrategy, CryptoPriceTracker};
 
+/// Size multiplier for a market's time to resolution, or `None` to skip it
+///
+/// Markets past their end date are always skipped, as are those resolving
+/// outside `min_minutes_to_resolution..=max_minutes_to_resolution`. Within
+/// the last `size_decay_minutes` the multiplier falls linearly from 1
+/// towards 0, since an edge estimated minutes before resolution has little
+/// time left to be right. Markets without an end date are not time-gated.
+pub fn resolution_factor(market: &Market, config: &StrategyConfig, now: DateTime<Utc>) -> Option<Decimal> {
+    let Some(end_date) = market.end_date else {
+        return Some(Decimal::ONE);
+    };
+    let remaining_secs = (end_date - now).num_seconds();
+    if remaining_secs <= 0 {
+        tracing::debug!("Skipping {}: already past its end date", market.id);
+        return None;
+    }
+
+    let minutes = Decimal::from(remaining_secs) / Decimal::from(60);
+    let too_soon = minutes < Decimal::from(config.min_minutes_to_resolution);
+    let too_late = config.max_minutes_to_resolution.is_some_and(|max| minutes > Decimal::from(max));
+    if too_soon || too_late {
+        tracing::debug!("Skipping {}: resolves in {:.1} minutes, outside the window", market.id, minutes);
+        return None;
+    }
+
+    if config.size_decay_minutes == 0 {
+        return Some(Decimal::ONE);
+    }
+    Some((minutes / Decimal::from(config.size_decay_minutes)).min(Decimal::ONE))
+}
+
+/// Why `market` is excluded by the allowlist, denylist or category filter
+///
+/// List entries match a market whose id contains the entry or that carries
+/// a tag equal to it, ignoring case. A denylisted market is always
+/// excluded; a non-empty allowlist admits only the markets it matches, and
+/// a non-empty `categories` only markets in one of them.
+pub fn market_exclusion(market: &Market, config: &StrategyConfig) -> Option<String> {
+    let matches = |entry: &String| {
+        market.id.to_lowercase().contains(&entry.to_lowercase())
+            || market.tags.iter().any(|tag| tag.eq_ignore_ascii_case(entry))
+    };
+    if let Some(entry) = config.denylist.iter().find(|e| matches(e)) {
+        return Some(format!("denylisted by \"{}\"", entry));
+    }
+    if !config.allowlist.is_empty() && !config.allowlist.iter().any(matches) {
+        return Some("not on the allowlist".to_string());
+    }
+    if !config.categories.is_empty() {
+        let listed = market
+            .category
+            .as_ref()
+            .is_some_and(|category| config.categories.iter().any(|c| c.eq_ignore_ascii_case(category)));
+        if !listed {
+            return Some(format!(
+                "category {} is not traded",
+                market.category.as_deref().unwrap_or("(none)")
+            ));
+        }
+    }
+    None
+}
+
+/// Whether trading `side` on the Yes token would chase `momentum`
+///
+/// A buy chases a sharp Yes-price runup and a sell a sharp drop; with
+/// `require_acceleration` the move must also still be speeding up.
+pub fn is_chasing(momentum: &Momentum, side: Side, config: &AntiChaseConfig) -> bool {
+    if !config.enabled || (config.require_acceleration && !momentum.accelerating) {
+        return false;
+    }
+    match side {
+        Side::Buy => momentum.price_change_pct >= config.threshold_pct,
+        Side::Sell => momentum.price_change_pct <= -config.threshold_pct,
+    }
+}
+
 /// Signal generator based on model predictions
 pub struct SignalGenerator {
-    config: StrategyConfig,
-    risk_config: RiskConfig,
+    config: Arc<ArcSwap<StrategyConfig>>,
+    risk_config: Arc<ArcSwap<RiskConfig>>,
+    /// Order books used to estimate slippage for the intended size
+    liquidity: Option<Arc<RwLock<LiquidityMonitor>>>,
+    /// Price momentum per market, for the anti-chase gate
+    market_state: Option<Arc<MarketStateMonitor>>,
+    /// Balance used to turn the Kelly fraction into a share count
+    bankroll: ArcSwap<Decimal>,
 }
 
 impl SignalGenerator {
     pub fn new(config: StrategyConfig, risk_config: RiskConfig) -> Self {
-        Self { config, risk_config }
+        Self::with_shared_config(
+            Arc::new(ArcSwap::from_pointee(config)),
+            Arc::new(ArcSwap::from_pointee(risk_config)),
+        )
+    }
+
+    /// Read settings from shared handles, e.g. from a `ConfigWatcher`
+    pub fn with_shared_config(
+        config: Arc<ArcSwap<StrategyConfig>>,
+        risk_config: Arc<ArcSwap<RiskConfig>>,
+    ) -> Self {
+        Self {
+            config,
+            risk_config,
+            liquidity: None,
+            market_state: None,
+            bankroll: ArcSwap::from_pointee(Decimal::ZERO),
+        }
+    }
+
+    /// Estimate slippage from live order books instead of `slippage_estimate`
+    pub fn with_liquidity(mut self, liquidity: Arc<RwLock<LiquidityMonitor>>) -> Self {
+        self.liquidity = Some(liquidity);
+        self
+    }
+
+    /// Read price momentum from this monitor for `anti_chase`
+    pub fn with_market_state(mut self, market_state: Arc<MarketStateMonitor>) -> Self {
+        self.market_state = Some(market_state);
+        self
+    }
+
+    /// Balance the suggested size is a fraction of, for slippage estimates
+    pub fn set_bankroll(&self, bankroll: Decimal) {
+        self.bankroll.store(Arc::new(bankroll));
+    }
+
+    /// How the current config matches outcome names to Yes/No
+    pub fn outcome_matcher(&self) -> OutcomeMatcher {
+        self.config.load().outcome_matching.clone()
+    }
+
+    /// Why the current config keeps `market` out of the scan, if it does
+    pub fn exclusion(&self, market: &Market) -> Option<String> {
+        market_exclusion(market, &self.config.load())
+    }
+
+    /// Why the current config abstains from `signal`, if it does
+    ///
+    /// Separate from the `min_confidence` gate in `generate`: a signal can
+    /// clear its edge and confidence checks and still be abstained from
+    /// when its confidence is below `abstain_below_confidence`.
+    pub fn abstention(&self, signal: &Signal) -> Option<String> {
+        let floor = self.config.load().abstain_below_confidence?;
+        (signal.confidence < floor).then(|| {
+            format!(
+                "confidence {:.1}% below abstention floor {:.1}% (edge {:.1}%)",
+                signal.confidence * Decimal::ONE_HUNDRED,
+                floor * Decimal::ONE_HUNDRED,
+                signal.edge * Decimal::ONE_HUNDRED
+            )
+        })
     }
 
     /// Generate trading signal from market and prediction
     pub fn generate(&self, market: &Market, prediction: &Prediction) -> Option<Signal> {
-        let market_prob = market.yes_price()?;
+        let config = self.config.load();
+
+        // Yes and No drifting apart means at least one quote is stale
+        if !market.is_price_consistent(config.max_price_deviation) {
+            tracing::debug!("Skipping {}: outcome prices do not sum to 1", market.id);
+            return None;
+        }
+        let time_factor = resolution_factor(market, &config, Utc::now())?;
+
+        let yes = market.find_outcome("yes", &config.outcome_matching)?;
+        let market_prob = yes.price;
         let model_prob = prediction.probability;
         let edge = model_prob - market_prob;
 
         // Check if edge is significant
-        if edge.abs() < self.config.min_edge {
+        if edge.abs() < config.min_edge {
             return None;
         }
 
         // Check confidence threshold
-        if prediction.confidence < self.config.min_confidence {
+        if prediction.confidence < config.min_confidence {
             return None;
         }
 
@@ -72,11 +245,7 @@ impl SignalGenerator {
         // edge > 0: Model thinks Yes underpriced -> Buy Yes
         // edge < 0: Model thinks Yes overpriced -> Sell Yes (not Buy No!)
         //           Selling Yes is usually more liquid than buying No
-        let yes_token = market
-            .outcomes
-            .iter()
-            .find(|o| o.outcome.to_lowercase() == "yes")
-            .map(|o| o.token_id.clone())?;
+        let yes_token = yes.token_id.clone();
 
         let (side, token_id, effective_prob) = if edge > Decimal::ZERO {
             (Side::Buy, yes_token, model_prob)
@@ -92,7 +261,36 @@ impl SignalGenerator {
         } else {
             Decimal::ONE - market_prob // Selling Yes = buying at (1 - price)
         };
-        let suggested_size = self.calculate_kelly_size(effective_prob, market_price, prediction.confidence);
+        let mut suggested_size =
+            self.calculate_kelly_size(effective_prob, market_price, prediction.confidence) * time_factor;
+
+        // Don't get picked off trading into a sharp move
+        if let Some(momentum) = self.chased_momentum(&market.id, side, &config.anti_chase) {
+            if config.anti_chase.size_multiplier <= Decimal::ZERO {
+                tracing::debug!(
+                    "Skipping {}: chasing a {:.1}% move",
+                    market.id,
+                    momentum.price_change_pct
+                );
+                return None;
+            }
+            suggested_size *= config.anti_chase.size_multiplier;
+        }
+
+        // Gate on edge after round-trip costs, not on the raw mispricing
+        let cost = self.round_trip_cost(&market.id, side, market_price, suggested_size, &config);
+        let net_magnitude = edge.abs() - cost;
+        if net_magnitude < config.min_edge {
+            tracing::debug!(
+                "Skipping {}: edge {:.4} is {:.4} after costs of {:.4}",
+                market.id,
+                edge,
+                net_magnitude,
+                cost
+            );
+            return None;
+        }
+        let net_edge = if edge > Decimal::ZERO { net_magnitude } else { -net_magnitude };
 
         Some(Signal {
             market_id: market.id.clone(),
@@ -101,12 +299,128 @@ impl SignalGenerator {
             model_probability: model_prob,
             market_probability: market_prob,
             edge,
+            net_edge,
             confidence: prediction.confidence,
             suggested_size,
             timestamp: Utc::now(),
+            max_staleness: None,
+        })
+    }
+
+    /// Generate a signal from per-outcome probabilities, e.g. for a
+    /// multi-outcome market
+    ///
+    /// `probabilities` maps outcome names (case-insensitive) to the model's
+    /// probability; when it covers every outcome it is normalized to sum
+    /// to 1. Outcomes are ranked by mispricing against the normalized market
+    /// probabilities, and the best one is bought if its edge at the price
+    /// actually paid clears `min_edge` after costs. Only buys are generated.
+    pub fn generate_for_outcomes(
+        &self,
+        market: &Market,
+        probabilities: &HashMap<String, Decimal>,
+        confidence: Decimal,
+    ) -> Option<Signal> {
+        let config = self.config.load();
+        if confidence < config.min_confidence {
+            return None;
+        }
+        let time_factor = resolution_factor(market, &config, Utc::now())?;
+
+        let model: HashMap<String, Decimal> =
+            probabilities.iter().map(|(name, p)| (name.to_lowercase(), *p)).collect();
+        let model_total: Decimal = model.values().sum();
+        let normalize = market.outcomes.iter().all(|o| model.contains_key(&o.outcome.to_lowercase()))
+            && model_total > Decimal::ZERO;
+
+        let (outcome, model_prob, _) = market
+            .implied_probabilities()
+            .into_iter()
+            .filter_map(|(outcome, implied)| {
+                let p = *model.get(&outcome.outcome.to_lowercase())?;
+                let p = if normalize { p / model_total } else { p };
+                Some((outcome, p, p - implied))
+            })
+            .max_by(|a, b| a.2.cmp(&b.2))?;
+
+        let price = outcome.price;
+        let edge = model_prob - price;
+        if edge < config.min_edge {
+            return None;
+        }
+
+        let suggested_size = self.calculate_kelly_size(model_prob, price, confidence) * time_factor;
+        let cost = self.round_trip_cost(&market.id, Side::Buy, price, suggested_size, &config);
+        let net_edge = edge - cost;
+        if net_edge < config.min_edge || suggested_size <= Decimal::ZERO {
+            tracing::debug!(
+                "Skipping {} ({}): edge {:.4} is {:.4} after costs of {:.4}",
+                market.id,
+                outcome.outcome,
+                edge,
+                net_edge,
+                cost
+            );
+            return None;
+        }
+
+        Some(Signal {
+            market_id: market.id.clone(),
+            token_id: outcome.token_id.clone(),
+            side: Side::Buy,
+            model_probability: model_prob,
+            market_probability: price,
+            edge,
+            net_edge,
+            confidence,
+            suggested_size,
+            timestamp: Utc::now(),
+            max_staleness: None,
         })
     }
 
+    /// The market's momentum, if trading `side` now would chase it
+    fn chased_momentum(&self, market_id: &str, side: Side, config: &AntiChaseConfig) -> Option<Momentum> {
+        if !config.enabled {
+            return None;
+        }
+        let momentum = self.market_state.as_ref()?.get_state(market_id)?.momentum;
+        is_chasing(&momentum, side, config).then_some(momentum)
+    }
+
+    /// Expected fees plus slippage for entering and exiting, in probability points
+    ///
+    /// Slippage comes from the liquidity monitor's book for the intended size
+    /// when one is available, otherwise from `slippage_estimate`.
+    fn round_trip_cost(
+        &self,
+        market_id: &str,
+        side: Side,
+        market_price: Decimal,
+        suggested_size: Decimal,
+        config: &StrategyConfig,
+    ) -> Decimal {
+        let bankroll = **self.bankroll.load();
+        let shares = if market_price > Decimal::ZERO {
+            suggested_size * bankroll / market_price
+        } else {
+            Decimal::ZERO
+        };
+
+        let slippage = self
+            .liquidity
+            .as_ref()
+            .filter(|_| shares > Decimal::ZERO)
+            .and_then(|l| {
+                l.read()
+                    .ok()?
+                    .estimate_trade_slippage(market_id, shares, side == Side::Buy)
+            })
+            .unwrap_or(config.slippage_estimate);
+
+        market_price * Decimal::TWO * (config.fee_rate + slippage)
+    }
+
     /// Calculate position size using fractional Kelly criterion
     ///
     /// Kelly formula for binary bets: f* = (p * b - q) / b
@@ -145,13 +459,13 @@ impl SignalGenerator {
         }
 
         // Fractional Kelly for safety (typically 0.25 - 0.5)
-        let fractional_kelly = full_kelly * self.config.kelly_fraction;
+        let fractional_kelly = full_kelly * self.config.load().kelly_fraction;
 
         // Adjust by model confidence
         let adjusted = fractional_kelly * confidence;
 
         // Cap at max position size
-        adjusted.min(self.risk_config.max_position_pct)
+        adjusted.min(self.risk_config.load().max_position_pct)
     }
 }
 pub mod realtime;
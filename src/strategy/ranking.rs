@@ -0,0 +1,71 @@
+//! Opportunity scoring for choosing between scan candidates
+//!
+//! A scan can turn up more signals than there is capital for. Rather than
+//! trading them in the order the markets were fetched, each candidate gets a
+//! single score and the best are executed first.
+
+use crate::risk::LiquidityAssessment;
+use crate::types::{Market, Signal};
+use chrono::Utc;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+
+/// Market liquidity (USD) treated as fully liquid when no book assessment
+/// is available
+const FULL_LIQUIDITY_USD: Decimal = dec!(100000);
+/// Days to resolution at which the time factor halves
+const RESOLUTION_HALF_LIFE_DAYS: Decimal = dec!(30);
+
+/// A signal with its market and opportunity score
+#[derive(Debug, Clone)]
+pub struct RankedOpportunity<'a> {
+    pub signal: Signal,
+    pub market: &'a Market,
+    pub score: Decimal,
+}
+
+/// Single score combining edge, confidence, liquidity and time to resolution
+///
+/// The score is `|net_edge| * confidence * liquidity * time`, where
+/// liquidity is the assessment's 0-100 score as a fraction (zero when the
+/// book is not tradeable) or, without an assessment, the market's reported
+/// liquidity relative to `FULL_LIQUIDITY_USD`. The time factor
+/// `1 / (1 + days / 30)` favours markets that free up capital sooner;
+/// markets with no end date are scored as resolving in 30 days.
+pub fn score_opportunity(
+    signal: &Signal,
+    market: &Market,
+    liquidity: Option<&LiquidityAssessment>,
+) -> Decimal {
+    let liquidity_factor = match liquidity {
+        Some(assessment) if !assessment.tradeable => Decimal::ZERO,
+        Some(assessment) => assessment.score / Decimal::ONE_HUNDRED,
+        None => market.liquidity / FULL_LIQUIDITY_USD,
+    }
+    .clamp(Decimal::ZERO, Decimal::ONE);
+
+    let days = match market.end_date {
+        Some(end) => Decimal::from((end - Utc::now()).num_seconds().max(0)) / dec!(86400),
+        None => RESOLUTION_HALF_LIFE_DAYS,
+    };
+    let time_factor = Decimal::ONE / (Decimal::ONE + days / RESOLUTION_HALF_LIFE_DAYS);
+
+    signal.net_edge.abs() * signal.confidence.clamp(Decimal::ZERO, Decimal::ONE) * liquidity_factor * time_factor
+}
+
+/// Score candidates and sort them best-first
+///
+/// Candidates with equal scores keep their original order.
+pub fn rank_opportunities<'a>(
+    candidates: impl IntoIterator<Item = (Signal, &'a Market, Option<&'a LiquidityAssessment>)>,
+) -> Vec<RankedOpportunity<'a>> {
+    let mut ranked: Vec<RankedOpportunity<'a>> = candidates
+        .into_iter()
+        .map(|(signal, market, liquidity)| {
+            let score = score_opportunity(&signal, market, liquidity);
+            RankedOpportunity { signal, market, score }
+        })
+        .collect();
+    ranked.sort_by_key(|r| std::cmp::Reverse(r.score));
+    ranked
+}
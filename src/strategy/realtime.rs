@@ -2,8 +2,10 @@
 //!
 //! Combines Binance price stream with Polymarket orderbook for better predictions.
 
+use crate::data::SymbolMapper;
 use crate::error::Result;
 use crate::types::{Market, Side, Signal};
+use crate::utils::truncate_chars;
 use chrono::Utc;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
@@ -13,6 +15,9 @@ use std::time::{Duration, Instant};
 use tokio::sync::{mpsc, RwLock};
 use tracing::{debug, info, warn};
 
+/// Momentum signals are only good while the move they saw is fresh
+const MOMENTUM_SIGNAL_TTL: chrono::Duration = chrono::Duration::seconds(30);
+
 /// Real-time price data from Binance
 #[derive(Debug, Clone)]
 pub struct RealtimePrice {
@@ -34,6 +39,8 @@ pub struct RealtimeEngine {
     min_momentum: Decimal,
     /// Signal output channel
     signal_tx: mpsc::Sender<Signal>,
+    /// Maps market questions to Binance pairs
+    symbols: SymbolMapper,
 }
 
 impl RealtimeEngine {
@@ -43,9 +50,16 @@ impl RealtimeEngine {
             history: Arc::new(RwLock::new(HashMap::new())),
             min_momentum: dec!(0.02), // 0.02% minimum
             signal_tx,
+            symbols: SymbolMapper::default(),
         }
     }
 
+    /// Map market questions to Binance pairs with `symbols`
+    pub fn with_symbols(mut self, symbols: SymbolMapper) -> Self {
+        self.symbols = symbols;
+        self
+    }
+
     /// Update price from WebSocket stream
     pub async fn update_price(&self, symbol: &str, price: Decimal) {
         let now = Instant::now();
@@ -119,9 +133,9 @@ impl RealtimeEngine {
     /// Generate signal for crypto market based on real-time data
     pub async fn generate_signal(&self, market: &Market) -> Option<Signal> {
         // Detect which crypto this market is for
-        let symbol = Self::detect_crypto(&market.question)?;
+        let symbol = self.symbols.symbol_for(&market.question)?;
         
-        let price_data = self.get_price(&symbol).await?;
+        let price_data = self.get_price(symbol).await?;
         
         // Use 1-minute momentum for short-term markets
         let momentum = price_data.change_1m;
@@ -151,7 +165,7 @@ impl RealtimeEngine {
         }
 
         info!("🎯 {} {}: momentum {:.3}% → {} | edge {:.1}%", 
-            symbol, direction, momentum, market.question.chars().take(30).collect::<String>(), 
+            symbol, direction, momentum, truncate_chars(&market.question, 30),
             edge * dec!(100));
 
         Some(Signal {
@@ -161,26 +175,13 @@ impl RealtimeEngine {
             model_probability: model_prob,
             market_probability: market_prob,
             edge,
+            net_edge: edge,
             confidence: dec!(0.7),
             suggested_size: dec!(0.1), // 10% of portfolio
             timestamp: Utc::now(),
+            max_staleness: Some(MOMENTUM_SIGNAL_TTL),
         })
     }
-
-    fn detect_crypto(question: &str) -> Option<String> {
-        let q = question.to_lowercase();
-        if q.contains("bitcoin") || q.contains("btc") {
-            Some("BTCUSDT".to_string())
-        } else if q.contains("ethereum") || q.contains("eth") {
-            Some("ETHUSDT".to_string())
-        } else if q.contains("solana") || q.contains("sol") {
-            Some("SOLUSDT".to_string())
-        } else if q.contains("xrp") {
-            Some("XRPUSDT".to_string())
-        } else {
-            None
-        }
-    }
 }
 
 /// Start Binance WebSocket and feed into engine
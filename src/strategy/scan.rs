@@ -0,0 +1,465 @@
+//! Single scan iteration of the main trading loop
+//!
+//! Fetches markets, turns them into signals (several markets at a time),
+//! applies the dedup filter, ranks what is left, fits it to the capital
+//! budget and executes best-first. Side effects that belong to the binary (notifications, storage)
+//! are left to the caller, which gets everything back in a `ScanReport`.
+
+use crate::client::mock::{ClobClientTrait, GammaClientTrait};
+use crate::config::StrategyConfig;
+use crate::error::{BotError, Result};
+use crate::executor::Executor;
+use crate::fusion::{FusionEngine, SignalBuilder, SignalContribution, SignalSource};
+use crate::model::ProbabilityModel;
+use crate::monitor::MarketStateMonitor;
+use crate::risk::{RiskCheckResult, RiskManager};
+use crate::strategy::crypto_hf::CryptoHfStrategy;
+use crate::strategy::allocation::allocate;
+use crate::strategy::ranking::rank_opportunities;
+use crate::strategy::scan_filter::{FilterChain, MinLiquidity};
+use crate::strategy::signal_filter::SignalFilter;
+use crate::strategy::skip_cache::MarketSkipCache;
+use crate::strategy::SignalGenerator;
+use crate::types::{Market, Side, Signal, Trade};
+use async_trait::async_trait;
+use futures_util::stream::{self, StreamExt};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use tokio::sync::{Mutex, RwLock};
+use tracing::Instrument;
+
+/// Number of top markets fetched per scan
+const TOP_MARKETS_PER_SCAN: usize = 20;
+
+/// Produces a trading signal for a market
+#[async_trait]
+pub trait SignalProvider: Send + Sync {
+    async fn signal_for(&self, market: &Market) -> Option<Signal>;
+
+    /// Why `market` should not be considered at all, checked before
+    /// `signal_for` so excluded markets never reach the model
+    fn exclusion(&self, _market: &Market) -> Option<String> {
+        None
+    }
+
+    /// Why to abstain from `signal` despite its edge, checked before the
+    /// dedup filter so abstentions never count as trades
+    fn abstention(&self, _signal: &Signal) -> Option<String> {
+        None
+    }
+
+    /// The fusion source credited with signals on `market`, if any
+    fn source(&self, _market: &Market) -> Option<SignalSource> {
+        None
+    }
+}
+
+/// Signals from a probability model run through `SignalGenerator`
+pub struct ModelSignalProvider<'a> {
+    pub model: &'a dyn ProbabilityModel,
+    pub signal_gen: &'a SignalGenerator,
+}
+
+#[async_trait]
+impl SignalProvider for ModelSignalProvider<'_> {
+    async fn signal_for(&self, market: &Market) -> Option<Signal> {
+        let prediction = match self.model.predict(market).await {
+            Ok(p) => p,
+            Err(e) => {
+                tracing::debug!("Model failed for {}: {}", market.id, e);
+                return None;
+            }
+        };
+        self.signal_gen.generate(market, &prediction)
+    }
+
+    fn exclusion(&self, market: &Market) -> Option<String> {
+        self.signal_gen.exclusion(market)
+    }
+
+    fn abstention(&self, signal: &Signal) -> Option<String> {
+        self.signal_gen.abstention(signal)
+    }
+
+    fn source(&self, _market: &Market) -> Option<SignalSource> {
+        Some(SignalSource::MLPredictor)
+    }
+}
+
+/// Dependencies for one scan iteration
+pub struct ScanContext<'a, G, C> {
+    pub gamma: &'a G,
+    pub executor: &'a Executor<C>,
+    pub signals: &'a dyn SignalProvider,
+    pub risk_manager: &'a Mutex<RiskManager>,
+    pub signal_filter: &'a SignalFilter,
+    /// Markets any of these skip are never analyzed; see `scan_filters`
+    pub filters: &'a FilterChain,
+    /// Skips the model for markets unchanged since their last analysis
+    pub skip_cache: Option<&'a MarketSkipCache>,
+    /// Fed each market's Yes price so signals can see its momentum
+    pub market_state: Option<&'a MarketStateMonitor>,
+    /// Markets run through `signals` at once; execution stays sequential
+    pub concurrency: usize,
+    /// Live fusion signals, credited alongside the signal behind each trade
+    pub fusion: Option<&'a RwLock<FusionEngine>>,
+}
+
+/// Outcome of one scan iteration
+#[derive(Debug, Default)]
+pub struct ScanReport {
+    /// Markets fetched this scan
+    pub markets: Vec<Market>,
+    /// Set when the risk manager blocked trading for this scan
+    pub blocked: Option<String>,
+    /// Signals that passed the dedup filter, best-ranked first
+    pub signals: Vec<Signal>,
+    /// Trades executed (paper fills in dry-run mode)
+    pub trades: Vec<Trade>,
+    /// Signals whose execution failed
+    pub failures: Vec<(Signal, BotError)>,
+    /// Markets not run through the model because they had not changed
+    pub skipped_unchanged: usize,
+    /// Markets kept out by the allowlist, denylist or category filter
+    pub excluded: usize,
+    /// Signals not traded for lack of confidence, with the reason
+    pub abstentions: Vec<(Signal, String)>,
+    /// Fusion sources behind each trade, by trade id
+    pub sources: Vec<(String, Vec<SignalContribution>)>,
+}
+
+/// Filters `run_scan` applies from the strategy settings: the liquidity
+/// floor, lower for crypto up/down markets
+pub fn scan_filters(strategy: &StrategyConfig) -> FilterChain {
+    FilterChain::new()
+        .with_filter(MinLiquidity::new(strategy.min_liquidity).with_crypto_min(strategy.crypto_min_liquidity))
+}
+
+/// Run exactly one scan over the current markets
+///
+/// Returns an error only if the market list could not be fetched.
+pub async fn run_scan<G, C>(ctx: &ScanContext<'_, G, C>, balance: Decimal) -> Result<ScanReport>
+where
+    G: GammaClientTrait,
+    C: ClobClientTrait,
+{
+    let mut markets = ctx.gamma.get_top_markets(TOP_MARKETS_PER_SCAN).await?;
+
+    // Also fetch crypto markets (BTC/ETH Up/Down)
+    match ctx.gamma.get_crypto_markets().await {
+        Ok(crypto_markets) => {
+            tracing::info!("Found {} crypto markets", crypto_markets.len());
+            for market in crypto_markets {
+                if !markets.iter().any(|m| m.id == market.id) {
+                    markets.push(market);
+                }
+            }
+        }
+        Err(e) => {
+            tracing::warn!("Failed to fetch crypto markets: {}", e);
+        }
+    }
+
+    tracing::info!("Scanning {} markets...", markets.len());
+    let mut report = ScanReport::default();
+
+    // Check risk limits before trading
+    if let RiskCheckResult::Blocked { reason } = ctx.risk_manager.lock().await.can_trade() {
+        tracing::warn!("⚠️ Trading blocked: {}", reason);
+        report.blocked = Some(reason);
+        report.markets = markets;
+        return Ok(report);
+    }
+
+    let mut to_analyze = Vec::new();
+    for market in &markets {
+        if let Some(reason) = ctx.signals.exclusion(market) {
+            tracing::debug!("Skipping {} - {}", market.id, reason);
+            report.excluded += 1;
+            continue;
+        }
+
+        let is_crypto_market = CryptoHfStrategy::is_crypto_hf_market(market).is_some();
+
+        // Update volatility data for risk manager
+        if let Some(price) = market.yes_price() {
+            let mut rm = ctx.risk_manager.lock().await;
+            rm.update_volatility(&market.id, price);
+            rm.update_correlation(&market.id, price, chrono::Utc::now().timestamp());
+            drop(rm);
+            if let Some(market_state) = ctx.market_state {
+                market_state.update(&market.id, price, Some(market.volume), None, None);
+            }
+        }
+
+        if let Some(skip) = ctx.filters.check(market) {
+            tracing::debug!("Skipping {} - {}", market.id, skip);
+            continue;
+        }
+
+        if ctx.skip_cache.is_some_and(|cache| cache.is_unchanged(market)) {
+            tracing::debug!("Skipping {} - unchanged since last analysis", market.id);
+            report.skipped_unchanged += 1;
+            continue;
+        }
+        to_analyze.push((market, is_crypto_market));
+    }
+
+    // Model calls are the slow part, so up to `concurrency` markets are
+    // analyzed at once; results are put back in scan order
+    let mut analyzed: Vec<_> = stream::iter(to_analyze.into_iter().enumerate())
+        .map(|(i, (market, is_crypto_market))| async move {
+            (i, market, is_crypto_market, ctx.signals.signal_for(market).await)
+        })
+        .buffer_unordered(ctx.concurrency.max(1))
+        .collect()
+        .await;
+    analyzed.sort_by_key(|(i, ..)| *i);
+
+    let mut candidates = Vec::new();
+    for (_, market, is_crypto_market, signal) in analyzed {
+        if let Some(cache) = ctx.skip_cache {
+            cache.record(market);
+        }
+        let Some(signal) = signal else {
+            continue;
+        };
+        if let Some(reason) = ctx.signals.abstention(&signal) {
+            tracing::info!("Abstaining from {} - {}", market.id, reason);
+            report.abstentions.push((signal, reason));
+            continue;
+        }
+
+        // Apply signal filter with dynamic cooldown
+        // Crypto markets: 2 min cooldown (fast trading)
+        // Other markets: 15 min cooldown
+        if !ctx.signal_filter.deduplicator.can_trade_dynamic(&market.id, is_crypto_market) {
+            tracing::debug!("Skipping {} - cooldown active", market.id);
+            continue;
+        }
+
+        candidates.push((signal, market, None));
+    }
+
+    // Capital goes to the highest-scoring opportunities first, and the
+    // scan as a whole is scaled to fit what open positions leave of the
+    // exposure budget
+    let ranked = rank_opportunities(candidates);
+    let signals = ranked.iter().map(|r| r.signal.clone()).collect();
+    let open = match ctx.executor.clob.get_positions().await {
+        Ok(open) => open,
+        Err(e) => {
+            tracing::warn!("Failed to get positions, allocating as if flat: {}", e);
+            Vec::new()
+        }
+    };
+    let mut allocations = allocate(signals, balance, ctx.executor.risk_config(), &open);
+    for ranked in ranked {
+        let Some(index) = allocations.iter().position(|(s, _)| s.token_id == ranked.signal.token_id) else {
+            continue;
+        };
+        let (signal, _) = allocations.remove(index);
+        let market = ranked.market;
+
+        // Mark as traded to prevent duplicates
+        ctx.signal_filter.deduplicator.mark_traded(&market.id);
+
+        // Everything logged from here to the fill shares one correlation id,
+        // and so does the trade, so its log lines can be found from it
+        let correlation_id = uuid::Uuid::new_v4().to_string();
+        let span = tracing::info_span!(
+            "signal",
+            market_id = %market.id,
+            token_id = %signal.token_id,
+            correlation_id = %correlation_id,
+            score = %ranked.score,
+        );
+
+        span.in_scope(|| {
+            tracing::info!(
+                "Signal: {} {} | Model: {:.1}% vs Market: {:.1}% | Edge: {:.1}%",
+                side_label(signal.side),
+                market.question,
+                signal.model_probability * Decimal::ONE_HUNDRED,
+                signal.market_probability * Decimal::ONE_HUNDRED,
+                signal.edge * Decimal::ONE_HUNDRED
+            )
+        });
+
+        ctx.executor.register_market(market).await;
+        match ctx.executor.execute(&signal, balance).instrument(span.clone()).await {
+            Ok(Some(mut trade)) => {
+                trade.correlation_id = Some(correlation_id);
+                span.in_scope(|| tracing::info!("Trade executed: {}", trade.id));
+
+                // Update PnL tracking for risk management
+                let mut rm = ctx.risk_manager.lock().await;
+                rm.pnl_tracker.set_starting_balance(balance);
+                // Record trade fee as immediate cost (actual P&L comes later on close)
+                rm.record_trade(-trade.fee);
+                tracing::debug!(
+                    "Risk: Daily P&L = ${:.2}, remaining budget = ${:.2}",
+                    rm.daily_pnl(),
+                    rm.pnl_tracker.remaining_loss_budget().unwrap_or(Decimal::ZERO)
+                );
+
+                if let (Some(fusion), Some(source)) = (ctx.fusion, ctx.signals.source(market)) {
+                    let sources = fusion.read().await.contributions_with(called(source, &signal));
+                    report.sources.push((trade.id.clone(), sources));
+                }
+                report.trades.push(trade);
+            }
+            Ok(None) => {}
+            Err(e) => {
+                span.in_scope(|| e.log("Execution failed"));
+                report.failures.push((signal.clone(), e));
+            }
+        }
+        report.signals.push(signal);
+    }
+
+    // Orders left resting by this or an earlier scan may have filled since
+    if let Err(e) = ctx.executor.refresh_resting_orders().await {
+        tracing::warn!("Cannot refresh resting orders: {}", e);
+    }
+    for trade in ctx.executor.take_late_fills().await {
+        ctx.risk_manager.lock().await.record_trade(-trade.fee);
+        report.trades.push(trade);
+    }
+
+    tracing::info!(
+        "Scan complete: {} signals, {} trades, {} abstained, {} markets skipped as unchanged",
+        report.signals.len(),
+        report.trades.len(),
+        report.abstentions.len(),
+        report.skipped_unchanged
+    );
+    report.markets = markets;
+    Ok(report)
+}
+
+/// Optional bounds on how long the main loop runs
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RunLimits {
+    /// Stop after this many loop passes
+    pub max_iterations: Option<u32>,
+    /// Stop once this much time has passed
+    pub duration: Option<std::time::Duration>,
+}
+
+impl RunLimits {
+    /// Why the run should stop after `iterations` passes, if it should
+    pub fn reached(&self, iterations: u32, elapsed: std::time::Duration) -> Option<String> {
+        if let Some(max) = self.max_iterations {
+            if iterations >= max {
+                return Some(format!("Reached {} iterations", max));
+            }
+        }
+        if let Some(limit) = self.duration {
+            if elapsed >= limit {
+                return Some(format!("Run duration of {}s elapsed", limit.as_secs()));
+            }
+        }
+        None
+    }
+}
+
+/// One pass of the main loop
+#[async_trait(?Send)]
+pub trait LoopPass {
+    /// Run a pass, returning how long to wait before the next one
+    async fn run_pass(&mut self) -> Result<std::time::Duration>;
+}
+
+/// How a `run_loop` ended
+#[derive(Debug, Clone)]
+pub struct LoopSummary {
+    pub iterations: u32,
+    pub stop_reason: String,
+    pub elapsed: std::time::Duration,
+}
+
+/// Run `pass` until `limits` is reached or `shutdown` resolves
+///
+/// Every pass counts toward `max_iterations`, including ones that bail out
+/// early on a failed fetch, and the limits are checked after each one.
+pub async fn run_loop<P, F>(pass: &mut P, limits: RunLimits, shutdown: F) -> Result<LoopSummary>
+where
+    P: LoopPass + ?Sized,
+    F: std::future::Future,
+{
+    tokio::pin!(shutdown);
+    let started = tokio::time::Instant::now();
+    let deadline = limits.duration.map(|d| started + d);
+    let mut iterations = 0u32;
+
+    let stop_reason = loop {
+        let wait = pass.run_pass().await?;
+        iterations += 1;
+        if let Some(reason) = limits.reached(iterations, started.elapsed()) {
+            break reason;
+        }
+        if wait_or_shutdown(&mut shutdown, wait, deadline).await {
+            break match deadline {
+                Some(d) if tokio::time::Instant::now() >= d => "Run duration elapsed".to_string(),
+                _ => "Ctrl-C".to_string(),
+            };
+        }
+    };
+
+    Ok(LoopSummary { iterations, stop_reason, elapsed: started.elapsed() })
+}
+
+/// Sleep for `duration`, returning early with `true` if shutdown was requested
+/// or the run deadline passed
+async fn wait_or_shutdown<F>(
+    shutdown: &mut F,
+    duration: std::time::Duration,
+    deadline: Option<tokio::time::Instant>,
+) -> bool
+where
+    F: std::future::Future + Unpin,
+{
+    let wake = tokio::time::Instant::now() + duration;
+    let (until, stop) = match deadline {
+        Some(d) if d <= wake => (d, true),
+        _ => (wake, false),
+    };
+    tokio::select! {
+        _ = tokio::time::sleep_until(until) => stop,
+        _ = shutdown => true,
+    }
+}
+
+impl ScanReport {
+    /// Look up a scanned market by id
+    pub fn market(&self, market_id: &str) -> Option<&Market> {
+        self.markets.iter().find(|m| m.id == market_id)
+    }
+
+    /// Fusion sources behind a trade of this scan
+    pub fn sources_for(&self, trade_id: &str) -> Option<&[SignalContribution]> {
+        self.sources.iter().find(|(id, _)| id == trade_id).map(|(_, sources)| sources.as_slice())
+    }
+}
+
+/// `signal` as a fusion signal from `source`
+///
+/// Directions are calls on the traded token: positive means it pays out.
+fn called(source: SignalSource, signal: &Signal) -> crate::fusion::Signal {
+    let direction = match signal.side {
+        Side::Buy => 1.0,
+        Side::Sell => -1.0,
+    };
+    SignalBuilder::new(source)
+        .direction(direction)
+        .confidence(signal.confidence.to_f64().unwrap_or(0.5))
+        .build()
+}
+
+fn side_label(side: Side) -> &'static str {
+    match side {
+        Side::Buy => "BUY",
+        Side::Sell => "SELL",
+    }
+}
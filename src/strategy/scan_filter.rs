@@ -0,0 +1,366 @@
+//! Composable market filters for the scan loop
+//!
+//! Each `ScanFilter` either keeps a market or gives the reason to skip it.
+//! A `FilterChain` runs its filters in order and stops at the first skip,
+//! so cheap checks should go first and the recorded reason is always the
+//! first one that applied.
+
+use crate::data::SymbolMapper;
+use crate::strategy::crypto_hf::CryptoHfStrategy;
+use crate::types::Market;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use std::collections::HashSet;
+use std::sync::{Arc, RwLock};
+
+/// A predicate deciding whether the scan looks at a market
+pub trait ScanFilter: Send + Sync {
+    /// Short identifier used in skip reasons, e.g. "min_liquidity"
+    fn name(&self) -> &'static str;
+
+    /// Why `market` should be skipped, `None` to keep it
+    fn skip_reason(&self, market: &Market) -> Option<String>;
+}
+
+/// Which filter skipped a market, and why
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkipReason {
+    pub filter: &'static str,
+    pub reason: String,
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.filter, self.reason)
+    }
+}
+
+/// Markets split by a `FilterChain`
+#[derive(Debug, Default)]
+pub struct FilterOutcome<'a> {
+    /// Markets every filter kept, in input order
+    pub passed: Vec<&'a Market>,
+    /// Skipped markets by id, in input order
+    pub skipped: Vec<(String, SkipReason)>,
+}
+
+/// Filters run in order; a market passes only if every filter keeps it
+#[derive(Default)]
+pub struct FilterChain {
+    filters: Vec<Box<dyn ScanFilter>>,
+}
+
+impl FilterChain {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append `filter` to the end of the chain
+    pub fn with_filter(mut self, filter: impl ScanFilter + 'static) -> Self {
+        self.filters.push(Box::new(filter));
+        self
+    }
+
+    /// Names of the filters, in the order they run
+    pub fn names(&self) -> Vec<&'static str> {
+        self.filters.iter().map(|f| f.name()).collect()
+    }
+
+    /// The first filter that skips `market`, if any
+    pub fn check(&self, market: &Market) -> Option<SkipReason> {
+        self.filters.iter().find_map(|filter| {
+            filter.skip_reason(market).map(|reason| SkipReason { filter: filter.name(), reason })
+        })
+    }
+
+    /// Run every market through the chain
+    pub fn apply<'a>(&self, markets: &'a [Market]) -> FilterOutcome<'a> {
+        let mut outcome = FilterOutcome::default();
+        for market in markets {
+            match self.check(market) {
+                Some(skip) => {
+                    tracing::debug!("Skipping {} - {}", market.id, skip);
+                    outcome.skipped.push((market.id.clone(), skip));
+                }
+                None => outcome.passed.push(market),
+            }
+        }
+        outcome
+    }
+}
+
+/// Skips markets with less liquidity than a floor
+#[derive(Debug, Clone)]
+pub struct MinLiquidity {
+    min: Decimal,
+    crypto_min: Option<Decimal>,
+}
+
+impl MinLiquidity {
+    pub fn new(min: Decimal) -> Self {
+        Self { min, crypto_min: None }
+    }
+
+    /// Use a different floor for crypto up/down markets
+    pub fn with_crypto_min(mut self, min: Decimal) -> Self {
+        self.crypto_min = Some(min);
+        self
+    }
+}
+
+impl ScanFilter for MinLiquidity {
+    fn name(&self) -> &'static str {
+        "min_liquidity"
+    }
+
+    fn skip_reason(&self, market: &Market) -> Option<String> {
+        let min = match self.crypto_min {
+            Some(crypto_min) if CryptoHfStrategy::is_crypto_hf_market(market).is_some() => crypto_min,
+            _ => self.min,
+        };
+        (market.liquidity < min).then(|| format!("liquidity ${} below ${}", market.liquidity, min))
+    }
+}
+
+/// Skips markets that do not resolve within a window from now
+///
+/// A market without an end date can't be placed in the window and is
+/// skipped too.
+#[derive(Debug, Clone)]
+pub struct SettlementWindow {
+    min_minutes: u64,
+    max_minutes: u64,
+    now: Option<DateTime<Utc>>,
+}
+
+impl SettlementWindow {
+    /// Keep markets resolving within `max_minutes`
+    pub fn new(max_minutes: u64) -> Self {
+        Self { min_minutes: 0, max_minutes, now: None }
+    }
+
+    /// Also skip markets resolving sooner than `min_minutes`
+    pub fn with_min_minutes(mut self, min_minutes: u64) -> Self {
+        self.min_minutes = min_minutes;
+        self
+    }
+
+    /// Measure from a fixed time instead of the clock
+    pub fn with_now(mut self, now: DateTime<Utc>) -> Self {
+        self.now = Some(now);
+        self
+    }
+}
+
+impl ScanFilter for SettlementWindow {
+    fn name(&self) -> &'static str {
+        "settlement_window"
+    }
+
+    fn skip_reason(&self, market: &Market) -> Option<String> {
+        let Some(end_date) = market.end_date else {
+            return Some("no end date".to_string());
+        };
+        let remaining = end_date - self.now.unwrap_or_else(Utc::now);
+        if remaining.num_seconds() <= 0 {
+            return Some("already past its end date".to_string());
+        }
+        let minutes = remaining.num_minutes();
+        if minutes < self.min_minutes as i64 || remaining.num_seconds() > self.max_minutes as i64 * 60 {
+            return Some(format!(
+                "resolves in {} minutes, outside {}-{}",
+                minutes, self.min_minutes, self.max_minutes
+            ));
+        }
+        None
+    }
+}
+
+/// Skips markets already traded
+///
+/// Clones share the traded set, so the trading loop can keep one handle
+/// to mark trades while another sits in the chain.
+#[derive(Debug, Clone, Default)]
+pub struct NotAlreadyTraded {
+    traded: Arc<RwLock<HashSet<String>>>,
+}
+
+impl NotAlreadyTraded {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn mark_traded(&self, market_id: &str) {
+        self.traded.write().unwrap().insert(market_id.to_string());
+    }
+
+    pub fn is_traded(&self, market_id: &str) -> bool {
+        self.traded.read().unwrap().contains(market_id)
+    }
+}
+
+impl ScanFilter for NotAlreadyTraded {
+    fn name(&self) -> &'static str {
+        "not_already_traded"
+    }
+
+    fn skip_reason(&self, market: &Market) -> Option<String> {
+        self.is_traded(&market.id).then(|| "already traded".to_string())
+    }
+}
+
+/// Keeps only listed markets
+///
+/// Entries match like `strategy.allowlist`: a market whose id contains the
+/// entry or that carries a tag equal to it, ignoring case. An empty list
+/// keeps everything.
+#[derive(Debug, Clone, Default)]
+pub struct Allowlist {
+    entries: Vec<String>,
+}
+
+impl Allowlist {
+    pub fn new<I, S>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self { entries: entries.into_iter().map(Into::into).collect() }
+    }
+}
+
+impl ScanFilter for Allowlist {
+    fn name(&self) -> &'static str {
+        "allowlist"
+    }
+
+    fn skip_reason(&self, market: &Market) -> Option<String> {
+        let id = market.id.to_lowercase();
+        let listed = self.entries.is_empty()
+            || self.entries.iter().any(|entry| {
+                id.contains(&entry.to_lowercase()) || market.tags.iter().any(|tag| tag.eq_ignore_ascii_case(entry))
+            });
+        (!listed).then(|| "not on the allowlist".to_string())
+    }
+}
+
+/// Skips markets whose question maps to no exchange symbol
+#[derive(Debug, Clone, Default)]
+pub struct HasMappableSymbol {
+    symbols: SymbolMapper,
+}
+
+impl HasMappableSymbol {
+    pub fn new(symbols: SymbolMapper) -> Self {
+        Self { symbols }
+    }
+}
+
+impl ScanFilter for HasMappableSymbol {
+    fn name(&self) -> &'static str {
+        "has_mappable_symbol"
+    }
+
+    fn skip_reason(&self, market: &Market) -> Option<String> {
+        match self.symbols.symbol_for(&market.question) {
+            Some(_) => None,
+            None => Some("no unambiguous exchange symbol".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Outcome;
+    use chrono::Duration;
+    use rust_decimal_macros::dec;
+
+    fn market(id: &str, question: &str, liquidity: Decimal, ends_in_minutes: Option<i64>, now: DateTime<Utc>) -> Market {
+        Market {
+            id: id.to_string(),
+            question: question.to_string(),
+            description: None,
+            end_date: ends_in_minutes.map(|m| now + Duration::minutes(m)),
+            volume: dec!(1000),
+            liquidity,
+            outcomes: vec![
+                Outcome { token_id: format!("{}-yes", id), outcome: "Yes".to_string(), price: dec!(0.5) },
+                Outcome { token_id: format!("{}-no", id), outcome: "No".to_string(), price: dec!(0.5) },
+            ],
+            active: true,
+            closed: false,
+            category: None,
+            tags: vec!["crypto".to_string()],
+            tick_size: None,
+            min_size: None,
+        }
+    }
+
+    #[test]
+    fn test_chain_records_first_skip_reason() {
+        let now = Utc::now();
+        let traded = NotAlreadyTraded::new();
+        let chain = FilterChain::new()
+            .with_filter(traded.clone())
+            .with_filter(SettlementWindow::new(30).with_now(now))
+            .with_filter(MinLiquidity::new(dec!(5000)))
+            .with_filter(HasMappableSymbol::new(SymbolMapper::default()));
+        assert_eq!(
+            chain.names(),
+            vec!["not_already_traded", "settlement_window", "min_liquidity", "has_mappable_symbol"]
+        );
+
+        let markets = vec![
+            market("ok", "Bitcoin Up or Down", dec!(8000), Some(20), now),
+            market("traded", "Bitcoin Up or Down", dec!(8000), Some(20), now),
+            market("no-end", "Ethereum Up or Down", dec!(8000), None, now),
+            market("late", "Ethereum Up or Down", dec!(8000), Some(90), now),
+            // Thin and unmappable: liquidity runs first, so it is the reason
+            market("thin", "Will it rain?", dec!(100), Some(10), now),
+            market("both", "Bitcoin vs Ethereum", dec!(8000), Some(10), now),
+        ];
+        traded.mark_traded("traded");
+
+        let outcome = chain.apply(&markets);
+        let passed: Vec<&str> = outcome.passed.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(passed, vec!["ok"]);
+
+        let skipped: Vec<(&str, &str)> = outcome.skipped.iter().map(|(id, s)| (id.as_str(), s.filter)).collect();
+        assert_eq!(
+            skipped,
+            vec![
+                ("traded", "not_already_traded"),
+                ("no-end", "settlement_window"),
+                ("late", "settlement_window"),
+                ("thin", "min_liquidity"),
+                ("both", "has_mappable_symbol"),
+            ]
+        );
+        assert_eq!(outcome.skipped[1].1.reason, "no end date");
+        assert_eq!(outcome.skipped[3].1.to_string(), "min_liquidity: liquidity $100 below $5000");
+    }
+
+    #[test]
+    fn test_individual_filters() {
+        let now = Utc::now();
+        let btc = market("btc-hourly", "Bitcoin Up or Down - 3PM ET", dec!(2000), Some(20), now);
+
+        assert!(MinLiquidity::new(dec!(10000)).skip_reason(&btc).is_some());
+        assert!(MinLiquidity::new(dec!(10000)).with_crypto_min(dec!(1000)).skip_reason(&btc).is_none());
+
+        assert!(SettlementWindow::new(30).with_now(now).skip_reason(&btc).is_none());
+        assert!(SettlementWindow::new(30).with_min_minutes(25).with_now(now).skip_reason(&btc).is_some());
+        assert_eq!(
+            SettlementWindow::new(30).with_now(now + Duration::hours(1)).skip_reason(&btc).as_deref(),
+            Some("already past its end date")
+        );
+
+        assert!(Allowlist::new(Vec::<String>::new()).skip_reason(&btc).is_none());
+        assert!(Allowlist::new(["BTC"]).skip_reason(&btc).is_none());
+        assert!(Allowlist::new(["Crypto"]).skip_reason(&btc).is_none());
+        assert_eq!(Allowlist::new(["eth"]).skip_reason(&btc).as_deref(), Some("not on the allowlist"));
+
+        assert!(FilterChain::new().check(&btc).is_none());
+    }
+}
@@ -0,0 +1,107 @@
+//! Skipping model inference on markets that have not moved
+//!
+//! Running the model (often an LLM call) on every market every scan is the
+//! bot's main running cost, and most markets barely change between scans.
+//! `MarketSkipCache` remembers each market's price and volume when it was
+//! last analyzed so the scan loop can skip it until it moves or the cached
+//! analysis gets old.
+
+use crate::types::Market;
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Re-analyze a market at least this often even if it has not moved
+const DEFAULT_MAX_AGE: Duration = Duration::from_secs(15 * 60);
+/// Yes-price change, in probability points, that counts as a move
+const DEFAULT_PRICE_EPSILON: Decimal = dec!(0.005);
+/// Relative volume change that counts as a move
+const DEFAULT_VOLUME_EPSILON: Decimal = dec!(0.01);
+
+/// Market state when it was last analyzed
+#[derive(Debug, Clone, Copy)]
+struct Analyzed {
+    price: Decimal,
+    volume: Decimal,
+    at: Instant,
+}
+
+/// Last analyzed price and volume per market
+pub struct MarketSkipCache {
+    entries: Mutex<HashMap<String, Analyzed>>,
+    max_age: Duration,
+    price_epsilon: Decimal,
+    volume_epsilon: Decimal,
+}
+
+impl MarketSkipCache {
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+            max_age: DEFAULT_MAX_AGE,
+            price_epsilon: DEFAULT_PRICE_EPSILON,
+            volume_epsilon: DEFAULT_VOLUME_EPSILON,
+        }
+    }
+
+    /// How long an analysis stays valid for an unchanged market
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    /// Smallest price change (absolute) and volume change (relative) that
+    /// make a market worth analyzing again
+    pub fn with_epsilons(mut self, price: Decimal, volume: Decimal) -> Self {
+        self.price_epsilon = price;
+        self.volume_epsilon = volume;
+        self
+    }
+
+    /// Whether the market is unchanged since a recent analysis
+    pub fn is_unchanged(&self, market: &Market) -> bool {
+        let entries = self.entries.lock().unwrap();
+        let Some(last) = entries.get(&market.id) else {
+            return false;
+        };
+        if last.at.elapsed() >= self.max_age {
+            return false;
+        }
+
+        let price = market.yes_price().unwrap_or(Decimal::ZERO);
+        let volume_moved = if last.volume.is_zero() {
+            !market.volume.is_zero()
+        } else {
+            ((market.volume - last.volume) / last.volume).abs() > self.volume_epsilon
+        };
+        (price - last.price).abs() <= self.price_epsilon && !volume_moved
+    }
+
+    /// Remember the market's state as of an analysis just run
+    pub fn record(&self, market: &Market) {
+        let analyzed = Analyzed {
+            price: market.yes_price().unwrap_or(Decimal::ZERO),
+            volume: market.volume,
+            at: Instant::now(),
+        };
+        self.entries.lock().unwrap().insert(market.id.clone(), analyzed);
+    }
+
+    /// Force the next scan to analyze every market
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    /// Force the next scan to analyze one market
+    pub fn invalidate(&self, market_id: &str) {
+        self.entries.lock().unwrap().remove(market_id);
+    }
+}
+
+impl Default for MarketSkipCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
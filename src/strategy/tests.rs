@@ -15,9 +15,24 @@ mod tests {
             min_confidence: dec!(0.6),
             kelly_fraction: dec!(0.25),
             scan_interval_secs: 60,
+            scan_concurrency: 4,
             model_update_interval_secs: 3600,
             compound_enabled: false,
             compound_sqrt_scaling: false,
+            fee_rate: Decimal::ZERO,
+            slippage_estimate: Decimal::ZERO,
+            max_price_deviation: dec!(0.05),
+            min_liquidity: dec!(10000),
+            crypto_min_liquidity: dec!(1000),
+            min_minutes_to_resolution: 0,
+            max_minutes_to_resolution: None,
+            size_decay_minutes: 0,
+            outcome_matching: Default::default(),
+            allowlist: Vec::new(),
+            denylist: Vec::new(),
+            categories: Vec::new(),
+            anti_chase: Default::default(),
+            abstain_below_confidence: None,
         };
         
         let risk = RiskConfig {
@@ -26,6 +41,7 @@ mod tests {
             max_daily_loss_pct: dec!(0.1),
             min_balance_reserve: dec!(100),
             max_open_positions: 10,
+            ..RiskConfig::default()
         };
         
         (strategy, risk)
@@ -53,6 +69,10 @@ mod tests {
                     price: Decimal::ONE - yes_price,
                 },
             ],
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         }
     }
 
@@ -166,6 +186,10 @@ mod tests {
             outcomes: vec![], // No outcomes
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         };
         let prediction = Prediction {
             probability: dec!(0.70),
@@ -334,4 +358,565 @@ mod tests {
         assert!(signal.is_some());
         assert_eq!(signal.unwrap().side, Side::Sell);
     }
+
+    #[test]
+    fn test_costs_reject_marginal_edge() {
+        let (mut strategy_config, risk_config) = make_test_config();
+        strategy_config.fee_rate = dec!(0.02);
+        strategy_config.slippage_estimate = dec!(0.01);
+        let market = make_test_market(dec!(0.40));
+        // 7% raw edge clears the 5% threshold...
+        let prediction = Prediction {
+            probability: dec!(0.47),
+            confidence: dec!(0.80),
+            reasoning: "Test".to_string(),
+        };
+
+        let (free_config, _) = make_test_config();
+        let free = SignalGenerator::new(free_config, risk_config.clone());
+        assert!(free.generate(&market, &prediction).is_some());
+
+        // ...but 0.40 * 2 * (2% fee + 1% slippage) = 2.4% of it goes to costs
+        let signal_gen = SignalGenerator::new(strategy_config, risk_config);
+        assert!(signal_gen.generate(&market, &prediction).is_none());
+    }
+
+    #[test]
+    fn test_net_edge_stored_alongside_raw_edge() {
+        let (mut strategy_config, risk_config) = make_test_config();
+        strategy_config.fee_rate = dec!(0.02);
+        strategy_config.slippage_estimate = dec!(0.01);
+        let signal_gen = SignalGenerator::new(strategy_config, risk_config);
+
+        let buy = signal_gen
+            .generate(&make_test_market(dec!(0.40)), &Prediction {
+                probability: dec!(0.55),
+                confidence: dec!(0.80),
+                reasoning: "Test".to_string(),
+            })
+            .unwrap();
+        assert_eq!(buy.edge, dec!(0.15));
+        assert_eq!(buy.net_edge, dec!(0.126));
+
+        // Selling Yes at 0.70 costs like buying No at 0.30
+        let sell = signal_gen
+            .generate(&make_test_market(dec!(0.70)), &Prediction {
+                probability: dec!(0.55),
+                confidence: dec!(0.80),
+                reasoning: "Test".to_string(),
+            })
+            .unwrap();
+        assert_eq!(sell.edge, dec!(-0.15));
+        assert_eq!(sell.net_edge, dec!(-0.132));
+    }
+
+    #[test]
+    fn test_slippage_from_liquidity_monitor() {
+        use crate::risk::{LiquidityConfig, LiquidityMonitor, OrderBookLevel, OrderBookSnapshot};
+        use std::sync::{Arc, RwLock};
+
+        let (strategy_config, risk_config) = make_test_config();
+        let mut monitor = LiquidityMonitor::new(LiquidityConfig::default());
+        // Only 100 shares at the touch, the rest well above it
+        monitor.update_order_book(OrderBookSnapshot {
+            market_id: "test-market-1".to_string(),
+            timestamp: chrono::Utc::now(),
+            bids: vec![OrderBookLevel { price: dec!(0.39), size: dec!(2000) }],
+            asks: vec![
+                OrderBookLevel { price: dec!(0.40), size: dec!(100) },
+                OrderBookLevel { price: dec!(0.50), size: dec!(2000) },
+            ],
+            mid_price: dec!(0.395),
+        });
+        let signal_gen = SignalGenerator::new(strategy_config, risk_config)
+            .with_liquidity(Arc::new(RwLock::new(monitor)));
+
+        let market = make_test_market(dec!(0.40));
+        let prediction = Prediction {
+            probability: dec!(0.55),
+            confidence: dec!(0.70),
+            reasoning: "Test".to_string(),
+        };
+
+        // Small bankroll fills at the touch
+        signal_gen.set_bankroll(dec!(500));
+        let signal = signal_gen.generate(&market, &prediction).unwrap();
+        assert_eq!(signal.net_edge, signal.edge);
+
+        // A large one walks the book far enough to eat the edge
+        signal_gen.set_bankroll(dec!(10000));
+        assert!(signal_gen.generate(&market, &prediction).is_none());
+    }
+
+    fn make_three_outcome_market(prices: [Decimal; 3]) -> Market {
+        let names = ["Alice", "Bob", "Carol"];
+        Market {
+            outcomes: names
+                .iter()
+                .zip(prices)
+                .map(|(name, price)| Outcome {
+                    outcome: name.to_string(),
+                    token_id: format!("token-{}", name.to_lowercase()),
+                    price,
+                })
+                .collect(),
+            ..make_test_market(dec!(0.5))
+        }
+    }
+
+    fn outcome_probabilities(probs: [(&str, Decimal); 3]) -> std::collections::HashMap<String, Decimal> {
+        probs.iter().map(|(name, p)| (name.to_string(), *p)).collect()
+    }
+
+    #[test]
+    fn test_multi_outcome_buys_most_mispriced() {
+        let (strategy_config, risk_config) = make_test_config();
+        let signal_gen = SignalGenerator::new(strategy_config, risk_config);
+        let market = make_three_outcome_market([dec!(0.50), dec!(0.30), dec!(0.20)]);
+
+        // Bob is the model's value pick; Alice is overpriced
+        let probs = outcome_probabilities([("alice", dec!(0.40)), ("BOB", dec!(0.45)), ("Carol", dec!(0.15))]);
+        let signal = signal_gen.generate_for_outcomes(&market, &probs, dec!(0.80)).unwrap();
+        assert_eq!(signal.token_id, "token-bob");
+        assert_eq!(signal.side, Side::Buy);
+        assert_eq!(signal.market_probability, dec!(0.30));
+        assert_eq!(signal.edge, dec!(0.15));
+        assert!(signal.suggested_size > Decimal::ZERO);
+
+        // Binary-only generation has nothing to say about this market
+        let prediction = Prediction { probability: dec!(0.45), confidence: dec!(0.80), reasoning: String::new() };
+        assert!(signal_gen.generate(&market, &prediction).is_none());
+    }
+
+    #[test]
+    fn test_multi_outcome_normalizes_probabilities() {
+        let (strategy_config, risk_config) = make_test_config();
+        let signal_gen = SignalGenerator::new(strategy_config, risk_config);
+
+        // Model output sums to 1.25; normalized Carol is 0.40 against a 0.25 price
+        let market = make_three_outcome_market([dec!(0.45), dec!(0.30), dec!(0.25)]);
+        let probs = outcome_probabilities([("Alice", dec!(0.45)), ("Bob", dec!(0.30)), ("Carol", dec!(0.50))]);
+        let signal = signal_gen.generate_for_outcomes(&market, &probs, dec!(0.80)).unwrap();
+        assert_eq!(signal.token_id, "token-carol");
+        assert_eq!(signal.model_probability, dec!(0.40));
+
+        // Overround market: the 0.35 edge against the normalized 0.25 is only
+        // 0.02 at the 0.38 actually paid, below min_edge
+        let market = make_three_outcome_market([dec!(0.60), dec!(0.40), dec!(0.38)]);
+        let probs = outcome_probabilities([("Alice", dec!(0.35)), ("Bob", dec!(0.25)), ("Carol", dec!(0.40))]);
+        assert!(signal_gen.generate_for_outcomes(&market, &probs, dec!(0.80)).is_none());
+    }
+
+    #[test]
+    fn test_generate_rejects_inconsistent_prices() {
+        let (strategy_config, risk_config) = make_test_config();
+        let signal_gen = SignalGenerator::new(strategy_config, risk_config);
+        let prediction = Prediction { probability: dec!(0.80), confidence: dec!(0.80), reasoning: String::new() };
+
+        // Yes 0.60 / No 0.50 sums to 1.1: the 0.20 edge on Yes is not trusted
+        let mut market = make_test_market(dec!(0.60));
+        market.outcomes[1].price = dec!(0.50);
+        assert!(signal_gen.generate(&market, &prediction).is_none());
+
+        // Once normalized, Yes is 0.5454.. and the edge is measured against that
+        market.normalize_prices();
+        let signal = signal_gen.generate(&market, &prediction).unwrap();
+        assert_eq!(signal.market_probability, dec!(0.60) / dec!(1.1));
+        assert_eq!(signal.edge, dec!(0.80) - dec!(0.60) / dec!(1.1));
+    }
+
+    fn ranking_signal(market_id: &str, net_edge: Decimal) -> crate::types::Signal {
+        crate::types::Signal {
+            market_id: market_id.to_string(),
+            token_id: format!("{}-yes", market_id),
+            side: Side::Buy,
+            model_probability: dec!(0.50) + net_edge,
+            market_probability: dec!(0.50),
+            edge: net_edge,
+            net_edge,
+            confidence: dec!(0.80),
+            suggested_size: dec!(0.05),
+            timestamp: chrono::Utc::now(),
+            max_staleness: None,
+        }
+    }
+
+    fn liquidity(market_id: &str, score: Decimal, tradeable: bool) -> crate::risk::LiquidityAssessment {
+        crate::risk::LiquidityAssessment {
+            market_id: market_id.to_string(),
+            timestamp: chrono::Utc::now(),
+            score,
+            spread: dec!(0.02),
+            total_depth: dec!(10000),
+            slippage_100: dec!(0.001),
+            slippage_1000: dec!(0.01),
+            imbalance: Decimal::ZERO,
+            tradeable,
+            size_multiplier: Decimal::ONE,
+            alerts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_illiquid_high_edge_ranks_below_liquid_lower_edge() {
+        let thin = Market { id: "thin".to_string(), ..make_test_market(dec!(0.5)) };
+        let deep = Market { id: "deep".to_string(), ..make_test_market(dec!(0.5)) };
+        let thin_book = liquidity("thin", dec!(20), true);
+        let deep_book = liquidity("deep", dec!(90), true);
+
+        let ranked = rank_opportunities(vec![
+            (ranking_signal("thin", dec!(0.15)), &thin, Some(&thin_book)),
+            (ranking_signal("deep", dec!(0.12)), &deep, Some(&deep_book)),
+        ]);
+        assert_eq!(ranked[0].signal.market_id, "deep");
+        assert_eq!(ranked[0].market.id, "deep");
+        assert!(ranked[0].score > ranked[1].score);
+
+        // Without book assessments, reported market liquidity decides
+        let thin = Market { liquidity: dec!(5000), ..thin };
+        let deep = Market { liquidity: dec!(80000), ..deep };
+        let ranked = rank_opportunities(vec![
+            (ranking_signal("thin", dec!(0.15)), &thin, None),
+            (ranking_signal("deep", dec!(0.12)), &deep, None),
+        ]);
+        assert_eq!(ranked[0].signal.market_id, "deep");
+    }
+
+    #[test]
+    fn test_score_opportunity_factors() {
+        let market = make_test_market(dec!(0.5));
+        let signal = ranking_signal("m", dec!(0.10));
+
+        // Untradeable books score zero however large the edge
+        let closed = liquidity("m", dec!(90), false);
+        assert_eq!(score_opportunity(&signal, &market, Some(&closed)), Decimal::ZERO);
+
+        // Capital that frees up sooner is worth more
+        let book = liquidity("m", dec!(100), true);
+        let soon = Market { end_date: Some(chrono::Utc::now() + chrono::Duration::days(1)), ..market.clone() };
+        let later = Market { end_date: Some(chrono::Utc::now() + chrono::Duration::days(90)), ..market.clone() };
+        assert!(score_opportunity(&signal, &soon, Some(&book)) > score_opportunity(&signal, &later, Some(&book)));
+
+        // Already past its end date: full edge * confidence
+        let ended = Market { end_date: Some(chrono::Utc::now() - chrono::Duration::days(1)), ..market };
+        assert_eq!(score_opportunity(&signal, &ended, Some(&book)), dec!(0.08));
+
+        // Sell edges are negative but score the same
+        let sell = crate::types::Signal { side: Side::Sell, edge: dec!(-0.10), net_edge: dec!(-0.10), ..signal.clone() };
+        assert_eq!(
+            score_opportunity(&sell, &ended, Some(&book)),
+            score_opportunity(&signal, &ended, Some(&book))
+        );
+    }
+
+    #[test]
+    fn test_allocate_scales_signals_to_exposure_cap() {
+        let risk = RiskConfig { max_exposure_pct: dec!(0.10), ..RiskConfig::default() };
+        let signals = ["a", "b", "c"]
+            .iter()
+            .map(|id| crate::types::Signal { suggested_size: dec!(0.05), ..ranking_signal(id, dec!(0.10)) })
+            .collect();
+
+        let allocations = allocate(signals, dec!(1000), &risk, &[]);
+
+        // 15% requested under a 10% cap: each scaled by 2/3
+        assert_eq!(allocations.len(), 3);
+        for (signal, size_usd) in &allocations {
+            assert_eq!(signal.suggested_size.round_dp(10), (dec!(0.10) / dec!(3)).round_dp(10));
+            assert_eq!(size_usd.round_dp(6), (dec!(100) / dec!(3)).round_dp(6));
+        }
+        let total: Decimal = allocations.iter().map(|(_, usd)| *usd).sum();
+        assert_eq!(total.round_dp(6), dec!(100));
+    }
+
+    #[test]
+    fn test_allocate_respects_position_limit_and_leaves_small_scans_alone() {
+        let risk = RiskConfig { max_exposure_pct: dec!(0.50), max_open_positions: 2, ..RiskConfig::default() };
+        let signals = ["best", "second", "third"]
+            .iter()
+            .map(|id| crate::types::Signal { suggested_size: dec!(0.05), ..ranking_signal(id, dec!(0.10)) })
+            .collect();
+
+        let allocations = allocate(signals, dec!(1000), &risk, &[]);
+
+        // Only the first two are kept, and 10% fits under the cap unscaled
+        let ids: Vec<_> = allocations.iter().map(|(s, _)| s.market_id.as_str()).collect();
+        assert_eq!(ids, vec!["best", "second"]);
+        assert!(allocations.iter().all(|(s, usd)| s.suggested_size == dec!(0.05) && *usd == dec!(50)));
+    }
+
+    #[test]
+    fn test_allocate_counts_open_positions_against_limits() {
+        let risk = RiskConfig { max_exposure_pct: dec!(0.20), max_open_positions: 3, ..RiskConfig::default() };
+        let held = |id: &str, value: Decimal| crate::types::Position {
+            token_id: format!("{}-yes", id),
+            market_id: id.to_string(),
+            side: Side::Buy,
+            size: value * dec!(2),
+            avg_entry_price: dec!(0.50),
+            current_price: dec!(0.50),
+            unrealized_pnl: Decimal::ZERO,
+        };
+        // Two slots and $150 of the $200 budget already taken
+        let open = vec![held("old", dec!(100)), held("held", dec!(50))];
+        let signals = ["held", "new", "extra"]
+            .iter()
+            .map(|id| crate::types::Signal { suggested_size: dec!(0.05), ..ranking_signal(id, dec!(0.10)) })
+            .collect();
+
+        let allocations = allocate(signals, dec!(1000), &risk, &open);
+
+        // Adding to "held" needs no slot; "new" takes the last one
+        let ids: Vec<_> = allocations.iter().map(|(s, _)| s.market_id.as_str()).collect();
+        assert_eq!(ids, vec!["held", "new"]);
+        // 10% requested into the 5% left: each halved
+        assert!(allocations.iter().all(|(s, usd)| s.suggested_size == dec!(0.025) && *usd == dec!(25)));
+
+        // Nothing left of the budget: nothing allocated
+        let full = vec![held("old", dec!(200))];
+        let signals = vec![ranking_signal("new", dec!(0.10))];
+        assert!(allocate(signals, dec!(1000), &risk, &full).is_empty());
+    }
+
+    /// Hourly-market window: trade between 5 and 60 minutes out, shrinking
+    /// size over the last 30
+    fn resolution_window_generator() -> SignalGenerator {
+        let (strategy_config, risk_config) = make_test_config();
+        let strategy_config = StrategyConfig {
+            min_minutes_to_resolution: 5,
+            max_minutes_to_resolution: Some(60),
+            size_decay_minutes: 30,
+            ..strategy_config
+        };
+        SignalGenerator::new(strategy_config, risk_config)
+    }
+
+    fn market_resolving_in(minutes: i64) -> Market {
+        Market {
+            end_date: Some(chrono::Utc::now() + chrono::Duration::minutes(minutes)),
+            ..make_test_market(dec!(0.40))
+        }
+    }
+
+    fn strong_prediction() -> Prediction {
+        Prediction { probability: dec!(0.60), confidence: dec!(0.90), reasoning: "Test".to_string() }
+    }
+
+    #[test]
+    fn test_size_decays_with_time_to_resolution() {
+        let signal_gen = resolution_window_generator();
+        let undated = signal_gen.generate(&make_test_market(dec!(0.40)), &strong_prediction()).unwrap();
+
+        // 25 minutes left: inside the window, at roughly 25/30 of full size
+        let signal = signal_gen.generate(&market_resolving_in(25), &strong_prediction()).unwrap();
+        let ratio = signal.suggested_size / undated.suggested_size;
+        assert!(ratio > dec!(0.82) && ratio <= dec!(0.834), "ratio {}", ratio);
+
+        // 45 minutes left: no decay yet
+        let signal = signal_gen.generate(&market_resolving_in(45), &strong_prediction()).unwrap();
+        assert_eq!(signal.suggested_size, undated.suggested_size);
+    }
+
+    #[test]
+    fn test_rejects_markets_outside_resolution_window() {
+        let signal_gen = resolution_window_generator();
+
+        // 2 minutes left is too late to act on the edge
+        assert!(signal_gen.generate(&market_resolving_in(2), &strong_prediction()).is_none());
+        // Already expired
+        assert!(signal_gen.generate(&market_resolving_in(-1), &strong_prediction()).is_none());
+        // Too far out
+        assert!(signal_gen.generate(&market_resolving_in(90), &strong_prediction()).is_none());
+
+        // Expired markets are skipped even with no window configured
+        let (strategy_config, risk_config) = make_test_config();
+        let default_gen = SignalGenerator::new(strategy_config, risk_config);
+        assert!(default_gen.generate(&market_resolving_in(-1), &strong_prediction()).is_none());
+        assert!(default_gen.generate(&market_resolving_in(2), &strong_prediction()).is_some());
+    }
+
+    #[test]
+    fn test_resolution_factor_bounds() {
+        let (strategy_config, _) = make_test_config();
+        let config = StrategyConfig { size_decay_minutes: 30, ..strategy_config };
+        let now = chrono::Utc::now();
+        let at = |minutes: i64| Market { end_date: Some(now + chrono::Duration::minutes(minutes)), ..make_test_market(dec!(0.5)) };
+
+        assert_eq!(resolution_factor(&at(15), &config, now), Some(dec!(0.5)));
+        assert_eq!(resolution_factor(&at(120), &config, now), Some(Decimal::ONE));
+        assert_eq!(resolution_factor(&at(0), &config, now), None);
+        assert_eq!(resolution_factor(&make_test_market(dec!(0.5)), &config, now), Some(Decimal::ONE));
+    }
+
+    #[test]
+    fn test_skip_cache_detects_moves() {
+        let cache = MarketSkipCache::new();
+        let market = make_test_market(dec!(0.50));
+        assert!(!cache.is_unchanged(&market));
+
+        cache.record(&market);
+        assert!(cache.is_unchanged(&market));
+        // Within epsilon
+        assert!(cache.is_unchanged(&make_test_market(dec!(0.503))));
+        // Price moved
+        assert!(!cache.is_unchanged(&make_test_market(dec!(0.52))));
+        // Volume moved by 5%
+        assert!(!cache.is_unchanged(&Market { volume: dec!(105000), ..market.clone() }));
+
+        cache.invalidate(&market.id);
+        assert!(!cache.is_unchanged(&market));
+    }
+
+    #[test]
+    fn test_skip_cache_expires_and_clears() {
+        let market = make_test_market(dec!(0.50));
+
+        let expired = MarketSkipCache::new().with_max_age(std::time::Duration::ZERO);
+        expired.record(&market);
+        assert!(!expired.is_unchanged(&market));
+
+        let cache = MarketSkipCache::new();
+        cache.record(&market);
+        cache.clear();
+        assert!(!cache.is_unchanged(&market));
+    }
+
+    #[test]
+    fn test_up_down_market_trades_the_up_token() {
+        let (strategy_config, risk_config) = make_test_config();
+        let mut market = make_test_market(dec!(0.40));
+        market.outcomes[0].outcome = "Up".to_string();
+        market.outcomes[1].outcome = "Down".to_string();
+        let prediction = Prediction {
+            probability: dec!(0.55),
+            confidence: dec!(0.70),
+            reasoning: "Test".to_string(),
+        };
+
+        let signal = SignalGenerator::new(strategy_config.clone(), risk_config.clone())
+            .generate(&market, &prediction)
+            .unwrap();
+        assert_eq!(signal.token_id, "token-yes");
+        assert_eq!(signal.side, Side::Buy);
+        assert_eq!(signal.market_probability, dec!(0.40));
+
+        // Without the Up/Down synonyms the market has no Yes side
+        let strict = StrategyConfig {
+            outcome_matching: crate::types::OutcomeMatcher::CaseInsensitive,
+            ..strategy_config
+        };
+        assert!(SignalGenerator::new(strict, risk_config).generate(&market, &prediction).is_none());
+    }
+
+    fn tagged_market(id: &str, tags: &[&str], category: Option<&str>) -> Market {
+        Market {
+            id: id.to_string(),
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            category: category.map(str::to_string),
+            ..make_test_market(dec!(0.40))
+        }
+    }
+
+    #[test]
+    fn test_no_lists_admit_every_market() {
+        let (strategy_config, _) = make_test_config();
+        let markets = [
+            tagged_market("btc-100k", &["Crypto"], Some("Crypto")),
+            tagged_market("fed-rate", &[], None),
+        ];
+        assert!(markets.iter().all(|m| market_exclusion(m, &strategy_config).is_none()));
+    }
+
+    #[test]
+    fn test_allowlist_admits_only_matching_markets() {
+        let (strategy_config, _) = make_test_config();
+        let config = StrategyConfig {
+            allowlist: vec!["BTC".to_string(), "politics".to_string()],
+            ..strategy_config
+        };
+
+        assert!(market_exclusion(&tagged_market("btc-100k", &[], None), &config).is_none());
+        assert!(market_exclusion(&tagged_market("election", &["Politics"], None), &config).is_none());
+        let reason = market_exclusion(&tagged_market("fed-rate", &["Economy"], None), &config);
+        assert_eq!(reason.as_deref(), Some("not on the allowlist"));
+    }
+
+    #[test]
+    fn test_denylist_overrides_allowlist() {
+        let (strategy_config, _) = make_test_config();
+        let config = StrategyConfig {
+            allowlist: vec!["btc".to_string()],
+            denylist: vec!["btc-up-5m".to_string(), "sports".to_string()],
+            ..strategy_config
+        };
+
+        let reason = market_exclusion(&tagged_market("btc-up-5m-1700", &[], None), &config);
+        assert_eq!(reason.as_deref(), Some("denylisted by \"btc-up-5m\""));
+        assert!(market_exclusion(&tagged_market("btc-100k", &["Sports"], None), &config).is_some());
+        assert!(market_exclusion(&tagged_market("btc-100k", &["Crypto"], None), &config).is_none());
+    }
+
+    #[test]
+    fn test_categories_filter_on_market_category() {
+        let (strategy_config, _) = make_test_config();
+        let config = StrategyConfig {
+            categories: vec!["crypto".to_string()],
+            ..strategy_config
+        };
+
+        assert!(market_exclusion(&tagged_market("btc-100k", &[], Some("Crypto")), &config).is_none());
+        assert!(market_exclusion(&tagged_market("fed-rate", &[], Some("Economy")), &config).is_some());
+        assert!(market_exclusion(&tagged_market("untagged", &[], None), &config).is_some());
+    }
+
+    #[test]
+    fn test_accelerating_runup_suppresses_buy_yes() {
+        use crate::config::AntiChaseConfig;
+        use crate::monitor::{MarketStateConfig, MarketStateMonitor};
+        use std::sync::Arc;
+
+        let (strategy_config, risk_config) = make_test_config();
+        let market = make_test_market(dec!(0.40));
+        let prediction = Prediction {
+            probability: dec!(0.60),
+            confidence: dec!(0.80),
+            reasoning: "Test".to_string(),
+        };
+
+        // Yes creeps up, then runs from 0.30 to 0.40 in the latest updates
+        let market_state = Arc::new(MarketStateMonitor::new(MarketStateConfig::default()));
+        for i in 0..5 {
+            market_state.update(&market.id, dec!(0.30) + Decimal::from(i) * dec!(0.001), None, None, None);
+        }
+        for i in 1..=5 {
+            market_state.update(&market.id, dec!(0.30) + Decimal::from(i) * dec!(0.02), None, None, None);
+        }
+        let momentum = market_state.get_state(&market.id).unwrap().momentum;
+        assert!(momentum.accelerating);
+
+        let anti_chase = AntiChaseConfig { enabled: true, ..AntiChaseConfig::default() };
+        assert!(is_chasing(&momentum, Side::Buy, &anti_chase));
+        assert!(!is_chasing(&momentum, Side::Sell, &anti_chase));
+
+        // Off by default: the buy goes through at full size
+        let unguarded = SignalGenerator::new(strategy_config.clone(), risk_config.clone())
+            .with_market_state(market_state.clone())
+            .generate(&market, &prediction)
+            .unwrap();
+        assert_eq!(unguarded.side, Side::Buy);
+
+        let suppress = StrategyConfig { anti_chase: anti_chase.clone(), ..strategy_config.clone() };
+        let guarded = SignalGenerator::new(suppress, risk_config.clone()).with_market_state(market_state.clone());
+        assert!(guarded.generate(&market, &prediction).is_none());
+
+        let scale = StrategyConfig {
+            anti_chase: AntiChaseConfig { size_multiplier: dec!(0.5), ..anti_chase },
+            ..strategy_config
+        };
+        let scaled = SignalGenerator::new(scale, risk_config)
+            .with_market_state(market_state)
+            .generate(&market, &prediction)
+            .unwrap();
+        assert_eq!(scaled.suggested_size, unguarded.suggested_size * dec!(0.5));
+    }
 }
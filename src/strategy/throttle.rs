@@ -0,0 +1,137 @@
+//! Bot-wide trade rate limiting
+//!
+//! Risk limits cap how much is at stake, not how often the bot trades: a
+//! noisy model can still churn through dozens of small orders an hour.
+//! `TradeThrottle` caps trades per rolling hour and enforces a minimum gap
+//! between trades. `Executor` consults it before every submission. Recent
+//! trade times can be persisted so a restart does not reset the hour.
+
+use crate::config::ThrottleConfig;
+use crate::error::{BotError, Result};
+use chrono::{DateTime, Duration, Utc};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Trades within this window count towards `max_trades_per_hour`
+const WINDOW: Duration = Duration::hours(1);
+
+/// Shared limit on how often the bot trades
+pub struct TradeThrottle {
+    config: ThrottleConfig,
+    /// Times of trades within the last hour, oldest first
+    recent: Mutex<VecDeque<DateTime<Utc>>>,
+}
+
+impl TradeThrottle {
+    /// Throttle with the given limits, restoring recent trades from
+    /// `state_path` when the file exists
+    pub fn new(config: ThrottleConfig) -> Self {
+        let throttle = Self {
+            config,
+            recent: Mutex::new(VecDeque::new()),
+        };
+        if let Some(path) = throttle.config.state_path.as_deref() {
+            if Path::new(path).exists() {
+                match throttle.load(path) {
+                    Ok(restored) => tracing::info!("Restored {} recent trade times from {}", restored, path),
+                    Err(e) => tracing::warn!("Failed to load throttle state: {}", e),
+                }
+            }
+        }
+        throttle
+    }
+
+    /// Why a trade now would be throttled, if it would
+    pub fn check(&self) -> Option<String> {
+        self.check_at(Utc::now())
+    }
+
+    /// Why a trade at `now` would be throttled, if it would
+    pub fn check_at(&self, now: DateTime<Utc>) -> Option<String> {
+        let mut recent = self.recent.lock().unwrap();
+        prune(&mut recent, now);
+
+        if self.config.min_seconds_between_trades > 0 {
+            if let Some(last) = recent.back() {
+                let gap = Duration::seconds(self.config.min_seconds_between_trades as i64);
+                let wait = *last + gap - now;
+                if wait > Duration::zero() {
+                    return Some(format!(
+                        "last trade {}s ago, minimum gap is {}s",
+                        (now - *last).num_seconds(),
+                        self.config.min_seconds_between_trades
+                    ));
+                }
+            }
+        }
+        let max = self.config.max_trades_per_hour;
+        if max > 0 && recent.len() >= max as usize {
+            return Some(format!("{} trades in the last hour, limit is {}", recent.len(), max));
+        }
+        None
+    }
+
+    /// Count a trade made now
+    pub fn record(&self) {
+        self.record_at(Utc::now());
+    }
+
+    /// Count a trade made at `at`, saving to `state_path` if configured
+    pub fn record_at(&self, at: DateTime<Utc>) {
+        {
+            let mut recent = self.recent.lock().unwrap();
+            recent.push_back(at);
+            prune(&mut recent, at);
+        }
+        if let Some(path) = self.config.state_path.as_deref() {
+            if let Err(e) = self.save(path) {
+                tracing::warn!("Failed to save throttle state: {}", e);
+            }
+        }
+    }
+
+    /// Trades counted in the hour before `now`
+    pub fn trades_in_window(&self, now: DateTime<Utc>) -> usize {
+        let mut recent = self.recent.lock().unwrap();
+        prune(&mut recent, now);
+        recent.len()
+    }
+
+    /// Write recent trade times to a JSON file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let times: Vec<DateTime<Utc>> = self.recent.lock().unwrap().iter().copied().collect();
+        let json = serde_json::to_string(&times)?;
+
+        if let Some(parent) = path.as_ref().parent() {
+            if !parent.as_os_str().is_empty() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| BotError::Internal(format!("Failed to create throttle state dir: {}", e)))?;
+            }
+        }
+        std::fs::write(path, json)
+            .map_err(|e| BotError::Internal(format!("Failed to write throttle state: {}", e)))
+    }
+
+    /// Replace recent trade times with those saved by `save`
+    ///
+    /// Times older than an hour are dropped. Returns how many were kept.
+    pub fn load<P: AsRef<Path>>(&self, path: P) -> Result<usize> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| BotError::Internal(format!("Failed to read throttle state: {}", e)))?;
+        let mut times: Vec<DateTime<Utc>> = serde_json::from_str(&content)?;
+        times.sort();
+
+        let mut recent = self.recent.lock().unwrap();
+        *recent = times.into();
+        prune(&mut recent, Utc::now());
+        Ok(recent.len())
+    }
+}
+
+/// Drop trades that fell out of the hour before `now`
+fn prune(recent: &mut VecDeque<DateTime<Utc>>, now: DateTime<Utc>) {
+    while recent.front().is_some_and(|t| now - *t >= WINDOW) {
+        recent.pop_front();
+    }
+}
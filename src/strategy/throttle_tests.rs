@@ -0,0 +1,106 @@
+//! Tests for the bot-wide trade throttle
+
+use super::TradeThrottle;
+use crate::client::mock::MockClobClient;
+use crate::config::{RiskConfig, ThrottleConfig};
+use crate::error::BotError;
+use crate::executor::Executor;
+use crate::types::{Side, Signal};
+use chrono::{Duration, Utc};
+use rust_decimal_macros::dec;
+use std::sync::Arc;
+
+fn throttle(max_trades_per_hour: u32, min_seconds_between_trades: u64) -> TradeThrottle {
+    TradeThrottle::new(ThrottleConfig {
+        max_trades_per_hour,
+        min_seconds_between_trades,
+        state_path: None,
+    })
+}
+
+#[test]
+fn test_unconfigured_throttle_never_blocks() {
+    let throttle = throttle(0, 0);
+    let now = Utc::now();
+    for _ in 0..100 {
+        throttle.record_at(now);
+    }
+    assert!(throttle.check_at(now).is_none());
+}
+
+#[test]
+fn test_hourly_cap_blocks_until_oldest_trade_ages_out() {
+    let throttle = throttle(3, 0);
+    let start = Utc::now();
+    for minutes in [0, 10, 20] {
+        throttle.record_at(start + Duration::minutes(minutes));
+    }
+
+    let reason = throttle.check_at(start + Duration::minutes(30)).unwrap();
+    assert!(reason.contains("limit is 3"), "{}", reason);
+    assert!(throttle.check_at(start + Duration::minutes(59)).is_some());
+
+    // The first trade leaves the rolling hour
+    assert!(throttle.check_at(start + Duration::minutes(60)).is_none());
+    assert_eq!(throttle.trades_in_window(start + Duration::minutes(60)), 2);
+}
+
+#[test]
+fn test_min_gap_between_trades() {
+    let throttle = throttle(0, 120);
+    let start = Utc::now();
+    assert!(throttle.check_at(start).is_none());
+    throttle.record_at(start);
+
+    let reason = throttle.check_at(start + Duration::seconds(90)).unwrap();
+    assert!(reason.contains("minimum gap is 120s"), "{}", reason);
+    assert!(throttle.check_at(start + Duration::seconds(120)).is_none());
+}
+
+#[test]
+fn test_recent_trades_survive_restart() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("state").join("throttle.json");
+    let config = ThrottleConfig {
+        max_trades_per_hour: 2,
+        min_seconds_between_trades: 0,
+        state_path: Some(path.to_string_lossy().into_owned()),
+    };
+
+    let now = Utc::now();
+    let first = TradeThrottle::new(config.clone());
+    first.record_at(now - Duration::hours(2));
+    first.record_at(now - Duration::minutes(30));
+    first.record_at(now - Duration::minutes(5));
+    assert!(first.check_at(now).is_some());
+
+    // A restart within the hour keeps the window, minus expired trades
+    let restarted = TradeThrottle::new(config);
+    assert_eq!(restarted.trades_in_window(now), 2);
+    assert!(restarted.check_at(now).is_some());
+}
+
+#[tokio::test]
+async fn test_executor_refuses_throttled_trade() {
+    let throttle = Arc::new(throttle(1, 0));
+    let executor = Executor::new(MockClobClient::new(), RiskConfig::default()).with_trade_throttle(throttle.clone());
+    let signal = Signal {
+        market_id: "m1".to_string(),
+        token_id: "t1".to_string(),
+        side: Side::Buy,
+        model_probability: dec!(0.70),
+        market_probability: dec!(0.55),
+        edge: dec!(0.15),
+        net_edge: dec!(0.15),
+        confidence: dec!(0.80),
+        suggested_size: dec!(0.05),
+        timestamp: Utc::now(),
+        max_staleness: None,
+    };
+
+    assert!(executor.execute(&signal, dec!(1000)).await.unwrap().is_some());
+    assert_eq!(throttle.trades_in_window(Utc::now()), 1);
+
+    let err = executor.execute(&signal, dec!(1000)).await.unwrap_err();
+    assert!(matches!(err.root(), BotError::RiskLimit(reason) if reason.contains("throttled")));
+}
@@ -9,6 +9,7 @@ use crate::client::PolymarketClient;
 use crate::config::Config;
 use crate::error::Result;
 use crate::storage::Database;
+use crate::utils::truncate_chars;
 use reqwest::Client;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
@@ -383,11 +384,7 @@ impl CommandHandler {
                 
                 for (i, market) in markets.iter().enumerate() {
                     let yes = market.yes_price().unwrap_or(Decimal::ZERO) * Decimal::ONE_HUNDRED;
-                    let question = if market.question.len() > 40 {
-                        format!("{}...", &market.question[..40])
-                    } else {
-                        market.question.clone()
-                    };
+                    let question = truncate_chars(&market.question, 40);
                     
                     text.push_str(&format!(
                         "{}. {} <code>{:.0}%</code>\n",
@@ -398,7 +395,7 @@ impl CommandHandler {
                 let _ = self.notifier.send(&text).await;
             }
             Err(e) => {
-                let _ = self.notifier.error("Markets fetch", &e.to_string()).await;
+                let _ = self.notifier.error("Markets fetch", &e).await;
             }
         }
     }
@@ -441,7 +438,7 @@ impl CommandHandler {
                 let _ = self.notifier.send(&text).await;
             }
             Err(e) => {
-                let _ = self.notifier.error("Positions fetch", &e.to_string()).await;
+                let _ = self.notifier.error("Positions fetch", &e).await;
             }
         }
     }
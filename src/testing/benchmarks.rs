@@ -57,6 +57,7 @@ impl BenchmarkSuite {
                 price: dec!(0.50),
                 size: dec!(10),
                 order_type: OrderType::GTC,
+                client_order_id: None,
             };
 
             let start = Instant::now();
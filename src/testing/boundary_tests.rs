@@ -120,6 +120,10 @@ mod tests {
             ],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         };
         
         assert_eq!(market.yes_price(), Some(dec!(0.50)));
@@ -201,6 +205,10 @@ mod tests {
             ],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         };
         
         assert_eq!(market.yes_price(), Some(dec!(0.001)));
@@ -369,6 +377,10 @@ mod tests {
             outcomes: vec![],  // Empty outcomes
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         };
         
         let prediction = Prediction {
@@ -431,6 +443,10 @@ mod tests {
             ],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         };
         
         assert!(market.arbitrage_opportunity().is_none());
@@ -451,6 +467,10 @@ mod tests {
             ],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         };
         
         assert!(market.arbitrage_opportunity().is_none());
@@ -471,6 +491,10 @@ mod tests {
             ],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         };
         
         let arb = market.arbitrage_opportunity();
@@ -489,9 +513,11 @@ mod tests {
             model_probability: dec!(0.60),
             market_probability: dec!(0.50),
             edge: dec!(0.05),       // Exactly at threshold
+            net_edge: dec!(0.05),
             confidence: dec!(0.60), // Exactly at threshold
             suggested_size: dec!(0.05),
             timestamp: Utc::now(),
+            max_staleness: None,
         };
         
         assert!(signal.is_tradeable(dec!(0.05), dec!(0.60)));
@@ -507,9 +533,11 @@ mod tests {
             model_probability: dec!(0.40),
             market_probability: dec!(0.50),
             edge: dec!(-0.10),
+            net_edge: dec!(-0.10),
             confidence: dec!(0.80),
             suggested_size: dec!(0.05),
             timestamp: Utc::now(),
+            max_staleness: None,
         };
         
         // Negative edge should use abs() in is_tradeable
@@ -526,6 +554,7 @@ mod tests {
             volatility: dec!(0.50),  // 50% volatility (very high)
             liquidity_score: dec!(1.0),
             time_pressure: dec!(0),
+            regime: None,
         };
         
         let result = kelly.calculate_position_size(
@@ -548,6 +577,7 @@ mod tests {
             volatility: dec!(0.05),
             liquidity_score: dec!(0.1),  // Very low liquidity
             time_pressure: dec!(0),
+            regime: None,
         };
         
         let result = kelly.calculate_position_size(
@@ -570,6 +600,7 @@ mod tests {
             volatility: dec!(0.05),
             liquidity_score: dec!(1.0),
             time_pressure: dec!(1.0),  // Maximum time pressure
+            regime: None,
         };
         
         let result = kelly.calculate_position_size(
@@ -600,6 +631,10 @@ mod tests {
             ],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         }
     }
 }
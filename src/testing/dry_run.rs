@@ -6,7 +6,8 @@
 use crate::client::mock::{MockClobClient, MockGammaClient, ClobClientTrait, GammaClientTrait};
 use crate::strategy::SignalGenerator;
 use crate::config::{StrategyConfig, RiskConfig};
-use crate::types::{Market, Signal, Side};
+use crate::types::{Market, OutcomeMatcher, Signal, Side};
+use crate::utils::truncate_chars;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use rust_decimal::MathematicalOps;
@@ -86,9 +87,24 @@ impl DryRunSimulator {
             min_confidence: dec!(0.50),  // 50% confidence
             kelly_fraction: dec!(0.35),  // 35% Kelly
             scan_interval_secs: 180,
+            scan_concurrency: 4,
             model_update_interval_secs: 900,
             compound_enabled: true,
             compound_sqrt_scaling: true,
+            fee_rate: Decimal::ZERO,
+            slippage_estimate: Decimal::ZERO,
+            max_price_deviation: dec!(0.05),
+            min_liquidity: dec!(10000),
+            crypto_min_liquidity: dec!(1000),
+            min_minutes_to_resolution: 0,
+            max_minutes_to_resolution: None,
+            size_decay_minutes: 0,
+            outcome_matching: OutcomeMatcher::default(),
+            allowlist: Vec::new(),
+            denylist: Vec::new(),
+            categories: Vec::new(),
+            anti_chase: Default::default(),
+            abstain_below_confidence: None,
         };
         let risk_config = RiskConfig::default();
         
@@ -189,6 +205,7 @@ impl DryRunSimulator {
             price: signal.market_probability,
             size: size_usd / signal.market_probability,
             order_type: crate::types::OrderType::GTC,
+            client_order_id: None,
         };
 
         match self.clob.place_order(&order).await {
@@ -415,11 +432,7 @@ impl DryRunSimulator {
             report.push_str("|---|------|--------|------|------|-----|------|\n");
             
             for trade in result.trades.iter().rev().take(20) {
-                let short_q = if trade.market_question.len() > 30 {
-                    format!("{}...", &trade.market_question[..27])
-                } else {
-                    trade.market_question.clone()
-                };
+                let short_q = truncate_chars(&trade.market_question, 27);
                 report.push_str(&format!(
                     "| {} | {} | {} | {:?} | ${:.2} | ${:.2} | {:.1}% |\n",
                     trade.id,
@@ -5,17 +5,31 @@
 //! - Multiple strategy testing
 //! - Performance attribution
 //! - Edge case handling
+//!
+//! All randomness comes from a seeded `StdRng`, so the same seed and config
+//! reproduce the same `EnhancedSimResult`. `monte_carlo` repeats the run over
+//! many seeds to get a distribution instead of a single path.
+//!
+//! With `with_history` the simulator replays recorded market snapshots
+//! instead of the mock market list, one timestamp per step.
 
 use crate::client::mock::{MockClobClient, MockGammaClient, GammaClientTrait};
 use crate::config::{RiskConfig, StrategyConfig};
 use crate::strategy::{SignalGenerator, DynamicKelly, DynamicKellyConfig};
-use crate::types::{Market, Side};
+use crate::types::{Market, OrderBook, Side};
 use crate::model::Prediction;
+use crate::testing::history::{group_by_timestamp, HistoricalSnapshot};
+use crate::testing::slippage::{book_walk_slippage, linear_slippage, synthetic_book, SlippageModel};
 use chrono::Utc;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Serialize, Deserialize};
-use std::collections::HashMap;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
 
 /// Comprehensive dry run simulation configuration
 #[derive(Debug, Clone)]
@@ -26,10 +40,14 @@ pub struct EnhancedDryRunConfig {
     pub market_volatility: Decimal,
     pub simulate_slippage: bool,
     pub slippage_factor: Decimal,
+    /// How slippage scales with order size; `Flat` uses `slippage_factor`
+    pub slippage_model: SlippageModel,
     pub simulate_partial_fills: bool,
     pub partial_fill_prob: Decimal,
     pub simulate_failures: bool,
     pub failure_prob: Decimal,
+    /// Halt when drawdown from peak equity exceeds this (e.g., 0.20 = 20%)
+    pub stop_on_drawdown_pct: Option<Decimal>,
 }
 
 impl Default for EnhancedDryRunConfig {
@@ -41,11 +59,59 @@ impl Default for EnhancedDryRunConfig {
             market_volatility: dec!(0.05),
             simulate_slippage: true,
             slippage_factor: dec!(0.005),
+            slippage_model: SlippageModel::Flat,
             simulate_partial_fills: true,
             partial_fill_prob: dec!(0.20),
             simulate_failures: true,
             failure_prob: dec!(0.05),
+            stop_on_drawdown_pct: None,
+        }
+    }
+}
+
+impl EnhancedDryRunConfig {
+    /// Apply `key=value` overrides separated by commas
+    ///
+    /// e.g. `initial_balance=500,simulate_failures=false,stop_on_drawdown_pct=0.2`
+    pub fn apply_overrides(&mut self, overrides: &str) -> anyhow::Result<()> {
+        for pair in overrides.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("Override '{}' is not key=value", pair))?;
+            let (key, value) = (key.trim(), value.trim());
+
+            let decimal = || {
+                Decimal::from_str(value)
+                    .map_err(|_| anyhow::anyhow!("Invalid number for {}: {}", key, value))
+            };
+            let boolean = || {
+                value.parse::<bool>()
+                    .map_err(|_| anyhow::anyhow!("Invalid bool for {}: {}", key, value))
+            };
+
+            match key {
+                "initial_balance" => self.initial_balance = decimal()?,
+                "steps" => {
+                    self.steps = value.parse()
+                        .map_err(|_| anyhow::anyhow!("Invalid step count: {}", value))?
+                }
+                "use_dynamic_kelly" => self.use_dynamic_kelly = boolean()?,
+                "market_volatility" => self.market_volatility = decimal()?,
+                "simulate_slippage" => self.simulate_slippage = boolean()?,
+                "slippage_factor" => self.slippage_factor = decimal()?,
+                "slippage_model" => {
+                    self.slippage_model = SlippageModel::parse(value)
+                        .ok_or_else(|| anyhow::anyhow!("Invalid slippage model: {}", value))?
+                }
+                "simulate_partial_fills" => self.simulate_partial_fills = boolean()?,
+                "partial_fill_prob" => self.partial_fill_prob = decimal()?,
+                "simulate_failures" => self.simulate_failures = boolean()?,
+                "failure_prob" => self.failure_prob = decimal()?,
+                "stop_on_drawdown_pct" => self.stop_on_drawdown_pct = Some(decimal()?),
+                _ => anyhow::bail!("Unknown override: {}", key),
+            }
         }
+        Ok(())
     }
 }
 
@@ -63,6 +129,9 @@ pub struct EnhancedSimResult {
     pub max_drawdown: Decimal,
     pub sharpe_ratio: Decimal,
     pub sortino_ratio: Decimal,
+    /// Return over max drawdown for the run (steps are not calendar time,
+    /// so it is not annualized)
+    pub calmar_ratio: Decimal,
     pub profit_factor: Decimal,
     pub total_slippage: Decimal,
     pub avg_slippage: Decimal,
@@ -72,7 +141,23 @@ pub struct EnhancedSimResult {
     pub signals_filtered: u32,
     pub trades: Vec<EnhancedSimTrade>,
     pub equity_curve: Vec<(u32, Decimal)>,
-    pub pnl_by_market: HashMap<String, Decimal>,
+    pub pnl_by_market: BTreeMap<String, Decimal>,
+    /// Whether the drawdown kill-switch ended the run
+    pub stopped_early: bool,
+    /// Step at which the kill-switch fired
+    pub stop_step: Option<u32>,
+}
+
+impl EnhancedSimResult {
+    /// Write the equity curve as `step,equity` CSV
+    pub fn write_equity_curve<P: AsRef<Path>>(&self, path: P) -> anyhow::Result<()> {
+        let mut csv = String::from("step,equity\n");
+        for (step, equity) in &self.equity_curve {
+            csv.push_str(&format!("{},{}\n", step, equity));
+        }
+        std::fs::write(path, csv)?;
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -124,7 +209,8 @@ pub struct EnhancedDryRun {
     current_step: u32,
     trades: Vec<EnhancedSimTrade>,
     trade_counter: u32,
-    open_positions: HashMap<String, OpenPosition>,
+    /// Ordered so exits (and the random draws they consume) are reproducible
+    open_positions: BTreeMap<String, OpenPosition>,
     equity_curve: Vec<(u32, Decimal)>,
     peak_balance: Decimal,
     max_drawdown: Decimal,
@@ -134,8 +220,19 @@ pub struct EnhancedDryRun {
     partial_fills: u32,
     total_slippage: Decimal,
     random_seed: u64,
+    rng: StdRng,
+    stop_step: Option<u32>,
+    /// Recorded market snapshots, one frame per step
+    history: Vec<Vec<HistoricalSnapshot>>,
+    /// Latest replayed Yes price per market, used as the exit price
+    last_prices: BTreeMap<String, Decimal>,
+    /// Recorded books by token id for `SlippageModel::BookWalk`
+    order_books: BTreeMap<String, OrderBook>,
 }
 
+/// Seed used when `with_seed` is not called
+const DEFAULT_SEED: u64 = 42;
+
 impl EnhancedDryRun {
     pub fn new(config: EnhancedDryRunConfig) -> Self {
         let strategy_config = StrategyConfig::default();
@@ -157,7 +254,7 @@ impl EnhancedDryRun {
             current_step: 0,
             trades: Vec::new(),
             trade_counter: 0,
-            open_positions: HashMap::new(),
+            open_positions: BTreeMap::new(),
             equity_curve: vec![(0, config.initial_balance)],
             peak_balance: config.initial_balance,
             max_drawdown: dec!(0),
@@ -166,7 +263,12 @@ impl EnhancedDryRun {
             failed_orders: 0,
             partial_fills: 0,
             total_slippage: dec!(0),
-            random_seed: 42,
+            random_seed: DEFAULT_SEED,
+            rng: StdRng::seed_from_u64(DEFAULT_SEED),
+            stop_step: None,
+            history: Vec::new(),
+            last_prices: BTreeMap::new(),
+            order_books: BTreeMap::new(),
         }
     }
 
@@ -175,14 +277,45 @@ impl EnhancedDryRun {
         self
     }
 
+    /// Use the given strategy and risk settings instead of the defaults
+    pub fn with_strategy(mut self, strategy: StrategyConfig, risk: RiskConfig) -> Self {
+        self.signal_gen = SignalGenerator::new(strategy, risk);
+        self
+    }
+
+    /// Replay recorded snapshots, one step per distinct timestamp, for at
+    /// most `config.steps` steps
+    pub fn with_history(mut self, snapshots: Vec<HistoricalSnapshot>) -> Self {
+        self.history = group_by_timestamp(snapshots);
+        self.config.steps = self.config.steps.min(self.history.len() as u32);
+        self
+    }
+
+    /// Walk this book for `token_id` instead of a synthetic one
+    pub fn with_order_book(mut self, token_id: impl Into<String>, book: OrderBook) -> Self {
+        self.order_books.insert(token_id.into(), book);
+        self
+    }
+
     pub fn with_seed(mut self, seed: u64) -> Self {
         self.random_seed = seed;
+        self.rng = StdRng::seed_from_u64(seed);
         self
     }
 
+    /// Seed driving this simulation's randomness
+    pub fn seed(&self) -> u64 {
+        self.random_seed
+    }
+
     pub async fn run(&mut self) -> anyhow::Result<EnhancedSimResult> {
         for _ in 0..self.config.steps {
             self.step().await?;
+
+            if self.drawdown_limit_hit() {
+                self.stop_step = Some(self.current_step);
+                break;
+            }
         }
         self.close_all_positions().await?;
         Ok(self.compile_results())
@@ -192,14 +325,31 @@ impl EnhancedDryRun {
         self.current_step += 1;
         self.update_position_pnl().await?;
         
-        let markets = self.gamma.get_top_markets(20).await?;
+        let frame = if self.history.is_empty() {
+            None
+        } else {
+            self.history.get(self.current_step as usize - 1).cloned()
+        };
+        let markets = match &frame {
+            Some(snapshots) => {
+                for snapshot in snapshots {
+                    self.last_prices.insert(snapshot.market_id.clone(), snapshot.yes_price);
+                }
+                snapshots.iter().map(HistoricalSnapshot::to_market).collect()
+            }
+            None => self.gamma.get_top_markets(20).await?,
+        };
         
-        for market in &markets {
+        for (i, market) in markets.iter().enumerate() {
             if market.liquidity < dec!(1000) {
                 continue;
             }
             
-            let prediction = self.generate_prediction(market);
+            let recorded = frame.as_ref().and_then(|f| f[i].prediction());
+            let prediction = match recorded {
+                Some(p) => p,
+                None => self.generate_prediction(market),
+            };
             
             if let Some(signal) = self.signal_gen.generate(market, &prediction) {
                 self.signals_generated += 1;
@@ -215,7 +365,17 @@ impl EnhancedDryRun {
         Ok(())
     }
 
+    /// Open a position for `signal`
+    ///
+    /// Positions are kept one per token, so a signal on a token that is
+    /// already held is counted as filtered rather than replacing the open
+    /// position and losing its cost.
     async fn process_signal(&mut self, signal: &crate::types::Signal, market: &Market) -> anyhow::Result<()> {
+        if self.open_positions.contains_key(&signal.token_id) {
+            self.signals_filtered += 1;
+            return Ok(());
+        }
+
         let kelly_fraction = if let Some(ref kelly) = self.kelly {
             let result = kelly.calculate_position_size(
                 signal.model_probability,
@@ -236,7 +396,7 @@ impl EnhancedDryRun {
             return Ok(());
         }
         
-        let (executed_size, slippage, status) = self.simulate_execution(intended_size).await;
+        let (executed_size, slippage, status) = self.simulate_execution(intended_size, signal, market).await;
         
         if status == TradeStatus::Failed {
             self.failed_orders += 1;
@@ -290,15 +450,33 @@ impl EnhancedDryRun {
         Ok(())
     }
 
-    async fn simulate_execution(&mut self, intended_size: Decimal) -> (Decimal, Decimal, TradeStatus) {
+    async fn simulate_execution(
+        &mut self,
+        intended_size: Decimal,
+        signal: &crate::types::Signal,
+        market: &Market,
+    ) -> (Decimal, Decimal, TradeStatus) {
         if self.config.simulate_failures && self.random() < self.config.failure_prob {
             return (dec!(0), dec!(0), TradeStatus::Failed);
         }
         
-        let slippage = if self.config.simulate_slippage {
-            intended_size * self.config.slippage_factor * (dec!(0.5) + self.random())
-        } else {
+        let slippage = if !self.config.simulate_slippage {
             dec!(0)
+        } else {
+            match self.config.slippage_model {
+                SlippageModel::Flat => intended_size * self.config.slippage_factor * (dec!(0.5) + self.random()),
+                SlippageModel::Linear { per_unit } => linear_slippage(per_unit, intended_size),
+                SlippageModel::BookWalk => {
+                    let slippage = match self.order_books.get(&signal.token_id) {
+                        Some(book) => book_walk_slippage(book, signal.side, intended_size),
+                        None => {
+                            let book = synthetic_book(signal.market_probability, market.liquidity);
+                            book_walk_slippage(&book, signal.side, intended_size)
+                        }
+                    };
+                    slippage.round_dp(6)
+                }
+            }
         };
         
         let executed_size = if self.config.simulate_partial_fills && self.random() < self.config.partial_fill_prob {
@@ -345,8 +523,13 @@ impl EnhancedDryRun {
 
     async fn close_position(&mut self, token_id: &str) -> anyhow::Result<()> {
         if let Some(position) = self.open_positions.remove(token_id) {
-            let noise = (self.random() - dec!(0.5)) * dec!(0.1);
-            let exit_price = (position.entry_price + position.edge + noise).max(dec!(0.01)).min(dec!(0.99));
+            let exit_price = match self.last_prices.get(&position.market_id) {
+                Some(&price) => price,
+                None => {
+                    let noise = (self.random() - dec!(0.5)) * dec!(0.1);
+                    (position.entry_price + position.edge + noise).max(dec!(0.01)).min(dec!(0.99))
+                }
+            };
             
             let pnl = match position.side {
                 Side::Buy => (exit_price - position.entry_price) * position.size,
@@ -386,6 +569,19 @@ impl EnhancedDryRun {
         self.current_balance + position_value
     }
 
+    /// Whether current drawdown from peak exceeds `stop_on_drawdown_pct`
+    fn drawdown_limit_hit(&self) -> bool {
+        let Some(limit) = self.config.stop_on_drawdown_pct else {
+            return false;
+        };
+        let Some(&(_, equity)) = self.equity_curve.last() else {
+            return false;
+        };
+
+        self.peak_balance > Decimal::ZERO
+            && (self.peak_balance - equity) / self.peak_balance > limit
+    }
+
     fn update_drawdown(&mut self, equity: Decimal) {
         if equity > self.peak_balance {
             self.peak_balance = equity;
@@ -429,9 +625,9 @@ impl EnhancedDryRun {
         });
     }
 
+    /// Uniform draw in [0, 1) at 0.001 resolution
     fn random(&mut self) -> Decimal {
-        self.random_seed = self.random_seed.wrapping_mul(1103515245).wrapping_add(12345);
-        Decimal::from(self.random_seed % 1000) / dec!(1000)
+        Decimal::from(self.rng.random_range(0..1000u32)) / dec!(1000)
     }
 
     fn compile_results(&self) -> EnhancedSimResult {
@@ -466,8 +662,9 @@ impl EnhancedDryRun {
         
         let sharpe_ratio = self.calculate_sharpe();
         let sortino_ratio = self.calculate_sortino();
+        let calmar_ratio = self.calculate_calmar(pnl_pct);
         
-        let mut pnl_by_market: HashMap<String, Decimal> = HashMap::new();
+        let mut pnl_by_market: BTreeMap<String, Decimal> = BTreeMap::new();
         for trade in &self.trades {
             if trade.status == TradeStatus::Completed {
                 *pnl_by_market.entry(trade.market_id.clone()).or_default() += trade.pnl;
@@ -492,6 +689,7 @@ impl EnhancedDryRun {
             max_drawdown: self.max_drawdown * dec!(100),
             sharpe_ratio,
             sortino_ratio,
+            calmar_ratio,
             profit_factor,
             total_slippage: self.total_slippage,
             avg_slippage,
@@ -502,6 +700,8 @@ impl EnhancedDryRun {
             trades: self.trades.clone(),
             equity_curve: self.equity_curve.clone(),
             pnl_by_market,
+            stopped_early: self.stop_step.is_some(),
+            stop_step: self.stop_step,
         }
     }
 
@@ -574,6 +774,18 @@ impl EnhancedDryRun {
         }
     }
 
+    /// Run return over max drawdown, both in percent
+    fn calculate_calmar(&self, pnl_pct: Decimal) -> Decimal {
+        let drawdown_pct = self.max_drawdown * dec!(100);
+        if drawdown_pct > Decimal::ZERO {
+            pnl_pct / drawdown_pct
+        } else if pnl_pct > Decimal::ZERO {
+            dec!(999)
+        } else {
+            dec!(0)
+        }
+    }
+
     pub fn generate_report(&self, result: &EnhancedSimResult) -> String {
         let mut report = String::new();
         
@@ -587,7 +799,11 @@ impl EnhancedDryRun {
         report.push_str(&format!("| Total P&L | ${:.2} ({:+.2}%) |\n", result.total_pnl, result.pnl_pct));
         report.push_str(&format!("| Sharpe Ratio | {:.2} |\n", result.sharpe_ratio));
         report.push_str(&format!("| Sortino Ratio | {:.2} |\n", result.sortino_ratio));
+        report.push_str(&format!("| Calmar Ratio | {:.2} |\n", result.calmar_ratio));
         report.push_str(&format!("| Max Drawdown | {:.2}% |\n", result.max_drawdown));
+        if let Some(step) = result.stop_step {
+            report.push_str(&format!("| Stopped Early | step {} (drawdown limit) |\n", step));
+        }
         report.push_str(&format!("| Profit Factor | {:.2} |\n", result.profit_factor));
         
         report.push_str("\n## 📈 Trade Statistics\n\n");
@@ -613,6 +829,120 @@ impl EnhancedDryRun {
     }
 }
 
+/// Fraction of the initial balance lost that counts as ruin in Monte Carlo runs
+const RUIN_LOSS_PCT: Decimal = dec!(0.50);
+
+/// Summary of one Monte Carlo run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonteCarloRun {
+    pub seed: u64,
+    pub final_balance: Decimal,
+    pub total_pnl: Decimal,
+    /// Max drawdown in percent, as in `EnhancedSimResult`
+    pub max_drawdown: Decimal,
+    pub total_trades: u32,
+    pub win_rate: Decimal,
+    pub sharpe_ratio: Decimal,
+}
+
+/// Distribution of a metric across Monte Carlo runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DistributionStats {
+    pub mean: Decimal,
+    pub median: Decimal,
+    pub p5: Decimal,
+    pub p95: Decimal,
+    pub min: Decimal,
+    pub max: Decimal,
+}
+
+impl DistributionStats {
+    /// Compute stats over a set of values (all zero if empty)
+    pub fn from_values(values: &[Decimal]) -> Self {
+        if values.is_empty() {
+            return Self::default();
+        }
+
+        let mut sorted = values.to_vec();
+        sorted.sort();
+        let mean = sorted.iter().sum::<Decimal>() / Decimal::from(sorted.len() as u64);
+
+        Self {
+            mean,
+            median: percentile(&sorted, dec!(0.50)),
+            p5: percentile(&sorted, dec!(0.05)),
+            p95: percentile(&sorted, dec!(0.95)),
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+/// Linear-interpolated percentile of sorted values (`q` in 0-1)
+fn percentile(sorted: &[Decimal], q: Decimal) -> Decimal {
+    let rank = q * Decimal::from(sorted.len() as u64 - 1);
+    let lower = rank.floor();
+    let idx = lower.to_usize().unwrap_or(0);
+    let frac = rank - lower;
+
+    match sorted.get(idx + 1) {
+        Some(next) => sorted[idx] + (*next - sorted[idx]) * frac,
+        None => sorted[idx],
+    }
+}
+
+/// Aggregated result of many simulation runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MonteCarloResult {
+    pub runs: u32,
+    pub final_balance: DistributionStats,
+    pub max_drawdown: DistributionStats,
+    /// Share of runs ending with a loss of at least `RUIN_LOSS_PCT`
+    pub probability_of_ruin: Decimal,
+    /// Share of runs ending above the initial balance
+    pub probability_of_profit: Decimal,
+    pub summaries: Vec<MonteCarloRun>,
+}
+
+/// Run the simulation `runs` times with consecutive seeds and aggregate
+pub async fn monte_carlo(config: EnhancedDryRunConfig, runs: u32) -> anyhow::Result<MonteCarloResult> {
+    let ruin_balance = config.initial_balance * (Decimal::ONE - RUIN_LOSS_PCT);
+    let mut summaries = Vec::with_capacity(runs as usize);
+
+    for i in 0..runs {
+        let seed = DEFAULT_SEED + i as u64;
+        let result = EnhancedDryRun::new(config.clone()).with_seed(seed).run().await?;
+        summaries.push(MonteCarloRun {
+            seed,
+            final_balance: result.final_balance,
+            total_pnl: result.total_pnl,
+            max_drawdown: result.max_drawdown,
+            total_trades: result.total_trades,
+            win_rate: result.win_rate,
+            sharpe_ratio: result.sharpe_ratio,
+        });
+    }
+
+    let balances: Vec<Decimal> = summaries.iter().map(|r| r.final_balance).collect();
+    let drawdowns: Vec<Decimal> = summaries.iter().map(|r| r.max_drawdown).collect();
+    let share = |count: usize| {
+        if runs == 0 {
+            dec!(0)
+        } else {
+            Decimal::from(count as u64) / Decimal::from(runs)
+        }
+    };
+
+    Ok(MonteCarloResult {
+        runs,
+        final_balance: DistributionStats::from_values(&balances),
+        max_drawdown: DistributionStats::from_values(&drawdowns),
+        probability_of_ruin: share(balances.iter().filter(|b| **b <= ruin_balance).count()),
+        probability_of_profit: share(balances.iter().filter(|b| **b > config.initial_balance).count()),
+        summaries,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -668,13 +998,90 @@ mod tests {
         let mut sim2 = EnhancedDryRun::new(config).with_seed(12345);
         let result2 = sim2.run().await.unwrap();
         
-        // With same seed and no randomness sources, simulation should be reproducible
-        // Note: exact trade count may vary due to HashMap iteration order, but equity curve length should match
-        assert_eq!(result1.equity_curve.len(), result2.equity_curve.len(), "Equity curve length should be identical");
+        // With same seed, simulation should be reproducible
+        assert_eq!(result1.equity_curve, result2.equity_curve, "Equity curve should be identical");
+        assert_eq!(result1.total_trades, result2.total_trades);
         // Both should complete same number of steps
         assert_eq!(result1.equity_curve.len(), 51);  // Initial + 50 steps
     }
 
+    #[tokio::test]
+    async fn test_same_seed_reproduces_result() {
+        // All randomness sources enabled
+        let config = EnhancedDryRunConfig {
+            steps: 40,
+            failure_prob: dec!(0.20),
+            partial_fill_prob: dec!(0.30),
+            ..Default::default()
+        };
+
+        let result1 = EnhancedDryRun::new(config.clone()).with_seed(7).run().await.unwrap();
+        let result2 = EnhancedDryRun::new(config).with_seed(7).run().await.unwrap();
+
+        assert!(result1.total_trades > 0);
+        assert_eq!(
+            serde_json::to_string(&result1).unwrap(),
+            serde_json::to_string(&result2).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_different_seed_changes_result() {
+        let config = EnhancedDryRunConfig {
+            steps: 40,
+            ..Default::default()
+        };
+
+        let result1 = EnhancedDryRun::new(config.clone()).with_seed(7).run().await.unwrap();
+        let result2 = EnhancedDryRun::new(config).with_seed(8).run().await.unwrap();
+
+        assert_ne!(
+            serde_json::to_string(&result1.trades).unwrap(),
+            serde_json::to_string(&result2.trades).unwrap()
+        );
+        assert_ne!(result1.equity_curve, result2.equity_curve);
+    }
+
+    #[tokio::test]
+    async fn test_stop_on_drawdown() {
+        // Slippage eats most of every entry, so equity falls off a cliff
+        let config = EnhancedDryRunConfig {
+            steps: 100,
+            simulate_slippage: true,
+            slippage_factor: dec!(0.80),
+            simulate_failures: false,
+            simulate_partial_fills: false,
+            stop_on_drawdown_pct: Some(dec!(0.05)),
+            ..Default::default()
+        };
+
+        let result = EnhancedDryRun::new(config).run().await.unwrap();
+
+        assert!(result.stopped_early);
+        let stop_step = result.stop_step.unwrap();
+        assert!(stop_step < 100);
+        assert_eq!(result.equity_curve.last().unwrap().0, stop_step);
+        assert!(result.max_drawdown > dec!(5));
+        // A losing run has a negative Calmar
+        assert_eq!(result.calmar_ratio, result.pnl_pct / result.max_drawdown);
+        assert!(result.calmar_ratio < Decimal::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_no_stop_without_limit() {
+        let config = EnhancedDryRunConfig {
+            steps: 30,
+            slippage_factor: dec!(0.80),
+            ..Default::default()
+        };
+
+        let result = EnhancedDryRun::new(config).run().await.unwrap();
+
+        assert!(!result.stopped_early);
+        assert!(result.stop_step.is_none());
+        assert_eq!(result.equity_curve.len(), 31);
+    }
+
     #[tokio::test]
     async fn test_report_generation() {
         let config = EnhancedDryRunConfig {
@@ -725,4 +1132,201 @@ mod tests {
             println!("Total slippage: ${:.4}", result.total_slippage);
         }
     }
+
+    #[tokio::test]
+    async fn test_monte_carlo_aggregates_runs() {
+        let config = EnhancedDryRunConfig {
+            steps: 20,
+            ..Default::default()
+        };
+
+        let mc = monte_carlo(config.clone(), 12).await.unwrap();
+
+        assert_eq!(mc.runs, 12);
+        assert_eq!(mc.summaries.len(), 12);
+
+        // Same seeds reproduce every run
+        let again = monte_carlo(config, 12).await.unwrap();
+        assert_eq!(
+            serde_json::to_string(&mc.summaries).unwrap(),
+            serde_json::to_string(&again.summaries).unwrap()
+        );
+
+        // Different seeds give different runs
+        let seeds: std::collections::HashSet<u64> = mc.summaries.iter().map(|r| r.seed).collect();
+        assert_eq!(seeds.len(), 12);
+        let first = &mc.summaries[0];
+        assert!(mc.summaries[1..].iter().any(|r| r.final_balance != first.final_balance));
+
+        let fb = &mc.final_balance;
+        assert!(fb.min <= fb.p5 && fb.p5 <= fb.median && fb.median <= fb.p95 && fb.p95 <= fb.max);
+        let mean: Decimal = mc.summaries.iter().map(|r| r.final_balance).sum::<Decimal>() / dec!(12);
+        assert_eq!(fb.mean, mean);
+        assert!(mc.max_drawdown.min >= Decimal::ZERO);
+        assert!(mc.probability_of_ruin >= Decimal::ZERO && mc.probability_of_ruin <= Decimal::ONE);
+        assert!(mc.probability_of_profit <= Decimal::ONE);
+    }
+
+    #[test]
+    fn test_distribution_stats() {
+        let values: Vec<Decimal> = (1..=11).map(Decimal::from).collect();
+        let stats = DistributionStats::from_values(&values);
+
+        assert_eq!(stats.mean, dec!(6));
+        assert_eq!(stats.median, dec!(6));
+        assert_eq!(stats.p5, dec!(1.5));
+        assert_eq!(stats.p95, dec!(10.5));
+        assert_eq!(stats.min, dec!(1));
+        assert_eq!(stats.max, dec!(11));
+
+        let empty = DistributionStats::from_values(&[]);
+        assert_eq!(empty.mean, Decimal::ZERO);
+    }
+
+    fn snapshot(ts: i64, price: Decimal, model: Option<Decimal>) -> HistoricalSnapshot {
+        HistoricalSnapshot {
+            timestamp: chrono::TimeZone::timestamp_opt(&Utc, ts, 0).unwrap(),
+            market_id: "m1".to_string(),
+            question: Some("Historical market?".to_string()),
+            yes_price: price,
+            volume: dec!(100000),
+            liquidity: dec!(50000),
+            model_probability: model,
+            confidence: model.map(|_| dec!(0.90)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_history_replay_uses_recorded_prices() {
+        let config = EnhancedDryRunConfig {
+            simulate_failures: false,
+            simulate_slippage: false,
+            simulate_partial_fills: false,
+            ..Default::default()
+        };
+        let history = vec![
+            snapshot(300, dec!(0.60), Some(dec!(0.60))),
+            snapshot(100, dec!(0.40), Some(dec!(0.60))),
+            snapshot(200, dec!(0.50), Some(dec!(0.60))),
+        ];
+
+        let mut sim = EnhancedDryRun::new(config).with_history(history);
+        let result = sim.run().await.unwrap();
+
+        // One step per timestamp, plus the starting point
+        assert_eq!(result.equity_curve.len(), 4);
+        assert!(result.total_trades >= 1);
+        assert_eq!(result.trades[0].entry_price, dec!(0.40));
+        assert_eq!(result.trades[0].side, Side::Buy);
+        for trade in &result.trades {
+            let exit = trade.exit_price.unwrap();
+            assert!([dec!(0.40), dec!(0.50), dec!(0.60)].contains(&exit));
+            assert!(trade.pnl >= Decimal::ZERO);
+        }
+        assert!(result.total_pnl > Decimal::ZERO);
+    }
+
+    #[tokio::test]
+    async fn test_steps_override_shortens_history_replay() {
+        let config = EnhancedDryRunConfig { steps: 2, ..Default::default() };
+        let history = vec![
+            snapshot(100, dec!(0.40), None),
+            snapshot(200, dec!(0.50), None),
+            snapshot(300, dec!(0.60), None),
+        ];
+
+        let result = EnhancedDryRun::new(config).with_history(history).run().await.unwrap();
+        assert_eq!(result.equity_curve.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_signal_on_held_token_is_filtered() {
+        let config = EnhancedDryRunConfig {
+            simulate_failures: false,
+            simulate_partial_fills: false,
+            ..Default::default()
+        };
+        let history = vec![snapshot(100, dec!(0.40), Some(dec!(0.60)))];
+        let mut sim = EnhancedDryRun::new(config).with_history(history);
+        let market = sim.history[0][0].to_market();
+        let signal = sim.signal_gen.generate(&market, &sim.history[0][0].prediction().unwrap()).unwrap();
+
+        sim.process_signal(&signal, &market).await.unwrap();
+        let balance = sim.current_balance;
+        let entry = sim.open_positions[&signal.token_id].entry_price;
+        sim.process_signal(&signal, &market).await.unwrap();
+
+        assert_eq!(sim.trade_counter, 1);
+        assert_eq!(sim.signals_filtered, 1);
+        assert_eq!(sim.current_balance, balance);
+        assert_eq!(sim.open_positions[&signal.token_id].entry_price, entry);
+    }
+
+    #[tokio::test]
+    async fn test_book_walk_uses_recorded_book() {
+        use crate::types::OrderBookLevel;
+
+        let config = EnhancedDryRunConfig {
+            simulate_failures: false,
+            simulate_partial_fills: false,
+            slippage_model: SlippageModel::BookWalk,
+            ..Default::default()
+        };
+        // Ten shares at the touch, then a 10c gap
+        let book = OrderBook {
+            bids: vec![],
+            asks: vec![
+                OrderBookLevel { price: dec!(0.40), size: dec!(10) },
+                OrderBookLevel { price: dec!(0.50), size: dec!(100000) },
+            ],
+        };
+        let history = vec![snapshot(100, dec!(0.40), Some(dec!(0.60)))];
+
+        let mut sim = EnhancedDryRun::new(config).with_history(history).with_order_book("m1_yes", book.clone());
+        let result = sim.run().await.unwrap();
+
+        let trade = &result.trades[0];
+        assert_eq!(trade.side, Side::Buy);
+        assert_eq!(trade.slippage, book_walk_slippage(&book, Side::Buy, trade.intended_size).round_dp(6));
+        // Far above what the flat factor would charge
+        assert!(trade.slippage > trade.intended_size * dec!(0.005) * dec!(1.5));
+    }
+
+    #[test]
+    fn test_apply_overrides() {
+        let mut config = EnhancedDryRunConfig::default();
+        config
+            .apply_overrides("initial_balance=500, simulate_failures=false,stop_on_drawdown_pct=0.2")
+            .unwrap();
+
+        assert_eq!(config.initial_balance, dec!(500));
+        assert!(!config.simulate_failures);
+        assert_eq!(config.stop_on_drawdown_pct, Some(dec!(0.2)));
+
+        config.apply_overrides("slippage_model=linear:0.00001").unwrap();
+        assert_eq!(config.slippage_model, SlippageModel::Linear { per_unit: dec!(0.00001) });
+        assert!(config.apply_overrides("slippage_model=impact").is_err());
+
+        assert!(config.apply_overrides("unknown=1").is_err());
+        assert!(config.apply_overrides("steps").is_err());
+        assert!(config.apply_overrides("use_dynamic_kelly=maybe").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_write_equity_curve() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("equity.csv");
+        let config = EnhancedDryRunConfig {
+            steps: 5,
+            ..Default::default()
+        };
+        let result = EnhancedDryRun::new(config).run().await.unwrap();
+
+        result.write_equity_curve(&path).unwrap();
+        let csv = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], "step,equity");
+        assert_eq!(lines.len(), result.equity_curve.len() + 1);
+        assert_eq!(lines[1], "0,10000");
+    }
 }
@@ -4,17 +4,56 @@
 
 use crate::types::{Market, Outcome, Signal, Side, Order, OrderType, Trade};
 use chrono::{Utc, Duration};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_decimal::prelude::FromPrimitive;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 
+/// Default seed for generated price paths
+const DEFAULT_SEED: u64 = 42;
+
+/// Per-step volatility of generated paths, in log-odds space
+const DEFAULT_PATH_VOLATILITY: f64 = 0.05;
+
 /// Generator for test data
 pub struct TestDataGenerator {
     counter: u32,
+    rng: StdRng,
+    path_volatility: f64,
+    /// (probability per step, fractional price drop)
+    jump: Option<(f64, f64)>,
 }
 
 impl TestDataGenerator {
     pub fn new() -> Self {
-        Self { counter: 0 }
+        Self {
+            counter: 0,
+            rng: StdRng::seed_from_u64(DEFAULT_SEED),
+            path_volatility: DEFAULT_PATH_VOLATILITY,
+            jump: None,
+        }
+    }
+
+    /// Reseed the RNG used for price paths
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = StdRng::seed_from_u64(seed);
+        self
+    }
+
+    /// Per-step volatility of price paths (log-odds units)
+    pub fn with_volatility(mut self, volatility: f64) -> Self {
+        self.path_volatility = volatility;
+        self
+    }
+
+    /// Inject flash-crash jumps into price paths
+    ///
+    /// At each step, with probability `prob`, every market drops by `size`
+    /// (e.g. 0.3 = -30%) at once, like a correlated crash.
+    pub fn with_jump(mut self, prob: f64, size: f64) -> Self {
+        self.jump = Some((prob.clamp(0.0, 1.0), size.clamp(0.0, 1.0)));
+        self
     }
 
     /// Generate a random market
@@ -44,6 +83,10 @@ impl TestDataGenerator {
             ],
             active: true,
             closed: false,
+            category: None,
+            tags: Vec::new(),
+            tick_size: None,
+            min_size: None,
         }
     }
 
@@ -71,6 +114,10 @@ impl TestDataGenerator {
             ],
             active: true,
             closed: false,
+            category: None,
+            tags: Vec::new(),
+            tick_size: None,
+            min_size: None,
         }
     }
 
@@ -92,9 +139,11 @@ impl TestDataGenerator {
             model_probability: model_prob,
             market_probability: market_prob,
             edge: model_prob - market_prob,
+            net_edge: model_prob - market_prob,
             confidence: dec!(0.75),
             suggested_size: dec!(0.03),
             timestamp: Utc::now(),
+            max_staleness: None,
         }
     }
 
@@ -107,6 +156,7 @@ impl TestDataGenerator {
             price: self.random_price(),
             size: Decimal::from(10 + (self.counter % 100)),
             order_type: OrderType::GTC,
+            client_order_id: None,
         }
     }
 
@@ -123,9 +173,82 @@ impl TestDataGenerator {
             size: Decimal::from(50),
             fee: dec!(0.50),
             timestamp: Utc::now(),
+            client_order_id: None,
+            correlation_id: None,
         }
     }
 
+    /// Generate aligned price paths with a uniform pairwise correlation
+    ///
+    /// Each market follows a random walk in log-odds space driven by a shared
+    /// factor plus idiosyncratic noise. Returns `n_markets` series of
+    /// `steps + 1` Yes prices (including the starting price).
+    ///
+    /// `correlation` is clamped to [-1/(n-1), 1], the range where a uniform
+    /// correlation matrix exists. The endpoints make the matrix singular, so
+    /// a tiny `PATH_JITTER` is added to its diagonal.
+    pub fn correlated_paths(&mut self, n_markets: usize, steps: usize, correlation: f64) -> Vec<Vec<Decimal>> {
+        let min_rho = if n_markets > 1 { -1.0 / (n_markets - 1) as f64 } else { -1.0 };
+        let rho = correlation.clamp(min_rho, 1.0);
+        let matrix: Vec<Vec<f64>> = (0..n_markets)
+            .map(|i| (0..n_markets).map(|j| if i == j { 1.0 + PATH_JITTER } else { rho }).collect())
+            .collect();
+        self.correlated_paths_with_matrix(&matrix, steps)
+    }
+
+    /// Generate aligned price paths from a full correlation matrix
+    ///
+    /// Panics if the matrix is not square, symmetric positive definite.
+    pub fn correlated_paths_with_matrix(&mut self, correlation: &[Vec<f64>], steps: usize) -> Vec<Vec<Decimal>> {
+        let n = correlation.len();
+        let chol = cholesky(correlation)
+            .expect("correlation matrix must be square and positive definite");
+        
+        let mut logits: Vec<f64> = (0..n)
+            .map(|_| {
+                let p: f64 = self.rng.random_range(0.30..0.70);
+                (p / (1.0 - p)).ln()
+            })
+            .collect();
+        let mut paths: Vec<Vec<Decimal>> = logits
+            .iter()
+            .map(|&x| {
+                let mut path = Vec::with_capacity(steps + 1);
+                path.push(to_price(x));
+                path
+            })
+            .collect();
+        
+        for _ in 0..steps {
+            let shocks: Vec<f64> = (0..n).map(|_| self.standard_normal()).collect();
+            let crash = match self.jump {
+                Some((prob, size)) if self.rng.random::<f64>() < prob => Some(size),
+                _ => None,
+            };
+            
+            for i in 0..n {
+                let correlated: f64 = (0..=i).map(|k| chol[i][k] * shocks[k]).sum();
+                logits[i] += self.path_volatility * correlated;
+                
+                if let Some(size) = crash {
+                    let p = sigmoid(logits[i]) * (1.0 - size);
+                    logits[i] = (p / (1.0 - p)).ln();
+                }
+                
+                paths[i].push(to_price(logits[i]));
+            }
+        }
+        
+        paths
+    }
+
+    /// Standard normal draw (Box-Muller)
+    fn standard_normal(&mut self) -> f64 {
+        let u1: f64 = self.rng.random_range(f64::EPSILON..1.0);
+        let u2: f64 = self.rng.random();
+        (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos()
+    }
+
     fn random_price(&self) -> Decimal {
         let base = (self.counter * 17 + 31) % 80 + 10;
         Decimal::new(base as i64, 2)
@@ -140,6 +263,48 @@ impl TestDataGenerator {
     }
 }
 
+/// Added to the diagonal of uniform correlation matrices so perfectly
+/// (anti-)correlated markets still factorize
+const PATH_JITTER: f64 = 1e-9;
+
+/// Lower-triangular Cholesky factor, `None` if not positive definite
+fn cholesky(matrix: &[Vec<f64>]) -> Option<Vec<Vec<f64>>> {
+    let n = matrix.len();
+    if matrix.iter().any(|row| row.len() != n) {
+        return None;
+    }
+    
+    let mut l = vec![vec![0.0; n]; n];
+    for i in 0..n {
+        for j in 0..=i {
+            if (matrix[i][j] - matrix[j][i]).abs() > 1e-9 {
+                return None;
+            }
+            let sum: f64 = (0..j).map(|k| l[i][k] * l[j][k]).sum();
+            if i == j {
+                let d = matrix[i][i] - sum;
+                if d <= 0.0 {
+                    return None;
+                }
+                l[i][j] = d.sqrt();
+            } else {
+                l[i][j] = (matrix[i][j] - sum) / l[j][j];
+            }
+        }
+    }
+    Some(l)
+}
+
+fn sigmoid(x: f64) -> f64 {
+    1.0 / (1.0 + (-x).exp())
+}
+
+/// Log-odds to a Yes price, kept strictly inside (0, 1)
+fn to_price(logit: f64) -> Decimal {
+    let p = sigmoid(logit).clamp(0.001, 0.999);
+    Decimal::from_f64(p).unwrap_or(dec!(0.5)).round_dp(4)
+}
+
 impl Default for TestDataGenerator {
     fn default() -> Self {
         Self::new()
@@ -194,4 +359,137 @@ mod tests {
         assert!(market.question.contains("BTC"));
         assert!(market.id.contains("btc"));
     }
+
+    fn returns(path: &[Decimal]) -> Vec<f64> {
+        use rust_decimal::prelude::ToPrimitive;
+        path.windows(2)
+            .map(|w| (w[1].to_f64().unwrap() / w[0].to_f64().unwrap()).ln())
+            .collect()
+    }
+
+    /// Step changes in log-odds, the space the paths are driven in
+    fn logit_steps(path: &[Decimal]) -> Vec<f64> {
+        use rust_decimal::prelude::ToPrimitive;
+        let logit = |p: &Decimal| {
+            let p = p.to_f64().unwrap();
+            (p / (1.0 - p)).ln()
+        };
+        path.windows(2).map(|w| logit(&w[1]) - logit(&w[0])).collect()
+    }
+
+    fn pearson(a: &[f64], b: &[f64]) -> f64 {
+        let n = a.len() as f64;
+        let mean_a = a.iter().sum::<f64>() / n;
+        let mean_b = b.iter().sum::<f64>() / n;
+        let cov: f64 = a.iter().zip(b).map(|(x, y)| (x - mean_a) * (y - mean_b)).sum();
+        let var_a: f64 = a.iter().map(|x| (x - mean_a).powi(2)).sum();
+        let var_b: f64 = b.iter().map(|y| (y - mean_b).powi(2)).sum();
+        cov / (var_a.sqrt() * var_b.sqrt())
+    }
+
+    #[test]
+    fn test_correlated_paths_shape() {
+        let mut gen = TestDataGenerator::new();
+        let paths = gen.correlated_paths(3, 100, 0.5);
+        
+        assert_eq!(paths.len(), 3);
+        for path in &paths {
+            assert_eq!(path.len(), 101);
+            assert!(path.iter().all(|p| *p > Decimal::ZERO && *p < Decimal::ONE));
+        }
+    }
+
+    #[test]
+    fn test_correlated_paths_match_requested_correlation() {
+        for target in [0.0, 0.5, 0.8] {
+            let mut gen = TestDataGenerator::new().with_seed(7);
+            let paths = gen.correlated_paths(3, 3000, target);
+            let rets: Vec<Vec<f64>> = paths.iter().map(|p| returns(p)).collect();
+            
+            for i in 0..rets.len() {
+                for j in (i + 1)..rets.len() {
+                    let corr = pearson(&rets[i], &rets[j]);
+                    assert!(
+                        (corr - target).abs() < 0.1,
+                        "pair ({}, {}): expected ~{}, got {:.3}", i, j, target, corr
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_correlated_paths_perfect_correlation() {
+        let mut gen = TestDataGenerator::new().with_seed(7);
+        let paths = gen.correlated_paths(3, 500, 1.0);
+        let rets: Vec<Vec<f64>> = paths.iter().map(|p| returns(p)).collect();
+        
+        assert!(pearson(&rets[0], &rets[1]) > 0.99);
+        assert!(pearson(&rets[1], &rets[2]) > 0.99);
+    }
+
+    #[test]
+    fn test_correlated_paths_negative_correlation() {
+        let mut gen = TestDataGenerator::new().with_seed(7);
+        let paths = gen.correlated_paths(3, 3000, -0.4);
+        // Opposed markets drift to opposite extremes, where price returns
+        // flatten, so compare the log-odds steps that drive them
+        let rets: Vec<Vec<f64>> = paths.iter().map(|p| logit_steps(p)).collect();
+        for (i, j) in [(0, 1), (0, 2), (1, 2)] {
+            let corr = pearson(&rets[i], &rets[j]);
+            assert!((corr + 0.4).abs() < 0.1, "pair ({}, {}): got {:.3}", i, j, corr);
+        }
+        
+        // Two markets can be perfectly opposed; anything lower is clamped
+        let pair = TestDataGenerator::new().with_seed(7).correlated_paths(2, 500, -3.0);
+        let rets: Vec<Vec<f64>> = pair.iter().map(|p| logit_steps(p)).collect();
+        assert!(pearson(&rets[0], &rets[1]) < -0.99);
+    }
+
+    #[test]
+    fn test_correlated_paths_with_matrix() {
+        let matrix = vec![
+            vec![1.0, 0.9, 0.0],
+            vec![0.9, 1.0, 0.0],
+            vec![0.0, 0.0, 1.0],
+        ];
+        let mut gen = TestDataGenerator::new();
+        let paths = gen.correlated_paths_with_matrix(&matrix, 3000);
+        let rets: Vec<Vec<f64>> = paths.iter().map(|p| returns(p)).collect();
+        
+        assert!(pearson(&rets[0], &rets[1]) > 0.8);
+        assert!(pearson(&rets[0], &rets[2]).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_correlated_paths_same_seed_is_reproducible() {
+        let a = TestDataGenerator::new().with_seed(3).correlated_paths(2, 50, 0.6);
+        let b = TestDataGenerator::new().with_seed(3).correlated_paths(2, 50, 0.6);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_jumps_crash_all_markets_together() {
+        let mut gen = TestDataGenerator::new()
+            .with_volatility(0.0)
+            .with_jump(0.1, 0.3);
+        let paths = gen.correlated_paths(3, 200, 0.5);
+        
+        let crash_steps: Vec<usize> = (1..paths[0].len())
+            .filter(|&t| paths[0][t] < paths[0][t - 1] * dec!(0.8))
+            .collect();
+        assert!(!crash_steps.is_empty());
+        for &t in &crash_steps {
+            for path in &paths {
+                assert!(path[t] < path[t - 1] * dec!(0.8));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "positive definite")]
+    fn test_invalid_correlation_matrix_panics() {
+        let matrix = vec![vec![1.0, 1.5], vec![1.5, 1.0]];
+        TestDataGenerator::new().correlated_paths_with_matrix(&matrix, 10);
+    }
 }
@@ -0,0 +1,277 @@
+//! Historical market data for backtests
+//!
+//! Loads market snapshots from CSV or JSONL. Each row is one market at one
+//! point in time; rows sharing a timestamp form one simulation step.
+//!
+//! CSV needs a header row with at least `timestamp,market_id,yes_price`.
+//! Optional columns: `question`, `volume`, `liquidity`, `model_probability`,
+//! `confidence`. Timestamps are RFC 3339 or unix seconds.
+
+use crate::model::Prediction;
+use crate::types::{Market, Outcome};
+use anyhow::Context;
+use chrono::{DateTime, TimeZone, Utc};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use serde::{Deserialize, Deserializer};
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::str::FromStr;
+
+/// One market observed at one point in time
+#[derive(Debug, Clone, Deserialize)]
+pub struct HistoricalSnapshot {
+    #[serde(deserialize_with = "deserialize_timestamp")]
+    pub timestamp: DateTime<Utc>,
+    pub market_id: String,
+    #[serde(default)]
+    pub question: Option<String>,
+    pub yes_price: Decimal,
+    #[serde(default = "default_volume")]
+    pub volume: Decimal,
+    #[serde(default = "default_liquidity")]
+    pub liquidity: Decimal,
+    /// Recorded model estimate; the simulator draws one when absent
+    #[serde(default)]
+    pub model_probability: Option<Decimal>,
+    #[serde(default)]
+    pub confidence: Option<Decimal>,
+}
+
+fn default_volume() -> Decimal {
+    dec!(100000)
+}
+
+fn default_liquidity() -> Decimal {
+    dec!(50000)
+}
+
+impl HistoricalSnapshot {
+    /// Market as the strategy would have seen it
+    pub fn to_market(&self) -> Market {
+        Market {
+            id: self.market_id.clone(),
+            question: self.question.clone().unwrap_or_else(|| self.market_id.clone()),
+            description: None,
+            end_date: None,
+            volume: self.volume,
+            liquidity: self.liquidity,
+            outcomes: vec![
+                Outcome {
+                    token_id: format!("{}_yes", self.market_id),
+                    outcome: "Yes".to_string(),
+                    price: self.yes_price,
+                },
+                Outcome {
+                    token_id: format!("{}_no", self.market_id),
+                    outcome: "No".to_string(),
+                    price: Decimal::ONE - self.yes_price,
+                },
+            ],
+            active: true,
+            closed: false,
+            category: None,
+            tags: Vec::new(),
+            tick_size: None,
+            min_size: None,
+        }
+    }
+
+    /// Recorded prediction, if the data has one
+    pub fn prediction(&self) -> Option<Prediction> {
+        self.model_probability.map(|probability| Prediction {
+            probability,
+            confidence: self.confidence.unwrap_or(dec!(0.75)),
+            reasoning: "Historical data".to_string(),
+        })
+    }
+}
+
+/// Load snapshots from a `.csv` or `.jsonl` file
+pub fn load_history<P: AsRef<Path>>(path: P) -> anyhow::Result<Vec<HistoricalSnapshot>> {
+    let path = path.as_ref();
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let is_csv = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("csv"));
+
+    if is_csv {
+        parse_csv(&content)
+    } else {
+        parse_jsonl(&content)
+    }
+}
+
+/// Parse JSON Lines, one snapshot per line
+pub fn parse_jsonl(content: &str) -> anyhow::Result<Vec<HistoricalSnapshot>> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).with_context(|| format!("Invalid snapshot on line {}", i + 1))
+        })
+        .collect()
+}
+
+/// Parse CSV with a header row
+pub fn parse_csv(content: &str) -> anyhow::Result<Vec<HistoricalSnapshot>> {
+    let mut lines = content.lines().enumerate().filter(|(_, l)| !l.trim().is_empty());
+    let (_, header) = lines.next().context("CSV file is empty")?;
+    let columns: Vec<String> = split_csv_line(header)
+        .into_iter()
+        .map(|c| c.trim().to_lowercase())
+        .collect();
+    let col = |name: &str| columns.iter().position(|c| c == name);
+
+    let timestamp_col = col("timestamp").context("CSV is missing a 'timestamp' column")?;
+    let market_col = col("market_id").context("CSV is missing a 'market_id' column")?;
+    let price_col = col("yes_price").context("CSV is missing a 'yes_price' column")?;
+    let question_col = col("question");
+    let volume_col = col("volume");
+    let liquidity_col = col("liquidity");
+    let model_col = col("model_probability");
+    let confidence_col = col("confidence");
+
+    let mut snapshots = Vec::new();
+    for (i, line) in lines {
+        let fields = split_csv_line(line);
+        let field = |idx: Option<usize>| {
+            idx.and_then(|i| fields.get(i))
+                .map(|f| f.trim())
+                .filter(|f| !f.is_empty())
+        };
+        let decimal = |idx: Option<usize>| -> anyhow::Result<Option<Decimal>> {
+            field(idx)
+                .map(|f| Decimal::from_str(f).with_context(|| format!("Invalid number '{}' on line {}", f, i + 1)))
+                .transpose()
+        };
+
+        let timestamp = field(Some(timestamp_col))
+            .with_context(|| format!("Missing timestamp on line {}", i + 1))?;
+        snapshots.push(HistoricalSnapshot {
+            timestamp: parse_timestamp(timestamp)
+                .with_context(|| format!("Invalid timestamp '{}' on line {}", timestamp, i + 1))?,
+            market_id: field(Some(market_col))
+                .with_context(|| format!("Missing market_id on line {}", i + 1))?
+                .to_string(),
+            question: field(question_col).map(str::to_string),
+            yes_price: decimal(Some(price_col))?
+                .with_context(|| format!("Missing yes_price on line {}", i + 1))?,
+            volume: decimal(volume_col)?.unwrap_or_else(default_volume),
+            liquidity: decimal(liquidity_col)?.unwrap_or_else(default_liquidity),
+            model_probability: decimal(model_col)?,
+            confidence: decimal(confidence_col)?,
+        });
+    }
+
+    Ok(snapshots)
+}
+
+/// Group snapshots into time-ordered steps
+pub fn group_by_timestamp(snapshots: Vec<HistoricalSnapshot>) -> Vec<Vec<HistoricalSnapshot>> {
+    let mut frames: BTreeMap<DateTime<Utc>, Vec<HistoricalSnapshot>> = BTreeMap::new();
+    for snapshot in snapshots {
+        frames.entry(snapshot.timestamp).or_default().push(snapshot);
+    }
+    frames.into_values().collect()
+}
+
+fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
+    if let Ok(secs) = value.parse::<i64>() {
+        return Utc.timestamp_opt(secs, 0).single();
+    }
+    DateTime::parse_from_rfc3339(value)
+        .ok()
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+fn deserialize_timestamp<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    let raw = match &value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Number(n) => n.to_string(),
+        _ => return Err(serde::de::Error::custom("timestamp must be a string or number")),
+    };
+    parse_timestamp(&raw)
+        .ok_or_else(|| serde::de::Error::custom(format!("invalid timestamp '{}'", raw)))
+}
+
+/// Split a CSV line, honouring double-quoted fields
+fn split_csv_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut current)),
+            _ => current.push(c),
+        }
+    }
+    fields.push(current);
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_csv() {
+        let csv = "timestamp,market_id,question,yes_price,liquidity,model_probability\n\
+                   1700000000,m1,\"Will it rain, today?\",0.40,20000,0.55\n\
+                   1700000000,m2,Other,0.70,,\n";
+        let snapshots = parse_csv(csv).unwrap();
+
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[0].question.as_deref(), Some("Will it rain, today?"));
+        assert_eq!(snapshots[0].yes_price, dec!(0.40));
+        assert_eq!(snapshots[0].liquidity, dec!(20000));
+        assert_eq!(snapshots[0].model_probability, Some(dec!(0.55)));
+        assert_eq!(snapshots[1].liquidity, default_liquidity());
+        assert!(snapshots[1].prediction().is_none());
+    }
+
+    #[test]
+    fn test_parse_csv_missing_column() {
+        let err = parse_csv("timestamp,market_id\n1,m1\n").unwrap_err();
+        assert!(err.to_string().contains("yes_price"));
+    }
+
+    #[test]
+    fn test_parse_jsonl() {
+        let jsonl = r#"{"timestamp":"2024-01-01T00:00:00Z","market_id":"m1","yes_price":"0.5"}
+{"timestamp":1704067260,"market_id":"m1","yes_price":0.55,"model_probability":0.7,"confidence":0.9}
+"#;
+        let snapshots = parse_jsonl(jsonl).unwrap();
+
+        assert_eq!(snapshots.len(), 2);
+        assert_eq!(snapshots[1].timestamp.timestamp(), 1704067260);
+        let prediction = snapshots[1].prediction().unwrap();
+        assert_eq!(prediction.probability, dec!(0.7));
+        assert_eq!(prediction.confidence, dec!(0.9));
+    }
+
+    #[test]
+    fn test_group_by_timestamp_orders_steps() {
+        let csv = "timestamp,market_id,yes_price\n20,m1,0.5\n10,m1,0.4\n10,m2,0.6\n";
+        let frames = group_by_timestamp(parse_csv(csv).unwrap());
+
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].len(), 2);
+        assert_eq!(frames[1][0].yes_price, dec!(0.5));
+    }
+}
@@ -4,18 +4,46 @@
 
 use crate::client::mock::{MockClobClient, MockGammaClient, MockScenarioBuilder, ClobClientTrait, GammaClientTrait};
 use crate::config::{StrategyConfig, RiskConfig};
-use crate::strategy::SignalGenerator;
+use crate::error::{BotError, Result};
+use crate::executor::Executor;
+use crate::fusion::FusionEngine;
+use crate::model::{MockLlmModel, Prediction};
+use crate::risk::RiskManager;
+use crate::strategy::signal_filter::SignalFilter;
+use crate::strategy::{
+    run_loop, run_scan, scan_filters, FilterChain, LoopPass, MarketSkipCache, ModelSignalProvider, RunLimits, ScanContext, ScanReport, SignalGenerator,
+};
+use async_trait::async_trait;
 use crate::types::{Market, Side, Order, OrderType, Outcome};
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
+use std::collections::HashMap;
 use std::time::Instant;
 use chrono::{Utc, Duration};
 
+/// A main loop pass against mocks: fetch the balance, then scan
+struct ScanPass<'a> {
+    ctx: ScanContext<'a, MockGammaClient, MockClobClient>,
+    reports: Vec<ScanReport>,
+}
+
+#[async_trait(?Send)]
+impl LoopPass for ScanPass<'_> {
+    async fn run_pass(&mut self) -> Result<std::time::Duration> {
+        let balance = self.ctx.executor.clob.get_balance().await?;
+        self.reports.push(run_scan(&self.ctx, balance).await?);
+        Ok(std::time::Duration::ZERO)
+    }
+}
+
 /// Integration test harness for running component tests
 pub struct IntegrationTestHarness {
     clob: MockClobClient,
     gamma: MockGammaClient,
     signal_gen: SignalGenerator,
+    skip_cache: Option<MarketSkipCache>,
+    scan_concurrency: usize,
+    scan_filters: FilterChain,
     test_results: Vec<TestResult>,
 }
 
@@ -37,10 +65,87 @@ impl IntegrationTestHarness {
             clob,
             gamma,
             signal_gen: SignalGenerator::new(StrategyConfig::default(), RiskConfig::default()),
+            skip_cache: None,
+            scan_concurrency: StrategyConfig::default().scan_concurrency,
+            scan_filters: scan_filters(&StrategyConfig::default()),
             test_results: Vec::new(),
         }
     }
 
+    /// Skip unchanged markets in scans, as the main loop does
+    pub fn with_skip_cache(mut self, cache: MarketSkipCache) -> Self {
+        self.skip_cache = Some(cache);
+        self
+    }
+
+    /// Scan with a different strategy config
+    pub fn with_strategy(mut self, config: StrategyConfig) -> Self {
+        self.scan_concurrency = config.scan_concurrency;
+        self.scan_filters = scan_filters(&config);
+        self.signal_gen = SignalGenerator::new(config, RiskConfig::default());
+        self
+    }
+
+    /// Run one iteration of the main scan loop against mock clients
+    ///
+    /// Markets are served by a `MockGammaClient`, predictions by a stub model
+    /// (markets without one are skipped), and signals are executed on a fresh
+    /// `MockClobClient` with a $10,000 balance.
+    pub async fn run_full_scan_once(
+        &self,
+        markets: Vec<Market>,
+        predictions: HashMap<String, Prediction>,
+    ) -> Result<ScanReport> {
+        let limits = RunLimits { max_iterations: Some(1), duration: None };
+        let mut reports = self.run_bounded_scans(markets, predictions, limits).await?;
+        reports.pop().ok_or_else(|| BotError::Internal("No scan ran".into()))
+    }
+
+    /// Run the main loop until `limits` is reached, with no wait between scans
+    ///
+    /// Uses the same mock setup as `run_full_scan_once`, shared across scans.
+    /// Errors if `limits` is unbounded.
+    pub async fn run_bounded_scans(
+        &self,
+        markets: Vec<Market>,
+        predictions: HashMap<String, Prediction>,
+        limits: RunLimits,
+    ) -> Result<Vec<ScanReport>> {
+        if limits.max_iterations.is_none() && limits.duration.is_none() {
+            return Err(BotError::Internal("Bounded scan needs max_iterations or duration".into()));
+        }
+
+        let (clob, gamma) = MockScenarioBuilder::new()
+            .with_balance(dec!(10000))
+            .with_markets(markets)
+            .build();
+
+        let model = MockLlmModel::with_predictions(predictions);
+        let signals = ModelSignalProvider { model: &model, signal_gen: &self.signal_gen };
+        let executor = Executor::new(clob, RiskConfig::default());
+        let risk_manager = tokio::sync::Mutex::new(RiskManager::new(RiskConfig::default()));
+        let signal_filter = SignalFilter::new();
+        let fusion = tokio::sync::RwLock::new(FusionEngine::new());
+
+        let mut pass = ScanPass {
+            ctx: ScanContext {
+                gamma: &gamma,
+                executor: &executor,
+                signals: &signals,
+                risk_manager: &risk_manager,
+                signal_filter: &signal_filter,
+                filters: &self.scan_filters,
+                skip_cache: self.skip_cache.as_ref(),
+                market_state: None,
+                concurrency: self.scan_concurrency,
+                fusion: Some(&fusion),
+            },
+            reports: Vec::new(),
+        };
+        run_loop(&mut pass, limits, std::future::pending::<()>()).await?;
+        Ok(pass.reports)
+    }
+
     /// Run all integration tests
     pub async fn run_all(&mut self) -> Vec<TestResult> {
         self.test_results.clear();
@@ -84,6 +189,7 @@ impl IntegrationTestHarness {
             price: dec!(0.55),
             size: dec!(100),
             order_type: OrderType::GTC,
+            client_order_id: None,
         };
         
         match self.clob.place_order(&order).await {
@@ -125,6 +231,10 @@ impl IntegrationTestHarness {
             ],
             active: true,
             closed: false,
+            category: None,
+            tags: Vec::new(),
+            tick_size: None,
+            min_size: None,
         };
 
         let prediction = crate::model::Prediction {
@@ -158,6 +268,7 @@ impl IntegrationTestHarness {
             price: dec!(0.50),
             size: dec!(100000), // Very large order
             order_type: OrderType::GTC,
+            client_order_id: None,
         };
         
         match self.clob.place_order(&order).await {
@@ -183,6 +294,7 @@ impl IntegrationTestHarness {
             price: dec!(0.50),
             size: dec!(10),
             order_type: OrderType::GTC,
+            client_order_id: None,
         };
         let _ = self.clob.place_order(&order).await;
         
@@ -270,6 +382,7 @@ impl Default for IntegrationTestHarness {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use async_trait::async_trait;
 
     #[tokio::test]
     async fn test_harness_creation() {
@@ -288,6 +401,281 @@ mod tests {
         assert!(passed > 0, "At least some tests should pass");
     }
 
+    fn scan_market(id: &str, yes_price: Decimal) -> Market {
+        Market {
+            id: id.to_string(),
+            question: format!("Will {} happen?", id),
+            description: None,
+            end_date: Some(Utc::now() + Duration::days(30)),
+            volume: dec!(100000),
+            liquidity: dec!(50000),
+            outcomes: vec![
+                Outcome {
+                    token_id: format!("{}_yes", id),
+                    outcome: "Yes".to_string(),
+                    price: yes_price,
+                },
+                Outcome {
+                    token_id: format!("{}_no", id),
+                    outcome: "No".to_string(),
+                    price: Decimal::ONE - yes_price,
+                },
+            ],
+            active: true,
+            closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
+        }
+    }
+
+    fn prediction(probability: Decimal) -> Prediction {
+        Prediction {
+            probability,
+            confidence: dec!(0.90),
+            reasoning: "stub".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_full_scan_trades_only_edge_market() {
+        let harness = IntegrationTestHarness::new();
+        let markets = vec![
+            scan_market("edge", dec!(0.40)),
+            scan_market("fair", dec!(0.50)),
+        ];
+        let predictions = HashMap::from([
+            ("edge".to_string(), prediction(dec!(0.60))),
+            ("fair".to_string(), prediction(dec!(0.505))),
+        ]);
+        
+        let report = harness.run_full_scan_once(markets, predictions).await.unwrap();
+        
+        assert_eq!(report.markets.len(), 2);
+        assert!(report.blocked.is_none());
+        assert!(report.failures.is_empty());
+        assert_eq!(report.signals.len(), 1);
+        assert_eq!(report.trades.len(), 1);
+        
+        let trade = &report.trades[0];
+        assert_eq!(trade.market_id, "edge");
+        assert_eq!(trade.token_id, "edge_yes");
+        assert_eq!(trade.side, Side::Buy);
+        assert!(trade.size > Decimal::ZERO);
+        // Tagged with the scan span's correlation id
+        let correlation_id = trade.correlation_id.as_deref().unwrap();
+        assert!(uuid::Uuid::parse_str(correlation_id).is_ok());
+
+        // Credited to the model, calling for the bought token
+        let sources = report.sources_for(&trade.id).unwrap();
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0].source, crate::fusion::SignalSource::MLPredictor);
+        assert!(sources[0].direction > 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_low_confidence_signal_abstained_despite_edge() {
+        let harness = IntegrationTestHarness::new().with_strategy(StrategyConfig {
+            abstain_below_confidence: Some(dec!(0.80)),
+            ..StrategyConfig::default()
+        });
+        let markets = vec![scan_market("bold", dec!(0.40)), scan_market("steady", dec!(0.40))];
+        let predictions = HashMap::from([
+            // 35% edge, but only 65% confident: clears min_confidence, not the floor
+            ("bold".to_string(), Prediction { confidence: dec!(0.65), ..prediction(dec!(0.75)) }),
+            ("steady".to_string(), prediction(dec!(0.52))),
+        ]);
+
+        let report = harness.run_full_scan_once(markets, predictions).await.unwrap();
+
+        assert_eq!(report.abstentions.len(), 1);
+        let (abstained, reason) = &report.abstentions[0];
+        assert_eq!(abstained.market_id, "bold");
+        assert!(reason.contains("abstention floor"), "{}", reason);
+        assert_eq!(report.signals.len(), 1);
+        assert_eq!(report.trades.len(), 1);
+        assert_eq!(report.trades[0].market_id, "steady");
+    }
+
+    #[tokio::test]
+    async fn test_liquidity_floor_comes_from_strategy_config() {
+        let thin = || Market { liquidity: dec!(5000), ..scan_market("thin", dec!(0.40)) };
+        let predictions = || HashMap::from([("thin".to_string(), prediction(dec!(0.52)))]);
+
+        // Below the default $10,000 floor
+        let report = IntegrationTestHarness::new().run_full_scan_once(vec![thin()], predictions()).await.unwrap();
+        assert!(report.signals.is_empty());
+
+        let harness = IntegrationTestHarness::new().with_strategy(StrategyConfig {
+            min_liquidity: dec!(2000),
+            ..StrategyConfig::default()
+        });
+        let report = harness.run_full_scan_once(vec![thin()], predictions()).await.unwrap();
+        assert_eq!(report.signals.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_full_scan_skips_markets_without_prediction() {
+        let harness = IntegrationTestHarness::new();
+        let markets = vec![scan_market("unknown", dec!(0.40))];
+        
+        let report = harness.run_full_scan_once(markets, HashMap::new()).await.unwrap();
+        
+        assert_eq!(report.markets.len(), 1);
+        assert!(report.signals.is_empty());
+        assert!(report.trades.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_bounded_run_stops_after_max_iterations() {
+        let harness = IntegrationTestHarness::new();
+        let markets = vec![scan_market("edge", dec!(0.40))];
+        let limits = RunLimits { max_iterations: Some(2), duration: None };
+        
+        let reports = harness.run_bounded_scans(markets, HashMap::new(), limits).await.unwrap();
+        
+        assert_eq!(reports.len(), 2);
+        assert!(reports.iter().all(|r| r.markets.len() == 1));
+    }
+
+    /// Pass that bails out the way the main loop does on a failed fetch
+    struct BailingPass {
+        runs: u32,
+    }
+
+    #[async_trait(?Send)]
+    impl LoopPass for BailingPass {
+        async fn run_pass(&mut self) -> Result<std::time::Duration> {
+            self.runs += 1;
+            Ok(std::time::Duration::from_secs(60))
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_early_exit_passes_count_toward_limits() {
+        let mut pass = BailingPass { runs: 0 };
+        let limits = RunLimits { max_iterations: Some(2), duration: None };
+        let summary = run_loop(&mut pass, limits, std::future::pending::<()>()).await.unwrap();
+        assert_eq!(pass.runs, 2);
+        assert_eq!(summary.iterations, 2);
+        assert_eq!(summary.stop_reason, "Reached 2 iterations");
+
+        // The deadline cuts the second 60s wait short
+        let mut pass = BailingPass { runs: 0 };
+        let limits = RunLimits { max_iterations: None, duration: Some(std::time::Duration::from_secs(90)) };
+        let summary = run_loop(&mut pass, limits, std::future::pending::<()>()).await.unwrap();
+        assert_eq!(pass.runs, 2);
+        assert_eq!(summary.stop_reason, "Run duration elapsed");
+        assert_eq!(summary.elapsed, std::time::Duration::from_secs(90));
+
+        // Shutdown stops the loop during a wait
+        let mut pass = BailingPass { runs: 0 };
+        let summary = run_loop(&mut pass, RunLimits::default(), std::future::ready(())).await.unwrap();
+        assert_eq!(pass.runs, 1);
+        assert_eq!(summary.stop_reason, "Ctrl-C");
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_market_skipped_on_second_scan() {
+        let harness = IntegrationTestHarness::new().with_skip_cache(MarketSkipCache::new());
+        let markets = vec![scan_market("fair", dec!(0.50))];
+        let predictions = HashMap::from([("fair".to_string(), prediction(dec!(0.505)))]);
+        let limits = RunLimits { max_iterations: Some(2), duration: None };
+
+        let reports = harness.run_bounded_scans(markets, predictions, limits).await.unwrap();
+
+        assert_eq!(reports[0].skipped_unchanged, 0);
+        assert_eq!(reports[1].skipped_unchanged, 1);
+        assert!(reports.iter().all(|r| r.signals.is_empty()));
+    }
+
+    #[tokio::test]
+    async fn test_denylisted_market_never_reaches_model() {
+        let harness = IntegrationTestHarness::new().with_strategy(StrategyConfig {
+            denylist: vec!["edge".to_string()],
+            ..StrategyConfig::default()
+        });
+        let markets = vec![scan_market("edge", dec!(0.40)), scan_market("fair", dec!(0.50))];
+        let predictions = HashMap::from([
+            ("edge".to_string(), prediction(dec!(0.60))),
+            ("fair".to_string(), prediction(dec!(0.505))),
+        ]);
+
+        let report = harness.run_full_scan_once(markets, predictions).await.unwrap();
+
+        assert_eq!(report.excluded, 1);
+        assert!(report.trades.is_empty());
+    }
+
+    /// Signal provider that records how many markets it analyzes at once
+    #[derive(Default)]
+    struct CountingSignals {
+        in_flight: std::sync::atomic::AtomicUsize,
+        peak: std::sync::atomic::AtomicUsize,
+        analyzed: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait]
+    impl crate::strategy::SignalProvider for CountingSignals {
+        async fn signal_for(&self, _market: &Market) -> Option<crate::types::Signal> {
+            use std::sync::atomic::Ordering;
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+            self.analyzed.fetch_add(1, Ordering::SeqCst);
+            None
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_scan_analyzes_markets_concurrently_up_to_limit() {
+        use std::sync::atomic::Ordering;
+
+        let markets: Vec<Market> = (0..8).map(|i| scan_market(&format!("m{}", i), dec!(0.50))).collect();
+        let (clob, gamma) = MockScenarioBuilder::new().with_markets(markets).build();
+        let executor = Executor::new(clob, RiskConfig::default());
+        let risk_manager = tokio::sync::Mutex::new(RiskManager::new(RiskConfig::default()));
+        let signal_filter = SignalFilter::new();
+        let signals = CountingSignals::default();
+        let ctx = ScanContext {
+            gamma: &gamma,
+            executor: &executor,
+            signals: &signals,
+            risk_manager: &risk_manager,
+            signal_filter: &signal_filter,
+            filters: &FilterChain::new(),
+            skip_cache: None,
+            market_state: None,
+            concurrency: 3,
+            fusion: None,
+        };
+
+        let started = tokio::time::Instant::now();
+        run_scan(&ctx, dec!(10000)).await.unwrap();
+
+        assert_eq!(signals.analyzed.load(Ordering::SeqCst), 8);
+        assert_eq!(signals.peak.load(Ordering::SeqCst), 3);
+        // Three rounds of 100ms rather than eight
+        assert!(started.elapsed() < std::time::Duration::from_millis(400));
+    }
+
+    #[tokio::test]
+    async fn test_bounded_run_stops_after_duration() {
+        let harness = IntegrationTestHarness::new();
+        let limits = RunLimits {
+            max_iterations: None,
+            duration: Some(std::time::Duration::ZERO),
+        };
+        
+        let reports = harness.run_bounded_scans(vec![], HashMap::new(), limits).await.unwrap();
+        assert_eq!(reports.len(), 1);
+        
+        let unbounded = harness.run_bounded_scans(vec![], HashMap::new(), RunLimits::default()).await;
+        assert!(unbounded.is_err());
+    }
+
     #[tokio::test]
     async fn test_report_generation() {
         let mut harness = IntegrationTestHarness::new();
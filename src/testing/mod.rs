@@ -7,6 +7,8 @@
 //! - Optimized simulator for A/B testing
 //! - Performance benchmarks
 //! - Test data generators
+//! - Historical data loading for backtests
+//! - Slippage models for simulated fills
 //! - Boundary condition tests
 
 pub mod dry_run;
@@ -15,6 +17,8 @@ pub mod generators;
 pub mod benchmarks;
 pub mod enhanced_dry_run;
 pub mod optimized_simulator;
+pub mod history;
+pub mod slippage;
 
 #[cfg(test)]
 mod boundary_tests;
@@ -24,5 +28,16 @@ mod performance_tests;
 pub use dry_run::{DryRunSimulator, SimulationResult, SimulatedTrade};
 pub use integration::IntegrationTestHarness;
 pub use generators::TestDataGenerator;
-pub use enhanced_dry_run::{EnhancedDryRun, EnhancedDryRunConfig, EnhancedSimResult};
-pub use optimized_simulator::EnhancedDryRunSimulator;
+pub use enhanced_dry_run::{
+    EnhancedDryRun, EnhancedDryRunConfig, EnhancedSimResult,
+    monte_carlo, MonteCarloResult, MonteCarloRun, DistributionStats,
+};
+pub use history::{load_history, HistoricalSnapshot};
+pub use slippage::SlippageModel;
+pub use optimized_simulator::{
+    EnhancedDryRunSimulator, walk_forward, evaluate_parameters,
+    ParameterGrid, ParameterSet, SeriesPoint, WalkForwardConfig, WalkForwardReport,
+    WalkForwardWindow, WindowMetrics,
+    compare, bootstrap_mean_difference, ArmSummary, ComparisonData, ComparisonReport,
+    SignificanceTest, SimulatorConfig,
+};
@@ -5,17 +5,23 @@
 //! - Configurable stop-loss, take-profit, trailing-stop
 //! - Trade rate limiting
 //! - Full parameter customization
+//! - Walk-forward parameter optimization over a market-data series
+//! - A/B comparison of two configs with a bootstrap significance test
 
 use crate::client::mock::{MockClobClient, MockGammaClient, GammaClientTrait};
 use crate::config::{RiskConfig, StrategyConfig};
-use crate::strategy::SignalGenerator;
+use crate::strategy::{DynamicKelly, DynamicKellyConfig, SignalGenerator};
 use crate::types::{Market, Side};
 use crate::model::Prediction;
 use chrono::{DateTime, Utc};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
+use std::ops::Range;
 
 /// Simulation result compatible with comparison
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -35,6 +41,8 @@ pub struct SimulationResult {
     pub max_drawdown: Decimal,
     pub sharpe_ratio: Decimal,
     pub sortino_ratio: Decimal,
+    /// Return over max drawdown for the run (not annualized)
+    pub calmar_ratio: Decimal,
     pub profit_factor: Decimal,
     pub trades: Vec<SimTrade>,
     pub equity_curve: Vec<(u32, Decimal)>,
@@ -80,6 +88,22 @@ pub enum ExitReason {
     MaxHoldTime,
 }
 
+/// Independent random draws made for a market at each step
+#[derive(Debug, Clone, Copy)]
+enum RandomStream {
+    Probability,
+    Confidence,
+    Mark,
+    Close,
+}
+
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
 #[derive(Debug)]
 struct OpenPosition {
     market_id: String,
@@ -313,7 +337,7 @@ impl EnhancedDryRunSimulator {
         
         for (market_id, pos) in positions {
             // Simulate current price
-            let noise = (self.random() - dec!(0.5)) * dec!(0.08);
+            let noise = (self.random(&market_id, RandomStream::Mark) - dec!(0.5)) * dec!(0.08);
             let current_price = (pos.entry_price + pos.edge * dec!(0.5) + noise)
                 .max(dec!(0.01))
                 .min(dec!(0.99));
@@ -440,7 +464,7 @@ impl EnhancedDryRunSimulator {
         
         // Now calculate and close
         for (market_id, entry_price, edge) in exit_data {
-            let noise = (self.random() - dec!(0.5)) * dec!(0.05);
+            let noise = (self.random(&market_id, RandomStream::Close) - dec!(0.5)) * dec!(0.05);
             let exit_price = (entry_price + edge + noise)
                 .max(dec!(0.01))
                 .min(dec!(0.99));
@@ -452,11 +476,11 @@ impl EnhancedDryRunSimulator {
     
     fn generate_prediction(&mut self, market: &Market) -> Prediction {
         let base = market.yes_price().unwrap_or(dec!(0.5));
-        let variance = (self.random() - dec!(0.5)) * dec!(0.20);
+        let variance = (self.random(&market.id, RandomStream::Probability) - dec!(0.5)) * dec!(0.20);
         let prob = (base + variance).max(dec!(0.05)).min(dec!(0.95));
         
         // Confidence varies more realistically
-        let confidence = dec!(0.5) + self.random() * dec!(0.45);
+        let confidence = dec!(0.5) + self.random(&market.id, RandomStream::Confidence) * dec!(0.45);
         
         Prediction {
             probability: prob,
@@ -484,9 +508,18 @@ impl EnhancedDryRunSimulator {
         }
     }
     
-    fn random(&mut self) -> Decimal {
-        self.random_seed = self.random_seed.wrapping_mul(1103515245).wrapping_add(12345);
-        Decimal::from(self.random_seed % 10000) / dec!(10000)
+    /// Uniform draw in [0, 1) fixed by the seed, step, market and stream
+    ///
+    /// Keyed rather than sequential, so every config run with the same seed
+    /// sees the same predictions and price moves whatever it traded before.
+    fn random(&self, market_id: &str, stream: RandomStream) -> Decimal {
+        let market = market_id
+            .bytes()
+            .fold(0xcbf2_9ce4_8422_2325u64, |h, b| (h ^ u64::from(b)).wrapping_mul(0x100_0000_01b3));
+        let key = splitmix64(
+            self.random_seed ^ splitmix64(market ^ splitmix64((u64::from(self.current_step) << 8) | stream as u64)),
+        );
+        Decimal::from(key % 10000) / dec!(10000)
     }
     
     /// Get simulation results
@@ -542,6 +575,7 @@ impl EnhancedDryRunSimulator {
             max_drawdown: self.max_drawdown * dec!(100),
             sharpe_ratio: self.calculate_sharpe(),
             sortino_ratio: self.calculate_sortino(),
+            calmar_ratio: self.calculate_calmar(pnl_pct),
             profit_factor,
             trades: self.trades.clone(),
             equity_curve: self.equity_curve.clone(),
@@ -592,6 +626,18 @@ impl EnhancedDryRunSimulator {
         }
     }
     
+    /// Run return over max drawdown, both in percent
+    fn calculate_calmar(&self, pnl_pct: Decimal) -> Decimal {
+        let drawdown_pct = self.max_drawdown * dec!(100);
+        if drawdown_pct > dec!(0) {
+            pnl_pct / drawdown_pct
+        } else if pnl_pct > dec!(0) {
+            dec!(99.99)
+        } else {
+            dec!(0)
+        }
+    }
+    
     fn calculate_sortino(&self) -> Decimal {
         if self.equity_curve.len() < 2 {
             return dec!(0);
@@ -629,6 +675,534 @@ impl EnhancedDryRunSimulator {
     }
 }
 
+// ============================================================================
+// Walk-forward optimization
+// ============================================================================
+
+/// One observation in a market-data series
+#[derive(Debug, Clone)]
+pub struct SeriesPoint {
+    /// Market as seen at entry (Yes price = entry price)
+    pub market: Market,
+    /// Model prediction available at entry
+    pub prediction: Prediction,
+    /// Yes price at exit (1/0 for a resolved market)
+    pub exit_price: Decimal,
+}
+
+/// A candidate parameter combination
+#[derive(Debug, Clone)]
+pub struct ParameterSet {
+    pub strategy: StrategyConfig,
+    pub kelly: DynamicKellyConfig,
+}
+
+impl ParameterSet {
+    /// Short label for report tables
+    pub fn label(&self) -> String {
+        format!(
+            "edge={} conf={} kelly={}",
+            self.strategy.min_edge, self.strategy.min_confidence, self.kelly.base_fraction
+        )
+    }
+}
+
+/// Cartesian grid over the tunable parameters
+#[derive(Debug, Clone)]
+pub struct ParameterGrid {
+    pub min_edge: Vec<Decimal>,
+    pub min_confidence: Vec<Decimal>,
+    pub kelly_base_fraction: Vec<Decimal>,
+    /// Values for everything not on the grid
+    pub base_strategy: StrategyConfig,
+    pub base_kelly: DynamicKellyConfig,
+}
+
+impl ParameterGrid {
+    pub fn new(
+        min_edge: Vec<Decimal>,
+        min_confidence: Vec<Decimal>,
+        kelly_base_fraction: Vec<Decimal>,
+    ) -> Self {
+        Self {
+            min_edge,
+            min_confidence,
+            kelly_base_fraction,
+            base_strategy: StrategyConfig::default(),
+            base_kelly: DynamicKellyConfig::default(),
+        }
+    }
+
+    /// All parameter combinations, edge-major
+    pub fn combinations(&self) -> Vec<ParameterSet> {
+        let mut sets = Vec::new();
+        for &min_edge in &self.min_edge {
+            for &min_confidence in &self.min_confidence {
+                for &base_fraction in &self.kelly_base_fraction {
+                    sets.push(ParameterSet {
+                        strategy: StrategyConfig {
+                            min_edge,
+                            min_confidence,
+                            ..self.base_strategy.clone()
+                        },
+                        kelly: DynamicKellyConfig {
+                            base_fraction,
+                            ..self.base_kelly.clone()
+                        },
+                    });
+                }
+            }
+        }
+        sets
+    }
+}
+
+/// Walk-forward window sizes (in series points)
+#[derive(Debug, Clone)]
+pub struct WalkForwardConfig {
+    pub in_sample: usize,
+    pub out_of_sample: usize,
+    /// How far each window rolls forward (usually `out_of_sample`)
+    pub step: usize,
+    pub initial_balance: Decimal,
+}
+
+impl WalkForwardConfig {
+    /// In-sample/out-of-sample ranges for a series of `len` points
+    pub fn windows(&self, len: usize) -> Vec<(Range<usize>, Range<usize>)> {
+        let mut windows = Vec::new();
+        if self.in_sample == 0 || self.out_of_sample == 0 || self.step == 0 {
+            return windows;
+        }
+        
+        let mut start = 0;
+        while start + self.in_sample + self.out_of_sample <= len {
+            let split = start + self.in_sample;
+            windows.push((start..split, split..split + self.out_of_sample));
+            start += self.step;
+        }
+        windows
+    }
+}
+
+/// Performance of one parameter set over one window
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WindowMetrics {
+    pub trades: u32,
+    pub final_balance: Decimal,
+    pub pnl: Decimal,
+    pub pnl_pct: Decimal,
+    pub win_rate: Decimal,
+    pub max_drawdown: Decimal,
+}
+
+/// Chosen parameters and their in/out-of-sample results for one window
+#[derive(Debug, Clone)]
+pub struct WalkForwardWindow {
+    pub in_sample: Range<usize>,
+    pub out_of_sample: Range<usize>,
+    pub chosen_index: usize,
+    pub chosen: ParameterSet,
+    pub in_sample_metrics: WindowMetrics,
+    pub out_of_sample_metrics: WindowMetrics,
+}
+
+/// Result of a walk-forward optimization
+#[derive(Debug, Clone)]
+pub struct WalkForwardReport {
+    pub windows: Vec<WalkForwardWindow>,
+}
+
+impl WalkForwardReport {
+    /// Sum of out-of-sample P&L across windows
+    pub fn total_oos_pnl(&self) -> Decimal {
+        self.windows.iter().map(|w| w.out_of_sample_metrics.pnl).sum()
+    }
+
+    /// Markdown table of chosen params and OOS metrics per window
+    pub fn to_table(&self) -> String {
+        let mut table = String::new();
+        table.push_str("| Window | In-sample | Out-of-sample | Params | IS P&L % | OOS P&L % | OOS Trades | OOS Win Rate | OOS Max DD |\n");
+        table.push_str("|--------|-----------|---------------|--------|----------|-----------|------------|--------------|------------|\n");
+        
+        for (i, w) in self.windows.iter().enumerate() {
+            let oos = &w.out_of_sample_metrics;
+            table.push_str(&format!(
+                "| {} | {}..{} | {}..{} | {} | {:.2}% | {:.2}% | {} | {:.1}% | {:.2}% |\n",
+                i + 1,
+                w.in_sample.start, w.in_sample.end,
+                w.out_of_sample.start, w.out_of_sample.end,
+                w.chosen.label(),
+                w.in_sample_metrics.pnl_pct,
+                oos.pnl_pct,
+                oos.trades,
+                oos.win_rate * dec!(100),
+                oos.max_drawdown * dec!(100),
+            ));
+        }
+        
+        table.push_str(&format!("\n**Total OOS P&L**: ${:.2}\n", self.total_oos_pnl()));
+        table
+    }
+}
+
+/// Replay a slice of the series with one parameter set
+pub fn evaluate_parameters(
+    params: &ParameterSet,
+    series: &[SeriesPoint],
+    initial_balance: Decimal,
+) -> WindowMetrics {
+    let signal_gen = SignalGenerator::new(params.strategy.clone(), RiskConfig::default());
+    let kelly = DynamicKelly::new(params.kelly.clone(), initial_balance);
+    
+    let mut balance = initial_balance;
+    let mut peak = initial_balance;
+    let mut max_drawdown = Decimal::ZERO;
+    let mut trades = 0u32;
+    let mut wins = 0u32;
+    
+    for point in series {
+        let Some(signal) = signal_gen.generate(&point.market, &point.prediction) else {
+            continue;
+        };
+        
+        // Selling Yes is a bet on No, priced at 1 - p
+        let (model_prob, entry, exit) = match signal.side {
+            Side::Buy => (signal.model_probability, signal.market_probability, point.exit_price),
+            Side::Sell => (
+                Decimal::ONE - signal.model_probability,
+                Decimal::ONE - signal.market_probability,
+                Decimal::ONE - point.exit_price,
+            ),
+        };
+        if entry <= Decimal::ZERO {
+            continue;
+        }
+        
+        let fraction = kelly
+            .calculate_position_size(model_prob, entry, signal.confidence, Decimal::ONE, None)
+            .effective_fraction;
+        let size = fraction * balance;
+        if size < dec!(1) {
+            continue;
+        }
+        
+        let pnl = size * (exit / entry - Decimal::ONE);
+        balance += pnl;
+        trades += 1;
+        if pnl > Decimal::ZERO {
+            wins += 1;
+        }
+        kelly.record_trade(pnl);
+        kelly.update_account_value(balance);
+        
+        if balance > peak {
+            peak = balance;
+        } else if peak > Decimal::ZERO {
+            max_drawdown = max_drawdown.max((peak - balance) / peak);
+        }
+    }
+    
+    let pnl = balance - initial_balance;
+    WindowMetrics {
+        trades,
+        final_balance: balance,
+        pnl,
+        pnl_pct: if initial_balance > Decimal::ZERO {
+            pnl / initial_balance * dec!(100)
+        } else {
+            dec!(0)
+        },
+        win_rate: if trades > 0 {
+            Decimal::from(wins) / Decimal::from(trades)
+        } else {
+            dec!(0)
+        },
+        max_drawdown,
+    }
+}
+
+/// Pick the best parameters on each in-sample window and score them out of sample
+///
+/// Selection maximizes in-sample P&L; ties keep the earlier grid entry.
+pub fn walk_forward(
+    series: &[SeriesPoint],
+    grid: &[ParameterSet],
+    config: &WalkForwardConfig,
+) -> WalkForwardReport {
+    let mut windows = Vec::new();
+    if grid.is_empty() {
+        return WalkForwardReport { windows };
+    }
+    
+    for (in_sample, out_of_sample) in config.windows(series.len()) {
+        let mut best: Option<(usize, WindowMetrics)> = None;
+        for (i, params) in grid.iter().enumerate() {
+            let metrics = evaluate_parameters(params, &series[in_sample.clone()], config.initial_balance);
+            if best.as_ref().is_none_or(|(_, b)| metrics.pnl > b.pnl) {
+                best = Some((i, metrics));
+            }
+        }
+        
+        let Some((chosen_index, in_sample_metrics)) = best else {
+            continue;
+        };
+        let chosen = grid[chosen_index].clone();
+        let out_of_sample_metrics =
+            evaluate_parameters(&chosen, &series[out_of_sample.clone()], config.initial_balance);
+        
+        windows.push(WalkForwardWindow {
+            in_sample,
+            out_of_sample,
+            chosen_index,
+            chosen,
+            in_sample_metrics,
+            out_of_sample_metrics,
+        });
+    }
+    
+    WalkForwardReport { windows }
+}
+
+// ============================================================================
+// A/B comparison
+// ============================================================================
+
+/// Bootstrap resamples used by `compare`
+const BOOTSTRAP_RESAMPLES: u32 = 2000;
+/// p-value below which a difference is reported as significant
+const SIGNIFICANCE_LEVEL: Decimal = dec!(0.05);
+
+/// Settings for one side of an A/B comparison
+#[derive(Debug, Clone, Default)]
+pub struct SimulatorConfig {
+    pub strategy: StrategyConfig,
+    pub risk: RiskConfig,
+    pub stop_loss: Option<Decimal>,
+    pub take_profit: Option<Decimal>,
+    pub trailing_stop: Option<Decimal>,
+    pub max_trades_per_hour: Option<u32>,
+}
+
+impl SimulatorConfig {
+    pub fn new(strategy: StrategyConfig, risk: RiskConfig) -> Self {
+        Self {
+            strategy,
+            risk,
+            ..Default::default()
+        }
+    }
+
+    fn simulator(&self, data: &ComparisonData) -> EnhancedDryRunSimulator {
+        let mut sim = EnhancedDryRunSimulator::new(data.initial_balance, self.strategy.clone(), self.risk.clone())
+            .with_markets(data.markets.clone())
+            .with_seed(data.seed);
+        if let Some(pct) = self.stop_loss {
+            sim = sim.with_stop_loss(pct);
+        }
+        if let Some(pct) = self.take_profit {
+            sim = sim.with_take_profit(pct);
+        }
+        if let Some(pct) = self.trailing_stop {
+            sim = sim.with_trailing_stop(pct);
+        }
+        if let Some(max) = self.max_trades_per_hour {
+            sim = sim.with_max_trades_per_hour(max);
+        }
+        sim
+    }
+}
+
+/// Markets, length and seed shared by both sides of a comparison
+#[derive(Debug, Clone)]
+pub struct ComparisonData {
+    pub markets: Vec<Market>,
+    pub steps: u32,
+    pub initial_balance: Decimal,
+    pub seed: u64,
+}
+
+/// Headline metrics for one side of a comparison
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArmSummary {
+    pub config: ConfigSnapshot,
+    pub final_balance: Decimal,
+    pub total_pnl: Decimal,
+    pub pnl_pct: Decimal,
+    pub total_trades: u32,
+    pub win_rate: Decimal,
+    pub mean_trade_pnl: Decimal,
+    pub max_drawdown: Decimal,
+    pub sharpe_ratio: Decimal,
+    pub sortino_ratio: Decimal,
+    pub calmar_ratio: Decimal,
+    pub profit_factor: Decimal,
+}
+
+impl ArmSummary {
+    fn from_result(result: &SimulationResult) -> Self {
+        Self {
+            config: result.config_used.clone(),
+            final_balance: result.final_balance,
+            total_pnl: result.total_pnl,
+            pnl_pct: result.pnl_pct,
+            total_trades: result.total_trades,
+            win_rate: result.win_rate,
+            mean_trade_pnl: mean(&trade_pnls(result)),
+            max_drawdown: result.max_drawdown,
+            sharpe_ratio: result.sharpe_ratio,
+            sortino_ratio: result.sortino_ratio,
+            calmar_ratio: result.calmar_ratio,
+            profit_factor: result.profit_factor,
+        }
+    }
+}
+
+/// Bootstrap test of the difference in mean per-trade PnL (A - B)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignificanceTest {
+    pub resamples: u32,
+    pub mean_difference: Decimal,
+    /// 95% percentile interval of the resampled difference
+    pub ci_low: Decimal,
+    pub ci_high: Decimal,
+    /// Two-sided share of resamples on the far side of zero
+    pub p_value: Decimal,
+    pub significant: bool,
+}
+
+/// Side-by-side results of two configs over the same market path
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComparisonReport {
+    pub seed: u64,
+    pub steps: u32,
+    pub a: ArmSummary,
+    pub b: ArmSummary,
+    /// Total PnL of A minus that of B
+    pub pnl_difference: Decimal,
+    pub significance: SignificanceTest,
+}
+
+impl ComparisonReport {
+    /// Markdown table of both sides' metrics and the test result
+    pub fn to_table(&self) -> String {
+        let rows = [
+            ("Final Balance", format!("${:.2}", self.a.final_balance), format!("${:.2}", self.b.final_balance)),
+            ("Total P&L", format!("${:.2}", self.a.total_pnl), format!("${:.2}", self.b.total_pnl)),
+            ("Return", format!("{:.2}%", self.a.pnl_pct), format!("{:.2}%", self.b.pnl_pct)),
+            ("Trades", self.a.total_trades.to_string(), self.b.total_trades.to_string()),
+            ("Win Rate", format!("{:.1}%", self.a.win_rate), format!("{:.1}%", self.b.win_rate)),
+            ("Mean P&L/Trade", format!("${:.4}", self.a.mean_trade_pnl), format!("${:.4}", self.b.mean_trade_pnl)),
+            ("Max Drawdown", format!("{:.2}%", self.a.max_drawdown), format!("{:.2}%", self.b.max_drawdown)),
+            ("Sharpe", format!("{:.2}", self.a.sharpe_ratio), format!("{:.2}", self.b.sharpe_ratio)),
+            ("Sortino", format!("{:.2}", self.a.sortino_ratio), format!("{:.2}", self.b.sortino_ratio)),
+            ("Calmar", format!("{:.2}", self.a.calmar_ratio), format!("{:.2}", self.b.calmar_ratio)),
+            ("Profit Factor", format!("{:.2}", self.a.profit_factor), format!("{:.2}", self.b.profit_factor)),
+        ];
+
+        let mut table = String::new();
+        table.push_str("| Metric | A | B |\n");
+        table.push_str("|--------|---|---|\n");
+        for (metric, a, b) in rows {
+            table.push_str(&format!("| {} | {} | {} |\n", metric, a, b));
+        }
+
+        let s = &self.significance;
+        table.push_str(&format!(
+            "\n**Mean P&L/trade A - B**: ${:.4} (95% CI ${:.4} to ${:.4}, p = {:.3}, {})\n",
+            s.mean_difference,
+            s.ci_low,
+            s.ci_high,
+            s.p_value,
+            if s.significant { "significant" } else { "not significant" },
+        ));
+        table
+    }
+}
+
+/// Run `a` and `b` over the same markets and seed and compare the results
+pub async fn compare(
+    a: &SimulatorConfig,
+    b: &SimulatorConfig,
+    data: &ComparisonData,
+) -> anyhow::Result<ComparisonReport> {
+    let mut sim_a = a.simulator(data);
+    sim_a.run_for(data.steps, 0).await?;
+    let result_a = sim_a.get_results().await?;
+
+    let mut sim_b = b.simulator(data);
+    sim_b.run_for(data.steps, 0).await?;
+    let result_b = sim_b.get_results().await?;
+
+    Ok(ComparisonReport {
+        seed: data.seed,
+        steps: data.steps,
+        pnl_difference: result_a.total_pnl - result_b.total_pnl,
+        significance: bootstrap_mean_difference(
+            &trade_pnls(&result_a),
+            &trade_pnls(&result_b),
+            BOOTSTRAP_RESAMPLES,
+            data.seed,
+        ),
+        a: ArmSummary::from_result(&result_a),
+        b: ArmSummary::from_result(&result_b),
+    })
+}
+
+/// Bootstrap the difference in means between `a` and `b`
+///
+/// Each resample draws both samples with replacement. With either sample
+/// empty there is nothing to test and the result is not significant.
+pub fn bootstrap_mean_difference(a: &[Decimal], b: &[Decimal], resamples: u32, seed: u64) -> SignificanceTest {
+    let observed = mean(a) - mean(b);
+    if a.is_empty() || b.is_empty() || resamples == 0 {
+        return SignificanceTest {
+            resamples: 0,
+            mean_difference: observed,
+            ci_low: observed,
+            ci_high: observed,
+            p_value: Decimal::ONE,
+            significant: false,
+        };
+    }
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut resample_mean = |values: &[Decimal]| {
+        let total: Decimal = (0..values.len()).map(|_| values[rng.random_range(0..values.len())]).sum();
+        total / Decimal::from(values.len())
+    };
+    let mut diffs: Vec<Decimal> = (0..resamples).map(|_| resample_mean(a) - resample_mean(b)).collect();
+    diffs.sort();
+
+    let at_or_below = diffs.iter().filter(|d| **d <= Decimal::ZERO).count();
+    let at_or_above = diffs.iter().filter(|d| **d >= Decimal::ZERO).count();
+    let p_value = (Decimal::from(2 * at_or_below.min(at_or_above)) / Decimal::from(resamples)).min(Decimal::ONE);
+    let percentile = |q: Decimal| {
+        let index = (q * Decimal::from(resamples - 1)).round().to_usize().unwrap_or(0);
+        diffs[index.min(diffs.len() - 1)]
+    };
+
+    SignificanceTest {
+        resamples,
+        mean_difference: observed,
+        ci_low: percentile(dec!(0.025)),
+        ci_high: percentile(dec!(0.975)),
+        p_value,
+        significant: p_value < SIGNIFICANCE_LEVEL,
+    }
+}
+
+fn trade_pnls(result: &SimulationResult) -> Vec<Decimal> {
+    result.trades.iter().map(|t| t.pnl).collect()
+}
+
+fn mean(values: &[Decimal]) -> Decimal {
+    if values.is_empty() {
+        return Decimal::ZERO;
+    }
+    values.iter().sum::<Decimal>() / Decimal::from(values.len())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -711,4 +1285,202 @@ mod tests {
         // Should have at most 2 trades per hour
         println!("Total trades with rate limit: {}", result.total_trades);
     }
+
+    fn series_point(id: usize, yes_price: Decimal, model_prob: Decimal, exit_price: Decimal) -> SeriesPoint {
+        SeriesPoint {
+            market: Market {
+                id: format!("m{}", id),
+                question: format!("Market {}?", id),
+                description: None,
+                end_date: None,
+                volume: dec!(100000),
+                liquidity: dec!(50000),
+                outcomes: vec![
+                    crate::types::Outcome {
+                        token_id: format!("m{}_yes", id),
+                        outcome: "Yes".to_string(),
+                        price: yes_price,
+                    },
+                    crate::types::Outcome {
+                        token_id: format!("m{}_no", id),
+                        outcome: "No".to_string(),
+                        price: Decimal::ONE - yes_price,
+                    },
+                ],
+                active: true,
+                closed: false,
+                tags: Vec::new(),
+                category: None,
+                tick_size: None,
+                min_size: None,
+            },
+            prediction: Prediction {
+                probability: model_prob,
+                confidence: dec!(0.90),
+                reasoning: "test".to_string(),
+            },
+            exit_price,
+        }
+    }
+
+    #[test]
+    fn test_walk_forward_windows() {
+        let config = WalkForwardConfig {
+            in_sample: 4,
+            out_of_sample: 2,
+            step: 2,
+            initial_balance: dec!(1000),
+        };
+        
+        let windows = config.windows(10);
+        assert_eq!(windows, vec![
+            (0..4, 4..6),
+            (2..6, 6..8),
+            (4..8, 8..10),
+        ]);
+        assert!(config.windows(5).is_empty());
+    }
+
+    #[test]
+    fn test_parameter_grid_combinations() {
+        let grid = ParameterGrid::new(
+            vec![dec!(0.05), dec!(0.15)],
+            vec![dec!(0.6)],
+            vec![dec!(0.1), dec!(0.25)],
+        );
+        let sets = grid.combinations();
+        
+        assert_eq!(sets.len(), 4);
+        assert_eq!(sets[0].strategy.min_edge, dec!(0.05));
+        assert_eq!(sets[1].kelly.base_fraction, dec!(0.25));
+        assert_eq!(sets[3].strategy.min_edge, dec!(0.15));
+    }
+
+    #[test]
+    fn test_walk_forward_selects_on_in_sample() {
+        // Small edges (8%) lose, large edges (20%) win: a high min_edge
+        // filter should be chosen and only trade the winners
+        let mut series = Vec::new();
+        for i in 0..12 {
+            if i % 2 == 0 {
+                series.push(series_point(i, dec!(0.50), dec!(0.58), dec!(0.30)));
+            } else {
+                series.push(series_point(i, dec!(0.50), dec!(0.70), dec!(0.80)));
+            }
+        }
+        
+        let grid = ParameterGrid::new(
+            vec![dec!(0.05), dec!(0.15)],
+            vec![dec!(0.6)],
+            vec![dec!(0.25)],
+        ).combinations();
+        let config = WalkForwardConfig {
+            in_sample: 6,
+            out_of_sample: 2,
+            step: 2,
+            initial_balance: dec!(1000),
+        };
+        
+        let report = walk_forward(&series, &grid, &config);
+        
+        assert_eq!(report.windows.len(), 3);
+        for window in &report.windows {
+            assert_eq!(window.chosen_index, 1);
+            assert_eq!(window.chosen.strategy.min_edge, dec!(0.15));
+            assert_eq!(window.in_sample.len(), 6);
+            assert_eq!(window.out_of_sample.len(), 2);
+            assert_eq!(window.out_of_sample_metrics.trades, 1);
+            assert!(window.out_of_sample_metrics.pnl > Decimal::ZERO);
+        }
+        assert!(report.total_oos_pnl() > Decimal::ZERO);
+        
+        let table = report.to_table();
+        assert!(table.contains("edge=0.15"));
+        assert_eq!(table.lines().filter(|l| l.starts_with("| ") && !l.starts_with("| Window")).count(), 3);
+    }
+
+    #[test]
+    fn test_walk_forward_empty_grid() {
+        let series: Vec<SeriesPoint> = (0..10)
+            .map(|i| series_point(i, dec!(0.50), dec!(0.70), dec!(0.80)))
+            .collect();
+        let config = WalkForwardConfig {
+            in_sample: 4,
+            out_of_sample: 2,
+            step: 2,
+            initial_balance: dec!(1000),
+        };
+        
+        assert!(walk_forward(&series, &[], &config).windows.is_empty());
+    }
+
+    fn comparison_data() -> ComparisonData {
+        ComparisonData {
+            markets: (0..6)
+                .map(|i| series_point(i, dec!(0.40) + Decimal::from(i as u32) * dec!(0.04), dec!(0.5), dec!(0.5)).market)
+                .collect(),
+            steps: 200,
+            initial_balance: dec!(1000),
+            seed: 99,
+        }
+    }
+
+    fn loose_strategy() -> StrategyConfig {
+        StrategyConfig {
+            min_edge: dec!(0.01),
+            min_confidence: dec!(0.30),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compare_flags_dominant_config() {
+        // A banks each gain and recycles the capital; B sits in every
+        // position until the max hold time
+        let b = SimulatorConfig::new(loose_strategy(), RiskConfig::default());
+        let a = SimulatorConfig {
+            take_profit: Some(dec!(0.005)),
+            ..b.clone()
+        };
+
+        let report = compare(&a, &b, &comparison_data()).await.unwrap();
+
+        assert!(report.a.total_trades > 10 && report.b.total_trades > 10);
+        assert!(report.pnl_difference > Decimal::ZERO);
+        assert!(report.significance.mean_difference > Decimal::ZERO);
+        assert!(report.significance.ci_low > Decimal::ZERO);
+        assert!(report.significance.significant);
+
+        // Serializable for logging
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["seed"], 99);
+        assert_eq!(json["significance"]["significant"], true);
+    }
+
+    #[tokio::test]
+    async fn test_compare_identical_configs_not_significant() {
+        let a = SimulatorConfig::new(loose_strategy(), RiskConfig::default());
+
+        let report = compare(&a, &a, &comparison_data()).await.unwrap();
+
+        // Same seed, same path
+        assert_eq!(report.pnl_difference, Decimal::ZERO);
+        assert!(!report.significance.significant);
+    }
+
+    #[test]
+    fn test_bootstrap_mean_difference() {
+        let high: Vec<Decimal> = (0..30).map(|i| dec!(10) + Decimal::from(i % 5)).collect();
+        let low: Vec<Decimal> = (0..30).map(|i| Decimal::from(i % 5)).collect();
+
+        let test = bootstrap_mean_difference(&high, &low, 500, 1);
+        assert_eq!(test.mean_difference, dec!(10));
+        assert!(test.ci_low > dec!(9) && test.ci_high < dec!(11));
+        assert_eq!(test.p_value, Decimal::ZERO);
+        assert!(test.significant);
+
+        let empty = bootstrap_mean_difference(&high, &[], 500, 1);
+        assert_eq!(empty.p_value, Decimal::ONE);
+        assert!(!empty.significant);
+    }
 }
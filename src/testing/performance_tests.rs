@@ -87,6 +87,7 @@ mod tests {
             volatility: dec!(0.08),
             liquidity_score: dec!(0.7),
             time_pressure: dec!(0.3),
+            regime: None,
         };
         
         let iterations = 10_000;
@@ -330,6 +331,7 @@ mod tests {
             volatility: dec!(0.08),
             liquidity_score: dec!(0.7),
             time_pressure: dec!(0.3),
+            regime: None,
         };
         
         let mut total_time_ns = 0u128;
@@ -381,6 +383,10 @@ mod tests {
             ],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         }
     }
 
@@ -408,6 +414,10 @@ mod tests {
             ],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         }
     }
 }
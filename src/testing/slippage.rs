@@ -0,0 +1,180 @@
+//! Slippage models for the dry-run simulators
+//!
+//! A flat percentage charges a $50 order and a $50k order the same rate,
+//! which flatters large sizes. `Linear` grows the rate with size, and
+//! `BookWalk` prices the order against a recorded or synthetic book so the
+//! cost reflects the depth it actually eats through.
+
+use crate::types::{walk_levels, OrderBook, OrderBookLevel, Side};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use std::str::FromStr;
+
+/// Price step between synthetic book levels
+const SYNTHETIC_TICK: Decimal = dec!(0.01);
+/// Levels per side of a synthetic book
+const SYNTHETIC_LEVELS: u32 = 10;
+
+/// How simulated fills are charged for slippage
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SlippageModel {
+    /// `slippage_factor` of the notional, with random jitter
+    #[default]
+    Flat,
+    /// Rate grows by `per_unit` for every USD of notional
+    Linear { per_unit: Decimal },
+    /// Walk the order book level by level
+    BookWalk,
+}
+
+impl SlippageModel {
+    /// Parse `flat`, `book_walk` or `linear:<per_unit>`
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "flat" => Some(Self::Flat),
+            "book_walk" | "bookwalk" => Some(Self::BookWalk),
+            other => {
+                let per_unit = other.strip_prefix("linear:")?;
+                Decimal::from_str(per_unit.trim())
+                    .ok()
+                    .filter(|p| *p >= Decimal::ZERO)
+                    .map(|per_unit| Self::Linear { per_unit })
+            }
+        }
+    }
+}
+
+/// USD slippage of a `Linear` model on `notional`
+pub fn linear_slippage(per_unit: Decimal, notional: Decimal) -> Decimal {
+    notional * notional * per_unit
+}
+
+/// USD slippage of spending `notional` against `book`
+///
+/// The order is sized in shares at the touch and walked through the asks
+/// (buys) or bids (sells). Anything the book cannot fill is charged at the
+/// last level's price. An empty side costs nothing.
+pub fn book_walk_slippage(book: &OrderBook, side: Side, notional: Decimal) -> Decimal {
+    let levels = match side {
+        Side::Buy => &book.asks,
+        Side::Sell => &book.bids,
+    };
+    let (Some(best), Some(last)) = (levels.first(), levels.last()) else {
+        return Decimal::ZERO;
+    };
+    if notional <= Decimal::ZERO || best.price <= Decimal::ZERO {
+        return Decimal::ZERO;
+    }
+
+    let shares = notional / best.price;
+    let (filled, value) = walk_levels(levels, shares);
+    let walked = (value - filled * best.price).abs();
+    let overflow = (shares - filled) * (last.price - best.price).abs();
+    walked + overflow
+}
+
+/// Book around `price` with `liquidity` USD spread evenly across both sides
+///
+/// Each side has ten levels one cent apart, the best at `price` itself.
+/// Levels outside (0, 1) are dropped.
+pub fn synthetic_book(price: Decimal, liquidity: Decimal) -> OrderBook {
+    let per_level = liquidity / Decimal::from(SYNTHETIC_LEVELS * 2);
+    let level = |price: Decimal| {
+        (price > Decimal::ZERO && price < Decimal::ONE && per_level > Decimal::ZERO).then(|| OrderBookLevel {
+            price,
+            size: per_level / price,
+        })
+    };
+
+    OrderBook {
+        bids: (0..SYNTHETIC_LEVELS)
+            .filter_map(|i| level(price - SYNTHETIC_TICK * Decimal::from(i)))
+            .collect(),
+        asks: (0..SYNTHETIC_LEVELS)
+            .filter_map(|i| level(price + SYNTHETIC_TICK * Decimal::from(i)))
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Flat charge without jitter, i.e. its midpoint
+    fn flat(notional: Decimal) -> Decimal {
+        notional * dec!(0.005)
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(SlippageModel::parse("flat"), Some(SlippageModel::Flat));
+        assert_eq!(SlippageModel::parse("Book_Walk"), Some(SlippageModel::BookWalk));
+        assert_eq!(
+            SlippageModel::parse("linear:0.0001"),
+            Some(SlippageModel::Linear { per_unit: dec!(0.0001) })
+        );
+        assert_eq!(SlippageModel::parse("linear:-1"), None);
+        assert_eq!(SlippageModel::parse("linear"), None);
+        assert_eq!(SlippageModel::parse("walk"), None);
+    }
+
+    #[test]
+    fn test_synthetic_book_shape() {
+        let book = synthetic_book(dec!(0.50), dec!(20000));
+
+        assert_eq!(book.asks.len(), 10);
+        assert_eq!(book.best_ask(), Some(dec!(0.50)));
+        assert_eq!(book.asks[9].price, dec!(0.59));
+        assert_eq!(book.bids[9].price, dec!(0.41));
+        // $1000 of depth per level
+        assert_eq!(book.asks[0].size, dec!(2000));
+
+        // Levels past the price range are dropped
+        assert_eq!(synthetic_book(dec!(0.95), dec!(20000)).asks.len(), 5);
+        assert!(synthetic_book(dec!(0.50), Decimal::ZERO).asks.is_empty());
+    }
+
+    #[test]
+    fn test_book_walk_cheaper_than_flat_for_small_order() {
+        let book = synthetic_book(dec!(0.50), dec!(20000));
+
+        // $100 fits in the first $1000 level
+        assert_eq!(book_walk_slippage(&book, Side::Buy, dec!(100)), Decimal::ZERO);
+        assert!(book_walk_slippage(&book, Side::Buy, dec!(100)) < flat(dec!(100)));
+    }
+
+    #[test]
+    fn test_book_walk_dearer_than_flat_for_large_order() {
+        let book = synthetic_book(dec!(0.50), dec!(20000));
+
+        // $5000 walks six of the $1000 levels, well past any flat jitter
+        let slippage = book_walk_slippage(&book, Side::Buy, dec!(5000));
+        assert!(slippage > flat(dec!(5000)) * dec!(1.5));
+
+        // Rate grows with size, unlike the flat factor
+        let small_rate = book_walk_slippage(&book, Side::Buy, dec!(1500)) / dec!(1500);
+        assert!(slippage / dec!(5000) > small_rate);
+    }
+
+    #[test]
+    fn test_book_walk_sell_and_overflow() {
+        let book = synthetic_book(dec!(0.50), dec!(2000));
+
+        // Sells walk down the bids
+        assert!(book_walk_slippage(&book, Side::Sell, dec!(300)) > Decimal::ZERO);
+        assert_eq!(book_walk_slippage(&book, Side::Sell, dec!(50)), Decimal::ZERO);
+
+        // Past the last level the remainder pays the worst price
+        let all = book_walk_slippage(&book, Side::Buy, dec!(1000));
+        let beyond = book_walk_slippage(&book, Side::Buy, dec!(2000));
+        assert_eq!(beyond - all, dec!(1000) / dec!(0.50) * dec!(0.09));
+        assert_eq!(book_walk_slippage(&OrderBook::default(), Side::Buy, dec!(100)), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_linear_slippage() {
+        let per_unit = dec!(0.000001);
+        assert_eq!(linear_slippage(per_unit, dec!(100)), dec!(0.01));
+        assert_eq!(linear_slippage(per_unit, dec!(10000)), dec!(100));
+    }
+}
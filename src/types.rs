@@ -3,6 +3,7 @@
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// A prediction market
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,6 +17,18 @@ pub struct Market {
     pub outcomes: Vec<Outcome>,
     pub active: bool,
     pub closed: bool,
+    /// Gamma category, e.g. "Crypto" or "Politics"
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Gamma tag labels
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Price increment the CLOB accepts; `None` if Gamma gave none
+    #[serde(default)]
+    pub tick_size: Option<Decimal>,
+    /// Smallest order the CLOB accepts, in shares
+    #[serde(default)]
+    pub min_size: Option<Decimal>,
 }
 
 /// An outcome (Yes/No) in a market
@@ -26,6 +39,84 @@ pub struct Outcome {
     pub price: Decimal,  // 0.00 - 1.00
 }
 
+/// A share price clamped into the [0, 1] range shares trade and settle in
+///
+/// Keeps the value as received so an out-of-range tick (e.g. 1.05 from a
+/// bad feed) is flagged as an anomaly rather than silently clamped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CheckedPrice {
+    /// The price clamped into [0, 1]
+    pub price: Decimal,
+    /// The price as received
+    pub raw: Decimal,
+}
+
+impl CheckedPrice {
+    pub fn new(raw: Decimal) -> Self {
+        Self { price: clamp_price(raw), raw }
+    }
+
+    /// Whether the raw price was outside [0, 1]
+    pub fn is_anomaly(&self) -> bool {
+        self.price != self.raw
+    }
+}
+
+/// Whether `price` is a possible share price, i.e. within [0, 1]
+pub fn is_valid_price(price: Decimal) -> bool {
+    (Decimal::ZERO..=Decimal::ONE).contains(&price)
+}
+
+/// Clamp `price` into [0, 1]
+pub fn clamp_price(price: Decimal) -> Decimal {
+    price.clamp(Decimal::ZERO, Decimal::ONE)
+}
+
+/// How outcome names are matched to a logical side such as "yes"
+///
+/// Markets do not all name their outcomes Yes/No: crypto price markets
+/// use Up/Down and some binaries name candidates. A matcher lets the
+/// strategy find the side it means regardless of the label.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum OutcomeMatcher {
+    /// The outcome name must equal the logical side exactly
+    Exact,
+    /// Names are compared ignoring ASCII case
+    CaseInsensitive,
+    /// Case-insensitive, with outcome names mapped to logical sides first
+    /// (e.g. `up = "yes"`)
+    Synonyms { synonyms: HashMap<String, String> },
+}
+
+impl Default for OutcomeMatcher {
+    /// Case-insensitive with Up/Down read as Yes/No
+    fn default() -> Self {
+        Self::Synonyms {
+            synonyms: HashMap::from([
+                ("up".to_string(), "yes".to_string()),
+                ("down".to_string(), "no".to_string()),
+            ]),
+        }
+    }
+}
+
+impl OutcomeMatcher {
+    /// Whether the outcome named `name` is the `logical` side
+    pub fn matches(&self, name: &str, logical: &str) -> bool {
+        match self {
+            Self::Exact => name == logical,
+            Self::CaseInsensitive => name.eq_ignore_ascii_case(logical),
+            Self::Synonyms { synonyms } => {
+                name.eq_ignore_ascii_case(logical)
+                    || synonyms
+                        .iter()
+                        .any(|(alias, side)| alias.eq_ignore_ascii_case(name) && side.eq_ignore_ascii_case(logical))
+            }
+        }
+    }
+}
+
 /// Order side
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
@@ -53,6 +144,10 @@ pub struct Order {
     pub price: Decimal,
     pub size: Decimal,
     pub order_type: OrderType,
+    /// Client-generated idempotency key; resubmitting with the same key
+    /// yields the same order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_order_id: Option<String>,
 }
 
 /// Order status from the exchange
@@ -63,6 +158,19 @@ pub struct OrderStatus {
     pub filled_size: Decimal,
     pub remaining_size: Decimal,
     pub avg_price: Option<Decimal>,
+    /// Fee charged on the filled portion
+    #[serde(default)]
+    pub fee: Decimal,
+}
+
+/// An order resting on one token's book
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpenOrder {
+    pub order_id: String,
+    pub token_id: String,
+    pub side: Side,
+    pub price: Decimal,
+    pub remaining_size: Decimal,
 }
 
 /// A trading signal generated by the strategy
@@ -73,10 +181,17 @@ pub struct Signal {
     pub side: Side,
     pub model_probability: Decimal,
     pub market_probability: Decimal,
+    /// Raw edge: model probability minus market probability
     pub edge: Decimal,
+    /// Edge left after estimated round-trip fees and slippage (same sign as `edge`)
+    pub net_edge: Decimal,
     pub confidence: Decimal,
     pub suggested_size: Decimal,
+    /// When the signal was generated
     pub timestamp: DateTime<Utc>,
+    /// How long the signal may wait for execution; `None` uses the
+    /// executor's `max_signal_age_secs`
+    pub max_staleness: Option<chrono::Duration>,
 }
 
 /// Portfolio position
@@ -103,23 +218,177 @@ pub struct Trade {
     pub size: Decimal,
     pub fee: Decimal,
     pub timestamp: DateTime<Utc>,
+    /// Idempotency key of the order that produced this trade
+    #[serde(default)]
+    pub client_order_id: Option<String>,
+    /// Correlation id of the event chain (book update, signal, order) that
+    /// led to this trade
+    #[serde(default)]
+    pub correlation_id: Option<String>,
+}
+
+/// Order book for one token, best level first on each side
+///
+/// Shared by the CLOB client and the liquidity monitor; see
+/// `risk::OrderBookSnapshot::from_book`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OrderBook {
+    pub bids: Vec<OrderBookLevel>,
+    pub asks: Vec<OrderBookLevel>,
+}
+
+/// Single level in order book
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderBookLevel {
+    pub price: Decimal,
+    pub size: Decimal,
+}
+
+impl OrderBook {
+    /// Get best bid price
+    pub fn best_bid(&self) -> Option<Decimal> {
+        self.bids.first().map(|l| l.price)
+    }
+
+    /// Get best ask price
+    pub fn best_ask(&self) -> Option<Decimal> {
+        self.asks.first().map(|l| l.price)
+    }
+
+    /// Get spread
+    pub fn spread(&self) -> Option<Decimal> {
+        Some(self.best_ask()? - self.best_bid()?)
+    }
+
+    /// Get midpoint
+    pub fn mid(&self) -> Option<Decimal> {
+        Some((self.best_bid()? + self.best_ask()?) / Decimal::TWO)
+    }
+
+    /// Mid weighted towards the thinner side of the touch
+    ///
+    /// `(bid * ask_size + ask * bid_size) / (bid_size + ask_size)`: a heavy
+    /// bid pulls the fair price up towards the ask, since the ask is the
+    /// side more likely to be taken out next.
+    pub fn microprice(&self) -> Option<Decimal> {
+        let bid = self.bids.first()?;
+        let ask = self.asks.first()?;
+        let total = bid.size + ask.size;
+        if total.is_zero() {
+            return self.mid();
+        }
+        Some((bid.price * ask.size + ask.price * bid.size) / total)
+    }
+
+    /// USD depth (price × size) on both sides within `bps` of the mid
+    pub fn depth_within(&self, bps: u32) -> Decimal {
+        let Some(mid) = self.mid() else {
+            return Decimal::ZERO;
+        };
+        let max_distance = mid * Decimal::from(bps) / Decimal::from(10_000);
+        self.bids
+            .iter()
+            .chain(self.asks.iter())
+            .filter(|l| (l.price - mid).abs() <= max_distance)
+            .map(|l| l.size * l.price)
+            .sum()
+    }
+
+    /// Average price of taking `size` shares from the book
+    ///
+    /// Buys walk the asks and sells the bids, best level first. `None` if
+    /// that side cannot fill the whole size.
+    pub fn vwap_to_fill(&self, size: Decimal, side: Side) -> Option<Decimal> {
+        if size <= Decimal::ZERO {
+            return None;
+        }
+        let levels = match side {
+            Side::Buy => &self.asks,
+            Side::Sell => &self.bids,
+        };
+        let (filled, notional) = walk_levels(levels, size);
+        (filled == size).then(|| notional / filled)
+    }
+}
+
+/// Take up to `size` from `levels` in order
+///
+/// Returns the filled size and its notional value.
+pub(crate) fn walk_levels(levels: &[OrderBookLevel], size: Decimal) -> (Decimal, Decimal) {
+    let mut remaining = size;
+    let mut notional = Decimal::ZERO;
+    for level in levels {
+        if remaining <= Decimal::ZERO {
+            break;
+        }
+        let take = remaining.min(level.size);
+        notional += take * level.price;
+        remaining -= take;
+    }
+    (size - remaining, notional)
 }
 
 impl Market {
+    /// Find an outcome by name, ignoring case
+    pub fn outcome(&self, name: &str) -> Option<&Outcome> {
+        self.outcomes.iter().find(|o| o.outcome.eq_ignore_ascii_case(name))
+    }
+
+    /// Find the outcome for a logical side (e.g. "yes") using `matcher`
+    pub fn find_outcome(&self, logical_side: &str, matcher: &OutcomeMatcher) -> Option<&Outcome> {
+        self.outcomes.iter().find(|o| matcher.matches(&o.outcome, logical_side))
+    }
+
+    /// Price of the named outcome
+    pub fn outcome_price(&self, name: &str) -> Option<Decimal> {
+        self.outcome(name).map(|o| o.price)
+    }
+
     /// Get the Yes outcome price
     pub fn yes_price(&self) -> Option<Decimal> {
-        self.outcomes
-            .iter()
-            .find(|o| o.outcome.to_lowercase() == "yes")
-            .map(|o| o.price)
+        self.outcome_price("yes")
     }
 
     /// Get the No outcome price  
     pub fn no_price(&self) -> Option<Decimal> {
-        self.outcomes
-            .iter()
-            .find(|o| o.outcome.to_lowercase() == "no")
-            .map(|o| o.price)
+        self.outcome_price("no")
+    }
+
+    /// Each outcome with its price normalized so the set sums to 1
+    ///
+    /// Prices on a book rarely sum to exactly 1 (spread, vig, stale
+    /// quotes). Empty if the prices sum to zero.
+    pub fn implied_probabilities(&self) -> Vec<(&Outcome, Decimal)> {
+        let total: Decimal = self.outcomes.iter().map(|o| o.price).sum();
+        if total <= Decimal::ZERO {
+            return Vec::new();
+        }
+        self.outcomes.iter().map(|o| (o, o.price / total)).collect()
+    }
+
+    /// Whether the outcome prices sum to within `tolerance` of 1
+    ///
+    /// Stale quotes from Gamma can drift apart (e.g. Yes 0.60, No 0.50),
+    /// and pricing a trade off either side would overstate the edge.
+    pub fn is_price_consistent(&self, tolerance: Decimal) -> bool {
+        if self.outcomes.is_empty() {
+            return false;
+        }
+        let total: Decimal = self.outcomes.iter().map(|o| o.price).sum();
+        (total - Decimal::ONE).abs() <= tolerance
+    }
+
+    /// Rescale outcome prices in place so they sum to 1
+    ///
+    /// Left unchanged if the prices sum to zero.
+    pub fn normalize_prices(&mut self) {
+        let total: Decimal = self.outcomes.iter().map(|o| o.price).sum();
+        if total <= Decimal::ZERO {
+            return;
+        }
+        for outcome in &mut self.outcomes {
+            outcome.price /= total;
+        }
     }
 
     /// Check if there's an arbitrage opportunity (Yes + No < 1)
@@ -135,6 +404,72 @@ impl Market {
     }
 }
 
+impl Position {
+    /// Profit or loss at `current_price`
+    pub fn unrealized_pnl(&self) -> Decimal {
+        match self.side {
+            Side::Buy => (self.current_price - self.avg_entry_price) * self.size,
+            Side::Sell => (self.avg_entry_price - self.current_price) * self.size,
+        }
+    }
+
+    /// Value of the position at `current_price`
+    pub fn notional(&self) -> Decimal {
+        self.size * self.current_price
+    }
+
+    /// Update the position for a fill and return the P&L it realized
+    ///
+    /// A fill on the position's side adds to it at a size-weighted average
+    /// entry, with the fee folded into the entry price. An opposite fill
+    /// closes up to `size` and realizes the difference to the entry, net of
+    /// its share of the fee; anything left over opens a position on the
+    /// other side. A flat position takes the side of the fill.
+    pub fn apply_fill(&mut self, side: Side, size: Decimal, price: Decimal, fee: Decimal) -> Decimal {
+        if size <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+        self.current_price = price;
+        if self.size.is_zero() {
+            self.side = side;
+        }
+
+        let closed = if side == self.side { Decimal::ZERO } else { size.min(self.size) };
+        let opened = size - closed;
+        let closing_fee = fee * closed / size;
+        let opening_fee = fee - closing_fee;
+
+        let mut realized = Decimal::ZERO;
+        if closed > Decimal::ZERO {
+            let per_share = match self.side {
+                Side::Buy => price - self.avg_entry_price,
+                Side::Sell => self.avg_entry_price - price,
+            };
+            realized = per_share * closed - closing_fee;
+            self.size -= closed;
+            if self.size.is_zero() {
+                self.avg_entry_price = Decimal::ZERO;
+            }
+        }
+
+        if opened > Decimal::ZERO {
+            if self.size.is_zero() {
+                self.side = side;
+            }
+            // Fees raise the cost of a long and lower the proceeds of a short
+            let basis = match side {
+                Side::Buy => price * opened + opening_fee,
+                Side::Sell => price * opened - opening_fee,
+            };
+            self.avg_entry_price = (self.avg_entry_price * self.size + basis) / (self.size + opened);
+            self.size += opened;
+        }
+
+        self.unrealized_pnl = self.unrealized_pnl();
+        realized
+    }
+}
+
 impl Signal {
     /// Check if signal is strong enough to trade
     pub fn is_tradeable(&self, min_edge: Decimal, min_confidence: Decimal) -> bool {
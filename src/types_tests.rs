@@ -100,6 +100,7 @@ mod tests {
             price: dec!(0.50),
             size: dec!(100),
             order_type: OrderType::GTC,
+            client_order_id: None,
         };
         assert_eq!(order.token_id, "token123");
         assert_eq!(order.side, Side::Buy);
@@ -115,6 +116,7 @@ mod tests {
             price: dec!(0.50),
             size: dec!(100),
             order_type: OrderType::GTC,
+            client_order_id: None,
         };
         let json = serde_json::to_string(&order).unwrap();
         assert!(json.contains("\"token_id\":\"token123\""));
@@ -135,6 +137,81 @@ mod tests {
         assert_eq!(position.unrealized_pnl, dec!(10));
     }
 
+    fn flat_position() -> Position {
+        Position {
+            token_id: "token123".to_string(),
+            market_id: "market456".to_string(),
+            side: Side::Buy,
+            size: Decimal::ZERO,
+            avg_entry_price: Decimal::ZERO,
+            current_price: Decimal::ZERO,
+            unrealized_pnl: Decimal::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_position_pnl_and_notional() {
+        let mut position = flat_position();
+        position.apply_fill(Side::Buy, dec!(100), dec!(0.45), Decimal::ZERO);
+        position.current_price = dec!(0.55);
+        assert_eq!(position.unrealized_pnl(), dec!(10));
+        assert_eq!(position.notional(), dec!(55));
+
+        position.side = Side::Sell;
+        assert_eq!(position.unrealized_pnl(), dec!(-10));
+    }
+
+    #[test]
+    fn test_apply_fill_adds_to_position() {
+        let mut position = flat_position();
+        assert_eq!(position.apply_fill(Side::Buy, dec!(100), dec!(0.40), dec!(1)), Decimal::ZERO);
+        assert_eq!(position.avg_entry_price, dec!(0.41));
+
+        assert_eq!(position.apply_fill(Side::Buy, dec!(100), dec!(0.61), dec!(1)), Decimal::ZERO);
+        assert_eq!(position.size, dec!(200));
+        assert_eq!(position.avg_entry_price, dec!(0.515));
+        assert_eq!(position.current_price, dec!(0.61));
+        assert_eq!(position.unrealized_pnl, dec!(19));
+    }
+
+    #[test]
+    fn test_apply_fill_partial_and_full_close() {
+        let mut position = flat_position();
+        position.apply_fill(Side::Buy, dec!(100), dec!(0.50), Decimal::ZERO);
+
+        let realized = position.apply_fill(Side::Sell, dec!(40), dec!(0.60), dec!(0.40));
+        assert_eq!(realized, dec!(3.60));
+        assert_eq!(position.size, dec!(60));
+        assert_eq!(position.side, Side::Buy);
+        assert_eq!(position.avg_entry_price, dec!(0.50));
+
+        let realized = position.apply_fill(Side::Sell, dec!(60), dec!(0.45), Decimal::ZERO);
+        assert_eq!(realized, dec!(-3));
+        assert!(position.size.is_zero());
+        assert_eq!(position.unrealized_pnl, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_apply_fill_flips_position() {
+        let mut position = flat_position();
+        position.apply_fill(Side::Buy, dec!(100), dec!(0.50), Decimal::ZERO);
+
+        // Sells 100 to close at a 10c gain and 50 more to open a short;
+        // the fee is split 2:1 between the two legs
+        let realized = position.apply_fill(Side::Sell, dec!(150), dec!(0.60), dec!(1.50));
+        assert_eq!(realized, dec!(9));
+        assert_eq!(position.side, Side::Sell);
+        assert_eq!(position.size, dec!(50));
+        assert_eq!(position.avg_entry_price, dec!(0.59));
+
+        // The short profits as the price falls
+        position.current_price = dec!(0.49);
+        assert_eq!(position.unrealized_pnl(), dec!(5));
+        let realized = position.apply_fill(Side::Buy, dec!(50), dec!(0.49), Decimal::ZERO);
+        assert_eq!(realized, dec!(5));
+        assert!(position.size.is_zero());
+    }
+
     #[test]
     fn test_trade_creation() {
         let trade = Trade {
@@ -147,6 +224,8 @@ mod tests {
             size: dec!(100),
             fee: dec!(0.50),
             timestamp: Utc::now(),
+            client_order_id: None,
+            correlation_id: None,
         };
         assert_eq!(trade.fee, dec!(0.50));
     }
@@ -170,6 +249,7 @@ mod tests {
             filled_size: dec!(100),
             remaining_size: dec!(0),
             avg_price: Some(dec!(0.50)),
+            fee: Decimal::ZERO,
         };
         assert_eq!(status.status, "FILLED");
         assert_eq!(status.filled_size, dec!(100));
@@ -187,13 +267,74 @@ mod tests {
             outcomes: vec![],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         };
         assert_eq!(market.yes_price(), None);
         assert_eq!(market.no_price(), None);
         assert_eq!(market.arbitrage_opportunity(), None);
     }
 
+    #[test]
+    fn test_multi_outcome_prices() {
+        // Three candidates quoted with 5% overround
+        let market = create_election_market(dec!(0.50), dec!(0.35), dec!(0.20));
+        assert_eq!(market.outcome_price("bob"), Some(dec!(0.35)));
+        assert_eq!(market.outcome_price("Dave"), None);
+        assert_eq!(market.yes_price(), None);
+
+        let implied = market.implied_probabilities();
+        assert_eq!(implied.len(), 3);
+        let total: Decimal = implied.iter().map(|(_, p)| *p).sum();
+        assert!((total - Decimal::ONE).abs() < dec!(0.0000001));
+        assert_eq!(implied[0].0.outcome, "Alice");
+        assert!((implied[0].1 - dec!(0.50) / dec!(1.05)).abs() < dec!(0.0000001));
+    }
+
+    #[test]
+    fn test_implied_probabilities_without_prices() {
+        let market = create_election_market(dec!(0), dec!(0), dec!(0));
+        assert!(market.implied_probabilities().is_empty());
+    }
+
+    #[test]
+    fn test_price_consistency_and_normalization() {
+        let consistent = create_test_market(dec!(0.52), dec!(0.49));
+        assert!(consistent.is_price_consistent(dec!(0.05)));
+
+        // Stale quotes: Yes + No = 1.1
+        let mut stale = create_test_market(dec!(0.60), dec!(0.50));
+        assert!(!stale.is_price_consistent(dec!(0.05)));
+        assert!(stale.is_price_consistent(dec!(0.10)));
+
+        stale.normalize_prices();
+        assert_eq!(stale.yes_price(), Some(dec!(0.60) / dec!(1.1)));
+        assert_eq!(stale.no_price(), Some(dec!(0.50) / dec!(1.1)));
+        let total: Decimal = stale.outcomes.iter().map(|o| o.price).sum();
+        assert!((total - Decimal::ONE).abs() < dec!(0.0000001));
+        assert!(stale.is_price_consistent(dec!(0.0000001)));
+
+        let mut unpriced = create_test_market(dec!(0), dec!(0));
+        unpriced.normalize_prices();
+        assert_eq!(unpriced.yes_price(), Some(dec!(0)));
+        assert!(!unpriced.is_price_consistent(dec!(0.05)));
+    }
+
     // Helper functions
+    fn create_election_market(alice: Decimal, bob: Decimal, carol: Decimal) -> Market {
+        let outcome = |name: &str, price| Outcome {
+            token_id: format!("{}-token", name.to_lowercase()),
+            outcome: name.to_string(),
+            price,
+        };
+        Market {
+            outcomes: vec![outcome("Alice", alice), outcome("Bob", bob), outcome("Carol", carol)],
+            ..create_test_market(dec!(0), dec!(0))
+        }
+    }
+
     fn create_test_market(yes_price: Decimal, no_price: Decimal) -> Market {
         Market {
             id: "test-market".to_string(),
@@ -216,6 +357,10 @@ mod tests {
             ],
             active: true,
             closed: false,
+            tags: Vec::new(),
+            category: None,
+            tick_size: None,
+            min_size: None,
         }
     }
 
@@ -227,9 +372,61 @@ mod tests {
             model_probability: dec!(0.70),
             market_probability: dec!(0.60),
             edge,
+            net_edge: edge,
             confidence,
             suggested_size: dec!(100),
             timestamp: Utc::now(),
+            max_staleness: None,
+        }
+    }
+
+    #[test]
+    fn test_find_outcome_on_up_down_market() {
+        let mut market = create_test_market(dec!(0.55), dec!(0.45));
+        market.outcomes[0].outcome = "Up".to_string();
+        market.outcomes[1].outcome = "Down".to_string();
+
+        let default = OutcomeMatcher::default();
+        assert_eq!(market.find_outcome("yes", &default).unwrap().token_id, "yes-token");
+        assert_eq!(market.find_outcome("No", &default).unwrap().token_id, "no-token");
+        assert!(market.find_outcome("yes", &OutcomeMatcher::CaseInsensitive).is_none());
+        assert_eq!(market.yes_price(), None);
+
+        // Plain Yes/No still matches, exactly only when the case agrees
+        let market = create_test_market(dec!(0.55), dec!(0.45));
+        assert_eq!(market.find_outcome("yes", &default).unwrap().token_id, "yes-token");
+        assert!(market.find_outcome("yes", &OutcomeMatcher::Exact).is_none());
+        assert!(market.find_outcome("Yes", &OutcomeMatcher::Exact).is_some());
+    }
+
+    #[test]
+    fn test_synonyms_map_candidate_names() {
+        let market = create_election_market(dec!(0.50), dec!(0.35), dec!(0.20));
+        let matcher = OutcomeMatcher::Synonyms {
+            synonyms: std::collections::HashMap::from([("ALICE".to_string(), "yes".to_string())]),
+        };
+        assert_eq!(market.find_outcome("yes", &matcher).unwrap().outcome, "Alice");
+        assert!(market.find_outcome("no", &matcher).is_none());
+    }
+
+    #[test]
+    fn test_checked_price_flags_and_clamps_out_of_range() {
+        let high = CheckedPrice::new(dec!(1.2));
+        assert!(high.is_anomaly());
+        assert_eq!(high.price, Decimal::ONE);
+        assert_eq!(high.raw, dec!(1.2));
+
+        let low = CheckedPrice::new(dec!(-0.1));
+        assert!(low.is_anomaly());
+        assert_eq!(low.price, Decimal::ZERO);
+
+        for edge in [Decimal::ZERO, dec!(0.55), Decimal::ONE] {
+            let price = CheckedPrice::new(edge);
+            assert!(!price.is_anomaly());
+            assert_eq!(price.price, edge);
+            assert!(is_valid_price(edge));
         }
+        assert!(!is_valid_price(dec!(1.2)));
+        assert!(!is_valid_price(dec!(-0.1)));
     }
 }
@@ -29,6 +29,41 @@ pub fn sqrt_decimal(x: Decimal) -> Decimal {
     guess
 }
 
+/// Parse a human duration like `90s`, `30m`, `2h` or `1d`
+///
+/// A bare number is taken as seconds.
+pub fn parse_duration(s: &str) -> std::result::Result<std::time::Duration, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+    let value: u64 = value
+        .parse()
+        .map_err(|_| format!("invalid duration '{}'", s))?;
+    let multiplier = match unit {
+        "" | "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        _ => return Err(format!("unknown duration unit '{}' (use s, m, h or d)", unit)),
+    };
+    let secs = value
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("duration '{}' is too long", s))?;
+    Ok(std::time::Duration::from_secs(secs))
+}
+
+/// Clip `s` to its first `max_chars` characters, adding `...` when
+/// anything was cut
+///
+/// Counts chars rather than bytes, so multi-byte text never splits
+/// mid-character.
+pub fn truncate_chars(s: &str, max_chars: usize) -> String {
+    match s.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}...", &s[..end]),
+        None => s.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,4 +103,39 @@ mod tests {
         let result = sqrt_decimal(dec!(10000));
         assert!((result - dec!(100)).abs() < dec!(0.1));
     }
+
+    #[test]
+    fn test_parse_duration() {
+        use std::time::Duration;
+        assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("45s").unwrap(), Duration::from_secs(45));
+        assert_eq!(parse_duration("30m").unwrap(), Duration::from_secs(1800));
+        assert_eq!(parse_duration("2h").unwrap(), Duration::from_secs(7200));
+        assert_eq!(parse_duration("1d").unwrap(), Duration::from_secs(86400));
+        assert!(parse_duration("m").is_err());
+        assert!(parse_duration("10x").is_err());
+        assert!(parse_duration("999999999999999999d").is_err());
+    }
+
+    #[test]
+    fn test_truncate_chars_multibyte() {
+        // Byte 14 falls inside the three-byte €
+        let q = "Will ETH hit €5,000 — 🚀🚀 by Friday?";
+        let clipped = truncate_chars(q, 14);
+        assert_eq!(clipped, "Will ETH hit €...");
+        assert_eq!(clipped.chars().count(), 14 + 3);
+
+        let clipped = truncate_chars("🚀🚀🚀🚀", 2);
+        assert_eq!(clipped, "🚀🚀...");
+
+        let clipped = truncate_chars("Élection présidentielle française", 10);
+        assert_eq!(clipped, "Élection p...");
+    }
+
+    #[test]
+    fn test_truncate_chars_short_input_unchanged() {
+        assert_eq!(truncate_chars("Café", 4), "Café");
+        assert_eq!(truncate_chars("", 5), "");
+        assert_eq!(truncate_chars("abc", 0), "...");
+    }
 }